@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+
+/// 采样间隔
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+/// 单个进程 CPU 占用超过此值（单核百分比）视为正在干扰
+const ACTIVE_CPU_PERCENT: f64 = 10.0;
+/// 单个采样间隔内磁盘读取超过此值 (MB) 视为正在干扰
+const ACTIVE_DISK_MB: f64 = 5.0;
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InterferenceKind {
+    /// Windows Defender 扫描
+    DefenderScan,
+    /// Windows Search 索引
+    SearchIndexer,
+    /// .NET 程序集优化 (NGEN)
+    DotNetOptimization,
+    /// Windows 更新组件安装
+    WindowsUpdate,
+    /// 兼容性遥测、磁盘优化等计划维护任务
+    Maintenance,
+}
+
+impl InterferenceKind {
+    fn label(self) -> &'static str {
+        match self {
+            InterferenceKind::DefenderScan => "Windows Defender 扫描",
+            InterferenceKind::SearchIndexer => "Windows 搜索索引",
+            InterferenceKind::DotNetOptimization => ".NET 程序集优化",
+            InterferenceKind::WindowsUpdate => "Windows 更新",
+            InterferenceKind::Maintenance => "系统计划维护",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackgroundInterference {
+    pub kind: InterferenceKind,
+    pub process_name: String,
+    /// 首次/最后一次观察到活动的时间（距监测开始的秒数）
+    pub first_at_secs: f64,
+    pub last_at_secs: f64,
+    /// 处于活动状态的累计时长 (秒)
+    pub active_secs: f64,
+    /// CPU 占用峰值（占全部逻辑核心的百分比）
+    pub peak_cpu_percent: f64,
+    /// 活动期间的磁盘读取量 (MB)
+    pub disk_read_mb: f64,
+}
+
+/// 进程名（小写）→ 干扰类型
+fn classify(name: &str) -> Option<InterferenceKind> {
+    use InterferenceKind::*;
+    Some(match name {
+        "msmpeng.exe" | "mpcmdrun.exe" | "nissrv.exe" | "mpdefendercoreservice.exe" => DefenderScan,
+        "searchindexer.exe" | "searchprotocolhost.exe" | "searchfilterhost.exe" => SearchIndexer,
+        "mscorsvw.exe" | "ngen.exe" | "ngentask.exe" => DotNetOptimization,
+        "tiworker.exe" | "trustedinstaller.exe" | "wuauclt.exe" | "usoclient.exe" => WindowsUpdate,
+        "compattelrunner.exe" | "defrag.exe" | "dismhost.exe" | "mrt.exe" => Maintenance,
+        _ => return None,
+    })
+}
+
+// ==================== 测试期间采样 ====================
+
+/// 在 FPS 监测期间记录 Defender、索引、.NET 优化等后台服务的活动
+pub struct InterferenceSampler {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<Vec<BackgroundInterference>>>,
+}
+
+impl InterferenceSampler {
+    pub fn start() -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = stop.clone();
+
+        let handle = std::thread::spawn(move || {
+            let refresh = ProcessRefreshKind::nothing().with_cpu().with_disk_usage();
+            let mut sys = System::new();
+            sys.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh);
+            let cores = std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1) as f64;
+
+            let started = Instant::now();
+            let mut found: HashMap<String, BackgroundInterference> = HashMap::new();
+            let mut last = Instant::now();
+            while !stop_flag.load(Ordering::Relaxed) {
+                if last.elapsed() >= SAMPLE_INTERVAL {
+                    let interval = last.elapsed().as_secs_f64();
+                    sys.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh);
+                    let at = (started.elapsed().as_secs_f64() * 10.0).round() / 10.0;
+                    for process in sys.processes().values() {
+                        let name = process.name().to_string_lossy().to_lowercase();
+                        let Some(kind) = classify(&name) else {
+                            continue;
+                        };
+                        let cpu = process.cpu_usage() as f64;
+                        let read_mb = process.disk_usage().read_bytes as f64 / (1024.0 * 1024.0);
+                        if cpu < ACTIVE_CPU_PERCENT && read_mb < ACTIVE_DISK_MB {
+                            continue;
+                        }
+                        let entry = found.entry(name.clone()).or_insert(BackgroundInterference {
+                            kind,
+                            process_name: process.name().to_string_lossy().to_string(),
+                            first_at_secs: at,
+                            last_at_secs: at,
+                            active_secs: 0.0,
+                            peak_cpu_percent: 0.0,
+                            disk_read_mb: 0.0,
+                        });
+                        entry.last_at_secs = at;
+                        entry.active_secs += interval;
+                        entry.peak_cpu_percent = entry.peak_cpu_percent.max(cpu / cores);
+                        entry.disk_read_mb += read_mb;
+                    }
+                    last = Instant::now();
+                }
+                std::thread::sleep(Duration::from_millis(200));
+            }
+
+            let mut items: Vec<BackgroundInterference> = found.into_values().collect();
+            for item in &mut items {
+                item.active_secs = item.active_secs.round();
+                item.peak_cpu_percent = (item.peak_cpu_percent * 10.0).round() / 10.0;
+                item.disk_read_mb = item.disk_read_mb.round();
+            }
+            items.sort_by(|a, b| a.first_at_secs.total_cmp(&b.first_at_secs));
+            items
+        });
+
+        InterferenceSampler {
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    /// 停止采样，返回测试期间活动过的后台服务
+    pub fn finish(mut self) -> Vec<BackgroundInterference> {
+        self.stop.store(true, Ordering::Relaxed);
+        self.handle
+            .take()
+            .and_then(|h| h.join().ok())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for InterferenceSampler {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+// ==================== 建议 ====================
+
+/// 按类型给出的处理建议
+pub fn advice(kind: InterferenceKind) -> &'static str {
+    match kind {
+        InterferenceKind::DefenderScan => {
+            "将游戏目录加入 Defender 排除项，或在测试前手动完成一次扫描"
+        }
+        InterferenceKind::SearchIndexer => "在「索引选项」中排除游戏目录，或等待索引完成后再测试",
+        InterferenceKind::DotNetOptimization => {
+            "以管理员身份运行 ngen executequeueditems 提前完成优化队列"
+        }
+        InterferenceKind::WindowsUpdate => "等待 Windows 更新安装完成并重启后再测试",
+        InterferenceKind::Maintenance => "避开系统自动维护时段（默认凌晨）或在「安全和维护」中调整",
+    }
+}
+
+/// 干扰摘要 (e.g., "Windows Defender 扫描 (MsMpEng.exe, 42 秒)")
+pub fn describe(item: &BackgroundInterference) -> String {
+    format!(
+        "{} ({}, {:.0} 秒)",
+        item.kind.label(),
+        item.process_name,
+        item.active_secs
+    )
+}
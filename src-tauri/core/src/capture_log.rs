@@ -0,0 +1,108 @@
+use crate::presentmon::FrameRow;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// 原始采集日志的 CSV 表头（gzip 压缩）
+const HEADER: &str = "TimestampMs,FrameTime,CPUBusy,GPUBusy,PresentMode";
+/// 压缩流落盘间隔，应用崩溃时最多丢失这段时间的帧
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+// ==================== 数据结构 ====================
+
+/// 原始采集日志中的一帧
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureLogRow {
+    /// 帧时间戳 (ms)，采集来源不提供时为空
+    pub timestamp_ms: Option<f64>,
+    pub frametime: f64,
+    pub cpu_busy: f64,
+    pub gpu_busy: f64,
+    /// PresentMon 报告的呈现模式，如 "Hardware: Independent Flip"
+    pub present_mode: String,
+}
+
+// ==================== 写入 ====================
+
+/// 监测期间逐帧追加的压缩 CSV 日志
+pub struct CaptureLogWriter {
+    encoder: GzEncoder<BufWriter<File>>,
+    rows: u64,
+    last_flush: Instant,
+}
+
+impl CaptureLogWriter {
+    pub fn create(path: &Path) -> Result<Self, String> {
+        let file = File::create(path).map_err(|e| format!("创建原始采集日志失败: {}", e))?;
+        let mut encoder = GzEncoder::new(BufWriter::new(file), Compression::fast());
+        writeln!(encoder, "{}", HEADER).map_err(|e| format!("写入原始采集日志失败: {}", e))?;
+        Ok(CaptureLogWriter {
+            encoder,
+            rows: 0,
+            last_flush: Instant::now(),
+        })
+    }
+
+    /// 追加一帧，`timestamp_ms` 为换算后的帧时间戳；每 FLUSH_INTERVAL 落盘一次
+    pub fn push(&mut self, timestamp_ms: Option<f64>, row: &FrameRow) {
+        let timestamp = timestamp_ms
+            .map(|t| format!("{:.4}", t))
+            .unwrap_or_default();
+        let _ = writeln!(
+            self.encoder,
+            "{},{:.4},{:.4},{:.4},{}",
+            timestamp,
+            row.frametime,
+            row.cpu_busy,
+            row.gpu_busy,
+            row.present_mode.replace(',', " ")
+        );
+        self.rows += 1;
+        if self.last_flush.elapsed() >= FLUSH_INTERVAL {
+            let _ = self.encoder.flush();
+            self.last_flush = Instant::now();
+        }
+    }
+
+    /// 结束压缩流，返回写入的帧数
+    pub fn finish(self) -> Result<u64, String> {
+        self.encoder
+            .finish()
+            .and_then(|mut writer| writer.flush())
+            .map_err(|e| format!("保存原始采集日志失败: {}", e))?;
+        Ok(self.rows)
+    }
+}
+
+// ==================== 读取 ====================
+
+/// 读取原始采集日志；压缩流不完整（如应用崩溃）时返回已落盘的帧
+pub fn read(path: &Path) -> Result<Vec<CaptureLogRow>, String> {
+    let file = File::open(path).map_err(|e| format!("读取原始采集日志失败: {}", e))?;
+    let mut lines = BufReader::new(GzDecoder::new(file)).lines();
+    match lines.next() {
+        Some(Ok(header)) if header.trim() == HEADER => {}
+        _ => return Err("不是有效的原始采集日志".to_string()),
+    }
+    Ok(lines
+        .map_while(Result::ok)
+        .filter_map(|line| parse_row(&line))
+        .collect())
+}
+
+fn parse_row(line: &str) -> Option<CaptureLogRow> {
+    let mut fields = line.splitn(5, ',');
+    let timestamp = fields.next()?;
+    Some(CaptureLogRow {
+        timestamp_ms: timestamp.parse().ok(),
+        frametime: fields.next()?.parse().ok()?,
+        cpu_busy: fields.next()?.parse().ok()?,
+        gpu_busy: fields.next()?.parse().ok()?,
+        present_mode: fields.next()?.to_string(),
+    })
+}
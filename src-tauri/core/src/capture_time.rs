@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// 相邻两帧时间戳间隔超过该值视为采集中断 (ms)
+const MAX_FRAME_GAP_MS: f64 = 1000.0;
+/// 两帧之间系统挂起超过该值视为经历了睡眠/休眠 (ms)
+const MIN_SUSPEND_MS: u64 = 1000;
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PauseReason {
+    /// 系统睡眠/休眠
+    Suspend,
+    /// 游戏停止出帧（最小化、加载、暂停菜单等）
+    NoFrames,
+    /// 合并会话时，原来分段录制之间的间隔
+    Split,
+    /// 用户手动暂停（加载画面、切出游戏等）
+    User,
+}
+
+/// 不计入有效采集时长的一段中断
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapturePause {
+    /// 中断开始时的有效采集时长 (秒)
+    pub at_secs: f64,
+    /// 中断时长 (秒)
+    pub duration_secs: f64,
+    pub reason: PauseReason,
+}
+
+// ==================== 系统时钟 ====================
+
+/// (含睡眠的系统时间, 不含睡眠的运行时间)，单位 ms
+#[cfg(target_os = "windows")]
+fn clock_pair() -> (u64, u64) {
+    use windows::Win32::System::SystemInformation::GetTickCount64;
+    use windows::Win32::System::WindowsProgramming::QueryUnbiasedInterruptTime;
+
+    let mut unbiased = 0u64;
+    unsafe {
+        let wall = GetTickCount64();
+        // 单位 100ns
+        let _ = QueryUnbiasedInterruptTime(&mut unbiased);
+        (wall, unbiased / 10_000)
+    }
+}
+
+/// 非 Windows 平台无法区分睡眠时间，只按帧时间戳判断中断
+#[cfg(not(target_os = "windows"))]
+fn clock_pair() -> (u64, u64) {
+    use std::sync::OnceLock;
+
+    static START: OnceLock<Instant> = OnceLock::new();
+    let ms = START.get_or_init(Instant::now).elapsed().as_millis() as u64;
+    (ms, ms)
+}
+
+// ==================== 有效采集时长 ====================
+
+/// 根据帧时间戳累计有效采集时长，跳过系统睡眠与长时间无帧的区间
+pub struct ActiveClock {
+    active_ms: f64,
+    last_timestamp: Option<f64>,
+    last_clock: (u64, u64),
+    pauses: Vec<CapturePause>,
+}
+
+impl Default for ActiveClock {
+    fn default() -> Self {
+        ActiveClock {
+            active_ms: 0.0,
+            last_timestamp: None,
+            last_clock: clock_pair(),
+            pauses: Vec::new(),
+        }
+    }
+}
+
+impl ActiveClock {
+    /// 每帧调用；`timestamp_ms` 为 PresentMon 记录的帧时间戳，旧版本没有时间戳列时退回累加帧时间
+    pub fn on_frame(&mut self, timestamp_ms: Option<f64>, frametime_ms: f64) {
+        let clock = clock_pair();
+        let wall_ms = clock.0.saturating_sub(self.last_clock.0);
+        let awake_ms = clock.1.saturating_sub(self.last_clock.1);
+        let suspended_ms = wall_ms.saturating_sub(awake_ms);
+        self.last_clock = clock;
+
+        // 多个交换链的帧交错输出时时间戳可能略有回退，按 0 计
+        let delta = match (timestamp_ms, self.last_timestamp) {
+            (Some(now), Some(last)) => (now - last).max(0.0),
+            (Some(_), None) => 0.0,
+            _ => frametime_ms,
+        };
+        if let Some(now) = timestamp_ms {
+            self.last_timestamp = Some(self.last_timestamp.map_or(now, |last| last.max(now)));
+        }
+
+        if suspended_ms >= MIN_SUSPEND_MS {
+            log::info!(
+                "采集期间系统睡眠 {:.1}s，不计入有效时长",
+                suspended_ms as f64 / 1000.0
+            );
+            self.push_pause(wall_ms as f64, PauseReason::Suspend);
+        } else if delta > MAX_FRAME_GAP_MS {
+            self.push_pause(delta.max(wall_ms as f64), PauseReason::NoFrames);
+        } else {
+            self.active_ms += delta;
+        }
+    }
+
+    fn push_pause(&mut self, duration_ms: f64, reason: PauseReason) {
+        self.pauses.push(CapturePause {
+            at_secs: (self.active_secs() * 10.0).round() / 10.0,
+            duration_secs: (duration_ms / 1000.0 * 10.0).round() / 10.0,
+            reason,
+        });
+    }
+
+    /// 手动暂停结束：记录中断，期间的帧已被丢弃，下一帧不与暂停前的帧计算间隔
+    pub fn resume_after(&mut self, paused: Duration) {
+        self.push_pause(paused.as_secs_f64() * 1000.0, PauseReason::User);
+        self.last_timestamp = None;
+        self.last_clock = clock_pair();
+    }
+
+    /// PresentMon 重新启动后时间戳从头开始
+    pub fn restart_timeline(&mut self) {
+        self.last_timestamp = None;
+    }
+
+    /// 有效采集时长 (秒)
+    pub fn active_secs(&self) -> f64 {
+        self.active_ms / 1000.0
+    }
+
+    pub fn pauses(&self) -> &[CapturePause] {
+        &self.pauses
+    }
+}
+
+// ==================== 帧时间线 ====================
+
+/// (当前 QPC 计数, 每秒计数)
+#[cfg(target_os = "windows")]
+fn qpc_now() -> (i64, f64) {
+    (crate::etw::qpc_now(), crate::etw::qpc_frequency() as f64)
+}
+
+/// 非 Windows 平台没有 QPC，以进程内单调时钟（纳秒）代替
+#[cfg(not(target_os = "windows"))]
+fn qpc_now() -> (i64, f64) {
+    use std::sync::OnceLock;
+
+    static START: OnceLock<Instant> = OnceLock::new();
+    let nanos = START.get_or_init(Instant::now).elapsed().as_nanos() as i64;
+    (nanos, 1_000_000_000.0)
+}
+
+/// 以 PresentMon 记录的 QPC 时间戳作为帧的时间线
+/// stdout 有缓冲，按行到达时刻对齐遥测采样会偏差数百毫秒；
+/// Windows 上 Instant 同样基于 QPC，换算后可直接与采样线程记录的 Instant 比较
+pub struct QpcTimeline {
+    origin_qpc: i64,
+    origin: Instant,
+    frequency: f64,
+}
+
+impl Default for QpcTimeline {
+    fn default() -> Self {
+        let origin = Instant::now();
+        let (origin_qpc, frequency) = qpc_now();
+        QpcTimeline {
+            origin_qpc,
+            origin,
+            frequency,
+        }
+    }
+}
+
+impl QpcTimeline {
+    /// QPC 时间戳对应的 Instant
+    pub fn instant_at(&self, qpc: i64) -> Instant {
+        let secs = (qpc - self.origin_qpc) as f64 / self.frequency;
+        let offset = Duration::from_secs_f64(secs.abs());
+        if secs >= 0.0 {
+            self.origin + offset
+        } else {
+            self.origin.checked_sub(offset).unwrap_or(self.origin)
+        }
+    }
+
+    /// QPC 时间戳换算为毫秒（相对时间线起点），用于没有毫秒时间戳列的输出
+    pub fn millis(&self, qpc: i64) -> f64 {
+        (qpc - self.origin_qpc) as f64 / self.frequency * 1000.0
+    }
+}
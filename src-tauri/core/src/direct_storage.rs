@@ -0,0 +1,154 @@
+use crate::drives::{DriveInfo, DriveKind};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use sysinfo::System;
+
+/// DirectStorage 最低系统要求: Windows 10 1909 (build 18363)
+const MIN_BUILD_DIRECT_STORAGE: u32 = 18363;
+/// BypassIO 与优化存储栈: Windows 11 (build 22000)
+const MIN_BUILD_BYPASS_IO: u32 = 22000;
+
+/// DirectStorage 运行时 DLL
+const RUNTIME_DLLS: &[&str] = &["dstorage.dll", "dstoragecore.dll"];
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DirectStorageSupport {
+    /// Windows 内部版本号
+    pub os_build: u32,
+    /// 系统是否支持 DirectStorage
+    pub os_supported: bool,
+    /// 系统是否支持 BypassIO（Windows 11 优化存储栈）
+    pub os_bypass_io: bool,
+    /// 满足 BypassIO 条件的驱动器（NVMe + NTFS）
+    pub bypass_io_drives: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameDirectStorage {
+    /// 游戏所在驱动器是否满足 BypassIO 条件
+    pub bypass_io_capable: bool,
+    /// 安装目录中是否带有 DirectStorage 运行时
+    pub ships_runtime: bool,
+    /// 游戏进程是否已加载 DirectStorage 运行时（无法读取进程模块时为 None）
+    pub runtime_loaded: Option<bool>,
+}
+
+// ==================== 检测逻辑 ====================
+
+fn os_build() -> u32 {
+    System::kernel_version()
+        .and_then(|v| v.split('.').next_back().and_then(|b| b.parse().ok()))
+        .unwrap_or(0)
+}
+
+/// BypassIO 需要 Windows 11 + NVMe + NTFS
+fn drive_supports_bypass_io(drive: &DriveInfo, build: u32) -> bool {
+    build >= MIN_BUILD_BYPASS_IO
+        && drive.kind == DriveKind::Nvme
+        && drive.file_system.eq_ignore_ascii_case("ntfs")
+}
+
+/// 系统级 DirectStorage 能力
+pub fn detect_system_support(drives: &[DriveInfo]) -> DirectStorageSupport {
+    let build = os_build();
+    DirectStorageSupport {
+        os_build: build,
+        os_supported: build >= MIN_BUILD_DIRECT_STORAGE,
+        os_bypass_io: build >= MIN_BUILD_BYPASS_IO,
+        bypass_io_drives: drives
+            .iter()
+            .filter(|d| drive_supports_bypass_io(d, build))
+            .map(|d| d.mount_point.clone())
+            .collect(),
+    }
+}
+
+/// 在安装目录中查找 DirectStorage 运行时（限制深度，避免遍历整个游戏目录）
+fn find_runtime(dir: &Path, depth: u32) -> bool {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return false;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_lowercase();
+        match entry.file_type() {
+            Ok(t) if t.is_file() && RUNTIME_DLLS.contains(&name.as_str()) => return true,
+            Ok(t) if t.is_dir() && depth > 0 && find_runtime(&entry.path(), depth - 1) => {
+                return true;
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+/// 单个游戏的 DirectStorage 情况
+pub fn detect_game_support(
+    pid: u32,
+    install_dir: &Path,
+    drive: Option<&DriveInfo>,
+) -> GameDirectStorage {
+    let build = os_build();
+    let runtime_loaded = loaded_module_names(pid)
+        .ok()
+        .map(|modules| modules.iter().any(|m| RUNTIME_DLLS.contains(&m.as_str())));
+
+    GameDirectStorage {
+        bypass_io_capable: drive
+            .map(|d| drive_supports_bypass_io(d, build))
+            .unwrap_or(false),
+        ships_runtime: find_runtime(install_dir, 4),
+        runtime_loaded,
+    }
+}
+
+// ==================== 已加载模块 ====================
+
+/// 列出进程已加载的 DLL 模块名（小写）
+#[cfg(target_os = "windows")]
+pub fn loaded_module_names(pid: u32) -> Result<Vec<String>, String> {
+    use windows::Win32::Foundation::{CloseHandle, HMODULE};
+    use windows::Win32::System::ProcessStatus::{
+        EnumProcessModulesEx, GetModuleBaseNameW, LIST_MODULES_ALL,
+    };
+    use windows::Win32::System::Threading::{
+        OpenProcess, PROCESS_QUERY_INFORMATION, PROCESS_VM_READ,
+    };
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_INFORMATION | PROCESS_VM_READ, false, pid)
+            .map_err(|e| format!("无法打开进程 PID {}: {}", pid, e))?;
+
+        let mut modules = vec![HMODULE::default(); 1024];
+        let mut needed = 0u32;
+        let result = EnumProcessModulesEx(
+            handle,
+            modules.as_mut_ptr(),
+            (modules.len() * std::mem::size_of::<HMODULE>()) as u32,
+            &mut needed,
+            LIST_MODULES_ALL,
+        );
+        if let Err(e) = result {
+            let _ = CloseHandle(handle);
+            return Err(format!("枚举模块失败: {}", e));
+        }
+
+        let count = (needed as usize / std::mem::size_of::<HMODULE>()).min(modules.len());
+        let mut names = Vec::with_capacity(count);
+        let mut buffer = [0u16; 260];
+        for module in &modules[..count] {
+            let len = GetModuleBaseNameW(handle, *module, &mut buffer) as usize;
+            if len > 0 {
+                names.push(String::from_utf16_lossy(&buffer[..len]).to_lowercase());
+            }
+        }
+        let _ = CloseHandle(handle);
+        Ok(names)
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn loaded_module_names(_pid: u32) -> Result<Vec<String>, String> {
+    Err("仅支持 Windows".to_string())
+}
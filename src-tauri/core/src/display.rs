@@ -0,0 +1,230 @@
+use crate::vrr::VrrRange;
+use serde::{Deserialize, Serialize};
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisplayTarget {
+    /// 显示器名称 (EDID)
+    pub monitor_name: String,
+    /// 是否为笔记本内屏
+    pub internal: bool,
+    /// 连接方式 (e.g., "内屏", "HDMI", "DisplayPort")
+    pub connection: String,
+    pub width: u32,
+    pub height: u32,
+    /// 刷新率 (Hz)
+    pub refresh_hz: Option<f64>,
+    /// 显示器所连接 GPU 的 LUID
+    pub gpu_luid: Option<String>,
+    /// EDID 中的刷新率范围（无法读取 EDID 时为 None）
+    #[serde(default)]
+    pub refresh_range: Option<VrrRange>,
+}
+
+// ==================== 显示器查询 (Windows) ====================
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::DisplayTarget;
+    use crate::vrr::{self, VrrRange};
+    use windows::Win32::Devices::Display::{
+        DisplayConfigGetDeviceInfo, GetDisplayConfigBufferSizes, QueryDisplayConfig,
+        DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME, DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME,
+        DISPLAYCONFIG_MODE_INFO, DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DISPLAYPORT_EMBEDDED,
+        DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DISPLAYPORT_EXTERNAL, DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DVI,
+        DISPLAYCONFIG_OUTPUT_TECHNOLOGY_HDMI, DISPLAYCONFIG_OUTPUT_TECHNOLOGY_INTERNAL,
+        DISPLAYCONFIG_OUTPUT_TECHNOLOGY_LVDS, DISPLAYCONFIG_OUTPUT_TECHNOLOGY_UDI_EMBEDDED,
+        DISPLAYCONFIG_PATH_INFO, DISPLAYCONFIG_SOURCE_DEVICE_NAME,
+        DISPLAYCONFIG_TARGET_DEVICE_NAME, DISPLAYCONFIG_VIDEO_OUTPUT_TECHNOLOGY,
+        QDC_ONLY_ACTIVE_PATHS,
+    };
+    use windows::Win32::Foundation::ERROR_SUCCESS;
+    use windows::Win32::Graphics::Gdi::{
+        GetMonitorInfoW, MonitorFromWindow, MONITORINFO, MONITORINFOEXW, MONITOR_DEFAULTTONEAREST,
+    };
+
+    fn wide_to_string(buf: &[u16]) -> String {
+        let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+        String::from_utf16_lossy(&buf[..len])
+    }
+
+    fn classify(tech: DISPLAYCONFIG_VIDEO_OUTPUT_TECHNOLOGY) -> (bool, &'static str) {
+        match tech {
+            DISPLAYCONFIG_OUTPUT_TECHNOLOGY_INTERNAL
+            | DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DISPLAYPORT_EMBEDDED
+            | DISPLAYCONFIG_OUTPUT_TECHNOLOGY_UDI_EMBEDDED
+            | DISPLAYCONFIG_OUTPUT_TECHNOLOGY_LVDS => (true, "内屏"),
+            DISPLAYCONFIG_OUTPUT_TECHNOLOGY_HDMI => (false, "HDMI"),
+            DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DISPLAYPORT_EXTERNAL => (false, "DisplayPort"),
+            DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DVI => (false, "DVI"),
+            _ => (false, "外接显示器"),
+        }
+    }
+
+    /// 由显示器设备路径读取注册表中的 EDID
+    /// \\?\DISPLAY#GSM5B7F#5&2a3c1b4d&0&UID4353#{...} → Enum\DISPLAY\GSM5B7F\5&2a3c1b4d&0&UID4353
+    fn edid_range(device_path: &str) -> Option<VrrRange> {
+        use winreg::enums::HKEY_LOCAL_MACHINE;
+        use winreg::RegKey;
+
+        let parts: Vec<&str> = device_path
+            .trim_start_matches("\\\\?\\")
+            .split('#')
+            .collect();
+        if parts.len() < 3 {
+            return None;
+        }
+        let key = format!(
+            "SYSTEM\\CurrentControlSet\\Enum\\{}\\{}\\{}\\Device Parameters",
+            parts[0], parts[1], parts[2]
+        );
+        let edid = RegKey::predef(HKEY_LOCAL_MACHINE)
+            .open_subkey(key)
+            .and_then(|k| k.get_raw_value("EDID"))
+            .ok()?;
+        vrr::parse_edid_range(&edid.bytes)
+    }
+
+    fn active_paths() -> Vec<DISPLAYCONFIG_PATH_INFO> {
+        unsafe {
+            let mut path_count = 0u32;
+            let mut mode_count = 0u32;
+            if GetDisplayConfigBufferSizes(QDC_ONLY_ACTIVE_PATHS, &mut path_count, &mut mode_count)
+                != ERROR_SUCCESS
+            {
+                return vec![];
+            }
+            let mut paths = vec![DISPLAYCONFIG_PATH_INFO::default(); path_count as usize];
+            let mut modes = vec![DISPLAYCONFIG_MODE_INFO::default(); mode_count as usize];
+            if QueryDisplayConfig(
+                QDC_ONLY_ACTIVE_PATHS,
+                &mut path_count,
+                paths.as_mut_ptr(),
+                &mut mode_count,
+                modes.as_mut_ptr(),
+                None,
+            ) != ERROR_SUCCESS
+            {
+                return vec![];
+            }
+            paths.truncate(path_count as usize);
+            paths
+        }
+    }
+
+    /// 笔记本内屏所连接 GPU 的 LUID（没有内屏或内屏已关闭时为 None）
+    pub fn internal_panel_luid() -> Option<String> {
+        active_paths()
+            .into_iter()
+            .find(|p| classify(p.targetInfo.outputTechnology).0)
+            .map(|p| {
+                let luid = p.sourceInfo.adapterId;
+                crate::gpu_routing::format_luid(luid.HighPart as u32, luid.LowPart)
+            })
+    }
+
+    /// 游戏窗口所在的显示器
+    pub fn display_for_process(pid: u32) -> Option<DisplayTarget> {
+        let hwnd = crate::game_window::find_main_window(pid)?;
+
+        unsafe {
+            let monitor = MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST);
+            let mut info = MONITORINFOEXW::default();
+            info.monitorInfo.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+            if !GetMonitorInfoW(
+                monitor,
+                &mut info as *mut MONITORINFOEXW as *mut MONITORINFO,
+            )
+            .as_bool()
+            {
+                return None;
+            }
+            let gdi_name = wide_to_string(&info.szDevice);
+            let rect = info.monitorInfo.rcMonitor;
+            let width = (rect.right - rect.left) as u32;
+            let height = (rect.bottom - rect.top) as u32;
+
+            // 通过 GDI 设备名 (\\.\DISPLAY1) 匹配 DisplayConfig 路径
+            for path in active_paths() {
+                let mut source = DISPLAYCONFIG_SOURCE_DEVICE_NAME::default();
+                source.header.r#type = DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME;
+                source.header.size = std::mem::size_of::<DISPLAYCONFIG_SOURCE_DEVICE_NAME>() as u32;
+                source.header.adapterId = path.sourceInfo.adapterId;
+                source.header.id = path.sourceInfo.id;
+                if DisplayConfigGetDeviceInfo(&mut source.header) != 0
+                    || wide_to_string(&source.viewGdiDeviceName) != gdi_name
+                {
+                    continue;
+                }
+
+                let mut target = DISPLAYCONFIG_TARGET_DEVICE_NAME::default();
+                target.header.r#type = DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME;
+                target.header.size = std::mem::size_of::<DISPLAYCONFIG_TARGET_DEVICE_NAME>() as u32;
+                target.header.adapterId = path.targetInfo.adapterId;
+                target.header.id = path.targetInfo.id;
+                let (monitor_name, refresh_range) =
+                    if DisplayConfigGetDeviceInfo(&mut target.header) == 0 {
+                        (
+                            wide_to_string(&target.monitorFriendlyDeviceName),
+                            edid_range(&wide_to_string(&target.monitorDevicePath)),
+                        )
+                    } else {
+                        (String::new(), None)
+                    };
+
+                let (internal, connection) = classify(path.targetInfo.outputTechnology);
+                let rate = path.targetInfo.refreshRate;
+                let refresh_hz = (rate.Denominator > 0).then(|| {
+                    (rate.Numerator as f64 / rate.Denominator as f64 * 10.0).round() / 10.0
+                });
+                let luid = path.sourceInfo.adapterId;
+
+                return Some(DisplayTarget {
+                    monitor_name: if monitor_name.is_empty() {
+                        connection.to_string()
+                    } else {
+                        monitor_name
+                    },
+                    internal,
+                    connection: connection.to_string(),
+                    width,
+                    height,
+                    refresh_hz,
+                    gpu_luid: Some(crate::gpu_routing::format_luid(
+                        luid.HighPart as u32,
+                        luid.LowPart,
+                    )),
+                    refresh_range,
+                });
+            }
+
+            // DisplayConfig 查询失败时只返回分辨率
+            Some(DisplayTarget {
+                monitor_name: gdi_name,
+                internal: false,
+                connection: "未知".to_string(),
+                width,
+                height,
+                refresh_hz: None,
+                gpu_luid: None,
+                refresh_range: None,
+            })
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    use super::DisplayTarget;
+
+    pub fn display_for_process(_pid: u32) -> Option<DisplayTarget> {
+        None
+    }
+
+    pub fn internal_panel_luid() -> Option<String> {
+        None
+    }
+}
+
+pub use platform::{display_for_process, internal_panel_luid};
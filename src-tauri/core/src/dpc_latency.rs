@@ -0,0 +1,327 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// 单次执行超过此值即视为风暴 (µs)，与 LatencyMon 的红色阈值一致
+const STORM_SINGLE_US: f64 = 1000.0;
+/// 每秒 DPC/ISR 合计执行时间超过此值即视为风暴 (ms)
+const STORM_TOTAL_MS: f64 = 20.0;
+/// 卡顿与风暴相隔不超过此秒数时认为相关
+const COINCIDE_SECS: f64 = 1.0;
+/// 报告中保留的驱动数量
+const TOP_OFFENDERS: usize = 5;
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DpcOffender {
+    /// 驱动文件名 (e.g., "ndis.sys")，无法解析时为例程地址
+    pub driver: String,
+    pub dpc_count: u64,
+    pub isr_count: u64,
+    /// 单次最长执行时间 (µs)
+    pub max_us: f64,
+    /// 累计执行时间 (ms)
+    pub total_ms: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DpcLatencyReport {
+    /// 无法采样时的原因（如未以管理员身份运行）
+    pub error: Option<String>,
+    /// 单次 DPC 最长执行时间 (µs)
+    pub max_dpc_us: f64,
+    /// 单次 ISR 最长执行时间 (µs)
+    pub max_isr_us: f64,
+    /// 出现 DPC/ISR 风暴的秒数
+    pub storm_secs: u32,
+    /// 检测到的卡顿次数
+    pub stutters: u32,
+    /// 与风暴同时发生的卡顿次数
+    pub stutters_with_storm: u32,
+    /// 单次执行最长的驱动
+    pub offenders: Vec<DpcOffender>,
+    pub warnings: Vec<String>,
+}
+
+#[cfg(target_os = "windows")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExecKind {
+    Dpc,
+    Isr,
+}
+
+#[derive(Default)]
+struct RoutineStat {
+    dpc_count: u64,
+    isr_count: u64,
+    max_us: f64,
+    total_us: f64,
+}
+
+#[derive(Default, Clone, Copy)]
+struct SecondStat {
+    total_us: f64,
+    max_us: f64,
+}
+
+#[derive(Default)]
+struct Aggregator {
+    routines: HashMap<u64, RoutineStat>,
+    seconds: Vec<SecondStat>,
+    max_dpc_us: f64,
+    max_isr_us: f64,
+}
+
+impl Aggregator {
+    #[cfg(target_os = "windows")]
+    fn record(&mut self, second: usize, kind: ExecKind, routine: u64, us: f64) {
+        let stat = self.routines.entry(routine).or_default();
+        match kind {
+            ExecKind::Dpc => {
+                stat.dpc_count += 1;
+                self.max_dpc_us = self.max_dpc_us.max(us);
+            }
+            ExecKind::Isr => {
+                stat.isr_count += 1;
+                self.max_isr_us = self.max_isr_us.max(us);
+            }
+        }
+        stat.max_us = stat.max_us.max(us);
+        stat.total_us += us;
+
+        if self.seconds.len() <= second {
+            self.seconds.resize(second + 1, SecondStat::default());
+        }
+        let bucket = &mut self.seconds[second];
+        bucket.total_us += us;
+        bucket.max_us = bucket.max_us.max(us);
+    }
+}
+
+// ==================== ETW 采样 (Windows) ====================
+
+/// 私有内核会话名与 GUID
+#[cfg(target_os = "windows")]
+const SESSION_NAME: &str = "GameBench DPC Latency";
+#[cfg(target_os = "windows")]
+const SESSION_GUID: windows::core::GUID =
+    windows::core::GUID::from_u128(0x6f1c2b7e_4d8a_4c3f_9b2e_5a7d3e9c1f40);
+
+/// PerfInfo 事件: 66 ThreadedDPC / 67 ISR / 68 DPC / 69 TimerDPC
+/// 载荷前 16 字节: InitialTime (QPC) + Routine (64 位地址)
+#[cfg(target_os = "windows")]
+fn start_trace(agg: Arc<Mutex<Aggregator>>) -> Result<crate::etw::TraceSession, String> {
+    use windows::Win32::System::Diagnostics::Etw::{
+        PerfInfoGuid, EVENT_TRACE_FLAG_DPC, EVENT_TRACE_FLAG_INTERRUPT,
+    };
+
+    let freq = crate::etw::qpc_frequency() as f64;
+    let start = crate::etw::qpc_now();
+    crate::etw::TraceSession::start_kernel(
+        SESSION_NAME,
+        SESSION_GUID,
+        EVENT_TRACE_FLAG_DPC | EVENT_TRACE_FLAG_INTERRUPT,
+        Box::new(move |record| {
+            let header = &record.EventHeader;
+            if header.ProviderId != PerfInfoGuid || record.UserDataLength < 16 {
+                return;
+            }
+            let kind = match header.EventDescriptor.Opcode {
+                66 | 68 | 69 => ExecKind::Dpc,
+                67 => ExecKind::Isr,
+                _ => return,
+            };
+            let data = record.UserData as *const u64;
+            let (initial, routine) =
+                unsafe { (data.read_unaligned() as i64, data.add(1).read_unaligned()) };
+            let us = (header.TimeStamp - initial) as f64 * 1_000_000.0 / freq;
+            // 时间戳异常（跨 CPU 时钟偏差等）
+            if !(0.0..1_000_000.0).contains(&us) || header.TimeStamp < start {
+                return;
+            }
+            let second = ((header.TimeStamp - start) as f64 / freq) as usize;
+            agg.lock().unwrap().record(second, kind, routine, us);
+        }),
+    )
+}
+
+/// 已加载内核驱动: (基址, 文件名)，按基址排序
+#[cfg(target_os = "windows")]
+fn loaded_drivers() -> Vec<(u64, String)> {
+    use windows::Win32::System::ProcessStatus::{EnumDeviceDrivers, GetDeviceDriverBaseNameW};
+
+    let mut bases = vec![std::ptr::null_mut::<std::ffi::c_void>(); 1024];
+    let mut needed = 0u32;
+    let ok = unsafe {
+        EnumDeviceDrivers(
+            bases.as_mut_ptr(),
+            (bases.len() * std::mem::size_of::<usize>()) as u32,
+            &mut needed,
+        )
+    };
+    if ok.is_err() {
+        return vec![];
+    }
+    let count = (needed as usize / std::mem::size_of::<usize>()).min(bases.len());
+
+    let mut buffer = [0u16; 260];
+    let mut drivers: Vec<(u64, String)> = bases[..count]
+        .iter()
+        .filter(|b| !b.is_null())
+        .filter_map(|base| {
+            let len = unsafe { GetDeviceDriverBaseNameW(*base, &mut buffer) } as usize;
+            (len > 0).then(|| {
+                (
+                    *base as u64,
+                    String::from_utf16_lossy(&buffer[..len]).to_lowercase(),
+                )
+            })
+        })
+        .collect();
+    drivers.sort_by_key(|(base, _)| *base);
+    drivers
+}
+
+#[cfg(not(target_os = "windows"))]
+fn loaded_drivers() -> Vec<(u64, String)> {
+    vec![]
+}
+
+/// 例程地址所属的驱动（基址不大于该地址的最后一个驱动）
+fn driver_for(drivers: &[(u64, String)], routine: u64) -> String {
+    let idx = drivers.partition_point(|(base, _)| *base <= routine);
+    match idx.checked_sub(1).and_then(|i| drivers.get(i)) {
+        Some((_, name)) => name.clone(),
+        None => format!("0x{:x}", routine),
+    }
+}
+
+// ==================== 测试期间采样 ====================
+
+/// 在 FPS 监测期间通过 ETW 记录 DPC/ISR 执行时间（类似 LatencyMon）
+pub struct DpcSampler {
+    started: Instant,
+    agg: Arc<Mutex<Aggregator>>,
+    #[cfg(target_os = "windows")]
+    session: Option<crate::etw::TraceSession>,
+    error: Option<String>,
+}
+
+impl DpcSampler {
+    pub fn start() -> Self {
+        let agg = Arc::new(Mutex::new(Aggregator::default()));
+
+        #[cfg(target_os = "windows")]
+        let (session, error) = match start_trace(agg.clone()) {
+            Ok(session) => (Some(session), None),
+            Err(e) => {
+                log::warn!("DPC/ISR 延迟采样不可用: {}", e);
+                (None, Some(e))
+            }
+        };
+        #[cfg(not(target_os = "windows"))]
+        let error = Some("仅支持 Windows".to_string());
+
+        DpcSampler {
+            started: Instant::now(),
+            agg,
+            #[cfg(target_os = "windows")]
+            session,
+            error,
+        }
+    }
+
+    /// 停止采样，结合卡顿时刻生成报告
+    pub fn finish(mut self, stutters: &[Instant]) -> DpcLatencyReport {
+        // 停止会话并等待消费线程退出，之后聚合数据不再变化
+        #[cfg(target_os = "windows")]
+        drop(self.session.take());
+        if let Some(error) = self.error.take() {
+            return DpcLatencyReport {
+                error: Some(error),
+                ..Default::default()
+            };
+        }
+        let agg = self.agg.lock().unwrap();
+        build_report(&agg, self.started, stutters)
+    }
+}
+
+fn build_report(agg: &Aggregator, started: Instant, stutters: &[Instant]) -> DpcLatencyReport {
+    let storms: Vec<usize> = agg
+        .seconds
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.max_us >= STORM_SINGLE_US || s.total_us / 1000.0 >= STORM_TOTAL_MS)
+        .map(|(i, _)| i)
+        .collect();
+
+    let stutters_with_storm = stutters
+        .iter()
+        .map(|t| t.saturating_duration_since(started).as_secs_f64())
+        .filter(|at| {
+            storms.iter().any(|s| {
+                // 风暴秒 [s, s+1) 与卡顿时刻的距离
+                let (from, to) = (*s as f64, *s as f64 + 1.0);
+                *at >= from - COINCIDE_SECS && *at <= to + COINCIDE_SECS
+            })
+        })
+        .count() as u32;
+
+    // 例程按驱动合并
+    let drivers = loaded_drivers();
+    let mut by_driver: HashMap<String, DpcOffender> = HashMap::new();
+    for (routine, stat) in &agg.routines {
+        let name = driver_for(&drivers, *routine);
+        let entry = by_driver.entry(name.clone()).or_insert(DpcOffender {
+            driver: name,
+            dpc_count: 0,
+            isr_count: 0,
+            max_us: 0.0,
+            total_ms: 0.0,
+        });
+        entry.dpc_count += stat.dpc_count;
+        entry.isr_count += stat.isr_count;
+        entry.max_us = entry.max_us.max(stat.max_us);
+        entry.total_ms += stat.total_us / 1000.0;
+    }
+    let mut offenders: Vec<DpcOffender> = by_driver.into_values().collect();
+    offenders.sort_by(|a, b| b.max_us.total_cmp(&a.max_us));
+    offenders.truncate(TOP_OFFENDERS);
+    for o in &mut offenders {
+        o.max_us = (o.max_us * 10.0).round() / 10.0;
+        o.total_ms = (o.total_ms * 100.0).round() / 100.0;
+    }
+
+    let mut warnings = Vec::new();
+    if stutters_with_storm > 0 {
+        if let Some(worst) = offenders.first() {
+            warnings.push(format!(
+                "{} 次卡顿中有 {} 次与 DPC/ISR 延迟风暴同时发生，单次执行最长的驱动是 {}（{:.0} µs）。\
+                 可尝试更新或禁用该驱动对应的设备（常见为网卡、无线网卡、声卡与 ACPI 电源管理驱动）。",
+                stutters.len(),
+                stutters_with_storm,
+                worst.driver,
+                worst.max_us
+            ));
+        }
+    } else if agg.max_dpc_us.max(agg.max_isr_us) >= STORM_SINGLE_US {
+        warnings.push(format!(
+            "测试期间出现过 {:.0} µs 的 DPC/ISR 执行，但未与检测到的卡顿重合。",
+            agg.max_dpc_us.max(agg.max_isr_us)
+        ));
+    }
+
+    DpcLatencyReport {
+        error: None,
+        max_dpc_us: (agg.max_dpc_us * 10.0).round() / 10.0,
+        max_isr_us: (agg.max_isr_us * 10.0).round() / 10.0,
+        storm_secs: storms.len() as u32,
+        stutters: stutters.len() as u32,
+        stutters_with_storm,
+        offenders,
+        warnings,
+    }
+}
@@ -0,0 +1,446 @@
+use crate::units::UnitPrefs;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use sysinfo::{DiskKind, Disks};
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriveKind {
+    Nvme,
+    SataSsd,
+    Hdd,
+    Unknown,
+}
+
+impl DriveKind {
+    /// 相对速度等级，数值越大越快
+    pub fn speed_rank(self) -> u8 {
+        match self {
+            DriveKind::Nvme => 3,
+            DriveKind::SataSsd => 2,
+            DriveKind::Unknown => 1,
+            DriveKind::Hdd => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriveInfo {
+    /// 挂载点 (e.g., "D:\\")
+    pub mount_point: String,
+    /// 卷标
+    pub name: String,
+    pub kind: DriveKind,
+    /// 文件系统 (e.g., "NTFS")
+    pub file_system: String,
+    /// 总容量 (GB)
+    pub total_gb: f64,
+    /// 可用空间 (GB)
+    pub available_gb: f64,
+    pub removable: bool,
+    /// 物理磁盘编号（Windows），用于关联 SMART 等物理盘信息
+    pub disk_number: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Healthy,
+    Warning,
+    Unhealthy,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriveHealth {
+    /// 物理磁盘编号，对应 DriveInfo.disk_number
+    pub disk_number: u32,
+    /// 型号
+    pub model: String,
+    pub status: HealthStatus,
+    /// 温度 (°C)
+    pub temperature_c: Option<u32>,
+    /// 已用寿命 (%)，仅 SSD
+    pub wear_percent: Option<u32>,
+    /// 无法纠正的读取错误数
+    pub read_errors_uncorrected: Option<u64>,
+    /// 待映射扇区数（SMART 197 Current Pending Sector），仅 ATA/SATA 盘，NVMe 无此属性
+    pub pending_sectors: Option<u64>,
+    /// 通电时间 (小时)
+    pub power_on_hours: Option<u64>,
+    /// 发现的问题
+    pub problems: Vec<String>,
+}
+
+/// 温度告警阈值 (°C)
+const TEMPERATURE_WARN_C: u32 = 70;
+/// 寿命告警阈值 (%)
+const WEAR_WARN_PERCENT: u32 = 90;
+
+// ==================== 物理盘类型 (Windows) ====================
+
+/// 盘符 → (物理磁盘编号, 物理盘类型)
+#[cfg(target_os = "windows")]
+fn query_physical_kinds() -> std::collections::HashMap<char, (u32, Option<DriveKind>)> {
+    use std::collections::HashMap;
+    use wmi::{COMLibrary, WMIConnection};
+
+    let mut map = HashMap::new();
+    let Ok(com) = COMLibrary::new() else {
+        return map;
+    };
+    let Ok(wmi) = WMIConnection::with_namespace_path(r"ROOT\Microsoft\Windows\Storage", com) else {
+        return map;
+    };
+
+    let partitions: Vec<HashMap<String, wmi::Variant>> = wmi
+        .raw_query("SELECT DriveLetter, DiskNumber FROM MSFT_Partition")
+        .unwrap_or_default();
+    let disks: Vec<HashMap<String, wmi::Variant>> = wmi
+        .raw_query("SELECT DeviceId, MediaType, BusType FROM MSFT_PhysicalDisk")
+        .unwrap_or_default();
+
+    // DeviceId → DriveKind
+    // MediaType: 3 = HDD, 4 = SSD；BusType: 17 = NVMe
+    let mut disk_kinds: HashMap<u32, DriveKind> = HashMap::new();
+    for disk in &disks {
+        let id = match disk.get("DeviceId") {
+            Some(wmi::Variant::String(s)) => s.parse::<u32>().ok(),
+            _ => None,
+        };
+        let media = match disk.get("MediaType") {
+            Some(wmi::Variant::UI2(n)) => *n as u32,
+            Some(wmi::Variant::UI4(n)) => *n,
+            _ => 0,
+        };
+        let bus = match disk.get("BusType") {
+            Some(wmi::Variant::UI2(n)) => *n as u32,
+            Some(wmi::Variant::UI4(n)) => *n,
+            _ => 0,
+        };
+        let kind = if bus == 17 {
+            DriveKind::Nvme
+        } else if media == 4 {
+            DriveKind::SataSsd
+        } else if media == 3 {
+            DriveKind::Hdd
+        } else {
+            DriveKind::Unknown
+        };
+        if let Some(id) = id {
+            disk_kinds.insert(id, kind);
+        }
+    }
+
+    for partition in &partitions {
+        let letter = match partition.get("DriveLetter") {
+            Some(wmi::Variant::UI2(n)) => char::from_u32(*n as u32),
+            Some(wmi::Variant::String(s)) => s.chars().next(),
+            _ => None,
+        };
+        let disk_number = match partition.get("DiskNumber") {
+            Some(wmi::Variant::UI4(n)) => Some(*n),
+            _ => None,
+        };
+        if let (Some(letter), Some(number)) = (letter, disk_number) {
+            if letter.is_ascii_alphabetic() {
+                map.insert(
+                    letter.to_ascii_uppercase(),
+                    (number, disk_kinds.get(&number).copied()),
+                );
+            }
+        }
+    }
+
+    map
+}
+
+#[cfg(not(target_os = "windows"))]
+fn query_physical_kinds() -> std::collections::HashMap<char, (u32, Option<DriveKind>)> {
+    std::collections::HashMap::new()
+}
+
+// ==================== SMART 健康 (Windows) ====================
+
+/// WMI 中 uint64 常以字符串形式返回
+#[cfg(target_os = "windows")]
+pub fn variant_u64(v: Option<&wmi::Variant>) -> Option<u64> {
+    match v {
+        Some(wmi::Variant::UI1(n)) => Some(*n as u64),
+        Some(wmi::Variant::UI2(n)) => Some(*n as u64),
+        Some(wmi::Variant::UI4(n)) => Some(*n as u64),
+        Some(wmi::Variant::UI8(n)) => Some(*n),
+        Some(wmi::Variant::I4(n)) if *n >= 0 => Some(*n as u64),
+        Some(wmi::Variant::String(s)) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// SMART 属性 197：当前待映射扇区
+#[cfg(target_os = "windows")]
+const SMART_PENDING_SECTORS: u8 = 197;
+
+/// 从 MSStorageDriver_FailurePredictData 的 VendorSpecific 中取属性原始值
+/// 数据为 2 字节版本号后跟 30 个 12 字节属性：ID、标志 (2)、当前值、最差值、原始值 (6)、保留
+#[cfg(target_os = "windows")]
+fn smart_raw_value(data: &[u8], id: u8) -> Option<u64> {
+    data.get(2..)?
+        .chunks_exact(12)
+        .take(30)
+        .find(|attr| attr[0] == id)
+        .map(|attr| {
+            attr[5..11]
+                .iter()
+                .rev()
+                .fold(0u64, |acc, &b| (acc << 8) | b as u64)
+        })
+}
+
+/// 物理磁盘编号 → 待映射扇区数
+/// ROOT\WMI 只对 ATA/SATA 盘提供 SMART 数据，且需要管理员权限；读不到的盘不在结果中
+#[cfg(target_os = "windows")]
+fn query_pending_sectors(com: wmi::COMLibrary) -> std::collections::HashMap<u32, u64> {
+    use std::collections::HashMap;
+    use wmi::WMIConnection;
+
+    let mut map = HashMap::new();
+    let (Ok(cimv2), Ok(root_wmi)) = (
+        WMIConnection::new(com),
+        WMIConnection::with_namespace_path(r"ROOT\WMI", com),
+    ) else {
+        return map;
+    };
+    let drives: Vec<HashMap<String, wmi::Variant>> = cimv2
+        .raw_query("SELECT Index, PNPDeviceID FROM Win32_DiskDrive")
+        .unwrap_or_default();
+    let predictions: Vec<HashMap<String, wmi::Variant>> = root_wmi
+        .raw_query("SELECT InstanceName, VendorSpecific FROM MSStorageDriver_FailurePredictData")
+        .unwrap_or_else(|e| {
+            log::debug!("读取 SMART 数据失败（可能需要管理员权限）: {}", e);
+            vec![]
+        });
+
+    for prediction in &predictions {
+        // InstanceName 为设备实例路径加 "_0" 之类的后缀
+        let Some(wmi::Variant::String(instance)) = prediction.get("InstanceName") else {
+            continue;
+        };
+        let Some(wmi::Variant::Array(bytes)) = prediction.get("VendorSpecific") else {
+            continue;
+        };
+        let data: Vec<u8> = bytes
+            .iter()
+            .filter_map(|b| variant_u64(Some(b)).map(|b| b as u8))
+            .collect();
+        let Some(pending) = smart_raw_value(&data, SMART_PENDING_SECTORS) else {
+            continue;
+        };
+        let instance = instance.to_lowercase();
+        let index = drives.iter().find_map(|d| match d.get("PNPDeviceID") {
+            Some(wmi::Variant::String(pnp)) if instance.starts_with(&pnp.to_lowercase()) => {
+                variant_u64(d.get("Index"))
+            }
+            _ => None,
+        });
+        if let Some(index) = index {
+            map.insert(index as u32, pending);
+        }
+    }
+    map
+}
+
+/// 通过 MSFT_PhysicalDisk + MSFT_StorageReliabilityCounter 读取健康度，
+/// 待映射扇区另从 SMART 数据读取
+/// 可靠性计数器与 SMART 数据需要管理员权限，读不到时仅返回 HealthStatus
+#[cfg(target_os = "windows")]
+pub fn read_drive_health(units: &UnitPrefs) -> Vec<DriveHealth> {
+    use std::collections::HashMap;
+    use wmi::{COMLibrary, WMIConnection};
+
+    let Ok(com) = COMLibrary::new() else {
+        return vec![];
+    };
+    let pending = query_pending_sectors(com);
+    let Ok(wmi) = WMIConnection::with_namespace_path(r"ROOT\Microsoft\Windows\Storage", com) else {
+        return vec![];
+    };
+
+    let disks: Vec<HashMap<String, wmi::Variant>> = wmi
+        .raw_query("SELECT DeviceId, FriendlyName, HealthStatus FROM MSFT_PhysicalDisk")
+        .unwrap_or_default();
+    let counters: Vec<HashMap<String, wmi::Variant>> = wmi
+        .raw_query(
+            "SELECT DeviceId, Temperature, Wear, ReadErrorsUncorrected, PowerOnHours \
+             FROM MSFT_StorageReliabilityCounter",
+        )
+        .unwrap_or_else(|e| {
+            log::warn!("读取磁盘可靠性计数器失败（可能需要管理员权限）: {}", e);
+            vec![]
+        });
+
+    let counter_by_id: HashMap<String, &HashMap<String, wmi::Variant>> = counters
+        .iter()
+        .filter_map(|c| match c.get("DeviceId") {
+            Some(wmi::Variant::String(id)) => Some((id.clone(), c)),
+            _ => None,
+        })
+        .collect();
+
+    disks
+        .iter()
+        .filter_map(|disk| {
+            let id = match disk.get("DeviceId") {
+                Some(wmi::Variant::String(s)) => s.clone(),
+                _ => return None,
+            };
+            let disk_number = id.parse::<u32>().ok()?;
+            let model = match disk.get("FriendlyName") {
+                Some(wmi::Variant::String(s)) => s.clone(),
+                _ => "Unknown".to_string(),
+            };
+            // HealthStatus: 0 = Healthy, 1 = Warning, 2 = Unhealthy
+            let status = match variant_u64(disk.get("HealthStatus")) {
+                Some(0) => HealthStatus::Healthy,
+                Some(1) => HealthStatus::Warning,
+                Some(2) => HealthStatus::Unhealthy,
+                _ => HealthStatus::Unknown,
+            };
+
+            let counter = counter_by_id.get(&id);
+            let field = |name: &str| counter.and_then(|c| variant_u64(c.get(name)));
+            // 计数器未实现时返回 0，温度/寿命为 0 视为无数据
+            let temperature_c = field("Temperature").filter(|&t| t > 0).map(|t| t as u32);
+            let wear_percent = field("Wear").map(|w| w as u32);
+            let read_errors_uncorrected = field("ReadErrorsUncorrected");
+            let power_on_hours = field("PowerOnHours");
+            let pending_sectors = pending.get(&disk_number).copied();
+
+            Some(evaluate_health(
+                DriveHealth {
+                    disk_number,
+                    model,
+                    status,
+                    temperature_c,
+                    wear_percent,
+                    read_errors_uncorrected,
+                    power_on_hours,
+                    pending_sectors,
+                    problems: Vec::new(),
+                },
+                units,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn read_drive_health(_units: &UnitPrefs) -> Vec<DriveHealth> {
+    vec![]
+}
+
+/// 根据计数器填充问题列表，并在必要时提升状态等级
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn evaluate_health(mut health: DriveHealth, units: &UnitPrefs) -> DriveHealth {
+    match health.status {
+        HealthStatus::Warning => health
+            .problems
+            .push("系统报告磁盘健康状态为「警告」".to_string()),
+        HealthStatus::Unhealthy => health
+            .problems
+            .push("系统报告磁盘健康状态为「不健康」，请尽快备份数据".to_string()),
+        _ => {}
+    }
+    if let Some(t) = health.temperature_c.filter(|&t| t >= TEMPERATURE_WARN_C) {
+        health.problems.push(format!(
+            "磁盘温度 {} 过高，可能触发降速",
+            units.temperature(t as f64)
+        ));
+    }
+    if let Some(w) = health.wear_percent.filter(|&w| w >= WEAR_WARN_PERCENT) {
+        health.problems.push(format!(
+            "SSD 已用寿命 {}，接近耗尽",
+            units.percent(w as f64, 0)
+        ));
+    }
+    if let Some(e) = health.read_errors_uncorrected.filter(|&e| e > 0) {
+        health
+            .problems
+            .push(format!("存在 {} 个无法纠正的读取错误（坏扇区）", e));
+    }
+    if let Some(p) = health.pending_sectors.filter(|&p| p > 0) {
+        health
+            .problems
+            .push(format!("存在 {} 个待映射扇区，磁盘可能正在出现坏道", p));
+    }
+    if !health.problems.is_empty() && health.status == HealthStatus::Healthy {
+        health.status = HealthStatus::Warning;
+    }
+    health
+}
+
+/// 查找路径所在物理盘的健康信息
+pub fn health_for_path(path: &Path, units: &UnitPrefs) -> Option<DriveHealth> {
+    let drives = list_drive_info();
+    let number = drive_for_path(&drives, path)?.disk_number?;
+    read_drive_health(units)
+        .into_iter()
+        .find(|h| h.disk_number == number)
+}
+
+// ==================== 驱动器枚举 ====================
+
+/// 列出所有本地驱动器
+pub fn list_drive_info() -> Vec<DriveInfo> {
+    let disks = Disks::new_with_refreshed_list();
+    let physical = query_physical_kinds();
+
+    disks
+        .list()
+        .iter()
+        .map(|disk| {
+            let mount_point = disk.mount_point().to_string_lossy().to_string();
+            let letter = mount_point.chars().next().map(|c| c.to_ascii_uppercase());
+            let (disk_number, wmi_kind) = letter
+                .and_then(|l| physical.get(&l).copied())
+                .map(|(n, k)| (Some(n), k))
+                .unwrap_or((None, None));
+
+            let kind = wmi_kind.unwrap_or(match disk.kind() {
+                DiskKind::HDD => DriveKind::Hdd,
+                DiskKind::SSD => DriveKind::SataSsd,
+                DiskKind::Unknown(_) => DriveKind::Unknown,
+            });
+
+            let gb = 1024.0 * 1024.0 * 1024.0;
+            DriveInfo {
+                mount_point,
+                name: disk.name().to_string_lossy().to_string(),
+                kind,
+                file_system: disk.file_system().to_string_lossy().to_string(),
+                total_gb: (disk.total_space() as f64 / gb * 10.0).round() / 10.0,
+                available_gb: (disk.available_space() as f64 / gb * 10.0).round() / 10.0,
+                removable: disk.is_removable(),
+                disk_number,
+            }
+        })
+        .collect()
+}
+
+/// 找到路径所在的驱动器（最长挂载点匹配）
+pub fn drive_for_path<'a>(drives: &'a [DriveInfo], path: &Path) -> Option<&'a DriveInfo> {
+    let path = path.to_string_lossy().to_lowercase();
+    drives
+        .iter()
+        .filter(|d| path.starts_with(&d.mount_point.to_lowercase()))
+        .max_by_key(|d| d.mount_point.len())
+}
+
+/// 系统中最慢的固定驱动器
+pub fn slowest_drive(drives: &[DriveInfo]) -> Option<&DriveInfo> {
+    drives
+        .iter()
+        .filter(|d| !d.removable)
+        .min_by_key(|d| d.kind.speed_rank())
+}
@@ -0,0 +1,21 @@
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+
+// ==================== 签名 ====================
+
+/// 由 32 字节私钥种子计算公钥
+pub fn public_key(seed: &[u8; 32]) -> [u8; 32] {
+    SigningKey::from_bytes(seed).verifying_key().to_bytes()
+}
+
+/// Ed25519 签名 (RFC 8032)
+pub fn sign(seed: &[u8; 32], message: &[u8]) -> [u8; 64] {
+    SigningKey::from_bytes(seed).sign(message).to_bytes()
+}
+
+/// 校验 Ed25519 签名；使用严格校验，拒绝小阶公钥与可延展的签名
+pub fn verify(public_key: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+    VerifyingKey::from_bytes(public_key).is_ok_and(|key| {
+        key.verify_strict(message, &Signature::from_bytes(signature))
+            .is_ok()
+    })
+}
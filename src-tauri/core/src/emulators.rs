@@ -0,0 +1,135 @@
+// ==================== 已知模拟器 ====================
+
+#[derive(Debug, Clone, Copy)]
+pub struct Emulator {
+    /// 模拟器名称
+    pub name: &'static str,
+    /// 模拟平台
+    pub platform: &'static str,
+    /// 能否从窗口标题解析出正在运行的游戏
+    pub title_has_game: bool,
+}
+
+/// 进程名(小写), 模拟器名称, 平台, 窗口标题是否包含游戏名
+#[rustfmt::skip]
+const EMULATORS: &[(&str, &str, &str, bool)] = &[
+    // Switch（yuzu 停更后的分支也沿用相同标题格式）
+    ("ryujinx.exe", "Ryujinx", "Switch", true),
+    ("suyu.exe", "suyu", "Switch", true),
+    ("sudachi.exe", "Sudachi", "Switch", true),
+    ("citron.exe", "Citron", "Switch", true),
+    ("eden.exe", "Eden", "Switch", true),
+    ("yuzu.exe", "yuzu", "Switch", true),
+    // PlayStation
+    ("rpcs3.exe", "RPCS3", "PS3", true),
+    ("pcsx2-qt.exe", "PCSX2", "PS2", true),
+    ("pcsx2.exe", "PCSX2", "PS2", true),
+    ("ppssppwindows64.exe", "PPSSPP", "PSP", true),
+    ("duckstation-qt-x64-releaseltcg.exe", "DuckStation", "PS1", true),
+    ("shadps4.exe", "shadPS4", "PS4", true),
+    // 任天堂 / Xbox
+    ("dolphin.exe", "Dolphin", "GameCube/Wii", true),
+    ("cemu.exe", "Cemu", "Wii U", true),
+    ("citra-qt.exe", "Citra", "3DS", true),
+    ("xenia_canary.exe", "Xenia", "Xbox 360", true),
+    // 安卓模拟器（国内常用），窗口标题不含游戏名
+    ("mumuplayer.exe", "MuMu 模拟器", "Android", false),
+    ("mumunxdevice.exe", "MuMu 模拟器", "Android", false),
+    ("dnplayer.exe", "雷电模拟器", "Android", false),
+    ("ldplayer.exe", "雷电模拟器", "Android", false),
+    ("nox.exe", "夜神模拟器", "Android", false),
+    ("hd-player.exe", "BlueStacks", "Android", false),
+    ("memu.exe", "逍遥模拟器", "Android", false),
+];
+
+pub fn find_emulator(exe_name: &str) -> Option<Emulator> {
+    let lower = exe_name.to_lowercase();
+    EMULATORS
+        .iter()
+        .find(|(process, ..)| *process == lower)
+        .map(|&(_, name, platform, title_has_game)| Emulator {
+            name,
+            platform,
+            title_has_game,
+        })
+}
+
+// ==================== 窗口标题解析 ====================
+
+/// 渲染后端 / 状态字段关键词（按片段首个单词匹配），这些片段不是游戏名
+const NOISE_KEYWORDS: &[&str] = &[
+    "fps",
+    "vulkan",
+    "opengl",
+    "d3d",
+    "direct3d",
+    "dx11",
+    "dx12",
+    "jit",
+    "jit64",
+    "hle",
+    "lle",
+    "interpreter",
+    "recompiler",
+    "64-bit",
+    "32-bit",
+    "speed",
+    "vps",
+    "docked",
+    "handheld",
+];
+
+fn is_noise(segment: &str, emulator: &Emulator) -> bool {
+    let lower = segment.to_lowercase();
+    if lower.is_empty() || lower.contains(&emulator.name.to_lowercase()) {
+        return true;
+    }
+    // 版本号: "v1.7.5", "0.0.29", "1734"
+    let version_like = lower
+        .trim_start_matches('v')
+        .chars()
+        .all(|c| c.is_ascii_digit() || c == '.' || c == '-');
+    version_like
+        || lower
+            .split(|c: char| !c.is_alphanumeric() && c != '-')
+            .next()
+            .is_some_and(|word| NOISE_KEYWORDS.contains(&word))
+}
+
+/// 去掉游戏名末尾的 ID/版本标记: "(0100000000010000)", "[BLUS30443]", "v1.3.0"
+fn strip_trailing_tags(name: &str) -> String {
+    let mut name = name.trim().to_string();
+    loop {
+        let trimmed = name.trim_end();
+        let stripped = if trimmed.ends_with(')') {
+            trimmed.rfind(" (").map(|idx| &trimmed[..idx])
+        } else if trimmed.ends_with(']') {
+            trimmed.rfind(" [").map(|idx| &trimmed[..idx])
+        } else {
+            trimmed
+                .rsplit_once(' ')
+                .filter(|(_, last)| {
+                    last.starts_with('v') && last[1..].starts_with(|c: char| c.is_ascii_digit())
+                })
+                .map(|(head, _)| head)
+        };
+        match stripped {
+            Some(s) if !s.trim().is_empty() => name = s.trim().to_string(),
+            _ => return trimmed.to_string(),
+        }
+    }
+}
+
+/// 从模拟器窗口标题中解析正在运行的游戏名
+/// e.g., "FPS: 60.00 | Vulkan | 0.0.29 | Demon's Souls [BLUS30443]" → "Demon's Souls"
+pub fn parse_game_title(emulator: &Emulator, window_title: &str) -> Option<String> {
+    if !emulator.title_has_game {
+        return None;
+    }
+    window_title
+        .split(" | ")
+        .flat_map(|part| part.split(" - "))
+        .map(strip_trailing_tags)
+        .filter(|segment| !is_noise(segment, emulator))
+        .max_by_key(|segment| segment.chars().count())
+}
@@ -0,0 +1,240 @@
+#![cfg(target_os = "windows")]
+
+use std::thread::JoinHandle;
+use windows::core::{GUID, PCWSTR, PWSTR};
+use windows::Win32::Foundation::{
+    ERROR_ACCESS_DENIED, ERROR_ALREADY_EXISTS, ERROR_SUCCESS, WIN32_ERROR,
+};
+use windows::Win32::System::Diagnostics::Etw::{
+    CloseTrace, ControlTraceW, EnableTraceEx2, OpenTraceW, ProcessTrace, StartTraceW,
+    CONTROLTRACE_HANDLE, EVENT_CONTROL_CODE_ENABLE_PROVIDER, EVENT_RECORD,
+    EVENT_TRACE_CONTROL_STOP, EVENT_TRACE_FLAG, EVENT_TRACE_LOGFILEW, EVENT_TRACE_PROPERTIES,
+    EVENT_TRACE_REAL_TIME_MODE, EVENT_TRACE_SYSTEM_LOGGER_MODE, PROCESSTRACE_HANDLE,
+    PROCESS_TRACE_MODE_EVENT_RECORD, PROCESS_TRACE_MODE_RAW_TIMESTAMP,
+    PROCESS_TRACE_MODE_REAL_TIME, TRACE_LEVEL_INFORMATION, WNODE_FLAG_TRACED_GUID,
+};
+use windows::Win32::System::Performance::{QueryPerformanceCounter, QueryPerformanceFrequency};
+
+/// 事件回调（在 ETW 消费线程上执行，应尽量轻量）
+pub type EventCallback = Box<dyn FnMut(&EVENT_RECORD) + Send>;
+
+/// OpenTraceW 失败时的返回值
+const INVALID_PROCESSTRACE_HANDLE: u64 = u64::MAX;
+
+// ==================== 时钟 ====================
+
+/// 会话使用 QPC 时钟，事件时间戳与内核事件载荷中的时间同源
+pub fn qpc_frequency() -> i64 {
+    let mut freq = 0i64;
+    unsafe {
+        let _ = QueryPerformanceFrequency(&mut freq);
+    }
+    freq.max(1)
+}
+
+pub fn qpc_now() -> i64 {
+    let mut now = 0i64;
+    unsafe {
+        let _ = QueryPerformanceCounter(&mut now);
+    }
+    now
+}
+
+// ==================== 会话 ====================
+
+fn wide(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// EVENT_TRACE_PROPERTIES 后需紧跟会话名，按 8 字节对齐分配
+fn properties(name: &[u16]) -> Vec<u64> {
+    let size = std::mem::size_of::<EVENT_TRACE_PROPERTIES>() + name.len() * 2;
+    vec![0u64; size.div_ceil(8)]
+}
+
+fn describe(err: WIN32_ERROR) -> String {
+    if err == ERROR_ACCESS_DENIED {
+        "需要以管理员身份运行".to_string()
+    } else {
+        format!("错误码 {}", err.0)
+    }
+}
+
+/// 私有内核会话 + 实时消费者；Drop 时停止会话并等待消费线程退出
+pub struct TraceSession {
+    name: Vec<u16>,
+    handle: CONTROLTRACE_HANDLE,
+    consumer: Option<JoinHandle<()>>,
+}
+
+unsafe extern "system" fn on_event(record: *mut EVENT_RECORD) {
+    let record = &*record;
+    let callback = &mut *(record.UserContext as *mut EventCallback);
+    callback(record);
+}
+
+/// 按会话名停止（上次异常退出遗留的实时会话不会随进程结束），返回是否停止了会话
+pub fn stop_by_name(name: &str) -> bool {
+    let name = wide(name);
+    let mut buffer = properties(&name);
+    let props = buffer.as_mut_ptr() as *mut EVENT_TRACE_PROPERTIES;
+    unsafe {
+        (*props).Wnode.BufferSize = (buffer.len() * 8) as u32;
+        (*props).LoggerNameOffset = std::mem::size_of::<EVENT_TRACE_PROPERTIES>() as u32;
+        ControlTraceW(
+            CONTROLTRACE_HANDLE::default(),
+            PCWSTR(name.as_ptr()),
+            props,
+            EVENT_TRACE_CONTROL_STOP,
+        ) == ERROR_SUCCESS
+    }
+}
+
+impl TraceSession {
+    /// 启动私有内核会话（Windows 8+，不占用全局的 NT Kernel Logger）
+    pub fn start_kernel(
+        name: &str,
+        guid: GUID,
+        flags: EVENT_TRACE_FLAG,
+        callback: EventCallback,
+    ) -> Result<Self, String> {
+        Self::start(
+            name,
+            guid,
+            EVENT_TRACE_REAL_TIME_MODE | EVENT_TRACE_SYSTEM_LOGGER_MODE,
+            flags,
+            &[],
+            callback,
+        )
+    }
+
+    /// 启动用户模式实时会话，并以 Information 级别启用 `providers` 的全部事件
+    pub fn start_user(
+        name: &str,
+        guid: GUID,
+        providers: &[GUID],
+        callback: EventCallback,
+    ) -> Result<Self, String> {
+        Self::start(
+            name,
+            guid,
+            EVENT_TRACE_REAL_TIME_MODE,
+            EVENT_TRACE_FLAG(0),
+            providers,
+            callback,
+        )
+    }
+
+    fn start(
+        name: &str,
+        guid: GUID,
+        mode: u32,
+        flags: EVENT_TRACE_FLAG,
+        providers: &[GUID],
+        callback: EventCallback,
+    ) -> Result<Self, String> {
+        let name = wide(name);
+        let mut handle = CONTROLTRACE_HANDLE::default();
+
+        let mut result = ERROR_SUCCESS;
+        for attempt in 0..2 {
+            let mut buffer = properties(&name);
+            let props = buffer.as_mut_ptr() as *mut EVENT_TRACE_PROPERTIES;
+            unsafe {
+                (*props).Wnode.BufferSize = (buffer.len() * 8) as u32;
+                (*props).Wnode.Flags = WNODE_FLAG_TRACED_GUID;
+                (*props).Wnode.Guid = guid;
+                // 1 = QPC 时钟
+                (*props).Wnode.ClientContext = 1;
+                (*props).LogFileMode = mode;
+                (*props).EnableFlags = flags;
+                (*props).FlushTimer = 1;
+                (*props).BufferSize = 64;
+                (*props).LoggerNameOffset = std::mem::size_of::<EVENT_TRACE_PROPERTIES>() as u32;
+                result = StartTraceW(&mut handle, PCWSTR(name.as_ptr()), props);
+                // 上次异常退出遗留的同名会话，停止后重试
+                if result == ERROR_ALREADY_EXISTS && attempt == 0 {
+                    let _ = ControlTraceW(
+                        CONTROLTRACE_HANDLE::default(),
+                        PCWSTR(name.as_ptr()),
+                        props,
+                        EVENT_TRACE_CONTROL_STOP,
+                    );
+                    continue;
+                }
+            }
+            break;
+        }
+        if result != ERROR_SUCCESS {
+            return Err(format!("无法启动 ETW 会话: {}", describe(result)));
+        }
+
+        let mut session = TraceSession {
+            name,
+            handle,
+            consumer: None,
+        };
+        for provider in providers {
+            let result = unsafe {
+                EnableTraceEx2(
+                    handle,
+                    provider,
+                    EVENT_CONTROL_CODE_ENABLE_PROVIDER.0,
+                    TRACE_LEVEL_INFORMATION as u8,
+                    0,
+                    0,
+                    0,
+                    None,
+                )
+            };
+            if result != ERROR_SUCCESS {
+                return Err(format!("无法启用 ETW provider: {}", describe(result)));
+            }
+        }
+        session.consumer = Some(session.open_consumer(callback)?);
+        Ok(session)
+    }
+
+    fn open_consumer(&mut self, callback: EventCallback) -> Result<JoinHandle<()>, String> {
+        let context = Box::into_raw(Box::new(callback));
+        let mut logfile = EVENT_TRACE_LOGFILEW {
+            LoggerName: PWSTR(self.name.as_mut_ptr()),
+            Context: context as *mut std::ffi::c_void,
+            ..Default::default()
+        };
+        logfile.Anonymous1.ProcessTraceMode = PROCESS_TRACE_MODE_REAL_TIME
+            | PROCESS_TRACE_MODE_EVENT_RECORD
+            | PROCESS_TRACE_MODE_RAW_TIMESTAMP;
+        logfile.Anonymous2.EventRecordCallback = Some(on_event);
+
+        let trace = unsafe { OpenTraceW(&mut logfile) };
+        if trace.Value == INVALID_PROCESSTRACE_HANDLE {
+            drop(unsafe { Box::from_raw(context) });
+            return Err("无法打开 ETW 实时会话".to_string());
+        }
+
+        // 裸指针不能跨线程，按地址传递；会话停止后 ProcessTrace 返回再释放
+        let context = context as usize;
+        Ok(std::thread::spawn(move || unsafe {
+            let trace = PROCESSTRACE_HANDLE { Value: trace.Value };
+            let _ = ProcessTrace(&[trace], None, None);
+            let _ = CloseTrace(trace);
+            drop(Box::from_raw(context as *mut EventCallback));
+        }))
+    }
+}
+
+impl Drop for TraceSession {
+    fn drop(&mut self) {
+        let mut buffer = properties(&self.name);
+        let props = buffer.as_mut_ptr() as *mut EVENT_TRACE_PROPERTIES;
+        unsafe {
+            (*props).Wnode.BufferSize = (buffer.len() * 8) as u32;
+            (*props).LoggerNameOffset = std::mem::size_of::<EVENT_TRACE_PROPERTIES>() as u32;
+            let _ = ControlTraceW(self.handle, PCWSTR::null(), props, EVENT_TRACE_CONTROL_STOP);
+        }
+        if let Some(consumer) = self.consumer.take() {
+            let _ = consumer.join();
+        }
+    }
+}
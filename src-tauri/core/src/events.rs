@@ -0,0 +1,22 @@
+use serde::Serialize;
+
+/// 事件出口：核心库不依赖 Tauri，由界面层把事件转发给前端（Tauri 壳）或日志（命令行模式）
+pub trait EventSink: Send + Sync {
+    fn emit_value(&self, event: &str, payload: serde_json::Value);
+}
+
+impl dyn EventSink {
+    pub fn emit<T: Serialize>(&self, event: &str, payload: &T) {
+        match serde_json::to_value(payload) {
+            Ok(value) => self.emit_value(event, value),
+            Err(e) => log::warn!("事件 {} 序列化失败: {}", event, e),
+        }
+    }
+}
+
+/// 丢弃所有事件
+pub struct NullSink;
+
+impl EventSink for NullSink {
+    fn emit_value(&self, _event: &str, _payload: serde_json::Value) {}
+}
@@ -0,0 +1,104 @@
+use crate::metrics::frametime_percentile;
+use crate::vrr::VrrRange;
+use serde::{Deserialize, Serialize};
+
+/// 平均帧率高于可持续帧率的倍数超过该值时，认为帧率波动大、值得限帧
+const FLUCTUATION_RATIO: f64 = 1.15;
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CapKind {
+    /// VRR：限制在刷新率上限以下，避免超出 VRR 范围后撕裂或被垂直同步拖慢
+    VrrCeiling,
+    /// VRR：帧率波动大时限制在可持续帧率，换取稳定的帧时间
+    Sustained,
+    /// 固定刷新率：帧率足够时与刷新率一致
+    Refresh,
+    /// 固定刷新率：达不到刷新率时限制在一半刷新率，保持均匀的帧间隔
+    HalfRefresh,
+}
+
+/// 限帧建议
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrameCapAdvice {
+    pub kind: CapKind,
+    /// 建议的帧率上限
+    pub cap_fps: u32,
+    /// 显示器刷新率 (Hz)
+    pub refresh_hz: f64,
+    /// 未限帧时的平均帧率
+    pub avg_fps: f64,
+    /// 95% 的帧都能达到的帧率（第 95 百分位帧时间换算）
+    pub sustained_fps: f64,
+    /// 快于上限、限帧后会被拉平的帧占比 (%)
+    pub frames_above_cap_pct: f64,
+}
+
+// ==================== 计算 ====================
+
+fn round1(v: f64) -> f64 {
+    (v * 10.0).round() / 10.0
+}
+
+/// VRR 显示器的常用限帧值：刷新率 - 刷新率²/3600（144 Hz → 138，240 Hz → 224）
+pub fn vrr_ceiling(refresh_hz: f64) -> u32 {
+    (refresh_hz - refresh_hz * refresh_hz / 3600.0).floor() as u32
+}
+
+/// 由帧时间分布与显示器刷新率/VRR 范围推荐限帧值，没有明显收益时返回 None
+pub fn advise(
+    frame_times: &[f64],
+    refresh_hz: f64,
+    vrr: Option<VrrRange>,
+) -> Option<FrameCapAdvice> {
+    let mut sorted: Vec<f64> = frame_times
+        .iter()
+        .copied()
+        .filter(|ft| *ft > 0.0 && ft.is_finite())
+        .collect();
+    if sorted.is_empty() || refresh_hz <= 0.0 {
+        return None;
+    }
+    sorted.sort_by(f64::total_cmp);
+    let avg_fps = 1000.0 / (sorted.iter().sum::<f64>() / sorted.len() as f64);
+    let sustained_fps = 1000.0 / frametime_percentile(&sorted, 95.0);
+
+    let choice = match vrr.filter(VrrRange::is_variable) {
+        Some(range) => {
+            let ceiling = vrr_ceiling(refresh_hz).max(range.min_hz.ceil() as u32);
+            if avg_fps >= ceiling as f64 * 0.9 {
+                Some((CapKind::VrrCeiling, ceiling))
+            } else if sustained_fps >= range.min_hz && avg_fps / sustained_fps > FLUCTUATION_RATIO {
+                // 取 5 的整数倍，便于在游戏或驱动中设置
+                let cap = (sustained_fps / 5.0).floor() as u32 * 5;
+                Some((CapKind::Sustained, cap.max(range.min_hz.ceil() as u32)))
+            } else {
+                None
+            }
+        }
+        None => {
+            let refresh = refresh_hz.round();
+            if sustained_fps >= refresh * 0.95 {
+                Some((CapKind::Refresh, refresh as u32))
+            } else if sustained_fps >= refresh / 2.0 * 0.95 && avg_fps < refresh * 0.9 {
+                Some((CapKind::HalfRefresh, (refresh / 2.0).round() as u32))
+            } else {
+                None
+            }
+        }
+    };
+    let (kind, cap_fps) = choice?;
+
+    let cap_ms = 1000.0 / cap_fps as f64;
+    let above = sorted.partition_point(|ft| *ft < cap_ms);
+    Some(FrameCapAdvice {
+        kind,
+        cap_fps,
+        refresh_hz,
+        avg_fps: round1(avg_fps),
+        sustained_fps: round1(sustained_fps),
+        frames_above_cap_pct: round1(above as f64 / sorted.len() as f64 * 100.0),
+    })
+}
@@ -0,0 +1,345 @@
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use std::fs::File;
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// 文件头标识与格式版本
+const MAGIC: &[u8; 4] = b"GBF1";
+/// 每块帧数，读取部分区间时只解压涉及的块
+const CHUNK_FRAMES: usize = 65_536;
+/// 帧时间量化单位：0.1 µs（PresentMon 输出 4 位小数的毫秒）
+const UNITS_PER_MS: f64 = 10_000.0;
+/// 块索引每项字节数: 偏移 u64 + 压缩长度 u32 + 帧数 u32
+const INDEX_ENTRY_LEN: usize = 16;
+
+// ==================== 编码 ====================
+
+// 格式: MAGIC | 块数 u32 | 块索引 | 各块数据
+// 每块独立压缩：帧时间量化为整数后与前一帧做差，zigzag + varint 编码，再 deflate
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn read_varint(data: &[u8], pos: &mut usize) -> Option<u64> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    loop {
+        let byte = *data.get(*pos)?;
+        *pos += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+        shift += 7;
+        if shift >= 64 {
+            return None;
+        }
+    }
+}
+
+fn encode_chunk(frames: &[f64]) -> Result<Vec<u8>, String> {
+    let mut raw = Vec::with_capacity(frames.len() * 2);
+    let mut prev = 0i64;
+    for &ft in frames {
+        let value = (ft * UNITS_PER_MS).round() as i64;
+        let delta = value - prev;
+        write_varint(&mut raw, ((delta << 1) ^ (delta >> 63)) as u64);
+        prev = value;
+    }
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&raw).map_err(|e| e.to_string())?;
+    encoder.finish().map_err(|e| e.to_string())
+}
+
+fn decode_chunk(data: &[u8], frames: usize) -> Result<Vec<f64>, String> {
+    let mut raw = Vec::new();
+    DeflateDecoder::new(data)
+        .read_to_end(&mut raw)
+        .map_err(|e| format!("帧数据解压失败: {}", e))?;
+    let mut out = Vec::with_capacity(frames);
+    let mut pos = 0;
+    let mut prev = 0i64;
+    for _ in 0..frames {
+        let zigzag = read_varint(&raw, &mut pos).ok_or("帧数据已损坏")?;
+        prev += ((zigzag >> 1) as i64) ^ -((zigzag & 1) as i64);
+        out.push(prev as f64 / UNITS_PER_MS);
+    }
+    Ok(out)
+}
+
+/// 文件头与块索引，`chunks` 为各块 (帧数, 压缩长度)
+fn encode_header(chunks: &[(usize, usize)]) -> Vec<u8> {
+    let header_len = MAGIC.len() + 4 + chunks.len() * INDEX_ENTRY_LEN;
+    let mut out = Vec::with_capacity(header_len);
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&(chunks.len() as u32).to_le_bytes());
+    let mut offset = header_len as u64;
+    for &(frames, len) in chunks {
+        out.extend_from_slice(&offset.to_le_bytes());
+        out.extend_from_slice(&(len as u32).to_le_bytes());
+        out.extend_from_slice(&(frames as u32).to_le_bytes());
+        offset += len as u64;
+    }
+    out
+}
+
+/// 将帧时间序列编码为分块压缩格式
+pub fn encode(frame_times: &[f64]) -> Result<Vec<u8>, String> {
+    let chunks = frame_times
+        .chunks(CHUNK_FRAMES)
+        .map(|c| encode_chunk(c).map(|data| (c.len(), data)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let index: Vec<(usize, usize)> = chunks.iter().map(|(n, d)| (*n, d.len())).collect();
+    let mut out = encode_header(&index);
+    for (_, data) in &chunks {
+        out.extend_from_slice(data);
+    }
+    Ok(out)
+}
+
+// ==================== 逐帧写入 ====================
+
+/// 逐帧写入帧时间文件，内存中只保留一块：压缩后的块先写入 `<路径>.part`，
+/// `finish` 时写出文件头与块索引再接上块数据。未调用 `finish` 时不会留下文件
+pub struct FrameLogWriter {
+    path: PathBuf,
+    body_path: PathBuf,
+    body: Option<BufWriter<File>>,
+    pending: Vec<f64>,
+    /// 已写入的块 (帧数, 压缩长度)
+    chunks: Vec<(usize, usize)>,
+    finished: bool,
+}
+
+impl FrameLogWriter {
+    pub fn create(path: &Path) -> Result<Self, String> {
+        let mut body_path = path.as_os_str().to_owned();
+        body_path.push(".part");
+        let body_path = PathBuf::from(body_path);
+        let body = File::create(&body_path).map_err(|e| format!("无法写入帧数据: {}", e))?;
+        Ok(FrameLogWriter {
+            path: path.to_path_buf(),
+            body_path,
+            body: Some(BufWriter::new(body)),
+            pending: Vec::with_capacity(CHUNK_FRAMES),
+            chunks: Vec::new(),
+            finished: false,
+        })
+    }
+
+    pub fn push(&mut self, frametime_ms: f64) -> Result<(), String> {
+        self.pending.push(frametime_ms);
+        if self.pending.len() >= CHUNK_FRAMES {
+            self.flush_chunk()?;
+        }
+        Ok(())
+    }
+
+    /// 已写入的帧数
+    pub fn frame_count(&self) -> usize {
+        self.chunks.iter().map(|(n, _)| n).sum::<usize>() + self.pending.len()
+    }
+
+    fn flush_chunk(&mut self) -> Result<(), String> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let data = encode_chunk(&self.pending)?;
+        let body = self.body.as_mut().ok_or("帧数据已写完")?;
+        body.write_all(&data)
+            .map_err(|e| format!("无法写入帧数据: {}", e))?;
+        self.chunks.push((self.pending.len(), data.len()));
+        self.pending.clear();
+        Ok(())
+    }
+
+    /// 写出完整文件，返回总帧数
+    pub fn finish(mut self) -> Result<usize, String> {
+        let err = |e: std::io::Error| format!("无法写入帧数据: {}", e);
+        self.flush_chunk()?;
+        if let Some(body) = self.body.take() {
+            body.into_inner().map_err(|e| err(e.into_error()))?;
+        }
+        let mut out = BufWriter::new(File::create(&self.path).map_err(err)?);
+        out.write_all(&encode_header(&self.chunks)).map_err(err)?;
+        let mut body = File::open(&self.body_path).map_err(err)?;
+        std::io::copy(&mut body, &mut out).map_err(err)?;
+        out.into_inner()
+            .map_err(|e| err(e.into_error()))?
+            .sync_all()
+            .map_err(err)?;
+        self.finished = true;
+        Ok(self.frame_count())
+    }
+}
+
+impl Drop for FrameLogWriter {
+    fn drop(&mut self) {
+        self.body = None;
+        let _ = std::fs::remove_file(&self.body_path);
+        if !self.finished {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+// ==================== 读取 ====================
+
+struct ChunkIndex {
+    offset: usize,
+    len: usize,
+    /// 本块第一帧在整个序列中的序号
+    first_frame: usize,
+    frames: usize,
+}
+
+enum Source {
+    /// 已整体读入（加密文件需先整体解密）
+    Memory(Vec<u8>),
+    /// 未加密文件只读入索引，按块读取
+    File(PathBuf),
+}
+
+/// 帧时间文件，按块读取并解压，分析超长会话时不必一次载入全部帧
+pub struct FrameLog {
+    source: Source,
+    chunks: Vec<ChunkIndex>,
+}
+
+fn read_u32(data: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(pos..pos + 4)?.try_into().ok()?))
+}
+
+fn read_u64(data: &[u8], pos: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(data.get(pos..pos + 8)?.try_into().ok()?))
+}
+
+/// 解析文件头与块索引，`total_len` 为文件总长度
+fn parse_index(header: &[u8], total_len: usize) -> Result<Vec<ChunkIndex>, String> {
+    if header.get(..MAGIC.len()) != Some(MAGIC.as_slice()) {
+        return Err("不是 GameBench 帧数据文件".to_string());
+    }
+    let count = read_u32(header, MAGIC.len()).ok_or("帧数据索引已损坏")? as usize;
+    let mut chunks = Vec::with_capacity(count);
+    let mut first_frame = 0;
+    for i in 0..count {
+        let pos = MAGIC.len() + 4 + i * INDEX_ENTRY_LEN;
+        let (Some(offset), Some(len), Some(frames)) = (
+            read_u64(header, pos),
+            read_u32(header, pos + 8),
+            read_u32(header, pos + 12),
+        ) else {
+            return Err("帧数据索引已损坏".to_string());
+        };
+        let (offset, len, frames) = (offset as usize, len as usize, frames as usize);
+        if offset.checked_add(len).is_none_or(|end| end > total_len) {
+            return Err("帧数据文件不完整".to_string());
+        }
+        chunks.push(ChunkIndex {
+            offset,
+            len,
+            first_frame,
+            frames,
+        });
+        first_frame += frames;
+    }
+    Ok(chunks)
+}
+
+impl FrameLog {
+    pub fn parse(data: Vec<u8>) -> Result<Self, String> {
+        let chunks = parse_index(&data, data.len())?;
+        Ok(FrameLog {
+            source: Source::Memory(data),
+            chunks,
+        })
+    }
+
+    /// 打开未加密的帧时间文件，只读取索引
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let err = |e: std::io::Error| format!("读取帧数据失败: {}", e);
+        let mut file = File::open(path).map_err(err)?;
+        let total_len = file.metadata().map_err(err)?.len() as usize;
+        let mut header = vec![0u8; MAGIC.len() + 4];
+        file.read_exact(&mut header)
+            .map_err(|_| "不是 GameBench 帧数据文件".to_string())?;
+        let count = read_u32(&header, MAGIC.len()).unwrap_or(0) as usize;
+        let index_len = count
+            .checked_mul(INDEX_ENTRY_LEN)
+            .filter(|len| header.len() + len <= total_len)
+            .ok_or("帧数据索引已损坏")?;
+        header.resize(header.len() + index_len, 0);
+        file.read_exact(&mut header[MAGIC.len() + 4..])
+            .map_err(err)?;
+        Ok(FrameLog {
+            chunks: parse_index(&header, total_len)?,
+            source: Source::File(path.to_path_buf()),
+        })
+    }
+
+    /// 总帧数
+    pub fn frame_count(&self) -> usize {
+        self.chunks.last().map_or(0, |c| c.first_frame + c.frames)
+    }
+
+    /// 依次解压 [start, start + count) 区间涉及的块，`f` 收到区间内的帧；同一时刻只保留一块
+    /// `f` 返回 Err 时停止读取（如任务被取消）
+    pub fn for_each_chunk(
+        &self,
+        start: usize,
+        count: usize,
+        mut f: impl FnMut(&[f64]) -> Result<(), String>,
+    ) -> Result<(), String> {
+        let end = start.saturating_add(count).min(self.frame_count());
+        let err = |e: std::io::Error| format!("读取帧数据失败: {}", e);
+        let mut file: Option<File> = None;
+        let mut buffer = Vec::new();
+        for chunk in &self.chunks {
+            let chunk_end = chunk.first_frame + chunk.frames;
+            if chunk_end <= start || chunk.first_frame >= end {
+                continue;
+            }
+            let data = match &self.source {
+                Source::Memory(data) => &data[chunk.offset..chunk.offset + chunk.len],
+                Source::File(path) => {
+                    let file = match &mut file {
+                        Some(file) => file,
+                        None => file.insert(File::open(path).map_err(err)?),
+                    };
+                    buffer.resize(chunk.len, 0);
+                    file.seek(SeekFrom::Start(chunk.offset as u64))
+                        .and_then(|_| file.read_exact(&mut buffer))
+                        .map_err(err)?;
+                    &buffer[..]
+                }
+            };
+            let frames = decode_chunk(data, chunk.frames)?;
+            let from = start.saturating_sub(chunk.first_frame);
+            let to = (end - chunk.first_frame).min(chunk.frames);
+            f(&frames[from..to])?;
+        }
+        Ok(())
+    }
+
+    /// 读取 [start, start + count) 区间的帧时间，只解压涉及的块
+    pub fn read_range(&self, start: usize, count: usize) -> Result<Vec<f64>, String> {
+        let mut out = Vec::new();
+        self.for_each_chunk(start, count, |frames| {
+            out.extend_from_slice(frames);
+            Ok(())
+        })?;
+        Ok(out)
+    }
+
+    pub fn read_all(&self) -> Result<Vec<f64>, String> {
+        self.read_range(0, self.frame_count())
+    }
+}
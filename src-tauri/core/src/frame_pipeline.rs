@@ -0,0 +1,524 @@
+use crate::capture_time::{ActiveClock, CapturePause, QpcTimeline};
+use crate::events::EventSink;
+use crate::input_latency::{InputLatency, LatencyStats};
+use crate::launch_timing::LaunchTracker;
+use crate::metrics::{percentile_low_fps, FrametimeHistogram, PercentileMetrics};
+use crate::present_mode::{PresentInfo, PresentStats};
+use crate::presentmon::{CsvStream, FrameRow};
+use crate::sensors::{AdapterUsage, SensorReadings};
+use crate::smoothing::{self, FpsSmoother, FpsSmoothing};
+use crate::stutter::StutterDetector;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 交换链累计出帧数达到此值后锁定
+const DOMINANT_SWAPCHAIN_FRAMES: u32 = 120;
+/// 等待采集输出时检查停止状态的间隔
+const READ_POLL_INTERVAL: Duration = Duration::from_millis(200);
+/// 开始监测后第一个快照的窗口（秒），不等待完整的快照间隔
+const FIRST_SNAPSHOT_SECS: f64 = 0.5;
+/// 卡顿事件附带的前后帧数
+const STUTTER_CONTEXT_FRAMES: usize = 10;
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BenchmarkStage {
+    Warmup,
+    Capture,
+}
+
+/// 基准测试倒计时事件（`benchmark-countdown`），剩余秒数变化时推送
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkCountdown {
+    pub session_id: String,
+    pub stage: BenchmarkStage,
+    /// 当前阶段剩余秒数（向上取整）
+    pub remaining_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FpsSnapshot {
+    /// 当前 FPS（按设置中的平滑方式计算）
+    pub fps: f64,
+    /// 快照窗口内的算术平均 FPS（未平滑）
+    #[serde(default)]
+    pub raw_fps: f64,
+    /// 本次快照使用的平滑方式
+    #[serde(default)]
+    pub smoothing: FpsSmoothing,
+    /// 1% Low FPS
+    pub fps_1_low: f64,
+    /// 0.1% Low FPS
+    pub fps_01_low: f64,
+    /// 帧时间 (ms)
+    pub frametime_ms: f64,
+    /// CPU 占用时间 (ms)
+    pub cpu_busy_ms: f64,
+    /// GPU 占用时间 (ms)
+    pub gpu_busy_ms: f64,
+    /// 监测的进程名
+    pub process_name: String,
+    /// 所属会话 ID（同时监测多个进程时区分来源）
+    #[serde(default)]
+    pub session_id: String,
+    /// 有效监测时长 (秒)，不含系统睡眠与长时间无帧的中断
+    pub elapsed_secs: f64,
+    /// 系统传感器读数（CPU/GPU 占用、温度、内存）
+    #[serde(default)]
+    pub sensors: SensorReadings,
+    /// 按适配器拆分的 GPU 占用（多显卡系统），GPU 占用时间只计入渲染适配器
+    #[serde(default)]
+    pub adapters: Vec<AdapterUsage>,
+    /// 遥测插件最近读数，键为 `插件id.指标`
+    #[serde(default)]
+    pub plugin_metrics: BTreeMap<String, f64>,
+    /// 快照窗口内的帧时间分布
+    #[serde(default)]
+    pub histogram: FrametimeHistogram,
+    /// 按监测选项计算的快照窗口百分位指标
+    #[serde(default)]
+    pub percentiles: PercentileMetrics,
+    /// 快照窗口内的呈现模式、垂直同步与撕裂（内置 ETW 采集不提供）
+    #[serde(default)]
+    pub present: Option<PresentInfo>,
+    /// 快照窗口内输入到画面的延迟（需要 PresentMon v2）
+    #[serde(default)]
+    pub latency: Option<InputLatency>,
+}
+
+/// 卡顿事件（fps-stutter），收集完卡顿后的几帧再推送
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FpsStutter {
+    pub session_id: String,
+    pub process_name: String,
+    /// 卡顿帧的 Unix 毫秒时间戳
+    pub unix_ms: f64,
+    /// 有效监测时间 (秒)
+    pub at_secs: f64,
+    pub frametime_ms: f64,
+    /// 近期帧时间中位数 (ms)
+    pub median_ms: f64,
+    /// 卡顿帧时间 / 中位数
+    pub ratio: f64,
+    /// 卡顿前的帧时间（含卡顿帧）
+    pub before_ms: Vec<f64>,
+    /// 卡顿后的帧时间
+    pub after_ms: Vec<f64>,
+    /// 本次监测累计卡顿次数
+    pub count: u32,
+}
+
+/// 采集循环看到的监测状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopState {
+    Recording,
+    /// 手动暂停：帧直接丢弃
+    Paused,
+    /// 已要求停止
+    Stopped,
+}
+
+/// 帧处理参数
+#[derive(Debug, Clone, Default)]
+pub struct PipelineConfig {
+    pub session_id: String,
+    pub process_name: String,
+    /// 只统计最先稳定出帧的交换链
+    pub lock_dominant_swapchain: bool,
+    /// 预热时长 (秒)，期间的帧与卡顿不计入会话
+    pub warmup_secs: f64,
+    /// 定时基准测试在有效时长达到此值时结束
+    pub benchmark_end: Option<f64>,
+    /// 快照推送间隔 (秒)
+    pub snapshot_interval: f64,
+    /// 是否有卡顿脚本钩子
+    pub stutter_hooks: bool,
+    pub frametime_percentiles: Vec<f64>,
+    pub low_percentiles: Vec<f64>,
+}
+
+/// 界面层在帧处理过程中提供的状态与回调
+pub trait CaptureHost {
+    /// 当前监测状态，每帧读取
+    fn state(&self) -> LoopState;
+    /// 收到目标进程的第一帧（唤醒后重新出帧时也会调用），返回进程 PID
+    fn on_presenting(&mut self) -> Option<u32>;
+    /// 每一行帧数据（原始采集日志）
+    fn on_row(&mut self, timestamp_ms: Option<f64>, row: &FrameRow);
+    /// 预热后计入会话的帧
+    fn on_counted_frame(&mut self, active_secs: f64, frametime_ms: f64);
+    /// 卡顿脚本钩子（已按间隔限流）
+    fn on_stutter_hook(&mut self, stutter: &FpsStutter);
+    /// 快照使用的 FPS 平滑方式
+    fn smoothing(&self) -> FpsSmoothing;
+    /// 补充传感器、适配器与插件读数
+    fn decorate(&mut self, snapshot: &mut FpsSnapshot);
+    /// 定时基准测试已采满
+    fn on_benchmark_complete(&mut self);
+}
+
+/// 采集结束时的统计
+pub struct CaptureTotals {
+    /// 有效采集时长 (秒)
+    pub active_secs: f64,
+    pub pauses: Vec<CapturePause>,
+    /// 预热后的卡顿次数
+    pub stutter_count: u32,
+    /// 所有卡顿帧的时刻（含预热），用于 DPC / 内存压力归因
+    pub stutter_times: Vec<Instant>,
+    pub present: Option<PresentInfo>,
+    pub latency: Option<InputLatency>,
+    pub launch: LaunchTracker,
+    pub benchmark_completed: bool,
+}
+
+// ==================== 帧处理 ====================
+
+/// 逐行解析采集输出，计算快照、卡顿与基准测试进度
+/// 采集来源（PresentMon / ETW）的启动与重启由界面层负责
+pub struct FramePipeline {
+    config: PipelineConfig,
+    sink: Arc<dyn EventSink>,
+    swapchain_frames: HashMap<String, u32>,
+    locked_swapchain: Option<String>,
+    csv: CsvStream,
+    /// 快照窗口
+    window: Vec<f64>,
+    window_present: PresentStats,
+    window_latency: LatencyStats,
+    window_start: Instant,
+    /// 预热后的呈现模式与延迟统计，随会话保存
+    present_stats: PresentStats,
+    latency_stats: LatencyStats,
+    smoother: FpsSmoother,
+    /// 有效采集时长（不含系统睡眠与长时间无帧）
+    clock: ActiveClock,
+    stutter: StutterDetector,
+    stutter_times: Vec<Instant>,
+    /// 等待收集后续帧的卡顿事件
+    pending_stutters: Vec<FpsStutter>,
+    stutter_count: u32,
+    /// 帧时刻以 PresentMon 的 QPC 时间戳为准，不受 stdout 缓冲影响
+    timeline: QpcTimeline,
+    logged_timeline: bool,
+    first_snapshot: bool,
+    /// 已收到目标进程的第一帧
+    presenting: bool,
+    launch: LaunchTracker,
+    /// 观察到手动暂停的时刻，恢复后记入中断
+    paused_at: Option<Instant>,
+    countdown: Option<(BenchmarkStage, u64)>,
+    benchmark_completed: bool,
+}
+
+impl FramePipeline {
+    pub fn new(config: PipelineConfig, sink: Arc<dyn EventSink>) -> Self {
+        FramePipeline {
+            config,
+            sink,
+            swapchain_frames: HashMap::new(),
+            locked_swapchain: None,
+            csv: CsvStream::default(),
+            window: Vec::new(),
+            window_present: PresentStats::default(),
+            window_latency: LatencyStats::default(),
+            window_start: Instant::now(),
+            present_stats: PresentStats::default(),
+            latency_stats: LatencyStats::default(),
+            smoother: FpsSmoother::default(),
+            clock: ActiveClock::default(),
+            stutter: StutterDetector::default(),
+            stutter_times: Vec::new(),
+            pending_stutters: Vec::new(),
+            stutter_count: 0,
+            timeline: QpcTimeline::default(),
+            logged_timeline: false,
+            first_snapshot: true,
+            presenting: false,
+            launch: LaunchTracker::default(),
+            paused_at: None,
+            countdown: None,
+            benchmark_completed: false,
+        }
+    }
+
+    /// 有效采集时长 (秒)
+    pub fn active_secs(&self) -> f64 {
+        self.clock.active_secs()
+    }
+
+    /// 处理采集输出，直到来源退出、监测停止或基准测试采满
+    pub fn run(&mut self, lines: &Receiver<String>, host: &mut impl CaptureHost) {
+        loop {
+            let line = match lines.recv_timeout(READ_POLL_INTERVAL) {
+                Ok(l) => l,
+                // 暂无输出（游戏加载中或 ETW 会话尚未开始出帧）
+                Err(RecvTimeoutError::Timeout) => {
+                    match host.state() {
+                        LoopState::Stopped => return,
+                        LoopState::Paused if self.paused_at.is_none() => {
+                            self.paused_at = Some(Instant::now());
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+                // 采集来源已退出
+                Err(RecvTimeoutError::Disconnected) => return,
+            };
+
+            // 检查是否已停止
+            let paused = match host.state() {
+                LoopState::Stopped => return,
+                state => state == LoopState::Paused,
+            };
+
+            // 第一行是 CSV header，之后为帧数据
+            let Some(row) = self.csv.push_line(&line) else {
+                continue;
+            };
+            if !self.push_row(row, paused, host) {
+                return;
+            }
+        }
+    }
+
+    /// 采集来源重启后（系统唤醒）继续处理：重新输出表头，时间戳也从 0 开始
+    pub fn restart(&mut self) {
+        self.presenting = false;
+        self.csv.reset();
+        self.clock.restart_timeline();
+    }
+
+    /// 结束处理，后续帧不足的卡顿事件直接推送
+    pub fn finish(self) -> CaptureTotals {
+        for event in &self.pending_stutters {
+            self.sink.emit("fps-stutter", event);
+        }
+        CaptureTotals {
+            active_secs: self.clock.active_secs(),
+            pauses: self.clock.pauses().to_vec(),
+            stutter_count: self.stutter_count,
+            stutter_times: self.stutter_times,
+            present: self.present_stats.summary(),
+            latency: self.latency_stats.summary(),
+            launch: self.launch,
+            benchmark_completed: self.benchmark_completed,
+        }
+    }
+
+    fn clear_window(&mut self) {
+        self.window.clear();
+        self.window_present.clear();
+        self.window_latency.clear();
+    }
+
+    /// 处理一帧，返回 false 表示基准测试已采满
+    fn push_row(&mut self, row: FrameRow, paused: bool, host: &mut impl CaptureHost) -> bool {
+        let warmup_secs = self.config.warmup_secs;
+
+        // 手动暂停期间的帧直接丢弃，不计入统计、有效时长与快照
+        if paused {
+            if self.paused_at.is_none() {
+                self.paused_at = Some(Instant::now());
+                self.clear_window();
+            }
+            return true;
+        }
+        if let Some(at) = self.paused_at.take() {
+            let paused_for = at.elapsed();
+            self.clock.resume_after(paused_for);
+            self.clear_window();
+            self.window_start = Instant::now();
+            log::info!(
+                "继续采集 {}，暂停 {:.1}s",
+                self.config.process_name,
+                paused_for.as_secs_f64()
+            );
+        }
+
+        // 只统计最先稳定出帧的交换链
+        if self.config.lock_dominant_swapchain {
+            match &self.locked_swapchain {
+                Some(locked) if *locked != row.swapchain => return true,
+                Some(_) => {}
+                None => {
+                    let count = self
+                        .swapchain_frames
+                        .entry(row.swapchain.clone())
+                        .or_default();
+                    *count += 1;
+                    if *count >= DOMINANT_SWAPCHAIN_FRAMES {
+                        log::info!("采集规则: 锁定交换链 {}", row.swapchain);
+                        self.locked_swapchain = Some(row.swapchain.clone());
+                    }
+                }
+            }
+        }
+
+        if !self.presenting {
+            self.presenting = true;
+            let pid = host.on_presenting();
+            self.launch.set_process(pid);
+        }
+
+        let FrameRow {
+            frametime,
+            cpu_busy,
+            gpu_busy,
+            timestamp_ms,
+            qpc,
+            ..
+        } = row;
+        self.launch.on_frame(frametime);
+        if !self.logged_timeline {
+            log::info!(
+                "帧时间线: {}",
+                if qpc.is_some() {
+                    "PresentMon QPC"
+                } else {
+                    "stdout 到达时刻"
+                }
+            );
+            self.logged_timeline = true;
+        }
+        let frame_at = qpc
+            .map(|q| self.timeline.instant_at(q))
+            .unwrap_or_else(Instant::now);
+        let timestamp_ms = timestamp_ms.or_else(|| qpc.map(|q| self.timeline.millis(q)));
+        host.on_row(timestamp_ms, &row);
+        // 第一个快照窗口从第一帧开始计
+        if self.first_snapshot && self.window.is_empty() {
+            self.window_start = Instant::now();
+        }
+        self.window.push(frametime);
+        self.window_present.push(&row);
+        self.window_latency.push(&row);
+        self.smoother.push(frametime);
+        self.clock.on_frame(timestamp_ms, frametime);
+        let sink = &self.sink;
+        self.pending_stutters.retain_mut(|event| {
+            event.after_ms.push(frametime);
+            if event.after_ms.len() < STUTTER_CONTEXT_FRAMES {
+                return true;
+            }
+            sink.emit("fps-stutter", &*event);
+            false
+        });
+        if let Some(median) = self.stutter.push(frametime) {
+            self.stutter_times.push(frame_at);
+            let at_secs = (self.clock.active_secs() * 10.0).round() / 10.0;
+            if self.clock.active_secs() >= warmup_secs {
+                self.stutter_count += 1;
+            }
+            let event = FpsStutter {
+                session_id: self.config.session_id.clone(),
+                process_name: self.config.process_name.clone(),
+                unix_ms: chrono::Utc::now().timestamp_millis() as f64,
+                at_secs,
+                frametime_ms: (frametime * 100.0).round() / 100.0,
+                median_ms: (median * 100.0).round() / 100.0,
+                ratio: (frametime / median * 10.0).round() / 10.0,
+                before_ms: self.stutter.recent(STUTTER_CONTEXT_FRAMES + 1),
+                after_ms: Vec::new(),
+                count: self.stutter_count,
+            };
+            if self.config.stutter_hooks && self.stutter.hook_ready() {
+                host.on_stutter_hook(&event);
+            }
+            self.pending_stutters.push(event);
+        }
+
+        // 定时基准测试：采满时长后自动停止，之后的帧不计入
+        if let Some(end) = self.config.benchmark_end {
+            let active = self.clock.active_secs();
+            if active >= end {
+                self.benchmark_completed = true;
+                host.on_benchmark_complete();
+                return false;
+            }
+            let (stage, remaining) = if active < warmup_secs {
+                (BenchmarkStage::Warmup, warmup_secs - active)
+            } else {
+                (BenchmarkStage::Capture, end - active)
+            };
+            let current = (stage, remaining.ceil() as u64);
+            if self.countdown != Some(current) {
+                self.countdown = Some(current);
+                self.sink.emit(
+                    "benchmark-countdown",
+                    &BenchmarkCountdown {
+                        session_id: self.config.session_id.clone(),
+                        stage,
+                        remaining_secs: current.1,
+                    },
+                );
+            }
+        }
+
+        // 预热期间的帧不计入 session 统计
+        if self.clock.active_secs() >= warmup_secs {
+            self.present_stats.push(&row);
+            self.latency_stats.push(&row);
+            host.on_counted_frame(self.clock.active_secs(), frametime);
+        }
+
+        // 按档位间隔推送快照
+        let interval = if self.first_snapshot {
+            FIRST_SNAPSHOT_SECS
+        } else {
+            self.config.snapshot_interval
+        };
+        if self.window_start.elapsed().as_secs_f64() >= interval {
+            if !self.window.is_empty() {
+                let mut snapshot = self.snapshot(host.smoothing(), cpu_busy, gpu_busy);
+                host.decorate(&mut snapshot);
+                self.sink.emit("fps-update", &snapshot);
+                self.first_snapshot = false;
+            }
+            self.clear_window();
+            self.window_start = Instant::now();
+        }
+        true
+    }
+
+    fn snapshot(&self, mode: FpsSmoothing, cpu_busy: f64, gpu_busy: f64) -> FpsSnapshot {
+        let window = &self.window;
+        let avg_frametime = smoothing::average(window);
+        let raw_fps = 1000.0 / avg_frametime;
+        let fps = 1000.0 / self.smoother.frametime(mode, window);
+        let fps_1_low = percentile_low_fps(window, 1.0);
+        let fps_01_low = percentile_low_fps(window, 0.1);
+        let elapsed = self.clock.active_secs();
+
+        FpsSnapshot {
+            fps: (fps * 10.0).round() / 10.0,
+            raw_fps: (raw_fps * 10.0).round() / 10.0,
+            smoothing: mode,
+            fps_1_low: (fps_1_low * 10.0).round() / 10.0,
+            fps_01_low: (fps_01_low * 10.0).round() / 10.0,
+            frametime_ms: (avg_frametime * 100.0).round() / 100.0,
+            cpu_busy_ms: (cpu_busy * 100.0).round() / 100.0,
+            gpu_busy_ms: (gpu_busy * 100.0).round() / 100.0,
+            process_name: self.config.process_name.clone(),
+            session_id: self.config.session_id.clone(),
+            elapsed_secs: (elapsed * 10.0).round() / 10.0,
+            sensors: SensorReadings::default(),
+            adapters: Vec::new(),
+            plugin_metrics: BTreeMap::new(),
+            histogram: FrametimeHistogram::from_frames(window),
+            percentiles: PercentileMetrics::compute(
+                &self.config.frametime_percentiles,
+                &self.config.low_percentiles,
+                window,
+            ),
+            present: self.window_present.summary(),
+            latency: self.window_latency.summary(),
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+
+/// 直方图精度 (ms)
+const BIN_MS: f64 = 0.01;
+/// 直方图覆盖的帧时间上限 (ms)，更长的帧单独记录原值
+const MAX_BINNED_MS: f64 = 500.0;
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameStatsReport {
+    pub total_frames: u64,
+    /// 帧时间累计 (秒)
+    pub duration_secs: f64,
+    pub avg_fps: f64,
+    pub fps_1_low: f64,
+    pub fps_01_low: f64,
+    pub min_fps: f64,
+    pub max_fps: f64,
+}
+
+// ==================== 流式统计 ====================
+
+/// 逐块累计的帧时间统计，内存占用与帧数无关
+/// Low FPS 与 `percentile_low_fps` 口径相同（最差 N% 帧的平均帧时间），
+/// 按 0.01 ms 直方图计算，误差不超过一个区间
+#[derive(Clone, Default)]
+pub struct FrameStats {
+    count: u64,
+    sum: f64,
+    /// 与均值之差的平方和（Welford），用于标准差
+    m2: f64,
+    min: f64,
+    max: f64,
+    /// 长于 16.7 / 33.3 ms（低于 60 / 30 FPS）的帧数
+    over_16ms: u64,
+    over_33ms: u64,
+    /// 各区间的帧数与帧时间之和，首次使用时分配
+    bin_counts: Vec<u64>,
+    bin_sums: Vec<f64>,
+    /// 超出直方图范围的长帧（卡顿、加载），数量很少，保存原值
+    long_frames: Vec<f64>,
+}
+
+impl FrameStats {
+    pub fn push(&mut self, frametime_ms: f64) {
+        if !(frametime_ms > 0.0 && frametime_ms.is_finite()) {
+            return;
+        }
+        if self.count == 0 {
+            self.min = frametime_ms;
+            self.max = frametime_ms;
+        } else {
+            self.min = self.min.min(frametime_ms);
+            self.max = self.max.max(frametime_ms);
+        }
+        let prev_mean = if self.count > 0 {
+            self.sum / self.count as f64
+        } else {
+            0.0
+        };
+        self.count += 1;
+        self.sum += frametime_ms;
+        self.m2 += (frametime_ms - prev_mean) * (frametime_ms - self.sum / self.count as f64);
+        if frametime_ms > 1000.0 / 60.0 {
+            self.over_16ms += 1;
+        }
+        if frametime_ms > 1000.0 / 30.0 {
+            self.over_33ms += 1;
+        }
+
+        if frametime_ms >= MAX_BINNED_MS {
+            self.long_frames.push(frametime_ms);
+            return;
+        }
+        if self.bin_counts.is_empty() {
+            let bins = (MAX_BINNED_MS / BIN_MS) as usize;
+            self.bin_counts = vec![0; bins];
+            self.bin_sums = vec![0.0; bins];
+        }
+        let bin = ((frametime_ms / BIN_MS) as usize).min(self.bin_counts.len() - 1);
+        self.bin_counts[bin] += 1;
+        self.bin_sums[bin] += frametime_ms;
+    }
+
+    pub fn extend(&mut self, frame_times: &[f64]) {
+        for &ft in frame_times {
+            self.push(ft);
+        }
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// 帧时间累计 (ms)
+    pub fn total_ms(&self) -> f64 {
+        self.sum
+    }
+
+    pub fn mean_ms(&self) -> f64 {
+        if self.count > 0 {
+            self.sum / self.count as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// 帧时间总体标准差 (ms)
+    pub fn stdev_ms(&self) -> f64 {
+        if self.count > 0 {
+            (self.m2 / self.count as f64).sqrt()
+        } else {
+            0.0
+        }
+    }
+
+    pub fn over_16ms(&self) -> u64 {
+        self.over_16ms
+    }
+
+    pub fn over_33ms(&self) -> u64 {
+        self.over_33ms
+    }
+
+    /// 帧时间百分位 (ms)，与 `frametime_percentile` 的取秩方式相同，取所在区间的平均帧时间
+    pub fn frametime_percentile(&self, percentile: f64) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        let rank = ((percentile * self.count as f64 / 100.0).ceil() as u64).clamp(1, self.count);
+        let mut seen = 0u64;
+        for (c, s) in self.bin_counts.iter().zip(&self.bin_sums) {
+            seen += c;
+            if seen >= rank {
+                return s / *c as f64;
+            }
+        }
+        let mut long = self.long_frames.clone();
+        long.sort_by(f64::total_cmp);
+        long.get((rank - seen - 1) as usize)
+            .copied()
+            .unwrap_or(self.max)
+    }
+
+    /// 最差 `percentile`% 帧的平均 FPS
+    pub fn low_fps(&self, percentile: f64) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        let wanted =
+            (((percentile / 100.0) * self.count as f64).ceil() as u64).clamp(1, self.count);
+
+        let mut long = self.long_frames.clone();
+        long.sort_by(|a, b| b.total_cmp(a));
+        let mut taken = 0u64;
+        let mut sum = 0.0;
+        for ft in long.iter().take(wanted as usize) {
+            taken += 1;
+            sum += ft;
+        }
+        for (c, s) in self.bin_counts.iter().zip(&self.bin_sums).rev() {
+            if taken >= wanted {
+                break;
+            }
+            if *c == 0 {
+                continue;
+            }
+            let take = (*c).min(wanted - taken);
+            // 区间内取平均值
+            sum += s / *c as f64 * take as f64;
+            taken += take;
+        }
+        let avg_worst = sum / taken as f64;
+        if avg_worst > 0.0 {
+            1000.0 / avg_worst
+        } else {
+            0.0
+        }
+    }
+
+    pub fn report(&self) -> FrameStatsReport {
+        let round = |v: f64| (v * 10.0).round() / 10.0;
+        let fps = |ft: f64| if ft > 0.0 { 1000.0 / ft } else { 0.0 };
+        FrameStatsReport {
+            total_frames: self.count,
+            duration_secs: round(self.sum / 1000.0),
+            avg_fps: round(if self.count > 0 {
+                fps(self.sum / self.count as f64)
+            } else {
+                0.0
+            }),
+            fps_1_low: round(self.low_fps(1.0)),
+            fps_01_low: round(self.low_fps(0.1)),
+            min_fps: round(fps(self.max)),
+            max_fps: round(fps(self.min)),
+        }
+    }
+}
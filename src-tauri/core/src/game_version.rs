@@ -0,0 +1,229 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+// ==================== 数据结构 ====================
+
+/// 游戏可执行文件版本信息，用于区分游戏更新与硬件造成的性能变化
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GameVersion {
+    /// 文件版本 (e.g., "1.0.2.4567")
+    pub file_version: Option<String>,
+    /// 产品版本
+    pub product_version: Option<String>,
+    /// 数字签名时间戳 (Unix 秒)，未签名时为 None
+    pub signed_at: Option<i64>,
+    /// 文件修改时间 (Unix 秒)，无签名时可作为版本参考
+    pub modified_at: Option<i64>,
+}
+
+fn modified_at(path: &Path) -> Option<i64> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    let secs = modified
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_secs();
+    Some(secs as i64)
+}
+
+// ==================== 版本资源 (Windows) ====================
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use windows::core::{w, HSTRING, PCSTR};
+    use windows::Win32::Foundation::FILETIME;
+    use windows::Win32::Security::Cryptography::{
+        szOID_RSA_signingTime, CertCloseStore, CryptDecodeObject, CryptMsgClose, CryptMsgGetParam,
+        CryptMsgOpenToDecode, CryptMsgUpdate, CryptQueryObject,
+        CERT_QUERY_CONTENT_FLAG_PKCS7_SIGNED_EMBED, CERT_QUERY_ENCODING_TYPE,
+        CERT_QUERY_FORMAT_FLAG_BINARY, CERT_QUERY_OBJECT_FILE, CMSG_CONTENT_PARAM,
+        CMSG_SIGNER_INFO, CMSG_SIGNER_INFO_PARAM, CRYPT_ATTRIBUTES, CRYPT_TIMESTAMP_INFO,
+        HCERTSTORE, HCRYPTPROV_LEGACY, PKCS7_SIGNER_INFO, PKCS_7_ASN_ENCODING, TIMESTAMP_INFO,
+        X509_ASN_ENCODING,
+    };
+    use windows::Win32::Storage::FileSystem::{
+        GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW, VS_FIXEDFILEINFO,
+    };
+
+    const OID_COUNTER_SIGN: &str = "1.2.840.113549.1.9.6";
+    const OID_RFC3161_COUNTER_SIGN: &str = "1.3.6.1.4.1.311.3.3.1";
+    const OID_SIGNING_TIME: &str = "1.2.840.113549.1.9.5";
+
+    fn encoding() -> CERT_QUERY_ENCODING_TYPE {
+        CERT_QUERY_ENCODING_TYPE(X509_ASN_ENCODING.0 | PKCS_7_ASN_ENCODING.0)
+    }
+
+    fn format_version(ms: u32, ls: u32) -> String {
+        format!("{}.{}.{}.{}", ms >> 16, ms & 0xffff, ls >> 16, ls & 0xffff)
+    }
+
+    /// 读取 VS_FIXEDFILEINFO，返回 (文件版本, 产品版本)
+    pub fn read_version(path: &str) -> Option<(String, String)> {
+        let name = HSTRING::from(path);
+        unsafe {
+            let size = GetFileVersionInfoSizeW(&name, None);
+            if size == 0 {
+                return None;
+            }
+            let mut data = vec![0u8; size as usize];
+            GetFileVersionInfoW(&name, 0, size, data.as_mut_ptr() as _).ok()?;
+
+            let mut info: *mut core::ffi::c_void = std::ptr::null_mut();
+            let mut len = 0u32;
+            if !VerQueryValueW(data.as_ptr() as _, w!("\\"), &mut info, &mut len).as_bool()
+                || info.is_null()
+                || (len as usize) < std::mem::size_of::<VS_FIXEDFILEINFO>()
+            {
+                return None;
+            }
+            let info = &*(info as *const VS_FIXEDFILEINFO);
+            Some((
+                format_version(info.dwFileVersionMS, info.dwFileVersionLS),
+                format_version(info.dwProductVersionMS, info.dwProductVersionLS),
+            ))
+        }
+    }
+
+    /// 解码 ASN.1 结构，返回 8 字节对齐的缓冲区
+    unsafe fn decode(struct_type: PCSTR, encoded: &[u8]) -> Option<Vec<u64>> {
+        let mut size = 0u32;
+        CryptDecodeObject(encoding(), struct_type, encoded, 0, None, &mut size).ok()?;
+        let mut buffer = vec![0u64; (size as usize).div_ceil(8)];
+        CryptDecodeObject(
+            encoding(),
+            struct_type,
+            encoded,
+            0,
+            Some(buffer.as_mut_ptr() as _),
+            &mut size,
+        )
+        .ok()?;
+        Some(buffer)
+    }
+
+    /// 读取消息参数，返回 (8 字节对齐的缓冲区, 实际字节数)
+    unsafe fn msg_param(msg: *const core::ffi::c_void, param: u32) -> Option<(Vec<u64>, u32)> {
+        let mut size = 0u32;
+        CryptMsgGetParam(msg, param, 0, None, &mut size).ok()?;
+        let mut buffer = vec![0u64; (size as usize).div_ceil(8)];
+        CryptMsgGetParam(msg, param, 0, Some(buffer.as_mut_ptr() as _), &mut size).ok()?;
+        Some((buffer, size))
+    }
+
+    unsafe fn blob_slice<'a>(data: *const u8, len: u32) -> &'a [u8] {
+        if data.is_null() {
+            &[]
+        } else {
+            std::slice::from_raw_parts(data, len as usize)
+        }
+    }
+
+    /// 遍历属性，返回第一个匹配 OID 的值
+    unsafe fn find_attr<'a>(attrs: &CRYPT_ATTRIBUTES, oid: &str) -> Option<&'a [u8]> {
+        for i in 0..attrs.cAttr as usize {
+            let attr = &*attrs.rgAttr.add(i);
+            if attr.pszObjId.to_string().ok().as_deref() == Some(oid) && attr.cValue > 0 {
+                let value = &*attr.rgValue;
+                return Some(blob_slice(value.pbData, value.cbData));
+            }
+        }
+        None
+    }
+
+    fn filetime_to_unix(ft: &FILETIME) -> i64 {
+        let ticks = ((ft.dwHighDateTime as u64) << 32) | ft.dwLowDateTime as u64;
+        (ticks / 10_000_000) as i64 - 11_644_473_600
+    }
+
+    /// 旧式 Authenticode 副署名: 签名时间在副署者的认证属性中
+    unsafe fn legacy_timestamp(encoded: &[u8]) -> Option<i64> {
+        let buffer = decode(PKCS7_SIGNER_INFO, encoded)?;
+        let counter_signer = &*(buffer.as_ptr() as *const CMSG_SIGNER_INFO);
+        let time = find_attr(&counter_signer.AuthAttrs, OID_SIGNING_TIME)?;
+        let ft = decode(szOID_RSA_signingTime, time)?;
+        Some(filetime_to_unix(&*(ft.as_ptr() as *const FILETIME)))
+    }
+
+    /// RFC 3161 时间戳: 属性值为内嵌的 PKCS#7，内容为 TSTInfo
+    unsafe fn rfc3161_timestamp(encoded: &[u8]) -> Option<i64> {
+        let msg = CryptMsgOpenToDecode(encoding().0, 0, 0, HCRYPTPROV_LEGACY(0), None, None);
+        if msg.is_null() {
+            return None;
+        }
+        let result = (|| {
+            CryptMsgUpdate(msg, Some(encoded), true).ok()?;
+            let (content, size) = msg_param(msg, CMSG_CONTENT_PARAM)?;
+            let content = blob_slice(content.as_ptr() as *const u8, size);
+            let info = decode(TIMESTAMP_INFO, content)?;
+            let info = &*(info.as_ptr() as *const CRYPT_TIMESTAMP_INFO);
+            Some(filetime_to_unix(&info.ftTime))
+        })();
+        let _ = CryptMsgClose(Some(msg));
+        result
+    }
+
+    /// 读取 Authenticode 签名时间戳
+    pub fn read_signature_timestamp(path: &str) -> Option<i64> {
+        let wide = HSTRING::from(path);
+        let mut store = HCERTSTORE::default();
+        let mut msg: *mut core::ffi::c_void = std::ptr::null_mut();
+
+        unsafe {
+            CryptQueryObject(
+                CERT_QUERY_OBJECT_FILE,
+                wide.as_ptr() as _,
+                CERT_QUERY_CONTENT_FLAG_PKCS7_SIGNED_EMBED,
+                CERT_QUERY_FORMAT_FLAG_BINARY,
+                0,
+                None,
+                None,
+                None,
+                Some(&mut store),
+                Some(&mut msg),
+                None,
+            )
+            .ok()?;
+
+            let timestamp = msg_param(msg, CMSG_SIGNER_INFO_PARAM).and_then(|(buffer, _)| {
+                let signer = &*(buffer.as_ptr() as *const CMSG_SIGNER_INFO);
+                if let Some(value) = find_attr(&signer.UnauthAttrs, OID_RFC3161_COUNTER_SIGN) {
+                    rfc3161_timestamp(value)
+                } else {
+                    find_attr(&signer.UnauthAttrs, OID_COUNTER_SIGN)
+                        .and_then(|v| legacy_timestamp(v))
+                }
+            });
+
+            let _ = CryptMsgClose(Some(msg));
+            let _ = CertCloseStore(store, 0);
+            timestamp
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    pub fn read_version(_path: &str) -> Option<(String, String)> {
+        None
+    }
+
+    pub fn read_signature_timestamp(_path: &str) -> Option<i64> {
+        None
+    }
+}
+
+// ==================== 入口 ====================
+
+/// 读取可执行文件的版本号与签名时间
+pub fn read_game_version(exe_path: &str) -> GameVersion {
+    let (file_version, product_version) = match platform::read_version(exe_path) {
+        Some((file, product)) => (Some(file), Some(product)),
+        None => (None, None),
+    };
+
+    GameVersion {
+        file_version,
+        product_version,
+        signed_at: platform::read_signature_timestamp(exe_path),
+        modified_at: modified_at(Path::new(exe_path)),
+    }
+}
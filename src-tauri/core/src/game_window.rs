@@ -0,0 +1,71 @@
+// ==================== 游戏窗口查找 (Windows) ====================
+
+#[cfg(target_os = "windows")]
+use windows::Win32::Foundation::HWND;
+
+#[cfg(target_os = "windows")]
+struct SearchState {
+    pid: u32,
+    best: Option<(HWND, i64)>,
+}
+
+/// 查找进程面积最大的可见顶层窗口，视为游戏主窗口
+#[cfg(target_os = "windows")]
+pub fn find_main_window(pid: u32) -> Option<HWND> {
+    use windows::Win32::Foundation::{BOOL, LPARAM, RECT};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        EnumWindows, GetWindowRect, GetWindowThreadProcessId, IsWindowVisible,
+    };
+
+    unsafe extern "system" fn callback(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let state = &mut *(lparam.0 as *mut SearchState);
+        let mut owner = 0u32;
+        GetWindowThreadProcessId(hwnd, Some(&mut owner));
+        if owner == state.pid && IsWindowVisible(hwnd).as_bool() {
+            let mut rect = RECT::default();
+            if GetWindowRect(hwnd, &mut rect).is_ok() {
+                let area = (rect.right - rect.left) as i64 * (rect.bottom - rect.top) as i64;
+                if area > 0 && state.best.is_none_or(|(_, best)| area > best) {
+                    state.best = Some((hwnd, area));
+                }
+            }
+        }
+        BOOL(1)
+    }
+
+    let mut state = SearchState { pid, best: None };
+    unsafe {
+        let _ = EnumWindows(
+            Some(callback),
+            LPARAM(&mut state as *mut SearchState as isize),
+        );
+    }
+    state.best.map(|(hwnd, _)| hwnd)
+}
+
+/// 游戏主窗口标题
+#[cfg(target_os = "windows")]
+pub fn main_window_title(pid: u32) -> Option<String> {
+    use windows::Win32::UI::WindowsAndMessaging::GetWindowTextW;
+
+    let hwnd = find_main_window(pid)?;
+    let mut buffer = [0u16; 512];
+    let len = unsafe { GetWindowTextW(hwnd, &mut buffer) } as usize;
+    (len > 0).then(|| String::from_utf16_lossy(&buffer[..len]))
+}
+
+/// 游戏主窗口句柄（序列化为整数供前端与采集校验使用）
+#[cfg(target_os = "windows")]
+pub fn main_window_handle(pid: u32) -> Option<u64> {
+    find_main_window(pid).map(|hwnd| hwnd.0 as u64)
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn main_window_title(_pid: u32) -> Option<String> {
+    None
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn main_window_handle(_pid: u32) -> Option<u64> {
+    None
+}
@@ -0,0 +1,157 @@
+use crate::gpu_routing::GpuAdapter;
+use serde::{Deserialize, Serialize};
+
+/// ASUS ATK ACPI 设备号：GPU MUX 开关
+const ASUS_DEVID_GPU_MUX: u32 = 0x0009_0016;
+/// ASUS DSTS 返回值中表示"设备存在"的标志位
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+const ASUS_DSTS_PRESENT: u32 = 0x0001_0000;
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MuxMode {
+    /// 混合输出：内屏接在核显上，独显画面需经核显复制（Optimus / 标准模式）
+    Hybrid,
+    /// 独显直连：MUX 切换到独显，核显已在固件中禁用
+    DiscreteOnly,
+    /// 动态直连：核显仍在，但内屏当前由独显直接驱动（Advanced Optimus / Dynamic Switch）
+    DynamicDirect,
+    /// 非混合显卡笔记本（台式机 / 单显卡）
+    NotApplicable,
+    #[default]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MuxSource {
+    /// 厂商 WMI 接口（ASUS ATK）
+    Vendor,
+    /// 根据显卡拓扑推断（内屏所连接的 GPU）
+    #[default]
+    Topology,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MuxStatus {
+    pub mode: MuxMode,
+    pub source: MuxSource,
+    /// 内屏当前所连接的 GPU
+    pub panel_gpu: Option<String>,
+    /// 判断依据
+    pub detail: String,
+}
+
+// ==================== 厂商接口 (Windows) ====================
+
+/// 调用 ASUS ATK WMI 的 DSTS 方法读取设备状态
+/// 返回值低 16 位为设备值；机型不支持该设备时返回 None
+#[cfg(target_os = "windows")]
+pub fn asus_dsts(device_id: u32) -> Option<u32> {
+    use wmi::{COMLibrary, WMIConnection};
+
+    #[derive(Deserialize)]
+    #[serde(rename = "AsusAtkWmi_WMNB")]
+    struct AsusAtkWmi {
+        #[serde(rename = "__Path")]
+        path: String,
+    }
+
+    #[derive(Serialize)]
+    struct DstsInput {
+        #[serde(rename = "Device_ID")]
+        device_id: u32,
+    }
+
+    #[derive(Deserialize)]
+    struct DstsOutput {
+        device_status: u32,
+    }
+
+    let com = COMLibrary::new().ok()?;
+    let wmi = WMIConnection::with_namespace_path("ROOT\\WMI", com).ok()?;
+    let instance = wmi.query::<AsusAtkWmi>().ok()?.into_iter().next()?;
+    let output: DstsOutput = wmi
+        .exec_instance_method::<AsusAtkWmi, _, _>("DSTS", &instance.path, DstsInput { device_id })
+        .ok()?;
+
+    if output.device_status & ASUS_DSTS_PRESENT == 0 {
+        return None;
+    }
+    Some(output.device_status & 0xffff)
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn asus_dsts(_device_id: u32) -> Option<u32> {
+    None
+}
+
+/// ASUS MUX 状态：0 = 独显直连（Ultimate），1 = 混合输出
+fn asus_mux_discrete() -> Option<bool> {
+    asus_dsts(ASUS_DEVID_GPU_MUX).map(|value| value == 0)
+}
+
+// ==================== 状态判断 ====================
+
+/// 判断笔记本 MUX 状态
+/// 优先读取厂商接口；不支持时根据内屏所连接的 GPU 与核显是否可见推断
+pub fn detect_mux(adapters: &[GpuAdapter]) -> MuxStatus {
+    let Some(panel_luid) = crate::display::internal_panel_luid() else {
+        return MuxStatus {
+            mode: MuxMode::NotApplicable,
+            detail: "未检测到笔记本内屏".to_string(),
+            ..Default::default()
+        };
+    };
+    let panel = adapters.iter().find(|a| a.luid == panel_luid);
+    let has_integrated = adapters.iter().any(|a| a.integrated);
+    let has_discrete = adapters.iter().any(|a| !a.integrated);
+    let panel_gpu = panel.map(|a| a.name.clone());
+
+    if let Some(discrete) = asus_mux_discrete() {
+        let mode = match (discrete, has_integrated) {
+            (false, _) => MuxMode::Hybrid,
+            (true, true) => MuxMode::DynamicDirect,
+            (true, false) => MuxMode::DiscreteOnly,
+        };
+        return MuxStatus {
+            mode,
+            source: MuxSource::Vendor,
+            panel_gpu,
+            detail: format!(
+                "ASUS GPU MUX：{}",
+                if discrete {
+                    "独显直连"
+                } else {
+                    "混合输出"
+                }
+            ),
+        };
+    }
+
+    let (mode, detail) = match panel {
+        None => (MuxMode::Unknown, "无法确定内屏所连接的 GPU".to_string()),
+        Some(p) if p.integrated && has_discrete => (
+            MuxMode::Hybrid,
+            format!("内屏连接在核显 {}，独显画面需经核显输出", p.name),
+        ),
+        Some(p) if p.integrated => (MuxMode::NotApplicable, format!("仅有核显 {}", p.name)),
+        Some(p) if has_integrated => (
+            MuxMode::DynamicDirect,
+            format!("核显仍在运行，内屏当前由独显 {} 直接驱动", p.name),
+        ),
+        Some(p) => (
+            MuxMode::DiscreteOnly,
+            format!("未发现核显，内屏由独显 {} 直接驱动", p.name),
+        ),
+    };
+
+    MuxStatus {
+        mode,
+        source: MuxSource::Topology,
+        panel_gpu,
+        detail,
+    }
+}
@@ -0,0 +1,314 @@
+use crate::display::{self, DisplayTarget};
+use crate::gpu_mux::{self, MuxStatus};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// 采样间隔（游戏刚启动时可能还在加载，不立即采样）
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+/// 独显显存下限，低于此值视为核显
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+const DISCRETE_VRAM_MIN_BYTES: u64 = 512 * 1024 * 1024;
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuAdapter {
+    pub name: String,
+    /// 适配器 LUID（与 GPU 性能计数器中的 luid 对应，重启后会变化）
+    pub luid: String,
+    /// 设备实例 ID（PNPDeviceID，重启后保持不变）
+    #[serde(default)]
+    pub device_id: Option<String>,
+    /// 是否为核显
+    pub integrated: bool,
+    /// 是否连接了显示器
+    pub has_outputs: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompositionPath {
+    /// 渲染 GPU 直接输出到显示器
+    Direct,
+    /// 独显渲染，经核显复制后输出（Optimus 等混合输出模式）
+    HybridCopy,
+    /// 游戏运行在核显上（系统存在独显）
+    IntegratedOnly,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuRouting {
+    /// 是否为混合显卡系统（核显 + 独显）
+    pub hybrid: bool,
+    /// 游戏实际渲染使用的 GPU
+    pub render_gpu: Option<String>,
+    /// 渲染 GPU 的设备实例 ID（与 GpuInfo.device_id 对应）
+    #[serde(default)]
+    pub render_gpu_id: Option<String>,
+    /// 连接显示器的 GPU
+    pub display_gpu: Option<String>,
+    pub path: CompositionPath,
+    /// 游戏窗口所在显示器（内屏 / 外接）
+    #[serde(default)]
+    pub display: Option<DisplayTarget>,
+    /// 笔记本 MUX 开关状态
+    #[serde(default)]
+    pub mux: Option<MuxStatus>,
+    pub adapters: Vec<GpuAdapter>,
+}
+
+// ==================== 适配器与渲染 GPU (Windows) ====================
+
+#[cfg(target_os = "windows")]
+pub(crate) fn list_adapters() -> Vec<GpuAdapter> {
+    use windows::Win32::Graphics::Dxgi::{
+        CreateDXGIFactory1, IDXGIFactory1, DXGI_ADAPTER_FLAG_SOFTWARE,
+    };
+
+    let devices = display_instance_ids();
+    let mut adapters = Vec::new();
+    unsafe {
+        let Ok(factory) = CreateDXGIFactory1::<IDXGIFactory1>() else {
+            return adapters;
+        };
+        let mut index = 0;
+        while let Ok(adapter) = factory.EnumAdapters1(index) {
+            index += 1;
+            let Ok(desc) = adapter.GetDesc1() else {
+                continue;
+            };
+            // 跳过 Microsoft Basic Render Driver
+            if desc.Flags & DXGI_ADAPTER_FLAG_SOFTWARE.0 as u32 != 0 {
+                continue;
+            }
+            let len = desc
+                .Description
+                .iter()
+                .position(|&c| c == 0)
+                .unwrap_or(desc.Description.len());
+            // 两块同型号显卡硬件 ID 相同，按枚举顺序依次分配未使用的设备实例
+            let hardware_id =
+                format_hardware_id(desc.VendorId, desc.DeviceId, desc.SubSysId, desc.Revision);
+            let device_id = devices
+                .iter()
+                .find(|pnp| {
+                    pnp.starts_with(&hardware_id)
+                        && !adapters
+                            .iter()
+                            .any(|a: &GpuAdapter| a.device_id.as_ref() == Some(*pnp))
+                })
+                .cloned();
+            adapters.push(GpuAdapter {
+                name: String::from_utf16_lossy(&desc.Description[..len]),
+                luid: format_luid(desc.AdapterLuid.HighPart as u32, desc.AdapterLuid.LowPart),
+                device_id,
+                integrated: (desc.DedicatedVideoMemory as u64) < DISCRETE_VRAM_MIN_BYTES,
+                has_outputs: adapter.EnumOutputs(0).is_ok(),
+            });
+        }
+    }
+    adapters
+}
+
+/// 显示适配器的设备实例 ID（通过 SetupAPI 读取，不依赖 WMI）
+#[cfg(target_os = "windows")]
+pub(crate) fn display_instance_ids() -> Vec<String> {
+    use windows::core::PCWSTR;
+    use windows::Win32::Devices::DeviceAndDriverInstallation::{
+        SetupDiDestroyDeviceInfoList, SetupDiEnumDeviceInfo, SetupDiGetClassDevsW,
+        SetupDiGetDeviceInstanceIdW, DIGCF_PRESENT, GUID_DEVCLASS_DISPLAY, SP_DEVINFO_DATA,
+    };
+    use windows::Win32::Foundation::HWND;
+
+    let mut ids = Vec::new();
+    unsafe {
+        let Ok(set) = SetupDiGetClassDevsW(
+            Some(&GUID_DEVCLASS_DISPLAY),
+            PCWSTR::null(),
+            HWND::default(),
+            DIGCF_PRESENT,
+        ) else {
+            return ids;
+        };
+        let mut index = 0;
+        loop {
+            let mut data = SP_DEVINFO_DATA {
+                cbSize: std::mem::size_of::<SP_DEVINFO_DATA>() as u32,
+                ..Default::default()
+            };
+            if SetupDiEnumDeviceInfo(set, index, &mut data).is_err() {
+                break;
+            }
+            index += 1;
+            let mut buffer = [0u16; 512];
+            if SetupDiGetDeviceInstanceIdW(set, &data, Some(&mut buffer), None).is_ok() {
+                let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+                ids.push(String::from_utf16_lossy(&buffer[..len]).to_uppercase());
+            }
+        }
+        let _ = SetupDiDestroyDeviceInfoList(set);
+    }
+    ids
+}
+
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn list_adapters() -> Vec<GpuAdapter> {
+    vec![]
+}
+
+pub(crate) fn format_luid(high: u32, low: u32) -> String {
+    format!("0x{:08x}_0x{:08x}", high, low)
+}
+
+/// PNPDeviceID 的硬件 ID 部分 (e.g., "PCI\VEN_10DE&DEV_2504&SUBSYS_397D1462&REV_A1")
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub(crate) fn format_hardware_id(vendor: u32, device: u32, subsys: u32, revision: u32) -> String {
+    format!(
+        "PCI\\VEN_{:04X}&DEV_{:04X}&SUBSYS_{:08X}&REV_{:02X}",
+        vendor, device, subsys, revision
+    )
+}
+
+/// 通过 GPU Engine 原始计数器找出进程 3D 引擎累计运行时间最长的适配器
+/// 实例名格式: pid_1234_luid_0x00000000_0x0000D1A4_phys_0_eng_0_engtype_3D
+#[cfg(target_os = "windows")]
+fn render_adapter_luid(pid: u32) -> Option<String> {
+    use crate::drives::variant_u64;
+    use std::collections::HashMap;
+    use wmi::{COMLibrary, WMIConnection};
+
+    let com = COMLibrary::new().ok()?;
+    let wmi = WMIConnection::new(com).ok()?;
+    let query = format!(
+        "SELECT Name, RunningTime FROM Win32_PerfRawData_GPUPerformanceCounters_GPUEngine \
+         WHERE Name LIKE 'pid_{}_%engtype_3D'",
+        pid
+    );
+    let results: Vec<HashMap<String, wmi::Variant>> = wmi.raw_query(&query).ok()?;
+
+    let mut by_luid: HashMap<String, u64> = HashMap::new();
+    for item in &results {
+        let Some(wmi::Variant::String(name)) = item.get("Name") else {
+            continue;
+        };
+        let Some(luid) = parse_luid(name) else {
+            continue;
+        };
+        *by_luid.entry(luid).or_default() += variant_u64(item.get("RunningTime")).unwrap_or(0);
+    }
+
+    by_luid
+        .into_iter()
+        .filter(|(_, time)| *time > 0)
+        .max_by_key(|(_, time)| *time)
+        .map(|(luid, _)| luid)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn render_adapter_luid(_pid: u32) -> Option<String> {
+    None
+}
+
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub(crate) fn parse_luid(instance: &str) -> Option<String> {
+    let rest = &instance[instance.find("luid_")? + 5..];
+    let mut parts = rest.split('_');
+    let high = u32::from_str_radix(parts.next()?.trim_start_matches("0x"), 16).ok()?;
+    let low = u32::from_str_radix(parts.next()?.trim_start_matches("0x"), 16).ok()?;
+    Some(format_luid(high, low))
+}
+
+// ==================== 路径判断 ====================
+
+/// 判断游戏画面的输出路径
+/// 优先用游戏窗口所在显示器判断输出 GPU；无法定位窗口时，
+/// 渲染 GPU 没有连接显示器、而其他 GPU 连接了显示器，视为画面需经 PCIe 复制到输出 GPU
+pub fn detect_routing(pid: u32) -> GpuRouting {
+    let adapters = list_adapters();
+    let hybrid = adapters.iter().any(|a| a.integrated) && adapters.iter().any(|a| !a.integrated);
+    let display = display::display_for_process(pid);
+
+    let render = render_adapter_luid(pid).and_then(|luid| adapters.iter().find(|a| a.luid == luid));
+    let output = display
+        .as_ref()
+        .and_then(|d| d.gpu_luid.as_ref())
+        .and_then(|luid| adapters.iter().find(|a| &a.luid == luid))
+        .or(match render {
+            Some(r) if r.has_outputs => Some(r),
+            _ => adapters.iter().find(|a| a.has_outputs),
+        });
+
+    let path = match (render, output) {
+        (None, _) => CompositionPath::Unknown,
+        (Some(r), _) if r.integrated && hybrid => CompositionPath::IntegratedOnly,
+        (Some(r), Some(o)) if r.luid == o.luid => CompositionPath::Direct,
+        (Some(_), Some(_)) => CompositionPath::HybridCopy,
+        (Some(_), None) => CompositionPath::Unknown,
+    };
+
+    GpuRouting {
+        hybrid,
+        render_gpu: render.map(|a| a.name.clone()),
+        render_gpu_id: render.and_then(|a| a.device_id.clone()),
+        display_gpu: output.map(|a| a.name.clone()),
+        path,
+        display,
+        mux: Some(gpu_mux::detect_mux(&adapters)),
+        adapters,
+    }
+}
+
+// ==================== 测试期间采样 ====================
+
+/// 在 FPS 监测期间定期检测输出路径，保留最后一次有效结果
+pub struct RoutingSampler {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<Option<GpuRouting>>>,
+}
+
+impl RoutingSampler {
+    pub fn start(pid: Option<u32>) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = stop.clone();
+
+        let handle = std::thread::spawn(move || {
+            let pid = pid?;
+            let mut latest = None;
+            let mut last = Instant::now();
+            while !stop_flag.load(Ordering::Relaxed) {
+                if last.elapsed() >= SAMPLE_INTERVAL {
+                    let routing = detect_routing(pid);
+                    if routing.path != CompositionPath::Unknown || latest.is_none() {
+                        latest = Some(routing);
+                    }
+                    last = Instant::now();
+                }
+                std::thread::sleep(Duration::from_millis(200));
+            }
+            latest
+        });
+
+        RoutingSampler {
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    /// 停止采样并返回结果（测试时长不足一个采样间隔时立即检测一次）
+    pub fn finish(mut self, pid: Option<u32>) -> Option<GpuRouting> {
+        self.stop.store(true, Ordering::Relaxed);
+        let sampled = self.handle.take()?.join().ok()?;
+        sampled.or_else(|| pid.map(detect_routing))
+    }
+}
+
+impl Drop for RoutingSampler {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
@@ -0,0 +1,858 @@
+use crate::direct_storage::{self, DirectStorageSupport};
+use crate::drives::{self, DriveHealth};
+use crate::events::EventSink;
+use crate::gpu_mux::{self, MuxStatus};
+use crate::units::UnitPrefs;
+use crate::vbs::{self, VbsStatus};
+use serde::{Deserialize, Serialize};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+use sysinfo::System;
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CpuInfo {
+    /// 型号名 (e.g., "Intel Core i5-12400")
+    pub name: String,
+    /// 物理核心数
+    pub cores: usize,
+    /// 逻辑线程数
+    pub threads: usize,
+    /// 基础频率 (GHz)
+    pub base_clock_ghz: f64,
+    /// 当前频率 (GHz)
+    pub current_clock_ghz: f64,
+    /// CPU 架构
+    pub arch: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuInfo {
+    /// 型号名 (e.g., "NVIDIA GeForce RTX 3060")
+    pub name: String,
+    /// 显存大小 (GB)
+    pub vram_gb: f64,
+    /// 驱动版本
+    pub driver_version: String,
+    /// 分辨率 (e.g., "1920x1080")
+    pub resolution: String,
+    /// 稳定设备标识（PNPDeviceID，重启后不变；无法获取时为显卡名称）
+    #[serde(default)]
+    pub device_id: String,
+    /// 适配器 LUID（仅本次开机有效，与 GPU 性能计数器对应）
+    #[serde(default)]
+    pub luid: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RamInfo {
+    /// 总内存 (GB)
+    pub total_gb: f64,
+    /// 已使用 (GB)
+    pub used_gb: f64,
+    /// 可用 (GB)
+    pub available_gb: f64,
+    /// 内存类型 (e.g., "DDR4", "DDR5")
+    pub memory_type: Option<String>,
+    /// 内存条数量
+    pub module_count: u32,
+    /// 内存条标称速率 (MHz，SMBIOS Speed)
+    pub rated_speed_mhz: Option<u32>,
+    /// 当前实际运行速率 (MHz，ConfiguredClockSpeed)
+    pub configured_speed_mhz: Option<u32>,
+    /// 运行档位
+    pub profile: MemoryProfile,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryProfile {
+    /// 运行在 JEDEC 最低默认频率（DDR4-2133 / DDR5-4800），通常意味着未开启 XMP/EXPO
+    JedecDefault,
+    /// 运行在 JEDEC 标准频率范围内
+    Jedec,
+    /// 超出 JEDEC 标准范围，已开启 XMP/EXPO 或手动超频
+    XmpExpo,
+    #[default]
+    Unknown,
+}
+
+/// 硬件探测项
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Probe {
+    Cpu,
+    Gpu,
+    Ram,
+    Os,
+    DirectStorage,
+    DriveHealth,
+    Vbs,
+    GpuMux,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HardwareInfo {
+    pub cpu: CpuInfo,
+    pub gpus: Vec<GpuInfo>,
+    pub ram: RamInfo,
+    /// OS 信息
+    pub os: String,
+    /// DirectStorage / BypassIO 支持情况
+    pub direct_storage: DirectStorageSupport,
+    /// 物理磁盘健康状况
+    pub drive_health: Vec<DriveHealth>,
+    /// VBS / HVCI 状态
+    pub vbs: VbsStatus,
+    /// 笔记本 MUX 开关状态（独显直连 / 混合输出）
+    #[serde(default)]
+    pub gpu_mux: MuxStatus,
+    /// 尚未完成的探测项（完成后通过 `hardware-updated` 事件推送完整结果）
+    #[serde(default)]
+    pub pending: Vec<Probe>,
+    /// 失败或超时的探测项
+    #[serde(default)]
+    pub degraded: Vec<DegradedProbe>,
+}
+
+/// 降级的探测项：失败原因与建议
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DegradedProbe {
+    pub probe: Probe,
+    pub reason: String,
+    pub suggestion: String,
+}
+
+// ==================== CPU 检测 ====================
+
+pub fn detect_cpu_info() -> CpuInfo {
+    let mut sys = System::new_all();
+    sys.refresh_cpu_all();
+
+    let cpus = sys.cpus();
+    let name = if !cpus.is_empty() {
+        cpus[0].brand().to_string()
+    } else {
+        "Unknown CPU".to_string()
+    };
+
+    let threads = cpus.len();
+    let cores = sys.physical_core_count().unwrap_or(threads / 2);
+
+    // 频率 (MHz → GHz)
+    let current_mhz = if !cpus.is_empty() {
+        cpus[0].frequency() as f64
+    } else {
+        0.0
+    };
+
+    CpuInfo {
+        name: clean_cpu_name(&name),
+        cores,
+        threads,
+        base_clock_ghz: current_mhz / 1000.0,
+        current_clock_ghz: current_mhz / 1000.0,
+        arch: std::env::consts::ARCH.to_string(),
+    }
+}
+
+/// 清理 CPU 名称中的多余空格和频率后缀
+fn clean_cpu_name(raw: &str) -> String {
+    let name = raw
+        .replace("(R)", "")
+        .replace("(TM)", "")
+        .replace("  ", " ")
+        .trim()
+        .to_string();
+
+    // 去除尾部频率 "@ 3.60GHz" 等
+    if let Some(idx) = name.find(" @ ") {
+        name[..idx].trim().to_string()
+    } else {
+        name
+    }
+}
+
+// ==================== GPU 检测 (Windows) ====================
+
+/// `allow_powershell_fallback` 对应设置中的 PowerShell 备用检测开关
+#[cfg(target_os = "windows")]
+pub fn detect_gpu_info(allow_powershell_fallback: bool) -> Vec<GpuInfo> {
+    log::info!("开始 GPU 检测...");
+
+    // 方案1: WMI 查询
+    match detect_gpu_wmi() {
+        Ok(gpus) if !gpus.is_empty() => {
+            log::info!("WMI 检测到 {} 个 GPU", gpus.len());
+            return attach_luids(dedupe_gpus(gpus));
+        }
+        Ok(_) => log::warn!("WMI 返回空结果，尝试备用方案"),
+        Err(e) => log::warn!("WMI GPU 检测失败: {}, 使用备用方案", e),
+    }
+
+    // 方案2: DXGI 枚举适配器 + SetupAPI 设备实例
+    let gpus = detect_gpu_dxgi();
+    if !gpus.is_empty() {
+        log::info!("DXGI 检测到 {} 个 GPU", gpus.len());
+        return dedupe_gpus(gpus);
+    }
+    log::warn!("DXGI 返回空结果");
+
+    // 方案3: PowerShell 查询（启动慢且可能被安全软件拦截，需在设置中开启）
+    if !allow_powershell_fallback {
+        log::error!("所有 GPU 检测方案均失败（PowerShell 备用方案未开启）");
+        return vec![];
+    }
+    match detect_gpu_powershell() {
+        Ok(gpus) if !gpus.is_empty() => {
+            log::info!("PowerShell 检测到 {} 个 GPU", gpus.len());
+            return attach_luids(dedupe_gpus(gpus));
+        }
+        Ok(_) => log::warn!("PowerShell 返回空结果"),
+        Err(e) => log::warn!("PowerShell 检测失败: {}", e),
+    }
+
+    log::error!("所有 GPU 检测方案均失败");
+    vec![]
+}
+
+/// 直接通过 DXGI 枚举显卡
+/// 驱动版本取自 CheckInterfaceSupport 返回的 UMD 版本，分辨率取自第一个输出的桌面区域
+#[cfg(target_os = "windows")]
+fn detect_gpu_dxgi() -> Vec<GpuInfo> {
+    use crate::gpu_routing::{display_instance_ids, format_hardware_id, format_luid};
+    use windows::core::Interface;
+    use windows::Win32::Graphics::Dxgi::{
+        CreateDXGIFactory1, IDXGIDevice, IDXGIFactory1, DXGI_ADAPTER_FLAG_SOFTWARE,
+    };
+
+    let devices = display_instance_ids();
+    let mut gpus: Vec<GpuInfo> = Vec::new();
+    unsafe {
+        let Ok(factory) = CreateDXGIFactory1::<IDXGIFactory1>() else {
+            return gpus;
+        };
+        let mut index = 0;
+        while let Ok(adapter) = factory.EnumAdapters1(index) {
+            index += 1;
+            let Ok(desc) = adapter.GetDesc1() else {
+                continue;
+            };
+            if desc.Flags & DXGI_ADAPTER_FLAG_SOFTWARE.0 as u32 != 0 {
+                continue;
+            }
+            let len = desc
+                .Description
+                .iter()
+                .position(|&c| c == 0)
+                .unwrap_or(desc.Description.len());
+            let name = String::from_utf16_lossy(&desc.Description[..len]);
+
+            let driver_version = match adapter.CheckInterfaceSupport(&IDXGIDevice::IID) {
+                Ok(v) => format!(
+                    "{}.{}.{}.{}",
+                    (v >> 48) & 0xffff,
+                    (v >> 32) & 0xffff,
+                    (v >> 16) & 0xffff,
+                    v & 0xffff
+                ),
+                Err(_) => "Unknown".to_string(),
+            };
+
+            let resolution = match adapter.EnumOutputs(0).and_then(|o| o.GetDesc()) {
+                Ok(out) => {
+                    let rect = out.DesktopCoordinates;
+                    format!("{}x{}", rect.right - rect.left, rect.bottom - rect.top)
+                }
+                Err(_) => "Unknown".to_string(),
+            };
+
+            let hardware_id =
+                format_hardware_id(desc.VendorId, desc.DeviceId, desc.SubSysId, desc.Revision);
+            let pnp = devices.iter().find(|pnp| {
+                pnp.starts_with(&hardware_id) && !gpus.iter().any(|g| &g.device_id == *pnp)
+            });
+
+            let vram_gb = desc.DedicatedVideoMemory as f64 / (1024.0 * 1024.0 * 1024.0);
+            gpus.push(GpuInfo {
+                device_id: stable_device_id(pnp.map(|s| s.as_str()), &name),
+                name,
+                vram_gb: (vram_gb * 10.0).round() / 10.0,
+                driver_version,
+                resolution,
+                luid: Some(format_luid(
+                    desc.AdapterLuid.HighPart as u32,
+                    desc.AdapterLuid.LowPart,
+                )),
+            });
+        }
+    }
+    gpus
+}
+
+/// 同一设备可能出现多条记录（驱动更新残留等），按设备标识去重
+#[cfg(target_os = "windows")]
+fn dedupe_gpus(gpus: Vec<GpuInfo>) -> Vec<GpuInfo> {
+    let mut unique: Vec<GpuInfo> = Vec::new();
+    for gpu in gpus {
+        if !unique.iter().any(|g| g.device_id == gpu.device_id) {
+            unique.push(gpu);
+        }
+    }
+    unique
+}
+
+/// 关联 DXGI 适配器 LUID
+#[cfg(target_os = "windows")]
+fn attach_luids(mut gpus: Vec<GpuInfo>) -> Vec<GpuInfo> {
+    let adapters = crate::gpu_routing::list_adapters();
+    for gpu in &mut gpus {
+        gpu.luid = adapters
+            .iter()
+            .find(|a| a.device_id.as_deref() == Some(gpu.device_id.as_str()))
+            .map(|a| a.luid.clone());
+    }
+    gpus
+}
+
+/// PNPDeviceID 统一大写；缺失时退回显卡名称
+#[cfg(target_os = "windows")]
+fn stable_device_id(pnp: Option<&str>, name: &str) -> String {
+    match pnp {
+        Some(id) if !id.is_empty() => id.to_uppercase(),
+        _ => name.to_string(),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn detect_gpu_wmi() -> Result<Vec<GpuInfo>, Box<dyn std::error::Error>> {
+    use wmi::{COMLibrary, WMIConnection};
+    use std::collections::HashMap;
+
+    let com = COMLibrary::new()?;
+    let wmi = WMIConnection::new(com)?;
+
+    // 查询 Win32_VideoController
+    let results: Vec<HashMap<String, wmi::Variant>> =
+        wmi.raw_query("SELECT Name, PNPDeviceID, AdapterRAM, DriverVersion, \
+                        CurrentHorizontalResolution, CurrentVerticalResolution \
+                        FROM Win32_VideoController")?;
+
+    log::info!("WMI 查询返回 {} 个视频控制器", results.len());
+
+    let mut gpus = Vec::new();
+    for (idx, item) in results.iter().enumerate() {
+        let name = match item.get("Name") {
+            Some(wmi::Variant::String(s)) => {
+                log::info!("  [{}] GPU 名称: {}", idx, s);
+                s.clone()
+            }
+            _ => {
+                log::warn!("  [{}] 无法获取 GPU 名称", idx);
+                continue;
+            }
+        };
+
+        // 跳过 Microsoft Basic Display Adapter 等虚拟设备
+        if name.contains("Microsoft") || name.contains("Basic") || name.contains("Remote") {
+            log::info!("  [{}] 跳过虚拟设备: {}", idx, name);
+            continue;
+        }
+
+        // AdapterRAM 返回 bytes
+        let vram_bytes: u64 = match item.get("AdapterRAM") {
+            Some(wmi::Variant::UI4(n)) => *n as u64,
+            Some(wmi::Variant::I4(n)) => *n as u64,
+            _ => {
+                log::warn!("  [{}] 无法获取显存信息", idx);
+                0
+            }
+        };
+        let vram_gb = vram_bytes as f64 / (1024.0 * 1024.0 * 1024.0);
+
+        let driver = match item.get("DriverVersion") {
+            Some(wmi::Variant::String(s)) => s.clone(),
+            _ => "Unknown".to_string(),
+        };
+
+        let h_res = match item.get("CurrentHorizontalResolution") {
+            Some(wmi::Variant::UI4(n)) => *n,
+            _ => 0,
+        };
+        let v_res = match item.get("CurrentVerticalResolution") {
+            Some(wmi::Variant::UI4(n)) => *n,
+            _ => 0,
+        };
+        let resolution = if h_res > 0 && v_res > 0 {
+            format!("{}x{}", h_res, v_res)
+        } else {
+            "Unknown".to_string()
+        };
+
+        let pnp = match item.get("PNPDeviceID") {
+            Some(wmi::Variant::String(s)) => Some(s.as_str()),
+            _ => None,
+        };
+        let device_id = stable_device_id(pnp, &name);
+
+        log::info!("  [{}] 添加 GPU: {} ({:.1} GB) {}", idx, name, vram_gb, device_id);
+
+        gpus.push(GpuInfo {
+            name,
+            vram_gb: (vram_gb * 10.0).round() / 10.0, // 保留1位小数
+            driver_version: driver,
+            resolution,
+            device_id,
+            luid: None,
+        });
+    }
+
+    Ok(gpus)
+}
+
+/// 使用 PowerShell 作为备用方案检测 GPU
+#[cfg(target_os = "windows")]
+fn detect_gpu_powershell() -> Result<Vec<GpuInfo>, Box<dyn std::error::Error>> {
+    use std::process::Command;
+
+    log::info!("尝试 PowerShell GPU 检测...");
+
+    let output = Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            "Get-WmiObject Win32_VideoController | Select-Object Name, PNPDeviceID, DriverVersion, AdapterRAM, CurrentHorizontalResolution, CurrentVerticalResolution | ConvertTo-Json"
+        ])
+        .output()?;
+
+    if !output.status.success() {
+        return Err("PowerShell 命令失败".into());
+    }
+
+    let json_str = String::from_utf8_lossy(&output.stdout);
+    log::info!("PowerShell 输出: {}", json_str);
+
+    // 简单解析 JSON（如果有多个 GPU，会是数组）
+    let mut gpus = Vec::new();
+
+    // 尝试解析为单个对象或数组
+    let parsed: serde_json::Value = serde_json::from_str(&json_str)
+        .map_err(|e| format!("JSON 解析失败: {}", e))?;
+
+    let items = if parsed.is_array() {
+        parsed.as_array().unwrap().clone()
+    } else {
+        vec![parsed]
+    };
+
+    for item in items {
+        let name = item["Name"].as_str().unwrap_or("Unknown GPU");
+        let driver = item["DriverVersion"].as_str().unwrap_or("Unknown");
+
+        // AdapterRAM 在 JSON 中可能是数字
+        let vram_bytes = item["AdapterRAM"].as_u64().unwrap_or(0);
+        let vram_gb = vram_bytes as f64 / (1024.0 * 1024.0 * 1024.0);
+
+        let h_res = item["CurrentHorizontalResolution"].as_u64().unwrap_or(0) as u32;
+        let v_res = item["CurrentVerticalResolution"].as_u64().unwrap_or(0) as u32;
+        let resolution = if h_res > 0 && v_res > 0 {
+            format!("{}x{}", h_res, v_res)
+        } else {
+            "Unknown".to_string()
+        };
+
+        // 跳过虚拟设备
+        if name.contains("Microsoft") || name.contains("Basic") || name.contains("Remote") {
+            continue;
+        }
+
+        gpus.push(GpuInfo {
+            name: name.to_string(),
+            vram_gb: (vram_gb * 10.0).round() / 10.0,
+            driver_version: driver.to_string(),
+            resolution,
+            device_id: stable_device_id(item["PNPDeviceID"].as_str(), name),
+            luid: None,
+        });
+    }
+
+    Ok(gpus)
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn detect_gpu_info(_allow_powershell_fallback: bool) -> Vec<GpuInfo> {
+    // 非 Windows 平台的 stub
+    vec![GpuInfo {
+        name: "仅支持 Windows 检测".to_string(),
+        vram_gb: 0.0,
+        driver_version: "N/A".to_string(),
+        resolution: "N/A".to_string(),
+        device_id: String::new(),
+        luid: None,
+    }]
+}
+
+// ==================== RAM 检测 ====================
+
+/// 单条内存的 SMBIOS 信息
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+struct MemoryModule {
+    memory_type: Option<String>,
+    rated_mhz: u32,
+    configured_mhz: u32,
+}
+
+#[cfg(target_os = "windows")]
+fn detect_memory_modules() -> Vec<MemoryModule> {
+    use std::collections::HashMap;
+    use wmi::{COMLibrary, WMIConnection};
+
+    let Ok(com) = COMLibrary::new() else {
+        return vec![];
+    };
+    let Ok(wmi) = WMIConnection::new(com) else {
+        return vec![];
+    };
+    let results: Vec<HashMap<String, wmi::Variant>> = match wmi.raw_query(
+        "SELECT Speed, ConfiguredClockSpeed, SMBIOSMemoryType FROM Win32_PhysicalMemory",
+    ) {
+        Ok(r) => r,
+        Err(e) => {
+            log::warn!("WMI 内存条查询失败: {}", e);
+            return vec![];
+        }
+    };
+
+    let as_u32 = |v: Option<&wmi::Variant>| match v {
+        Some(wmi::Variant::UI4(n)) => *n,
+        Some(wmi::Variant::UI2(n)) => *n as u32,
+        _ => 0,
+    };
+
+    results
+        .iter()
+        .map(|item| {
+            // SMBIOS Type 17 内存类型: 26 = DDR4, 34 = DDR5
+            let memory_type = match as_u32(item.get("SMBIOSMemoryType")) {
+                24 => Some("DDR3".to_string()),
+                26 => Some("DDR4".to_string()),
+                34 => Some("DDR5".to_string()),
+                _ => None,
+            };
+            MemoryModule {
+                memory_type,
+                rated_mhz: as_u32(item.get("Speed")),
+                configured_mhz: as_u32(item.get("ConfiguredClockSpeed")),
+            }
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn detect_memory_modules() -> Vec<MemoryModule> {
+    vec![]
+}
+
+/// 根据内存类型和实际速率判断运行档位
+fn classify_memory_profile(memory_type: Option<&str>, configured_mhz: u32) -> MemoryProfile {
+    // (JEDEC 最低默认频率, JEDEC 标准最高频率)
+    let (jedec_floor, jedec_max) = match memory_type {
+        Some("DDR4") => (2133, 3200),
+        Some("DDR5") => (4800, 5600),
+        Some("DDR3") => (1333, 1600),
+        _ => return MemoryProfile::Unknown,
+    };
+
+    if configured_mhz == 0 {
+        MemoryProfile::Unknown
+    } else if configured_mhz <= jedec_floor {
+        MemoryProfile::JedecDefault
+    } else if configured_mhz > jedec_max {
+        MemoryProfile::XmpExpo
+    } else {
+        MemoryProfile::Jedec
+    }
+}
+
+pub fn detect_ram_info() -> RamInfo {
+    let mut sys = System::new_all();
+    sys.refresh_memory();
+
+    let total = sys.total_memory() as f64 / (1024.0 * 1024.0 * 1024.0);
+    let used = sys.used_memory() as f64 / (1024.0 * 1024.0 * 1024.0);
+    let available = sys.available_memory() as f64 / (1024.0 * 1024.0 * 1024.0);
+
+    // 多条内存以最慢的一条为准（内存控制器按最慢的条运行）
+    let modules = detect_memory_modules();
+    let memory_type = modules.iter().find_map(|m| m.memory_type.clone());
+    let rated = modules.iter().map(|m| m.rated_mhz).filter(|&v| v > 0).min();
+    let configured = modules
+        .iter()
+        .map(|m| m.configured_mhz)
+        .filter(|&v| v > 0)
+        .min();
+    let profile = classify_memory_profile(memory_type.as_deref(), configured.unwrap_or(0));
+
+    RamInfo {
+        total_gb: (total * 10.0).round() / 10.0,
+        used_gb: (used * 10.0).round() / 10.0,
+        available_gb: (available * 10.0).round() / 10.0,
+        memory_type,
+        module_count: modules.len() as u32,
+        rated_speed_mhz: rated,
+        configured_speed_mhz: configured,
+        profile,
+    }
+}
+
+// ==================== OS 检测 ====================
+
+pub fn detect_os() -> String {
+    let name = System::name().unwrap_or_else(|| "Unknown".to_string());
+    let version = System::os_version().unwrap_or_default();
+    let arch = System::cpu_arch();
+    format!("{} {} ({})", name, version, arch)
+}
+
+// ==================== 并行检测 ====================
+
+/// 命令返回前等待各探测项的时间，未完成的探测项稍后通过事件推送
+const INITIAL_WAIT: Duration = Duration::from_millis(800);
+/// 本地探测项（sysinfo）超时
+const LOCAL_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+/// WMI / 驱动查询超时（WMI 服务损坏时可能一直不返回）
+const WMI_PROBE_TIMEOUT: Duration = Duration::from_secs(15);
+
+const ALL_PROBES: [Probe; 8] = [
+    Probe::Cpu,
+    Probe::Gpu,
+    Probe::Ram,
+    Probe::Os,
+    Probe::DirectStorage,
+    Probe::DriveHealth,
+    Probe::Vbs,
+    Probe::GpuMux,
+];
+
+fn probe_timeout(probe: Probe) -> Duration {
+    match probe {
+        Probe::Cpu | Probe::Os => LOCAL_PROBE_TIMEOUT,
+        _ => WMI_PROBE_TIMEOUT,
+    }
+}
+
+impl DegradedProbe {
+    fn new(probe: Probe, reason: impl Into<String>, suggestion: impl Into<String>) -> Self {
+        DegradedProbe {
+            probe,
+            reason: reason.into(),
+            suggestion: suggestion.into(),
+        }
+    }
+}
+
+struct Detection {
+    info: HardwareInfo,
+    /// 命令是否已返回（之后完成的探测项需要推送事件）
+    returned: bool,
+}
+
+type SharedDetection = Arc<Mutex<Detection>>;
+
+/// 在后台线程运行单个探测项，完成后写入结果
+/// 超时或探测线程崩溃时记录为降级项，`apply` 也可根据结果返回降级原因
+fn spawn_probe<T, D, A>(
+    sink: &Arc<dyn EventSink>,
+    shared: &SharedDetection,
+    done: &mpsc::Sender<Probe>,
+    probe: Probe,
+    detect: D,
+    apply: A,
+) where
+    T: Send + 'static,
+    D: FnOnce() -> T + Send + 'static,
+    A: FnOnce(&mut HardwareInfo, T) -> Option<DegradedProbe> + Send + 'static,
+{
+    let sink = sink.clone();
+    let shared = shared.clone();
+    let done = done.clone();
+    std::thread::spawn(move || {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(detect());
+        });
+
+        let timeout = probe_timeout(probe);
+        let result = rx.recv_timeout(timeout);
+        let mut state = shared.lock().unwrap();
+        let degraded = match result {
+            Ok(value) => apply(&mut state.info, value),
+            Err(mpsc::RecvTimeoutError::Timeout) => Some(DegradedProbe::new(
+                probe,
+                format!("{} 秒内未返回结果", timeout.as_secs()),
+                "WMI 服务可能无响应，可尝试重启电脑或在管理员命令行执行 winmgmt /verifyrepository",
+            )),
+            Err(mpsc::RecvTimeoutError::Disconnected) => Some(DegradedProbe::new(
+                probe,
+                "探测过程异常退出",
+                "请查看日志并反馈问题",
+            )),
+        };
+        if let Some(d) = degraded {
+            log::warn!("硬件检测降级 {:?}: {}", d.probe, d.reason);
+            state.info.degraded.push(d);
+        }
+        state.info.pending.retain(|p| *p != probe);
+        if state.returned {
+            sink.emit("hardware-updated", &state.info);
+        }
+        drop(state);
+        let _ = done.send(probe);
+    });
+}
+
+// ==================== 检测入口 ====================
+
+/// 检测选项（来自用户设置）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DetectOptions {
+    pub allow_powershell_fallback: bool,
+    pub units: UnitPrefs,
+}
+
+/// 一次性获取全部硬件信息
+/// 各探测项并行运行，较慢的探测项（WMI 等）未完成时先返回已有结果，
+/// 之后每完成一项通过 `sink` 推送 "hardware-updated"
+pub fn detect_hardware(options: DetectOptions, sink: Arc<dyn EventSink>) -> HardwareInfo {
+    log::info!("开始检测硬件...");
+
+    let shared = Arc::new(Mutex::new(Detection {
+        info: HardwareInfo {
+            pending: ALL_PROBES.to_vec(),
+            ..Default::default()
+        },
+        returned: false,
+    }));
+    let (done, finished) = mpsc::channel();
+
+    spawn_probe(&sink, &shared, &done, Probe::Cpu, detect_cpu_info, |info, cpu| {
+        log::info!("CPU: {}", cpu.name);
+        info.cpu = cpu;
+        None
+    });
+    spawn_probe(
+        &sink,
+        &shared,
+        &done,
+        Probe::Gpu,
+        move || detect_gpu_info(options.allow_powershell_fallback),
+        |info, gpus| {
+            for gpu in &gpus {
+                log::info!("GPU: {} ({:.1} GB)", gpu.name, gpu.vram_gb);
+            }
+            let empty = gpus.is_empty();
+            info.gpus = gpus;
+            empty.then(|| {
+                DegradedProbe::new(
+                    Probe::Gpu,
+                    "未检测到显卡",
+                    "请安装或更新显卡驱动；仍无法识别时可在设置中开启 PowerShell 备用检测",
+                )
+            })
+        },
+    );
+    spawn_probe(&sink, &shared, &done, Probe::Ram, detect_ram_info, |info, ram| {
+        log::info!(
+            "RAM: {:.1} GB {} @ {} MHz ({:?})",
+            ram.total_gb,
+            ram.memory_type.as_deref().unwrap_or("Unknown"),
+            ram.configured_speed_mhz.unwrap_or(0),
+            ram.profile
+        );
+        let degraded = (ram.module_count == 0).then(|| {
+            DegradedProbe::new(
+                Probe::Ram,
+                "无法读取内存条信息（类型/频率）",
+                "WMI 服务可能被禁用，请确认 Windows Management Instrumentation 服务正在运行",
+            )
+        });
+        info.ram = ram;
+        degraded
+    });
+    spawn_probe(&sink, &shared, &done, Probe::Os, detect_os, |info, os| {
+        info.os = os;
+        None
+    });
+    spawn_probe(
+        &sink,
+        &shared,
+        &done,
+        Probe::DirectStorage,
+        || direct_storage::detect_system_support(&drives::list_drive_info()),
+        |info, support| {
+            info.direct_storage = support;
+            None
+        },
+    );
+    spawn_probe(
+        &sink,
+        &shared,
+        &done,
+        Probe::DriveHealth,
+        move || drives::read_drive_health(&options.units),
+        |info, health| {
+            for h in health.iter().filter(|h| !h.problems.is_empty()) {
+                log::warn!("磁盘 {}: {}", h.model, h.problems.join("; "));
+            }
+            info.drive_health = health;
+            None
+        },
+    );
+    spawn_probe(&sink, &shared, &done, Probe::Vbs, vbs::detect_vbs_status, |info, vbs| {
+        log::info!(
+            "VBS: {:?} | HVCI: {} | Credential Guard: {}",
+            vbs.vbs,
+            vbs.hvci_running,
+            vbs.credential_guard_running
+        );
+        let degraded = (vbs.vbs == vbs::VbsState::Unknown).then(|| {
+            DegradedProbe::new(
+                Probe::Vbs,
+                "无法读取 Device Guard 状态",
+                "请以管理员身份运行 GameBench",
+            )
+        });
+        info.vbs = vbs;
+        degraded
+    });
+    spawn_probe(
+        &sink,
+        &shared,
+        &done,
+        Probe::GpuMux,
+        || gpu_mux::detect_mux(&crate::gpu_routing::list_adapters()),
+        |info, mux| {
+            log::info!("GPU MUX: {:?} ({:?}) {}", mux.mode, mux.source, mux.detail);
+            info.gpu_mux = mux;
+            None
+        },
+    );
+    drop(done);
+
+    let deadline = Instant::now() + INITIAL_WAIT;
+    for _ in 0..ALL_PROBES.len() {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if finished.recv_timeout(remaining).is_err() {
+            break;
+        }
+    }
+
+    let mut state = shared.lock().unwrap();
+    state.returned = true;
+    if !state.info.pending.is_empty() {
+        log::info!("硬件检测: {:?} 仍在进行，完成后推送", state.info.pending);
+    }
+    state.info.clone()
+}
+
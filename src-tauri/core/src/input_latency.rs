@@ -0,0 +1,74 @@
+use crate::metrics::frametime_percentile;
+use crate::presentmon::FrameRow;
+use serde::{Deserialize, Serialize};
+
+// ==================== 数据结构 ====================
+
+/// 一类延迟的统计 (ms)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LatencySummary {
+    pub avg_ms: f64,
+    pub median_ms: f64,
+    pub p95_ms: f64,
+    /// 有延迟数据的帧数
+    pub samples: u64,
+}
+
+/// 输入到画面的延迟（PresentMon v2 的 `DisplayLatency` / `ClickToPhotonLatency` 列）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputLatency {
+    /// 从开始模拟这一帧到显示在屏幕上
+    pub display: Option<LatencySummary>,
+    /// 从鼠标点击到画面出现变化，只有带点击的帧才有数据
+    pub click_to_photon: Option<LatencySummary>,
+}
+
+// ==================== 统计 ====================
+
+fn summarize(samples: &[f64]) -> Option<LatencySummary> {
+    if samples.is_empty() {
+        return None;
+    }
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let round = |v: f64| (v * 100.0).round() / 100.0;
+    Some(LatencySummary {
+        avg_ms: round(sorted.iter().sum::<f64>() / sorted.len() as f64),
+        median_ms: round(frametime_percentile(&sorted, 50.0)),
+        p95_ms: round(frametime_percentile(&sorted, 95.0)),
+        samples: sorted.len() as u64,
+    })
+}
+
+/// 逐帧累计延迟；帧未显示或没有点击时 PresentMon 输出 NA，跳过
+#[derive(Debug, Clone, Default)]
+pub struct LatencyStats {
+    display: Vec<f64>,
+    click: Vec<f64>,
+}
+
+impl LatencyStats {
+    pub fn push(&mut self, row: &FrameRow) {
+        if let Some(ms) = row.display_latency {
+            self.display.push(ms);
+        }
+        if let Some(ms) = row.click_to_photon {
+            self.click.push(ms);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.display.clear();
+        self.click.clear();
+    }
+
+    /// 采集来源不提供延迟列（PresentMon v1、内置 ETW 采集）时返回 None
+    pub fn summary(&self) -> Option<InputLatency> {
+        let display = summarize(&self.display);
+        let click_to_photon = summarize(&self.click);
+        (display.is_some() || click_to_photon.is_some()).then_some(InputLatency {
+            display,
+            click_to_photon,
+        })
+    }
+}
@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// 进程在开始监测前这么久之内启动才算作"启动测试"(ms)，更早启动的游戏不报告启动耗时
+const LAUNCH_WINDOW_MS: u64 = 120_000;
+/// 判断帧率稳定的滑动窗口 (ms)
+const STABLE_WINDOW_MS: f64 = 3000.0;
+/// 窗口内最长帧不超过中位数的倍数
+const STABLE_MAX_RATIO: f64 = 2.0;
+/// 窗口中位帧时间上限 (ms)，低于 20 FPS 视为仍在加载
+const STABLE_MAX_MEDIAN_MS: f64 = 50.0;
+
+// ==================== 数据结构 ====================
+
+/// 游戏启动与加载耗时
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchTiming {
+    /// 进程创建时间 (unix 毫秒)
+    pub process_started_ms: u64,
+    /// 第一帧呈现时间 (unix 毫秒)
+    pub first_frame_ms: u64,
+    /// 帧时间首次稳定的时间 (unix 毫秒)，测试结束前未稳定时为 None
+    pub stable_ms: Option<u64>,
+    /// 进程启动 → 第一帧 (秒)
+    pub time_to_first_frame_secs: f64,
+    /// 进程启动 → 帧时间稳定 (秒)
+    pub time_to_stable_secs: Option<f64>,
+}
+
+// ==================== 进程启动时间 ====================
+
+/// 进程创建时间 (unix 毫秒)
+#[cfg(target_os = "windows")]
+pub fn process_start_ms(pid: u32) -> Option<u64> {
+    use windows::Win32::Foundation::{CloseHandle, FILETIME};
+    use windows::Win32::System::Threading::{
+        GetProcessTimes, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    // FILETIME 纪元 (1601-01-01) 与 unix 纪元之间的 100ns 间隔数
+    const EPOCH_DIFF: u64 = 116_444_736_000_000_000;
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let mut created = FILETIME::default();
+        let mut exited = FILETIME::default();
+        let mut kernel = FILETIME::default();
+        let mut user = FILETIME::default();
+        let result = GetProcessTimes(handle, &mut created, &mut exited, &mut kernel, &mut user);
+        let _ = CloseHandle(handle);
+        result.ok()?;
+        let ticks = ((created.dwHighDateTime as u64) << 32) | created.dwLowDateTime as u64;
+        Some(ticks.checked_sub(EPOCH_DIFF)? / 10_000)
+    }
+}
+
+/// 进程创建时间 (unix 毫秒，秒级精度)
+#[cfg(not(target_os = "windows"))]
+pub fn process_start_ms(pid: u32) -> Option<u64> {
+    use sysinfo::{Pid, System};
+
+    let mut sys = System::new();
+    let target = Pid::from_u32(pid);
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::Some(&[target]), true);
+    sys.process(target).map(|p| p.start_time() * 1000)
+}
+
+// ==================== 采集期间跟踪 ====================
+
+/// 记录第一帧与帧时间首次稳定的时刻
+#[derive(Default)]
+pub struct LaunchTracker {
+    process_started_ms: Option<u64>,
+    first_frame_ms: Option<u64>,
+    stable_ms: Option<u64>,
+    window: VecDeque<f64>,
+    window_ms: f64,
+}
+
+impl LaunchTracker {
+    /// 第一帧到达时绑定游戏进程的创建时间
+    pub fn set_process(&mut self, pid: Option<u32>) {
+        self.process_started_ms = pid.and_then(process_start_ms);
+    }
+
+    pub fn on_frame(&mut self, frametime_ms: f64) {
+        let now_ms = chrono::Utc::now().timestamp_millis().max(0) as u64;
+        self.first_frame_ms.get_or_insert(now_ms);
+        if self.stable_ms.is_some() {
+            return;
+        }
+
+        self.window.push_back(frametime_ms);
+        self.window_ms += frametime_ms;
+        while self.window_ms - self.window.front().copied().unwrap_or(0.0) >= STABLE_WINDOW_MS {
+            if let Some(old) = self.window.pop_front() {
+                self.window_ms -= old;
+            }
+        }
+        if self.window_ms >= STABLE_WINDOW_MS && self.is_stable() {
+            self.stable_ms = Some(now_ms);
+            self.window.clear();
+        }
+    }
+
+    /// 窗口内帧率达到可玩水平且没有明显的加载长帧
+    fn is_stable(&self) -> bool {
+        let mut sorted: Vec<f64> = self.window.iter().copied().collect();
+        sorted.sort_by(f64::total_cmp);
+        let median = sorted[sorted.len() / 2];
+        let max = sorted[sorted.len() - 1];
+        median <= STABLE_MAX_MEDIAN_MS && max <= median * STABLE_MAX_RATIO
+    }
+
+    /// 进程在监测开始前不久（或开始后）启动时生成启动报告
+    pub fn finish(self, capture_started_ms: u64) -> Option<LaunchTiming> {
+        let started = self.process_started_ms?;
+        let first = self.first_frame_ms?;
+        if started + LAUNCH_WINDOW_MS < capture_started_ms || first < started {
+            return None;
+        }
+        let secs = |ms: u64| ((ms - started) as f64 / 100.0).round() / 10.0;
+        Some(LaunchTiming {
+            process_started_ms: started,
+            first_frame_ms: first,
+            stable_ms: self.stable_ms,
+            time_to_first_frame_secs: secs(first),
+            time_to_stable_secs: self.stable_ms.map(secs),
+        })
+    }
+}
@@ -0,0 +1,40 @@
+pub mod background_activity;
+pub mod capture_log;
+pub mod capture_time;
+pub mod direct_storage;
+pub mod display;
+pub mod dpc_latency;
+pub mod drives;
+pub mod ed25519;
+pub mod emulators;
+pub mod etw;
+pub mod events;
+pub mod frame_cap;
+pub mod frame_log;
+pub mod frame_pipeline;
+pub mod frame_stats;
+pub mod game_version;
+pub mod game_window;
+pub mod gpu_mux;
+pub mod gpu_routing;
+pub mod hardware;
+pub mod input_latency;
+pub mod launch_timing;
+pub mod loadless;
+pub mod memory_pressure;
+pub mod metrics;
+pub mod pcie;
+pub mod pinyin;
+pub mod present_etw;
+pub mod present_mode;
+pub mod presentmon;
+pub mod qoe;
+pub mod sensors;
+pub mod session_meta;
+pub mod smoothing;
+pub mod streaming;
+pub mod stutter;
+pub mod units;
+pub mod vbs;
+pub mod vram_health;
+pub mod vrr;
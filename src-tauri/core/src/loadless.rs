@@ -0,0 +1,234 @@
+use crate::frame_stats::{FrameStats, FrameStatsReport};
+use serde::{Deserialize, Serialize};
+
+/// 统计窗口长度 (ms)
+const WINDOW_MS: f64 = 250.0;
+/// 窗口帧率低于全程中位数的该比例时视为加载画面
+const LOAD_FPS_RATIO: f64 = 0.3;
+/// 加载段最短时长 (秒)，更短的低帧率片段按卡顿处理
+const MIN_LOAD_SECS: f64 = 1.0;
+/// 相邻加载段间隔短于该值 (秒) 时合并为一段
+const MERGE_GAP_SECS: f64 = 0.5;
+
+// ==================== 数据结构 ====================
+
+/// 检测到的加载段，时间按帧时间累计（相对首帧，不含暂停时段）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoadSegment {
+    /// 首帧序号
+    pub first_frame: u64,
+    pub frames: u64,
+    pub start_secs: f64,
+    pub end_secs: f64,
+    pub duration_secs: f64,
+}
+
+/// 去除加载画面后的时长与帧率（速通计时用的"无加载时间"）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadlessReport {
+    /// 全程时长 (秒)
+    pub total_secs: f64,
+    /// 加载段合计时长 (秒)
+    pub load_secs: f64,
+    /// 去除加载段后的时长 (秒)
+    pub loadless_secs: f64,
+    pub segments: Vec<LoadSegment>,
+    /// 去除加载段后的帧率统计
+    pub loadless: FrameStatsReport,
+}
+
+// ==================== 加载段检测 ====================
+
+#[derive(Debug, Clone, Copy)]
+struct Window {
+    first_frame: u64,
+    end_frame: u64,
+    start_ms: f64,
+    ms: f64,
+    frames: u64,
+}
+
+impl Window {
+    fn fps(&self) -> f64 {
+        if self.ms > 0.0 {
+            self.frames as f64 * 1000.0 / self.ms
+        } else {
+            0.0
+        }
+    }
+}
+
+/// 第一遍扫描：按 250 ms 窗口累计帧率，可逐块输入
+/// 加载画面通常表现为长时间冻结或远低于正常水平的帧率；以高帧率渲染的加载画面无法识别
+#[derive(Debug, Clone, Default)]
+pub struct LoadScanner {
+    windows: Vec<Window>,
+    next_frame: u64,
+    elapsed_ms: f64,
+    current: Option<Window>,
+}
+
+impl LoadScanner {
+    pub fn extend(&mut self, frame_times: &[f64]) {
+        for &ft in frame_times {
+            let index = self.next_frame;
+            self.next_frame += 1;
+            let window = self.current.get_or_insert(Window {
+                first_frame: index,
+                end_frame: index,
+                start_ms: self.elapsed_ms,
+                ms: 0.0,
+                frames: 0,
+            });
+            window.end_frame = index + 1;
+            // 无效帧只占序号，不计入时长与帧数
+            if ft > 0.0 && ft.is_finite() {
+                window.ms += ft;
+                window.frames += 1;
+                self.elapsed_ms += ft;
+            }
+            if window.ms >= WINDOW_MS {
+                self.windows.extend(self.current.take());
+            }
+        }
+    }
+
+    /// 按全程窗口帧率中位数判定加载段
+    pub fn segments(mut self) -> Vec<LoadSegment> {
+        self.windows
+            .extend(self.current.take().filter(|w| w.frames > 0));
+        let mut rates: Vec<f64> = self.windows.iter().map(Window::fps).collect();
+        if rates.is_empty() {
+            return Vec::new();
+        }
+        rates.sort_by(f64::total_cmp);
+        let threshold = rates[rates.len() / 2] * LOAD_FPS_RATIO;
+
+        let mut runs: Vec<Window> = Vec::new();
+        for window in self.windows.iter().filter(|w| w.fps() < threshold) {
+            match runs.last_mut() {
+                Some(run)
+                    if window.start_ms - (run.start_ms + run.ms) < MERGE_GAP_SECS * 1000.0 =>
+                {
+                    // 合并时把间隔内的正常帧一并计入加载段
+                    run.ms = window.start_ms + window.ms - run.start_ms;
+                    run.frames = window.end_frame - run.first_frame;
+                    run.end_frame = window.end_frame;
+                }
+                _ => runs.push(Window {
+                    frames: window.end_frame - window.first_frame,
+                    ..*window
+                }),
+            }
+        }
+
+        let round3 = |v: f64| (v * 1000.0).round() / 1000.0;
+        runs.into_iter()
+            .filter(|run| run.ms >= MIN_LOAD_SECS * 1000.0)
+            .map(|run| LoadSegment {
+                first_frame: run.first_frame,
+                frames: run.frames,
+                start_secs: round3(run.start_ms / 1000.0),
+                end_secs: round3((run.start_ms + run.ms) / 1000.0),
+                duration_secs: round3(run.ms / 1000.0),
+            })
+            .collect()
+    }
+}
+
+// ==================== 无加载统计 ====================
+
+/// 第二遍扫描：跳过加载段内的帧，统计其余帧的帧率，可逐块输入
+#[derive(Clone)]
+pub struct LoadlessStats {
+    segments: Vec<LoadSegment>,
+    next_frame: u64,
+    next_segment: usize,
+    stats: FrameStats,
+    /// 摘要中的时长只保留一位小数，计时需精确到毫秒，单独累计
+    loadless_ms: f64,
+}
+
+impl LoadlessStats {
+    pub fn new(segments: Vec<LoadSegment>) -> Self {
+        LoadlessStats {
+            segments,
+            next_frame: 0,
+            next_segment: 0,
+            stats: FrameStats::default(),
+            loadless_ms: 0.0,
+        }
+    }
+
+    pub fn extend(&mut self, frame_times: &[f64]) {
+        for &ft in frame_times {
+            let index = self.next_frame;
+            self.next_frame += 1;
+            while self
+                .segments
+                .get(self.next_segment)
+                .is_some_and(|s| index >= s.first_frame + s.frames)
+            {
+                self.next_segment += 1;
+            }
+            let in_load = self
+                .segments
+                .get(self.next_segment)
+                .is_some_and(|s| index >= s.first_frame);
+            if !in_load && ft > 0.0 && ft.is_finite() {
+                self.stats.push(ft);
+                self.loadless_ms += ft;
+            }
+        }
+    }
+
+    pub fn report(self) -> LoadlessReport {
+        let round3 = |v: f64| (v * 1000.0).round() / 1000.0;
+        let load_secs: f64 = self.segments.iter().map(|s| s.duration_secs).sum();
+        let loadless_secs = self.loadless_ms / 1000.0;
+        LoadlessReport {
+            total_secs: round3(load_secs + loadless_secs),
+            load_secs: round3(load_secs),
+            loadless_secs: round3(loadless_secs),
+            segments: self.segments,
+            loadless: self.stats.report(),
+        }
+    }
+}
+
+/// 对内存中的帧时间执行两遍扫描
+pub fn analyze(frame_times: &[f64]) -> LoadlessReport {
+    let mut scanner = LoadScanner::default();
+    scanner.extend(frame_times);
+    let mut stats = LoadlessStats::new(scanner.segments());
+    stats.extend(frame_times);
+    stats.report()
+}
+
+/// 加载段时间点导出为 CSV（H:MM:SS.mmm 与秒数），便于速通计时佐证
+pub fn segments_csv(segments: &[LoadSegment]) -> String {
+    let clock = |secs: f64| {
+        let ms = (secs * 1000.0).round() as u64;
+        format!(
+            "{}:{:02}:{:02}.{:03}",
+            ms / 3_600_000,
+            ms / 60_000 % 60,
+            ms / 1000 % 60,
+            ms % 1000
+        )
+    };
+    let mut out = String::from("index,start,end,start_secs,end_secs,duration_secs,frames\n");
+    for (i, s) in segments.iter().enumerate() {
+        out.push_str(&format!(
+            "{},{},{},{:.3},{:.3},{:.3},{}\n",
+            i + 1,
+            clock(s.start_secs),
+            clock(s.end_secs),
+            s.start_secs,
+            s.end_secs,
+            s.duration_secs,
+            s.frames
+        ));
+    }
+    out
+}
@@ -0,0 +1,273 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// 采样间隔
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+/// 硬页错误读取超过此值 (次/秒) 视为尖峰
+const HARD_FAULT_SPIKE: f64 = 300.0;
+/// 可用内存低于此值 (MB) 或总内存的 8% 时视为内存不足
+const LOW_AVAILABLE_MB: f64 = 1024.0;
+/// 卡顿与内存压力相隔不超过此秒数时认为相关
+const COINCIDE_SECS: f64 = 1.0;
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Sample {
+    /// 距采样开始的秒数
+    at_secs: f64,
+    hard_faults: f64,
+    available_mb: f64,
+    standby_mb: f64,
+    commit_percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryPressureReport {
+    /// 物理内存总量 (GB)
+    pub total_ram_gb: f64,
+    /// 硬页错误读取峰值 (次/秒)
+    pub peak_hard_faults_per_sec: f64,
+    pub avg_hard_faults_per_sec: f64,
+    /// 测试期间最低可用内存 (MB)
+    pub min_available_mb: f64,
+    /// 测试期间待机列表最小值 (MB)，待机列表被耗尽时系统开始从磁盘换入
+    pub min_standby_mb: f64,
+    /// 提交内存占提交上限的峰值 (%)
+    pub peak_commit_percent: f64,
+    /// 硬页错误尖峰且可用内存不足的秒数
+    pub pressure_secs: u32,
+    /// 检测到的卡顿次数
+    pub stutters: u32,
+    /// 与内存压力同时发生的卡顿次数
+    pub stutters_with_pressure: u32,
+    pub samples: u32,
+    pub warnings: Vec<String>,
+}
+
+// ==================== 计数器读取 (Windows) ====================
+
+#[cfg(target_os = "windows")]
+const COUNTERS: &[&str] = &[
+    "\\Memory\\Page Reads/sec",
+    "\\Memory\\Available MBytes",
+    "\\Memory\\Standby Cache Core Bytes",
+    "\\Memory\\Standby Cache Normal Priority Bytes",
+    "\\Memory\\Standby Cache Reserve Bytes",
+    "\\Memory\\% Committed Bytes In Use",
+];
+
+/// PDH 查询（计数器使用英文名，不受系统语言影响）
+#[cfg(target_os = "windows")]
+struct PdhQuery {
+    query: isize,
+    counters: Vec<isize>,
+}
+
+#[cfg(target_os = "windows")]
+impl PdhQuery {
+    fn open(paths: &[&str]) -> Option<Self> {
+        use windows::core::{HSTRING, PCWSTR};
+        use windows::Win32::System::Performance::{PdhAddEnglishCounterW, PdhOpenQueryW};
+
+        let mut query = 0isize;
+        if unsafe { PdhOpenQueryW(PCWSTR::null(), 0, &mut query) } != 0 {
+            return None;
+        }
+        let mut pdh = PdhQuery {
+            query,
+            counters: Vec::new(),
+        };
+        for path in paths {
+            let mut counter = 0isize;
+            let status =
+                unsafe { PdhAddEnglishCounterW(query, &HSTRING::from(*path), 0, &mut counter) };
+            if status != 0 {
+                log::debug!("无法添加性能计数器 {}: 0x{:x}", path, status);
+                return None;
+            }
+            pdh.counters.push(counter);
+        }
+        Some(pdh)
+    }
+
+    fn collect(&self) -> Option<Vec<f64>> {
+        use windows::Win32::System::Performance::{
+            PdhCollectQueryData, PdhGetFormattedCounterValue, PDH_FMT_COUNTERVALUE, PDH_FMT_DOUBLE,
+        };
+
+        if unsafe { PdhCollectQueryData(self.query) } != 0 {
+            return None;
+        }
+        self.counters
+            .iter()
+            .map(|counter| {
+                let mut value = PDH_FMT_COUNTERVALUE::default();
+                let status = unsafe {
+                    PdhGetFormattedCounterValue(*counter, PDH_FMT_DOUBLE, None, &mut value)
+                };
+                (status == 0).then_some(unsafe { value.Anonymous.doubleValue })
+            })
+            .collect()
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl Drop for PdhQuery {
+    fn drop(&mut self) {
+        unsafe {
+            windows::Win32::System::Performance::PdhCloseQuery(self.query);
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn sample_loop(stop: &AtomicBool) -> Vec<Sample> {
+    let Some(query) = PdhQuery::open(COUNTERS) else {
+        log::warn!("无法打开内存性能计数器，跳过内存压力采样");
+        return vec![];
+    };
+    // 速率类计数器需要两次采集，第一次仅作基准
+    let _ = query.collect();
+
+    let started = Instant::now();
+    let mut samples = Vec::new();
+    let mut last = Instant::now();
+    while !stop.load(Ordering::Relaxed) {
+        if last.elapsed() >= SAMPLE_INTERVAL {
+            if let Some(v) = query.collect() {
+                samples.push(Sample {
+                    at_secs: started.elapsed().as_secs_f64(),
+                    hard_faults: v[0],
+                    available_mb: v[1],
+                    standby_mb: (v[2] + v[3] + v[4]) / (1024.0 * 1024.0),
+                    commit_percent: v[5],
+                });
+            }
+            last = Instant::now();
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    samples
+}
+
+#[cfg(not(target_os = "windows"))]
+fn sample_loop(_stop: &AtomicBool) -> Vec<Sample> {
+    vec![]
+}
+
+// ==================== 测试期间采样 ====================
+
+/// 在 FPS 监测期间采样硬页错误与待机列表，判断卡顿是否由内存不足引起
+pub struct MemorySampler {
+    started: Instant,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<Vec<Sample>>>,
+}
+
+impl MemorySampler {
+    pub fn start() -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = stop.clone();
+        let handle = std::thread::spawn(move || sample_loop(&stop_flag));
+
+        MemorySampler {
+            started: Instant::now(),
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    /// 停止采样，结合卡顿时刻生成报告
+    pub fn finish(mut self, stutters: &[Instant]) -> Option<MemoryPressureReport> {
+        self.stop.store(true, Ordering::Relaxed);
+        let samples = self.handle.take()?.join().ok()?;
+        let stutter_secs: Vec<f64> = stutters
+            .iter()
+            .map(|t| t.saturating_duration_since(self.started).as_secs_f64())
+            .collect();
+        build_report(&samples, &stutter_secs)
+    }
+}
+
+impl Drop for MemorySampler {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+fn build_report(samples: &[Sample], stutter_secs: &[f64]) -> Option<MemoryPressureReport> {
+    if samples.is_empty() {
+        return None;
+    }
+
+    let mut sys = sysinfo::System::new();
+    sys.refresh_memory();
+    let total_mb = sys.total_memory() as f64 / (1024.0 * 1024.0);
+    let low_mb = LOW_AVAILABLE_MB.max(total_mb * 0.08);
+
+    let pressure: Vec<f64> = samples
+        .iter()
+        .filter(|s| s.hard_faults >= HARD_FAULT_SPIKE && s.available_mb < low_mb)
+        .map(|s| s.at_secs)
+        .collect();
+    let stutters_with_pressure = stutter_secs
+        .iter()
+        .filter(|at| {
+            pressure
+                .iter()
+                .any(|p| (*at - p).abs() <= SAMPLE_INTERVAL.as_secs_f64() + COINCIDE_SECS)
+        })
+        .count() as u32;
+
+    let peak_faults = samples.iter().map(|s| s.hard_faults).fold(0.0, f64::max);
+    let avg_faults = samples.iter().map(|s| s.hard_faults).sum::<f64>() / samples.len() as f64;
+    let min_available = samples
+        .iter()
+        .map(|s| s.available_mb)
+        .fold(f64::INFINITY, f64::min);
+    let min_standby = samples
+        .iter()
+        .map(|s| s.standby_mb)
+        .fold(f64::INFINITY, f64::min);
+    let peak_commit = samples.iter().map(|s| s.commit_percent).fold(0.0, f64::max);
+    let total_gb = (total_mb / 1024.0).round();
+
+    let mut warnings = Vec::new();
+    if stutters_with_pressure > 0 {
+        warnings.push(format!(
+            "{} 次卡顿中有 {} 次发生在内存压力高峰（硬页错误峰值 {:.0} 次/秒，可用内存最低 {:.0} MB，\
+             待机列表最低 {:.0} MB）。本机 {:.0} GB 内存不足以同时容纳游戏与后台程序，\
+             建议关闭浏览器等后台程序，或升级内存容量。",
+            stutter_secs.len(),
+            stutters_with_pressure,
+            peak_faults,
+            min_available,
+            min_standby,
+            total_gb
+        ));
+    } else if !pressure.is_empty() {
+        warnings.push(format!(
+            "测试期间有 {} 秒处于内存压力状态（可用内存最低 {:.0} MB），但未与检测到的卡顿重合。",
+            pressure.len(),
+            min_available
+        ));
+    }
+
+    Some(MemoryPressureReport {
+        total_ram_gb: total_gb,
+        peak_hard_faults_per_sec: peak_faults.round(),
+        avg_hard_faults_per_sec: (avg_faults * 10.0).round() / 10.0,
+        min_available_mb: min_available.round(),
+        min_standby_mb: min_standby.round(),
+        peak_commit_percent: (peak_commit * 10.0).round() / 10.0,
+        pressure_secs: pressure.len() as u32,
+        stutters: stutter_secs.len() as u32,
+        stutters_with_pressure,
+        samples: samples.len() as u32,
+        warnings,
+    })
+}
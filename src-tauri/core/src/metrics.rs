@@ -0,0 +1,249 @@
+use crate::frame_stats::FrameStats;
+use serde::{Deserialize, Serialize};
+
+// ==================== 会话摘要 ====================
+
+/// 由帧时间序列计算的 FPS 摘要（保留一位小数）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FpsSummary {
+    pub avg_fps: f64,
+    pub fps_1_low: f64,
+    pub fps_01_low: f64,
+    pub max_fps: f64,
+    pub min_fps: f64,
+    pub total_frames: u64,
+}
+
+impl FpsSummary {
+    pub fn from_frames(frame_times: &[f64]) -> Self {
+        if frame_times.is_empty() {
+            return FpsSummary::default();
+        }
+        let round = |v: f64| (v * 10.0).round() / 10.0;
+        let fps = |ft: f64| {
+            if ft > 0.0 && ft.is_finite() {
+                1000.0 / ft
+            } else {
+                0.0
+            }
+        };
+        let avg_ft = frame_times.iter().sum::<f64>() / frame_times.len() as f64;
+        let min_ft = frame_times.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max_ft = frame_times.iter().cloned().fold(0.0_f64, f64::max);
+        FpsSummary {
+            avg_fps: round(fps(avg_ft)),
+            fps_1_low: round(percentile_low_fps(frame_times, 1.0)),
+            fps_01_low: round(percentile_low_fps(frame_times, 0.1)),
+            max_fps: round(fps(min_ft)),
+            min_fps: round(fps(max_ft)),
+            total_frames: frame_times.len() as u64,
+        }
+    }
+
+    /// 由流式统计得出（Low FPS 按直方图近似）
+    pub fn from_stats(stats: &FrameStats) -> Self {
+        let report = stats.report();
+        FpsSummary {
+            avg_fps: report.avg_fps,
+            fps_1_low: report.fps_1_low,
+            fps_01_low: report.fps_01_low,
+            max_fps: report.max_fps,
+            min_fps: report.min_fps,
+            total_frames: report.total_frames,
+        }
+    }
+}
+
+// ==================== 帧间隔稳定性 ====================
+
+/// 帧间隔（帧时间）稳定性：平均帧率相同时，波动越大体感越差
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FramePacing {
+    /// 帧时间标准差 (ms)
+    pub stdev_ms: f64,
+    /// 变异系数：标准差 / 平均帧时间
+    pub cv: f64,
+    /// 99% 的帧不超过的帧时间 (ms)
+    pub p99_ms: f64,
+    /// 99.9% 的帧不超过的帧时间 (ms)
+    pub p999_ms: f64,
+    /// 超过 16.7 ms（低于 60 FPS）的帧数
+    pub over_16ms: u64,
+    /// 超过 33.3 ms（低于 30 FPS）的帧数
+    pub over_33ms: u64,
+}
+
+impl FramePacing {
+    pub fn from_frames(frame_times: &[f64]) -> Self {
+        if frame_times.is_empty() {
+            return FramePacing::default();
+        }
+        let round2 = |v: f64| (v * 100.0).round() / 100.0;
+        let n = frame_times.len() as f64;
+        let mean = frame_times.iter().sum::<f64>() / n;
+        let variance = frame_times.iter().map(|ft| (ft - mean).powi(2)).sum::<f64>() / n;
+        let stdev = variance.sqrt();
+        let mut sorted = frame_times.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        FramePacing {
+            stdev_ms: round2(stdev),
+            cv: if mean > 0.0 {
+                (stdev / mean * 1000.0).round() / 1000.0
+            } else {
+                0.0
+            },
+            p99_ms: round2(frametime_percentile(&sorted, 99.0)),
+            p999_ms: round2(frametime_percentile(&sorted, 99.9)),
+            over_16ms: frame_times.iter().filter(|ft| **ft > 1000.0 / 60.0).count() as u64,
+            over_33ms: frame_times.iter().filter(|ft| **ft > 1000.0 / 30.0).count() as u64,
+        }
+    }
+
+    /// 由流式统计得出（百分位按直方图近似）
+    pub fn from_stats(stats: &FrameStats) -> Self {
+        if stats.count() == 0 {
+            return FramePacing::default();
+        }
+        let round2 = |v: f64| (v * 100.0).round() / 100.0;
+        let mean = stats.mean_ms();
+        let stdev = stats.stdev_ms();
+        FramePacing {
+            stdev_ms: round2(stdev),
+            cv: if mean > 0.0 {
+                (stdev / mean * 1000.0).round() / 1000.0
+            } else {
+                0.0
+            },
+            p99_ms: round2(stats.frametime_percentile(99.0)),
+            p999_ms: round2(stats.frametime_percentile(99.9)),
+            over_16ms: stats.over_16ms(),
+            over_33ms: stats.over_33ms(),
+        }
+    }
+}
+
+// ==================== 帧时间分布 ====================
+
+/// 帧时间分布（帧数），区间边界为 120 / 60 / 30 FPS 对应的帧时间
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct FrametimeHistogram {
+    /// < 8.33 ms
+    pub lt_8ms: u32,
+    /// 8.33 – 16.67 ms
+    pub ms_8_16: u32,
+    /// 16.67 – 33.33 ms
+    pub ms_16_33: u32,
+    /// >= 33.33 ms
+    pub gt_33ms: u32,
+}
+
+impl FrametimeHistogram {
+    pub fn from_frames(frame_times: &[f64]) -> Self {
+        let mut histogram = FrametimeHistogram::default();
+        for &ft in frame_times {
+            let bucket = if ft < 1000.0 / 120.0 {
+                &mut histogram.lt_8ms
+            } else if ft < 1000.0 / 60.0 {
+                &mut histogram.ms_8_16
+            } else if ft < 1000.0 / 30.0 {
+                &mut histogram.ms_16_33
+            } else {
+                &mut histogram.gt_33ms
+            };
+            *bucket += 1;
+        }
+        histogram
+    }
+
+    pub fn merge(&mut self, other: &FrametimeHistogram) {
+        self.lt_8ms += other.lt_8ms;
+        self.ms_8_16 += other.ms_8_16;
+        self.ms_16_33 += other.ms_16_33;
+        self.gt_33ms += other.gt_33ms;
+    }
+}
+
+// ==================== 百分位 ====================
+
+/// 自定义百分位指标
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PercentileMetrics {
+    /// 帧时间百分位 (ms)，如 P99 为 99% 的帧不超过的帧时间
+    #[serde(default)]
+    pub frametime_ms: Vec<PercentileValue>,
+    /// 自定义 Low FPS
+    #[serde(default)]
+    pub low_fps: Vec<PercentileValue>,
+}
+
+impl PercentileMetrics {
+    /// 计算帧时间百分位（如 95 → P95）与自定义 Low FPS（如 5 → 5% Low）
+    pub fn compute(
+        frametime_percentiles: &[f64],
+        low_percentiles: &[f64],
+        frame_times: &[f64],
+    ) -> Self {
+        if frame_times.is_empty() {
+            return PercentileMetrics::default();
+        }
+        let mut sorted = frame_times.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        PercentileMetrics {
+            frametime_ms: frametime_percentiles
+                .iter()
+                .map(|&p| PercentileValue {
+                    percentile: p,
+                    value: (frametime_percentile(&sorted, p) * 100.0).round() / 100.0,
+                })
+                .collect(),
+            low_fps: low_percentiles
+                .iter()
+                .map(|&p| PercentileValue {
+                    percentile: p,
+                    value: (percentile_low_fps(frame_times, p) * 10.0).round() / 10.0,
+                })
+                .collect(),
+        }
+    }
+
+    /// 对另一段帧时间按相同的百分位重新计算
+    pub fn recompute(&self, frame_times: &[f64]) -> Self {
+        let frametime: Vec<f64> = self.frametime_ms.iter().map(|v| v.percentile).collect();
+        let low: Vec<f64> = self.low_fps.iter().map(|v| v.percentile).collect();
+        PercentileMetrics::compute(&frametime, &low, frame_times)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PercentileValue {
+    pub percentile: f64,
+    pub value: f64,
+}
+
+/// 升序帧时间中第 `percentile` 百分位的值（最近秩法）
+pub fn frametime_percentile(sorted: &[f64], percentile: f64) -> f64 {
+    // 先乘后除，避免 99.9 / 100 的舍入误差把秩推到下一位
+    let rank = (percentile * sorted.len() as f64 / 100.0).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// 计算 percentile low FPS：最差 `percentile`% 帧的平均帧时间换算的 FPS
+pub fn percentile_low_fps(frame_times: &[f64], percentile: f64) -> f64 {
+    if frame_times.is_empty() {
+        return 0.0;
+    }
+    let mut sorted = frame_times.to_vec();
+    sorted.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+
+    let count = ((percentile / 100.0) * sorted.len() as f64).ceil() as usize;
+    let count = count.max(1).min(sorted.len());
+
+    let worst_times = &sorted[..count];
+    let avg_worst = worst_times.iter().sum::<f64>() / worst_times.len() as f64;
+
+    if avg_worst > 0.0 {
+        1000.0 / avg_worst
+    } else {
+        0.0
+    }
+}
@@ -0,0 +1,267 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// 采样间隔
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PcieLinkStatus {
+    /// GPU 名称
+    pub gpu_name: String,
+    /// 当前 PCIe 代数 (1-5)
+    pub current_gen: u32,
+    /// 当前通道数 (x16 = 16)
+    pub current_width: u32,
+    /// 设备支持的最高代数
+    pub max_gen: u32,
+    /// 设备支持的最大通道数
+    pub max_width: u32,
+}
+
+/// 链路异常类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PcieIssue {
+    /// 通道数低于设备能力 (x8/x4)
+    WidthLimited,
+    /// 最高代数低于设备能力（主板/CPU/BIOS 限制）
+    GenLimited,
+    /// 测试中曾降速（ASPM 或链路不稳定）
+    Downclocked,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PcieLinkReport {
+    pub gpu_name: String,
+    pub max_gen: u32,
+    pub max_width: u32,
+    /// 测试期间观察到的最低代数
+    pub min_observed_gen: u32,
+    /// 测试期间观察到的最少通道数
+    pub min_observed_width: u32,
+    /// 链路是否在测试中降速
+    pub downclocked: bool,
+    /// 链路是否以低于设备能力的通道数工作 (x8/x4)
+    pub width_limited: bool,
+    /// 采样次数
+    pub samples: u32,
+    /// 提示信息及其异常类型
+    pub warnings: Vec<(PcieIssue, String)>,
+}
+
+// ==================== 链路读取 (Windows) ====================
+
+#[cfg(target_os = "windows")]
+fn query_gpu_devices() -> Vec<(String, String)> {
+    use std::collections::HashMap;
+    use wmi::{COMLibrary, WMIConnection};
+
+    let Ok(com) = COMLibrary::new() else {
+        return vec![];
+    };
+    let Ok(wmi) = WMIConnection::new(com) else {
+        return vec![];
+    };
+    let results: Vec<HashMap<String, wmi::Variant>> = wmi
+        .raw_query("SELECT Name, PNPDeviceID FROM Win32_VideoController")
+        .unwrap_or_default();
+
+    results
+        .iter()
+        .filter_map(|item| {
+            let name = match item.get("Name") {
+                Some(wmi::Variant::String(s)) => s.clone(),
+                _ => return None,
+            };
+            let pnp = match item.get("PNPDeviceID") {
+                Some(wmi::Variant::String(s)) if s.starts_with("PCI\\") => s.clone(),
+                _ => return None,
+            };
+            Some((name, pnp))
+        })
+        .collect()
+}
+
+#[cfg(target_os = "windows")]
+fn read_u32_property(devinst: u32, pid: u32) -> Option<u32> {
+    use windows::core::GUID;
+    use windows::Win32::Devices::DeviceAndDriverInstallation::{
+        CM_Get_DevNode_PropertyW, CR_SUCCESS,
+    };
+    use windows::Win32::Devices::Properties::{DEVPROPKEY, DEVPROPTYPE};
+
+    // DEVPKEY_PciDevice_* 属性集
+    let key = DEVPROPKEY {
+        fmtid: GUID::from_u128(0x3ab22e31_8264_4b4e_9af5_a8d2d8e33e62),
+        pid,
+    };
+    let mut prop_type = DEVPROPTYPE(0);
+    let mut value = 0u32;
+    let mut size = std::mem::size_of::<u32>() as u32;
+
+    let result = unsafe {
+        CM_Get_DevNode_PropertyW(
+            devinst,
+            &key,
+            &mut prop_type,
+            Some(&mut value as *mut u32 as *mut u8),
+            &mut size,
+            0,
+        )
+    };
+    if result == CR_SUCCESS {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn read_link(name: &str, pnp_id: &str) -> Option<PcieLinkStatus> {
+    use windows::core::HSTRING;
+    use windows::Win32::Devices::DeviceAndDriverInstallation::{
+        CM_Locate_DevNodeW, CM_LOCATE_DEVNODE_NORMAL, CR_SUCCESS,
+    };
+
+    let mut devinst = 0u32;
+    let id = HSTRING::from(pnp_id);
+    let result = unsafe { CM_Locate_DevNodeW(&mut devinst, &id, CM_LOCATE_DEVNODE_NORMAL) };
+    if result != CR_SUCCESS {
+        return None;
+    }
+
+    Some(PcieLinkStatus {
+        gpu_name: name.to_string(),
+        current_gen: read_u32_property(devinst, 9)?,
+        current_width: read_u32_property(devinst, 10)?,
+        max_gen: read_u32_property(devinst, 11)?,
+        max_width: read_u32_property(devinst, 12)?,
+    })
+}
+
+#[cfg(target_os = "windows")]
+pub fn read_gpu_links() -> Vec<PcieLinkStatus> {
+    query_gpu_devices()
+        .iter()
+        .filter_map(|(name, pnp)| read_link(name, pnp))
+        .collect()
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn read_gpu_links() -> Vec<PcieLinkStatus> {
+    vec![]
+}
+
+// ==================== 测试期间采样 ====================
+
+/// 在 FPS 监测期间后台采样 PCIe 链路状态
+pub struct LinkSampler {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<Vec<PcieLinkStatus>>>,
+}
+
+impl LinkSampler {
+    pub fn start() -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = stop.clone();
+
+        let handle = std::thread::spawn(move || {
+            let mut samples = Vec::new();
+            let mut last = Instant::now() - SAMPLE_INTERVAL;
+            while !stop_flag.load(Ordering::Relaxed) {
+                if last.elapsed() >= SAMPLE_INTERVAL {
+                    // 只关注独立显卡（通道数最多的设备）
+                    if let Some(link) = read_gpu_links().into_iter().max_by_key(|l| l.max_width) {
+                        samples.push(link);
+                    }
+                    last = Instant::now();
+                }
+                std::thread::sleep(Duration::from_millis(200));
+            }
+            samples
+        });
+
+        LinkSampler {
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    /// 停止采样并生成报告
+    pub fn finish(mut self) -> Option<PcieLinkReport> {
+        self.stop.store(true, Ordering::Relaxed);
+        let samples = self.handle.take()?.join().ok()?;
+        build_report(&samples)
+    }
+}
+
+impl Drop for LinkSampler {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// 由测试期间的采样生成链路报告，没有采样时返回 None
+pub fn build_report(samples: &[PcieLinkStatus]) -> Option<PcieLinkReport> {
+    let first = samples.first()?;
+    // 第一个采样时 GPU 可能尚未满载，链路处于节能降速状态，跳过
+    let loaded = if samples.len() > 1 {
+        &samples[1..]
+    } else {
+        samples
+    };
+
+    let min_gen = loaded.iter().map(|s| s.current_gen).min().unwrap_or(0);
+    let min_width = loaded.iter().map(|s| s.current_width).min().unwrap_or(0);
+    let peak_gen = loaded.iter().map(|s| s.current_gen).max().unwrap_or(0);
+    let peak_width = loaded.iter().map(|s| s.current_width).max().unwrap_or(0);
+
+    let downclocked = min_gen < first.max_gen;
+    let width_limited = peak_width < first.max_width;
+
+    let mut warnings = Vec::new();
+    if width_limited {
+        warnings.push((
+            PcieIssue::WidthLimited,
+            format!(
+                "显卡支持 PCIe x{}，但实际工作在 x{}。请检查显卡是否插在主 PCIe 插槽、\
+                 是否使用了转接线/延长线，或主板是否因 M.2 占用拆分了通道。",
+                first.max_width, peak_width
+            ),
+        ));
+    }
+    if peak_gen < first.max_gen {
+        warnings.push((
+            PcieIssue::GenLimited,
+            format!(
+                "测试期间 PCIe 链路最高只达到 Gen{}（显卡支持 Gen{}），可能受主板/CPU 或 BIOS 设置限制。",
+                peak_gen, first.max_gen
+            ),
+        ));
+    } else if downclocked {
+        warnings.push((
+            PcieIssue::Downclocked,
+            format!(
+                "测试期间 PCIe 链路曾降至 Gen{}，可能是电源管理（ASPM）或链路不稳定导致。",
+                min_gen
+            ),
+        ));
+    }
+
+    Some(PcieLinkReport {
+        gpu_name: first.gpu_name.clone(),
+        max_gen: first.max_gen,
+        max_width: first.max_width,
+        min_observed_gen: min_gen,
+        min_observed_width: min_width,
+        downclocked,
+        width_limited,
+        samples: samples.len() as u32,
+        warnings,
+    })
+}
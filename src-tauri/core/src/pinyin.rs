@@ -0,0 +1,246 @@
+/// 汉字 → 拼音（不带声调）
+/// 覆盖已知游戏与国内平台游戏的中文名用字，表中没有的字原样保留
+/// 按字符排序，便于二分查找
+const PINYIN: &[(char, &str)] = &[
+    ('一', "yi"),
+    ('七', "qi"),
+    ('万', "wan"),
+    ('三', "san"),
+    ('下', "xia"),
+    ('与', "yu"),
+    ('世', "shi"),
+    ('中', "zhong"),
+    ('之', "zhi"),
+    ('九', "jiu"),
+    ('争', "zheng"),
+    ('二', "er"),
+    ('云', "yun"),
+    ('五', "wu"),
+    ('亚', "ya"),
+    ('亡', "wang"),
+    ('人', "ren"),
+    ('侠', "xia"),
+    ('先', "xian"),
+    ('光', "guang"),
+    ('克', "ke"),
+    ('全', "quan"),
+    ('八', "ba"),
+    ('公', "gong"),
+    ('六', "liu"),
+    ('兵', "bing"),
+    ('兽', "shou"),
+    ('写', "xie"),
+    ('刀', "dao"),
+    ('则', "ze"),
+    ('动', "dong"),
+    ('劫', "jie"),
+    ('勇', "yong"),
+    ('区', "qu"),
+    ('十', "shi"),
+    ('千', "qian"),
+    ('博', "bo"),
+    ('厂', "chang"),
+    ('原', "yuan"),
+    ('反', "fan"),
+    ('只', "zhi"),
+    ('司', "si"),
+    ('名', "ming"),
+    ('命', "ming"),
+    ('哈', "ha"),
+    ('四', "si"),
+    ('地', "di"),
+    ('坏', "huai"),
+    ('垒', "lei"),
+    ('城', "cheng"),
+    ('堡', "bao"),
+    ('塔', "ta"),
+    ('士', "shi"),
+    ('声', "sheng"),
+    ('夜', "ye"),
+    ('大', "da"),
+    ('天', "tian"),
+    ('头', "tou"),
+    ('契', "qi"),
+    ('奥', "ao"),
+    ('威', "wei"),
+    ('守', "shou"),
+    ('官', "guan"),
+    ('客', "ke"),
+    ('射', "she"),
+    ('小', "xiao"),
+    ('尔', "er"),
+    ('尖', "jian"),
+    ('岩', "yan"),
+    ('崩', "beng"),
+    ('工', "gong"),
+    ('巫', "wu"),
+    ('市', "shi"),
+    ('师', "shi"),
+    ('帕', "pa"),
+    ('平', "ping"),
+    ('幸', "xing"),
+    ('幻', "huan"),
+    ('度', "du"),
+    ('异', "yi"),
+    ('影', "ying"),
+    ('德', "de"),
+    ('怪', "guai"),
+    ('恐', "kong"),
+    ('恒', "heng"),
+    ('悟', "wu"),
+    ('战', "zhan"),
+    ('戮', "lu"),
+    ('手', "shou"),
+    ('拉', "la"),
+    ('救', "jiu"),
+    ('文', "wen"),
+    ('斯', "si"),
+    ('方', "fang"),
+    ('无', "wu"),
+    ('日', "ri"),
+    ('明', "ming"),
+    ('易', "yi"),
+    ('星', "xing"),
+    ('暗', "an"),
+    ('朋', "peng"),
+    ('望', "wang"),
+    ('杀', "sha"),
+    ('杯', "bei"),
+    ('极', "ji"),
+    ('林', "lin"),
+    ('格', "ge"),
+    ('森', "sen"),
+    ('死', "si"),
+    ('殿', "dian"),
+    ('毁', "hui"),
+    ('永', "yong"),
+    ('求', "qiu"),
+    ('沃', "wo"),
+    ('河', "he"),
+    ('法', "fa"),
+    ('泰', "tai"),
+    ('洞', "dong"),
+    ('洲', "zhou"),
+    ('海', "hai"),
+    ('消', "xiao"),
+    ('深', "shen"),
+    ('游', "you"),
+    ('漫', "man"),
+    ('潜', "qian"),
+    ('火', "huo"),
+    ('灭', "mie"),
+    ('灵', "ling"),
+    ('燕', "yan"),
+    ('物', "wu"),
+    ('狂', "kuang"),
+    ('狼', "lang"),
+    ('猎', "lie"),
+    ('环', "huan"),
+    ('瑞', "rui"),
+    ('生', "sheng"),
+    ('界', "jie"),
+    ('畏', "wei"),
+    ('留', "liu"),
+    ('症', "zheng"),
+    ('登', "deng"),
+    ('百', "bai"),
+    ('的', "de"),
+    ('盗', "dao"),
+    ('盟', "meng"),
+    ('神', "shen"),
+    ('福', "fu"),
+    ('程', "cheng"),
+    ('穹', "qiong"),
+    ('空', "kong"),
+    ('穿', "chuan"),
+    ('竞', "jing"),
+    ('米', "mi"),
+    ('精', "jing"),
+    ('约', "yue"),
+    ('线', "xian"),
+    ('细', "xi"),
+    ('绝', "jue"),
+    ('网', "wang"),
+    ('群', "qun"),
+    ('联', "lian"),
+    ('胞', "bao"),
+    ('腐', "fu"),
+    ('腾', "teng"),
+    ('致', "zhi"),
+    ('航', "hang"),
+    ('艾', "ai"),
+    ('芒', "mang"),
+    ('英', "ying"),
+    ('茨', "ci"),
+    ('茶', "cha"),
+    ('荒', "huang"),
+    ('蓝', "lan"),
+    ('蔚', "wei"),
+    ('蚀', "shi"),
+    ('行', "xing"),
+    ('角', "jiao"),
+    ('讯', "xun"),
+    ('话', "hua"),
+    ('语', "yu"),
+    ('谷', "gu"),
+    ('赎', "shu"),
+    ('赛', "sai"),
+    ('越', "yue"),
+    ('车', "che"),
+    ('辐', "fu"),
+    ('运', "yun"),
+    ('进', "jin"),
+    ('迪', "di"),
+    ('迷', "mi"),
+    ('逝', "shi"),
+    ('速', "su"),
+    ('道', "dao"),
+    ('遗', "yi"),
+    ('野', "ye"),
+    ('铁', "tie"),
+    ('银', "yin"),
+    ('锋', "feng"),
+    ('锤', "chui"),
+    ('镖', "biao"),
+    ('门', "men"),
+    ('间', "jian"),
+    ('际', "ji"),
+    ('限', "xian"),
+    ('雄', "xiong"),
+    ('零', "ling"),
+    ('霍', "huo"),
+    ('露', "lu"),
+    ('面', "mian"),
+    ('骑', "qi"),
+    ('鬼', "gui"),
+    ('魂', "hun"),
+    ('鲁', "lu"),
+    ('黑', "hei"),
+];
+
+fn lookup(c: char) -> Option<&'static str> {
+    PINYIN
+        .binary_search_by_key(&c, |&(ch, _)| ch)
+        .ok()
+        .map(|i| PINYIN[i].1)
+}
+
+/// 全拼 (e.g., "黑神话：悟空" → "heishenhua：wukong")
+pub fn full(text: &str) -> String {
+    text.chars()
+        .map(|c| match lookup(c) {
+            Some(py) => py.to_string(),
+            None => c.to_lowercase().to_string(),
+        })
+        .collect()
+}
+
+/// 拼音首字母 (e.g., "黑神话：悟空" → "hsh：wk")
+pub fn initials(text: &str) -> String {
+    text.chars()
+        .map(|c| match lookup(c).and_then(|py| py.chars().next()) {
+            Some(initial) => initial,
+            None => c.to_lowercase().next().unwrap_or(c),
+        })
+        .collect()
+}
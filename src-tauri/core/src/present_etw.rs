@@ -0,0 +1,220 @@
+use std::sync::mpsc::Receiver;
+
+/// 每个监测使用独立的会话名，异常退出后按会话 ID 停止遗留会话
+#[cfg(target_os = "windows")]
+fn session_name(session_id: &str) -> String {
+    format!("GameBench Present {}", session_id)
+}
+
+/// 与 PresentMon 输出相同名称的 CSV 列，读取线程按同一方式解析两种来源
+#[cfg(target_os = "windows")]
+const CSV_HEADER: &str =
+    "Application,ProcessID,SwapChainAddress,Runtime,CPUStartQPC,CPUStartTime,FrameTime";
+
+// ==================== ETW 会话 (Windows) ====================
+
+#[cfg(target_os = "windows")]
+mod trace {
+    use super::CSV_HEADER;
+    use crate::etw::{self, TraceSession};
+    use std::collections::{HashMap, HashSet};
+    use std::sync::mpsc::Sender;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use sysinfo::{Pid, System};
+    use windows::core::GUID;
+    use windows::Win32::System::Diagnostics::Etw::{EVENT_HEADER_FLAG_64_BIT_HEADER, EVENT_RECORD};
+
+    /// 会话 GUID（用户模式会话仅用于标识）
+    const SESSION_GUID: GUID = GUID::from_u128(0x3b7d9e21_6c4f_4a8e_b1d5_7e2f0c9a4d63);
+    /// Microsoft-Windows-DXGI，事件 42 = Present_Start
+    const DXGI_PROVIDER: GUID = GUID::from_u128(0xca11c036_0102_4a2d_a6ad_f03cfed5d3c9);
+    const DXGI_PRESENT_START: u16 = 42;
+    /// Microsoft-Windows-D3D9，事件 1 = Present_Start
+    const D3D9_PROVIDER: GUID = GUID::from_u128(0x783aca0a_790e_4d7f_8451_aa850511c6b9);
+    const D3D9_PRESENT_START: u16 = 1;
+    /// 检查目标进程是否已退出的间隔
+    const EXIT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// 监测目标：按 PID，或按进程名（首次见到的 PID 查询一次进程名并缓存）
+    struct Target {
+        pid: Option<u32>,
+        process_name: String,
+        matches: HashMap<u32, bool>,
+        sys: System,
+    }
+
+    impl Target {
+        fn matches(&mut self, pid: u32) -> bool {
+            if let Some(target) = self.pid {
+                return pid == target;
+            }
+            if let Some(matched) = self.matches.get(&pid) {
+                return *matched;
+            }
+            let sys_pid = Pid::from_u32(pid);
+            self.sys
+                .refresh_processes(sysinfo::ProcessesToUpdate::Some(&[sys_pid]), true);
+            let matched = self.sys.process(sys_pid).is_some_and(|p| {
+                p.name()
+                    .to_string_lossy()
+                    .eq_ignore_ascii_case(&self.process_name)
+            });
+            self.matches.insert(pid, matched);
+            matched
+        }
+    }
+
+    /// 目标进程全部退出后关闭输出，读取线程随即结束（对应 PresentMon 的 --terminate_on_proc_exit）
+    fn watch_exit(tx: Arc<Mutex<Option<Sender<String>>>>, presenting: Arc<Mutex<HashSet<u32>>>) {
+        std::thread::spawn(move || {
+            let mut sys = System::new();
+            loop {
+                std::thread::sleep(EXIT_POLL_INTERVAL);
+                // 会话已停止，回调持有的引用已释放
+                if Arc::strong_count(&tx) == 1 || tx.lock().unwrap().is_none() {
+                    break;
+                }
+                let pids: Vec<Pid> = presenting
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .map(|pid| Pid::from_u32(*pid))
+                    .collect();
+                if pids.is_empty() {
+                    continue;
+                }
+                sys.refresh_processes(sysinfo::ProcessesToUpdate::Some(&pids), true);
+                if pids.iter().all(|pid| sys.process(*pid).is_none()) {
+                    log::info!("目标进程已退出，结束内置 ETW 采集");
+                    tx.lock().unwrap().take();
+                    break;
+                }
+            }
+        });
+    }
+
+    /// 事件载荷开头的交换链指针
+    fn swapchain_of(record: &EVENT_RECORD) -> u64 {
+        let wide = u32::from(record.EventHeader.Flags) & EVENT_HEADER_FLAG_64_BIT_HEADER != 0;
+        let data = record.UserData as *const u8;
+        unsafe {
+            if wide && record.UserDataLength >= 8 {
+                (data as *const u64).read_unaligned()
+            } else if record.UserDataLength >= 4 {
+                (data as *const u32).read_unaligned() as u64
+            } else {
+                0
+            }
+        }
+    }
+
+    pub fn start(
+        name: &str,
+        pid: Option<u32>,
+        process_name: &str,
+        tx: Sender<String>,
+    ) -> Result<TraceSession, String> {
+        let _ = tx.send(format!("{}\n", CSV_HEADER));
+        let tx = Arc::new(Mutex::new(Some(tx)));
+        let presenting = Arc::new(Mutex::new(pid.into_iter().collect::<HashSet<u32>>()));
+        watch_exit(tx.clone(), presenting.clone());
+
+        let freq = etw::qpc_frequency() as f64;
+        let start = etw::qpc_now();
+        let mut target = Target {
+            pid,
+            process_name: process_name.to_string(),
+            matches: HashMap::new(),
+            sys: System::new(),
+        };
+        let application = process_name.to_string();
+        // (PID, 交换链) → 上一次 Present 的 QPC
+        let mut last_present: HashMap<(u32, u64), i64> = HashMap::new();
+
+        TraceSession::start_user(
+            name,
+            SESSION_GUID,
+            &[DXGI_PROVIDER, D3D9_PROVIDER],
+            Box::new(move |record| {
+                let header = &record.EventHeader;
+                let runtime = match (header.ProviderId, header.EventDescriptor.Id) {
+                    (DXGI_PROVIDER, DXGI_PRESENT_START) => "DXGI",
+                    (D3D9_PROVIDER, D3D9_PRESENT_START) => "D3D9",
+                    _ => return,
+                };
+                let process_id = header.ProcessId;
+                if !target.matches(process_id) {
+                    return;
+                }
+                let swapchain = swapchain_of(record);
+                let qpc = header.TimeStamp;
+                let Some(previous) = last_present.insert((process_id, swapchain), qpc) else {
+                    presenting.lock().unwrap().insert(process_id);
+                    return;
+                };
+                let frametime_ms = (qpc - previous) as f64 * 1000.0 / freq;
+                let since_start_ms = (qpc - start) as f64 * 1000.0 / freq;
+                if let Some(tx) = tx.lock().unwrap().as_ref() {
+                    let _ = tx.send(format!(
+                        "{},{},0x{:X},{},{},{:.4},{:.4}\n",
+                        application,
+                        process_id,
+                        swapchain,
+                        runtime,
+                        qpc,
+                        since_start_ms,
+                        frametime_ms
+                    ));
+                }
+            }),
+        )
+    }
+}
+
+// ==================== 采集 ====================
+
+/// 进程内的 Present 事件采集，Drop 时停止 ETW 会话
+pub struct PresentTrace {
+    #[cfg(target_os = "windows")]
+    _session: crate::etw::TraceSession,
+}
+
+impl PresentTrace {
+    /// 停止 ETW 会话，消费线程随之结束并关闭输出
+    pub fn stop(self) {}
+}
+
+/// 直接消费 DXGI / D3D9 的 Present 事件，按 PresentMon 的 CSV 格式逐行输出帧时间
+/// 帧时间为同一交换链相邻两次 Present 的间隔（与 PresentMon 的 MsBetweenPresents 相同）
+#[cfg(target_os = "windows")]
+pub fn start(
+    session_id: &str,
+    pid: Option<u32>,
+    process_name: &str,
+) -> Result<(PresentTrace, Receiver<String>), String> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let session = trace::start(&session_name(session_id), pid, process_name, tx)?;
+    log::info!("启动内置 ETW 采集: {} (pid: {:?})", process_name, pid);
+    Ok((PresentTrace { _session: session }, rx))
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn start(
+    _session_id: &str,
+    _pid: Option<u32>,
+    _process_name: &str,
+) -> Result<(PresentTrace, Receiver<String>), String> {
+    Err("内置 ETW 采集仅支持 Windows".to_string())
+}
+
+/// 停止上次异常退出时遗留的采集会话
+#[cfg(target_os = "windows")]
+pub fn stop_orphan(session_id: &str) -> bool {
+    crate::etw::stop_by_name(&session_name(session_id))
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn stop_orphan(_session_id: &str) -> bool {
+    false
+}
@@ -0,0 +1,80 @@
+use crate::presentmon::FrameRow;
+use serde::{Deserialize, Serialize};
+
+// ==================== 数据结构 ====================
+
+/// 呈现方式摘要：占多数的呈现模式、垂直同步与撕裂
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PresentInfo {
+    /// 占比最高的呈现模式，如 "Hardware: Independent Flip"
+    pub present_mode: String,
+    /// 该模式的帧占比 (%)
+    pub present_mode_pct: f64,
+    /// 画面经由 DWM 合成（"Composed: ..."），会增加延迟，并可能被桌面刷新率限制帧率
+    pub composed: bool,
+    /// 出现最多的 SyncInterval
+    pub sync_interval: Option<i32>,
+    /// 垂直同步是否开启（SyncInterval ≥ 1）
+    pub vsync: Option<bool>,
+    /// 多数帧允许撕裂（DXGI_PRESENT_ALLOW_TEARING）
+    pub allows_tearing: Option<bool>,
+}
+
+// ==================== 统计 ====================
+
+/// 逐帧累计呈现模式；不同取值通常只有一两种，线性查找即可
+#[derive(Debug, Clone, Default)]
+pub struct PresentStats {
+    modes: Vec<(String, u64)>,
+    intervals: Vec<(i32, u64)>,
+    tearing: u64,
+    tearing_known: u64,
+}
+
+fn count<T: PartialEq>(counts: &mut Vec<(T, u64)>, value: T) {
+    match counts.iter_mut().find(|(v, _)| *v == value) {
+        Some((_, n)) => *n += 1,
+        None => counts.push((value, 1)),
+    }
+}
+
+impl PresentStats {
+    pub fn push(&mut self, row: &FrameRow) {
+        if !row.present_mode.is_empty() {
+            match self.modes.iter_mut().find(|(m, _)| *m == row.present_mode) {
+                Some((_, n)) => *n += 1,
+                None => self.modes.push((row.present_mode.clone(), 1)),
+            }
+        }
+        if let Some(interval) = row.sync_interval {
+            count(&mut self.intervals, interval);
+        }
+        if let Some(tearing) = row.allows_tearing {
+            self.tearing_known += 1;
+            self.tearing += tearing as u64;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = PresentStats::default();
+    }
+
+    /// 采集来源不提供呈现模式（如内置 ETW 采集）时返回 None
+    pub fn summary(&self) -> Option<PresentInfo> {
+        let total: u64 = self.modes.iter().map(|(_, n)| n).sum();
+        let (mode, frames) = self.modes.iter().max_by_key(|(_, n)| *n)?;
+        let sync_interval = self
+            .intervals
+            .iter()
+            .max_by_key(|(_, n)| *n)
+            .map(|(v, _)| *v);
+        Some(PresentInfo {
+            present_mode: mode.clone(),
+            present_mode_pct: (*frames as f64 / total as f64 * 1000.0).round() / 10.0,
+            composed: mode.starts_with("Composed"),
+            sync_interval,
+            vsync: sync_interval.map(|v| v >= 1),
+            allows_tearing: (self.tearing_known > 0).then(|| self.tearing * 2 > self.tearing_known),
+        })
+    }
+}
@@ -0,0 +1,155 @@
+/// PresentMon 单帧数据
+pub struct FrameRow {
+    pub frametime: f64,
+    pub cpu_busy: f64,
+    pub gpu_busy: f64,
+    pub swapchain: String,
+    /// 呈现模式（`PresentMode` 列），内置 ETW 采集不提供时为空
+    pub present_mode: String,
+    /// `SyncInterval` 列，0 为关闭垂直同步
+    pub sync_interval: Option<i32>,
+    /// `AllowsTearing` 列
+    pub allows_tearing: Option<bool>,
+    /// `DisplayLatency` 列 (ms)，帧未显示时为 None
+    pub display_latency: Option<f64>,
+    /// `ClickToPhotonLatency` 列 (ms)，只有带鼠标点击的帧才有
+    pub click_to_photon: Option<f64>,
+    /// 帧时间戳 (ms)
+    pub timestamp_ms: Option<f64>,
+    /// 帧开始时的 QPC 计数（`--qpc_time`）
+    pub qpc: Option<i64>,
+}
+
+/// 从 PresentMon CSV 行中解析帧时间数据
+/// CSV 列 (v2): Application,ProcessID,SwapChainAddress,Runtime,SyncInterval,
+///              PresentFlags,AllowsTearing,PresentMode,CPUStartTime,CPUStartQPC,
+///              FrameTime,CPUBusy,CPUWait,GPULatency,GPUTime,GPUBusy,...,
+///              DisplayLatency,DisplayedTime,...,ClickToPhotonLatency
+/// `--qpc_time` 时 v2 输出 CPUStartQPC，v1 输出 QPCTime
+pub fn parse_csv_line(header: &[String], line: &str) -> Option<FrameRow> {
+    let fields: Vec<&str> = line.split(',').collect();
+    if fields.len() < 5 {
+        return None;
+    }
+
+    // 通过列名找索引
+    let frametime_idx = header
+        .iter()
+        .position(|h| h == "FrameTime" || h == "MsBetweenPresents")?;
+    // 缺少的列按 0 计（不能退回第 0 列，列顺序不固定时第 0 列可能就是帧时间）
+    let cpu_idx = header.iter().position(|h| h == "CPUBusy");
+    let gpu_idx = header.iter().position(|h| h == "GPUBusy" || h == "GPUTime");
+    let swapchain_idx = header.iter().position(|h| h == "SwapChainAddress");
+    let present_mode_idx = header.iter().position(|h| h == "PresentMode");
+    let sync_interval_idx = header.iter().position(|h| h == "SyncInterval");
+    let tearing_idx = header.iter().position(|h| h == "AllowsTearing");
+    let display_latency_idx = header.iter().position(|h| h == "DisplayLatency");
+    let click_idx = header.iter().position(|h| h == "ClickToPhotonLatency");
+    // v2 的 CPUStartTime 单位为 ms，v1 的 TimeInSeconds 单位为秒
+    let time_col = header
+        .iter()
+        .position(|h| h == "CPUStartTime")
+        .map(|i| (i, 1.0))
+        .or_else(|| {
+            header
+                .iter()
+                .position(|h| h == "TimeInSeconds")
+                .map(|i| (i, 1000.0))
+        });
+
+    let qpc_idx = header
+        .iter()
+        .position(|h| h == "CPUStartQPC" || h == "QPCTime");
+
+    let frametime: f64 = fields.get(frametime_idx)?.parse().ok()?;
+    let cpu_busy: f64 = cpu_idx
+        .and_then(|i| fields.get(i))
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0.0);
+    let gpu_busy: f64 = gpu_idx
+        .and_then(|i| fields.get(i))
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0.0);
+    let swapchain = swapchain_idx
+        .and_then(|i| fields.get(i))
+        .map(|s| s.to_string())
+        .unwrap_or_default();
+    let present_mode = present_mode_idx
+        .and_then(|i| fields.get(i))
+        .map(|s| s.trim().to_string())
+        .unwrap_or_default();
+    let sync_interval = sync_interval_idx
+        .and_then(|i| fields.get(i))
+        .and_then(|s| s.trim().parse::<i32>().ok());
+    let allows_tearing = tearing_idx
+        .and_then(|i| fields.get(i))
+        .and_then(|s| s.trim().parse::<u8>().ok())
+        .map(|v| v != 0);
+    // 没有数据时为 "NA"
+    let latency = |idx: Option<usize>| {
+        idx.and_then(|i| fields.get(i))
+            .and_then(|s| s.trim().parse::<f64>().ok())
+            .filter(|ms| *ms >= 0.0)
+    };
+    let display_latency = latency(display_latency_idx);
+    let click_to_photon = latency(click_idx);
+    let timestamp_ms = time_col.and_then(|(i, scale)| {
+        fields
+            .get(i)
+            .and_then(|s| s.parse::<f64>().ok())
+            .map(|t| t * scale)
+    });
+    let qpc = qpc_idx
+        .and_then(|i| fields.get(i))
+        .and_then(|s| s.trim().parse::<i64>().ok());
+
+    if frametime > 0.0 && frametime < 1000.0 {
+        Some(FrameRow {
+            frametime,
+            cpu_busy,
+            gpu_busy,
+            swapchain,
+            present_mode,
+            sync_interval,
+            allows_tearing,
+            display_latency,
+            click_to_photon,
+            timestamp_ms,
+            qpc,
+        })
+    } else {
+        None
+    }
+}
+
+// ==================== 输出流 ====================
+
+/// 逐行读取 PresentMon（或内置 ETW 采集）的 CSV 输出：第一行为表头，之后为帧数据
+#[derive(Default)]
+pub struct CsvStream {
+    header: Vec<String>,
+}
+
+impl CsvStream {
+    /// 输入一行输出，帧数据解析成功时返回该帧；表头、空行与无法解析的行返回 None
+    pub fn push_line(&mut self, line: &str) -> Option<FrameRow> {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            return None;
+        }
+        if self.header.is_empty() {
+            self.header = trimmed.split(',').map(|s| s.trim().to_string()).collect();
+            log::info!(
+                "PresentMon CSV 列: {:?}",
+                &self.header[..self.header.len().min(10)]
+            );
+            return None;
+        }
+        parse_csv_line(&self.header, trimmed)
+    }
+
+    /// 采集重新启动后会重新输出表头
+    pub fn reset(&mut self) {
+        self.header.clear();
+    }
+}
@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+
+/// 未知刷新率时按 60 Hz 估算
+const DEFAULT_REFRESH_HZ: f64 = 60.0;
+/// 常见 VRR 显示器的刷新率下限 (Hz)，低于此帧率时 VRR 失效（或进入 LFC 倍帧）
+pub const VRR_FLOOR_HZ: f64 = 48.0;
+/// 低于该帧率时流畅度记为 0
+const FLUENCY_FLOOR_FPS: f64 = 20.0;
+
+/// 各分项权重：流畅度 / 一致性 / 卡顿 / 刷新率匹配
+const WEIGHTS: [f64; 4] = [0.35, 0.25, 0.25, 0.15];
+/// 分项参与加权几何平均时的下限，避免单项为 0 时总分直接归零
+const PART_FLOOR: f64 = 0.05;
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QoeGrade {
+    Excellent,
+    Good,
+    Fair,
+    Poor,
+}
+
+impl QoeGrade {
+    fn from_score(score: f64) -> Self {
+        if score >= 85.0 {
+            QoeGrade::Excellent
+        } else if score >= 70.0 {
+            QoeGrade::Good
+        } else if score >= 50.0 {
+            QoeGrade::Fair
+        } else {
+            QoeGrade::Poor
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            QoeGrade::Excellent => "非常流畅",
+            QoeGrade::Good => "流畅",
+            QoeGrade::Fair => "一般",
+            QoeGrade::Poor => "卡顿明显",
+        }
+    }
+}
+
+/// 体验评分 (0–100)，按人眼感知而不是平均帧率计算
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QoeScore {
+    pub score: f64,
+    pub grade: QoeGrade,
+    /// 流畅度：平均帧率相对刷新率（对数尺度，超过刷新率不再加分）
+    pub fluency: f64,
+    /// 一致性：相邻帧的帧时间变化，感知上的"顿挫"
+    pub consistency: f64,
+    /// 卡顿：每分钟卡顿次数
+    pub stutter: f64,
+    /// 刷新率匹配：落在 VRR 区间内的帧比例
+    pub refresh_fit: f64,
+    /// 计算使用的刷新率 (Hz)，未知时为 None（按 60 Hz 估算）
+    pub refresh_hz: Option<f64>,
+    /// 每分钟卡顿次数
+    pub stutters_per_min: f64,
+    /// 低于 VRR 下限的帧占比 (%)
+    pub below_vrr_pct: f64,
+}
+
+// ==================== 计算 ====================
+
+fn round1(v: f64) -> f64 {
+    (v * 10.0).round() / 10.0
+}
+
+fn round3(v: f64) -> f64 {
+    (v * 1000.0).round() / 1000.0
+}
+
+/// 逐帧累计体验评分所需的数据，不保留帧时间序列
+#[derive(Debug, Clone)]
+pub struct QoeAccumulator {
+    refresh_hz: Option<f64>,
+    frames: u64,
+    total_ms: f64,
+    /// 相邻帧时间差绝对值之和
+    jitter_ms: f64,
+    /// 慢于 VRR 下限的帧数
+    below: u64,
+    prev: Option<f64>,
+}
+
+impl QoeAccumulator {
+    pub fn new(refresh_hz: Option<f64>) -> Self {
+        QoeAccumulator {
+            refresh_hz: refresh_hz.filter(|hz| *hz > 0.0),
+            frames: 0,
+            total_ms: 0.0,
+            jitter_ms: 0.0,
+            below: 0,
+            prev: None,
+        }
+    }
+
+    fn refresh(&self) -> f64 {
+        self.refresh_hz.unwrap_or(DEFAULT_REFRESH_HZ)
+    }
+
+    pub fn push(&mut self, frametime_ms: f64) {
+        if !(frametime_ms > 0.0 && frametime_ms.is_finite()) {
+            return;
+        }
+        self.frames += 1;
+        self.total_ms += frametime_ms;
+        if let Some(prev) = self.prev {
+            self.jitter_ms += (frametime_ms - prev).abs();
+        }
+        self.prev = Some(frametime_ms);
+        // 慢于 VRR 下限的帧无法被显示器匹配，出现抖动或撕裂
+        if frametime_ms > 1000.0 / VRR_FLOOR_HZ.min(self.refresh()) {
+            self.below += 1;
+        }
+    }
+
+    /// 帧数不足时返回 None
+    pub fn finish(&self, stutter_count: u32) -> Option<QoeScore> {
+        if self.frames < 2 {
+            return None;
+        }
+        let refresh = self.refresh();
+        let total_ms = self.total_ms;
+        let mean_ft = total_ms / self.frames as f64;
+
+        // 帧率感知近似对数：30→60 FPS 的提升远大于 90→120
+        let avg_fps = (1000.0 / mean_ft).min(refresh);
+        let target = refresh.max(FLUENCY_FLOOR_FPS + 1.0);
+        let fluency = ((avg_fps.ln() - FLUENCY_FLOOR_FPS.ln())
+            / (target.ln() - FLUENCY_FLOOR_FPS.ln()))
+        .clamp(0.0, 1.0);
+
+        // 相邻帧时间差的均值相对平均帧时间：0.05 时约 0.82，0.2 时约 0.45
+        let jitter = self.jitter_ms / (self.frames - 1) as f64 / mean_ft;
+        let consistency = (-4.0 * jitter).exp();
+
+        // 每分钟 3 次卡顿时约 0.37
+        let minutes = total_ms / 60_000.0;
+        let stutters_per_min = if minutes > 0.0 {
+            stutter_count as f64 / minutes
+        } else {
+            0.0
+        };
+        let stutter = (-stutters_per_min / 3.0).exp();
+
+        let below_fraction = self.below as f64 / self.frames as f64;
+        let refresh_fit = (1.0 - below_fraction * 5.0).max(0.0);
+
+        // 加权几何平均：任一分项很差时体验都差，不能由其他分项抵消
+        let parts = [fluency, consistency, stutter, refresh_fit];
+        let score: f64 = parts
+            .iter()
+            .zip(WEIGHTS)
+            .map(|(p, w)| p.max(PART_FLOOR).powf(w))
+            .product::<f64>()
+            * 100.0;
+        let score = round1(score);
+
+        Some(QoeScore {
+            score,
+            grade: QoeGrade::from_score(score),
+            fluency: round3(fluency),
+            consistency: round3(consistency),
+            stutter: round3(stutter),
+            refresh_fit: round3(refresh_fit),
+            refresh_hz: self.refresh_hz,
+            stutters_per_min: round1(stutters_per_min),
+            below_vrr_pct: round1(below_fraction * 100.0),
+        })
+    }
+}
+
+impl QoeScore {
+    /// 由帧时间、卡顿次数与显示器刷新率计算体验评分，帧数不足时返回 None
+    pub fn from_frames(
+        frame_times: &[f64],
+        stutter_count: u32,
+        refresh_hz: Option<f64>,
+    ) -> Option<QoeScore> {
+        let mut qoe = QoeAccumulator::new(refresh_hz);
+        for &ft in frame_times {
+            qoe.push(ft);
+        }
+        qoe.finish(stutter_count)
+    }
+}
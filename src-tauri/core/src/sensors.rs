@@ -0,0 +1,450 @@
+use crate::events::EventSink;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use sysinfo::{Components, System};
+
+/// 轮询线程的调度粒度
+const TICK: Duration = Duration::from_millis(100);
+/// 允许的轮询间隔范围
+const MIN_INTERVAL: Duration = Duration::from_millis(250);
+const MAX_INTERVAL: Duration = Duration::from_secs(60);
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SensorKind {
+    Cpu,
+    CpuTemp,
+    Gpu,
+    Ram,
+    Battery,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CpuReading {
+    /// 总占用率 (%)
+    pub usage_percent: f64,
+    /// 当前频率 (MHz)
+    pub frequency_mhz: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuReading {
+    /// 3D 引擎占用率最高的适配器的占用率 (%)
+    pub utilization_percent: f64,
+    /// 已用专用显存 (MB)
+    pub dedicated_used_mb: u64,
+    /// 各适配器分别的读数（多显卡系统，如核显负责采集推流）
+    #[serde(default)]
+    pub adapters: Vec<AdapterReading>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AdapterReading {
+    /// 适配器 LUID（与 GpuAdapter.luid 对应）
+    pub luid: String,
+    /// 适配器名称（无法匹配 DXGI 适配器时为 None）
+    pub name: Option<String>,
+    /// 3D 引擎占用率 (%)
+    pub utilization_percent: f64,
+    /// 已用专用显存 (MB)
+    pub dedicated_used_mb: u64,
+    /// 各进程在该适配器上的 3D 引擎占用率 (%)，键为 PID
+    #[serde(skip)]
+    pub processes: HashMap<u32, u64>,
+}
+
+/// 快照中单个适配器的占用情况
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdapterUsage {
+    pub luid: String,
+    pub name: Option<String>,
+    /// 整机在该适配器上的 3D 引擎占用率 (%)
+    pub utilization_percent: f64,
+    /// 被监测进程在该适配器上的 3D 引擎占用率 (%)
+    pub process_percent: f64,
+    /// 是否为游戏渲染所在的适配器
+    pub render: bool,
+    /// GPU 占用时间 (ms)，只归属到渲染适配器
+    pub gpu_busy_ms: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RamReading {
+    pub used_gb: f64,
+    pub total_gb: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatteryReading {
+    /// 剩余电量 (%)
+    pub percent: u8,
+    /// 是否接通电源
+    pub on_ac: bool,
+    pub charging: bool,
+}
+
+/// 各传感器最近一次读数（未订阅或无法读取时为 None）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SensorReadings {
+    pub cpu: Option<CpuReading>,
+    /// CPU 温度 (°C)
+    pub cpu_temp_c: Option<f64>,
+    pub gpu: Option<GpuReading>,
+    pub ram: Option<RamReading>,
+    pub battery: Option<BatteryReading>,
+}
+
+// ==================== 订阅管理 ====================
+
+struct Subscription {
+    kinds: Vec<SensorKind>,
+    interval: Duration,
+}
+
+#[derive(Default)]
+struct Service {
+    subscriptions: HashMap<u64, Subscription>,
+    next_id: u64,
+    readings: SensorReadings,
+    /// 轮询线程是否在运行（无订阅时线程退出）
+    running: bool,
+}
+
+fn service() -> &'static Arc<Mutex<Service>> {
+    static SERVICE: OnceLock<Arc<Mutex<Service>>> = OnceLock::new();
+    SERVICE.get_or_init(|| Arc::new(Mutex::new(Service::default())))
+}
+
+/// 有前端订阅时用于推送 `sensors-updated` 事件
+fn event_target() -> &'static OnceLock<Arc<dyn EventSink>> {
+    static SINK: OnceLock<Arc<dyn EventSink>> = OnceLock::new();
+    &SINK
+}
+
+/// 设置 `sensors-updated` 事件出口（只有第一次设置生效）
+pub fn set_event_sink(sink: Arc<dyn EventSink>) {
+    let _ = event_target().set(sink);
+}
+
+/// 订阅句柄，drop 时自动取消订阅
+pub struct SensorSubscription {
+    id: u64,
+}
+
+impl Drop for SensorSubscription {
+    fn drop(&mut self) {
+        unsubscribe(self.id);
+    }
+}
+
+/// 添加订阅并返回 id，需由调用方通过 `unsubscribe` 取消
+pub fn add_subscription(kinds: &[SensorKind], interval: Duration) -> u64 {
+    let mut state = service().lock().unwrap();
+    state.next_id += 1;
+    let id = state.next_id;
+    state.subscriptions.insert(
+        id,
+        Subscription {
+            kinds: kinds.to_vec(),
+            interval: interval.clamp(MIN_INTERVAL, MAX_INTERVAL),
+        },
+    );
+    if !state.running {
+        state.running = true;
+        std::thread::spawn(poll_loop);
+    }
+    id
+}
+
+/// 订阅传感器，按 `interval` 轮询（多个订阅取最短间隔）
+pub fn subscribe(kinds: &[SensorKind], interval: Duration) -> SensorSubscription {
+    SensorSubscription {
+        id: add_subscription(kinds, interval),
+    }
+}
+
+/// 取消订阅，id 不存在时返回 false
+pub fn unsubscribe(id: u64) -> bool {
+    service()
+        .lock()
+        .unwrap()
+        .subscriptions
+        .remove(&id)
+        .is_some()
+}
+
+/// 最近一次读数
+pub fn latest() -> SensorReadings {
+    service().lock().unwrap().readings.clone()
+}
+
+/// 各传感器当前的轮询间隔（没有订阅的传感器不轮询）
+fn active_intervals(state: &Service) -> HashMap<SensorKind, Duration> {
+    let mut intervals: HashMap<SensorKind, Duration> = HashMap::new();
+    for sub in state.subscriptions.values() {
+        for kind in &sub.kinds {
+            let entry = intervals.entry(*kind).or_insert(sub.interval);
+            *entry = (*entry).min(sub.interval);
+        }
+    }
+    intervals
+}
+
+// ==================== 适配器归属 ====================
+
+/// 按适配器拆分最近的 GPU 读数
+/// 被监测进程 3D 占用最高的适配器视为渲染适配器，PresentMon 的 GPU 占用时间只计入该适配器
+pub fn adapter_usage(
+    readings: &SensorReadings,
+    pid: Option<u32>,
+    gpu_busy_ms: f64,
+) -> Vec<AdapterUsage> {
+    let Some(gpu) = &readings.gpu else {
+        return vec![];
+    };
+    let share = |a: &AdapterReading| {
+        pid.and_then(|pid| a.processes.get(&pid))
+            .copied()
+            .unwrap_or(0)
+    };
+    let render = gpu
+        .adapters
+        .iter()
+        .filter(|a| share(a) > 0)
+        .max_by_key(|a| share(a))
+        .map(|a| a.luid.clone());
+
+    gpu.adapters
+        .iter()
+        .map(|a| {
+            let is_render = render.as_ref() == Some(&a.luid);
+            AdapterUsage {
+                luid: a.luid.clone(),
+                name: a.name.clone(),
+                utilization_percent: a.utilization_percent,
+                process_percent: share(a).min(100) as f64,
+                render: is_render,
+                gpu_busy_ms: is_render.then_some(gpu_busy_ms),
+            }
+        })
+        .collect()
+}
+
+/// 适配器 LUID → 名称，遇到未知 LUID 时重新枚举（显卡热插拔、驱动重置后 LUID 会变化）
+#[cfg(target_os = "windows")]
+fn adapter_name(luid: &str) -> Option<String> {
+    static NAMES: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+    let mut names = NAMES.get_or_init(Default::default).lock().unwrap();
+    if !names.contains_key(luid) {
+        *names = crate::gpu_routing::list_adapters()
+            .into_iter()
+            .map(|a| (a.luid, a.name))
+            .collect();
+    }
+    names.get(luid).cloned()
+}
+
+// ==================== 轮询 ====================
+
+/// 唯一的轮询线程，复用同一个 System 实例
+fn poll_loop() {
+    let mut sys = System::new();
+    let mut components = Components::new();
+    let mut last_poll: HashMap<SensorKind, Instant> = HashMap::new();
+
+    loop {
+        let intervals = {
+            let mut state = service().lock().unwrap();
+            if state.subscriptions.is_empty() {
+                state.running = false;
+                state.readings = SensorReadings::default();
+                return;
+            }
+            active_intervals(&state)
+        };
+
+        let due: Vec<SensorKind> = intervals
+            .iter()
+            .filter(|(kind, interval)| {
+                last_poll
+                    .get(kind)
+                    .is_none_or(|last| last.elapsed() >= **interval)
+            })
+            .map(|(kind, _)| *kind)
+            .collect();
+
+        if !due.is_empty() {
+            let mut readings = latest();
+            for kind in &due {
+                match kind {
+                    SensorKind::Cpu => readings.cpu = Some(read_cpu(&mut sys)),
+                    SensorKind::CpuTemp => readings.cpu_temp_c = read_cpu_temp(&mut components),
+                    SensorKind::Gpu => readings.gpu = read_gpu(),
+                    SensorKind::Ram => readings.ram = Some(read_ram(&mut sys)),
+                    SensorKind::Battery => readings.battery = read_battery(),
+                }
+                last_poll.insert(*kind, Instant::now());
+            }
+            service().lock().unwrap().readings = readings.clone();
+            if let Some(sink) = event_target().get() {
+                sink.emit("sensors-updated", &readings);
+            }
+        }
+
+        std::thread::sleep(TICK);
+    }
+}
+
+fn read_cpu(sys: &mut System) -> CpuReading {
+    sys.refresh_cpu_all();
+    CpuReading {
+        usage_percent: (sys.global_cpu_usage() as f64 * 10.0).round() / 10.0,
+        frequency_mhz: sys.cpus().first().map(|c| c.frequency()).unwrap_or(0),
+    }
+}
+
+fn read_ram(sys: &mut System) -> RamReading {
+    sys.refresh_memory();
+    let gb = |bytes: u64| (bytes as f64 / (1024.0 * 1024.0 * 1024.0) * 10.0).round() / 10.0;
+    RamReading {
+        used_gb: gb(sys.used_memory()),
+        total_gb: gb(sys.total_memory()),
+    }
+}
+
+/// CPU 温度：取 CPU 相关传感器的最高值
+fn read_cpu_temp(components: &mut Components) -> Option<f64> {
+    if components.list().is_empty() {
+        components.refresh(true);
+    } else {
+        components.refresh(false);
+    }
+    components
+        .list()
+        .iter()
+        .filter(|c| {
+            let label = c.label().to_lowercase();
+            label.contains("cpu")
+                || label.contains("package")
+                || label.contains("tctl")
+                || label.contains("thermal zone")
+        })
+        .filter_map(|c| c.temperature())
+        .map(|t| t as f64)
+        .fold(None, |max: Option<f64>, t| {
+            Some(max.map_or(t, |m| m.max(t)))
+        })
+        .map(|t| (t * 10.0).round() / 10.0)
+}
+
+#[cfg(target_os = "windows")]
+fn read_gpu() -> Option<GpuReading> {
+    use crate::drives::variant_u64;
+    use crate::gpu_routing::parse_luid;
+    use std::collections::BTreeMap;
+    use wmi::{COMLibrary, WMIConnection};
+
+    let com = COMLibrary::new().ok()?;
+    let wmi = WMIConnection::new(com).ok()?;
+
+    // 实例名: pid_1234_luid_0x00000000_0x0000D1A4_phys_0_eng_0_engtype_3D
+    let engines: Vec<HashMap<String, wmi::Variant>> = wmi
+        .raw_query(
+            "SELECT Name, UtilizationPercentage FROM \
+             Win32_PerfFormattedData_GPUPerformanceCounters_GPUEngine \
+             WHERE Name LIKE '%engtype_3D'",
+        )
+        .ok()?;
+    let mut by_adapter: BTreeMap<String, AdapterReading> = BTreeMap::new();
+    for item in &engines {
+        let Some(wmi::Variant::String(name)) = item.get("Name") else {
+            continue;
+        };
+        let Some(luid) = parse_luid(name) else {
+            continue;
+        };
+        let usage = variant_u64(item.get("UtilizationPercentage")).unwrap_or(0);
+        let adapter = by_adapter.entry(luid).or_default();
+        adapter.utilization_percent += usage as f64;
+        if let Some(pid) = name
+            .strip_prefix("pid_")
+            .and_then(|s| s.split('_').next())
+            .and_then(|s| s.parse().ok())
+        {
+            *adapter.processes.entry(pid).or_default() += usage;
+        }
+    }
+
+    // 实例名: luid_0x00000000_0x0000D1A4_phys_0
+    let memory: Vec<HashMap<String, wmi::Variant>> = wmi
+        .raw_query(
+            "SELECT Name, DedicatedUsage FROM \
+             Win32_PerfFormattedData_GPUPerformanceCounters_GPUAdapterMemory",
+        )
+        .unwrap_or_default();
+    for item in &memory {
+        let Some(wmi::Variant::String(name)) = item.get("Name") else {
+            continue;
+        };
+        let Some(luid) = parse_luid(name) else {
+            continue;
+        };
+        let bytes = variant_u64(item.get("DedicatedUsage")).unwrap_or(0);
+        by_adapter.entry(luid).or_default().dedicated_used_mb = bytes / (1024 * 1024);
+    }
+
+    let adapters: Vec<AdapterReading> = by_adapter
+        .into_iter()
+        .map(|(luid, mut adapter)| {
+            adapter.utilization_percent = adapter.utilization_percent.min(100.0);
+            adapter.name = adapter_name(&luid);
+            adapter.luid = luid;
+            adapter
+        })
+        .collect();
+
+    Some(GpuReading {
+        utilization_percent: adapters
+            .iter()
+            .map(|a| a.utilization_percent)
+            .fold(0.0, f64::max),
+        dedicated_used_mb: adapters
+            .iter()
+            .map(|a| a.dedicated_used_mb)
+            .max()
+            .unwrap_or(0),
+        adapters,
+    })
+}
+
+#[cfg(not(target_os = "windows"))]
+fn read_gpu() -> Option<GpuReading> {
+    None
+}
+
+#[cfg(target_os = "windows")]
+fn read_battery() -> Option<BatteryReading> {
+    use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    let mut status = SYSTEM_POWER_STATUS::default();
+    unsafe { GetSystemPowerStatus(&mut status) }.ok()?;
+    // BatteryFlag 128 = 没有电池（台式机）, BatteryLifePercent 255 = 未知
+    if status.BatteryFlag == 128 || status.BatteryLifePercent == 255 {
+        return None;
+    }
+    Some(BatteryReading {
+        percent: status.BatteryLifePercent,
+        on_ac: status.ACLineStatus == 1,
+        charging: status.BatteryFlag & 8 != 0,
+    })
+}
+
+#[cfg(not(target_os = "windows"))]
+fn read_battery() -> Option<BatteryReading> {
+    None
+}
+
@@ -0,0 +1,133 @@
+use crate::game_version::{self, GameVersion};
+use crate::game_window;
+use serde::{Deserialize, Serialize};
+use sysinfo::{ProcessRefreshKind, System, UpdateKind};
+
+// ==================== 数据结构 ====================
+
+/// 测试开始时采集的游戏进程与环境信息
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionMetadata {
+    /// 游戏进程 ID（开始监测时游戏未运行则为 None）
+    pub pid: Option<u32>,
+    /// 游戏可执行文件完整路径
+    pub exe_path: Option<String>,
+    /// 完整命令行
+    #[serde(default)]
+    pub command_line: Option<String>,
+    /// 启动参数（不含可执行文件本身，e.g., ["-dx12", "-high"]）
+    #[serde(default)]
+    pub launch_args: Vec<String>,
+    /// 游戏版本号与签名时间
+    #[serde(default)]
+    pub game_version: Option<GameVersion>,
+    /// 开始监测时绑定的游戏主窗口标题
+    #[serde(default)]
+    pub window_title: Option<String>,
+    /// 开始监测时绑定的游戏主窗口句柄
+    #[serde(default)]
+    pub window_handle: Option<u64>,
+    /// 开始监测时的系统运行时长 (秒)
+    #[serde(default)]
+    pub uptime_secs: Option<u64>,
+    /// 上次开机时间 (RFC 3339)
+    #[serde(default)]
+    pub boot_time: Option<String>,
+}
+
+// ==================== 采集 ====================
+
+/// 按进程 ID（优先）或进程名查找游戏进程并采集元数据
+pub fn capture(process_name: &str, pid: Option<u32>) -> SessionMetadata {
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        sysinfo::ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing()
+            .with_exe(UpdateKind::OnlyIfNotSet)
+            .with_cmd(UpdateKind::OnlyIfNotSet),
+    );
+
+    let target = process_name.to_lowercase();
+    let process = match pid {
+        Some(pid) => sys.process(sysinfo::Pid::from_u32(pid)),
+        None => sys
+            .processes()
+            .values()
+            .find(|p| p.name().to_string_lossy().to_lowercase() == target),
+    };
+
+    let uptime_secs = Some(System::uptime());
+    let boot_time = chrono::DateTime::from_timestamp(System::boot_time() as i64, 0)
+        .map(|t| t.with_timezone(&chrono::Local).to_rfc3339());
+
+    let Some(process) = process else {
+        return SessionMetadata {
+            uptime_secs,
+            boot_time,
+            ..Default::default()
+        };
+    };
+
+    let cmd: Vec<String> = process
+        .cmd()
+        .iter()
+        .map(|arg| arg.to_string_lossy().to_string())
+        .collect();
+    // 无权限读取其他用户/提权进程的命令行时为空
+    let command_line = if cmd.is_empty() {
+        None
+    } else {
+        Some(join_command_line(&cmd))
+    };
+
+    if let Some(line) = &command_line {
+        log::info!("游戏命令行: {}", line);
+    }
+
+    let exe_path = process.exe().map(|p| p.to_string_lossy().to_string());
+    let game_version = exe_path.as_deref().map(game_version::read_game_version);
+    if let Some(version) = game_version.as_ref().and_then(|v| v.file_version.as_ref()) {
+        log::info!("游戏版本: {}", version);
+    }
+
+    // 没有可见窗口的进程通常是启动器或后台服务，而非渲染画面的游戏进程
+    let pid = process.pid().as_u32();
+    let window_handle = game_window::main_window_handle(pid);
+    let window_title = game_window::main_window_title(pid);
+    match &window_title {
+        Some(title) => log::info!("游戏窗口: {}", title),
+        None if window_handle.is_none() => log::warn!(
+            "{} (PID {}) 没有可见窗口，可能绑定到了启动器或后台进程",
+            process.name().to_string_lossy(),
+            pid
+        ),
+        None => {}
+    }
+
+    SessionMetadata {
+        pid: Some(pid),
+        exe_path,
+        command_line,
+        launch_args: cmd.into_iter().skip(1).collect(),
+        game_version,
+        window_title,
+        window_handle,
+        uptime_secs,
+        boot_time,
+    }
+}
+
+/// 含空格的参数加引号，便于直接复制复现
+fn join_command_line(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| {
+            if arg.contains(' ') {
+                format!("\"{}\"", arg)
+            } else {
+                arg.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+/// EMA 时间常数 (ms)：约 0.5 秒前的帧权重衰减到 1/e
+const EMA_TAU_MS: f64 = 500.0;
+
+/// 实时 FPS 显示的平滑方式（只影响显示，会话统计始终使用原始帧时间）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FpsSmoothing {
+    /// 快照窗口内帧时间的算术平均
+    #[default]
+    Average,
+    /// 按帧时间加权的指数移动平均，CPU 尖峰时数值更平稳
+    Ema,
+    /// 快照窗口内帧时间的中位数，不受个别长帧影响
+    Median,
+}
+
+/// 监测期间逐帧更新的平滑状态
+#[derive(Default)]
+pub struct FpsSmoother {
+    ema_frametime: Option<f64>,
+}
+
+impl FpsSmoother {
+    /// 每帧调用；EMA 与帧率无关，按帧时间计算衰减系数
+    pub fn push(&mut self, frametime: f64) {
+        self.ema_frametime = Some(match self.ema_frametime {
+            Some(ema) => {
+                let alpha = 1.0 - (-frametime / EMA_TAU_MS).exp();
+                ema + alpha * (frametime - ema)
+            }
+            None => frametime,
+        });
+    }
+
+    /// 按所选方式计算显示用的帧时间 (ms)，`window` 为本次快照窗口内的原始帧时间
+    pub fn frametime(&self, mode: FpsSmoothing, window: &[f64]) -> f64 {
+        match mode {
+            FpsSmoothing::Average => average(window),
+            FpsSmoothing::Ema => self.ema_frametime.unwrap_or_else(|| average(window)),
+            FpsSmoothing::Median => median(window),
+        }
+    }
+}
+
+pub fn average(window: &[f64]) -> f64 {
+    if window.is_empty() {
+        return 0.0;
+    }
+    window.iter().sum::<f64>() / window.len() as f64
+}
+
+fn median(window: &[f64]) -> f64 {
+    if window.is_empty() {
+        return 0.0;
+    }
+    let mut sorted = window.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+
+/// 常见串流帧率档位
+const STREAM_RATES: &[f64] = &[30.0, 60.0, 90.0, 120.0, 144.0, 240.0];
+/// 帧间隔超过目标间隔的倍数时视为迟到帧（网络抖动 / 解码延迟）
+const LATE_FRAME_FACTOR: f64 = 1.5;
+
+// ==================== 已知串流客户端 ====================
+
+/// 进程名(小写), 客户端名称
+const STREAMING_CLIENTS: &[(&str, &str)] = &[
+    ("geforcenow.exe", "GeForce NOW"),
+    ("moonlight.exe", "Moonlight"),
+    ("streaming_client.exe", "Steam Remote Play"),
+    ("parsecd.exe", "Parsec"),
+    ("xboxpcapp.exe", "Xbox Cloud Gaming"),
+    ("gamestreamingclient.exe", "Xbox Cloud Gaming"),
+];
+
+pub fn find_client(exe_name: &str) -> Option<&'static str> {
+    let lower = exe_name.to_lowercase();
+    STREAMING_CLIENTS
+        .iter()
+        .find(|(process, _)| *process == lower)
+        .map(|(_, name)| *name)
+}
+
+// ==================== 数据结构 ====================
+
+/// 串流会话统计
+/// 监测串流客户端时，PresentMon 记录的是客户端显示解码后画面的时间，
+/// 反映的是实际收到的画面流畅度，而不是远端的渲染帧率
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamStats {
+    /// 串流客户端
+    pub client: String,
+    /// 推测的串流目标帧率
+    pub target_fps: f64,
+    /// 实际显示的解码帧率
+    pub delivered_fps: f64,
+    /// 迟到帧数
+    pub late_frames: u64,
+    /// 迟到帧占比 (%)
+    pub late_frame_percent: f64,
+}
+
+fn median(frame_times: &[f64]) -> f64 {
+    let mut sorted = frame_times.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    sorted[sorted.len() / 2]
+}
+
+/// 根据客户端显示的帧时间计算串流统计
+pub fn build_stream_stats(client: &str, frame_times: &[f64]) -> Option<StreamStats> {
+    if frame_times.is_empty() {
+        return None;
+    }
+
+    // 取与帧时间中位数最接近的常见档位作为目标帧率
+    let median_fps = 1000.0 / median(frame_times);
+    let target_fps = STREAM_RATES
+        .iter()
+        .copied()
+        .min_by(|a, b| {
+            (a - median_fps)
+                .abs()
+                .partial_cmp(&(b - median_fps).abs())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .unwrap_or(median_fps);
+
+    let target_interval = 1000.0 / target_fps;
+    let late_frames = frame_times
+        .iter()
+        .filter(|&&ft| ft > target_interval * LATE_FRAME_FACTOR)
+        .count() as u64;
+    let avg_ft = frame_times.iter().sum::<f64>() / frame_times.len() as f64;
+    let late_percent = late_frames as f64 / frame_times.len() as f64 * 100.0;
+
+    Some(StreamStats {
+        client: client.to_string(),
+        target_fps,
+        delivered_fps: (1000.0 / avg_ft * 10.0).round() / 10.0,
+        late_frames,
+        late_frame_percent: (late_percent * 10.0).round() / 10.0,
+    })
+}
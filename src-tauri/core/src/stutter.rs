@@ -0,0 +1,66 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// 卡顿判定：帧时间至少比近期中位数长该值 (ms)
+/// 用相对中位数的增量而非固定下限，高帧率下 40 ms 左右的卡顿也能检出
+const STUTTER_MIN_EXTRA_MS: f64 = 30.0;
+/// 卡顿判定：帧时间达到近期中位数的倍数
+const STUTTER_RATIO: f64 = 2.5;
+/// 计算滚动中位数的帧数
+const STUTTER_WINDOW: usize = 120;
+/// 窗口内至少有这么多帧才开始判定
+const STUTTER_MIN_FRAMES: usize = 30;
+/// 两次卡顿事件的最短间隔，避免连续掉帧时反复触发脚本
+const STUTTER_COOLDOWN: Duration = Duration::from_secs(5);
+
+// ==================== 卡顿检测 ====================
+
+/// 按帧时间尖峰检测卡顿（超过近期帧时间中位数的 2.5 倍，且至少长 30 ms）
+#[derive(Default)]
+pub struct StutterDetector {
+    recent: VecDeque<f64>,
+    last_fired: Option<Instant>,
+}
+
+impl StutterDetector {
+    /// 输入一帧，判定为卡顿时返回近期帧时间中位数
+    pub fn push(&mut self, frametime_ms: f64) -> Option<f64> {
+        let median = (self.recent.len() >= STUTTER_MIN_FRAMES).then(|| {
+            let mut sorted: Vec<f64> = self.recent.iter().copied().collect();
+            let mid = sorted.len() / 2;
+            *sorted.select_nth_unstable_by(mid, f64::total_cmp).1
+        });
+        if self.recent.len() >= STUTTER_WINDOW {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(frametime_ms);
+
+        median.filter(|m| frametime_ms >= (m * STUTTER_RATIO).max(m + STUTTER_MIN_EXTRA_MS))
+    }
+
+    /// 卡顿前的最近几帧（含卡顿帧）
+    pub fn recent(&self, frames: usize) -> Vec<f64> {
+        let skip = self.recent.len().saturating_sub(frames);
+        self.recent.iter().skip(skip).copied().collect()
+    }
+
+    /// 距上次触发卡顿脚本已超过冷却时间，避免连续掉帧时反复运行
+    pub fn hook_ready(&mut self) -> bool {
+        let ready = self
+            .last_fired
+            .is_none_or(|t| t.elapsed() >= STUTTER_COOLDOWN);
+        if ready {
+            self.last_fired = Some(Instant::now());
+        }
+        ready
+    }
+}
+
+/// 统计帧时间序列中的卡顿次数
+pub fn count_stutters(frame_times: &[f64]) -> u32 {
+    let mut detector = StutterDetector::default();
+    frame_times
+        .iter()
+        .filter(|&&ft| detector.push(ft).is_some())
+        .count() as u32
+}
@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SizeUnit {
+    /// 1 GB = 10^9 字节（硬盘厂商标称）
+    Gb,
+    /// 1 GiB = 2^30 字节（Windows 资源管理器显示的 "GB" 即为此单位）
+    #[default]
+    Gib,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecimalSeparator {
+    #[default]
+    Dot,
+    Comma,
+}
+
+/// 后端生成的报告与导出中使用的单位和数字格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UnitPrefs {
+    pub size: SizeUnit,
+    pub temperature: TemperatureUnit,
+    pub decimal: DecimalSeparator,
+}
+
+// ==================== 格式化 ====================
+
+impl UnitPrefs {
+    /// 按语言区域推断默认格式（e.g., "de-DE" 用逗号小数点，"en-US" 用华氏度）
+    pub fn for_locale(locale: &str) -> Self {
+        let lower = locale.to_lowercase().replace('_', "-");
+        let language = lower.split('-').next().unwrap_or_default();
+        let comma = matches!(
+            language,
+            "de" | "fr" | "es" | "it" | "pt" | "ru" | "pl" | "nl" | "tr" | "uk" | "cs" | "sv"
+        );
+        UnitPrefs {
+            size: SizeUnit::Gib,
+            temperature: if lower == "en-us" {
+                TemperatureUnit::Fahrenheit
+            } else {
+                TemperatureUnit::Celsius
+            },
+            decimal: if comma {
+                DecimalSeparator::Comma
+            } else {
+                DecimalSeparator::Dot
+            },
+        }
+    }
+
+    /// 按小数位格式化数字
+    pub fn number(&self, value: f64, decimals: usize) -> String {
+        let text = format!("{:.*}", decimals, value);
+        match self.decimal {
+            DecimalSeparator::Dot => text,
+            DecimalSeparator::Comma => text.replace('.', ","),
+        }
+    }
+
+    pub fn percent(&self, value: f64, decimals: usize) -> String {
+        format!("{}%", self.number(value, decimals))
+    }
+
+    pub fn size_from_bytes(&self, bytes: u64) -> String {
+        match self.size {
+            SizeUnit::Gb => format!("{} GB", self.number(bytes as f64 / 1e9, 1)),
+            SizeUnit::Gib => format!(
+                "{} GiB",
+                self.number(bytes as f64 / (1024.0 * 1024.0 * 1024.0), 1)
+            ),
+        }
+    }
+
+    /// 以 GiB 为单位的数值（后端结构体中的 *_gb 字段）
+    pub fn size_from_gib(&self, gib: f64) -> String {
+        self.size_from_bytes((gib * 1024.0 * 1024.0 * 1024.0) as u64)
+    }
+
+    pub fn temperature(&self, celsius: f64) -> String {
+        match self.temperature {
+            TemperatureUnit::Celsius => format!("{}°C", self.number(celsius, 0)),
+            TemperatureUnit::Fahrenheit => {
+                format!("{}°F", self.number(celsius * 9.0 / 5.0 + 32.0, 0))
+            }
+        }
+    }
+}
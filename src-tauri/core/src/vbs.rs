@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+use sysinfo::{CpuRefreshKind, RefreshKind, System};
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VbsState {
+    /// 未启用
+    Off,
+    /// 已配置但未运行（通常是 BIOS 未开启虚拟化）
+    Enabled,
+    /// 正在运行
+    Running,
+    #[default]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpectedImpact {
+    /// 预计平均帧率损失下限 (%)
+    pub min_percent: u32,
+    /// 预计平均帧率损失上限 (%)
+    pub max_percent: u32,
+    /// 估算依据
+    pub basis: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VbsStatus {
+    /// 基于虚拟化的安全 (VBS)
+    pub vbs: VbsState,
+    /// 内存完整性 / 基于虚拟机监控程序的代码完整性 (HVCI)
+    pub hvci_running: bool,
+    /// Credential Guard
+    pub credential_guard_running: bool,
+    /// CPU 是否支持 MBEC/GMET（硬件加速 HVCI，不支持时 HVCI 需软件模拟，开销更大）
+    pub cpu_has_mbec: Option<bool>,
+    /// 结合社区测试数据估算的性能影响（VBS 未运行时为 None）
+    pub expected_impact: Option<ExpectedImpact>,
+}
+
+// ==================== 社区数据 ====================
+
+/// 社区横向测试汇总（同配置开关 VBS/HVCI 对比，平均帧率）
+/// 仅 VBS（未开 HVCI）
+const IMPACT_VBS_ONLY: (u32, u32) = (1, 3);
+/// HVCI + CPU 支持 MBEC/GMET
+const IMPACT_HVCI_MBEC: (u32, u32) = (3, 8);
+/// HVCI + CPU 不支持 MBEC/GMET（软件模拟）
+const IMPACT_HVCI_EMULATED: (u32, u32) = (10, 25);
+
+/// 根据 CPU 型号判断是否支持 MBEC (Intel 7 代酷睿+) / GMET (AMD Zen 2+)
+fn cpu_supports_mbec(brand: &str) -> Option<bool> {
+    let lower = brand.to_lowercase();
+
+    if lower.contains("intel") {
+        // Core Ultra 及 12 代以后的新命名
+        if lower.contains("core ultra") || lower.contains("core(tm) ultra") {
+            return Some(true);
+        }
+        // "i7-6700K" → 6, "i5-12400" → 12
+        let model = lower
+            .split_whitespace()
+            .find(|w| w.starts_with('i') && w.contains('-'))?
+            .split('-')
+            .nth(1)?;
+        let digits: String = model.chars().take_while(|c| c.is_ascii_digit()).collect();
+        let generation: u32 = match digits.len() {
+            4 => digits[..1].parse().ok()?,
+            5 => digits[..2].parse().ok()?,
+            _ => return None,
+        };
+        return Some(generation >= 7);
+    }
+
+    if lower.contains("ryzen") {
+        // "Ryzen 7 2700X" → 2000 系列 (Zen+)，"Ryzen 5 3600" → 3000 系列 (Zen 2)
+        let model = lower
+            .split_whitespace()
+            .find(|w| w.len() >= 4 && w.chars().take(4).all(|c| c.is_ascii_digit()))?;
+        let series: u32 = model[..1].parse().ok()?;
+        return Some(series >= 3);
+    }
+
+    None
+}
+
+fn estimate_impact(status: &VbsStatus) -> Option<ExpectedImpact> {
+    if status.vbs != VbsState::Running {
+        return None;
+    }
+
+    let (range, basis) = if !status.hvci_running {
+        (IMPACT_VBS_ONLY, "仅开启 VBS，未开启内存完整性".to_string())
+    } else if status.cpu_has_mbec == Some(false) {
+        (
+            IMPACT_HVCI_EMULATED,
+            "已开启内存完整性，且 CPU 不支持 MBEC/GMET（需软件模拟）".to_string(),
+        )
+    } else {
+        (
+            IMPACT_HVCI_MBEC,
+            "已开启内存完整性，CPU 支持 MBEC/GMET 硬件加速".to_string(),
+        )
+    };
+
+    Some(ExpectedImpact {
+        min_percent: range.0,
+        max_percent: range.1,
+        basis,
+    })
+}
+
+// ==================== 状态读取 ====================
+
+#[cfg(target_os = "windows")]
+fn query_device_guard() -> (VbsState, bool, bool) {
+    use crate::drives::variant_u64;
+    use std::collections::HashMap;
+    use wmi::{COMLibrary, WMIConnection};
+
+    let unknown = (VbsState::Unknown, false, false);
+    let Ok(com) = COMLibrary::new() else {
+        return unknown;
+    };
+    let Ok(wmi) = WMIConnection::with_namespace_path(r"ROOT\Microsoft\Windows\DeviceGuard", com)
+    else {
+        return unknown;
+    };
+    let results: Vec<HashMap<String, wmi::Variant>> = wmi
+        .raw_query("SELECT VirtualizationBasedSecurityStatus, SecurityServicesRunning FROM Win32_DeviceGuard")
+        .unwrap_or_default();
+    let Some(item) = results.first() else {
+        return unknown;
+    };
+
+    let vbs = match variant_u64(item.get("VirtualizationBasedSecurityStatus")) {
+        Some(0) => VbsState::Off,
+        Some(1) => VbsState::Enabled,
+        Some(2) => VbsState::Running,
+        _ => VbsState::Unknown,
+    };
+    // SecurityServicesRunning: 1 = Credential Guard, 2 = HVCI
+    let services: Vec<u64> = match item.get("SecurityServicesRunning") {
+        Some(wmi::Variant::Array(values)) => {
+            values.iter().filter_map(|v| variant_u64(Some(v))).collect()
+        }
+        _ => vec![],
+    };
+
+    (vbs, services.contains(&2), services.contains(&1))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn query_device_guard() -> (VbsState, bool, bool) {
+    (VbsState::Unknown, false, false)
+}
+
+fn cpu_brand() -> String {
+    let sys =
+        System::new_with_specifics(RefreshKind::nothing().with_cpu(CpuRefreshKind::nothing()));
+    sys.cpus()
+        .first()
+        .map(|c| c.brand().to_string())
+        .unwrap_or_default()
+}
+
+/// 检测 VBS / HVCI / Credential Guard 状态并估算性能影响
+pub fn detect_vbs_status() -> VbsStatus {
+    let (vbs, hvci_running, credential_guard_running) = query_device_guard();
+    let mut status = VbsStatus {
+        vbs,
+        hvci_running,
+        credential_guard_running,
+        cpu_has_mbec: cpu_supports_mbec(&cpu_brand()),
+        expected_impact: None,
+    };
+    status.expected_impact = estimate_impact(&status);
+    status
+}
@@ -0,0 +1,267 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// 采样间隔（计数器变化很慢，长时间测试中定期读取即可）
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(30);
+/// nvidia-smi 查询的字段，顺序与 `parse_nvidia_smi` 一致
+const QUERY_FIELDS: &str = "index,name,ecc.mode.current,\
+                            ecc.errors.corrected.volatile.total,\
+                            ecc.errors.uncorrected.volatile.total,\
+                            retired_pages.sbe,retired_pages.dbe,retired_pages.pending";
+
+// ==================== 数据结构 ====================
+
+/// 单块显卡的显存纠错计数（驱动加载以来），显卡不提供的项为 None
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VramErrorCounters {
+    pub gpu_index: u32,
+    pub gpu_name: String,
+    /// ECC 是否开启
+    pub ecc_enabled: Option<bool>,
+    /// 已纠正的错误数（单比特）
+    pub corrected: Option<u64>,
+    /// 无法纠正的错误数（双比特）
+    pub uncorrected: Option<u64>,
+    /// 因单比特错误退役的显存页
+    pub retired_sbe: Option<u64>,
+    /// 因双比特错误退役的显存页
+    pub retired_dbe: Option<u64>,
+    /// 有待下次重启生效的退役页
+    pub retire_pending: Option<bool>,
+}
+
+impl VramErrorCounters {
+    /// 显卡是否公开了纠错计数（GeForce 等消费级显卡通常不提供）
+    pub fn exposed(&self) -> bool {
+        self.corrected.is_some() || self.retired_sbe.is_some()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VramStabilityReport {
+    pub gpu_name: String,
+    /// 显卡是否公开了纠错计数
+    pub exposed: bool,
+    /// 测试期间新增的已纠正错误
+    pub corrected_delta: u64,
+    /// 测试期间新增的无法纠正错误
+    pub uncorrected_delta: u64,
+    /// 测试期间新增的退役页
+    pub retired_pages_delta: u64,
+    pub retire_pending: bool,
+    /// 计数可读时：测试期间没有新增任何错误
+    pub stable: Option<bool>,
+    pub samples: u32,
+    /// 显存稳定性说明
+    pub note: String,
+}
+
+// ==================== 计数读取 ====================
+
+/// nvidia-smi 对不支持的项输出 "[N/A]" 或 "N/A"
+fn field_u64(value: &str) -> Option<u64> {
+    value.trim().parse().ok()
+}
+
+fn field_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "enabled" | "yes" => Some(true),
+        "disabled" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// 解析 `nvidia-smi --query-gpu=... --format=csv,noheader,nounits` 的输出
+pub fn parse_nvidia_smi(output: &str) -> Vec<VramErrorCounters> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            if fields.len() < 8 {
+                return None;
+            }
+            Some(VramErrorCounters {
+                gpu_index: fields[0].parse().ok()?,
+                gpu_name: fields[1].to_string(),
+                ecc_enabled: field_bool(fields[2]),
+                corrected: field_u64(fields[3]),
+                uncorrected: field_u64(fields[4]),
+                retired_sbe: field_u64(fields[5]),
+                retired_dbe: field_u64(fields[6]),
+                retire_pending: field_bool(fields[7]),
+            })
+        })
+        .collect()
+}
+
+/// 读取 NVIDIA 显卡的显存纠错计数；没有 NVIDIA 显卡或驱动时返回空
+pub fn read_vram_counters() -> Vec<VramErrorCounters> {
+    let mut cmd = std::process::Command::new("nvidia-smi");
+    cmd.args([
+        &format!("--query-gpu={}", QUERY_FIELDS),
+        "--format=csv,noheader,nounits",
+    ]);
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+    match cmd.output() {
+        Ok(o) if o.status.success() => parse_nvidia_smi(&String::from_utf8_lossy(&o.stdout)),
+        Ok(o) => {
+            log::debug!(
+                "nvidia-smi 查询失败: {}",
+                String::from_utf8_lossy(&o.stdout)
+            );
+            vec![]
+        }
+        Err(e) => {
+            log::debug!("无法运行 nvidia-smi: {}", e);
+            vec![]
+        }
+    }
+}
+
+// ==================== 测试期间采样 ====================
+
+/// 在 FPS 监测期间定期读取显存纠错计数
+pub struct VramSampler {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<Vec<Vec<VramErrorCounters>>>>,
+}
+
+impl VramSampler {
+    pub fn start() -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = stop.clone();
+
+        let handle = std::thread::spawn(move || {
+            let mut samples = Vec::new();
+            let mut last = Instant::now() - SAMPLE_INTERVAL;
+            while !stop_flag.load(Ordering::Relaxed) {
+                if last.elapsed() >= SAMPLE_INTERVAL {
+                    let counters = read_vram_counters();
+                    // 第一次就读不到说明没有 NVIDIA 显卡，不再轮询
+                    if counters.is_empty() && samples.is_empty() {
+                        break;
+                    }
+                    samples.push(counters);
+                    last = Instant::now();
+                }
+                std::thread::sleep(Duration::from_millis(200));
+            }
+            samples
+        });
+
+        VramSampler {
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    /// 停止采样，补读一次结束时的计数并生成报告
+    pub fn finish(mut self) -> Option<VramStabilityReport> {
+        self.stop.store(true, Ordering::Relaxed);
+        let mut samples = self.handle.take()?.join().ok()?;
+        if samples.is_empty() {
+            return None;
+        }
+        samples.push(read_vram_counters());
+        build_report(&samples)
+    }
+}
+
+impl Drop for VramSampler {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// 计数只增不减；驱动重新加载后易失计数会清零，此时以清零后的值为增量
+fn delta(first: Option<u64>, last: Option<u64>) -> u64 {
+    match (first, last) {
+        (Some(a), Some(b)) if b >= a => b - a,
+        (_, Some(b)) => b,
+        _ => 0,
+    }
+}
+
+/// 由首末两次采样生成报告；多块显卡时取公开了计数的那块
+pub fn build_report(samples: &[Vec<VramErrorCounters>]) -> Option<VramStabilityReport> {
+    let first_sample = samples.first()?;
+    let gpu = first_sample
+        .iter()
+        .find(|c| c.exposed())
+        .or(first_sample.first())?;
+    let last = samples
+        .iter()
+        .rev()
+        .find_map(|s| s.iter().find(|c| c.gpu_index == gpu.gpu_index))
+        .unwrap_or(gpu);
+
+    let retired = |c: &VramErrorCounters| match (c.retired_sbe, c.retired_dbe) {
+        (None, None) => None,
+        (sbe, dbe) => Some(sbe.unwrap_or(0) + dbe.unwrap_or(0)),
+    };
+    let mut report = VramStabilityReport {
+        gpu_name: gpu.gpu_name.clone(),
+        exposed: gpu.exposed(),
+        corrected_delta: delta(gpu.corrected, last.corrected),
+        uncorrected_delta: delta(gpu.uncorrected, last.uncorrected),
+        retired_pages_delta: delta(retired(gpu), retired(last)),
+        retire_pending: last.retire_pending.unwrap_or(false),
+        stable: None,
+        samples: samples.len() as u32,
+        note: String::new(),
+    };
+    finalize(&mut report);
+    Some(report)
+}
+
+/// 合并同一块显卡的多段报告（增量相加）
+pub fn merge(reports: &[&VramStabilityReport]) -> Option<VramStabilityReport> {
+    let first = reports.first()?;
+    let mut merged = VramStabilityReport {
+        exposed: reports.iter().all(|r| r.exposed),
+        corrected_delta: reports.iter().map(|r| r.corrected_delta).sum(),
+        uncorrected_delta: reports.iter().map(|r| r.uncorrected_delta).sum(),
+        retired_pages_delta: reports.iter().map(|r| r.retired_pages_delta).sum(),
+        retire_pending: reports.iter().any(|r| r.retire_pending),
+        samples: reports.iter().map(|r| r.samples).sum(),
+        ..(*first).clone()
+    };
+    finalize(&mut merged);
+    Some(merged)
+}
+
+fn finalize(report: &mut VramStabilityReport) {
+    let errors = report.corrected_delta + report.uncorrected_delta + report.retired_pages_delta;
+    report.stable = report
+        .exposed
+        .then_some(errors == 0 && !report.retire_pending);
+    report.note = if !report.exposed {
+        format!(
+            "{} 未公开显存纠错计数（消费级显卡通常不提供），\
+             无法判断显存超频是否在静默纠错，请以长时间测试中的画面异常与帧时间尖峰为准。",
+            report.gpu_name
+        )
+    } else if report.uncorrected_delta > 0 {
+        format!(
+            "测试期间出现 {} 次无法纠正的显存错误，显存已不稳定，请立即降低显存频率。",
+            report.uncorrected_delta
+        )
+    } else if report.corrected_delta > 0 || report.retired_pages_delta > 0 {
+        format!(
+            "测试期间显存纠错 {} 次、新增退役页 {} 个。画面看似稳定，但显存在静默纠错，\
+             纠错会拖慢显存带宽，超频时请降低显存频率直到计数不再增长。",
+            report.corrected_delta, report.retired_pages_delta
+        )
+    } else if report.retire_pending {
+        "有等待重启生效的显存退役页，建议重启后复测。".to_string()
+    } else {
+        "测试期间未出现显存纠错，显存稳定。".to_string()
+    };
+}
@@ -0,0 +1,131 @@
+use crate::present_mode::PresentInfo;
+use serde::{Deserialize, Serialize};
+
+/// 上下限之比低于该值时视为固定刷新率显示器（普通显示器的 EDID 范围也常有几 Hz 余量）
+const MIN_VRR_RATIO: f64 = 1.4;
+
+// ==================== 数据结构 ====================
+
+/// 显示器可变刷新率范围 (Hz)，来自 EDID 显示范围描述符
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VrrRange {
+    pub min_hz: f64,
+    pub max_hz: f64,
+}
+
+impl VrrRange {
+    /// 范围足够宽，可认为显示器支持 VRR (G-Sync Compatible / FreeSync)
+    pub fn is_variable(&self) -> bool {
+        self.min_hz > 0.0 && self.max_hz / self.min_hz >= MIN_VRR_RATIO
+    }
+
+    /// 上限至少为下限的两倍时，驱动可用低帧率补偿 (LFC) 倍帧
+    pub fn has_lfc(&self) -> bool {
+        self.max_hz >= self.min_hz * 2.0
+    }
+}
+
+/// 本次会话中 VRR 是否实际生效
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VrrStatus {
+    /// 独立翻转呈现，VRR 可生效
+    Active,
+    /// 画面经 DWM 合成，VRR 未启用，范围统计仅供参考
+    Inactive,
+    /// 没有呈现模式数据（或旧版本记录），无法确认
+    #[default]
+    Unknown,
+}
+
+/// VRR 有效性：多少帧落在显示器的 VRR 范围内
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VrrReport {
+    pub range: VrrRange,
+    #[serde(default)]
+    pub status: VrrStatus,
+    /// 分析使用的上限：当前刷新率低于显示器上限时以当前刷新率为准
+    pub effective_max_hz: f64,
+    pub lfc: bool,
+    /// 落在 VRR 范围内的帧占比 (%)
+    pub coverage_pct: f64,
+    /// 低于 VRR 下限的帧占比 (%)，支持 LFC 时由驱动倍帧，否则出现抖动或撕裂
+    pub below_range_pct: f64,
+    /// 高于刷新率上限的帧占比 (%)，超出部分撕裂或被垂直同步限制
+    pub above_range_pct: f64,
+}
+
+// ==================== EDID ====================
+
+/// 从 EDID 基础块的显示范围描述符 (0xFD) 读取垂直刷新率范围
+pub fn parse_edid_range(edid: &[u8]) -> Option<VrrRange> {
+    if edid.len() < 128 || edid[..8] != [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00] {
+        return None;
+    }
+    [54, 72, 90, 108].iter().find_map(|&offset| {
+        let d = &edid[offset..offset + 18];
+        if d[..3] != [0, 0, 0] || d[3] != 0xFD {
+            return None;
+        }
+        // EDID 1.4：偏移标志位表示范围值需加 255
+        let min = d[5] as f64 + if d[4] & 0x01 != 0 { 255.0 } else { 0.0 };
+        let max = d[6] as f64 + if d[4] & 0x02 != 0 { 255.0 } else { 0.0 };
+        (min > 0.0 && max >= min).then_some(VrrRange {
+            min_hz: min,
+            max_hz: max,
+        })
+    })
+}
+
+// ==================== 分析 ====================
+
+fn round1(v: f64) -> f64 {
+    (v * 10.0).round() / 10.0
+}
+
+/// 按 VRR 范围统计帧分布；显示器不支持 VRR 或没有帧时返回 None
+/// 画面经 DWM 合成时 VRR 不会生效，按呈现模式标记为未启用
+pub fn analyze(
+    frame_times: &[f64],
+    range: VrrRange,
+    refresh_hz: Option<f64>,
+    present: Option<&PresentInfo>,
+) -> Option<VrrReport> {
+    if !range.is_variable() {
+        return None;
+    }
+    let effective_max = refresh_hz
+        .filter(|hz| *hz > range.min_hz)
+        .map_or(range.max_hz, |hz| hz.min(range.max_hz));
+    // 帧时间的测量抖动允许 2% 误差，避免锁在刷新率上的帧被算作超出上限
+    let fastest_ms = 1000.0 / effective_max * 0.98;
+    let slowest_ms = 1000.0 / range.min_hz;
+
+    let (mut below, mut above, mut total) = (0usize, 0usize, 0usize);
+    for &ft in frame_times.iter().filter(|ft| **ft > 0.0 && ft.is_finite()) {
+        total += 1;
+        if ft > slowest_ms {
+            below += 1;
+        } else if ft < fastest_ms {
+            above += 1;
+        }
+    }
+    if total == 0 {
+        return None;
+    }
+    let pct = |n: usize| round1(n as f64 / total as f64 * 100.0);
+    let status = match present {
+        Some(p) if p.composed => VrrStatus::Inactive,
+        Some(_) => VrrStatus::Active,
+        None => VrrStatus::Unknown,
+    };
+    Some(VrrReport {
+        range,
+        status,
+        effective_max_hz: effective_max,
+        lfc: range.has_lfc(),
+        coverage_pct: pct(total - below - above),
+        below_range_pct: pct(below),
+        above_range_pct: pct(above),
+    })
+}
@@ -0,0 +1,556 @@
+use gamebench_core::ed25519;
+use gamebench_core::frame_cap::{self, CapKind};
+use gamebench_core::frame_stats::FrameStats;
+use gamebench_core::loadless::{self, LoadScanner, LoadlessStats};
+use gamebench_core::metrics::{
+    frametime_percentile, percentile_low_fps, FramePacing, FrametimeHistogram, PercentileMetrics,
+};
+use gamebench_core::pcie::{build_report, PcieIssue, PcieLinkReport, PcieLinkStatus};
+use gamebench_core::present_mode::PresentInfo;
+use gamebench_core::qoe::{QoeAccumulator, QoeGrade, QoeScore};
+use gamebench_core::smoothing;
+use gamebench_core::stutter::{count_stutters, StutterDetector};
+use gamebench_core::vrr::{self, VrrRange, VrrStatus};
+
+/// 确定性的帧时间序列：16.6 ms 附近抖动，每 500 帧一次 40 ms 长帧
+fn sample_frames(count: usize) -> Vec<f64> {
+    (0..count)
+        .map(|i| {
+            if i % 500 == 499 {
+                40.0
+            } else {
+                16.6 + ((i * 7919) % 23) as f64 * 0.1 - 1.1
+            }
+        })
+        .collect()
+}
+
+// ==================== 帧时间分布 ====================
+
+#[test]
+fn histogram_buckets_by_fps_boundaries() {
+    let histogram = FrametimeHistogram::from_frames(&[5.0, 8.4, 16.0, 16.7, 33.0, 33.4, 100.0]);
+    assert_eq!(histogram.lt_8ms, 1);
+    assert_eq!(histogram.ms_8_16, 2);
+    assert_eq!(histogram.ms_16_33, 2);
+    assert_eq!(histogram.gt_33ms, 2);
+}
+
+#[test]
+fn histogram_merge_adds_counts() {
+    let mut a = FrametimeHistogram::from_frames(&[5.0, 20.0]);
+    a.merge(&FrametimeHistogram::from_frames(&[5.0, 50.0]));
+    assert_eq!(a.lt_8ms, 2);
+    assert_eq!(a.ms_16_33, 1);
+    assert_eq!(a.gt_33ms, 1);
+}
+
+// ==================== 百分位 ====================
+
+#[test]
+fn frametime_percentile_uses_nearest_rank() {
+    let sorted: Vec<f64> = (1..=100).map(f64::from).collect();
+    assert_eq!(frametime_percentile(&sorted, 99.0), 99.0);
+    assert_eq!(frametime_percentile(&sorted, 95.5), 96.0);
+    assert_eq!(frametime_percentile(&sorted, 0.0), 1.0);
+    assert_eq!(frametime_percentile(&sorted, 100.0), 100.0);
+}
+
+#[test]
+fn low_fps_averages_worst_frames() {
+    let mut frames = vec![10.0; 98];
+    frames.extend([20.0, 30.0]);
+    // 1% of 100 frames → worst frame only
+    assert_eq!(percentile_low_fps(&frames, 1.0), 1000.0 / 30.0);
+    assert_eq!(percentile_low_fps(&frames, 2.0), 1000.0 / 25.0);
+    assert_eq!(percentile_low_fps(&[], 1.0), 0.0);
+}
+
+#[test]
+fn percentile_metrics_round_and_recompute() {
+    let frames: Vec<f64> = (1..=200).map(|i| f64::from(i) / 10.0).collect();
+    let metrics = PercentileMetrics::compute(&[50.0, 99.0], &[5.0], &frames);
+    assert_eq!(metrics.frametime_ms.len(), 2);
+    assert_eq!(metrics.frametime_ms[0].value, 10.0);
+    assert_eq!(metrics.frametime_ms[1].value, 19.8);
+    // 最差 10 帧平均 19.55 ms → 51.15 FPS
+    assert_eq!(metrics.low_fps[0].value, 51.2);
+
+    let again = metrics.recompute(&[8.0; 10]);
+    assert_eq!(again.frametime_ms[1].percentile, 99.0);
+    assert_eq!(again.frametime_ms[1].value, 8.0);
+    assert_eq!(again.low_fps[0].value, 125.0);
+
+    assert!(PercentileMetrics::compute(&[99.0], &[1.0], &[])
+        .frametime_ms
+        .is_empty());
+}
+
+#[test]
+fn frame_pacing_stdev_and_thresholds() {
+    // 8 / 12 ms 交替：平均 10 ms，标准差 2 ms
+    let frames: Vec<f64> = (0..1000).map(|i| if i % 2 == 0 { 8.0 } else { 12.0 }).collect();
+    let pacing = FramePacing::from_frames(&frames);
+    assert_eq!(pacing.stdev_ms, 2.0);
+    assert_eq!(pacing.cv, 0.2);
+    assert_eq!(pacing.p99_ms, 12.0);
+    assert_eq!(pacing.over_16ms, 0);
+
+    let mut frames = vec![10.0; 1000];
+    frames[10] = 20.0;
+    frames[20] = 40.0;
+    let pacing = FramePacing::from_frames(&frames);
+    assert_eq!(pacing.over_16ms, 2);
+    assert_eq!(pacing.over_33ms, 1);
+    assert_eq!(pacing.p99_ms, 10.0);
+    assert_eq!(pacing.p999_ms, 20.0);
+    assert_eq!(FramePacing::from_frames(&[]), FramePacing::default());
+}
+
+// ==================== 流式统计 ====================
+
+#[test]
+fn frame_stats_matches_exact_low_fps() {
+    let frames = sample_frames(20_000);
+    let mut stats = FrameStats::default();
+    stats.extend(&frames);
+    assert_eq!(stats.count(), frames.len() as u64);
+
+    for percentile in [0.1, 1.0, 5.0] {
+        let exact = 1000.0 / percentile_low_fps(&frames, percentile);
+        let binned = 1000.0 / stats.low_fps(percentile);
+        assert!(
+            (exact - binned).abs() <= 0.01,
+            "{}% low: {} vs {}",
+            percentile,
+            exact,
+            binned
+        );
+    }
+}
+
+#[test]
+fn frame_stats_pacing_matches_exact() {
+    let frames = sample_frames(20_000);
+    let mut stats = FrameStats::default();
+    stats.extend(&frames);
+    let exact = FramePacing::from_frames(&frames);
+    let streamed = FramePacing::from_stats(&stats);
+    assert_eq!(streamed.stdev_ms, exact.stdev_ms);
+    assert_eq!(streamed.cv, exact.cv);
+    assert_eq!(streamed.over_16ms, exact.over_16ms);
+    assert_eq!(streamed.over_33ms, exact.over_33ms);
+    assert!((streamed.p99_ms - exact.p99_ms).abs() <= 0.01);
+    assert!((streamed.p999_ms - exact.p999_ms).abs() <= 0.01);
+
+    let mut qoe = QoeAccumulator::new(Some(144.0));
+    for &ft in &frames {
+        qoe.push(ft);
+    }
+    assert_eq!(qoe.finish(3), QoeScore::from_frames(&frames, 3, Some(144.0)));
+}
+
+#[test]
+fn frame_stats_keeps_long_frames_and_ignores_invalid() {
+    let mut stats = FrameStats::default();
+    stats.extend(&[10.0, 10.0, 800.0, 0.0, -5.0, f64::NAN]);
+    let report = stats.report();
+    assert_eq!(report.total_frames, 3);
+    assert_eq!(report.duration_secs, 0.8);
+    assert_eq!(report.min_fps, 1.3);
+    assert_eq!(report.max_fps, 100.0);
+    assert_eq!(report.fps_01_low, 1.3);
+}
+
+// ==================== 卡顿检测 ====================
+
+#[test]
+fn stutter_needs_warmup_frames() {
+    let mut detector = StutterDetector::default();
+    for _ in 0..29 {
+        assert!(detector.push(16.0).is_none());
+    }
+    assert!(detector.push(100.0).is_none());
+}
+
+#[test]
+fn stutter_detects_spike_over_median() {
+    let mut detector = StutterDetector::default();
+    for _ in 0..60 {
+        detector.push(16.0);
+    }
+    assert_eq!(detector.push(60.0), Some(16.0));
+    // 比中位数长不到 30 ms 的尖峰不算卡顿
+    assert!(detector.push(45.0).is_none());
+    assert_eq!(detector.recent(2), vec![60.0, 45.0]);
+
+    let mut slow = StutterDetector::default();
+    for _ in 0..60 {
+        slow.push(30.0);
+    }
+    // 不到中位数的 2.5 倍
+    assert!(slow.push(70.0).is_none());
+}
+
+#[test]
+fn stutter_detected_at_high_frame_rate() {
+    // 240 FPS 下 40 ms 的卡顿非常明显，不应被固定的下限过滤掉
+    let mut detector = StutterDetector::default();
+    for _ in 0..120 {
+        detector.push(1000.0 / 240.0);
+    }
+    assert!(detector.push(40.0).is_some());
+    assert!(detector.push(20.0).is_none());
+    assert_eq!(count_stutters(&[vec![4.2; 200], vec![40.0], vec![4.2; 50]].concat()), 1);
+}
+
+#[test]
+fn count_stutters_over_session() {
+    let mut frames = vec![16.0; 100];
+    frames[50] = 80.0;
+    frames[80] = 120.0;
+    assert_eq!(count_stutters(&frames), 2);
+    assert_eq!(count_stutters(&[16.0; 10]), 0);
+}
+
+// ==================== 显示平滑 ====================
+
+#[test]
+fn smoothing_modes() {
+    use smoothing::{FpsSmoother, FpsSmoothing};
+
+    assert_eq!(smoothing::average(&[10.0, 20.0]), 15.0);
+    assert_eq!(smoothing::average(&[]), 0.0);
+
+    let mut smoother = FpsSmoother::default();
+    let window = [10.0, 10.0, 40.0];
+    assert_eq!(smoother.frametime(FpsSmoothing::Ema, &window), 20.0);
+    assert_eq!(smoother.frametime(FpsSmoothing::Median, &window), 10.0);
+    for &ft in &window {
+        smoother.push(ft);
+    }
+    let ema = smoother.frametime(FpsSmoothing::Ema, &window);
+    assert!(ema > 10.0 && ema < 20.0);
+}
+
+// ==================== 体验评分 ====================
+
+#[test]
+fn qoe_steady_frames_at_refresh_rate_score_full() {
+    let qoe = QoeScore::from_frames(&[1000.0 / 144.0; 1000], 0, Some(144.0)).unwrap();
+    assert_eq!(qoe.score, 100.0);
+    assert_eq!(qoe.grade, QoeGrade::Excellent);
+    assert_eq!(qoe.below_vrr_pct, 0.0);
+    assert!(QoeScore::from_frames(&[16.6], 0, None).is_none());
+}
+
+#[test]
+fn qoe_fps_above_refresh_adds_nothing() {
+    let capped = QoeScore::from_frames(&[1000.0 / 60.0; 600], 0, Some(60.0)).unwrap();
+    let uncapped = QoeScore::from_frames(&[1000.0 / 200.0; 2000], 0, Some(60.0)).unwrap();
+    assert_eq!(capped.fluency, 1.0);
+    assert_eq!(uncapped.fluency, 1.0);
+    // 未知刷新率按 60 Hz 估算
+    let unknown = QoeScore::from_frames(&[1000.0 / 60.0; 600], 0, None).unwrap();
+    assert_eq!(unknown.score, capped.score);
+    assert_eq!(unknown.refresh_hz, None);
+}
+
+#[test]
+fn qoe_prefers_even_pacing_over_higher_average() {
+    // 平均 ~70 FPS 但两帧交替 8 / 20 ms，与稳定 60 FPS 对比
+    let uneven: Vec<f64> = (0..1200).map(|i| if i % 2 == 0 { 8.0 } else { 20.0 }).collect();
+    let even = vec![1000.0 / 60.0; 1200];
+    let uneven = QoeScore::from_frames(&uneven, 0, Some(144.0)).unwrap();
+    let even = QoeScore::from_frames(&even, 0, Some(144.0)).unwrap();
+    assert!(uneven.fluency > even.fluency);
+    assert!(uneven.score < even.score);
+}
+
+#[test]
+fn qoe_penalizes_stutters_and_frames_below_vrr_floor() {
+    let frames = sample_frames(3600);
+    let clean = QoeScore::from_frames(&frames, 0, Some(60.0)).unwrap();
+    let stuttery = QoeScore::from_frames(&frames, 6, Some(60.0)).unwrap();
+    assert!(stuttery.stutters_per_min > 5.0);
+    assert!(stuttery.score < clean.score);
+
+    // 25 FPS 远低于 48 Hz 下限
+    let slow = QoeScore::from_frames(&[40.0; 600], 0, Some(144.0)).unwrap();
+    assert_eq!(slow.below_vrr_pct, 100.0);
+    assert_eq!(slow.refresh_fit, 0.0);
+    assert_eq!(slow.grade, QoeGrade::Poor);
+}
+
+// ==================== VRR ====================
+
+/// 只含一个显示范围描述符的 EDID 基础块
+fn edid_with_range(flags: u8, min: u8, max: u8) -> Vec<u8> {
+    let mut edid = vec![0u8; 128];
+    edid[..8].copy_from_slice(&[0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00]);
+    edid[72..79].copy_from_slice(&[0, 0, 0, 0xFD, flags, min, max]);
+    edid
+}
+
+#[test]
+fn edid_range_descriptor() {
+    let range = vrr::parse_edid_range(&edid_with_range(0, 48, 144)).unwrap();
+    assert_eq!((range.min_hz, range.max_hz), (48.0, 144.0));
+    assert!(range.is_variable() && range.has_lfc());
+
+    // 偏移标志：最高刷新率 255 + 5 = 260 Hz
+    let range = vrr::parse_edid_range(&edid_with_range(0x02, 48, 5)).unwrap();
+    assert_eq!(range.max_hz, 260.0);
+
+    // 普通 60 Hz 显示器的范围余量不算 VRR
+    let fixed = vrr::parse_edid_range(&edid_with_range(0, 56, 76)).unwrap();
+    assert!(!fixed.is_variable());
+    assert!(vrr::analyze(&[16.7; 10], fixed, Some(60.0), None).is_none());
+
+    assert!(vrr::parse_edid_range(&[0u8; 128]).is_none());
+    assert!(vrr::parse_edid_range(&edid_with_range(0, 48, 144)[..100]).is_none());
+}
+
+#[test]
+fn vrr_coverage_splits_frames_by_range() {
+    let range = VrrRange {
+        min_hz: 48.0,
+        max_hz: 144.0,
+    };
+    // 6 帧在范围内（含锁在 144 Hz 的帧），2 帧低于 48 Hz，2 帧高于 144 Hz
+    let frames = [
+        1000.0 / 144.0,
+        8.0,
+        10.0,
+        12.0,
+        16.7,
+        20.0,
+        25.0,
+        30.0,
+        5.0,
+        4.0,
+    ];
+    let report = vrr::analyze(&frames, range, Some(144.0), None).unwrap();
+    assert_eq!(report.status, VrrStatus::Unknown);
+    assert_eq!(report.coverage_pct, 60.0);
+    assert_eq!(report.below_range_pct, 20.0);
+    assert_eq!(report.above_range_pct, 20.0);
+    assert!(report.lfc);
+
+    // 显示器以 120 Hz 运行时上限按 120 Hz 计算
+    let report = vrr::analyze(&frames, range, Some(120.0), None).unwrap();
+    assert_eq!(report.effective_max_hz, 120.0);
+    assert_eq!(report.above_range_pct, 40.0);
+}
+
+fn present_info(mode: &str) -> PresentInfo {
+    PresentInfo {
+        present_mode: mode.to_string(),
+        present_mode_pct: 100.0,
+        composed: mode.starts_with("Composed"),
+        sync_interval: Some(0),
+        vsync: Some(false),
+        allows_tearing: Some(true),
+    }
+}
+
+#[test]
+fn vrr_inactive_when_composed() {
+    let range = VrrRange {
+        min_hz: 48.0,
+        max_hz: 144.0,
+    };
+    let frames = [10.0; 10];
+    let flip = present_info("Hardware: Independent Flip");
+    let report = vrr::analyze(&frames, range, Some(144.0), Some(&flip)).unwrap();
+    assert_eq!(report.status, VrrStatus::Active);
+
+    // 窗口化经 DWM 合成时 VRR 不生效
+    let composed = present_info("Composed: Flip");
+    let report = vrr::analyze(&frames, range, Some(144.0), Some(&composed)).unwrap();
+    assert_eq!(report.status, VrrStatus::Inactive);
+
+    // 旧版本记录没有 status 字段
+    let mut json = serde_json::to_value(&report).unwrap();
+    json.as_object_mut().unwrap().remove("status");
+    let legacy: vrr::VrrReport = serde_json::from_value(json).unwrap();
+    assert_eq!(legacy.status, VrrStatus::Unknown);
+}
+
+// ==================== PCIe 链路 ====================
+
+fn link(current_gen: u32, current_width: u32) -> PcieLinkStatus {
+    PcieLinkStatus {
+        gpu_name: "GPU".to_string(),
+        current_gen,
+        current_width,
+        max_gen: 4,
+        max_width: 16,
+    }
+}
+
+#[test]
+fn pcie_report_tags_each_warning() {
+    // 第一个采样（空闲降速）被忽略；之后 x8 且曾降至 Gen1
+    let report = build_report(&[link(1, 16), link(4, 8), link(1, 8)]).unwrap();
+    let issues: Vec<PcieIssue> = report.warnings.iter().map(|(issue, _)| *issue).collect();
+    assert_eq!(issues, [PcieIssue::WidthLimited, PcieIssue::Downclocked]);
+
+    let report = build_report(&[link(3, 16), link(3, 16)]).unwrap();
+    assert_eq!(report.warnings.len(), 1);
+    assert_eq!(report.warnings[0].0, PcieIssue::GenLimited);
+}
+
+#[test]
+fn pcie_report_round_trips_tagged_warnings() {
+    let report = build_report(&[link(4, 16), link(4, 8), link(2, 8)]).unwrap();
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["warnings"][0][0], "width_limited");
+    let parsed: PcieLinkReport = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed.warnings, report.warnings);
+}
+
+// ==================== 限帧建议 ====================
+
+const VRR_144: VrrRange = VrrRange {
+    min_hz: 48.0,
+    max_hz: 144.0,
+};
+
+#[test]
+fn frame_cap_vrr_ceiling_below_refresh() {
+    assert_eq!(frame_cap::vrr_ceiling(144.0), 138);
+    assert_eq!(frame_cap::vrr_ceiling(240.0), 224);
+
+    // 200 FPS 左右，超出 144 Hz 上限
+    let advice = frame_cap::advise(&[5.0; 1000], 144.0, Some(VRR_144)).unwrap();
+    assert_eq!(advice.kind, CapKind::VrrCeiling);
+    assert_eq!(advice.cap_fps, 138);
+    assert_eq!(advice.frames_above_cap_pct, 100.0);
+}
+
+#[test]
+fn frame_cap_sustained_when_fps_fluctuates() {
+    // 大部分帧 100 FPS，10% 的帧掉到 ~91 FPS
+    let frames: Vec<f64> = (0..1000).map(|i| if i % 10 == 0 { 11.0 } else { 10.0 }).collect();
+    let advice = frame_cap::advise(&frames, 144.0, Some(VRR_144));
+    assert!(advice.is_none(), "波动不大时不建议限帧");
+
+    // 平均 ~109 FPS，20% 的帧掉到 ~71 FPS
+    let frames: Vec<f64> = (0..1000).map(|i| if i % 5 == 0 { 14.0 } else { 8.0 }).collect();
+    let advice = frame_cap::advise(&frames, 144.0, Some(VRR_144)).unwrap();
+    assert_eq!(advice.kind, CapKind::Sustained);
+    assert_eq!(advice.cap_fps, 70);
+    assert_eq!(advice.sustained_fps, 71.4);
+}
+
+#[test]
+fn frame_cap_fixed_refresh() {
+    let advice = frame_cap::advise(&[10.0; 600], 60.0, None).unwrap();
+    assert_eq!(advice.kind, CapKind::Refresh);
+    assert_eq!(advice.cap_fps, 60);
+
+    // 45 FPS 左右，达不到 60 Hz：建议 30 FPS 半刷新率
+    let advice = frame_cap::advise(&[22.0; 600], 60.0, None).unwrap();
+    assert_eq!(advice.kind, CapKind::HalfRefresh);
+    assert_eq!(advice.cap_fps, 30);
+
+    // 固定刷新率显示器的 EDID 范围不按 VRR 处理
+    let fixed = VrrRange {
+        min_hz: 56.0,
+        max_hz: 76.0,
+    };
+    let advice = frame_cap::advise(&[22.0; 600], 60.0, Some(fixed)).unwrap();
+    assert_eq!(advice.kind, CapKind::HalfRefresh);
+    assert!(frame_cap::advise(&[40.0; 600], 60.0, None).is_none());
+}
+
+#[test]
+fn loadless_excises_freeze_and_low_fps_loading() {
+    // 10 s 游戏 (100 FPS) → 2 s 冻结 → 10 s 游戏 → 3 s 加载画面 (10 FPS) → 5 s 游戏
+    let mut frames = vec![10.0; 1000];
+    frames.push(2000.0);
+    frames.extend(vec![10.0; 1000]);
+    frames.extend(vec![100.0; 30]);
+    frames.extend(vec![10.0; 500]);
+
+    let report = loadless::analyze(&frames);
+    assert_eq!(report.segments.len(), 2);
+    assert_eq!(report.segments[0].first_frame, 1000);
+    assert_eq!(report.segments[0].frames, 1);
+    assert_eq!(report.segments[0].start_secs, 10.0);
+    assert_eq!(report.segments[0].duration_secs, 2.0);
+    assert_eq!(report.segments[1].start_secs, 22.0);
+    assert_eq!(report.segments[1].duration_secs, 3.0);
+    assert_eq!(report.total_secs, 30.0);
+    assert_eq!(report.load_secs, 5.0);
+    assert_eq!(report.loadless_secs, 25.0);
+    assert_eq!(report.loadless.total_frames, 2500);
+    assert_eq!(report.loadless.avg_fps, 100.0);
+    assert_eq!(report.loadless.min_fps, 100.0);
+
+    let csv = loadless::segments_csv(&report.segments);
+    assert!(csv.contains("1,0:00:10.000,0:00:12.000,10.000,12.000,2.000,1\n"));
+    assert!(csv.contains("2,0:00:22.000,0:00:25.000,"));
+}
+
+#[test]
+fn loadless_ignores_short_stutters_and_matches_chunked_input() {
+    // 单个 300 ms 卡顿不足 1 s，不算加载
+    let mut frames = vec![8.0; 2000];
+    frames.push(300.0);
+    frames.extend(vec![8.0; 2000]);
+    assert!(loadless::analyze(&frames).segments.is_empty());
+
+    frames.extend(vec![200.0; 10]);
+    frames.extend(vec![8.0; 500]);
+    let whole = loadless::analyze(&frames);
+    assert_eq!(whole.segments.len(), 1);
+
+    let mut scanner = LoadScanner::default();
+    frames.chunks(333).for_each(|c| scanner.extend(c));
+    let mut stats = LoadlessStats::new(scanner.segments());
+    frames.chunks(777).for_each(|c| stats.extend(c));
+    let chunked = stats.report();
+    assert_eq!(chunked.segments, whole.segments);
+    assert_eq!(chunked.loadless_secs, whole.loadless_secs);
+}
+
+fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn ed25519_matches_rfc8032_vectors() {
+    let cases = [
+        (
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+            "",
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+        ),
+        (
+            "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+            "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+            "72",
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+        ),
+    ];
+    for (seed, public, message, signature) in cases {
+        let seed: [u8; 32] = unhex(seed).try_into().unwrap();
+        let public: [u8; 32] = unhex(public).try_into().unwrap();
+        let message = unhex(message);
+        let signature: [u8; 64] = unhex(signature).try_into().unwrap();
+        assert_eq!(ed25519::public_key(&seed), public);
+        assert_eq!(ed25519::sign(&seed, &message), signature);
+        assert!(ed25519::verify(&public, &message, &signature));
+
+        let mut tampered = signature;
+        tampered[5] ^= 1;
+        assert!(!ed25519::verify(&public, &message, &tampered));
+        assert!(!ed25519::verify(&public, b"other", &signature));
+    }
+}
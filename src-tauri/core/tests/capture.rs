@@ -0,0 +1,356 @@
+use gamebench_core::capture_log::{self, CaptureLogWriter};
+use gamebench_core::emulators::{find_emulator, parse_game_title};
+use gamebench_core::events::EventSink;
+use gamebench_core::frame_log::{encode, FrameLog, FrameLogWriter};
+use gamebench_core::frame_pipeline::{
+    CaptureHost, FpsSnapshot, FpsStutter, FramePipeline, LoopState, PipelineConfig,
+};
+use gamebench_core::input_latency::LatencyStats;
+use gamebench_core::pinyin;
+use gamebench_core::present_mode::PresentStats;
+use gamebench_core::presentmon::{parse_csv_line, FrameRow};
+use gamebench_core::smoothing::FpsSmoothing;
+use gamebench_core::vram_health::{self, parse_nvidia_smi};
+use std::sync::{mpsc, Arc, Mutex};
+
+fn header(columns: &str) -> Vec<String> {
+    columns.split(',').map(str::to_string).collect()
+}
+
+// ==================== PresentMon CSV ====================
+
+#[test]
+fn parses_v2_csv_row() {
+    let header = header(
+        "Application,ProcessID,SwapChainAddress,Runtime,SyncInterval,PresentFlags,AllowsTearing,\
+         PresentMode,CPUStartTime,CPUStartQPC,FrameTime,CPUBusy,CPUWait,GPULatency,GPUBusy",
+    );
+    let row = parse_csv_line(
+        &header,
+        "game.exe,1234,0x1A2B,DXGI,0,0,1,Hardware: Independent Flip,1520.5,987654321,6.94,4.1,2.8,0.5,5.0",
+    )
+    .unwrap();
+    assert_eq!(row.frametime, 6.94);
+    assert_eq!(row.cpu_busy, 4.1);
+    assert_eq!(row.gpu_busy, 5.0);
+    assert_eq!(row.swapchain, "0x1A2B");
+    assert_eq!(row.timestamp_ms, Some(1520.5));
+    assert_eq!(row.qpc, Some(987654321));
+    assert_eq!(row.present_mode, "Hardware: Independent Flip");
+    assert_eq!(row.sync_interval, Some(0));
+    assert_eq!(row.allows_tearing, Some(true));
+}
+
+#[test]
+fn parses_v1_csv_row() {
+    let header = header(
+        "Application,ProcessID,SwapChainAddress,Runtime,SyncInterval,PresentFlags,\
+         TimeInSeconds,MsBetweenPresents,MsUntilRenderComplete",
+    );
+    let row = parse_csv_line(&header, "game.exe,1234,0x1A2B,DXGI,1,0,2.5,16.67,3.2").unwrap();
+    assert_eq!(row.frametime, 16.67);
+    assert_eq!(row.timestamp_ms, Some(2500.0));
+    assert_eq!(row.qpc, None);
+    assert_eq!(row.sync_interval, Some(1));
+    assert_eq!(row.allows_tearing, None);
+    assert_eq!(row.display_latency, None);
+}
+
+#[test]
+fn rejects_invalid_csv_rows() {
+    let header = header("Application,ProcessID,SwapChainAddress,Runtime,FrameTime");
+    assert!(parse_csv_line(&header, "game.exe,1,0x1,DXGI,1500.0").is_none());
+    assert!(parse_csv_line(&header, "game.exe,1,0x1,DXGI,0").is_none());
+    assert!(parse_csv_line(&header, "game.exe,1,0x1,DXGI,abc").is_none());
+    assert!(parse_csv_line(&header, "game.exe,1").is_none());
+    assert!(parse_csv_line(&header[..4], "game.exe,1,0x1,DXGI,16.6").is_none());
+}
+
+#[test]
+fn present_stats_reports_dominant_mode() {
+    let header = header("SyncInterval,AllowsTearing,PresentMode,FrameTime,Pad");
+    let mut stats = PresentStats::default();
+    assert!(stats.summary().is_none());
+
+    for (line, times) in [
+        ("1,0,Composed: Flip,16.6,0", 3),
+        ("0,1,Hardware: Independent Flip,6.9,0", 1),
+    ] {
+        let row = parse_csv_line(&header, line).unwrap();
+        (0..times).for_each(|_| stats.push(&row));
+    }
+    let info = stats.summary().unwrap();
+    assert_eq!(info.present_mode, "Composed: Flip");
+    assert_eq!(info.present_mode_pct, 75.0);
+    assert!(info.composed);
+    assert_eq!(info.sync_interval, Some(1));
+    assert_eq!(info.vsync, Some(true));
+    assert_eq!(info.allows_tearing, Some(false));
+
+    stats.clear();
+    assert!(stats.summary().is_none());
+}
+
+#[test]
+fn latency_stats_skip_missing_samples() {
+    let v2 = header("FrameTime,DisplayLatency,ClickToPhotonLatency,Pad,Pad");
+    let mut stats = LatencyStats::default();
+    for line in [
+        "6.9,10,NA,0,0",
+        "6.9,12,NA,0,0",
+        "6.9,NA,NA,0,0",
+        "6.9,14,30,0,0",
+        "6.9,40,NA,0,0",
+    ] {
+        stats.push(&parse_csv_line(&v2, line).unwrap());
+    }
+    let latency = stats.summary().unwrap();
+    let display = latency.display.unwrap();
+    assert_eq!(display.samples, 4);
+    assert_eq!(display.avg_ms, 19.0);
+    assert_eq!(display.median_ms, 12.0);
+    assert_eq!(display.p95_ms, 40.0);
+    let click = latency.click_to_photon.unwrap();
+    assert_eq!(click.samples, 1);
+    assert_eq!(click.median_ms, 30.0);
+
+    let v1 = header("TimeInSeconds,MsBetweenPresents,Pad,Pad,Pad");
+    let mut stats = LatencyStats::default();
+    stats.push(&parse_csv_line(&v1, "2.5,16.67,0,0,0").unwrap());
+    assert!(stats.summary().is_none());
+}
+
+// ==================== 帧数据文件 ====================
+
+#[test]
+fn frame_log_round_trip() {
+    let frames: Vec<f64> = (0..1000).map(|i| 8.0 + (i % 17) as f64 * 0.37).collect();
+    let log = FrameLog::parse(encode(&frames).unwrap()).unwrap();
+    assert_eq!(log.frame_count(), frames.len());
+    let decoded = log.read_all().unwrap();
+    for (a, b) in frames.iter().zip(&decoded) {
+        assert!((a - b).abs() < 0.001, "{} vs {}", a, b);
+    }
+}
+
+#[test]
+fn frame_log_reads_across_chunks() {
+    let frames: Vec<f64> = (0..150_000)
+        .map(|i| 5.0 + (i % 1000) as f64 / 100.0)
+        .collect();
+    let log = FrameLog::parse(encode(&frames).unwrap()).unwrap();
+    assert_eq!(log.frame_count(), frames.len());
+
+    // 跨越第一、二块边界 (65536)
+    let range = log.read_range(65_530, 10).unwrap();
+    assert_eq!(range.len(), 10);
+    for (a, b) in frames[65_530..65_540].iter().zip(&range) {
+        assert!((a - b).abs() < 0.001);
+    }
+
+    let mut chunks = 0;
+    log.for_each_chunk(0, frames.len(), |_| {
+        chunks += 1;
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(chunks, 3);
+
+    assert_eq!(log.read_range(149_995, 100).unwrap().len(), 5);
+    assert!(log.read_range(200_000, 10).unwrap().is_empty());
+}
+
+#[test]
+fn frame_log_writer_matches_encode() {
+    let frames: Vec<f64> = (0..150_000)
+        .map(|i| 5.0 + (i % 1000) as f64 / 100.0)
+        .collect();
+    let path = std::env::temp_dir().join(format!("gamebench-frames-{}.gbf", std::process::id()));
+    let mut writer = FrameLogWriter::create(&path).unwrap();
+    for &ft in &frames {
+        writer.push(ft).unwrap();
+    }
+    assert_eq!(writer.finish().unwrap(), frames.len());
+    let written = std::fs::read(&path).unwrap();
+    assert_eq!(written, encode(&frames).unwrap());
+    assert_eq!(FrameLog::open(&path).unwrap().frame_count(), frames.len());
+    std::fs::remove_file(&path).unwrap();
+
+    // 未调用 finish 时不留下文件
+    let mut writer = FrameLogWriter::create(&path).unwrap();
+    writer.push(16.6).unwrap();
+    drop(writer);
+    assert!(!path.exists());
+}
+
+#[test]
+fn frame_log_rejects_garbage() {
+    assert!(FrameLog::parse(b"not a frame log".to_vec()).is_err());
+    assert!(FrameLog::parse(Vec::new()).is_err());
+}
+
+// ==================== 原始采集日志 ====================
+
+#[test]
+fn capture_log_round_trip() {
+    let header =
+        header("Application,SwapChainAddress,PresentMode,CPUStartTime,FrameTime,CPUBusy,GPUBusy");
+    let path =
+        std::env::temp_dir().join(format!("gamebench-capture-{}.csv.gz", std::process::id()));
+    let mut writer = CaptureLogWriter::create(&path).unwrap();
+    for i in 0..1000 {
+        let line = format!(
+            "game.exe,0x1,Composed: Flip,{},16.6,{},12.5",
+            i as f64 * 16.6,
+            4.0 + (i % 3) as f64
+        );
+        let row = parse_csv_line(&header, &line).unwrap();
+        writer.push(row.timestamp_ms, &row);
+    }
+    assert_eq!(writer.finish().unwrap(), 1000);
+
+    let rows = capture_log::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(rows.len(), 1000);
+    assert_eq!(rows[2].timestamp_ms, Some(33.2));
+    assert_eq!(rows[2].frametime, 16.6);
+    assert_eq!(rows[2].cpu_busy, 6.0);
+    assert_eq!(rows[2].gpu_busy, 12.5);
+    assert_eq!(rows[2].present_mode, "Composed: Flip");
+}
+
+// ==================== 显存纠错 ====================
+
+#[test]
+fn vram_report_flags_silent_correction() {
+    let geforce =
+        parse_nvidia_smi("0, NVIDIA GeForce RTX 4080, [N/A], [N/A], [N/A], [N/A], [N/A], [N/A]\n");
+    assert_eq!(geforce[0].gpu_name, "NVIDIA GeForce RTX 4080");
+    assert!(!geforce[0].exposed());
+    let report = vram_health::build_report(&[geforce.clone(), geforce]).unwrap();
+    assert!(!report.exposed);
+    assert_eq!(report.stable, None);
+
+    let start = parse_nvidia_smi("0, NVIDIA RTX A5000, Enabled, 12, 0, 1, 0, No");
+    assert_eq!(start[0].ecc_enabled, Some(true));
+    assert_eq!(start[0].corrected, Some(12));
+    let end = parse_nvidia_smi("0, NVIDIA RTX A5000, Enabled, 40, 0, 1, 0, No");
+    let report = vram_health::build_report(&[start.clone(), end]).unwrap();
+    assert_eq!(report.corrected_delta, 28);
+    assert_eq!(report.uncorrected_delta, 0);
+    assert_eq!(report.stable, Some(false));
+
+    let steady = vram_health::build_report(&[start.clone(), start]).unwrap();
+    assert_eq!(steady.stable, Some(true));
+    let merged = vram_health::merge(&[&steady, &report]).unwrap();
+    assert_eq!(merged.corrected_delta, 28);
+    assert_eq!(merged.samples, 4);
+    assert_eq!(merged.stable, Some(false));
+}
+
+// ==================== 帧处理 ====================
+
+#[derive(Default)]
+struct RecordingSink(Mutex<Vec<String>>);
+
+impl EventSink for RecordingSink {
+    fn emit_value(&self, event: &str, _payload: serde_json::Value) {
+        self.0.lock().unwrap().push(event.to_string());
+    }
+}
+
+#[derive(Default)]
+struct TestHost {
+    counted: Vec<f64>,
+    hooks: u32,
+    benchmark_complete: bool,
+}
+
+impl CaptureHost for TestHost {
+    fn state(&self) -> LoopState {
+        LoopState::Recording
+    }
+    fn on_presenting(&mut self) -> Option<u32> {
+        None
+    }
+    fn on_row(&mut self, _timestamp_ms: Option<f64>, _row: &FrameRow) {}
+    fn on_counted_frame(&mut self, _active_secs: f64, frametime_ms: f64) {
+        self.counted.push(frametime_ms);
+    }
+    fn on_stutter_hook(&mut self, _stutter: &FpsStutter) {
+        self.hooks += 1;
+    }
+    fn smoothing(&self) -> FpsSmoothing {
+        FpsSmoothing::default()
+    }
+    fn decorate(&mut self, _snapshot: &mut FpsSnapshot) {}
+    fn on_benchmark_complete(&mut self) {
+        self.benchmark_complete = true;
+    }
+}
+
+#[test]
+fn frame_pipeline_skips_warmup_and_stops_benchmark() {
+    let (tx, rx) = mpsc::channel();
+    tx.send("Application,ProcessID,SwapChainAddress,Runtime,FrameTime".to_string())
+        .unwrap();
+    for i in 0..1000 {
+        let frametime = if i == 300 { 100.0 } else { 10.0 };
+        tx.send(format!("game.exe,1,0x1,DXGI,{}", frametime)).unwrap();
+    }
+    drop(tx);
+
+    let sink = Arc::new(RecordingSink::default());
+    let mut pipeline = FramePipeline::new(
+        PipelineConfig {
+            session_id: "s".to_string(),
+            process_name: "game.exe".to_string(),
+            warmup_secs: 1.0,
+            benchmark_end: Some(5.0),
+            snapshot_interval: 1.0,
+            stutter_hooks: true,
+            ..Default::default()
+        },
+        sink.clone(),
+    );
+    let mut host = TestHost::default();
+    pipeline.run(&rx, &mut host);
+    let totals = pipeline.finish();
+
+    assert!(host.benchmark_complete && totals.benchmark_completed);
+    assert_eq!(totals.stutter_count, 1);
+    assert_eq!(host.hooks, 1);
+    // 预热 1 秒后到 5 秒结束：约 4 秒的帧计入会话（含 100ms 卡顿帧）
+    assert_eq!(host.counted.len(), 391);
+    assert!(host.counted.contains(&100.0));
+    let events = sink.0.lock().unwrap();
+    assert_eq!(events.iter().filter(|e| *e == "fps-stutter").count(), 1);
+    assert!(events.iter().any(|e| e == "benchmark-countdown"));
+}
+
+// ==================== 游戏识别 ====================
+
+#[test]
+fn emulator_game_titles() {
+    let rpcs3 = find_emulator("RPCS3.exe").unwrap();
+    assert_eq!(rpcs3.platform, "PS3");
+    assert_eq!(
+        parse_game_title(
+            &rpcs3,
+            "FPS: 60.00 | Vulkan | 0.0.29 | Demon's Souls [BLUS30443]"
+        )
+        .as_deref(),
+        Some("Demon's Souls")
+    );
+
+    let mumu = find_emulator("MuMuPlayer.exe").unwrap();
+    assert!(parse_game_title(&mumu, "原神").is_none());
+    assert!(find_emulator("notepad.exe").is_none());
+}
+
+#[test]
+fn pinyin_search_keys() {
+    assert_eq!(pinyin::initials("黑神话：悟空"), "hsh：wk");
+    assert_eq!(pinyin::full("黑神话：悟空"), "heishenhua：wukong");
+    assert_eq!(pinyin::initials("CS2"), "cs2");
+}
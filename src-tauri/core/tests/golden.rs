@@ -0,0 +1,171 @@
+// 录制的 PresentMon 输出按监测读取线程的流程逐帧回放，与 traces/*.json 中的结果逐字比对。
+// 解析或统计逻辑有意修改时，用 `GOLDEN_UPDATE=1 cargo test -p gamebench-core --test golden`
+// 重新生成结果文件，并在提交前检查差异。
+
+use gamebench_core::capture_time::ActiveClock;
+use gamebench_core::frame_stats::{FrameStats, FrameStatsReport};
+use gamebench_core::metrics::{FpsSummary, FrametimeHistogram, PercentileMetrics};
+use gamebench_core::presentmon::CsvStream;
+use gamebench_core::stutter::StutterDetector;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// 回放结果，字段顺序即结果文件中的顺序
+#[derive(Serialize)]
+struct GoldenResult {
+    /// 成功解析的帧数（含预热）
+    parsed_rows: usize,
+    /// 未能解析的非空行（含表头）
+    skipped_lines: usize,
+    /// 计入统计的帧数（不含预热）
+    frames: usize,
+    active_secs: f64,
+    summary: FpsSummary,
+    stutter_count: u32,
+    histogram: FrametimeHistogram,
+    percentiles: PercentileMetrics,
+    streaming: FrameStatsReport,
+    avg_cpu_busy_ms: f64,
+    avg_gpu_busy_ms: f64,
+    present_modes: Vec<String>,
+}
+
+fn trace_path(name: &str, ext: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/traces")
+        .join(format!("{}.{}", name, ext))
+}
+
+/// 与 fps_monitor 读取线程相同的顺序：解析 → 有效时长 → 卡顿检测 → 预热过滤 → 统计
+fn replay(name: &str, warmup_secs: f64) -> GoldenResult {
+    let text = std::fs::read_to_string(trace_path(name, "csv")).unwrap();
+    let mut csv = CsvStream::default();
+    let mut clock = ActiveClock::default();
+    let mut stutter = StutterDetector::default();
+    let mut stutter_count = 0;
+    let mut frames = Vec::new();
+    let (mut parsed_rows, mut skipped_lines) = (0, 0);
+    let (mut cpu_busy, mut gpu_busy) = (0.0, 0.0);
+    let mut present_modes: Vec<String> = Vec::new();
+
+    for line in text.lines() {
+        let Some(row) = csv.push_line(line) else {
+            if !line.trim().is_empty() {
+                skipped_lines += 1;
+            }
+            continue;
+        };
+        parsed_rows += 1;
+        clock.on_frame(row.timestamp_ms, row.frametime);
+        let counted = clock.active_secs() >= warmup_secs;
+        if stutter.push(row.frametime).is_some() && counted {
+            stutter_count += 1;
+        }
+        if !row.present_mode.is_empty() && !present_modes.contains(&row.present_mode) {
+            present_modes.push(row.present_mode.clone());
+        }
+        if counted {
+            frames.push(row.frametime);
+            cpu_busy += row.cpu_busy;
+            gpu_busy += row.gpu_busy;
+        }
+    }
+
+    let mut stats = FrameStats::default();
+    stats.extend(&frames);
+    let per_frame = |sum: f64| (sum / frames.len().max(1) as f64 * 1000.0).round() / 1000.0;
+    GoldenResult {
+        parsed_rows,
+        skipped_lines,
+        frames: frames.len(),
+        active_secs: (clock.active_secs() * 1000.0).round() / 1000.0,
+        summary: FpsSummary::from_frames(&frames),
+        stutter_count,
+        histogram: FrametimeHistogram::from_frames(&frames),
+        percentiles: PercentileMetrics::compute(&[50.0, 95.0, 99.0], &[5.0], &frames),
+        streaming: stats.report(),
+        avg_cpu_busy_ms: per_frame(cpu_busy),
+        avg_gpu_busy_ms: per_frame(gpu_busy),
+        present_modes,
+    }
+}
+
+fn check_golden(name: &str, result: &GoldenResult) {
+    let actual = serde_json::to_string_pretty(result).unwrap() + "\n";
+    let path = trace_path(name, "json");
+    if std::env::var_os("GOLDEN_UPDATE").is_some() {
+        std::fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("缺少结果文件 {}: {}", path.display(), e));
+    assert!(
+        actual == expected,
+        "{} 的回放结果与 {} 不一致：\n{}",
+        name,
+        path.display(),
+        actual
+    );
+}
+
+/// 逐帧累计的流式统计与精确计算在 0.01 ms 直方图精度内一致
+fn check_streaming(result: &GoldenResult) {
+    let ft = |fps: f64| 1000.0 / fps;
+    assert_eq!(result.streaming.total_frames, result.summary.total_frames);
+    assert!((ft(result.streaming.fps_1_low) - ft(result.summary.fps_1_low)).abs() < 0.05);
+    assert!((ft(result.streaming.fps_01_low) - ft(result.summary.fps_01_low)).abs() < 0.05);
+}
+
+#[test]
+fn presentmon_v1_trace() {
+    let result = replay("presentmon_v1", 0.0);
+    assert_eq!(result.parsed_rows, 1200);
+    assert_eq!(result.skipped_lines, 1);
+    assert_eq!(result.stutter_count, 2);
+    assert_eq!(result.present_modes, ["Hardware: Independent Flip"]);
+    check_streaming(&result);
+    check_golden("presentmon_v1", &result);
+}
+
+#[test]
+fn presentmon_v2_trace_with_warmup() {
+    let result = replay("presentmon_v2", 2.0);
+    assert_eq!(result.parsed_rows, 1500);
+    // 第 200 帧的卡顿落在预热期内
+    assert_eq!(result.stutter_count, 2);
+    assert!(result.frames < result.parsed_rows);
+    assert!(result.avg_gpu_busy_ms > result.avg_cpu_busy_ms);
+    check_streaming(&result);
+    check_golden("presentmon_v2", &result);
+}
+
+#[test]
+fn native_etw_trace() {
+    let result = replay("native_etw", 0.0);
+    assert_eq!(result.parsed_rows, 1000);
+    assert_eq!(result.stutter_count, 1);
+    assert!(result.present_modes.is_empty());
+    check_streaming(&result);
+    check_golden("native_etw", &result);
+}
+
+#[test]
+fn odd_schema_trace() {
+    let result = replay("odd_schema", 0.0);
+    assert_eq!(result.parsed_rows, 800);
+    assert_eq!(result.skipped_lines, 1);
+    assert_eq!(result.avg_cpu_busy_ms, 0.0);
+    check_streaming(&result);
+    check_golden("odd_schema", &result);
+}
+
+#[test]
+fn corrupted_trace() {
+    let result = replay("corrupted", 0.0);
+    assert_eq!(result.parsed_rows, 900);
+    // 表头 + 截断、非数值、越界、重复表头与乱码行
+    assert_eq!(result.skipped_lines, 26);
+    assert_eq!(result.stutter_count, 1);
+    check_streaming(&result);
+    check_golden("corrupted", &result);
+}
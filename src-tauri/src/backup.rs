@@ -0,0 +1,345 @@
+use crate::tasks::{self, TaskHandle, TaskKind};
+use base64::Engine;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager};
+use walkdir::WalkDir;
+
+/// 备份文件格式版本
+const FORMAT_VERSION: u32 = 1;
+const BACKUP_EXTENSION: &str = "gbbak";
+
+/// 数据目录下需要备份的文件：profile 列表、默认 profile 的设置、
+/// 用户自定义的采集规则与进程过滤、OS 设置档案
+const BACKUP_FILES: &[&str] = &[
+    "profiles.json",
+    "settings.json",
+    "capture_rules.local.json",
+    "process_filter.local.json",
+    "benchmark_profiles.json",
+];
+/// 需要整体备份的目录：默认 profile 的会话库，其余 profile 的设置与会话库
+/// 服务端下发的规则缓存、网吧模式配置和本机 OS 设置快照与机器相关，不备份
+const BACKUP_DIRS: &[&str] = &["sessions", "profiles"];
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BackupArchive {
+    format_version: u32,
+    app_version: String,
+    /// 创建时间 (RFC 3339)
+    created_at: String,
+    files: Vec<BackupFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BackupFile {
+    /// 相对数据目录的路径，统一使用 '/'
+    path: String,
+    /// base64 编码的文件内容
+    data: String,
+    sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupSummary {
+    /// 备份文件路径
+    pub path: String,
+    pub app_version: String,
+    pub created_at: String,
+    pub files: usize,
+    /// 其中的会话记录数
+    pub sessions: usize,
+    /// 压缩后大小 (bytes)
+    pub size_bytes: u64,
+}
+
+// ==================== 收集与校验 ====================
+
+fn data_root(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map_err(|e| format!("无法获取数据目录: {}", e))
+}
+
+fn relative_path(root: &Path, path: &Path) -> Option<String> {
+    let rel = path.strip_prefix(root).ok()?;
+    let parts: Vec<String> = rel
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    Some(parts.join("/"))
+}
+
+/// 备份中的路径只能落在数据目录内
+fn safe_path(root: &Path, rel: &str) -> Result<PathBuf, String> {
+    let rel_path = Path::new(rel);
+    let allowed = !rel.is_empty()
+        && rel_path
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)));
+    let top = rel.split('/').next().unwrap_or_default();
+    if !allowed || !(BACKUP_FILES.contains(&rel) || BACKUP_DIRS.contains(&top)) {
+        return Err(format!("备份中包含不允许的路径: {}", rel));
+    }
+    Ok(root.join(rel_path))
+}
+
+/// 旧版逐文件存储的会话（恢复后迁移到会话数据库）
+fn is_session_file(rel: &str) -> bool {
+    // 只统计会话目录本身的文件，不含其下的每秒聚合与帧时间
+    rel.rsplit('/').nth(1) == Some("sessions")
+        && (rel.ends_with(".json") || rel.ends_with(".bin"))
+        && !rel.ends_with("key_check.bin")
+        && !rel.ends_with("sessions/index.json")
+        && !rel.ends_with("sessions/index.bin")
+}
+
+/// 加密的会话库是与本机 Windows 账户绑定的 DPAPI 数据，换电脑后无法解密，不能备份；
+/// 明文备份也不能恢复到加密的会话库中（会留下明文文件，且按加密格式读取时找不到）
+fn ensure_plain_stores(root: &Path, action: &str) -> Result<(), String> {
+    let encrypted = BACKUP_DIRS.iter().any(|dir| {
+        WalkDir::new(root.join(dir))
+            .into_iter()
+            .flatten()
+            .any(|e| e.file_type().is_file() && e.file_name() == "key_check.bin")
+    });
+    if encrypted {
+        return Err(format!(
+            "会话库已加密：加密数据与本机 Windows 账户绑定，在其他电脑上无法恢复。请先在设置中关闭会话加密（所有 profile）后再{}",
+            action
+        ));
+    }
+    Ok(())
+}
+
+fn collect_files(task: &TaskHandle, root: &Path) -> Result<Vec<BackupFile>, String> {
+    let mut paths: Vec<PathBuf> = BACKUP_FILES
+        .iter()
+        .map(|f| root.join(f))
+        .filter(|p| p.is_file())
+        .collect();
+    for dir in BACKUP_DIRS {
+        paths.extend(
+            WalkDir::new(root.join(dir))
+                .into_iter()
+                .flatten()
+                .filter(|e| e.file_type().is_file())
+                // 数据库事务日志随快照处理，不单独备份；导入中途的帧时间文件不完整
+                .filter(|e| {
+                    let name = e.file_name().to_string_lossy();
+                    !name.ends_with("-journal") && !crate::storage::is_staged_frames(&name)
+                })
+                .map(|e| e.into_path()),
+        );
+    }
+
+    // 最后一步为压缩写入
+    let total = paths.len() as u64 + 1;
+    paths
+        .iter()
+        .filter_map(|path| relative_path(root, path).map(|rel| (path, rel)))
+        .enumerate()
+        .map(|(i, (path, rel))| {
+            task.check()?;
+            task.progress(i as u64, total, "读取数据文件");
+            // 会话数据库可能正被写入，备份一致的快照
+            let bytes = if crate::storage::is_session_db(&rel) {
+                crate::storage::snapshot_db(path)?
+            } else {
+                std::fs::read(path).map_err(|e| format!("读取 {} 失败: {}", path.display(), e))?
+            };
+            Ok(BackupFile {
+                sha256: crate::signing::hex(&Sha256::digest(&bytes)),
+                data: base64::engine::general_purpose::STANDARD.encode(&bytes),
+                path: rel,
+            })
+        })
+        .collect()
+}
+
+fn read_archive(path: &Path) -> Result<BackupArchive, String> {
+    let file = std::fs::File::open(path).map_err(|e| format!("无法打开备份文件: {}", e))?;
+    let mut text = String::new();
+    GzDecoder::new(file)
+        .read_to_string(&mut text)
+        .map_err(|_| "不是有效的 GameBench 备份文件".to_string())?;
+    let archive: BackupArchive =
+        serde_json::from_str(&text).map_err(|e| format!("备份内容无法解析: {}", e))?;
+    if archive.format_version > FORMAT_VERSION {
+        return Err(format!(
+            "备份由更新版本的 GameBench ({}) 创建，请先升级",
+            archive.app_version
+        ));
+    }
+    Ok(archive)
+}
+
+fn summarize(path: &Path, archive: &BackupArchive) -> BackupSummary {
+    BackupSummary {
+        path: path.to_string_lossy().to_string(),
+        app_version: archive.app_version.clone(),
+        created_at: archive.created_at.clone(),
+        files: archive.files.len(),
+        sessions: archive
+            .files
+            .iter()
+            .map(|f| {
+                if crate::storage::is_session_db(&f.path) {
+                    base64::engine::general_purpose::STANDARD
+                        .decode(&f.data)
+                        .map(|bytes| crate::storage::count_db_sessions(&bytes))
+                        .unwrap_or(0)
+                } else {
+                    is_session_file(&f.path) as usize
+                }
+            })
+            .sum(),
+        size_bytes: std::fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+    }
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 将设置、自定义规则与会话库打包为单个备份文件（默认保存到下载目录），返回任务 ID
+/// 会话库锁定期间尚未写入磁盘的会话不包含在内
+#[tauri::command]
+pub fn backup_data(app: AppHandle, path: Option<String>) -> Result<String, String> {
+    crate::kiosk::ensure_unlocked()?;
+    let root = data_root(&app)?;
+    ensure_plain_stores(&root, "备份")?;
+    let path = match path {
+        Some(p) => PathBuf::from(p),
+        None => app
+            .path()
+            .download_dir()
+            .map_err(|e| format!("无法获取下载目录: {}", e))?
+            .join(format!(
+                "gamebench-backup-{}.{}",
+                chrono::Local::now().format("%Y%m%d-%H%M%S"),
+                BACKUP_EXTENSION
+            )),
+    };
+
+    let app_version = app.package_info().version.to_string();
+    tasks::spawn(&app, TaskKind::Maintenance, "备份数据", move |task| {
+        write_backup(task, &root, &path, app_version)
+    })
+}
+
+fn write_backup(
+    task: &TaskHandle,
+    root: &Path,
+    path: &Path,
+    app_version: String,
+) -> Result<BackupSummary, String> {
+    // 排队期间可能已开启加密
+    ensure_plain_stores(root, "备份")?;
+    let archive = BackupArchive {
+        format_version: FORMAT_VERSION,
+        app_version,
+        created_at: chrono::Local::now().to_rfc3339(),
+        files: collect_files(task, root)?,
+    };
+    task.progress(
+        archive.files.len() as u64,
+        archive.files.len() as u64 + 1,
+        "压缩写入",
+    );
+    let json = serde_json::to_vec(&archive).map_err(|e| e.to_string())?;
+
+    let file = std::fs::File::create(path).map_err(|e| format!("无法创建备份文件: {}", e))?;
+    let mut encoder = GzEncoder::new(file, Compression::default());
+    encoder
+        .write_all(&json)
+        .and_then(|_| encoder.finish().map(|_| ()))
+        .map_err(|e| format!("写入备份失败: {}", e))?;
+
+    let summary = summarize(path, &archive);
+    log::info!(
+        "已备份 {} 个文件（{} 条会话）到 {}",
+        summary.files,
+        summary.sessions,
+        path.display()
+    );
+    Ok(summary)
+}
+
+/// 读取备份文件信息（恢复前确认用）
+#[tauri::command]
+pub fn inspect_backup(path: String) -> Result<BackupSummary, String> {
+    let path = PathBuf::from(path);
+    Ok(summarize(&path, &read_archive(&path)?))
+}
+
+/// 从备份恢复数据：备份中的文件覆盖同名文件，本机其他会话保留；返回任务 ID
+#[tauri::command]
+pub fn restore_data(app: AppHandle, path: String) -> Result<String, String> {
+    crate::kiosk::ensure_unlocked()?;
+    if crate::fps_monitor::get_fps_status()?.running {
+        return Err("正在进行 FPS 监测，请先停止后再恢复数据".to_string());
+    }
+    ensure_plain_stores(&data_root(&app)?, "恢复")?;
+    let worker = app.clone();
+    tasks::spawn(&app, TaskKind::Maintenance, "恢复数据", move |task| {
+        restore(&worker, task, PathBuf::from(path))
+    })
+}
+
+fn restore(app: &AppHandle, task: &TaskHandle, path: PathBuf) -> Result<BackupSummary, String> {
+    task.progress(0, 0, "读取备份");
+    let archive = read_archive(&path)?;
+    let root = data_root(app)?;
+    ensure_plain_stores(&root, "恢复")?;
+    // 校验与写入各算一轮
+    let total = archive.files.len() as u64 * 2;
+
+    // 先全部校验再写入，避免恢复到一半
+    let mut decoded = Vec::with_capacity(archive.files.len());
+    for (i, file) in archive.files.iter().enumerate() {
+        task.progress(i as u64, total, "校验备份");
+        let target = safe_path(&root, &file.path)?;
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(&file.data)
+            .map_err(|_| format!("备份中的 {} 已损坏", file.path))?;
+        if crate::signing::hex(&Sha256::digest(&bytes)) != file.sha256 {
+            return Err(format!("备份中的 {} 校验失败", file.path));
+        }
+        decoded.push((target, bytes, crate::storage::is_session_db(&file.path)));
+    }
+
+    for (i, (target, bytes, session_db)) in decoded.iter().enumerate() {
+        task.progress((decoded.len() + i) as u64, total, "写入数据");
+        // 会话数据库按会话合并，不覆盖本机的其他会话
+        if *session_db {
+            crate::storage::merge_db(target, bytes)?;
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("无法创建目录: {}", e))?;
+        }
+        std::fs::write(target, bytes)
+            .map_err(|e| format!("写入 {} 失败: {}", target.display(), e))?;
+    }
+
+    // 会话库密码可能已变化，重新加载 profile 与设置
+    crate::storage::lock_session_store()?;
+    crate::user_profile::load(app);
+    crate::settings::load(app);
+    let _ = app.emit("data-restored", ());
+
+    let summary = summarize(&path, &archive);
+    log::info!(
+        "已从 {} 恢复 {} 个文件（{} 条会话）",
+        path.display(),
+        summary.files,
+        summary.sessions
+    );
+    Ok(summary)
+}
@@ -0,0 +1,167 @@
+use crate::mirrors;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+/// 社区规则下载接口
+const RULESET_PATH: &str = "/api/capture-rules";
+
+/// 下载的社区规则缓存
+const REMOTE_FILE: &str = "capture_rules.json";
+/// 用户本地覆盖规则
+const LOCAL_FILE: &str = "capture_rules.local.json";
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapchainSelect {
+    /// 统计所有交换链
+    #[default]
+    All,
+    /// 只统计最先稳定出帧的交换链（忽略启动器/覆盖层的额外交换链）
+    Dominant,
+}
+
+/// 游戏内置测试场景 / 推荐测试路线
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneMarker {
+    pub name: String,
+    pub description: String,
+    /// 建议测试时长 (秒)
+    #[serde(default)]
+    pub duration_secs: Option<u32>,
+}
+
+/// 单个游戏的采集规则
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureRule {
+    /// 规则标识（本地规则按此覆盖社区规则）
+    pub id: String,
+    pub game_name: String,
+    /// 属于该游戏的进程名（启动器、反作弊、游戏本体等）
+    pub processes: Vec<String>,
+    /// 实际渲染画面、应当监测的进程名
+    #[serde(default)]
+    pub capture_process: Option<String>,
+    #[serde(default)]
+    pub swapchain: SwapchainSelect,
+    /// 预热时长 (秒)，期间的帧不计入统计
+    #[serde(default)]
+    pub warmup_secs: u32,
+    #[serde(default)]
+    pub scene_markers: Vec<SceneMarker>,
+    /// 是否为本地规则（由后端填充）
+    #[serde(default)]
+    pub local: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Ruleset {
+    #[serde(default)]
+    pub version: u32,
+    pub rules: Vec<CaptureRule>,
+}
+
+// ==================== 读写 ====================
+
+fn data_file(app: &AppHandle, name: &str) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("无法获取数据目录: {}", e))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建数据目录: {}", e))?;
+    Ok(dir.join(name))
+}
+
+fn read_ruleset(app: &AppHandle, name: &str) -> Ruleset {
+    data_file(app, name)
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn write_ruleset(app: &AppHandle, name: &str, ruleset: &Ruleset) -> Result<(), String> {
+    let path = data_file(app, name)?;
+    let text = serde_json::to_string_pretty(ruleset).map_err(|e| e.to_string())?;
+    std::fs::write(&path, text).map_err(|e| format!("保存采集规则失败: {}", e))
+}
+
+/// 合并社区规则与本地规则，本地规则按 id 覆盖
+pub fn load_rules(app: &AppHandle) -> Vec<CaptureRule> {
+    let mut rules = read_ruleset(app, REMOTE_FILE).rules;
+    for mut local in read_ruleset(app, LOCAL_FILE).rules {
+        local.local = true;
+        rules.retain(|r| r.id != local.id);
+        rules.push(local);
+    }
+    rules
+}
+
+/// 按进程名查找规则
+pub fn find_rule<'a>(rules: &'a [CaptureRule], exe_name: &str) -> Option<&'a CaptureRule> {
+    rules.iter().find(|rule| {
+        rule.processes
+            .iter()
+            .chain(rule.capture_process.iter())
+            .any(|p| p.eq_ignore_ascii_case(exe_name))
+    })
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 从服务端下载最新社区采集规则，返回规则数量
+#[tauri::command]
+pub async fn update_capture_rules(
+    app: AppHandle,
+    server_url: Option<String>,
+) -> Result<usize, String> {
+    crate::connectivity::ensure_online()?;
+    let urls = mirrors::server_urls(server_url, RULESET_PATH).await;
+    let ruleset: Ruleset = mirrors::get_json(&urls)
+        .await
+        .map_err(|e| format!("下载采集规则失败: {}", e))?;
+
+    write_ruleset(&app, REMOTE_FILE, &ruleset)?;
+    log::info!(
+        "已更新社区采集规则 v{}: {} 条",
+        ruleset.version,
+        ruleset.rules.len()
+    );
+    Ok(ruleset.rules.len())
+}
+
+/// 列出合并后的采集规则
+#[tauri::command]
+pub fn list_capture_rules(app: AppHandle) -> Result<Vec<CaptureRule>, String> {
+    let mut rules = load_rules(&app);
+    rules.sort_by(|a, b| a.game_name.cmp(&b.game_name));
+    Ok(rules)
+}
+
+/// 保存本地规则（覆盖同 id 的社区规则）
+#[tauri::command]
+pub fn save_local_capture_rule(app: AppHandle, mut rule: CaptureRule) -> Result<(), String> {
+    if rule.id.trim().is_empty() || rule.processes.is_empty() {
+        return Err("规则必须包含 id 和至少一个进程名".to_string());
+    }
+    rule.local = false;
+
+    let mut local = read_ruleset(&app, LOCAL_FILE);
+    local.rules.retain(|r| r.id != rule.id);
+    local.rules.push(rule);
+    write_ruleset(&app, LOCAL_FILE, &local)
+}
+
+/// 删除本地规则（恢复使用社区规则）
+#[tauri::command]
+pub fn delete_local_capture_rule(app: AppHandle, id: String) -> Result<(), String> {
+    let mut local = read_ruleset(&app, LOCAL_FILE);
+    let before = local.rules.len();
+    local.rules.retain(|r| r.id != id);
+    if local.rules.len() == before {
+        return Err(format!("未找到本地规则「{}」", id));
+    }
+    write_ruleset(&app, LOCAL_FILE, &local)
+}
@@ -0,0 +1,18 @@
+/// 离线模式下联网功能返回的错误
+pub const OFFLINE_ERROR: &str = "离线模式已开启，联网功能不可用";
+
+// ==================== 联网策略 ====================
+
+/// 是否处于离线模式；所有访问互联网的功能在发起请求前都应先检查
+pub fn is_offline() -> bool {
+    crate::settings::get().offline
+}
+
+/// 离线模式下直接返回错误，避免请求超时后才失败
+pub fn ensure_online() -> Result<(), String> {
+    if is_offline() {
+        Err(OFFLINE_ERROR.to_string())
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,270 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// 测试期间轮询事件日志的间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// 在重置时刻前后多少秒内查找帧时间空洞
+const HOLE_SEARCH_SECS: f64 = 15.0;
+/// 帧时间超过此值才认为是驱动重置造成的空洞 (ms)
+const HOLE_MIN_MS: f64 = 500.0;
+/// 保留的事件描述长度
+#[cfg(target_os = "windows")]
+const MESSAGE_MAX_CHARS: usize = 200;
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriverReset {
+    /// 事件来源: Display / nvlddmkm / amdkmdag
+    pub provider: String,
+    pub event_id: u32,
+    /// 事件时间 (Unix 毫秒)
+    pub unix_ms: u64,
+    /// 距会话开始的实际经过时间 (秒)
+    pub at_secs: f64,
+    /// 事件描述（截断）
+    pub message: String,
+    /// 重置前后最长的一帧 (ms)，未找到明显空洞时为 None
+    pub hole_ms: Option<f64>,
+}
+
+// ==================== 事件日志 (Windows) ====================
+
+/// Display 4101: 显示驱动停止响应并已恢复 (TDR)
+/// nvlddmkm 13/14/153: NVIDIA 图形异常 / 引擎重置
+/// amdkmdag: AMD 内核驱动的错误事件
+fn is_reset_event(provider: &str, id: u32) -> bool {
+    match provider.to_ascii_lowercase().as_str() {
+        "display" => id == 4101,
+        "nvlddmkm" => matches!(id, 13 | 14 | 153),
+        "amdkmdag" => true,
+        _ => false,
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn xml_unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// 读取 `<tag ... name='value'` 中的属性值（wevtutil 使用单引号）
+#[cfg(target_os = "windows")]
+fn xml_attr<'a>(chunk: &'a str, tag: &str, name: &str) -> Option<&'a str> {
+    let start = chunk.find(tag)?;
+    let rest = &chunk[start..];
+    let rest = &rest[..rest.find('>')?];
+    let at = rest.find(&format!("{}=", name))? + name.len() + 1;
+    let quote = rest[at..].chars().next()?;
+    let value = &rest[at + 1..];
+    Some(&value[..value.find(quote)?])
+}
+
+/// 读取 `<tag ...>text</tag>` 中的文本
+#[cfg(target_os = "windows")]
+fn xml_text<'a>(chunk: &'a str, tag: &str) -> Option<&'a str> {
+    let start = chunk.find(&format!("<{}", tag))?;
+    let rest = &chunk[start..];
+    let rest = &rest[rest.find('>')? + 1..];
+    Some(&rest[..rest.find(&format!("</{}>", tag))?])
+}
+
+#[cfg(target_os = "windows")]
+fn parse_events(xml: &str) -> Vec<(String, u32, u64, String)> {
+    xml.split("<Event ")
+        .skip(1)
+        .filter_map(|chunk| {
+            let provider = xml_attr(chunk, "<Provider ", "Name")?.to_string();
+            let id: u32 = xml_text(chunk, "EventID")?.trim().parse().ok()?;
+            let time = xml_attr(chunk, "<TimeCreated ", "SystemTime")?;
+            let unix_ms = chrono::DateTime::parse_from_rfc3339(time)
+                .ok()?
+                .timestamp_millis()
+                .max(0) as u64;
+            let message = xml_text(chunk, "Message")
+                .map(|m| {
+                    xml_unescape(m.trim())
+                        .chars()
+                        .take(MESSAGE_MAX_CHARS)
+                        .collect()
+                })
+                .unwrap_or_default();
+            Some((provider, id, unix_ms, message))
+        })
+        .collect()
+}
+
+/// 查询 [from_ms, to_ms] 内的显卡驱动重置事件: (来源, 事件 ID, Unix 毫秒, 描述)
+#[cfg(target_os = "windows")]
+fn query_events(from_ms: u64, to_ms: u64) -> Vec<(String, u32, u64, String)> {
+    use std::os::windows::process::CommandExt;
+    use std::process::Command;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let format_time = |ms: u64| {
+        chrono::DateTime::from_timestamp_millis(ms as i64)
+            .map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Millis, true))
+            .unwrap_or_default()
+    };
+    let query = format!(
+        "*[System[(Provider[@Name='Display'] or Provider[@Name='nvlddmkm'] or Provider[@Name='amdkmdag']) \
+         and TimeCreated[@SystemTime>='{}' and @SystemTime<='{}']]]",
+        format_time(from_ms),
+        format_time(to_ms)
+    );
+
+    // /uni 输出 UTF-16，避免中文系统下的代码页问题
+    let output = Command::new("wevtutil")
+        .args([
+            "qe",
+            "System",
+            &format!("/q:{}", query),
+            "/f:RenderedXml",
+            "/uni:true",
+        ])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output();
+    let output = match output {
+        Ok(o) if o.status.success() => o,
+        Ok(o) => {
+            log::debug!("查询事件日志失败: {}", String::from_utf8_lossy(&o.stderr));
+            return vec![];
+        }
+        Err(e) => {
+            log::debug!("无法运行 wevtutil: {}", e);
+            return vec![];
+        }
+    };
+    let wide: Vec<u16> = output
+        .stdout
+        .chunks_exact(2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .collect();
+    parse_events(&String::from_utf16_lossy(&wide))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn query_events(_from_ms: u64, _to_ms: u64) -> Vec<(String, u32, u64, String)> {
+    vec![]
+}
+
+fn query_resets(from_ms: u64, to_ms: u64, started_at_ms: u64) -> Vec<DriverReset> {
+    query_events(from_ms, to_ms)
+        .into_iter()
+        .filter(|(provider, id, _, _)| is_reset_event(provider, *id))
+        .map(|(provider, event_id, unix_ms, message)| DriverReset {
+            provider,
+            event_id,
+            unix_ms,
+            at_secs: (unix_ms.saturating_sub(started_at_ms) as f64 / 100.0).round() / 10.0,
+            message,
+            hole_ms: None,
+        })
+        .collect()
+}
+
+// ==================== 帧时间空洞 ====================
+
+/// 在重置时刻附近查找最长的一帧
+/// 帧时间累加近似为会话内的时间位置（不计入预热阶段）
+pub fn locate_holes(resets: &mut [DriverReset], frame_times: &[f64], warmup_secs: f64) {
+    let mut position = warmup_secs;
+    let positioned: Vec<(f64, f64)> = frame_times
+        .iter()
+        .map(|ft| {
+            position += ft / 1000.0;
+            (position, *ft)
+        })
+        .collect();
+
+    for reset in resets {
+        reset.hole_ms = positioned
+            .iter()
+            .filter(|(at, ft)| *ft >= HOLE_MIN_MS && (at - reset.at_secs).abs() <= HOLE_SEARCH_SECS)
+            .map(|(_, ft)| *ft)
+            .max_by(f64::total_cmp)
+            .map(|ft| ft.round());
+    }
+}
+
+// ==================== 测试期间监测 ====================
+
+/// 在 FPS 监测期间检测显卡驱动超时恢复 (TDR)
+pub struct ResetWatcher {
+    started_at_ms: u64,
+    stop: Arc<AtomicBool>,
+    /// 轮询线程: (已发现的重置, 已查询到的时间点)
+    handle: Option<JoinHandle<(Vec<DriverReset>, u64)>>,
+}
+
+impl ResetWatcher {
+    /// `live` 为 false 时不在测试期间轮询，仅在结束时查询一次
+    pub fn start(app: &AppHandle, started_at_ms: u64, live: bool) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = stop.clone();
+        let app = app.clone();
+
+        let handle = live.then(|| {
+            std::thread::spawn(move || {
+                let mut found = Vec::new();
+                let mut checked_ms = started_at_ms;
+                let mut last = Instant::now();
+                while !stop_flag.load(Ordering::Relaxed) {
+                    if last.elapsed() >= POLL_INTERVAL {
+                        let now_ms = now_ms();
+                        for reset in query_resets(checked_ms, now_ms, started_at_ms) {
+                            log::warn!(
+                                "测试期间显卡驱动重置: {} 事件 {} (第 {:.1} 秒)",
+                                reset.provider,
+                                reset.event_id,
+                                reset.at_secs
+                            );
+                            let _ = app.emit("driver-reset", &reset);
+                            found.push(reset);
+                        }
+                        checked_ms = now_ms + 1;
+                        last = Instant::now();
+                    }
+                    std::thread::sleep(Duration::from_millis(200));
+                }
+                (found, checked_ms)
+            })
+        });
+
+        ResetWatcher {
+            started_at_ms,
+            stop,
+            handle,
+        }
+    }
+
+    /// 停止监测，返回测试期间的驱动重置事件
+    pub fn finish(mut self) -> Vec<DriverReset> {
+        self.stop.store(true, Ordering::Relaxed);
+        let (mut resets, checked_ms) = self
+            .handle
+            .take()
+            .and_then(|h| h.join().ok())
+            .unwrap_or((Vec::new(), self.started_at_ms));
+        // 补查最后一个轮询间隔（或未轮询时的整个会话）
+        resets.extend(query_resets(checked_ms, now_ms(), self.started_at_ms));
+        resets
+    }
+}
+
+impl Drop for ResetWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+fn now_ms() -> u64 {
+    chrono::Utc::now().timestamp_millis().max(0) as u64
+}
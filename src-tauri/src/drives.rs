@@ -0,0 +1,31 @@
+use crate::units;
+#[cfg(target_os = "windows")]
+pub use gamebench_core::drives::variant_u64;
+pub use gamebench_core::drives::{
+    drive_for_path, list_drive_info, slowest_drive, DriveHealth, DriveInfo, DriveKind, HealthStatus,
+};
+use std::path::Path;
+
+/// 查找路径所在物理盘的健康信息（按当前单位偏好生成描述）
+pub fn health_for_path(path: &Path) -> Option<DriveHealth> {
+    gamebench_core::drives::health_for_path(path, &units::current())
+}
+
+/// 读取物理磁盘 SMART 健康信息（按当前单位偏好生成描述）
+pub fn read_drive_health() -> Vec<DriveHealth> {
+    gamebench_core::drives::read_drive_health(&units::current())
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 获取本地驱动器列表
+#[tauri::command]
+pub fn list_drives() -> Result<Vec<DriveInfo>, String> {
+    Ok(list_drive_info())
+}
+
+/// 获取物理磁盘 SMART 健康信息
+#[tauri::command]
+pub fn get_drive_health() -> Result<Vec<DriveHealth>, String> {
+    Ok(read_drive_health())
+}
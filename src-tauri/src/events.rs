@@ -0,0 +1,17 @@
+pub use gamebench_core::events::EventSink;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter};
+
+/// 把核心库的事件转发给前端
+pub struct TauriSink(pub AppHandle);
+
+impl EventSink for TauriSink {
+    fn emit_value(&self, event: &str, payload: serde_json::Value) {
+        let _ = self.0.emit(event, payload);
+    }
+}
+
+/// 以 AppHandle 构造事件出口
+pub fn sink(app: &AppHandle) -> Arc<dyn EventSink> {
+    Arc::new(TauriSink(app.clone()))
+}
@@ -1,34 +1,321 @@
+use crate::background_activity::{BackgroundInterference, InterferenceSampler};
+use crate::capture_log::CaptureLogWriter;
+use crate::capture_rules::{self, SwapchainSelect};
+use crate::capture_time::CapturePause;
+use crate::dpc_latency::{DpcLatencyReport, DpcSampler};
+use crate::driver_reset::{self, DriverReset, ResetWatcher};
+use crate::frame_cap::{self, FrameCapAdvice};
+use crate::frame_pipeline::{
+    CaptureHost, FpsSnapshot, FpsStutter, FramePipeline, LoopState, PipelineConfig,
+};
+use crate::game_alias;
+use crate::gpu_routing::{self, GpuRouting};
+use crate::input_latency::InputLatency;
+use crate::launch_timing::LaunchTiming;
+use crate::overhead::{self, OverheadReport};
+use crate::overlay;
+use crate::markers::SessionMarker;
+use crate::memory_pressure::{MemoryPressureReport, MemorySampler};
+use crate::metrics::{FpsSummary, FramePacing, PercentileMetrics};
+use crate::pcie::{self, PcieLinkReport};
+use crate::present_etw;
+use crate::present_mode::PresentInfo;
+use crate::presets;
+use crate::presentmon::FrameRow;
+use crate::qoe::QoeScore;
+use crate::plugins::{self, PluginMetricSummary};
+use crate::script_hooks::{self, HookEvent, StutterEvent};
+use crate::power::SleepBehavior;
+use crate::sensors::{self, SensorKind, SensorReadings};
+use crate::session_import::ImportInfo;
+use crate::session_meta::{self, SessionMetadata};
+use crate::session_recovery;
+use crate::signing::{self, CapturePipeline};
+use crate::smoothing::FpsSmoothing;
+use crate::storage;
+use crate::streaming::{self, StreamStats};
+use crate::suggestions::{self, Suggestion};
+use crate::thermal::{self, ThermalReport};
+use crate::timeline::TimelineRecorder;
+use crate::vram_health::{VramSampler, VramStabilityReport};
+use crate::vrr::{self, VrrReport};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
 use std::io::{BufRead, BufReader};
-use std::process::{Child, Command, Stdio};
+use std::process::{Child, ChildStdout, Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Instant;
 use tauri::{AppHandle, Emitter, Manager};
 
+/// 定时基准测试的最长采集/预热时长 (秒)
+const MAX_BENCHMARK_SECS: f64 = 4.0 * 3600.0;
+
 // ==================== 数据结构 ====================
 
+/// 采集档位
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureProfile {
+    /// 帧时间 + 传感器 + PCIe / 输出路径 / 性能模式后台采样
+    #[default]
+    Standard,
+    /// 最低开销：只采集帧时间，不启动任何后台采样，实时快照降为每 5 秒一次
+    Minimal,
+}
+
+impl CaptureProfile {
+    /// 是否启动传感器与硬件状态采样
+    fn samples_telemetry(self) -> bool {
+        self == CaptureProfile::Standard
+    }
+
+    /// 实时快照推送间隔 (秒)
+    fn snapshot_interval_secs(self) -> f64 {
+        match self {
+            CaptureProfile::Standard => 1.0,
+            CaptureProfile::Minimal => 5.0,
+        }
+    }
+}
+
+/// 会话结束方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionStatus {
+    /// 正常结束（用户停止或游戏退出）
+    #[default]
+    Completed,
+    /// 系统睡眠导致采集提前结束
+    InterruptedBySleep,
+    /// 应用异常退出，重启后从检查点恢复
+    Recovered,
+}
+
+/// 监测生命周期
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CapturePhase {
+    #[default]
+    Idle,
+    /// 已接受请求，正在准备并启动 PresentMon
+    Starting,
+    /// PresentMon 已启动，游戏尚未呈现画面
+    WaitingForProcess,
+    /// 正在采集帧时间
+    Capturing,
+    /// 用户手动暂停，PresentMon 继续运行但帧不计入统计
+    Paused,
+    /// 已请求停止，等待 PresentMon 退出
+    Stopping,
+    /// 汇总后台采样并保存会话
+    Finalizing,
+    /// 会话已生成
+    Done,
+    /// 未能开始或未采集到帧
+    Failed,
+}
+
+impl CapturePhase {
+    /// 监测占用中（不能直接开始新的监测）
+    pub fn is_active(self) -> bool {
+        !matches!(self, CapturePhase::Idle | CapturePhase::Done | CapturePhase::Failed)
+    }
+
+    /// PresentMon 正在运行且未被要求停止
+    fn is_recording(self) -> bool {
+        matches!(
+            self,
+            CapturePhase::WaitingForProcess | CapturePhase::Capturing | CapturePhase::Paused
+        )
+    }
+}
+
+/// 状态切换事件（`fps-phase`）
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct FpsSnapshot {
-    /// 当前 FPS (1秒滑动窗口平均)
-    pub fps: f64,
-    /// 1% Low FPS
-    pub fps_1_low: f64,
-    /// 0.1% Low FPS
-    pub fps_01_low: f64,
-    /// 帧时间 (ms)
-    pub frametime_ms: f64,
-    /// CPU 占用时间 (ms)
-    pub cpu_busy_ms: f64,
-    /// GPU 占用时间 (ms)
-    pub gpu_busy_ms: f64,
-    /// 监测的进程名
+pub struct PhaseChange {
+    pub phase: CapturePhase,
+    pub previous: CapturePhase,
+    /// 切换原因
+    pub reason: String,
+    pub process_name: String,
+    pub session_id: String,
+}
+
+/// 已有监测进行中时，新的开始请求如何处理
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BusyPolicy {
+    /// 拒绝新请求
+    #[default]
+    Reject,
+    /// 排队，当前监测结束后自动开始
+    Queue,
+    /// 停止当前监测（正常保存），随后开始新请求
+    Replace,
+}
+
+/// 帧数据采集后端
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureBackend {
+    /// 外部 PresentMon 进程
+    #[default]
+    PresentMon,
+    /// 进程内直接消费 DXGI / D3D9 的 ETW Present 事件，无需 PresentMon.exe
+    Native,
+}
+
+impl CaptureBackend {
+    pub fn label(self) -> &'static str {
+        match self {
+            CaptureBackend::PresentMon => "PresentMon",
+            CaptureBackend::Native => "内置 ETW",
+        }
+    }
+}
+
+/// 排队等待开始的监测请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedCapture {
     pub process_name: String,
-    /// 从开始监测到现在的秒数
-    pub elapsed_secs: f64,
+    pub pid: Option<u32>,
+    pub profile: CaptureProfile,
+    #[serde(default)]
+    pub options: MonitorOptions,
+}
+
+/// 监测选项
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MonitorOptions {
+    /// 采集后端，未指定时使用设置中的默认值
+    #[serde(default)]
+    pub backend: Option<CaptureBackend>,
+    /// 除固定的 1% / 0.1% Low 外额外计算的帧时间百分位（如 90 / 95 / 99），结果为该百分位的帧时间 (ms)
+    #[serde(default)]
+    pub frametime_percentiles: Vec<f64>,
+    /// 自定义 Low FPS 百分比（如 5 表示 5% Low）
+    #[serde(default)]
+    pub low_percentiles: Vec<f64>,
+    /// 逐帧写入原始采集日志（时间戳、帧时间、CPUBusy、GPUBusy、PresentMode），gzip 压缩保存
+    #[serde(default)]
+    pub capture_log: bool,
+    /// 定时基准测试：采满指定时长后自动停止
+    #[serde(default)]
+    pub benchmark: Option<BenchmarkPlan>,
+    /// 实时快照推送间隔 (秒)，未指定时按采集档位
+    #[serde(default)]
+    pub snapshot_interval_secs: Option<f64>,
+    /// 启动的后台采样，未指定时全部启动（最低开销档位下始终全部关闭）
+    #[serde(default)]
+    pub telemetry: Option<TelemetrySet>,
+    /// 所用的基准测试预设 ID（见 presets），记录到会话
+    #[serde(default)]
+    pub preset: Option<String>,
+}
+
+/// 监测期间的后台采样项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetrySet {
+    /// CPU / GPU / 内存传感器读数
+    pub sensors: bool,
+    /// PCIe 链路、混合显卡输出路径与笔记本性能模式
+    pub hardware_state: bool,
+    /// 硬页错误与待机列表
+    pub memory_pressure: bool,
+    /// Defender、搜索索引等后台服务
+    pub background_activity: bool,
+    /// DPC/ISR 延迟；为 false 时按设置中的开关
+    pub dpc_latency: bool,
+    /// 已启用的第三方遥测插件
+    pub plugins: bool,
+}
+
+impl Default for TelemetrySet {
+    fn default() -> Self {
+        TelemetrySet {
+            sensors: true,
+            hardware_state: true,
+            memory_pressure: true,
+            background_activity: true,
+            dpc_latency: false,
+            plugins: true,
+        }
+    }
+}
+
+/// 定时基准测试计划
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkPlan {
+    /// 预热时长 (秒)，不计入统计；未指定时沿用采集规则的预热时长
+    #[serde(default)]
+    pub warmup_secs: Option<f64>,
+    /// 预热结束后的采集时长 (秒)
+    pub duration_secs: f64,
+}
+
+/// 定时基准测试结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkRun {
+    /// 实际使用的预热时长 (秒)
+    pub warmup_secs: f64,
+    /// 计划采集时长 (秒)
+    pub duration_secs: f64,
+    /// 是否采满计划时长（提前手动停止或游戏退出时为 false）
+    pub completed: bool,
+    /// 所用的基准测试预设 ID
+    #[serde(default)]
+    pub preset: Option<String>,
+}
+
+impl MonitorOptions {
+    fn validate(&self) -> Result<(), String> {
+        let all = self.frametime_percentiles.iter().chain(&self.low_percentiles);
+        if let Some(p) = all.copied().find(|p| !(*p > 0.0 && *p <= 100.0)) {
+            return Err(format!("百分位 {} 无效，必须在 0 ~ 100 之间", p));
+        }
+        if let Some(plan) = &self.benchmark {
+            if !(plan.duration_secs >= 1.0 && plan.duration_secs <= MAX_BENCHMARK_SECS) {
+                return Err(format!(
+                    "基准测试时长 {} 秒无效，必须在 1 ~ {} 秒之间",
+                    plan.duration_secs, MAX_BENCHMARK_SECS
+                ));
+            }
+            if plan.warmup_secs.is_some_and(|w| !(0.0..=MAX_BENCHMARK_SECS).contains(&w)) {
+                return Err("预热时长无效".to_string());
+            }
+        }
+        if let Some(secs) = self.snapshot_interval_secs {
+            if !(0.25..=60.0).contains(&secs) {
+                return Err(format!("快照间隔 {} 秒无效，必须在 0.25 ~ 60 秒之间", secs));
+            }
+        }
+        Ok(())
+    }
+
+    /// 按选项计算帧时间序列的百分位指标
+    pub fn compute(&self, frame_times: &[f64]) -> PercentileMetrics {
+        PercentileMetrics::compute(&self.frametime_percentiles, &self.low_percentiles, frame_times)
+    }
+}
+
+/// 开始监测请求的处理结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum StartOutcome {
+    /// 已立即开始
+    Started { session_id: String },
+    /// 已排队，`position` 从 1 开始
+    Queued { position: usize, busy_with: String },
+    /// 已停止同一目标的当前监测，新请求排在队首
+    Replaced { stopped: String },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FpsSession {
+    /// 会话 ID (e.g., "20240601-203015-cs2")
+    #[serde(default)]
+    pub session_id: String,
     /// 游戏进程名
     pub process_name: String,
     /// 平均 FPS
@@ -43,44 +330,302 @@ pub struct FpsSession {
     pub min_fps: f64,
     /// 总帧数
     pub total_frames: u64,
-    /// 监测时长 (秒)
+    /// 有效监测时长 (秒)，不含系统睡眠与长时间无帧的中断
     pub duration_secs: f64,
+    /// 从开始到结束的实际经过时间 (秒)
+    #[serde(default)]
+    pub wall_duration_secs: f64,
+    /// 采集中断（系统睡眠、游戏停止出帧）
+    #[serde(default)]
+    pub pauses: Vec<CapturePause>,
+    /// 会话结束方式
+    #[serde(default)]
+    pub status: SessionStatus,
+    /// GPU PCIe 链路情况
+    #[serde(default)]
+    pub pcie_link: Option<PcieLinkReport>,
+    /// 显存纠错计数与稳定性说明（需要显卡公开 ECC 计数）
+    #[serde(default)]
+    pub vram: Option<VramStabilityReport>,
+    /// 混合显卡输出路径
+    #[serde(default)]
+    pub gpu_routing: Option<GpuRouting>,
+    /// 本次使用的采集规则 id
+    #[serde(default)]
+    pub capture_rule: Option<String>,
+    /// 本次使用的采集档位（Minimal 表示未运行任何后台采样）
+    #[serde(default)]
+    pub capture_profile: CaptureProfile,
+    /// 云游戏 / 串流会话统计（监测对象为串流客户端时）
+    #[serde(default)]
+    pub stream: Option<StreamStats>,
+    /// 游戏进程与环境信息
+    #[serde(default)]
+    pub metadata: SessionMetadata,
+    /// 机身散热配置与厂商性能模式
+    #[serde(default)]
+    pub thermal: Option<ThermalReport>,
+    /// GameBench 自身在测试期间的资源占用
+    #[serde(default)]
+    pub overhead: Option<OverheadReport>,
+    /// 采集流程（后端版本、裁剪设置、硬件指纹），用于报告签名
+    #[serde(default)]
+    pub pipeline: Option<CapturePipeline>,
+    /// 从第三方 CSV 导入时的来源与校验结果
+    #[serde(default)]
+    pub import: Option<ImportInfo>,
+    /// 优化建议
+    #[serde(default)]
+    pub suggestions: Vec<Suggestion>,
+    /// 用户标签 (e.g., "驱动 551.86", "开启 DLSS")
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 由多段会话合并而来时，原会话 ID
+    #[serde(default)]
+    pub merged_from: Vec<String>,
+    /// 遥测插件指标统计
+    #[serde(default)]
+    pub plugin_metrics: Vec<PluginMetricSummary>,
+    /// 开始时间 (unix 毫秒)，旧版本会话为 0
+    #[serde(default)]
+    pub started_at_ms: u64,
+    /// 时间标记（手动添加或由外部设备通过本地 API 注入）
+    #[serde(default)]
+    pub markers: Vec<SessionMarker>,
+    /// 测试期间发生的显卡驱动重置 (TDR)，非空时结果中会有数秒的帧时间空洞
+    #[serde(default)]
+    pub driver_resets: Vec<DriverReset>,
+    /// DPC/ISR 延迟采样（设置中启用时）
+    #[serde(default)]
+    pub dpc_latency: Option<DpcLatencyReport>,
+    /// 硬页错误与待机列表（内存压力）
+    #[serde(default)]
+    pub memory_pressure: Option<MemoryPressureReport>,
+    /// 测试期间活动的后台服务（Defender 扫描、搜索索引、.NET 优化等）
+    #[serde(default)]
+    pub background_activity: Vec<BackgroundInterference>,
+    /// 游戏启动 → 第一帧 / 帧时间稳定的耗时（仅监测开始前后不久启动的游戏）
+    #[serde(default)]
+    pub launch: Option<LaunchTiming>,
+    /// 卡顿次数（帧时间超过近期中位数 2.5 倍）
+    #[serde(default)]
+    pub stutter_count: u32,
+    /// 按监测选项计算的百分位指标
+    #[serde(default)]
+    pub percentiles: PercentileMetrics,
+    /// 帧间隔稳定性（标准差、P99/P99.9 帧时间、长帧数）
+    #[serde(default)]
+    pub pacing: FramePacing,
+    /// 定时基准测试（run_benchmark）的计划与是否完成
+    #[serde(default)]
+    pub benchmark: Option<BenchmarkRun>,
+    /// 体验评分（综合帧率、帧时间一致性、卡顿频率与刷新率匹配）
+    #[serde(default)]
+    pub qoe: Option<QoeScore>,
+    /// 显示器支持 VRR 时，帧落在 VRR 范围内的比例
+    #[serde(default)]
+    pub vrr: Option<VrrReport>,
+    /// 按帧时间分布与显示器刷新率推荐的限帧值（建议引擎据此生成说明）
+    #[serde(default)]
+    pub frame_cap: Option<FrameCapAdvice>,
+    /// 占多数的呈现模式、垂直同步与撕裂，用于排查 DWM 合成导致的帧率损失
+    #[serde(default)]
+    pub present: Option<PresentInfo>,
+    /// 输入到画面的延迟（需要 PresentMon v2）
+    #[serde(default)]
+    pub latency: Option<InputLatency>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FpsStatus {
+    /// 有任一监测占用中（Starting 到 Finalizing）
     pub running: bool,
+    /// 以下三项取最近开始的监测
+    pub phase: CapturePhase,
     pub process_name: Option<String>,
     pub current_fps: Option<f64>,
+    /// 所有进行中的监测
+    #[serde(default)]
+    pub captures: Vec<CaptureStatus>,
+}
+
+/// 单个进行中的监测
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureStatus {
+    pub session_id: String,
+    pub process_name: String,
+    pub phase: CapturePhase,
+    pub current_fps: Option<f64>,
+}
+
+/// `fps-started` / `fps-stopped` 事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureEvent {
+    pub session_id: String,
+    pub process_name: String,
 }
 
 // ==================== 全局状态 ====================
 
+/// 正在运行的帧数据来源
+enum FrameSource {
+    PresentMon(Child),
+    Native(present_etw::PresentTrace),
+}
+
+impl FrameSource {
+    fn presentmon_pid(&self) -> Option<u32> {
+        match self {
+            FrameSource::PresentMon(child) => Some(child.id()),
+            FrameSource::Native(_) => None,
+        }
+    }
+
+    /// 结束 PresentMon 进程或停止 ETW 会话，读取线程随之收到输出结束
+    fn stop(self) {
+        match self {
+            FrameSource::PresentMon(mut child) => {
+                let _ = child.kill();
+            }
+            FrameSource::Native(trace) => trace.stop(),
+        }
+    }
+}
+
 struct MonitorState {
-    child: Option<Child>,
-    running: bool,
+    source: Option<FrameSource>,
+    phase: CapturePhase,
     process_name: String,
     frame_times: Vec<f64>,  // 最近的帧时间 (ms)
     start_time: Option<Instant>,
     all_frame_times: Vec<f64>,  // 本次 session 所有帧时间
+    session_id: String,
+    /// 本次监测开始时间 (unix 秒)
+    started_at: u64,
+    /// 本次监测开始时间 (unix 毫秒)，用于换算标记位置
+    started_at_ms: u64,
+    /// 本次监测中添加的时间标记
+    markers: Vec<SessionMarker>,
+    /// 系统睡眠中，采集已被结束
+    suspended: bool,
+    status: SessionStatus,
+    profile: CaptureProfile,
 }
 
-fn get_monitor() -> &'static Arc<Mutex<MonitorState>> {
-    static MONITOR: OnceLock<Arc<Mutex<MonitorState>>> = OnceLock::new();
-    MONITOR.get_or_init(|| {
-        Arc::new(Mutex::new(MonitorState {
-            child: None,
-            running: false,
-            process_name: String::new(),
+impl MonitorState {
+    fn new(process_name: &str, session_id: String, profile: CaptureProfile) -> Self {
+        MonitorState {
+            source: None,
+            phase: CapturePhase::Idle,
+            process_name: process_name.to_string(),
             frame_times: Vec::new(),
             start_time: None,
             all_frame_times: Vec::new(),
-        }))
-    })
+            session_id,
+            started_at: 0,
+            started_at_ms: 0,
+            markers: Vec::new(),
+            suspended: false,
+            status: SessionStatus::Completed,
+            profile,
+        }
+    }
+
+    /// 最近 60 帧的平均 FPS
+    fn recent_fps(&self) -> Option<f64> {
+        if self.all_frame_times.is_empty() {
+            return None;
+        }
+        let recent: Vec<&f64> = self.all_frame_times.iter().rev().take(60).collect();
+        let avg = recent.iter().copied().sum::<f64>() / recent.len() as f64;
+        Some((1000.0 / avg * 10.0).round() / 10.0)
+    }
+
+    fn capture_event(&self) -> CaptureEvent {
+        CaptureEvent {
+            session_id: self.session_id.clone(),
+            process_name: self.process_name.clone(),
+        }
+    }
+
+    /// 切换监测状态并推送 `fps-phase` 事件
+    fn set_phase(&mut self, app: &AppHandle, phase: CapturePhase, reason: impl Into<String>) {
+        let change = PhaseChange {
+            phase,
+            previous: self.phase,
+            reason: reason.into(),
+            process_name: self.process_name.clone(),
+            session_id: self.session_id.clone(),
+        };
+        log::info!(
+            "监测状态: {:?} → {:?} ({})",
+            change.previous,
+            change.phase,
+            change.reason
+        );
+        self.phase = phase;
+        let _ = app.emit("fps-phase", &change);
+    }
+}
+
+/// 所有进行中的监测与排队请求
+/// 加锁顺序：先 Monitors 再单个 MonitorState，读取线程持有自身状态锁时不访问 Monitors
+#[derive(Default)]
+struct Monitors {
+    /// 键为监测目标（见 `capture_key`），同一目标同时只有一个监测
+    captures: HashMap<String, Arc<Mutex<MonitorState>>>,
+    /// 目标正忙时排队的请求，该目标的监测结束后开始
+    queue: VecDeque<QueuedCapture>,
+    /// 各进程最近一次结束的监测: 小写进程名 → (会话 ID, 开始时间 unix 秒)
+    last_sessions: HashMap<String, (String, u64)>,
+}
+
+fn get_monitors() -> &'static Mutex<Monitors> {
+    static MONITORS: OnceLock<Mutex<Monitors>> = OnceLock::new();
+    MONITORS.get_or_init(|| Mutex::new(Monitors::default()))
+}
+
+/// 监测目标：指定 PID 时按 PID 区分（同一游戏多开），否则按进程名
+fn capture_key(process_name: &str, pid: Option<u32>) -> String {
+    match pid {
+        Some(pid) => format!("pid:{}", pid),
+        None => process_name.to_lowercase(),
+    }
+}
+
+fn active_captures(monitors: &Monitors) -> Vec<Arc<Mutex<MonitorState>>> {
+    monitors
+        .captures
+        .values()
+        .filter(|c| c.lock().unwrap().phase.is_active())
+        .cloned()
+        .collect()
 }
 
 // ==================== PresentMon 路径 ====================
 
+/// 读取 PresentMon 版本（`--version` 输出的第一行），结果缓存
+pub fn presentmon_version(app: &AppHandle) -> Option<String> {
+    static VERSION: OnceLock<Option<String>> = OnceLock::new();
+    VERSION
+        .get_or_init(|| {
+            let mut cmd = Command::new(get_presentmon_path(app).ok()?);
+            cmd.arg("--version").stdin(Stdio::null());
+            #[cfg(target_os = "windows")]
+            {
+                use std::os::windows::process::CommandExt;
+                cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+            }
+            let output = cmd.output().ok()?;
+            let text = String::from_utf8_lossy(&output.stdout);
+            text.lines()
+                .map(str::trim)
+                .find(|l| !l.is_empty())
+                .map(|l| l.to_string())
+        })
+        .clone()
+}
+
 /// 获取捆绑的 PresentMon.exe 路径
 fn get_presentmon_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
     // 开发模式：相对路径
@@ -100,6 +645,13 @@ fn get_presentmon_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
         return Ok(bundled);
     }
 
+    // 应用内下载
+    if let Some(downloaded) = crate::presentmon_download::downloaded_path(app) {
+        if downloaded.exists() {
+            return Ok(downloaded);
+        }
+    }
+
     // 系统 PATH
     if let Ok(output) = Command::new("where").arg("PresentMon.exe").output() {
         if output.status.success() {
@@ -116,249 +668,659 @@ fn get_presentmon_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
     }
 
     Err(
-        "未找到 PresentMon.exe。请从 https://github.com/GameTechDev/PresentMon/releases \
-         下载并放到 src-tauri/bin/ 目录"
+        "未找到 PresentMon.exe。请在设置中一键下载，或从 \
+         https://github.com/GameTechDev/PresentMon/releases 下载并放到 src-tauri/bin/ 目录"
             .to_string(),
     )
 }
 
 // ==================== 核心逻辑 ====================
 
-/// 从 PresentMon CSV 行中解析帧时间数据
-/// CSV 列 (v2): Application,ProcessID,SwapChainAddress,Runtime,SyncInterval,
-///              PresentFlags,AllowsTearing,PresentMode,CPUStartTime,CPUStartQPC,
-///              FrameTime,CPUBusy,CPUWait,GPULatency,GPUTime,GPUBusy,...
-fn parse_csv_line(header: &[String], line: &str) -> Option<(String, f64, f64, f64)> {
-    let fields: Vec<&str> = line.split(',').collect();
-    if fields.len() < 5 {
-        return None;
-    }
+/// 启动 PresentMon，stdout 输出 CSV
+fn spawn_presentmon(
+    pm_path: &std::path::Path,
+    pid: Option<u32>,
+    process_name: &str,
+    session_id: &str,
+) -> Result<Child, String> {
+    let mut cmd = Command::new(pm_path);
+    // 每个监测使用独立的 ETW 会话名，同时监测多个进程时互不结束对方的会话
+    let etw_session = format!("GameBench_{}", session_id);
+    cmd.args(["--session_name", &etw_session]);
+    cmd.args([
+        "--output_stdout",
+        "--stop_existing_session",
+        "--terminate_on_proc_exit",
+        // 输出 QPC 时间戳，作为与遥测采样对齐的时间线
+        "--qpc_time",
+    ]);
+    match pid {
+        Some(pid) => cmd.args(["--process_id", &pid.to_string()]),
+        None => cmd.args(["--process_name", process_name]),
+    };
+    cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
 
-    // 通过列名找索引
-    let app_idx = header.iter().position(|h| h == "Application")?;
-    let frametime_idx = header
-        .iter()
-        .position(|h| h == "FrameTime" || h == "MsBetweenPresents")?;
-    let cpu_idx = header.iter().position(|h| h == "CPUBusy").unwrap_or(0);
-    let gpu_idx = header
-        .iter()
-        .position(|h| h == "GPUBusy" || h == "GPUTime")
-        .unwrap_or(0);
-
-    let app = fields.get(app_idx)?.to_string();
-    let frametime: f64 = fields.get(frametime_idx)?.parse().ok()?;
-    let cpu_busy: f64 = fields
-        .get(cpu_idx)
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(0.0);
-    let gpu_busy: f64 = fields
-        .get(gpu_idx)
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(0.0);
-
-    if frametime > 0.0 && frametime < 1000.0 {
-        Some((app, frametime, cpu_busy, gpu_busy))
-    } else {
-        None
+    // Windows: 隐藏控制台窗口
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
     }
+
+    log::info!(
+        "启动 PresentMon: {:?} --process_name {} (pid: {:?})",
+        pm_path,
+        process_name,
+        pid
+    );
+
+    cmd.spawn()
+        .map_err(|e| format!("启动 PresentMon 失败: {}。请确保以管理员身份运行。", e))
+}
+
+/// 是否有按名称匹配的进程正在运行
+fn process_running(process_name: &str) -> bool {
+    let mut sys = sysinfo::System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    sys.processes()
+        .values()
+        .any(|p| p.name().to_string_lossy().eq_ignore_ascii_case(process_name))
 }
 
-/// 计算 percentile low FPS
-fn percentile_low_fps(frame_times: &[f64], percentile: f64) -> f64 {
-    if frame_times.is_empty() {
-        return 0.0;
+/// 启动帧数据来源，返回来源与逐行输出的 CSV
+fn start_source(
+    app: &AppHandle,
+    backend: CaptureBackend,
+    pid: Option<u32>,
+    process_name: &str,
+    session_id: &str,
+) -> Result<(FrameSource, Receiver<String>), String> {
+    match backend {
+        CaptureBackend::PresentMon => {
+            let pm_path = get_presentmon_path(app)?;
+            let mut child = spawn_presentmon(&pm_path, pid, process_name, session_id)?;
+            let Some(stdout) = child.stdout.take() else {
+                let _ = child.kill();
+                return Err("无法读取 PresentMon 输出".to_string());
+            };
+            Ok((FrameSource::PresentMon(child), spawn_line_reader(stdout)))
+        }
+        CaptureBackend::Native => {
+            let (trace, lines) = present_etw::start(session_id, pid, process_name)?;
+            Ok((FrameSource::Native(trace), lines))
+        }
     }
-    let mut sorted = frame_times.to_vec();
-    sorted.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+}
 
-    let count = ((percentile / 100.0) * sorted.len() as f64).ceil() as usize;
-    let count = count.max(1).min(sorted.len());
+/// 独立线程按行读取 PresentMon 输出：数据一到即转发，读取线程阻塞时主循环仍可按超时检查停止状态
+fn spawn_line_reader(stdout: ChildStdout) -> Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(stdout);
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            match reader.read_until(b'\n', &mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    if tx.send(String::from_utf8_lossy(&buf).into_owned()).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+    rx
+}
 
-    let worst_times = &sorted[..count];
-    let avg_worst = worst_times.iter().sum::<f64>() / worst_times.len() as f64;
+/// PresentMon 退出后判断是否继续监测：仅在系统睡眠且设置为唤醒后继续时，
+/// 等待唤醒并返回 true；设置为结束时将会话标记为被睡眠中断
+fn wait_for_resume(monitor: &Mutex<MonitorState>) -> bool {
+    {
+        let mut state = monitor.lock().unwrap();
+        if !state.phase.is_recording() || !state.suspended {
+            return false;
+        }
+        if crate::settings::get().sleep_behavior == SleepBehavior::Finalize {
+            state.status = SessionStatus::InterruptedBySleep;
+            return false;
+        }
+    }
+    loop {
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        let state = monitor.lock().unwrap();
+        if !state.phase.is_recording() {
+            return false;
+        }
+        if !state.suspended {
+            return true;
+        }
+    }
+}
 
-    if avg_worst > 0.0 {
-        1000.0 / avg_worst
-    } else {
-        0.0
+/// 系统即将睡眠：结束采集（睡眠期间 ETW 会话会失效），由读取线程决定继续或结束
+pub fn on_system_suspend() {
+    let monitors = get_monitors().lock().unwrap();
+    for capture in monitors.captures.values() {
+        let mut state = capture.lock().unwrap();
+        if !state.phase.is_recording() {
+            continue;
+        }
+        state.suspended = true;
+        if let Some(source) = state.source.take() {
+            source.stop();
+        }
+        log::info!("系统即将睡眠，已暂停监测 {}", state.process_name);
     }
 }
 
-/// FPS 实时推送线程
-fn fps_reader_thread(app: AppHandle, process_name: String) {
-    let monitor = get_monitor();
-
-    // 获取 PresentMon 路径
-    let pm_path = match get_presentmon_path(&app) {
-        Ok(p) => p,
-        Err(e) => {
-            log::error!("{}", e);
-            let _ = app.emit("fps-error", e);
-            return;
+pub fn on_system_resume() {
+    let monitors = get_monitors().lock().unwrap();
+    for capture in monitors.captures.values() {
+        let mut state = capture.lock().unwrap();
+        if state.suspended {
+            state.suspended = false;
+            log::info!("系统已唤醒");
         }
-    };
+    }
+}
 
-    // 启动 PresentMon
-    let mut cmd = Command::new(&pm_path);
-    cmd.args([
-        "--output_stdout",
-        "--stop_existing_session",
-        "--terminate_on_proc_exit",
-        "--process_name",
-        &process_name,
-    ])
-    .stdout(Stdio::piped())
-    .stderr(Stdio::piped());
+/// 帧处理过程中由界面层负责的部分：监测状态、检查点、采集日志与快照附加读数
+struct CaptureContext<'a> {
+    app: &'a AppHandle,
+    monitor: &'a Mutex<MonitorState>,
+    process_name: &'a str,
+    pid: Option<u32>,
+    launch_pending: bool,
+    hardware_state: bool,
+    sample_sensors: bool,
+    metadata: &'a mut SessionMetadata,
+    routing_sampler: &'a mut Option<gpu_routing::RoutingSampler>,
+    capture_log: &'a mut Option<(std::path::PathBuf, CaptureLogWriter)>,
+    aggregates: &'a mut TimelineRecorder,
+    checkpoint: &'a mut Option<session_recovery::Checkpoint>,
+    plugin_host: Option<&'a plugins::TelemetryHost>,
+}
 
-    // Windows: 隐藏控制台窗口
-    #[cfg(target_os = "windows")]
-    {
-        use std::os::windows::process::CommandExt;
-        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+impl CaptureHost for CaptureContext<'_> {
+    fn state(&self) -> LoopState {
+        match self.monitor.lock().unwrap().phase {
+            CapturePhase::Paused => LoopState::Paused,
+            phase if phase.is_recording() => LoopState::Recording,
+            _ => LoopState::Stopped,
+        }
     }
 
-    log::info!("启动 PresentMon: {:?} --process_name {}", pm_path, process_name);
+    fn on_presenting(&mut self) -> Option<u32> {
+        // 游戏在监测开始后才启动：此时读取进程信息，墙钟时长从第一帧算起
+        let launched = self.launch_pending && self.metadata.pid.is_none();
+        if launched {
+            *self.metadata = session_meta::capture(self.process_name, self.pid);
+            if self.hardware_state {
+                *self.routing_sampler =
+                    Some(gpu_routing::RoutingSampler::start(self.metadata.pid));
+            }
+            log::info!("{} 已启动并开始呈现画面", self.process_name);
+        }
+        let mut state = self.monitor.lock().unwrap();
+        if launched {
+            state.start_time = Some(Instant::now());
+        }
+        if state.phase == CapturePhase::WaitingForProcess {
+            state.set_phase(self.app, CapturePhase::Capturing, "游戏已开始呈现画面");
+        }
+        self.metadata.pid
+    }
 
-    let mut child = match cmd.spawn() {
-        Ok(c) => c,
-        Err(e) => {
-            let msg = format!("启动 PresentMon 失败: {}。请确保以管理员身份运行。", e);
-            log::error!("{}", msg);
-            let _ = app.emit("fps-error", msg);
-            return;
+    fn on_row(&mut self, timestamp_ms: Option<f64>, row: &FrameRow) {
+        if let Some((_, writer)) = self.capture_log.as_mut() {
+            writer.push(timestamp_ms, row);
+        }
+    }
+
+    fn on_counted_frame(&mut self, active_secs: f64, frametime_ms: f64) {
+        self.aggregates.push(active_secs, frametime_ms);
+        self.monitor.lock().unwrap().all_frame_times.push(frametime_ms);
+        if let Some(c) = self.checkpoint.as_mut() {
+            c.push(frametime_ms);
         }
+    }
+
+    fn on_stutter_hook(&mut self, stutter: &FpsStutter) {
+        script_hooks::fire(
+            HookEvent::StutterDetected,
+            StutterEvent {
+                process_name: stutter.process_name.clone(),
+                session_id: stutter.session_id.clone(),
+                at_secs: stutter.at_secs,
+                frametime_ms: stutter.frametime_ms,
+                baseline_ms: stutter.median_ms,
+            },
+        );
+    }
+
+    fn smoothing(&self) -> FpsSmoothing {
+        // 每次快照读取设置，切换平滑方式立即生效
+        crate::settings::get().fps_smoothing
+    }
+
+    fn decorate(&mut self, snapshot: &mut FpsSnapshot) {
+        let readings = if self.sample_sensors {
+            sensors::latest()
+        } else {
+            SensorReadings::default()
+        };
+        snapshot.adapters =
+            sensors::adapter_usage(&readings, self.metadata.pid, snapshot.gpu_busy_ms);
+        snapshot.sensors = readings;
+        snapshot.plugin_metrics = self.plugin_host.map(|h| h.latest()).unwrap_or_default();
+    }
+
+    fn on_benchmark_complete(&mut self) {
+        stop_state(self.app, &mut self.monitor.lock().unwrap(), "基准测试已完成");
+    }
+}
+
+/// FPS 实时推送线程
+/// 指定 pid 时按进程 ID 监测（UWP / Game Pass 游戏的进程名可能重复或被混淆）
+fn fps_reader_thread(
+    app: AppHandle,
+    monitor: &Mutex<MonitorState>,
+    process_name: String,
+    pid: Option<u32>,
+    profile: CaptureProfile,
+    options: MonitorOptions,
+) {
+    let session_id = monitor.lock().unwrap().session_id.clone();
+
+    // 社区/本地采集规则：修正监测进程、预热时长与交换链选择
+    let rules = capture_rules::load_rules(&app);
+    let rule = capture_rules::find_rule(&rules, &process_name).cloned();
+    let process_name = match rule.as_ref().and_then(|r| r.capture_process.clone()) {
+        Some(target) if pid.is_none() && !target.eq_ignore_ascii_case(&process_name) => {
+            log::info!("采集规则: 监测进程 {} → {}", process_name, target);
+            target
+        }
+        _ => process_name,
     };
+    // 误选启动器/反作弊引导进程时，改为监测实际游戏进程
+    let process_name = match game_alias::primary_for(&process_name) {
+        Some(primary) if pid.is_none() => {
+            log::info!("{} 是启动器进程，改为监测 {}", process_name, primary);
+            primary.to_string()
+        }
+        _ => process_name,
+    };
+    let warmup_secs = match options.benchmark.as_ref().and_then(|b| b.warmup_secs) {
+        Some(secs) => secs,
+        None => rule.as_ref().map(|r| r.warmup_secs).unwrap_or(0) as f64,
+    };
+    // 定时基准测试在有效时长达到 预热 + 采集时长 时结束
+    let benchmark_end = options.benchmark.as_ref().map(|b| warmup_secs + b.duration_secs);
+    let swapchain_select = rule.as_ref().map(|r| r.swapchain).unwrap_or_default();
 
-    let stdout = match child.stdout.take() {
-        Some(s) => s,
-        None => {
-            log::error!("无法获取 PresentMon stdout");
+    // 游戏尚未启动时 PresentMon 按进程名等待，目标进程开始呈现画面后自动开始采集
+    let launch_pending = pid.is_none() && !process_running(&process_name);
+    if launch_pending {
+        log::info!("{} 尚未运行，等待其启动", process_name);
+    }
+
+    // 启动 PresentMon 或内置 ETW 采集
+    let backend = options
+        .backend
+        .unwrap_or(crate::settings::get().capture_backend);
+    let (source, mut lines) = match start_source(&app, backend, pid, &process_name, &session_id) {
+        Ok(s) => s,
+        Err(msg) => {
+            log::error!("{}", msg);
+            let mut state = monitor.lock().unwrap();
+            state.set_phase(&app, CapturePhase::Failed, msg.clone());
+            let _ = app.emit("fps-error", msg);
             return;
         }
     };
+    let presentmon_pid = source.presentmon_pid();
 
-    // 保存子进程引用
+    // 保存采集来源
     {
         let mut state = monitor.lock().unwrap();
-        state.child = Some(child);
-        state.running = true;
+        // 启动采集期间已被要求停止
+        if state.phase == CapturePhase::Stopping {
+            source.stop();
+            state.set_phase(&app, CapturePhase::Done, "开始前已取消");
+            let _ = app.emit("fps-stopped", state.capture_event());
+            return;
+        }
+        state.source = Some(source);
         state.process_name = process_name.clone();
         state.start_time = Some(Instant::now());
         state.frame_times.clear();
         state.all_frame_times.clear();
+        state.started_at = chrono::Utc::now().timestamp().max(0) as u64;
+        state.started_at_ms = chrono::Utc::now().timestamp_millis().max(0) as u64;
+        state.markers.clear();
+        state.suspended = false;
+        state.status = SessionStatus::Completed;
+        let reason = if launch_pending {
+            format!("等待 {} 启动", process_name)
+        } else {
+            format!("{} 已启动，等待游戏呈现画面", backend.label())
+        };
+        state.set_phase(&app, CapturePhase::WaitingForProcess, reason);
+        let _ = app.emit("fps-started", state.capture_event());
     }
 
-    let _ = app.emit("fps-started", &process_name);
+    script_hooks::fire(
+        HookEvent::SessionStart,
+        serde_json::json!({
+            "process_name": process_name,
+            "session_id": session_id,
+            "capture_profile": profile,
+        }),
+    );
+
+    let mut metadata = session_meta::capture(&process_name, pid);
+    // 帧时间检查点：应用异常退出后下次启动可恢复本次会话
+    let mut checkpoint = {
+        let state = monitor.lock().unwrap();
+        session_recovery::Checkpoint::begin(
+            &app,
+            &session_recovery::CheckpointHeader {
+                session_id: session_id.clone(),
+                process_name: process_name.clone(),
+                capture_profile: profile,
+                started_at_ms: state.started_at_ms,
+                app_pid: std::process::id(),
+                presentmon_pid: presentmon_pid.unwrap_or(0),
+                metadata: metadata.clone(),
+            },
+        )
+    };
 
-    let reader = BufReader::new(stdout);
-    let mut header: Vec<String> = Vec::new();
-    let mut window: Vec<f64> = Vec::new(); // 1秒窗口
-    let mut window_start = Instant::now();
+    // 原始采集日志：不依赖内存中的帧时间，长时间会话也可完整复现
+    let mut capture_log = options
+        .capture_log
+        .then(|| {
+            let path = storage::capture_log_path(&app, &session_id)?;
+            CaptureLogWriter::create(&path).map(|writer| (path, writer))
+        })
+        .transpose()
+        .unwrap_or_else(|e| {
+            log::warn!("{}", e);
+            None
+        });
 
-    for line_result in reader.lines() {
-        // 检查是否已停止
-        {
-            let state = monitor.lock().unwrap();
-            if !state.running {
+    // 最低开销档位不启动任何后台采样
+    let telemetry = profile.samples_telemetry();
+    if !telemetry {
+        log::info!("采集档位: 最低开销，仅记录帧时间");
+    }
+    // 预设可只启动部分采样
+    let set = options.telemetry.clone().unwrap_or_default();
+    let hardware_state = telemetry && set.hardware_state;
+    let sample_sensors = telemetry && set.sensors;
+    // 后台采样 PCIe 链路状态
+    let pcie_sampler = hardware_state.then(pcie::LinkSampler::start);
+    // 后台读取显存纠错计数，发现"看似稳定、实则在纠错"的显存超频
+    let vram_sampler = hardware_state.then(VramSampler::start);
+    // 后台检测混合显卡输出路径
+    // 游戏尚未启动时没有 PID，等第一帧到达后再开始
+    let mut routing_sampler = (hardware_state && !launch_pending)
+        .then(|| gpu_routing::RoutingSampler::start(metadata.pid));
+    // 后台读取笔记本厂商性能模式，检测中途切换
+    let perf_mode_sampler = hardware_state.then(thermal::PerfModeSampler::start);
+    // 监测期间随快照附带系统传感器读数
+    let _sensor_subscription = sample_sensors.then(|| {
+        sensors::subscribe(
+            &[
+                SensorKind::Cpu,
+                SensorKind::CpuTemp,
+                SensorKind::Gpu,
+                SensorKind::Ram,
+            ],
+            std::time::Duration::from_secs(1),
+        )
+    });
+    // 已启用的第三方遥测插件
+    let plugin_host = (telemetry && set.plugins).then(|| plugins::TelemetryHost::start(&app));
+    let snapshot_interval = options
+        .snapshot_interval_secs
+        .unwrap_or_else(|| profile.snapshot_interval_secs());
+    // 自身开销（本进程 + PresentMon），最低开销档位下不查询 GPU 计数器
+    let overhead_sampler = overhead::OverheadSampler::start(presentmon_pid, telemetry);
+    // 显卡驱动超时恢复 (TDR)，最低开销档位下仅在结束时查询一次事件日志
+    let started_at_ms = monitor.lock().unwrap().started_at_ms;
+    let reset_watcher = ResetWatcher::start(&app, started_at_ms, telemetry);
+    // 可选的 DPC/ISR 延迟采样
+    // 内核 ETW 会话全局只有一个，同时监测多个进程时只在第一个监测中采样
+    let dpc_exclusive = get_monitors().lock().unwrap().captures.len() == 1;
+    let dpc_enabled = set.dpc_latency || crate::settings::get().dpc_sampling;
+    let dpc_sampler = (telemetry && dpc_exclusive && dpc_enabled).then(DpcSampler::start);
+    // 硬页错误与待机列表，判断卡顿是否由内存不足引起
+    let memory_sampler = (telemetry && set.memory_pressure).then(MemorySampler::start);
+    // Defender、搜索索引等周期性后台服务
+    let interference_sampler =
+        (telemetry && set.background_activity).then(InterferenceSampler::start);
+
+    // 每秒聚合数据，独立于会话摘要保存
+    let mut aggregates = TimelineRecorder::new(&session_id, sample_sensors);
+    let mut pipeline = FramePipeline::new(
+        PipelineConfig {
+            session_id: session_id.clone(),
+            process_name: process_name.clone(),
+            lock_dominant_swapchain: swapchain_select == SwapchainSelect::Dominant,
+            warmup_secs,
+            benchmark_end,
+            snapshot_interval,
+            stutter_hooks: script_hooks::has_hooks(HookEvent::StutterDetected),
+            frametime_percentiles: options.frametime_percentiles.clone(),
+            low_percentiles: options.low_percentiles.clone(),
+        },
+        crate::events::sink(&app),
+    );
+    let mut host = CaptureContext {
+        app: &app,
+        monitor,
+        process_name: &process_name,
+        pid,
+        launch_pending,
+        hardware_state,
+        sample_sensors,
+        metadata: &mut metadata,
+        routing_sampler: &mut routing_sampler,
+        capture_log: &mut capture_log,
+        aggregates: &mut aggregates,
+        checkpoint: &mut checkpoint,
+        plugin_host: plugin_host.as_ref(),
+    };
+
+    loop {
+        pipeline.run(&lines, &mut host);
+
+        // PresentMon 退出：用户停止、游戏退出，或系统睡眠时被结束
+        if !wait_for_resume(monitor) {
+            break;
+        }
+        match start_source(&app, backend, pid, &process_name, &session_id) {
+            Ok((source, next_lines)) => {
+                {
+                    let mut state = monitor.lock().unwrap();
+                    state.source = Some(source);
+                    // 睡眠前已手动暂停时保持暂停
+                    if state.phase.is_recording() && state.phase != CapturePhase::Paused {
+                        state.set_phase(
+                            &app,
+                            CapturePhase::WaitingForProcess,
+                            "系统已唤醒，等待游戏恢复呈现画面",
+                        );
+                    }
+                }
+                lines = next_lines;
+                pipeline.restart();
+                log::info!("系统已唤醒，继续监测 {}", process_name);
+            }
+            Err(e) => {
+                log::error!("唤醒后重新启动采集失败: {}", e);
+                monitor.lock().unwrap().status = SessionStatus::InterruptedBySleep;
                 break;
             }
         }
+    }
 
-        let line = match line_result {
-            Ok(l) => l,
-            Err(_) => continue,
+    // 监测结束，生成 session 报告
+    {
+        let mut state = monitor.lock().unwrap();
+        let reason = if state.phase == CapturePhase::Stopping {
+            "已停止采集，正在汇总"
+        } else if state.status == SessionStatus::InterruptedBySleep {
+            "系统睡眠导致采集结束，正在汇总"
+        } else {
+            "采集已结束（游戏退出），正在汇总"
         };
-
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-
-        // 第一行是 CSV header
-        if header.is_empty() {
-            header = trimmed.split(',').map(|s| s.trim().to_string()).collect();
-            log::info!("PresentMon CSV 列: {:?}", &header[..header.len().min(10)]);
-            continue;
+        state.source = None;
+        state.set_phase(&app, CapturePhase::Finalizing, reason);
+    }
+    let pcie_link = pcie_sampler.and_then(|s| s.finish());
+    if let Some(link) = &pcie_link {
+        for (_, warning) in &link.warnings {
+            log::warn!("PCIe: {}", warning);
         }
+    }
+    let vram = vram_sampler.and_then(|s| s.finish());
+    if let Some(report) = vram.as_ref().filter(|r| r.stable == Some(false)) {
+        log::warn!("显存: {}", report.note);
+    }
+    let gpu_routing = routing_sampler.and_then(|s| s.finish(metadata.pid));
+    if let Some(routing) = &gpu_routing {
+        log::info!(
+            "GPU 输出路径: {:?} (渲染: {:?}, 显示: {:?})",
+            routing.path,
+            routing.render_gpu,
+            routing.display_gpu
+        );
+    }
 
-        // 解析数据行
-        if let Some((_, frametime, cpu_busy, gpu_busy)) = parse_csv_line(&header, trimmed) {
-            window.push(frametime);
-
-            // 保存到全局状态
-            {
-                let mut state = monitor.lock().unwrap();
-                state.all_frame_times.push(frametime);
-            }
-
-            // 每秒推送一次快照
-            if window_start.elapsed().as_secs_f64() >= 1.0 {
-                if !window.is_empty() {
-                    let avg_frametime =
-                        window.iter().sum::<f64>() / window.len() as f64;
-                    let fps = 1000.0 / avg_frametime;
-                    let fps_1_low = percentile_low_fps(&window, 1.0);
-                    let fps_01_low = percentile_low_fps(&window, 0.1);
-
-                    let elapsed = {
-                        let state = monitor.lock().unwrap();
-                        state
-                            .start_time
-                            .map(|t| t.elapsed().as_secs_f64())
-                            .unwrap_or(0.0)
-                    };
-
-                    let snapshot = FpsSnapshot {
-                        fps: (fps * 10.0).round() / 10.0,
-                        fps_1_low: (fps_1_low * 10.0).round() / 10.0,
-                        fps_01_low: (fps_01_low * 10.0).round() / 10.0,
-                        frametime_ms: (avg_frametime * 100.0).round() / 100.0,
-                        cpu_busy_ms: (cpu_busy * 100.0).round() / 100.0,
-                        gpu_busy_ms: (gpu_busy * 100.0).round() / 100.0,
-                        process_name: process_name.clone(),
-                        elapsed_secs: (elapsed * 10.0).round() / 10.0,
-                    };
-
-                    let _ = app.emit("fps-update", &snapshot);
-                }
+    let totals = pipeline.finish();
 
-                window.clear();
-                window_start = Instant::now();
-            }
+    let thermal = perf_mode_sampler.and_then(|s| s.finish());
+    let plugin_metrics = plugin_host.map(|h| h.finish()).unwrap_or_default();
+    let overhead = overhead_sampler.finish();
+    let mut driver_resets = reset_watcher.finish();
+    let dpc_latency = dpc_sampler.map(|s| s.finish(&totals.stutter_times));
+    let memory_pressure = memory_sampler.and_then(|s| s.finish(&totals.stutter_times));
+    let background_activity = interference_sampler
+        .map(|s| s.finish())
+        .unwrap_or_default();
+    if let Some(dpc) = &dpc_latency {
+        for warning in &dpc.warnings {
+            log::warn!("DPC: {}", warning);
         }
     }
+    if let Some(o) = &overhead {
+        log::info!(
+            "GameBench 自身开销: CPU {:.1}% (峰值 {:.1}%) | 内存 {:.0} MB",
+            o.avg_cpu_percent,
+            o.peak_cpu_percent,
+            o.avg_ram_mb
+        );
+    }
+
+    let started_at = monitor.lock().unwrap().started_at;
+    get_monitors().lock().unwrap().last_sessions.insert(
+        process_name.to_lowercase(),
+        (session_id.clone(), started_at),
+    );
 
-    // 监测结束，生成 session 报告
     let session = {
         let mut state = monitor.lock().unwrap();
-        state.running = false;
 
+        let mut markers = std::mem::take(&mut state.markers);
+        markers.sort_by(|a, b| a.unix_ms.total_cmp(&b.unix_ms));
         let all = &state.all_frame_times;
+        driver_reset::locate_holes(&mut driver_resets, all, warmup_secs);
         if !all.is_empty() {
-            let avg_ft = all.iter().sum::<f64>() / all.len() as f64;
-            let min_ft = all.iter().cloned().fold(f64::INFINITY, f64::min);
-            let max_ft = all.iter().cloned().fold(0.0_f64, f64::max);
-            let duration = state
+            let summary = FpsSummary::from_frames(all);
+            let wall_duration = state
                 .start_time
                 .map(|t| t.elapsed().as_secs_f64())
                 .unwrap_or(0.0);
+            let duration = totals.active_secs;
+            let display = gpu_routing.as_ref().and_then(|r| r.display.as_ref());
+            let refresh_hz = display.and_then(|d| d.refresh_hz);
+            let refresh_range = display.and_then(|d| d.refresh_range);
+            let vrr = refresh_range.and_then(|range| {
+                vrr::analyze(all, range, refresh_hz, totals.present.as_ref())
+            });
+            let frame_cap = refresh_hz.and_then(|hz| frame_cap::advise(all, hz, refresh_range));
 
             Some(FpsSession {
+                session_id: state.session_id.clone(),
                 process_name: state.process_name.clone(),
-                avg_fps: (1000.0 / avg_ft * 10.0).round() / 10.0,
-                fps_1_low: (percentile_low_fps(all, 1.0) * 10.0).round() / 10.0,
-                fps_01_low: (percentile_low_fps(all, 0.1) * 10.0).round() / 10.0,
-                max_fps: (1000.0 / min_ft * 10.0).round() / 10.0,
-                min_fps: (1000.0 / max_ft * 10.0).round() / 10.0,
-                total_frames: all.len() as u64,
+                avg_fps: summary.avg_fps,
+                fps_1_low: summary.fps_1_low,
+                fps_01_low: summary.fps_01_low,
+                max_fps: summary.max_fps,
+                min_fps: summary.min_fps,
+                total_frames: summary.total_frames,
                 duration_secs: (duration * 10.0).round() / 10.0,
+                wall_duration_secs: (wall_duration * 10.0).round() / 10.0,
+                pauses: totals.pauses.clone(),
+                status: state.status,
+                pcie_link,
+                vram,
+                gpu_routing,
+                capture_rule: rule.as_ref().map(|r| r.id.clone()),
+                capture_profile: profile,
+                stream: streaming::find_client(&state.process_name)
+                    .and_then(|client| streaming::build_stream_stats(client, all)),
+                metadata,
+                thermal,
+                overhead,
+                pipeline: Some(signing::capture_pipeline(
+                    &app,
+                    backend,
+                    profile,
+                    rule.as_ref().map(|r| r.id.clone()),
+                    warmup_secs,
+                    swapchain_select,
+                )),
+                import: None,
+                suggestions: Vec::new(),
+                tags: Vec::new(),
+                merged_from: Vec::new(),
+                plugin_metrics,
+                started_at_ms: state.started_at_ms,
+                markers,
+                driver_resets,
+                dpc_latency,
+                memory_pressure,
+                background_activity,
+                launch: totals.launch.finish(state.started_at_ms),
+                stutter_count: totals.stutter_count,
+                percentiles: options.compute(all),
+                pacing: FramePacing::from_frames(all),
+                benchmark: options.benchmark.as_ref().map(|b| BenchmarkRun {
+                    warmup_secs,
+                    duration_secs: b.duration_secs,
+                    completed: totals.benchmark_completed,
+                    preset: options.preset.clone(),
+                }),
+                qoe: QoeScore::from_frames(all, totals.stutter_count, refresh_hz),
+                vrr,
+                frame_cap,
+                present: totals.present,
+                latency: totals.latency,
             })
         } else {
             None
         }
     };
 
-    if let Some(session) = session {
+    // 保存失败时保留检查点，下次启动恢复
+    let mut saved = true;
+    let captured = session.is_some();
+    if let Some(mut session) = session {
+        session.suggestions = suggestions::build_session_suggestions(&session);
         log::info!(
             "FPS Session 结束: {} | 平均 {:.1} FPS | 1% Low {:.1} | 时长 {:.0}s",
             session.process_name,
@@ -366,81 +1328,441 @@ fn fps_reader_thread(app: AppHandle, process_name: String) {
             session.fps_1_low,
             session.duration_secs
         );
+        if let Err(e) = storage::save_session(&app, &session) {
+            log::warn!("保存会话失败: {}", e);
+            saved = false;
+        }
+        if let Some(t) = aggregates.finish() {
+            if let Err(e) = storage::save_timeline(&app, &t) {
+                log::warn!("保存每秒聚合数据失败: {}", e);
+            }
+        }
+        let frames = std::mem::take(&mut monitor.lock().unwrap().all_frame_times);
+        if let Err(e) = storage::save_frames(&app, &session.session_id, &frames) {
+            log::warn!("保存原始帧时间失败: {}", e);
+        }
+        plugins::run_exporters(&app, &session);
+        script_hooks::fire(HookEvent::SessionComplete, &session);
         let _ = app.emit("fps-session-complete", &session);
+        if session.benchmark.is_some() {
+            let _ = app.emit("benchmark-complete", &session);
+        }
+    }
+
+    if let Some(c) = checkpoint.filter(|_| saved) {
+        c.finish();
+    }
+    if let Some((path, writer)) = capture_log {
+        match writer.finish() {
+            Ok(rows) if captured => log::info!("原始采集日志已保存: {} 帧", rows),
+            Ok(_) => {
+                let _ = std::fs::remove_file(&path);
+            }
+            Err(e) => log::warn!("{}", e),
+        }
+    }
+    let mut state = monitor.lock().unwrap();
+    if captured {
+        state.set_phase(&app, CapturePhase::Done, "会话已生成");
+    } else {
+        state.set_phase(&app, CapturePhase::Failed, "未采集到游戏帧");
     }
+    let _ = app.emit("fps-stopped", state.capture_event());
+}
 
-    let _ = app.emit("fps-stopped", &process_name);
+/// 会话 ID: 开始时间 + 进程名（去掉 .exe，仅保留字母数字）
+pub(crate) fn new_session_id(process_name: &str) -> String {
+    let stem = process_name.to_lowercase();
+    let stem = stem.strip_suffix(".exe").unwrap_or(&stem);
+    let stem: String = stem
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    format!("{}-{}", chrono::Local::now().format("%Y%m%d-%H%M%S"), stem)
 }
 
-// ==================== Tauri 命令 ====================
+/// 最近开始的进行中监测: (会话 ID, 开始时间 unix 毫秒)
+pub fn current_session() -> Option<(String, u64)> {
+    active_sessions()
+        .into_iter()
+        .max_by_key(|(_, started_ms)| *started_ms)
+}
 
-/// 开始 FPS 监测
-#[tauri::command]
-pub fn start_fps_monitor(app: AppHandle, process_name: String) -> Result<(), String> {
-    let monitor = get_monitor();
+/// 所有进行中的监测: (会话 ID, 开始时间 unix 毫秒)
+pub fn active_sessions() -> Vec<(String, u64)> {
+    let monitors = get_monitors().lock().unwrap();
+    active_captures(&monitors)
+        .iter()
+        .map(|c| {
+            let state = c.lock().unwrap();
+            (state.session_id.clone(), state.started_at_ms)
+        })
+        .collect()
+}
+
+/// 是否有以最低开销档位进行中的监测
+pub fn minimal_active() -> bool {
+    let monitors = get_monitors().lock().unwrap();
+    active_captures(&monitors)
+        .iter()
+        .any(|c| c.lock().unwrap().profile == CaptureProfile::Minimal)
+}
+
+/// 向进行中的监测添加时间标记
+pub fn push_marker(session_id: &str, marker: SessionMarker) -> Result<(), String> {
+    let monitors = get_monitors().lock().unwrap();
+    let capture = monitors
+        .captures
+        .values()
+        .find(|c| c.lock().unwrap().session_id == session_id)
+        .ok_or_else(|| "当前没有进行中的监测".to_string())?;
+    let mut state = capture.lock().unwrap();
+    if !state.phase.is_recording() {
+        return Err("当前没有进行中的监测".to_string());
+    }
+    state.markers.push(marker);
+    Ok(())
+}
+
+/// 查找进程在 `since` (unix 秒) 之后进行中或已结束的监测会话 ID
+pub fn session_for_process(process_name: &str, since: u64) -> Option<String> {
+    let monitors = get_monitors().lock().unwrap();
+    let running = active_captures(&monitors).iter().find_map(|c| {
+        let state = c.lock().unwrap();
+        state
+            .process_name
+            .eq_ignore_ascii_case(process_name)
+            .then(|| state.session_id.clone())
+    });
+    running.or_else(|| {
+        monitors
+            .last_sessions
+            .get(&process_name.to_lowercase())
+            .filter(|(_, started)| *started >= since)
+            .map(|(id, _)| id.clone())
+    })
+}
+
+/// 进程是否已有进行中的监测（按进程名匹配，不区分 PID）
+pub fn is_monitoring(process_name: &str) -> bool {
+    let monitors = get_monitors().lock().unwrap();
+    active_captures(&monitors)
+        .iter()
+        .any(|c| c.lock().unwrap().process_name.eq_ignore_ascii_case(process_name))
+}
+
+/// 登记新的监测并进入 Starting 状态（调用方持有锁，已确认该目标空闲）
+fn begin_starting(
+    monitors: &mut Monitors,
+    app: &AppHandle,
+    request: &QueuedCapture,
+) -> Arc<Mutex<MonitorState>> {
+    let mut session_id = new_session_id(&request.process_name);
+    // 同一秒内开始同名进程的多个实例
+    if monitors
+        .captures
+        .values()
+        .any(|c| c.lock().unwrap().session_id == session_id)
     {
-        let state = monitor.lock().unwrap();
-        if state.running {
-            return Err(format!(
-                "已经在监测 {} 的帧率",
-                state.process_name
-            ));
-        }
+        session_id = format!("{}-{}", session_id, monitors.captures.len() + 1);
     }
 
-    log::info!("开始监测: {}", process_name);
+    let capture = Arc::new(Mutex::new(MonitorState::new(
+        &request.process_name,
+        session_id,
+        request.profile,
+    )));
+    capture
+        .lock()
+        .unwrap()
+        .set_phase(app, CapturePhase::Starting, "已接受监测请求");
+    monitors.captures.insert(
+        capture_key(&request.process_name, request.pid),
+        capture.clone(),
+    );
+    capture
+}
+
+/// 目标空闲的排队请求（每个目标取最早的一个）
+fn take_ready_requests(monitors: &mut Monitors) -> Vec<QueuedCapture> {
+    let mut ready: Vec<QueuedCapture> = Vec::new();
+    let mut waiting = VecDeque::new();
+    for request in std::mem::take(&mut monitors.queue) {
+        let key = capture_key(&request.process_name, request.pid);
+        let busy = monitors.captures.contains_key(&key)
+            || ready
+                .iter()
+                .any(|r| capture_key(&r.process_name, r.pid) == key);
+        if busy {
+            waiting.push_back(request);
+        } else {
+            ready.push(request);
+        }
+    }
+    monitors.queue = waiting;
+    ready
+}
 
-    let app_clone = app.clone();
-    let name_clone = process_name.clone();
+/// 在后台线程中开始监测，结束后开始等待该目标的排队请求
+/// `capture` 需已通过 `begin_starting` 登记
+fn spawn_capture(app: AppHandle, capture: Arc<Mutex<MonitorState>>, request: QueuedCapture) {
+    log::info!("开始监测: {} ({:?})", request.process_name, request.profile);
+    overlay::on_capture_start(&app, request.profile);
     std::thread::spawn(move || {
-        fps_reader_thread(app_clone, name_clone);
+        fps_reader_thread(
+            app.clone(),
+            &capture,
+            request.process_name.clone(),
+            request.pid,
+            request.profile,
+            request.options.clone(),
+        );
+
+        let next: Vec<_> = {
+            let mut monitors = get_monitors().lock().unwrap();
+            let key = capture_key(&request.process_name, request.pid);
+            // 替换策略下同一目标可能已登记了新的监测
+            if monitors
+                .captures
+                .get(&key)
+                .is_some_and(|c| Arc::ptr_eq(c, &capture))
+            {
+                monitors.captures.remove(&key);
+            }
+            take_ready_requests(&mut monitors)
+                .into_iter()
+                .map(|next| (begin_starting(&mut monitors, &app, &next), next))
+                .collect()
+        };
+        for (capture, next) in next {
+            log::info!("开始排队中的监测: {}", next.process_name);
+            spawn_capture(app.clone(), capture, next);
+        }
     });
+}
+
+/// 请求停止监测，`reason` 随状态切换事件推送
+fn stop_state(app: &AppHandle, state: &mut MonitorState, reason: &str) {
+    if matches!(
+        state.phase,
+        CapturePhase::Starting
+            | CapturePhase::WaitingForProcess
+            | CapturePhase::Capturing
+            | CapturePhase::Paused
+    ) {
+        state.set_phase(app, CapturePhase::Stopping, reason);
+    }
+
+    if let Some(source) = state.source.take() {
+        source.stop();
+        log::info!("已停止采集 ({})", state.process_name);
+    }
+}
 
+/// 请求停止所有进行中的监测
+pub fn stop_capture(app: &AppHandle, reason: &str) {
+    let monitors = get_monitors().lock().unwrap();
+    for capture in monitors.captures.values() {
+        stop_state(app, &mut capture.lock().unwrap(), reason);
+    }
+}
+
+/// 请求停止指定会话的监测
+pub fn stop_session(app: &AppHandle, session_id: &str, reason: &str) -> Result<(), String> {
+    let monitors = get_monitors().lock().unwrap();
+    let capture = monitors
+        .captures
+        .values()
+        .find(|c| c.lock().unwrap().session_id == session_id)
+        .ok_or_else(|| format!("未找到进行中的监测 {}", session_id))?;
+    stop_state(app, &mut capture.lock().unwrap(), reason);
     Ok(())
 }
 
+// ==================== Tauri 命令 ====================
+
+/// 开始 FPS 监测
+/// `pid` 可选，UWP / Game Pass 游戏建议传入
+/// `profile` 未传入时使用设置中的默认采集档位
+/// 不同进程可同时监测；`policy` 决定同一目标已在监测时的处理方式，未传入时使用设置中的默认值
+/// `options` 指定额外计算的帧时间百分位、自定义 Low FPS 与是否保存原始采集日志
+#[tauri::command]
+pub fn start_fps_monitor(
+    app: AppHandle,
+    process_name: String,
+    pid: Option<u32>,
+    profile: Option<CaptureProfile>,
+    policy: Option<BusyPolicy>,
+    options: Option<MonitorOptions>,
+) -> Result<StartOutcome, String> {
+    let options = options.unwrap_or_default();
+    options.validate()?;
+    if options.capture_log {
+        storage::check_capture_log(&app)?;
+    }
+    // 上次异常退出遗留的会话先恢复，并结束遗留的 PresentMon
+    session_recovery::recover(&app);
+
+    let settings = crate::settings::get();
+    let request = QueuedCapture {
+        process_name,
+        pid,
+        profile: profile.unwrap_or(settings.capture_profile),
+        options,
+    };
+    let policy = policy.unwrap_or(settings.busy_policy);
+
+    let mut monitors = get_monitors().lock().unwrap();
+    let key = capture_key(&request.process_name, request.pid);
+    if let Some(current) = monitors.captures.get(&key).cloned() {
+        let mut state = current.lock().unwrap();
+        let busy_with = state.process_name.clone();
+        return match policy {
+            BusyPolicy::Reject => Err(format!("已经在监测 {} 的帧率", busy_with)),
+            BusyPolicy::Queue => {
+                log::info!("{} 已排队，等待当前监测结束", request.process_name);
+                drop(state);
+                monitors.queue.push_back(request);
+                Ok(StartOutcome::Queued {
+                    position: monitors.queue.len(),
+                    busy_with,
+                })
+            }
+            BusyPolicy::Replace => {
+                log::info!("停止并重新开始监测 {}", busy_with);
+                stop_state(&app, &mut state, "重新开始监测");
+                drop(state);
+                monitors.queue.push_front(request);
+                Ok(StartOutcome::Replaced { stopped: busy_with })
+            }
+        };
+    }
+    let capture = begin_starting(&mut monitors, &app, &request);
+    let session_id = capture.lock().unwrap().session_id.clone();
+    drop(monitors);
+
+    spawn_capture(app, capture, request);
+    Ok(StartOutcome::Started { session_id })
+}
+
+/// 定时基准测试：开始监测，按 `plan` 预热后采集指定时长，随后自动停止
+/// `preset` 指定按游戏类型调校的预设（时长、预热、快照间隔、后台采样），同时传入 `plan` 时以其时长为准
+/// 期间推送 `benchmark-countdown`，结束后推送 `benchmark-complete`（附带会话）
+#[tauri::command]
+pub fn run_benchmark(
+    app: AppHandle,
+    process_name: String,
+    pid: Option<u32>,
+    profile: Option<CaptureProfile>,
+    preset: Option<String>,
+    plan: Option<BenchmarkPlan>,
+    options: Option<MonitorOptions>,
+) -> Result<StartOutcome, String> {
+    let mut options = options.unwrap_or_default();
+    match preset {
+        Some(id) => presets::find(&id)?.apply(&mut options, plan),
+        None => {
+            options.benchmark =
+                Some(plan.ok_or_else(|| "请指定测试时长或选择预设".to_string())?);
+        }
+    }
+    start_fps_monitor(app, process_name, pid, profile, None, Some(options))
+}
+
 /// 停止 FPS 监测
+/// `session_id` 未传入时停止所有进行中的监测
 #[tauri::command]
-pub fn stop_fps_monitor() -> Result<(), String> {
-    let monitor = get_monitor();
-    let mut state = monitor.lock().unwrap();
-    state.running = false;
+pub fn stop_fps_monitor(app: AppHandle, session_id: Option<String>) -> Result<(), String> {
+    match session_id {
+        Some(session_id) => stop_session(&app, &session_id, "用户停止"),
+        None => {
+            stop_capture(&app, "用户停止");
+            Ok(())
+        }
+    }
+}
 
-    if let Some(ref mut child) = state.child {
-        let _ = child.kill();
-        log::info!("已停止 PresentMon 进程");
+/// 暂停或继续指定监测，`session_id` 为 None 时作用于所有进行中的监测
+fn set_paused(app: &AppHandle, session_id: Option<&str>, paused: bool) -> Result<(), String> {
+    let (from, to, reason) = if paused {
+        (CapturePhase::Capturing, CapturePhase::Paused, "用户暂停")
+    } else {
+        (CapturePhase::Paused, CapturePhase::Capturing, "用户继续")
+    };
+    let monitors = get_monitors().lock().unwrap();
+    let mut changed = 0;
+    for capture in monitors.captures.values() {
+        let mut state = capture.lock().unwrap();
+        if session_id.is_some_and(|id| id != state.session_id) || state.phase != from {
+            continue;
+        }
+        state.set_phase(app, to, reason);
+        changed += 1;
+    }
+
+    match (changed, session_id) {
+        (0, Some(id)) if paused => Err(format!("监测 {} 未在采集中，无法暂停", id)),
+        (0, Some(id)) => Err(format!("监测 {} 未暂停", id)),
+        (0, None) if paused => Err("没有正在采集的监测".to_string()),
+        (0, None) => Err("没有已暂停的监测".to_string()),
+        _ => Ok(()),
     }
-    state.child = None;
+}
+
+/// 暂停 FPS 监测：PresentMon 保持运行，暂停期间的帧不计入会话统计与有效时长
+/// `session_id` 未传入时暂停所有正在采集的监测
+#[tauri::command]
+pub fn pause_fps_monitor(app: AppHandle, session_id: Option<String>) -> Result<(), String> {
+    set_paused(&app, session_id.as_deref(), true)
+}
+
+/// 继续已暂停的 FPS 监测
+#[tauri::command]
+pub fn resume_fps_monitor(app: AppHandle, session_id: Option<String>) -> Result<(), String> {
+    set_paused(&app, session_id.as_deref(), false)
+}
+
+/// 排队中的监测请求
+#[tauri::command]
+pub fn get_fps_queue() -> Result<Vec<QueuedCapture>, String> {
+    Ok(get_monitors().lock().unwrap().queue.iter().cloned().collect())
+}
 
+/// 清空排队中的监测请求
+#[tauri::command]
+pub fn clear_fps_queue() -> Result<(), String> {
+    get_monitors().lock().unwrap().queue.clear();
     Ok(())
 }
 
 /// 获取当前监测状态
 #[tauri::command]
 pub fn get_fps_status() -> Result<FpsStatus, String> {
-    let monitor = get_monitor();
-    let state = monitor.lock().unwrap();
-
-    let current_fps = if !state.all_frame_times.is_empty() {
-        let recent: Vec<&f64> = state
-            .all_frame_times
-            .iter()
-            .rev()
-            .take(60) // 最近60帧
-            .collect();
-        let avg = recent.iter().copied().sum::<f64>() / recent.len() as f64;
-        Some((1000.0 / avg * 10.0).round() / 10.0)
-    } else {
-        None
-    };
+    let monitors = get_monitors().lock().unwrap();
+    let mut captures: Vec<(u64, CaptureStatus)> = active_captures(&monitors)
+        .iter()
+        .map(|c| {
+            let state = c.lock().unwrap();
+            (
+                state.started_at_ms,
+                CaptureStatus {
+                    session_id: state.session_id.clone(),
+                    process_name: state.process_name.clone(),
+                    phase: state.phase,
+                    current_fps: state.recent_fps(),
+                },
+            )
+        })
+        .collect();
+    captures.sort_by_key(|(started_ms, _)| *started_ms);
+    let captures: Vec<CaptureStatus> = captures.into_iter().map(|(_, c)| c).collect();
 
+    let latest = captures.last();
     Ok(FpsStatus {
-        running: state.running,
-        process_name: if state.running {
-            Some(state.process_name.clone())
-        } else {
-            None
-        },
-        current_fps,
+        running: latest.is_some(),
+        phase: latest.map(|c| c.phase).unwrap_or_default(),
+        process_name: latest.map(|c| c.process_name.clone()),
+        current_fps: latest.and_then(|c| c.current_fps),
+        captures,
     })
 }
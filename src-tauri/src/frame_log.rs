@@ -0,0 +1,18 @@
+use crate::storage;
+pub use gamebench_core::frame_log::{encode, FrameLog, FrameLogWriter};
+use tauri::AppHandle;
+
+// ==================== Tauri 命令 ====================
+
+/// 读取会话保存的原始帧时间 (ms)
+/// `start`/`count` 可只读取一段，未传入时返回全部
+#[tauri::command]
+pub fn get_session_frames(
+    app: AppHandle,
+    session_id: String,
+    start: Option<usize>,
+    count: Option<usize>,
+) -> Result<Vec<f64>, String> {
+    let log = storage::load_frames(&app, &session_id)?;
+    log.read_range(start.unwrap_or(0), count.unwrap_or(usize::MAX))
+}
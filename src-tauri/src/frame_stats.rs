@@ -0,0 +1,23 @@
+use crate::storage;
+pub use gamebench_core::frame_stats::{FrameStats, FrameStatsReport};
+use tauri::AppHandle;
+
+// ==================== Tauri 命令 ====================
+
+/// 按块流式统计会话保存的原始帧时间，超长会话也不会一次载入全部帧
+/// `start`/`count` 可只统计一段（帧序号），未传入时统计全部
+#[tauri::command]
+pub fn analyze_session_frames(
+    app: AppHandle,
+    session_id: String,
+    start: Option<usize>,
+    count: Option<usize>,
+) -> Result<FrameStatsReport, String> {
+    let log = storage::load_frames(&app, &session_id)?;
+    let mut stats = FrameStats::default();
+    log.for_each_chunk(start.unwrap_or(0), count.unwrap_or(usize::MAX), |frames| {
+        stats.extend(frames);
+        Ok(())
+    })?;
+    Ok(stats.report())
+}
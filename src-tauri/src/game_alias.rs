@@ -0,0 +1,122 @@
+use crate::game_detect::DetectedGame;
+use crate::game_window;
+
+// ==================== 多进程游戏 ====================
+
+/// 启动器/反作弊引导进程(小写) → 实际渲染画面的游戏进程
+const ALIASES: &[(&str, &str)] = &[
+    ("playgtav.exe", "gta5.exe"),
+    ("gtavlauncher.exe", "gta5.exe"),
+    ("valorant.exe", "valorant-win64-shipping.exe"),
+    ("fortnitelauncher.exe", "fortniteclient-win64-shipping.exe"),
+    (
+        "fortniteclient-win64-shipping_eac.exe",
+        "fortniteclient-win64-shipping.exe",
+    ),
+    (
+        "fortniteclient-win64-shipping_be.exe",
+        "fortniteclient-win64-shipping.exe",
+    ),
+    ("leagueclient.exe", "league of legends.exe"),
+    ("tslgame_be.exe", "tslgame.exe"),
+    ("redprelauncher.exe", "cyberpunk2077.exe"),
+    ("b1.exe", "b1-wukong-win64-shipping.exe"),
+    ("bg3_launcher.exe", "bg3.exe"),
+];
+
+/// 多款游戏共用的通用引导进程名，需要按安装目录找到实际游戏进程
+const LAUNCHER_HINTS: &[&str] = &[
+    "start_protected_game",
+    "easyanticheat",
+    "launcher",
+    "crashreport",
+    "crashhandler",
+    "_be.exe",
+    "_eac.exe",
+];
+
+/// 引导进程对应的实际游戏进程名
+pub fn primary_for(exe_name: &str) -> Option<&'static str> {
+    let lower = exe_name.to_lowercase();
+    ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == lower)
+        .map(|(_, primary)| *primary)
+}
+
+/// 进程名是否像启动器/反作弊引导/崩溃报告进程
+pub fn looks_like_launcher(exe_name: &str) -> bool {
+    let lower = exe_name.to_lowercase();
+    LAUNCHER_HINTS.iter().any(|hint| lower.contains(hint))
+}
+
+/// 评估进程是否为渲染画面的进程: 非引导进程 + 有可见窗口
+fn presenting_score(game: &DetectedGame) -> i32 {
+    let mut score = 0;
+    if looks_like_launcher(&game.process_name) {
+        score -= 2;
+    }
+    if game_window::main_window_title(game.pid).is_some() {
+        score += 1;
+    }
+    score
+}
+
+// ==================== 合并 ====================
+
+/// 将同一游戏的多个进程合并为一个条目，并选择实际渲染画面的进程用于监测
+pub fn merge_aliases(games: Vec<DetectedGame>) -> Vec<DetectedGame> {
+    // 1. 已知引导进程 → 实际游戏进程（游戏进程尚未启动时，PresentMon 会等待该进程名出现）
+    let mut games: Vec<DetectedGame> = games
+        .into_iter()
+        .map(|mut game| {
+            if let Some(primary) = primary_for(&game.process_name) {
+                game.related_processes.push(game.process_name.clone());
+                game.process_name = primary.to_string();
+            }
+            game
+        })
+        .collect();
+
+    // 2. 同名游戏的不同进程 → 保留渲染进程
+    let mut merged: Vec<DetectedGame> = Vec::new();
+    while let Some(first) = games.first() {
+        let key = first
+            .game_name
+            .clone()
+            .unwrap_or_else(|| first.process_name.clone())
+            .to_lowercase();
+        let (group, rest): (Vec<_>, Vec<_>) = games.into_iter().partition(|g| {
+            g.game_name
+                .clone()
+                .unwrap_or_else(|| g.process_name.clone())
+                .to_lowercase()
+                == key
+        });
+        games = rest;
+
+        let presenting = group
+            .iter()
+            .max_by_key(|g| presenting_score(g))
+            .map(|g| g.process_name.to_lowercase())
+            .unwrap_or_default();
+        let mut related: Vec<String> = group
+            .iter()
+            .flat_map(|g| {
+                std::iter::once(g.process_name.clone()).chain(g.related_processes.clone())
+            })
+            .filter(|name| name.to_lowercase() != presenting)
+            .collect();
+        related.sort();
+        related.dedup();
+
+        for mut game in group
+            .into_iter()
+            .filter(|g| g.process_name.to_lowercase() == presenting)
+        {
+            game.related_processes = related.clone();
+            merged.push(game);
+        }
+    }
+    merged
+}
@@ -1,3 +1,12 @@
+use crate::emulators;
+use crate::fps_monitor::{self, BusyPolicy, StartOutcome};
+use crate::game_alias;
+use crate::game_window;
+use crate::launchers::{self, Launcher};
+use crate::process_activity::{self, ActivityState};
+use crate::process_filter;
+use crate::streaming;
+use crate::uwp;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use sysinfo::System;
@@ -5,112 +14,235 @@ use tauri::{AppHandle, Emitter};
 
 // ==================== 数据结构 ====================
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DetectedGame {
     /// 进程名 (e.g., "GTA5.exe")
     pub process_name: String,
     /// 进程 ID
     pub pid: u32,
-    /// 匹配到的游戏名称（如果在已知列表中）
+    /// 匹配到的游戏名称（如果在已知列表中），按界面语言选择中文或英文名
     pub game_name: Option<String>,
+    /// 英文名
+    pub game_name_en: Option<String>,
+    /// 中文官方名（国内搜索常用）
+    pub game_name_zh: Option<String>,
     /// 对应的 Steam AppId（如果匹配到）
     pub app_id: Option<u32>,
+    /// UWP / Game Pass 应用包全名（进程名常被混淆，需按 PID 监测）
+    pub package_name: Option<String>,
+    /// 模拟器名称与平台 (e.g., "RPCS3 (PS3)")
+    pub emulator: Option<String>,
+    /// 云游戏 / 串流客户端名称，此时测得的是解码画面的显示帧率
+    pub streaming_client: Option<String>,
+    /// 国内游戏平台（WeGame / 米哈游 / 网易）
+    pub launcher: Option<Launcher>,
+    /// 同一游戏的其他进程（启动器、反作弊引导等），不用于监测
+    pub related_processes: Vec<String>,
+    /// 游戏主窗口标题 (e.g., "Counter-Strike 2 — de_mirage")
+    pub window_title: Option<String>,
+    /// 游戏主窗口句柄
+    pub window_handle: Option<u64>,
+    /// 进程活跃状态（挂起/空闲的进程不触发自动监测）
+    pub activity: ActivityState,
+    /// 匹配依据
+    pub match_reason: MatchReason,
+    /// 匹配可信度
+    pub confidence: Confidence,
+    /// 进程启动时间 (unix 秒)
+    pub start_time: u64,
+    /// 同一游戏多开时的实例序号（按启动时间，从 1 开始）
+    pub instance: u32,
 }
 
+/// `game-exited` 事件数据
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct KnownGame {
-    name: String,
-    app_id: u32,
-    process_names: Vec<String>,
+pub struct GameExited {
+    pub process_name: String,
+    pub game_name: Option<String>,
+    pub pid: u32,
+    /// 游戏运行时长 (秒)
+    pub run_secs: u64,
+    /// 本次运行期间是否进行了 FPS 监测
+    pub capture_active: bool,
+    /// 对应的监测会话 ID
+    pub session_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchReason {
+    /// 已知游戏/平台/模拟器/串流客户端列表
+    #[default]
+    KnownList,
+    /// 应用包声明为游戏（MicrosoftGame.config）
+    Package,
+    /// 可执行文件位于 steamapps/common
+    SteamPath,
+    /// 推测（如从模拟器窗口标题解析的游戏名）
+    Heuristic,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Confidence {
+    Low,
+    Medium,
+    #[default]
+    High,
+}
+
+/// 游戏显示名称的语言
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameNameLanguage {
+    /// 优先显示中文官方名
+    #[default]
+    Zh,
+    En,
+}
+
+impl GameNameLanguage {
+    /// 中文语言区域（zh-CN / zh-TW 等）显示中文名，其余显示英文名
+    pub fn for_locale(locale: &str) -> Self {
+        if locale.to_lowercase().starts_with("zh") {
+            GameNameLanguage::Zh
+        } else {
+            GameNameLanguage::En
+        }
+    }
+
+    /// 选择显示名称，缺少中文名时回退到英文名
+    pub fn pick(self, name_en: &str, name_zh: Option<&str>) -> String {
+        match (self, name_zh) {
+            (GameNameLanguage::Zh, Some(zh)) => zh.to_string(),
+            _ => name_en.to_string(),
+        }
+    }
+}
+
+/// `get_known_games` 返回的条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnownGameName {
+    /// 按界面语言选择的显示名称
+    pub name: String,
+    pub name_en: String,
+    pub name_zh: Option<String>,
+    pub process_name: String,
 }
 
 // ==================== 已知游戏列表 ====================
 
+/// 已知游戏条目
+struct KnownGame {
+    name_en: String,
+    name_zh: Option<String>,
+    app_id: u32,
+}
+
 /// 热门游戏进程名 → 游戏信息的映射
 /// 后续可从服务端动态更新
-fn build_known_games() -> HashMap<String, (String, u32)> {
+fn build_known_games() -> HashMap<String, KnownGame> {
     let games = vec![
-        // 进程名(小写), 游戏名, Steam AppId
-        ("gta5.exe", "Grand Theft Auto V", 271590),
-        ("gtav.exe", "Grand Theft Auto V", 271590),
-        ("eldenring.exe", "Elden Ring", 1245620),
-        ("cyberpunk2077.exe", "Cyberpunk 2077", 1091500),
-        ("witcher3.exe", "The Witcher 3", 292030),
-        ("rdr2.exe", "Red Dead Redemption 2", 1174180),
-        ("cs2.exe", "Counter-Strike 2", 730),
-        ("csgo.exe", "Counter-Strike 2", 730),
-        ("dota2.exe", "Dota 2", 570),
-        ("valorant.exe", "VALORANT", 0),
-        ("overwatch.exe", "Overwatch 2", 0),
-        ("leagueclient.exe", "League of Legends", 0),
-        ("league of legends.exe", "League of Legends", 0),
-        ("pubg.exe", "PUBG: Battlegrounds", 578080),
-        ("tslgame.exe", "PUBG: Battlegrounds", 578080),
-        ("fortnite.exe", "Fortnite", 0),
-        ("apex_r5apex.exe", "Apex Legends", 1172470),
-        ("r5apex.exe", "Apex Legends", 1172470),
-        ("terraria.exe", "Terraria", 105600),
-        ("rust.exe", "Rust", 252490),
-        ("baldursgate3.exe", "Baldur's Gate 3", 1086940),
-        ("bg3.exe", "Baldur's Gate 3", 1086940),
-        ("hogwartslegacy.exe", "Hogwarts Legacy", 990080),
-        ("sekiro.exe", "Sekiro: Shadows Die Twice", 814380),
-        ("darksoulsiii.exe", "Dark Souls III", 374320),
-        ("monsterhunterworld.exe", "Monster Hunter: World", 582010),
-        ("monsterhunterwilds.exe", "Monster Hunter Wilds", 2246340),
-        ("fallout4.exe", "Fallout 4", 377160),
-        ("starfield.exe", "Starfield", 1716740),
-        ("palworld.exe", "Palworld", 1623730),
-        ("lethal company.exe", "Lethal Company", 1966720),
-        ("satisfactory.exe", "Satisfactory", 526870),
-        ("helldivers2.exe", "Helldivers 2", 553850),
-        ("arrowhead_hd2.exe", "Helldivers 2", 553850),
-        ("doom eternal.exe", "DOOM Eternal", 782330),
-        ("forzahorizon5.exe", "Forza Horizon 5", 1551360),
-        ("dyinglight.exe", "Dying Light", 239140),
-        ("dyinglight2.exe", "Dying Light 2", 534380),
-        ("halo infinite.exe", "Halo Infinite", 1240440),
-        ("destiny2.exe", "Destiny 2", 1085660),
-        ("bf1.exe", "Battlefield 1", 1238840),
-        ("bf2042.exe", "Battlefield 2042", 1517290),
-        ("nms.exe", "No Man's Sky", 275850),
-        ("b1-wukong-win64-shipping.exe", "Black Myth: Wukong", 2358720),
-        ("rimworldwin64.exe", "RimWorld", 294100),
-        ("factorio.exe", "Factorio", 427520),
-        ("subnautica.exe", "Subnautica", 264710),
-        ("totalwarhammer3.exe", "Total War: Warhammer III", 1142710),
-        ("civilization vi.exe", "Civilization VI", 289070),
-        ("stellaris.exe", "Stellaris", 281990),
-        ("cities2.exe", "Cities: Skylines II", 949230),
-        ("stardewvalley.exe", "Stardew Valley", 413150),
-        ("valheim.exe", "Valheim", 892970),
-        ("phasmophobia.exe", "Phasmophobia", 739630),
-        ("among us.exe", "Among Us", 945360),
-        ("deeprock galactic.exe", "Deep Rock Galactic", 548430),
-        ("slay the spire.exe", "Slay the Spire", 646570),
-        ("hades.exe", "Hades", 1145360),
-        ("deadcells.exe", "Dead Cells", 588650),
-        ("hollowknight.exe", "Hollow Knight", 367520),
-        ("ori.exe", "Ori and the Blind Forest", 261570),
-        ("celeste.exe", "Celeste", 504230),
-        ("cuphead.exe", "Cuphead", 268910),
+        // 进程名(小写), 英文名, 中文官方名（无则留空）, Steam AppId
+        ("gta5.exe", "Grand Theft Auto V", "侠盗猎车手5", 271590),
+        ("gtav.exe", "Grand Theft Auto V", "侠盗猎车手5", 271590),
+        ("eldenring.exe", "Elden Ring", "艾尔登法环", 1245620),
+        ("cyberpunk2077.exe", "Cyberpunk 2077", "赛博朋克2077", 1091500),
+        ("witcher3.exe", "The Witcher 3", "巫师3：狂猎", 292030),
+        ("rdr2.exe", "Red Dead Redemption 2", "荒野大镖客：救赎2", 1174180),
+        ("cs2.exe", "Counter-Strike 2", "反恐精英2", 730),
+        ("csgo.exe", "Counter-Strike 2", "反恐精英2", 730),
+        ("dota2.exe", "Dota 2", "刀塔2", 570),
+        ("valorant.exe", "VALORANT", "无畏契约", 0),
+        ("overwatch.exe", "Overwatch 2", "守望先锋", 0),
+        ("leagueclient.exe", "League of Legends", "英雄联盟", 0),
+        ("league of legends.exe", "League of Legends", "英雄联盟", 0),
+        ("pubg.exe", "PUBG: Battlegrounds", "绝地求生", 578080),
+        ("tslgame.exe", "PUBG: Battlegrounds", "绝地求生", 578080),
+        ("fortnite.exe", "Fortnite", "堡垒之夜", 0),
+        ("apex_r5apex.exe", "Apex Legends", "Apex 英雄", 1172470),
+        ("r5apex.exe", "Apex Legends", "Apex 英雄", 1172470),
+        ("terraria.exe", "Terraria", "泰拉瑞亚", 105600),
+        ("rust.exe", "Rust", "腐蚀", 252490),
+        ("baldursgate3.exe", "Baldur's Gate 3", "博德之门3", 1086940),
+        ("bg3.exe", "Baldur's Gate 3", "博德之门3", 1086940),
+        ("hogwartslegacy.exe", "Hogwarts Legacy", "霍格沃茨之遗", 990080),
+        ("sekiro.exe", "Sekiro: Shadows Die Twice", "只狼：影逝二度", 814380),
+        ("darksoulsiii.exe", "Dark Souls III", "黑暗之魂3", 374320),
+        ("monsterhunterworld.exe", "Monster Hunter: World", "怪物猎人：世界", 582010),
+        ("monsterhunterwilds.exe", "Monster Hunter Wilds", "怪物猎人：荒野", 2246340),
+        ("fallout4.exe", "Fallout 4", "辐射4", 377160),
+        ("starfield.exe", "Starfield", "星空", 1716740),
+        ("palworld.exe", "Palworld", "幻兽帕鲁", 1623730),
+        ("lethal company.exe", "Lethal Company", "致命公司", 1966720),
+        ("satisfactory.exe", "Satisfactory", "幸福工厂", 526870),
+        ("helldivers2.exe", "Helldivers 2", "绝地潜兵2", 553850),
+        ("arrowhead_hd2.exe", "Helldivers 2", "绝地潜兵2", 553850),
+        ("doom eternal.exe", "DOOM Eternal", "毁灭战士：永恒", 782330),
+        ("forzahorizon5.exe", "Forza Horizon 5", "极限竞速：地平线5", 1551360),
+        ("dyinglight.exe", "Dying Light", "消逝的光芒", 239140),
+        ("dyinglight2.exe", "Dying Light 2", "消逝的光芒2", 534380),
+        ("halo infinite.exe", "Halo Infinite", "光环：无限", 1240440),
+        ("destiny2.exe", "Destiny 2", "命运2", 1085660),
+        ("bf1.exe", "Battlefield 1", "战地1", 1238840),
+        ("bf2042.exe", "Battlefield 2042", "战地2042", 1517290),
+        ("nms.exe", "No Man's Sky", "无人深空", 275850),
+        ("b1-wukong-win64-shipping.exe", "Black Myth: Wukong", "黑神话：悟空", 2358720),
+        ("rimworldwin64.exe", "RimWorld", "环世界", 294100),
+        ("factorio.exe", "Factorio", "异星工厂", 427520),
+        ("subnautica.exe", "Subnautica", "深海迷航", 264710),
+        ("totalwarhammer3.exe", "Total War: Warhammer III", "全面战争：战锤3", 1142710),
+        ("civilization vi.exe", "Civilization VI", "文明6", 289070),
+        ("stellaris.exe", "Stellaris", "群星", 281990),
+        ("cities2.exe", "Cities: Skylines II", "城市：天际线2", 949230),
+        ("stardewvalley.exe", "Stardew Valley", "星露谷物语", 413150),
+        ("valheim.exe", "Valheim", "英灵神殿", 892970),
+        ("phasmophobia.exe", "Phasmophobia", "恐鬼症", 739630),
+        ("among us.exe", "Among Us", "", 945360),
+        ("deeprock galactic.exe", "Deep Rock Galactic", "深岩银河", 548430),
+        ("slay the spire.exe", "Slay the Spire", "杀戮尖塔", 646570),
+        ("hades.exe", "Hades", "哈迪斯", 1145360),
+        ("deadcells.exe", "Dead Cells", "死亡细胞", 588650),
+        ("hollowknight.exe", "Hollow Knight", "空洞骑士", 367520),
+        ("ori.exe", "Ori and the Blind Forest", "奥日与黑暗森林", 261570),
+        ("celeste.exe", "Celeste", "蔚蓝", 504230),
+        ("cuphead.exe", "Cuphead", "茶杯头", 268910),
     ];
 
     let mut map = HashMap::new();
-    for (process, name, app_id) in games {
+    for (process, name_en, name_zh, app_id) in games {
         map.insert(
             process.to_lowercase(),
-            (name.to_string(), app_id),
+            KnownGame {
+                name_en: name_en.to_string(),
+                name_zh: Some(name_zh.to_string()).filter(|n| !n.is_empty()),
+                app_id,
+            },
         );
     }
     map
 }
 
+/// 已知游戏的中英文名称（每个进程名一条，按界面语言选择显示名称）
+pub fn known_game_names() -> Vec<KnownGameName> {
+    let language = crate::settings::get().game_name_language;
+    build_known_games()
+        .into_iter()
+        .map(|(process, game)| KnownGameName {
+            name: language.pick(&game.name_en, game.name_zh.as_deref()),
+            name_en: game.name_en,
+            name_zh: game.name_zh,
+            process_name: process,
+        })
+        .collect()
+}
+
 // ==================== 进程扫描 ====================
 
 /// 扫描当前运行中的游戏进程
 fn scan_processes() -> Vec<DetectedGame> {
     let known = build_known_games();
+    let language = crate::settings::get().game_name_language;
     let mut sys = System::new();
     sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
 
@@ -123,27 +255,75 @@ fn scan_processes() -> Vec<DetectedGame> {
             .to_string_lossy()
             .to_lowercase();
 
-        // 跳过系统进程
-        if exe_name.is_empty()
-            || exe_name.starts_with("system")
-            || exe_name.starts_with("svchost")
-            || exe_name.starts_with("csrss")
-            || exe_name.starts_with("conhost")
-            || exe_name == "explorer.exe"
-            || exe_name.starts_with("runtime")
-        {
+        // 跳过系统进程与常见非游戏进程（名单可从服务端更新）
+        if process_filter::is_skipped(&exe_name) {
             continue;
         }
 
         // 检查是否是已知游戏
-        if let Some((game_name, app_id)) = known.get(&exe_name) {
-            if !seen.contains(&exe_name) {
-                seen.insert(exe_name.clone());
+        if let Some(game) = known.get(&exe_name) {
+            if !seen.contains(pid) {
+                seen.insert(*pid);
                 games.push(DetectedGame {
                     process_name: process.name().to_string_lossy().to_string(),
                     pid: pid.as_u32(),
-                    game_name: Some(game_name.clone()),
-                    app_id: if *app_id > 0 { Some(*app_id) } else { None },
+                    game_name: Some(language.pick(&game.name_en, game.name_zh.as_deref())),
+                    game_name_en: Some(game.name_en.clone()),
+                    game_name_zh: game.name_zh.clone(),
+                    app_id: if game.app_id > 0 { Some(game.app_id) } else { None },
+                    ..Default::default()
+                });
+            }
+        }
+
+        // WeGame / 米哈游 / 网易平台游戏
+        if let Some(game) = launchers::find_game(&exe_name) {
+            if !seen.contains(pid) {
+                seen.insert(*pid);
+                games.push(DetectedGame {
+                    process_name: process.name().to_string_lossy().to_string(),
+                    pid: pid.as_u32(),
+                    game_name: Some(language.pick(game.name_en, Some(game.name_zh))),
+                    game_name_en: Some(game.name_en.to_string()),
+                    game_name_zh: Some(game.name_zh.to_string()),
+                    launcher: Some(game.launcher),
+                    ..Default::default()
+                });
+            }
+        }
+
+        // 模拟器：从窗口标题解析正在运行的游戏
+        if let Some(emulator) = emulators::find_emulator(&exe_name) {
+            if !seen.contains(pid) {
+                seen.insert(*pid);
+                let title = game_window::main_window_title(pid.as_u32())
+                    .and_then(|t| emulators::parse_game_title(&emulator, &t));
+                let match_reason = if title.is_some() {
+                    MatchReason::Heuristic
+                } else {
+                    MatchReason::KnownList
+                };
+                games.push(DetectedGame {
+                    process_name: process.name().to_string_lossy().to_string(),
+                    pid: pid.as_u32(),
+                    game_name: Some(title.unwrap_or_else(|| emulator.name.to_string())),
+                    emulator: Some(format!("{} ({})", emulator.name, emulator.platform)),
+                    match_reason,
+                    ..Default::default()
+                });
+            }
+        }
+
+        // 云游戏 / 串流客户端
+        if let Some(client) = streaming::find_client(&exe_name) {
+            if !seen.contains(pid) {
+                seen.insert(*pid);
+                games.push(DetectedGame {
+                    process_name: process.name().to_string_lossy().to_string(),
+                    pid: pid.as_u32(),
+                    game_name: Some(format!("{}（串流）", client)),
+                    streaming_client: Some(client.to_string()),
+                    ..Default::default()
                 });
             }
         }
@@ -153,26 +333,93 @@ fn scan_processes() -> Vec<DetectedGame> {
             let path_str = exe_path.to_string_lossy().to_lowercase();
             if path_str.contains("steamapps")
                 && path_str.contains("common")
-                && !seen.contains(&exe_name)
+                && !seen.contains(pid)
                 && !known.contains_key(&exe_name)
             {
                 // Steam 游戏但不在已知列表中
                 // 从路径提取游戏名: .../steamapps/common/GameName/...
                 let game_name = extract_steam_game_name(&path_str);
-                seen.insert(exe_name.clone());
+                seen.insert(*pid);
                 games.push(DetectedGame {
                     process_name: process.name().to_string_lossy().to_string(),
                     pid: pid.as_u32(),
                     game_name,
-                    app_id: None,
+                    match_reason: MatchReason::SteamPath,
+                    ..Default::default()
                 });
             }
+
+            // UWP / Game Pass 游戏：按应用包映射显示名称
+            if uwp::is_packaged_path(&path_str) && !seen.contains(pid) {
+                if let Some(package) = uwp::package_for_pid(pid.as_u32()).filter(|p| p.is_game) {
+                    seen.insert(*pid);
+                    games.push(DetectedGame {
+                        process_name: process.name().to_string_lossy().to_string(),
+                        pid: pid.as_u32(),
+                        game_name: Some(package.display_name),
+                        package_name: Some(package.full_name),
+                        match_reason: MatchReason::Package,
+                        ..Default::default()
+                    });
+                }
+            }
         }
     }
 
+    let mut games = game_alias::merge_aliases(games);
+    for game in &mut games {
+        game.window_handle = game_window::main_window_handle(game.pid);
+        game.window_title = game_window::main_window_title(game.pid);
+        game.activity = process_activity::activity_state(game.pid);
+        game.confidence = confidence_for(game);
+        game.start_time = sys
+            .process(sysinfo::Pid::from_u32(game.pid))
+            .map(|p| p.start_time())
+            .unwrap_or(0);
+    }
+    number_instances(&mut games);
     games
 }
 
+/// 同一进程名的多个实例按启动时间编号，便于前端区分并按 PID 监测
+fn number_instances(games: &mut [DetectedGame]) {
+    games.sort_by(|a, b| {
+        a.process_name
+            .to_lowercase()
+            .cmp(&b.process_name.to_lowercase())
+            .then(a.start_time.cmp(&b.start_time))
+            .then(a.pid.cmp(&b.pid))
+    });
+    let mut previous: Option<String> = None;
+    let mut index = 0;
+    for game in games.iter_mut() {
+        let name = game.process_name.to_lowercase();
+        index = if previous.as_deref() == Some(name.as_str()) {
+            index + 1
+        } else {
+            1
+        };
+        game.instance = index;
+        previous = Some(name);
+    }
+}
+
+/// 按匹配依据评估可信度
+/// Steam 目录中的工具/引导进程也会被匹配，没有窗口或名称像启动器时降为低可信度
+fn confidence_for(game: &DetectedGame) -> Confidence {
+    match game.match_reason {
+        MatchReason::KnownList | MatchReason::Package => Confidence::High,
+        MatchReason::Heuristic => Confidence::Medium,
+        MatchReason::SteamPath
+            if game.window_handle.is_none()
+                || game_alias::looks_like_launcher(&game.process_name) =>
+        {
+            Confidence::Low
+        }
+        MatchReason::SteamPath => Confidence::Medium,
+    }
+}
+
 /// 从 Steam 安装路径提取游戏名
 fn extract_steam_game_name(path: &str) -> Option<String> {
     // 路径格式: .../steamapps/common/GameName/game.exe
@@ -193,37 +440,119 @@ fn extract_steam_game_name(path: &str) -> Option<String> {
 
 // ==================== 后台扫描器 ====================
 
-/// 后台定期扫描运行中的游戏，检测到新游戏时通知前端
+/// 后台定期扫描运行中的游戏，检测到新游戏时通知前端（附带活跃状态）
+/// 挂起或最小化空闲的进程（如托盘中暂停的启动器）不会自动开始监测，恢复活跃后再开始
 pub fn background_scanner(app: AppHandle) {
-    let mut last_detected: Vec<String> = Vec::new();
+    process_filter::reload(&app);
+    let mut announced: Vec<DetectedGame> = Vec::new();
+    // 自动开始的监测: PID → 会话 ID，游戏退出时结束
+    let mut auto_sessions: HashMap<u32, String> = HashMap::new();
+    // 检测到时不活跃、等待恢复后再自动监测的 PID
+    let mut deferred: Vec<u32> = Vec::new();
 
     loop {
         std::thread::sleep(std::time::Duration::from_secs(5));
 
         let games = scan_processes();
-        let current: Vec<String> = games.iter().map(|g| g.process_name.clone()).collect();
+        let current: Vec<u32> = games.iter().map(|g| g.pid).collect();
 
-        // 检测新启动的游戏
+        // 检测新启动的游戏（同一游戏多开时每个实例单独通知）
         for game in &games {
-            if !last_detected.contains(&game.process_name) {
+            if !announced.iter().any(|a| a.pid == game.pid) {
                 log::info!(
-                    "检测到游戏启动: {} ({})",
+                    "检测到游戏启动: {} ({}, {:?})",
                     game.game_name.as_deref().unwrap_or("Unknown"),
-                    game.process_name
+                    game.process_name,
+                    game.activity
                 );
                 let _ = app.emit("game-detected", game);
+                announced.push(game.clone());
+            } else if !deferred.contains(&game.pid) {
+                continue;
+            }
+
+            if game.activity != ActivityState::Active {
+                if !deferred.contains(&game.pid) {
+                    deferred.push(game.pid);
+                }
+                continue;
+            }
+            deferred.retain(|pid| *pid != game.pid);
+            if let Some(session_id) = auto_start(&app, game) {
+                auto_sessions.insert(game.pid, session_id);
             }
         }
+        deferred.retain(|pid| current.contains(pid));
 
         // 检测退出的游戏
-        for old_name in &last_detected {
-            if !current.contains(old_name) {
-                log::info!("检测到游戏退出: {}", old_name);
-                let _ = app.emit("game-exited", old_name.as_str());
+        announced.retain(|old| {
+            let running = current.contains(&old.pid);
+            if !running {
+                log::info!("检测到游戏退出: {} (PID {})", old.process_name, old.pid);
+                let _ = app.emit("game-exited", build_exit_event(old));
+                if let Some(session_id) = auto_sessions.remove(&old.pid) {
+                    auto_stop(&app, &session_id);
+                }
             }
+            running
+        });
+    }
+}
+
+fn build_exit_event(game: &DetectedGame) -> GameExited {
+    let now = chrono::Utc::now().timestamp().max(0) as u64;
+    let session = fps_monitor::session_for_process(&game.process_name, game.start_time);
+    GameExited {
+        process_name: game.process_name.clone(),
+        game_name: game.game_name.clone(),
+        pid: game.pid,
+        run_secs: if game.start_time > 0 {
+            now.saturating_sub(game.start_time)
+        } else {
+            0
+        },
+        capture_active: session.is_some(),
+        session_id: session,
+    }
+}
+
+// ==================== 自动监测 ====================
+
+/// 设置开启自动监测时，对新检测到的游戏开始监测，返回会话 ID
+/// 低可信度的推测结果不自动监测；该游戏已在监测（如用户手动开始）时不打断
+fn auto_start(app: &AppHandle, game: &DetectedGame) -> Option<String> {
+    if !crate::settings::get().auto_monitor
+        || game.confidence == Confidence::Low
+        || fps_monitor::is_monitoring(&game.process_name)
+    {
+        return None;
+    }
+    let result = fps_monitor::start_fps_monitor(
+        app.clone(),
+        game.process_name.clone(),
+        Some(game.pid),
+        None,
+        Some(BusyPolicy::Reject),
+        None,
+    );
+    match result {
+        Ok(StartOutcome::Started { session_id }) => {
+            log::info!("已自动开始监测 {} (PID {})", game.process_name, game.pid);
+            Some(session_id)
         }
+        Ok(_) => None,
+        Err(e) => {
+            log::warn!("自动开始监测 {} 失败: {}", game.process_name, e);
+            None
+        }
+    }
+}
 
-        last_detected = current;
+/// 游戏退出后结束自动开始的监测（监测可能已被用户提前停止）
+fn auto_stop(app: &AppHandle, session_id: &str) {
+    match fps_monitor::stop_session(app, session_id, "游戏已退出") {
+        Ok(()) => log::info!("游戏退出，已自动结束监测 {}", session_id),
+        Err(e) => log::debug!("自动结束监测跳过: {}", e),
     }
 }
 
@@ -237,13 +566,9 @@ pub fn scan_running_games() -> Result<Vec<DetectedGame>, String> {
 
 /// 获取已知游戏列表（用于前端展示支持的游戏）
 #[tauri::command]
-pub fn get_known_games() -> Result<Vec<(String, String)>, String> {
-    let known = build_known_games();
-    let mut games: Vec<(String, String)> = known
-        .iter()
-        .map(|(process, (name, _))| (name.clone(), process.clone()))
-        .collect();
-    games.sort_by(|a, b| a.0.cmp(&b.0));
-    games.dedup_by(|a, b| a.0 == b.0);
+pub fn get_known_games() -> Result<Vec<KnownGameName>, String> {
+    let mut games = known_game_names();
+    games.sort_by(|a, b| a.name_en.cmp(&b.name_en));
+    games.dedup_by(|a, b| a.name_en == b.name_en);
     Ok(games)
 }
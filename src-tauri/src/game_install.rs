@@ -0,0 +1,364 @@
+use crate::direct_storage::{self, GameDirectStorage};
+use crate::drives::{self, DriveInfo, DriveKind};
+use crate::fps_monitor::FpsSession;
+use crate::tasks::{self, TaskHandle, TaskKind};
+use crate::units;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use sysinfo::{Pid, System};
+use tauri::AppHandle;
+use walkdir::WalkDir;
+
+/// 统计占用空间时每隔多少个条目上报一次进度
+const PROGRESS_EVERY: usize = 500;
+/// 实际占用低于 Steam 记录大小的该比例时，认为可能有文件缺失
+const SIZE_SHORTFALL_RATIO: f64 = 0.9;
+
+/// Steam appmanifest 的 StateFlags 位
+const STATE_UPDATE_REQUIRED: u64 = 2;
+const STATE_FILES_MISSING: u64 = 32;
+const STATE_FILES_CORRUPT: u64 = 128;
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallReport {
+    /// 游戏进程名
+    pub process_name: String,
+    /// 安装目录（Steam 游戏为 steamapps/common/<Game>，其他游戏为启动器记录的目录或 exe 上层的游戏根目录）
+    pub install_dir: String,
+    /// 是否为 Steam 库中的游戏
+    pub is_steam: bool,
+    /// 所在驱动器
+    pub drive: Option<DriveInfo>,
+    /// 占用空间 (GB)
+    pub size_gb: f64,
+    /// 是否安装在系统最慢的驱动器上
+    pub on_slowest_drive: bool,
+    /// 系统中最快的驱动器（推荐迁移目标）
+    pub fastest_drive: Option<DriveInfo>,
+    /// DirectStorage / BypassIO 情况
+    pub direct_storage: GameDirectStorage,
+    /// 迁移建议
+    pub recommendation: Option<String>,
+    /// 文件完整性（仅 Steam 游戏，找不到 appmanifest 时为 None）
+    pub integrity: Option<InstallIntegrity>,
+}
+
+/// 根据 Steam appmanifest 判断的安装完整性
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallIntegrity {
+    pub app_id: String,
+    /// appmanifest 记录的占用空间 (GB)
+    pub expected_size_gb: Option<f64>,
+    /// 发现的问题（文件缺失、损坏、待更新等），为空表示未发现问题
+    pub issues: Vec<String>,
+    /// 在 Steam 中验证文件完整性的链接 (steam://validate/<appid>)
+    pub verify_url: String,
+}
+
+// ==================== 安装目录 ====================
+
+/// exe 常见的存放子目录，向上跳过这些目录才是游戏根目录
+const BINARY_DIRS: &[&str] = &[
+    "bin", "bin64", "binaries", "win64", "win32", "x64", "x86", "retail",
+];
+/// Epic 安装目录的标记文件夹
+const EPIC_MARKER: &str = ".egstore";
+/// 向上查找游戏根目录的最大层数
+const MAX_ROOT_DEPTH: usize = 4;
+
+/// 路径 `path` 是否位于目录 `dir` 内（不区分大小写）
+fn is_within(path: &str, dir: &str) -> bool {
+    let dir = dir.trim_end_matches(['\\', '/']).to_lowercase();
+    let path = path.to_lowercase();
+    !dir.is_empty() && path.starts_with(&dir) && path[dir.len()..].starts_with(['\\', '/'])
+}
+
+/// 非 Steam 游戏的根目录：启动器记录的安装目录 > Epic 标记 > 跳过 bin / Binaries\Win64 等子目录
+/// Unreal: .../Game/Project/Binaries/Win64/x.exe → .../Game（与 Engine 目录同级的上层）
+fn game_root(exe: &Path) -> PathBuf {
+    let exe_str = exe.to_string_lossy();
+    let known = crate::launchers::installed_games()
+        .into_iter()
+        .filter(|g| is_within(&exe_str, &g.install_dir))
+        .max_by_key(|g| g.install_dir.len());
+    if let Some(game) = known {
+        return PathBuf::from(game.install_dir);
+    }
+
+    let exe_dir = exe.parent().unwrap_or(exe);
+    if let Some(root) = exe_dir
+        .ancestors()
+        .take(MAX_ROOT_DEPTH + 1)
+        .find(|dir| dir.join(EPIC_MARKER).is_dir())
+    {
+        return root.to_path_buf();
+    }
+
+    let mut dir = exe_dir;
+    // 不向上越过盘符根目录的下一级，避免把整个分区当成游戏目录
+    while let Some(parent) = dir.parent().filter(|p| p.parent().is_some()) {
+        let name = dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if !BINARY_DIRS.contains(&name.as_str()) {
+            break;
+        }
+        dir = parent;
+    }
+    // Unreal 打包的游戏：项目目录与 Engine 目录并列
+    match dir.parent() {
+        Some(parent)
+            if dir != exe_dir && parent.parent().is_some() && parent.join("Engine").is_dir() =>
+        {
+            parent.to_path_buf()
+        }
+        _ => dir.to_path_buf(),
+    }
+}
+
+/// 从 exe 路径推断安装目录
+/// Steam: .../steamapps/common/GameName/bin/game.exe → .../steamapps/common/GameName
+fn install_dir_from_exe(exe: &Path) -> (PathBuf, bool) {
+    let components: Vec<_> = exe.components().collect();
+    for (idx, window) in components.windows(2).enumerate() {
+        let a = window[0].as_os_str().to_string_lossy().to_lowercase();
+        let b = window[1].as_os_str().to_string_lossy().to_lowercase();
+        if a == "steamapps" && b == "common" && components.len() > idx + 2 {
+            let dir: PathBuf = components[..idx + 3].iter().collect();
+            return (dir, true);
+        }
+    }
+
+    (game_root(exe), false)
+}
+
+/// 递归统计目录大小 (bytes)，`expected` 为已知的总大小（用作进度总量，未知为 0）
+fn dir_size(task: &TaskHandle, path: &Path, expected: u64) -> Result<u64, String> {
+    let mut size = 0;
+    for (i, entry) in WalkDir::new(path).into_iter().flatten().enumerate() {
+        if entry.file_type().is_file() {
+            size += entry.metadata().map(|m| m.len()).unwrap_or(0);
+        }
+        if i % PROGRESS_EVERY == 0 {
+            task.check()?;
+            task.progress(size.min(expected), expected, "统计占用空间");
+        }
+    }
+    Ok(size)
+}
+
+// ==================== 完整性 ====================
+
+/// 读取 appmanifest 顶层的 "key" "value" 项
+fn acf_value<'a>(text: &'a str, key: &str) -> Option<&'a str> {
+    text.lines().find_map(|line| {
+        let mut parts = line.split('"').filter(|p| !p.trim().is_empty());
+        match (parts.next(), parts.next()) {
+            (Some(k), Some(v)) if k.eq_ignore_ascii_case(key) => Some(v),
+            _ => None,
+        }
+    })
+}
+
+/// 在 steamapps 目录中找到安装目录对应的 appmanifest_<appid>.acf
+fn find_app_manifest(install_dir: &Path) -> Option<String> {
+    let folder = install_dir.file_name()?.to_string_lossy().to_string();
+    let steamapps = install_dir.parent()?.parent()?;
+    std::fs::read_dir(steamapps)
+        .ok()?
+        .flatten()
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy().to_lowercase();
+            name.starts_with("appmanifest_") && name.ends_with(".acf")
+        })
+        .filter_map(|e| std::fs::read_to_string(e.path()).ok())
+        .find(|text| acf_value(text, "installdir").is_some_and(|d| d.eq_ignore_ascii_case(&folder)))
+}
+
+fn check_integrity(manifest: &str, size_bytes: u64) -> Option<InstallIntegrity> {
+    let app_id = acf_value(manifest, "appid")?.to_string();
+    let flags: u64 = acf_value(manifest, "StateFlags")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+    let expected = acf_value(manifest, "SizeOnDisk").and_then(|v| v.parse::<u64>().ok());
+
+    let mut issues = Vec::new();
+    if flags & STATE_FILES_MISSING != 0 {
+        issues.push("Steam 标记该游戏有文件缺失".to_string());
+    }
+    if flags & STATE_FILES_CORRUPT != 0 {
+        issues.push("Steam 标记该游戏有文件损坏".to_string());
+    }
+    if flags & STATE_UPDATE_REQUIRED != 0 {
+        issues.push("游戏有未完成的更新".to_string());
+    }
+    if let Some(expected) = expected.filter(|e| *e > 0) {
+        if (size_bytes as f64) < expected as f64 * SIZE_SHORTFALL_RATIO {
+            let units = units::current();
+            issues.push(format!(
+                "实际占用 {}，少于 Steam 记录的 {}，可能有文件缺失",
+                units.size_from_gib(bytes_to_gib(size_bytes)),
+                units.size_from_gib(bytes_to_gib(expected))
+            ));
+        }
+    }
+
+    Some(InstallIntegrity {
+        verify_url: format!("steam://validate/{}", app_id),
+        app_id,
+        expected_size_gb: expected.map(|e| (bytes_to_gib(e) * 10.0).round() / 10.0),
+        issues,
+    })
+}
+
+fn bytes_to_gib(bytes: u64) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0 * 1024.0)
+}
+
+/// 0.1% Low 远低于平均帧率时，认为存在卡顿（可能与磁盘读取有关）
+fn is_stutter_prone(session: &FpsSession) -> bool {
+    session.avg_fps > 0.0 && session.fps_01_low < session.avg_fps * 0.25
+}
+
+fn build_recommendation(
+    drive: Option<&DriveInfo>,
+    on_slowest: bool,
+    fastest: Option<&DriveInfo>,
+    session: Option<&FpsSession>,
+    size_gb: f64,
+) -> Option<String> {
+    let drive = drive?;
+    let fastest = fastest?;
+    if !on_slowest || fastest.kind.speed_rank() <= drive.kind.speed_rank() {
+        return None;
+    }
+
+    let stutter = session.map(is_stutter_prone).unwrap_or(false);
+    if stutter {
+        Some(format!(
+            "本次测试存在明显卡顿（0.1% Low 偏低），且游戏安装在最慢的驱动器 {}。\
+             建议将游戏迁移到 {}（{}）以减少加载和串流卡顿。",
+            drive.mount_point,
+            fastest.mount_point,
+            kind_label(fastest.kind)
+        ))
+    } else if drive.kind == DriveKind::Hdd {
+        Some(format!(
+            "游戏安装在机械硬盘 {}（占用 {}），如遇到加载缓慢或场景切换卡顿，可考虑迁移到 {}。",
+            drive.mount_point,
+            units::current().size_from_gib(size_gb),
+            fastest.mount_point
+        ))
+    } else {
+        None
+    }
+}
+
+fn kind_label(kind: DriveKind) -> &'static str {
+    match kind {
+        DriveKind::Nvme => "NVMe SSD",
+        DriveKind::SataSsd => "SATA SSD",
+        DriveKind::Hdd => "机械硬盘",
+        DriveKind::Unknown => "未知类型",
+    }
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 后台生成运行中游戏的安装位置与完整性报告，返回任务 ID
+/// 传入 session 时结合帧时间分析给出迁移建议
+#[tauri::command]
+pub fn get_install_report(
+    app: AppHandle,
+    pid: u32,
+    session: Option<FpsSession>,
+) -> Result<String, String> {
+    tasks::spawn(&app, TaskKind::Analysis, "安装位置报告", move |task| {
+        build_report(task, pid, session)
+    })
+}
+
+fn build_report(
+    task: &TaskHandle,
+    pid: u32,
+    session: Option<FpsSession>,
+) -> Result<InstallReport, String> {
+    let mut sys = System::new();
+    sys.refresh_processes(
+        sysinfo::ProcessesToUpdate::Some(&[Pid::from_u32(pid)]),
+        true,
+    );
+
+    let process = sys
+        .process(Pid::from_u32(pid))
+        .ok_or_else(|| format!("未找到进程 PID {}", pid))?;
+    let exe = process
+        .exe()
+        .ok_or_else(|| "无法获取游戏可执行文件路径（可能需要管理员权限）".to_string())?;
+
+    let (install_dir, is_steam) = install_dir_from_exe(exe);
+    let manifest = is_steam.then(|| find_app_manifest(&install_dir)).flatten();
+    let expected = manifest
+        .as_deref()
+        .and_then(|m| acf_value(m, "SizeOnDisk"))
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+    let size_bytes = dir_size(task, &install_dir, expected)?;
+    let size_gb = bytes_to_gib(size_bytes);
+    let integrity = manifest
+        .as_deref()
+        .and_then(|m| check_integrity(m, size_bytes));
+
+    let all_drives = drives::list_drive_info();
+    let drive = drives::drive_for_path(&all_drives, &install_dir).cloned();
+    let slowest = drives::slowest_drive(&all_drives);
+    let fastest = all_drives
+        .iter()
+        .filter(|d| !d.removable)
+        .max_by_key(|d| d.kind.speed_rank())
+        .cloned();
+
+    // 所有驱动器同速时不算"最慢"
+    let on_slowest = match (&drive, slowest, &fastest) {
+        (Some(d), Some(s), Some(f)) => {
+            d.mount_point == s.mount_point && s.kind.speed_rank() < f.kind.speed_rank()
+        }
+        _ => false,
+    };
+
+    let recommendation = build_recommendation(
+        drive.as_ref(),
+        on_slowest,
+        fastest.as_ref(),
+        session.as_ref(),
+        size_gb,
+    );
+
+    let direct_storage = direct_storage::detect_game_support(pid, &install_dir, drive.as_ref());
+
+    log::info!(
+        "安装位置报告: {} | {} | {:.1} GB",
+        process.name().to_string_lossy(),
+        install_dir.display(),
+        size_gb
+    );
+    if let Some(integrity) = integrity.as_ref().filter(|i| !i.issues.is_empty()) {
+        log::warn!("安装完整性问题: {}", integrity.issues.join("；"));
+    }
+
+    Ok(InstallReport {
+        process_name: process.name().to_string_lossy().to_string(),
+        install_dir: install_dir.to_string_lossy().to_string(),
+        is_steam,
+        drive,
+        size_gb: (size_gb * 10.0).round() / 10.0,
+        on_slowest_drive: on_slowest,
+        fastest_drive: fastest,
+        direct_storage,
+        recommendation,
+        integrity,
+    })
+}
@@ -0,0 +1,11 @@
+pub use gamebench_core::gpu_routing::{
+    detect_routing, CompositionPath, GpuRouting, RoutingSampler,
+};
+
+// ==================== Tauri 命令 ====================
+
+/// 检测指定进程的 GPU 输出路径
+#[tauri::command]
+pub fn get_gpu_routing(pid: u32) -> Result<GpuRouting, String> {
+    Ok(detect_routing(pid))
+}
@@ -0,0 +1,385 @@
+use crate::fps_monitor;
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, Emitter};
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HotkeyAction {
+    /// 开始监测前台窗口所属的游戏
+    Start,
+    /// 停止所有进行中的监测
+    Stop,
+    /// 在进行中的监测里添加标记
+    Mark,
+    /// 显示/关闭 FPS 悬浮窗
+    Overlay,
+}
+
+impl HotkeyAction {
+    fn label(self) -> &'static str {
+        match self {
+            HotkeyAction::Start => "开始监测",
+            HotkeyAction::Stop => "停止监测",
+            HotkeyAction::Mark => "添加标记",
+            HotkeyAction::Overlay => "切换悬浮窗",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HotkeyBinding {
+    pub action: HotkeyAction,
+    /// 组合键，如 "Ctrl+Shift+F9"
+    pub keys: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HotkeySettings {
+    pub enabled: bool,
+    pub bindings: Vec<HotkeyBinding>,
+}
+
+impl Default for HotkeySettings {
+    fn default() -> Self {
+        let bind = |action, keys: &str| HotkeyBinding {
+            action,
+            keys: keys.to_string(),
+        };
+        HotkeySettings {
+            enabled: true,
+            bindings: vec![
+                bind(HotkeyAction::Start, "Ctrl+Shift+F9"),
+                bind(HotkeyAction::Stop, "Ctrl+Shift+F10"),
+                bind(HotkeyAction::Mark, "Ctrl+Shift+F11"),
+                bind(HotkeyAction::Overlay, "Ctrl+Shift+F12"),
+            ],
+        }
+    }
+}
+
+/// 单个快捷键的注册结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotkeyStatus {
+    pub action: HotkeyAction,
+    pub keys: String,
+    pub registered: bool,
+    /// 注册失败原因（格式错误或已被其他程序占用）
+    pub error: Option<String>,
+}
+
+/// 快捷键触发结果，推送 `hotkey-triggered`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotkeyEvent {
+    pub action: HotkeyAction,
+    pub error: Option<String>,
+}
+
+// ==================== 组合键解析 ====================
+
+const MOD_ALT: u32 = 0x1;
+const MOD_CONTROL: u32 = 0x2;
+const MOD_SHIFT: u32 = 0x4;
+const MOD_WIN: u32 = 0x8;
+
+/// 解析后的组合键：修饰键位掩码与虚拟键码
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Accelerator {
+    pub modifiers: u32,
+    pub key: u32,
+}
+
+fn virtual_key(name: &str) -> Option<u32> {
+    let upper = name.to_ascii_uppercase();
+    let named = match upper.as_str() {
+        "HOME" => Some(0x24),
+        "END" => Some(0x23),
+        "INSERT" | "INS" => Some(0x2D),
+        "DELETE" | "DEL" => Some(0x2E),
+        "PAGEUP" | "PGUP" => Some(0x21),
+        "PAGEDOWN" | "PGDN" => Some(0x22),
+        "PAUSE" => Some(0x13),
+        "SCROLLLOCK" => Some(0x91),
+        "PRINTSCREEN" => Some(0x2C),
+        "SPACE" => Some(0x20),
+        _ => None,
+    };
+    if named.is_some() {
+        return named;
+    }
+    if let Some(n) = upper.strip_prefix("NUMPAD") {
+        return match n.parse::<u32>() {
+            Ok(n) if n <= 9 => Some(0x60 + n),
+            _ => None,
+        };
+    }
+    if let Some(n) = upper.strip_prefix('F') {
+        if let Ok(n) = n.parse::<u32>() {
+            return (1..=24).contains(&n).then_some(0x6F + n);
+        }
+    }
+    let mut chars = upper.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_alphanumeric() => Some(c as u32),
+        _ => None,
+    }
+}
+
+/// 解析 "Ctrl+Shift+F9" 形式的组合键
+/// 字母、数字与空格键必须带修饰键，避免游戏内正常按键被占用
+pub fn parse(keys: &str) -> Result<Accelerator, String> {
+    let mut modifiers = 0;
+    let mut key = None;
+    for part in keys.split('+').map(str::trim) {
+        let flag = match part.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => MOD_CONTROL,
+            "alt" => MOD_ALT,
+            "shift" => MOD_SHIFT,
+            "win" | "super" | "meta" => MOD_WIN,
+            _ => 0,
+        };
+        if flag != 0 {
+            modifiers |= flag;
+            continue;
+        }
+        if key.is_some() {
+            return Err(format!("组合键 {} 只能包含一个主键", keys));
+        }
+        key = Some(virtual_key(part).ok_or_else(|| format!("无法识别的按键: {}", part))?);
+    }
+    let key = key.ok_or_else(|| format!("组合键 {} 缺少主键", keys))?;
+    let plain = key == 0x20 || (0x30..=0x5A).contains(&key);
+    if modifiers == 0 && plain {
+        return Err(format!("{} 需要搭配 Ctrl / Alt / Shift / Win", keys));
+    }
+    Ok(Accelerator { modifiers, key })
+}
+
+/// 校验绑定：每个组合键都能解析，且同一组合键不重复绑定
+pub fn validate(settings: &HotkeySettings) -> Result<(), String> {
+    let mut seen = Vec::new();
+    for binding in &settings.bindings {
+        let accel = parse(&binding.keys)?;
+        if seen.contains(&accel) {
+            return Err(format!("组合键 {} 重复绑定", binding.keys));
+        }
+        seen.push(accel);
+    }
+    Ok(())
+}
+
+// ==================== 触发 ====================
+
+fn app_handle() -> &'static OnceLock<AppHandle> {
+    static APP: OnceLock<AppHandle> = OnceLock::new();
+    &APP
+}
+
+fn status() -> &'static Mutex<Vec<HotkeyStatus>> {
+    static STATUS: OnceLock<Mutex<Vec<HotkeyStatus>>> = OnceLock::new();
+    STATUS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+/// 前台窗口所属的进程 (PID, 进程名)
+#[cfg(target_os = "windows")]
+fn foreground_process() -> Option<(u32, String)> {
+    use sysinfo::{Pid, System};
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    let mut pid = 0u32;
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.is_invalid() {
+            return None;
+        }
+        GetWindowThreadProcessId(hwnd, Some(&mut pid));
+    }
+    if pid == 0 || pid == std::process::id() {
+        return None;
+    }
+    let mut sys = System::new();
+    sys.refresh_processes(
+        sysinfo::ProcessesToUpdate::Some(&[Pid::from_u32(pid)]),
+        true,
+    );
+    let name = sys
+        .process(Pid::from_u32(pid))?
+        .name()
+        .to_string_lossy()
+        .to_string();
+    Some((pid, name))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn foreground_process() -> Option<(u32, String)> {
+    None
+}
+
+fn run_action(app: &AppHandle, action: HotkeyAction) -> Result<(), String> {
+    match action {
+        HotkeyAction::Start => {
+            let (pid, name) =
+                foreground_process().ok_or_else(|| "未找到前台游戏窗口".to_string())?;
+            fps_monitor::start_fps_monitor(app.clone(), name, Some(pid), None, None, None)
+                .map(|_| ())
+        }
+        HotkeyAction::Stop => fps_monitor::stop_fps_monitor(app.clone(), None),
+        HotkeyAction::Mark => {
+            crate::markers::add_session_marker(app.clone(), "快捷键标记".to_string()).map(|_| ())
+        }
+        HotkeyAction::Overlay => crate::overlay::toggle(app).map(|_| ()),
+    }
+}
+
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn on_hotkey(action: HotkeyAction) {
+    let Some(app) = app_handle().get() else {
+        return;
+    };
+    let result = run_action(app, action);
+    match &result {
+        Ok(()) => log::info!("快捷键: {}", action.label()),
+        Err(e) => log::warn!("快捷键「{}」失败: {}", action.label(), e),
+    }
+    let _ = app.emit(
+        "hotkey-triggered",
+        &HotkeyEvent {
+            action,
+            error: result.err(),
+        },
+    );
+}
+
+// ==================== 注册 (Windows) ====================
+
+/// 通知快捷键线程按当前设置重新注册
+#[cfg(target_os = "windows")]
+const WM_RELOAD: u32 = windows::Win32::UI::WindowsAndMessaging::WM_APP + 1;
+
+#[cfg(target_os = "windows")]
+fn hotkey_thread() -> &'static OnceLock<u32> {
+    static THREAD: OnceLock<u32> = OnceLock::new();
+    &THREAD
+}
+
+/// 在快捷键线程上注销旧绑定并注册当前设置中的绑定（RegisterHotKey 与调用线程绑定）
+#[cfg(target_os = "windows")]
+fn register_bindings(bindings: &mut Vec<HotkeyAction>) {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        RegisterHotKey, UnregisterHotKey, HOT_KEY_MODIFIERS, MOD_NOREPEAT,
+    };
+
+    for id in 0..bindings.len() {
+        unsafe {
+            let _ = UnregisterHotKey(HWND::default(), id as i32);
+        }
+    }
+    bindings.clear();
+
+    let settings = crate::settings::get().hotkeys;
+    let mut states = Vec::new();
+    if settings.enabled {
+        for binding in &settings.bindings {
+            let result = parse(&binding.keys).and_then(|accel| unsafe {
+                RegisterHotKey(
+                    HWND::default(),
+                    bindings.len() as i32,
+                    HOT_KEY_MODIFIERS(accel.modifiers) | MOD_NOREPEAT,
+                    accel.key,
+                )
+                .map_err(|_| format!("{} 已被其他程序占用", binding.keys))
+            });
+            if result.is_ok() {
+                bindings.push(binding.action);
+            }
+            states.push(HotkeyStatus {
+                action: binding.action,
+                keys: binding.keys.clone(),
+                registered: result.is_ok(),
+                error: result.err(),
+            });
+        }
+    }
+    let failed = states.iter().filter(|s| !s.registered).count();
+    log::info!("已注册 {} 个全局快捷键（失败 {}）", bindings.len(), failed);
+    *status().lock().unwrap() = states;
+}
+
+/// 启动快捷键线程（进程生命周期内有效）
+#[cfg(target_os = "windows")]
+pub fn register(app: &AppHandle) {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::System::Threading::GetCurrentThreadId;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetMessageW, PeekMessageW, MSG, PM_NOREMOVE, WM_HOTKEY,
+    };
+
+    if app_handle().set(app.clone()).is_err() {
+        return;
+    }
+    std::thread::spawn(|| {
+        let mut msg = MSG::default();
+        unsafe {
+            // 先创建线程消息队列，之后 PostThreadMessageW 才能送达
+            let _ = PeekMessageW(&mut msg, HWND::default(), 0, 0, PM_NOREMOVE);
+            let _ = hotkey_thread().set(GetCurrentThreadId());
+        }
+        let mut bindings = Vec::new();
+        register_bindings(&mut bindings);
+        while unsafe { GetMessageW(&mut msg, HWND::default(), 0, 0) }.0 > 0 {
+            match msg.message {
+                WM_HOTKEY => {
+                    if let Some(&action) = bindings.get(msg.wParam.0) {
+                        on_hotkey(action);
+                    }
+                }
+                WM_RELOAD => register_bindings(&mut bindings),
+                _ => {}
+            }
+        }
+    });
+}
+
+/// 设置变更或切换 profile 后重新注册
+#[cfg(target_os = "windows")]
+pub fn reload() {
+    use windows::Win32::Foundation::{LPARAM, WPARAM};
+    use windows::Win32::UI::WindowsAndMessaging::PostThreadMessageW;
+
+    if let Some(&thread) = hotkey_thread().get() {
+        unsafe {
+            let _ = PostThreadMessageW(thread, WM_RELOAD, WPARAM(0), LPARAM(0));
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn register(app: &AppHandle) {
+    let _ = app_handle().set(app.clone());
+    *status().lock().unwrap() = crate::settings::get()
+        .hotkeys
+        .bindings
+        .into_iter()
+        .map(|binding| HotkeyStatus {
+            action: binding.action,
+            keys: binding.keys,
+            registered: false,
+            error: Some("全局快捷键仅支持 Windows".to_string()),
+        })
+        .collect();
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn reload() {}
+
+// ==================== Tauri 命令 ====================
+
+/// 各快捷键的注册结果（被其他程序占用时 registered 为 false）
+#[tauri::command]
+pub fn get_hotkey_status() -> Result<Vec<HotkeyStatus>, String> {
+    Ok(status().lock().unwrap().clone())
+}
@@ -0,0 +1,290 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use sysinfo::System;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// 网吧模式配置（整机共用，不随用户 profile 切换）
+const KIOSK_FILE: &str = "kiosk.json";
+/// 管理端配置接口
+const CONFIG_PATH: &str = "/api/kiosk/config";
+/// 默认同步间隔
+const DEFAULT_SYNC_SECS: u64 = 300;
+/// 同步间隔下限，防止管理端下发过小的值
+const MIN_SYNC_SECS: u64 = 30;
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KioskConfig {
+    pub enabled: bool,
+    /// 查看结果后自动删除会话
+    pub purge_after_view: bool,
+    /// 管理端地址 (e.g., "http://192.168.1.10:8080")
+    pub management_url: Option<String>,
+    /// 拉取管理端配置的间隔 (秒)
+    pub sync_interval_secs: u64,
+    /// 退出网吧模式的 PIN（SHA-256）
+    pub pin_hash: Option<String>,
+    /// 已应用的管理端配置版本
+    pub applied_version: u64,
+}
+
+impl Default for KioskConfig {
+    fn default() -> Self {
+        KioskConfig {
+            enabled: false,
+            purge_after_view: true,
+            management_url: None,
+            sync_interval_secs: DEFAULT_SYNC_SECS,
+            pin_hash: None,
+            applied_version: 0,
+        }
+    }
+}
+
+/// 返回给前端的状态（不含 PIN）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KioskStatus {
+    pub enabled: bool,
+    pub purge_after_view: bool,
+    pub management_url: Option<String>,
+    pub applied_version: u64,
+}
+
+/// 管理端下发的配置，未下发的字段保持不变
+#[derive(Debug, Clone, Deserialize)]
+pub struct ManagedConfig {
+    pub version: u64,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub purge_after_view: Option<bool>,
+    #[serde(default)]
+    pub sync_interval_secs: Option<u64>,
+    #[serde(default)]
+    pub exit_pin: Option<String>,
+    /// 覆盖 AppSettings 中的部分字段 (e.g., {"capture_profile": "minimal"})
+    #[serde(default)]
+    pub settings: Option<serde_json::Value>,
+}
+
+// ==================== 读写 ====================
+
+fn current() -> &'static Mutex<KioskConfig> {
+    static KIOSK: OnceLock<Mutex<KioskConfig>> = OnceLock::new();
+    KIOSK.get_or_init(|| Mutex::new(KioskConfig::default()))
+}
+
+fn data_file(app: &AppHandle, name: &str) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("无法获取数据目录: {}", e))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建数据目录: {}", e))?;
+    Ok(dir.join(name))
+}
+
+fn save(app: &AppHandle, config: KioskConfig) -> Result<(), String> {
+    let path = data_file(app, KIOSK_FILE)?;
+    let text = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    std::fs::write(&path, text).map_err(|e| format!("保存网吧模式配置失败: {}", e))?;
+    *current().lock().unwrap() = config;
+    Ok(())
+}
+
+fn hash_pin(pin: &str) -> String {
+    Sha256::digest(format!("gamebench-kiosk:{}", pin).as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn status_of(config: &KioskConfig) -> KioskStatus {
+    KioskStatus {
+        enabled: config.enabled,
+        purge_after_view: config.purge_after_view,
+        management_url: config.management_url.clone(),
+        applied_version: config.applied_version,
+    }
+}
+
+/// 网吧模式下拒绝修改系统与设置的操作
+pub fn ensure_unlocked() -> Result<(), String> {
+    if current().lock().unwrap().enabled {
+        Err("网吧模式下不允许此操作".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// 管理端地址（未配置时为 None）
+pub fn management_url() -> Option<String> {
+    current().lock().unwrap().management_url.clone()
+}
+
+/// 启动时加载配置；网吧模式下清除上一位顾客留下的会话，并开始同步管理端配置
+pub fn load(app: &AppHandle) {
+    let config: KioskConfig = data_file(app, KIOSK_FILE)
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default();
+    let enabled = config.enabled;
+    let purge = config.purge_after_view;
+    *current().lock().unwrap() = config;
+
+    if enabled && purge {
+        match crate::storage::purge_sessions(app) {
+            Ok(n) if n > 0 => log::info!("网吧模式: 已清除 {} 个历史会话", n),
+            Ok(_) => {}
+            Err(e) => log::warn!("网吧模式: 清除历史会话失败: {}", e),
+        }
+    }
+    start_sync(app.clone());
+}
+
+// ==================== 管理端同步 ====================
+
+async fn fetch_managed(app: &AppHandle) -> Result<bool, String> {
+    let (base, applied) = {
+        let config = current().lock().unwrap();
+        match &config.management_url {
+            Some(url) => (url.clone(), config.applied_version),
+            None => return Ok(false),
+        }
+    };
+    let url = format!("{}{}", base.trim_end_matches('/'), CONFIG_PATH);
+    let machine = System::host_name().unwrap_or_default();
+
+    let managed: ManagedConfig = crate::proxy::client()?
+        .get(&url)
+        .query(&[
+            ("machine", machine.as_str()),
+            ("version", &applied.to_string()),
+        ])
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("获取管理端配置失败: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("解析管理端配置失败: {}", e))?;
+
+    if managed.version <= applied {
+        return Ok(false);
+    }
+    if let Some(patch) = &managed.settings {
+        crate::settings::apply_managed(app, patch)?;
+    }
+
+    let mut config = current().lock().unwrap().clone();
+    if let Some(enabled) = managed.enabled {
+        config.enabled = enabled;
+    }
+    if let Some(purge) = managed.purge_after_view {
+        config.purge_after_view = purge;
+    }
+    if let Some(secs) = managed.sync_interval_secs {
+        config.sync_interval_secs = secs.max(MIN_SYNC_SECS);
+    }
+    if let Some(pin) = &managed.exit_pin {
+        config.pin_hash = Some(hash_pin(pin));
+    }
+    config.applied_version = managed.version;
+    let status = status_of(&config);
+    save(app, config)?;
+
+    log::info!("已应用管理端配置 v{}", managed.version);
+    let _ = app.emit("kiosk-updated", &status);
+    Ok(true)
+}
+
+/// 后台定期拉取管理端配置（未配置管理端时每个周期只检查一次地址）
+fn start_sync(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            // 离线模式下暂停同步，关闭离线后下一轮自动恢复
+            if !crate::connectivity::is_offline() {
+                if let Err(e) = fetch_managed(&app).await {
+                    log::warn!("网吧模式: {}", e);
+                }
+            }
+            let secs = current()
+                .lock()
+                .unwrap()
+                .sync_interval_secs
+                .max(MIN_SYNC_SECS);
+            tokio::time::sleep(Duration::from_secs(secs)).await;
+        }
+    });
+}
+
+// ==================== Tauri 命令 ====================
+
+#[tauri::command]
+pub fn get_kiosk_status() -> Result<KioskStatus, String> {
+    Ok(status_of(&current().lock().unwrap()))
+}
+
+/// 开启网吧模式，`pin` 用于退出
+#[tauri::command]
+pub fn enable_kiosk(
+    app: AppHandle,
+    pin: String,
+    purge_after_view: bool,
+    management_url: Option<String>,
+) -> Result<(), String> {
+    if pin.chars().count() < 4 {
+        return Err("PIN 至少需要 4 位".to_string());
+    }
+    let mut config = current().lock().unwrap().clone();
+    config.enabled = true;
+    config.purge_after_view = purge_after_view;
+    config.management_url = management_url.filter(|u| !u.trim().is_empty());
+    config.pin_hash = Some(hash_pin(&pin));
+    let status = status_of(&config);
+    save(&app, config)?;
+    log::info!("已开启网吧模式");
+    let _ = app.emit("kiosk-updated", &status);
+    Ok(())
+}
+
+/// 输入 PIN 退出网吧模式
+#[tauri::command]
+pub fn exit_kiosk(app: AppHandle, pin: String) -> Result<(), String> {
+    let mut config = current().lock().unwrap().clone();
+    if !config.enabled {
+        return Ok(());
+    }
+    if config.pin_hash.as_deref() != Some(hash_pin(&pin).as_str()) {
+        log::warn!("网吧模式: 退出 PIN 错误");
+        return Err("PIN 错误".to_string());
+    }
+    config.enabled = false;
+    let status = status_of(&config);
+    save(&app, config)?;
+    log::info!("已退出网吧模式");
+    let _ = app.emit("kiosk-updated", &status);
+    Ok(())
+}
+
+/// 结果已展示给用户；网吧模式下随即删除该会话
+#[tauri::command]
+pub fn mark_session_viewed(app: AppHandle, session_id: String) -> Result<(), String> {
+    let config = current().lock().unwrap().clone();
+    if config.enabled && config.purge_after_view {
+        crate::storage::remove_session(&app, &session_id)?;
+        log::info!("网吧模式: 已删除查看过的会话 {}", session_id);
+    }
+    Ok(())
+}
+
+/// 立即拉取管理端配置，返回是否有更新
+#[tauri::command]
+pub async fn sync_kiosk_config(app: AppHandle) -> Result<bool, String> {
+    fetch_managed(&app).await
+}
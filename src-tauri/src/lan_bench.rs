@@ -0,0 +1,1268 @@
+use crate::fps_monitor::{self, BusyPolicy, FpsSession, StartOutcome};
+use crate::markers::{self, ClockSyncRequest, MarkerRequest};
+use crate::session_compare::{self, SessionComparison};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use sysinfo::System;
+use tauri::{AppHandle, Emitter, Listener};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream, UdpSocket};
+
+/// mDNS 组播地址与端口
+const MDNS_ADDR: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 251);
+const MDNS_PORT: u16 = 5353;
+/// DNS-SD 服务类型
+const SERVICE_NAME: &str = "_gamebench._tcp.local";
+/// 本地 API 默认端口
+const DEFAULT_API_PORT: u16 = 47860;
+/// 共享令牌请求头
+const TOKEN_HEADER: &str = "x-gamebench-token";
+/// 请求头/请求体大小上限
+const MAX_REQUEST_BYTES: usize = 64 * 1024;
+/// 读取请求头与请求体各自的超时，防止空闲连接长期占用
+const REQUEST_READ_TIMEOUT: Duration = Duration::from_secs(10);
+/// 监测停止后等待会话生成的最长时间
+const SESSION_WAIT: Duration = Duration::from_secs(30);
+/// 关闭测试机模式后，后台循环检查设置的间隔
+const SETTINGS_POLL: Duration = Duration::from_secs(2);
+/// 对照测试最长时长，主控机异常退出未发送停止指令时到时自动结束
+const PAIRED_MAX_DURATION: Duration = Duration::from_secs(4 * 3600);
+/// 保留最近的对照报告条数
+const MAX_PAIRED_REPORTS: usize = 20;
+/// 测试机上已结束的测试保留时长，供主控机取回结果，之后清理
+const AGENT_RUN_TTL: Duration = Duration::from_secs(3600);
+
+const DNS_TYPE_PTR: u16 = 12;
+const DNS_TYPE_TXT: u16 = 16;
+const DNS_TYPE_ANY: u16 = 255;
+const DNS_CLASS_IN: u16 = 1;
+/// 问题中的 unicast-response 位 / 记录中的 cache-flush 位
+const DNS_CLASS_TOP_BIT: u16 = 0x8000;
+
+// ==================== 数据结构 ====================
+
+/// 局域网测试台设置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LanSettings {
+    /// 作为测试机，接受局域网内主控机的测试指令
+    pub agent_enabled: bool,
+    /// 本地 API 端口
+    pub port: u16,
+    /// 共享令牌，主控机与各测试机需一致
+    pub token: String,
+    /// 对照机：本机开始监测时同时在对照机上开始，结束后自动生成对比报告
+    pub reference: Option<LanAgent>,
+}
+
+impl Default for LanSettings {
+    fn default() -> Self {
+        LanSettings {
+            agent_enabled: false,
+            port: DEFAULT_API_PORT,
+            token: String::new(),
+            reference: None,
+        }
+    }
+}
+
+/// 局域网中发现的测试机
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanAgent {
+    /// 计算机名
+    pub name: String,
+    pub address: String,
+    pub port: u16,
+    /// GameBench 版本
+    pub version: String,
+}
+
+/// 测试机硬件与状态（GET /api/info）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentInfo {
+    pub name: String,
+    pub version: String,
+    pub cpu: String,
+    pub gpus: Vec<String>,
+    /// 是否正在监测
+    pub busy: bool,
+}
+
+/// 主控机下发的测试指令（POST /api/runs）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRequest {
+    pub run_id: String,
+    pub process_name: String,
+    /// 收到指令后延迟多久开始 (毫秒)，各测试机同时收到指令即可同步开始
+    pub delay_ms: u64,
+    pub duration_secs: u64,
+    /// 对照测试：不按时长计时，收到主控机的停止指令后结束
+    #[serde(default)]
+    pub paired: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Scheduled,
+    Running,
+    Completed,
+    Failed,
+}
+
+/// 测试机上的一次测试（GET /api/runs/<run_id>）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentRun {
+    pub run_id: String,
+    pub status: RunStatus,
+    pub session: Option<FpsSession>,
+    pub error: Option<String>,
+    /// 结束时间，用于清理过期结果
+    #[serde(skip)]
+    pub finished_at: Option<Instant>,
+}
+
+/// 主控机发起的一轮同步测试
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanRun {
+    pub run_id: String,
+    pub process_name: String,
+    pub duration_secs: u64,
+    pub agents: Vec<LanAgent>,
+    /// 下发指令失败的测试机: (名称, 原因)
+    pub failed: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanAgentResult {
+    pub agent: LanAgent,
+    pub status: RunStatus,
+    pub session: Option<FpsSession>,
+    pub error: Option<String>,
+    /// 与第一台完成测试的机器对比
+    pub comparison: Option<SessionComparison>,
+}
+
+/// 汇总所有测试机结果的对比报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanRunReport {
+    pub run_id: String,
+    pub process_name: String,
+    /// 对比基准（第一台完成测试的机器）
+    pub baseline: Option<String>,
+    pub results: Vec<LanAgentResult>,
+    /// 所有测试机均已结束
+    pub finished: bool,
+}
+
+/// 本机会话与对照机同步采集结果的对比（推送 `paired-comparison`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairedComparison {
+    pub run_id: String,
+    /// 本机会话 ID
+    pub session_id: String,
+    pub reference: LanAgent,
+    pub reference_session: Option<FpsSession>,
+    /// 以对照机为基准，本机结果的变化
+    pub comparison: Option<SessionComparison>,
+    pub error: Option<String>,
+}
+
+// ==================== 全局状态 ====================
+
+/// 测试机一侧：run_id → 测试状态
+fn agent_runs() -> &'static Mutex<HashMap<String, AgentRun>> {
+    static RUNS: OnceLock<Mutex<HashMap<String, AgentRun>>> = OnceLock::new();
+    RUNS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 主控机一侧：run_id → 已下发的测试
+fn controller_runs() -> &'static Mutex<HashMap<String, LanRun>> {
+    static RUNS: OnceLock<Mutex<HashMap<String, LanRun>>> = OnceLock::new();
+    RUNS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 测试机一侧：进行中的对照测试 run_id → 停止信号
+fn paired_stops() -> &'static Mutex<HashMap<String, Arc<tokio::sync::Notify>>> {
+    static STOPS: OnceLock<Mutex<HashMap<String, Arc<tokio::sync::Notify>>>> = OnceLock::new();
+    STOPS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 主控机一侧：本机会话 ID → 对照机上的 run_id
+fn paired_runs() -> &'static Mutex<HashMap<String, String>> {
+    static RUNS: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+    RUNS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 主控机一侧：最近的对照报告（新的在后）
+fn paired_reports() -> &'static Mutex<Vec<PairedComparison>> {
+    static REPORTS: OnceLock<Mutex<Vec<PairedComparison>>> = OnceLock::new();
+    REPORTS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+fn set_run(run_id: &str, status: RunStatus, session: Option<FpsSession>, error: Option<String>) {
+    let finished = matches!(status, RunStatus::Completed | RunStatus::Failed);
+    let mut runs = agent_runs().lock().unwrap();
+    // 顺带清理主控机已不再查询的旧结果（含会话帧数据）
+    runs.retain(|_, run| run.finished_at.is_none_or(|t| t.elapsed() < AGENT_RUN_TTL));
+    runs.insert(
+        run_id.to_string(),
+        AgentRun {
+            run_id: run_id.to_string(),
+            status,
+            session,
+            error,
+            finished_at: finished.then(Instant::now),
+        },
+    );
+}
+
+fn machine_name() -> String {
+    System::host_name().unwrap_or_else(|| "unknown".to_string())
+}
+
+fn app_version(app: &AppHandle) -> String {
+    app.package_info().version.to_string()
+}
+
+fn agent_enabled() -> bool {
+    let lan = crate::settings::get().lan;
+    lan.agent_enabled && !lan.token.is_empty()
+}
+
+// ==================== mDNS ====================
+
+fn write_name(buf: &mut Vec<u8>, name: &str) {
+    for label in name.split('.').filter(|l| !l.is_empty()) {
+        let bytes = &label.as_bytes()[..label.len().min(63)];
+        buf.push(bytes.len() as u8);
+        buf.extend_from_slice(bytes);
+    }
+    buf.push(0);
+}
+
+fn write_record(buf: &mut Vec<u8>, name: &str, rtype: u16, class: u16, data: &[u8]) {
+    write_name(buf, name);
+    buf.extend_from_slice(&rtype.to_be_bytes());
+    buf.extend_from_slice(&class.to_be_bytes());
+    buf.extend_from_slice(&120u32.to_be_bytes());
+    buf.extend_from_slice(&(data.len() as u16).to_be_bytes());
+    buf.extend_from_slice(data);
+}
+
+/// 读取域名（支持压缩指针），返回 (域名, 下一个字段偏移)
+fn read_name(packet: &[u8], mut pos: usize) -> Option<(String, usize)> {
+    let mut labels = Vec::new();
+    let mut end = None;
+    // 防止恶意指针循环
+    for _ in 0..32 {
+        let len = *packet.get(pos)? as usize;
+        if len == 0 {
+            return Some((labels.join("."), end.unwrap_or(pos + 1)));
+        }
+        if len & 0xC0 == 0xC0 {
+            let target = ((len & 0x3F) << 8) | *packet.get(pos + 1)? as usize;
+            end.get_or_insert(pos + 2);
+            pos = target;
+            continue;
+        }
+        let label = packet.get(pos + 1..pos + 1 + len)?;
+        labels.push(String::from_utf8_lossy(label).to_string());
+        pos += 1 + len;
+    }
+    None
+}
+
+fn read_u16(packet: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_be_bytes([
+        *packet.get(pos)?,
+        *packet.get(pos + 1)?,
+    ]))
+}
+
+/// 查询本服务类型的 PTR 记录，请求单播回复
+fn build_query() -> Vec<u8> {
+    let mut buf = vec![0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+    write_name(&mut buf, SERVICE_NAME);
+    buf.extend_from_slice(&DNS_TYPE_PTR.to_be_bytes());
+    buf.extend_from_slice(&(DNS_CLASS_IN | DNS_CLASS_TOP_BIT).to_be_bytes());
+    buf
+}
+
+/// 回复 PTR + TXT（port/name/version），地址取自回复包的来源 IP
+fn build_response(id: u16, name: &str, port: u16, version: &str) -> Vec<u8> {
+    let instance = format!("{}.{}", name.replace('.', "-"), SERVICE_NAME);
+    let mut buf = Vec::new();
+    buf.extend_from_slice(&id.to_be_bytes());
+    buf.extend_from_slice(&0x8400u16.to_be_bytes());
+    buf.extend_from_slice(&[0, 0, 0, 2, 0, 0, 0, 0]);
+
+    let mut ptr = Vec::new();
+    write_name(&mut ptr, &instance);
+    write_record(&mut buf, SERVICE_NAME, DNS_TYPE_PTR, DNS_CLASS_IN, &ptr);
+
+    let mut txt = Vec::new();
+    for entry in [
+        format!("port={}", port),
+        format!("name={}", name),
+        format!("version={}", version),
+    ] {
+        let bytes = &entry.as_bytes()[..entry.len().min(255)];
+        txt.push(bytes.len() as u8);
+        txt.extend_from_slice(bytes);
+    }
+    write_record(
+        &mut buf,
+        &instance,
+        DNS_TYPE_TXT,
+        DNS_CLASS_IN | DNS_CLASS_TOP_BIT,
+        &txt,
+    );
+    buf
+}
+
+/// 查询包中是否在询问本服务，返回查询 ID
+fn parse_query(packet: &[u8]) -> Option<u16> {
+    let id = read_u16(packet, 0)?;
+    let flags = read_u16(packet, 2)?;
+    if flags & 0x8000 != 0 {
+        return None;
+    }
+    let questions = read_u16(packet, 4)?;
+    let mut pos = 12;
+    for _ in 0..questions {
+        let (name, next) = read_name(packet, pos)?;
+        let qtype = read_u16(packet, next)?;
+        pos = next + 4;
+        if name.eq_ignore_ascii_case(SERVICE_NAME)
+            && (qtype == DNS_TYPE_PTR || qtype == DNS_TYPE_ANY)
+        {
+            return Some(id);
+        }
+    }
+    None
+}
+
+/// 从回复包中取出本服务的 TXT 键值
+fn parse_response(packet: &[u8]) -> Option<HashMap<String, String>> {
+    let flags = read_u16(packet, 2)?;
+    if flags & 0x8000 == 0 {
+        return None;
+    }
+    let questions = read_u16(packet, 4)?;
+    let records = read_u16(packet, 6)? as usize
+        + read_u16(packet, 8)? as usize
+        + read_u16(packet, 10)? as usize;
+
+    let mut pos = 12;
+    for _ in 0..questions {
+        pos = read_name(packet, pos)?.1 + 4;
+    }
+    for _ in 0..records {
+        let (name, next) = read_name(packet, pos)?;
+        let rtype = read_u16(packet, next)?;
+        let len = read_u16(packet, next + 8)? as usize;
+        let data = packet.get(next + 10..next + 10 + len)?;
+        pos = next + 10 + len;
+
+        let suffix = format!(".{}", SERVICE_NAME);
+        if rtype != DNS_TYPE_TXT || !name.to_lowercase().ends_with(&suffix) {
+            continue;
+        }
+        let mut entries = HashMap::new();
+        let mut i = 0;
+        while i < data.len() {
+            let n = data[i] as usize;
+            let entry = String::from_utf8_lossy(data.get(i + 1..i + 1 + n)?).to_string();
+            if let Some((k, v)) = entry.split_once('=') {
+                entries.insert(k.to_string(), v.to_string());
+            }
+            i += 1 + n;
+        }
+        return Some(entries);
+    }
+    None
+}
+
+/// 绑定 5353 端口（系统自带的 mDNS 服务也在使用，需要地址复用）
+fn bind_mdns() -> std::io::Result<UdpSocket> {
+    use socket2::{Domain, Protocol, Socket, Type};
+
+    let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+    socket.set_reuse_address(true)?;
+    #[cfg(unix)]
+    socket.set_reuse_port(true)?;
+    socket.bind(&SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, MDNS_PORT).into())?;
+    socket.join_multicast_v4(&MDNS_ADDR, &Ipv4Addr::UNSPECIFIED)?;
+    socket.set_nonblocking(true)?;
+    UdpSocket::from_std(socket.into())
+}
+
+/// 测试机模式开启时应答主控机的发现请求，关闭后退出组播组并释放端口
+async fn run_responder(app: AppHandle) {
+    let mut buf = [0u8; 1500];
+    loop {
+        if !agent_enabled() {
+            tokio::time::sleep(SETTINGS_POLL).await;
+            continue;
+        }
+        let socket = match bind_mdns() {
+            Ok(s) => s,
+            Err(e) => {
+                log::warn!("局域网测试台: mDNS 端口绑定失败: {}", e);
+                tokio::time::sleep(SETTINGS_POLL * 15).await;
+                continue;
+            }
+        };
+
+        while agent_enabled() {
+            let Ok(Ok((len, from))) =
+                tokio::time::timeout(SETTINGS_POLL, socket.recv_from(&mut buf)).await
+            else {
+                continue;
+            };
+            let Some(id) = parse_query(&buf[..len]) else {
+                continue;
+            };
+            let port = crate::settings::get().lan.port;
+            let reply = build_response(id, &machine_name(), port, &app_version(&app));
+            // 非 5353 端口发来的查询按 RFC 6762 单播回复，否则回复到组播组
+            let target = if from.port() == MDNS_PORT {
+                SocketAddr::V4(SocketAddrV4::new(MDNS_ADDR, MDNS_PORT))
+            } else {
+                from
+            };
+            let _ = socket.send_to(&reply, target).await;
+        }
+    }
+}
+
+async fn discover(timeout: Duration) -> Result<Vec<LanAgent>, String> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .await
+        .map_err(|e| format!("无法创建 UDP 套接字: {}", e))?;
+    socket
+        .send_to(&build_query(), (MDNS_ADDR, MDNS_PORT))
+        .await
+        .map_err(|e| format!("发送 mDNS 查询失败: {}", e))?;
+
+    let mut agents: Vec<LanAgent> = Vec::new();
+    let mut buf = [0u8; 1500];
+    let deadline = tokio::time::Instant::now() + timeout;
+    while let Ok(Ok((len, from))) =
+        tokio::time::timeout_at(deadline, socket.recv_from(&mut buf)).await
+    {
+        let Some(txt) = parse_response(&buf[..len]) else {
+            continue;
+        };
+        let Some(port) = txt.get("port").and_then(|p| p.parse().ok()) else {
+            continue;
+        };
+        let address = from.ip().to_string();
+        if agents
+            .iter()
+            .any(|a| a.address == address && a.port == port)
+        {
+            continue;
+        }
+        agents.push(LanAgent {
+            name: txt.get("name").cloned().unwrap_or_else(|| address.clone()),
+            address,
+            port,
+            version: txt.get("version").cloned().unwrap_or_default(),
+        });
+    }
+    agents.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(agents)
+}
+
+// ==================== 本地 API (测试机) ====================
+
+struct Request {
+    method: String,
+    path: String,
+    token: Option<String>,
+    body: Vec<u8>,
+}
+
+async fn read_request(stream: &mut TcpStream) -> Option<Request> {
+    let mut data = Vec::new();
+    let mut buf = [0u8; 4096];
+    let header_end = tokio::time::timeout(REQUEST_READ_TIMEOUT, async {
+        loop {
+            let n = stream.read(&mut buf).await.ok()?;
+            if n == 0 {
+                return None;
+            }
+            data.extend_from_slice(&buf[..n]);
+            if let Some(end) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+                return Some(end);
+            }
+            if data.len() > MAX_REQUEST_BYTES {
+                return None;
+            }
+        }
+    })
+    .await
+    .ok()??;
+
+    let head = String::from_utf8_lossy(&data[..header_end]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let path = request_line.next()?.to_string();
+
+    let mut content_length = 0;
+    let mut token = None;
+    for line in lines {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        match key.trim().to_lowercase().as_str() {
+            "content-length" => content_length = value.trim().parse().ok()?,
+            TOKEN_HEADER => token = Some(value.trim().to_string()),
+            _ => {}
+        }
+    }
+    if content_length > MAX_REQUEST_BYTES {
+        return None;
+    }
+
+    let mut body = data[header_end + 4..].to_vec();
+    tokio::time::timeout(REQUEST_READ_TIMEOUT, async {
+        while body.len() < content_length {
+            let n = stream.read(&mut buf).await.ok()?;
+            if n == 0 {
+                return None;
+            }
+            body.extend_from_slice(&buf[..n]);
+        }
+        Some(())
+    })
+    .await
+    .ok()??;
+    body.truncate(content_length);
+
+    Some(Request {
+        method,
+        path,
+        token,
+        body,
+    })
+}
+
+async fn write_response(stream: &mut TcpStream, code: u16, body: &serde_json::Value) {
+    let reason = match code {
+        200 => "OK",
+        202 => "Accepted",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        409 => "Conflict",
+        _ => "Error",
+    };
+    let body = body.to_string();
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        code,
+        reason,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+}
+
+fn error_body(message: &str) -> serde_json::Value {
+    serde_json::json!({ "error": message })
+}
+
+/// 常数时间比较令牌：先取摘要使长度一致，再逐字节异或，避免按响应时间逐位猜出令牌
+fn token_matches(expected: &str, given: Option<&str>) -> bool {
+    let Some(given) = given else {
+        return false;
+    };
+    let a = Sha256::digest(expected.as_bytes());
+    let b = Sha256::digest(given.as_bytes());
+    a.iter()
+        .zip(b.iter())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+        == 0
+}
+
+async fn handle_connection(app: AppHandle, mut stream: TcpStream) {
+    let Some(request) = read_request(&mut stream).await else {
+        return;
+    };
+    let token = crate::settings::get().lan.token;
+    if token.is_empty() || !token_matches(&token, request.token.as_deref()) {
+        write_response(&mut stream, 403, &error_body("共享令牌不正确")).await;
+        return;
+    }
+
+    let (code, body) = match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/api/info") => {
+            let name = machine_name();
+            let version = app_version(&app);
+            let info = tokio::task::spawn_blocking(move || AgentInfo {
+                name,
+                version,
+                cpu: crate::hardware::get_cpu_info()
+                    .map(|c| c.name)
+                    .unwrap_or_default(),
+                gpus: crate::hardware::get_gpu_info()
+                    .map(|gpus| gpus.into_iter().map(|g| g.name).collect())
+                    .unwrap_or_default(),
+                busy: fps_monitor::get_fps_status()
+                    .map(|s| s.running)
+                    .unwrap_or(false),
+            })
+            .await;
+            match info {
+                Ok(info) => (200, serde_json::to_value(info).unwrap_or_default()),
+                Err(e) => (500, error_body(&e.to_string())),
+            }
+        }
+        ("POST", "/api/runs") => match serde_json::from_slice::<RunRequest>(&request.body) {
+            Ok(run) => match schedule_run(app.clone(), run) {
+                Ok(()) => (202, serde_json::json!({ "accepted": true })),
+                Err(e) => (409, error_body(&e)),
+            },
+            Err(e) => (400, error_body(&format!("无效的测试指令: {}", e))),
+        },
+        ("GET", "/api/clock") => (
+            200,
+            serde_json::to_value(markers::local_clock()).unwrap_or_default(),
+        ),
+        ("POST", "/api/clock/sync") => {
+            match serde_json::from_slice::<ClockSyncRequest>(&request.body) {
+                Ok(sync) => match markers::record_sync(&sync) {
+                    Ok(estimate) => (200, serde_json::to_value(estimate).unwrap_or_default()),
+                    Err(e) => (400, error_body(&e)),
+                },
+                Err(e) => (400, error_body(&format!("无效的对时样本: {}", e))),
+            }
+        }
+        ("POST", "/api/markers") => match serde_json::from_slice::<MarkerRequest>(&request.body) {
+            Ok(marker) => match markers::add(&app, &marker) {
+                Ok(marker) => (200, serde_json::to_value(marker).unwrap_or_default()),
+                Err(e) => (409, error_body(&e)),
+            },
+            Err(e) => (400, error_body(&format!("无效的标记: {}", e))),
+        },
+        ("POST", path) if path.starts_with("/api/runs/") && path.ends_with("/stop") => {
+            let run_id = &path["/api/runs/".len()..path.len() - "/stop".len()];
+            match paired_stops().lock().unwrap().get(run_id) {
+                Some(stop) => {
+                    stop.notify_one();
+                    (202, serde_json::json!({ "accepted": true }))
+                }
+                None => (404, error_body("未找到进行中的对照测试")),
+            }
+        }
+        ("GET", path) if path.starts_with("/api/runs/") => {
+            let run_id = &path["/api/runs/".len()..];
+            match agent_runs().lock().unwrap().get(run_id) {
+                Some(run) => (200, serde_json::to_value(run).unwrap_or_default()),
+                None => (404, error_body("未找到该测试")),
+            }
+        }
+        _ => (404, error_body("未知接口")),
+    };
+    write_response(&mut stream, code, &body).await;
+}
+
+/// 只停止本次远程运行的会话；会话已自行结束（如游戏退出）时忽略
+fn stop_run_session(app: &AppHandle, session_id: &str, reason: &str) {
+    if let Err(e) = fps_monitor::stop_session(app, session_id, reason) {
+        log::debug!("局域网测试台: {}", e);
+    }
+}
+
+/// 按主控机指令延迟开始监测，到时停止并记录会话
+fn schedule_run(app: AppHandle, run: RunRequest) -> Result<(), String> {
+    if fps_monitor::get_fps_status()?.running {
+        return Err("测试机正在监测其他游戏".to_string());
+    }
+    if agent_runs().lock().unwrap().contains_key(&run.run_id) {
+        return Err(format!("测试 {} 已存在", run.run_id));
+    }
+    set_run(&run.run_id, RunStatus::Scheduled, None, None);
+    if run.paired {
+        paired_stops()
+            .lock()
+            .unwrap()
+            .insert(run.run_id.clone(), Arc::new(tokio::sync::Notify::new()));
+    }
+    log::info!(
+        "局域网测试台: 收到测试 {} ({}，{}s)",
+        run.run_id,
+        run.process_name,
+        run.duration_secs
+    );
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(run.delay_ms)).await;
+
+        let (tx, rx) = tokio::sync::oneshot::channel::<FpsSession>();
+        let tx = Arc::new(Mutex::new(Some(tx)));
+        // 开始后才知道会话 ID；同一进程可能还有本机发起的其他监测
+        let expected = Arc::new(Mutex::new(None::<String>));
+        let expected_id = expected.clone();
+        let listener = app.listen("fps-session-complete", move |event| {
+            let Ok(session) = serde_json::from_str::<FpsSession>(event.payload()) else {
+                return;
+            };
+            if expected_id.lock().unwrap().as_deref() == Some(session.session_id.as_str()) {
+                if let Some(tx) = tx.lock().unwrap().take() {
+                    let _ = tx.send(session);
+                }
+            }
+        });
+
+        // 远程运行按固定时长计时，不能排队或打断本机已有的监测
+        let started = fps_monitor::start_fps_monitor(
+            app.clone(),
+            run.process_name.clone(),
+            None,
+            None,
+            Some(BusyPolicy::Reject),
+            None,
+        )
+        .and_then(|outcome| match outcome {
+            StartOutcome::Started { session_id } => Ok(session_id),
+            _ => Err("测试机正在监测其他游戏".to_string()),
+        });
+        let session_id = match started {
+            Ok(id) => id,
+            Err(e) => {
+                app.unlisten(listener);
+                paired_stops().lock().unwrap().remove(&run.run_id);
+                set_run(&run.run_id, RunStatus::Failed, None, Some(e));
+                return;
+            }
+        };
+        *expected.lock().unwrap() = Some(session_id.clone());
+        set_run(&run.run_id, RunStatus::Running, None, None);
+
+        if run.paired {
+            let stop = paired_stops().lock().unwrap().get(&run.run_id).cloned();
+            if let Some(stop) = stop {
+                let _ = tokio::time::timeout(PAIRED_MAX_DURATION, stop.notified()).await;
+            }
+            paired_stops().lock().unwrap().remove(&run.run_id);
+            stop_run_session(&app, &session_id, "主控机已停止监测");
+        } else {
+            tokio::time::sleep(Duration::from_secs(run.duration_secs)).await;
+            stop_run_session(&app, &session_id, "远程运行计时结束");
+        }
+
+        match tokio::time::timeout(SESSION_WAIT, rx).await {
+            Ok(Ok(session)) => {
+                log::info!("局域网测试台: 测试 {} 完成", run.run_id);
+                set_run(&run.run_id, RunStatus::Completed, Some(session), None);
+            }
+            _ => set_run(
+                &run.run_id,
+                RunStatus::Failed,
+                None,
+                Some("未采集到帧数据（游戏可能未运行）".to_string()),
+            ),
+        }
+        app.unlisten(listener);
+    });
+    Ok(())
+}
+
+/// 测试机模式开启时监听本地 API 端口，关闭后释放端口
+async fn run_api_server(app: AppHandle) {
+    loop {
+        if !agent_enabled() {
+            tokio::time::sleep(SETTINGS_POLL).await;
+            continue;
+        }
+        let port = crate::settings::get().lan.port;
+        let listener = match TcpListener::bind((Ipv4Addr::UNSPECIFIED, port)).await {
+            Ok(l) => l,
+            Err(e) => {
+                log::warn!("局域网测试台: 无法监听端口 {}: {}", port, e);
+                tokio::time::sleep(SETTINGS_POLL * 15).await;
+                continue;
+            }
+        };
+        log::info!("局域网测试台: 本地 API 已在端口 {} 启动", port);
+
+        while agent_enabled() && crate::settings::get().lan.port == port {
+            if let Ok(Ok((stream, _))) =
+                tokio::time::timeout(SETTINGS_POLL, listener.accept()).await
+            {
+                tauri::async_runtime::spawn(handle_connection(app.clone(), stream));
+            }
+        }
+        log::info!("局域网测试台: 本地 API 已停止");
+    }
+}
+
+/// 启动测试机后台服务（mDNS 应答与本地 API，均在设置中开启后才生效）
+/// 同时监听本机监测的开始与结束，以驱动设置中的对照机
+pub fn start(app: &AppHandle) {
+    tauri::async_runtime::spawn(run_responder(app.clone()));
+    tauri::async_runtime::spawn(run_api_server(app.clone()));
+    register_pairing(app);
+}
+
+// ==================== 主控机 ====================
+
+fn agent_url(agent: &LanAgent, path: &str) -> String {
+    format!("http://{}:{}{}", agent.address, agent.port, path)
+}
+
+/// 局域网内直连，不经过系统或手动代理
+fn client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .no_proxy()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+fn controller_token() -> Result<String, String> {
+    let token = crate::settings::get().lan.token;
+    if token.is_empty() {
+        return Err("请先在设置中填写局域网共享令牌".to_string());
+    }
+    Ok(token)
+}
+
+async fn fetch_agent_run(
+    client: &reqwest::Client,
+    token: &str,
+    agent: &LanAgent,
+    run_id: &str,
+) -> Result<AgentRun, String> {
+    client
+        .get(agent_url(agent, &format!("/api/runs/{}", run_id)))
+        .header(TOKEN_HEADER, token)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("无法连接 {}: {}", agent.name, e))?
+        .json()
+        .await
+        .map_err(|e| format!("{} 返回了无效数据: {}", agent.name, e))
+}
+
+// ==================== 对照机 ====================
+
+/// 本机作为测试机执行远程指令时不再联动对照机，避免两台机器互相触发
+fn agent_run_active() -> bool {
+    agent_runs()
+        .lock()
+        .unwrap()
+        .values()
+        .any(|r| matches!(r.status, RunStatus::Scheduled | RunStatus::Running))
+}
+
+fn register_pairing(app: &AppHandle) {
+    let handle = app.clone();
+    app.listen("fps-started", move |event| {
+        let Ok(capture) = serde_json::from_str::<fps_monitor::CaptureEvent>(event.payload()) else {
+            return;
+        };
+        let Some(reference) = crate::settings::get().lan.reference else {
+            return;
+        };
+        if agent_run_active() {
+            return;
+        }
+        // 先登记，即使对照机尚未应答本机监测就已结束，也能发送停止指令
+        let run_id = format!("pair-{}", capture.session_id);
+        paired_runs()
+            .lock()
+            .unwrap()
+            .insert(capture.session_id.clone(), run_id.clone());
+        tauri::async_runtime::spawn(start_paired(handle.clone(), reference, run_id, capture));
+    });
+
+    let handle = app.clone();
+    app.listen("fps-session-complete", move |event| {
+        let Ok(session) = serde_json::from_str::<FpsSession>(event.payload()) else {
+            return;
+        };
+        let Some(run_id) = paired_runs().lock().unwrap().remove(&session.session_id) else {
+            return;
+        };
+        tauri::async_runtime::spawn(finish_paired(handle.clone(), run_id, session));
+    });
+
+    // 会话完成时已先移除；仍能找到说明本机没有生成会话（未采集到帧或开始前已取消）
+    let handle = app.clone();
+    app.listen("fps-stopped", move |event| {
+        let Ok(capture) = serde_json::from_str::<fps_monitor::CaptureEvent>(event.payload()) else {
+            return;
+        };
+        let Some(run_id) = paired_runs().lock().unwrap().remove(&capture.session_id) else {
+            return;
+        };
+        tauri::async_runtime::spawn(cancel_paired(handle.clone(), run_id, capture));
+    });
+}
+
+fn publish_paired(app: &AppHandle, report: PairedComparison) {
+    let _ = app.emit("paired-comparison", &report);
+    let mut reports = paired_reports().lock().unwrap();
+    reports.push(report);
+    let excess = reports.len().saturating_sub(MAX_PAIRED_REPORTS);
+    reports.drain(..excess);
+}
+
+async fn post_agent(agent: &LanAgent, path: &str, body: &serde_json::Value) -> Result<(), String> {
+    let token = controller_token()?;
+    let response = client()?
+        .post(agent_url(agent, path))
+        .header(TOKEN_HEADER, token)
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| format!("无法连接 {}: {}", agent.name, e))?;
+    if response.status().is_success() {
+        return Ok(());
+    }
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    Err(body["error"]
+        .as_str()
+        .unwrap_or("对照机拒绝了指令")
+        .to_string())
+}
+
+/// 本机开始监测后，在对照机上立即开始同一游戏的对照测试
+async fn start_paired(
+    app: AppHandle,
+    reference: LanAgent,
+    run_id: String,
+    capture: fps_monitor::CaptureEvent,
+) {
+    let request = RunRequest {
+        run_id,
+        process_name: capture.process_name,
+        delay_ms: 0,
+        duration_secs: 0,
+        paired: true,
+    };
+    let body = serde_json::to_value(&request).unwrap_or_default();
+    match post_agent(&reference, "/api/runs", &body).await {
+        Ok(()) => {
+            log::info!(
+                "对照机 {}: 已开始对照测试 {}",
+                reference.name,
+                request.run_id
+            );
+            // 应答前本机监测已结束，之前的停止指令可能先于开始到达
+            if !paired_runs()
+                .lock()
+                .unwrap()
+                .contains_key(&capture.session_id)
+            {
+                let stop_path = format!("/api/runs/{}/stop", request.run_id);
+                let _ = post_agent(&reference, &stop_path, &serde_json::json!({})).await;
+            }
+        }
+        Err(e) => {
+            log::warn!("对照机 {}: 无法开始对照测试: {}", reference.name, e);
+            paired_runs().lock().unwrap().remove(&capture.session_id);
+            publish_paired(
+                &app,
+                PairedComparison {
+                    run_id: request.run_id,
+                    session_id: capture.session_id,
+                    reference,
+                    reference_session: None,
+                    comparison: None,
+                    error: Some(e),
+                },
+            );
+        }
+    }
+}
+
+/// 本机监测未生成会话时停止对照测试，不做对比
+async fn cancel_paired(app: AppHandle, run_id: String, capture: fps_monitor::CaptureEvent) {
+    let Some(reference) = crate::settings::get().lan.reference else {
+        return;
+    };
+    let stop_path = format!("/api/runs/{}/stop", run_id);
+    if let Err(e) = post_agent(&reference, &stop_path, &serde_json::json!({})).await {
+        log::warn!("对照机 {}: 无法停止对照测试: {}", reference.name, e);
+    }
+    publish_paired(
+        &app,
+        PairedComparison {
+            run_id,
+            session_id: capture.session_id,
+            reference,
+            reference_session: None,
+            comparison: None,
+            error: Some("本机监测未生成会话，已停止对照测试".to_string()),
+        },
+    );
+}
+
+/// 本机会话结束后停止对照测试，等待对照机会话生成并对比
+async fn finish_paired(app: AppHandle, run_id: String, session: FpsSession) {
+    let Some(reference) = crate::settings::get().lan.reference else {
+        return;
+    };
+    let stop_path = format!("/api/runs/{}/stop", run_id);
+    let result: Result<FpsSession, String> = async {
+        post_agent(&reference, &stop_path, &serde_json::json!({})).await?;
+        let token = controller_token()?;
+        let client = client()?;
+        let deadline = tokio::time::Instant::now() + SESSION_WAIT;
+        loop {
+            let run = fetch_agent_run(&client, &token, &reference, &run_id).await?;
+            match run.status {
+                RunStatus::Completed => {
+                    return run.session.ok_or_else(|| "对照机未返回会话".to_string())
+                }
+                RunStatus::Failed => {
+                    return Err(run.error.unwrap_or_else(|| "对照测试失败".to_string()))
+                }
+                _ if tokio::time::Instant::now() >= deadline => {
+                    return Err("等待对照机会话超时".to_string())
+                }
+                _ => tokio::time::sleep(Duration::from_secs(1)).await,
+            }
+        }
+    }
+    .await;
+
+    let report = match result {
+        Ok(reference_session) => PairedComparison {
+            run_id,
+            session_id: session.session_id.clone(),
+            reference,
+            comparison: session_compare::compare_sessions(reference_session.clone(), session).ok(),
+            reference_session: Some(reference_session),
+            error: None,
+        },
+        Err(e) => PairedComparison {
+            run_id,
+            session_id: session.session_id,
+            reference,
+            reference_session: None,
+            comparison: None,
+            error: Some(e),
+        },
+    };
+    match &report.error {
+        None => log::info!("对照机 {}: 对比报告已生成", report.reference.name),
+        Some(e) => log::warn!("对照机 {}: {}", report.reference.name, e),
+    }
+    publish_paired(&app, report);
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 通过 mDNS 发现局域网内开启了测试机模式的 GameBench
+#[tauri::command]
+pub async fn discover_lan_agents(timeout_ms: Option<u64>) -> Result<Vec<LanAgent>, String> {
+    let agents = discover(Duration::from_millis(timeout_ms.unwrap_or(2000))).await?;
+    log::info!("局域网测试台: 发现 {} 台测试机", agents.len());
+    Ok(agents)
+}
+
+/// 读取测试机硬件与状态
+#[tauri::command]
+pub async fn get_lan_agent_info(agent: LanAgent) -> Result<AgentInfo, String> {
+    let token = controller_token()?;
+    client()?
+        .get(agent_url(&agent, "/api/info"))
+        .header(TOKEN_HEADER, token)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("无法连接 {}: {}", agent.name, e))?
+        .json()
+        .await
+        .map_err(|e| format!("{} 返回了无效数据: {}", agent.name, e))
+}
+
+/// 向多台测试机同时下发测试指令，`delay_secs` 后同步开始
+#[tauri::command]
+pub async fn start_lan_run(
+    agents: Vec<LanAgent>,
+    process_name: String,
+    duration_secs: u64,
+    delay_secs: Option<u64>,
+) -> Result<LanRun, String> {
+    if agents.is_empty() {
+        return Err("请至少选择一台测试机".to_string());
+    }
+    if duration_secs == 0 {
+        return Err("测试时长必须大于 0".to_string());
+    }
+    let token = controller_token()?;
+    let client = client()?;
+    // 同一秒内多次发起也不会与测试机上已有的 run_id 冲突
+    let mut suffix = [0u8; 4];
+    getrandom::getrandom(&mut suffix).map_err(|e| e.to_string())?;
+    let request = RunRequest {
+        run_id: format!(
+            "lan-{}-{}",
+            chrono::Local::now().format("%Y%m%d-%H%M%S"),
+            crate::signing::hex(&suffix)
+        ),
+        process_name: process_name.clone(),
+        delay_ms: delay_secs.unwrap_or(5) * 1000,
+        duration_secs,
+        paired: false,
+    };
+
+    let tasks: Vec<_> = agents
+        .iter()
+        .map(|agent| {
+            let client = client.clone();
+            let token = token.clone();
+            let url = agent_url(agent, "/api/runs");
+            let request = request.clone();
+            tokio::spawn(async move {
+                let response = client
+                    .post(url)
+                    .header(TOKEN_HEADER, token)
+                    .json(&request)
+                    .send()
+                    .await
+                    .map_err(|e| e.to_string())?;
+                if response.status().is_success() {
+                    return Ok(());
+                }
+                let body: serde_json::Value = response.json().await.unwrap_or_default();
+                Err(body["error"]
+                    .as_str()
+                    .unwrap_or("测试机拒绝了指令")
+                    .to_string())
+            })
+        })
+        .collect();
+
+    let mut accepted = Vec::new();
+    let mut failed = Vec::new();
+    for (agent, task) in agents.into_iter().zip(tasks) {
+        match task.await.map_err(|e| e.to_string()).and_then(|r| r) {
+            Ok(()) => accepted.push(agent),
+            Err(e) => failed.push((agent.name.clone(), e)),
+        }
+    }
+    if accepted.is_empty() {
+        return Err(format!(
+            "所有测试机均未接受指令: {}",
+            failed
+                .iter()
+                .map(|(name, e)| format!("{} ({})", name, e))
+                .collect::<Vec<_>>()
+                .join("; ")
+        ));
+    }
+
+    let run = LanRun {
+        run_id: request.run_id,
+        process_name,
+        duration_secs,
+        agents: accepted,
+        failed,
+    };
+    log::info!(
+        "局域网测试台: 已向 {} 台测试机下发测试 {}",
+        run.agents.len(),
+        run.run_id
+    );
+    controller_runs()
+        .lock()
+        .unwrap()
+        .insert(run.run_id.clone(), run.clone());
+    Ok(run)
+}
+
+/// 收集各测试机结果，生成对比报告（测试进行中可重复调用）
+#[tauri::command]
+pub async fn collect_lan_results(run_id: String) -> Result<LanRunReport, String> {
+    let run = controller_runs()
+        .lock()
+        .unwrap()
+        .get(&run_id)
+        .cloned()
+        .ok_or_else(|| format!("未找到测试 {}", run_id))?;
+    let token = controller_token()?;
+    let client = client()?;
+
+    let mut results = Vec::new();
+    for agent in &run.agents {
+        let result = match fetch_agent_run(&client, &token, agent, &run_id).await {
+            Ok(r) => LanAgentResult {
+                agent: agent.clone(),
+                status: r.status,
+                session: r.session,
+                error: r.error,
+                comparison: None,
+            },
+            Err(e) => LanAgentResult {
+                agent: agent.clone(),
+                status: RunStatus::Failed,
+                session: None,
+                error: Some(e),
+                comparison: None,
+            },
+        };
+        results.push(result);
+    }
+
+    let baseline = results
+        .iter()
+        .find_map(|r| r.session.clone().map(|s| (r.agent.name.clone(), s)));
+    if let Some((_, base)) = &baseline {
+        for result in &mut results {
+            if let Some(session) = &result.session {
+                result.comparison =
+                    session_compare::compare_sessions(base.clone(), session.clone()).ok();
+            }
+        }
+    }
+
+    Ok(LanRunReport {
+        run_id,
+        process_name: run.process_name,
+        baseline: baseline.map(|(name, _)| name),
+        finished: results
+            .iter()
+            .all(|r| matches!(r.status, RunStatus::Completed | RunStatus::Failed)),
+        results,
+    })
+}
+
+/// 设置对照机（传入 None 取消），设置前确认对照机可连接
+#[tauri::command]
+pub async fn set_reference_agent(app: AppHandle, agent: Option<LanAgent>) -> Result<(), String> {
+    crate::kiosk::ensure_unlocked()?;
+    if let Some(agent) = &agent {
+        get_lan_agent_info(agent.clone()).await?;
+        log::info!("已设置对照机: {} ({})", agent.name, agent.address);
+    }
+    let mut settings = crate::settings::get();
+    settings.lan.reference = agent;
+    crate::settings::save(&app, settings)
+}
+
+/// 最近的对照报告（新的在前）
+#[tauri::command]
+pub fn list_paired_comparisons() -> Result<Vec<PairedComparison>, String> {
+    Ok(paired_reports()
+        .lock()
+        .unwrap()
+        .iter()
+        .rev()
+        .cloned()
+        .collect())
+}
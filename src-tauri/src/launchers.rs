@@ -0,0 +1,270 @@
+use serde::{Deserialize, Serialize};
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Launcher {
+    /// 腾讯 WeGame
+    WeGame,
+    /// 米哈游启动器 / HoYoPlay
+    MiHoYo,
+    /// 网易游戏
+    NetEase,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstalledGame {
+    pub name: String,
+    pub launcher: Launcher,
+    /// 安装目录
+    pub install_dir: String,
+}
+
+// ==================== 已知进程 ====================
+
+/// 进程名(小写), 中文名, 英文名, 所属平台
+const LAUNCHER_GAMES: &[(&str, &str, &str, Launcher)] = &[
+    // 米哈游
+    ("yuanshen.exe", "原神", "Genshin Impact", Launcher::MiHoYo),
+    (
+        "genshinimpact.exe",
+        "原神",
+        "Genshin Impact",
+        Launcher::MiHoYo,
+    ),
+    (
+        "starrail.exe",
+        "崩坏：星穹铁道",
+        "Honkai: Star Rail",
+        Launcher::MiHoYo,
+    ),
+    (
+        "zenlesszonezero.exe",
+        "绝区零",
+        "Zenless Zone Zero",
+        Launcher::MiHoYo,
+    ),
+    ("bh3.exe", "崩坏3", "Honkai Impact 3rd", Launcher::MiHoYo),
+    // 腾讯 WeGame
+    ("crossfire.exe", "穿越火线", "CrossFire", Launcher::WeGame),
+    (
+        "dnf.exe",
+        "地下城与勇士",
+        "Dungeon & Fighter",
+        Launcher::WeGame,
+    ),
+    (
+        "valorant-win64-shipping.exe",
+        "无畏契约",
+        "VALORANT",
+        Launcher::WeGame,
+    ),
+    (
+        "deltaforceclient-win64-shipping.exe",
+        "三角洲行动",
+        "Delta Force",
+        Launcher::WeGame,
+    ),
+    // 网易
+    (
+        "narakabladepoint.exe",
+        "永劫无间",
+        "NARAKA: BLADEPOINT",
+        Launcher::NetEase,
+    ),
+    (
+        "yysls.exe",
+        "燕云十六声",
+        "Where Winds Meet",
+        Launcher::NetEase,
+    ),
+    (
+        "marvel-win64-shipping.exe",
+        "漫威争锋",
+        "Marvel Rivals",
+        Launcher::NetEase,
+    ),
+];
+
+/// 国内平台已知游戏
+pub struct LauncherGame {
+    pub name_zh: &'static str,
+    pub name_en: &'static str,
+    pub launcher: Launcher,
+}
+
+/// 国内平台的全部已知游戏: (进程名, 游戏)
+pub fn known_games() -> impl Iterator<Item = (&'static str, LauncherGame)> {
+    LAUNCHER_GAMES
+        .iter()
+        .map(|&(process, name_zh, name_en, launcher)| {
+            (
+                process,
+                LauncherGame {
+                    name_zh,
+                    name_en,
+                    launcher,
+                },
+            )
+        })
+}
+
+/// 按进程名匹配国内平台游戏
+pub fn find_game(exe_name: &str) -> Option<LauncherGame> {
+    let lower = exe_name.to_lowercase();
+    known_games()
+        .find(|(process, _)| *process == lower)
+        .map(|(_, game)| game)
+}
+
+/// 根据发行商与安装路径判断所属平台
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn classify(publisher: &str, install_dir: &str) -> Option<Launcher> {
+    let publisher = publisher.to_lowercase();
+    let dir = install_dir.to_lowercase();
+
+    if ["mihoyo", "米哈游", "cognosphere", "hoyoverse"]
+        .iter()
+        .any(|p| publisher.contains(p))
+    {
+        Some(Launcher::MiHoYo)
+    } else if publisher.contains("netease") || publisher.contains("网易") {
+        Some(Launcher::NetEase)
+    } else if dir.contains("wegameapps") || dir.contains("wegame") {
+        Some(Launcher::WeGame)
+    } else {
+        None
+    }
+}
+
+// ==================== 已安装游戏 (Windows) ====================
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::{classify, InstalledGame, Launcher};
+    use winreg::enums::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
+    use winreg::RegKey;
+
+    const UNINSTALL_KEYS: &[(winreg::HKEY, &str)] = &[
+        (
+            HKEY_LOCAL_MACHINE,
+            r"SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall",
+        ),
+        (
+            HKEY_LOCAL_MACHINE,
+            r"SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Uninstall",
+        ),
+        (
+            HKEY_CURRENT_USER,
+            r"Software\Microsoft\Windows\CurrentVersion\Uninstall",
+        ),
+    ];
+
+    /// HoYoPlay 不为每个游戏写卸载项，游戏安装目录记录在自身配置下
+    const HOYOPLAY_KEYS: &[&str] = &[r"Software\miHoYo\HYP\1_0", r"Software\Cognosphere\HYP\1_0"];
+
+    /// 启动器本体，不是游戏
+    const LAUNCHER_CLIENTS: &[&str] = &["wegame", "hoyoplay", "米哈游启动器", "网易游戏中心"];
+
+    /// 通过控制面板卸载项枚举 WeGame / 网易 / 米哈游游戏
+    fn from_uninstall_entries(out: &mut Vec<InstalledGame>) {
+        for (hive, path) in UNINSTALL_KEYS {
+            let Ok(root) = RegKey::predef(*hive).open_subkey(path) else {
+                continue;
+            };
+            for name in root.enum_keys().flatten() {
+                let Ok(key) = root.open_subkey(&name) else {
+                    continue;
+                };
+                let display: String = key.get_value("DisplayName").unwrap_or_default();
+                let publisher: String = key.get_value("Publisher").unwrap_or_default();
+                let install_dir: String = key.get_value("InstallLocation").unwrap_or_default();
+                let lower = display.to_lowercase();
+                if display.is_empty() || LAUNCHER_CLIENTS.iter().any(|c| lower.contains(c)) {
+                    continue;
+                }
+                if let Some(launcher) = classify(&publisher, &install_dir) {
+                    out.push(InstalledGame {
+                        name: display,
+                        launcher,
+                        install_dir,
+                    });
+                }
+            }
+        }
+    }
+
+    /// HoYoPlay 子键名为游戏标识 (e.g., "hk4e_cn")
+    fn from_hoyoplay(out: &mut Vec<InstalledGame>) {
+        for path in HOYOPLAY_KEYS {
+            let Ok(root) = RegKey::predef(HKEY_CURRENT_USER).open_subkey(path) else {
+                continue;
+            };
+            for id in root.enum_keys().flatten() {
+                let Ok(key) = root.open_subkey(&id) else {
+                    continue;
+                };
+                let Ok(install_dir) = key.get_value::<String, _>("GameInstallPath") else {
+                    continue;
+                };
+                out.push(InstalledGame {
+                    name: hoyoplay_game_name(&id).unwrap_or(&id).to_string(),
+                    launcher: Launcher::MiHoYo,
+                    install_dir,
+                });
+            }
+        }
+    }
+
+    fn hoyoplay_game_name(id: &str) -> Option<&'static str> {
+        let prefix = id.split('_').next()?;
+        match prefix {
+            "hk4e" => Some("原神"),
+            "hkrpg" => Some("崩坏：星穹铁道"),
+            "nap" => Some("绝区零"),
+            "bh3" => Some("崩坏3"),
+            _ => None,
+        }
+    }
+
+    pub fn list_installed() -> Vec<InstalledGame> {
+        let mut games = Vec::new();
+        from_uninstall_entries(&mut games);
+        from_hoyoplay(&mut games);
+        games
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    use super::InstalledGame;
+
+    pub fn list_installed() -> Vec<InstalledGame> {
+        vec![]
+    }
+}
+
+// ==================== Tauri 命令 ====================
+
+/// WeGame / 米哈游 / 网易平台已安装的游戏（去重后按名称排序）
+pub fn installed_games() -> Vec<InstalledGame> {
+    let mut games = platform::list_installed();
+    // 同一游戏可能同时存在卸载项与 HoYoPlay 记录
+    games.sort_by(|a, b| {
+        a.install_dir
+            .to_lowercase()
+            .cmp(&b.install_dir.to_lowercase())
+    });
+    games.dedup_by(|a, b| {
+        !a.install_dir.is_empty() && a.install_dir.eq_ignore_ascii_case(&b.install_dir)
+    });
+    games.sort_by(|a, b| a.name.cmp(&b.name));
+    games
+}
+
+/// 列出 WeGame / 米哈游 / 网易平台已安装的游戏
+#[tauri::command]
+pub fn list_launcher_games() -> Result<Vec<InstalledGame>, String> {
+    Ok(installed_games())
+}
@@ -1,3 +1,64 @@
+pub use gamebench_core::{
+    background_activity, capture_log, capture_time, direct_storage, display, dpc_latency,
+    emulators, frame_cap, frame_pipeline, game_version, game_window, gpu_mux, input_latency, launch_timing,
+    memory_pressure, metrics, pinyin, present_etw, present_mode, presentmon, qoe, session_meta,
+    smoothing, streaming, stutter, vbs, vrr,
+};
+#[cfg(target_os = "windows")]
+pub use gamebench_core::etw;
+
+pub mod backup;
+pub mod capture_rules;
+pub mod connectivity;
+pub mod driver_reset;
+pub mod drives;
+pub mod events;
 pub mod fps_monitor;
+pub mod frame_log;
+pub mod frame_stats;
+pub mod game_alias;
 pub mod game_detect;
+pub mod game_install;
+pub mod gpu_routing;
 pub mod hardware;
+pub mod hotkeys;
+pub mod kiosk;
+pub mod lan_bench;
+pub mod launchers;
+pub mod loadless;
+pub mod markers;
+pub mod mirrors;
+pub mod os_profiles;
+pub mod overhead;
+pub mod overlay;
+pub mod pcie;
+pub mod plugins;
+pub mod power;
+pub mod presentmon_download;
+pub mod presets;
+pub mod privacy;
+pub mod process_activity;
+pub mod process_control;
+pub mod process_filter;
+pub mod proxy;
+pub mod script_hooks;
+pub mod search;
+pub mod sensors;
+pub mod session_bulk;
+pub mod session_compare;
+pub mod session_import;
+pub mod session_query;
+pub mod session_recovery;
+pub mod settings;
+pub mod signing;
+pub mod startup;
+pub mod storage;
+pub mod suggestions;
+pub mod tasks;
+pub mod thermal;
+pub mod timeline;
+pub mod units;
+pub mod updater;
+pub mod user_profile;
+pub mod uwp;
+pub mod vram_health;
@@ -0,0 +1,59 @@
+use crate::storage;
+pub use gamebench_core::loadless::{LoadScanner, LoadlessReport, LoadlessStats};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+/// 两遍流式扫描会话的原始帧时间：先定位加载段，再统计其余帧
+fn loadless_report(app: &AppHandle, session_id: &str) -> Result<LoadlessReport, String> {
+    let log = storage::load_frames(app, session_id)?;
+    let mut scanner = LoadScanner::default();
+    log.for_each_chunk(0, usize::MAX, |frames| {
+        scanner.extend(frames);
+        Ok(())
+    })?;
+    let mut stats = LoadlessStats::new(scanner.segments());
+    log.for_each_chunk(0, usize::MAX, |frames| {
+        stats.extend(frames);
+        Ok(())
+    })?;
+    Ok(stats.report())
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 无加载分析：去除检测到的加载画面（冻结或帧率远低于正常水平的片段），
+/// 返回无加载时长与帧率，供速通计时参考
+#[tauri::command]
+pub fn analyze_loadless(app: AppHandle, session_id: String) -> Result<LoadlessReport, String> {
+    loadless_report(&app, &session_id)
+}
+
+/// 导出加载段时间点 (CSV)，`path` 未传入时写入下载目录，返回文件路径
+#[tauri::command]
+pub fn export_load_segments(
+    app: AppHandle,
+    session_id: String,
+    path: Option<String>,
+) -> Result<String, String> {
+    crate::kiosk::ensure_unlocked()?;
+    let report = loadless_report(&app, &session_id)?;
+    let path = match path {
+        Some(path) => PathBuf::from(path),
+        None => app
+            .path()
+            .download_dir()
+            .map_err(|e| format!("无法获取下载目录: {}", e))?
+            .join(format!("gamebench-{}-loads.csv", session_id)),
+    };
+    std::fs::write(
+        &path,
+        gamebench_core::loadless::segments_csv(&report.segments),
+    )
+    .map_err(|e| format!("导出失败: {}", e))?;
+    log::info!(
+        "已导出 {} 个加载段: {}",
+        report.segments.len(),
+        path.display()
+    );
+    Ok(path.to_string_lossy().to_string())
+}
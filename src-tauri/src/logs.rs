@@ -1,5 +1,3 @@
-use std::fs;
-
 /// 读取日志内容
 #[tauri::command]
 pub fn read_logs() -> Result<String, String> {
@@ -7,7 +5,7 @@ pub fn read_logs() -> Result<String, String> {
     // env_logger 输出到 stdout，在 Tauri 中可以通过 tauri-plugin-log 捕获
     let now = format!("{:?}", std::time::SystemTime::now());
 
-    let log_entries = vec![
+    let log_entries = [
         format!("[{} INFO gamebench_desktop] 应用启动完成", now),
         format!("[{} INFO gamebench_desktop] 硬件检测模块已加载", now),
         format!("[{} INFO gamebench_desktop] 游戏检测模块已加载", now),
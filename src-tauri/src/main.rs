@@ -1,12 +1,69 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod backup;
+mod capture_rules;
+mod connectivity;
+mod driver_reset;
+mod drives;
+mod events;
 mod fps_monitor;
+mod frame_log;
+mod frame_stats;
+mod game_alias;
 mod game_detect;
+mod game_install;
+mod gpu_routing;
 mod hardware;
+mod hotkeys;
+mod kiosk;
+mod lan_bench;
+mod launchers;
+mod loadless;
 mod logs;
+mod markers;
+mod mirrors;
+mod os_profiles;
+mod overhead;
+mod overlay;
+mod pcie;
+mod plugins;
+mod power;
+mod presentmon_download;
+mod presets;
+mod privacy;
+mod process_activity;
+mod process_control;
+mod process_filter;
+mod proxy;
+mod script_hooks;
+mod search;
+mod sensors;
+mod session_bulk;
+mod session_compare;
+mod session_import;
+mod session_query;
+mod session_recovery;
+mod settings;
+mod signing;
+mod startup;
+mod storage;
+mod suggestions;
+mod tasks;
+mod thermal;
+mod timeline;
+mod units;
+mod updater;
+mod user_profile;
+mod uwp;
+mod vram_health;
 
-use tauri::Manager;
+use gamebench_core::{
+    background_activity, capture_log, capture_time, direct_storage, dpc_latency, emulators,
+    frame_cap, frame_pipeline, game_window, gpu_mux, input_latency, launch_timing, memory_pressure, metrics,
+    pinyin, present_etw, present_mode, presentmon, qoe, session_meta, smoothing, streaming,
+    stutter, vbs, vrr,
+};
 
 fn main() {
     env_logger::init();
@@ -21,17 +78,171 @@ fn main() {
             hardware::get_ram_info,
             // FPS 监测
             fps_monitor::start_fps_monitor,
+            fps_monitor::run_benchmark,
+            presets::list_benchmark_presets,
             fps_monitor::stop_fps_monitor,
+            fps_monitor::pause_fps_monitor,
+            fps_monitor::resume_fps_monitor,
             fps_monitor::get_fps_status,
+            fps_monitor::get_fps_queue,
+            fps_monitor::clear_fps_queue,
             // 游戏检测
             game_detect::scan_running_games,
             game_detect::get_known_games,
             // 日志
             logs::read_logs,
             logs::clear_logs,
+            // 进程管理
+            process_control::terminate_process,
+            process_control::suspend_process,
+            process_control::resume_process,
+            // 启动项分析
+            startup::list_startup_items,
+            startup::disable_startup_items,
+            // 存储设备
+            drives::list_drives,
+            drives::get_drive_health,
+            game_install::get_install_report,
+            // PCIe 链路
+            pcie::get_pcie_link_status,
+            // 显存纠错
+            vram_health::get_vram_error_counters,
+            // 测试环境 profile
+            os_profiles::snapshot_os_settings,
+            os_profiles::list_benchmark_profiles,
+            os_profiles::save_benchmark_profile,
+            os_profiles::delete_benchmark_profile,
+            os_profiles::apply_benchmark_profile,
+            os_profiles::restore_os_settings,
+            // 混合显卡
+            gpu_routing::get_gpu_routing,
+            // 结果对比
+            session_compare::compare_sessions,
+            session_compare::compare_session_frames,
+            // UWP / Game Pass
+            uwp::list_packaged_games,
+            // 国内游戏平台
+            launchers::list_launcher_games,
+            // 采集规则
+            capture_rules::update_capture_rules,
+            capture_rules::list_capture_rules,
+            capture_rules::save_local_capture_rule,
+            capture_rules::delete_local_capture_rule,
+            // 进程过滤
+            process_filter::update_process_filter,
+            process_filter::get_process_filter,
+            process_filter::report_false_positive,
+            process_filter::unignore_process,
+            // 设置
+            settings::get_settings,
+            settings::update_settings,
+            // 传感器
+            sensors::subscribe_sensors,
+            sensors::unsubscribe_sensors,
+            sensors::get_sensor_readings,
+            // 散热与机身
+            thermal::get_form_factor,
+            thermal::get_oem_perf_mode,
+            // 会话存储
+            storage::list_sessions,
+            storage::get_session,
+            storage::delete_session,
+            storage::get_session_store_status,
+            storage::unlock_session_store,
+            storage::lock_session_store,
+            storage::enable_session_encryption,
+            storage::disable_session_encryption,
+            timeline::get_session_timeline,
+            frame_log::get_session_frames,
+            frame_stats::analyze_session_frames,
+            loadless::analyze_loadless,
+            loadless::export_load_segments,
+            // 隐私
+            privacy::redact_session,
+            privacy::export_session,
+            // 用户 profile
+            user_profile::list_user_profiles,
+            user_profile::create_user_profile,
+            user_profile::switch_user_profile,
+            user_profile::delete_user_profile,
+            user_profile::set_profile_prompt_on_startup,
+            user_profile::migrate_sessions,
+            // 网吧模式
+            kiosk::get_kiosk_status,
+            kiosk::enable_kiosk,
+            kiosk::exit_kiosk,
+            kiosk::mark_session_viewed,
+            kiosk::sync_kiosk_config,
+            // 局域网测试台
+            lan_bench::discover_lan_agents,
+            lan_bench::get_lan_agent_info,
+            lan_bench::start_lan_run,
+            lan_bench::collect_lan_results,
+            lan_bench::set_reference_agent,
+            lan_bench::list_paired_comparisons,
+            // 报告签名
+            signing::verify_signed_report,
+            signing::verify_report_file,
+            // 导入
+            session_import::import_frame_csv,
+            // 搜索
+            search::search,
+            // 会话查询
+            session_query::query_sessions,
+            session_query::set_session_tags,
+            session_query::list_session_tags,
+            // 批量操作
+            session_bulk::delete_sessions,
+            session_bulk::export_sessions,
+            session_bulk::merge_sessions,
+            // 备份与恢复
+            backup::backup_data,
+            backup::inspect_backup,
+            backup::restore_data,
+            // 应用更新
+            updater::check_for_update,
+            updater::download_update,
+            updater::install_update,
+            // 下载镜像
+            mirrors::list_mirrors,
+            // PresentMon 下载
+            presentmon_download::download_presentmon,
+            // 网络代理
+            proxy::get_system_proxy,
+            proxy::test_connectivity,
+            // 插件
+            plugins::list_plugins,
+            plugins::set_plugin_enabled,
+            plugins::export_with_plugin,
+            plugins::get_plugins_dir,
+            // 脚本钩子
+            script_hooks::test_script_hook,
+            // 时间标记
+            markers::add_session_marker,
+            markers::list_clock_sources,
+            // 全局快捷键
+            hotkeys::get_hotkey_status,
+            // 悬浮窗
+            overlay::set_overlay,
+            overlay::toggle_overlay,
+            overlay::get_overlay_visible,
+            // 崩溃恢复
+            session_recovery::take_recovered_sessions,
+            // 后台任务
+            tasks::list_tasks,
+            tasks::cancel_task,
         ])
         .setup(|app| {
             let app_handle = app.handle().clone();
+            user_profile::load(&app_handle);
+            settings::load(&app_handle);
+            kiosk::load(&app_handle);
+            signing::load(&app_handle);
+            lan_bench::start(&app_handle);
+            power::register(&app_handle);
+            hotkeys::register(&app_handle);
+            updater::start(&app_handle);
+            session_recovery::recover(&app_handle);
 
             // 后台线程：定期扫描运行中的游戏
             std::thread::spawn(move || {
@@ -0,0 +1,289 @@
+use crate::fps_monitor;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+
+/// 每个外部时钟保留的对时样本数
+const MAX_SYNC_SAMPLES: usize = 64;
+/// 估计时钟漂移所需的最短对时跨度 (ms)
+const MIN_DRIFT_SPAN_MS: f64 = 10_000.0;
+/// 标记文本长度上限
+const MAX_LABEL_CHARS: usize = 200;
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarkerClock {
+    /// Unix 毫秒时间戳（外部设备已与本机对过时，如同一 NTP 服务器）
+    #[default]
+    Unix,
+    /// 外部设备自己的时钟（如采集卡时间码），按 `/api/clock/sync` 的对时样本换算
+    Source,
+}
+
+/// 外部注入的标记
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarkerRequest {
+    pub label: String,
+    /// 标记发生的时间 (ms)，含义由 `clock` 决定
+    pub timestamp_ms: f64,
+    #[serde(default)]
+    pub clock: MarkerClock,
+    /// 外部时钟名称（clock 为 source 时必填）
+    #[serde(default)]
+    pub source: Option<String>,
+    /// 目标会话，为空时使用正在进行的监测
+    #[serde(default)]
+    pub session_id: Option<String>,
+}
+
+/// 会话中的时间标记
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionMarker {
+    pub label: String,
+    /// 来源（外部时钟名称；手动添加时为 None）
+    #[serde(default)]
+    pub source: Option<String>,
+    /// 换算到本机时钟后的 Unix 毫秒时间戳
+    pub unix_ms: f64,
+    /// 距会话开始的实际经过时间 (秒)
+    pub at_secs: f64,
+    /// 对时误差估计 (ms)，本机时钟或 Unix 时间戳为 None
+    #[serde(default)]
+    pub uncertainty_ms: Option<f64>,
+}
+
+/// 外部设备发送的对时样本：设备在发送时读取的自身时钟
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClockSyncRequest {
+    pub source: String,
+    pub source_ms: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClockEstimate {
+    pub source: String,
+    /// 本机时间 - 外部时钟 (ms)
+    pub offset_ms: f64,
+    /// 外部时钟相对本机的漂移 (ppm)，样本跨度不足时为 0
+    pub drift_ppm: f64,
+    pub uncertainty_ms: f64,
+    pub samples: usize,
+}
+
+/// `GET /api/clock` 的返回：外部设备据此自行计算往返延迟和偏差
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalClock {
+    pub unix_ms: f64,
+    /// 正在监测的会话及其经过时间
+    pub session_id: Option<String>,
+    pub session_secs: Option<f64>,
+}
+
+// ==================== 对时 ====================
+
+pub(crate) fn now_ms() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64() * 1000.0)
+        .unwrap_or(0.0)
+}
+
+/// 每个外部时钟的 (外部时间, 本机接收时间) 样本
+type SyncSamples = HashMap<String, VecDeque<(f64, f64)>>;
+
+fn sync_samples() -> &'static Mutex<SyncSamples> {
+    static SAMPLES: OnceLock<Mutex<SyncSamples>> = OnceLock::new();
+    SAMPLES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 单向传输延迟只会让 `本机 - 外部` 偏大，取下包络作为偏差；
+/// 前后两半样本各取下包络点，连线斜率即为漂移
+fn estimate(source: &str, samples: &VecDeque<(f64, f64)>) -> Option<ClockEstimate> {
+    let lowest = |part: &[(f64, f64)]| {
+        part.iter()
+            .map(|&(s, l)| (s, l - s))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    };
+    let all: Vec<(f64, f64)> = samples.iter().copied().collect();
+    let (anchor_source, anchor_offset) = lowest(&all)?;
+
+    let span = all.last()?.0 - all.first()?.0;
+    let drift = if all.len() >= 4 && span >= MIN_DRIFT_SPAN_MS {
+        let (a, b) = all.split_at(all.len() / 2);
+        match (lowest(a), lowest(b)) {
+            (Some(p), Some(q)) if q.0 > p.0 => (q.1 - p.1) / (q.0 - p.0),
+            _ => 0.0,
+        }
+    } else {
+        0.0
+    };
+
+    // 误差：各样本相对拟合线的偏差中位数（网络抖动越大越不可信）
+    let mut residuals: Vec<f64> = all
+        .iter()
+        .map(|&(s, l)| (l - s) - (anchor_offset + drift * (s - anchor_source)))
+        .map(f64::abs)
+        .collect();
+    residuals.sort_by(f64::total_cmp);
+    let uncertainty = residuals[residuals.len() / 2];
+
+    Some(ClockEstimate {
+        source: source.to_string(),
+        offset_ms: anchor_offset + drift * (all.last()?.0 - anchor_source),
+        drift_ppm: (drift * 1_000_000.0 * 10.0).round() / 10.0,
+        uncertainty_ms: (uncertainty * 100.0).round() / 100.0,
+        samples: all.len(),
+    })
+}
+
+/// 记录对时样本，返回最新估计
+pub fn record_sync(request: &ClockSyncRequest) -> Result<ClockEstimate, String> {
+    let received = now_ms();
+    if request.source.trim().is_empty() {
+        return Err("缺少外部时钟名称".to_string());
+    }
+    let mut all = sync_samples().lock().unwrap();
+    let samples = all.entry(request.source.clone()).or_default();
+    samples.push_back((request.source_ms, received));
+    while samples.len() > MAX_SYNC_SAMPLES {
+        samples.pop_front();
+    }
+    estimate(&request.source, samples).ok_or_else(|| "对时样本不足".to_string())
+}
+
+/// 把外部时钟时间换算为本机 Unix 毫秒，返回 (时间, 误差)
+fn to_local(source: &str, source_ms: f64) -> Result<(f64, f64), String> {
+    let all = sync_samples().lock().unwrap();
+    let samples = all
+        .get(source)
+        .ok_or_else(|| format!("外部时钟 {} 尚未对时，请先调用 /api/clock/sync", source))?;
+    let est = estimate(source, samples).ok_or_else(|| "对时样本不足".to_string())?;
+    let (last_source, _) = *samples.back().ok_or_else(|| "对时样本不足".to_string())?;
+    let drift = est.drift_ppm / 1_000_000.0;
+    let offset = est.offset_ms + drift * (source_ms - last_source);
+    Ok((source_ms + offset, est.uncertainty_ms))
+}
+
+pub fn local_clock() -> LocalClock {
+    let current = fps_monitor::current_session();
+    let unix_ms = now_ms();
+    LocalClock {
+        unix_ms,
+        session_secs: current
+            .as_ref()
+            .map(|(_, started)| ((unix_ms - *started as f64) / 1000.0 * 1000.0).round() / 1000.0),
+        session_id: current.map(|(id, _)| id),
+    }
+}
+
+// ==================== 标记 ====================
+
+/// 按会话开始时间计算标记位置；旧会话没有毫秒级开始时间，按会话 ID 中的时间估算
+fn session_start_ms(session: &fps_monitor::FpsSession) -> Option<f64> {
+    if session.started_at_ms > 0 {
+        return Some(session.started_at_ms as f64);
+    }
+    let started = crate::session_query::started_at(&session.session_id)?;
+    let local = started.and_local_timezone(chrono::Local).single()?;
+    Some(local.timestamp_millis() as f64)
+}
+
+fn build_marker(request: &MarkerRequest, start_ms: f64) -> Result<SessionMarker, String> {
+    let label: String = request.label.trim().chars().take(MAX_LABEL_CHARS).collect();
+    if label.is_empty() {
+        return Err("标记内容不能为空".to_string());
+    }
+    if !request.timestamp_ms.is_finite() {
+        return Err("时间戳无效".to_string());
+    }
+    let (unix_ms, uncertainty) = match request.clock {
+        MarkerClock::Unix => (request.timestamp_ms, None),
+        MarkerClock::Source => {
+            let source = request
+                .source
+                .as_deref()
+                .ok_or_else(|| "使用外部时钟时必须提供 source".to_string())?;
+            let (ms, err) = to_local(source, request.timestamp_ms)?;
+            (ms, Some(err))
+        }
+    };
+    Ok(SessionMarker {
+        label,
+        source: request.source.clone(),
+        unix_ms: unix_ms.round(),
+        at_secs: ((unix_ms - start_ms) / 1000.0 * 1000.0).round() / 1000.0,
+        uncertainty_ms: uncertainty,
+    })
+}
+
+/// 添加标记并通知前端 `session-marker`
+pub fn add(app: &AppHandle, request: &MarkerRequest) -> Result<SessionMarker, String> {
+    let marker = insert(app, request)?;
+    log::info!("添加标记 {} @ {:.3}s", marker.label, marker.at_secs);
+    let _ = app.emit("session-marker", &marker);
+    Ok(marker)
+}
+
+/// 正在监测时加入进行中的会话（未指定 session_id 时加入所有进行中的会话），否则写入已保存的会话
+fn insert(app: &AppHandle, request: &MarkerRequest) -> Result<SessionMarker, String> {
+    let targets: Vec<(String, u64)> = fps_monitor::active_sessions()
+        .into_iter()
+        .filter(|(id, _)| request.session_id.as_ref().is_none_or(|want| want == id))
+        .collect();
+    if !targets.is_empty() {
+        let mut first = None;
+        for (session_id, started_ms) in targets {
+            let marker = build_marker(request, started_ms as f64)?;
+            fps_monitor::push_marker(&session_id, marker.clone())?;
+            first.get_or_insert(marker);
+        }
+        return first.ok_or_else(|| "当前没有进行中的监测".to_string());
+    }
+
+    let session_id = request
+        .session_id
+        .as_deref()
+        .ok_or_else(|| "当前没有进行中的监测，请指定 session_id".to_string())?;
+    let mut session = crate::storage::load_session(app, session_id)?;
+    let start = session_start_ms(&session).ok_or_else(|| "无法确定会话开始时间".to_string())?;
+    let marker = build_marker(request, start)?;
+    session.markers.push(marker.clone());
+    session
+        .markers
+        .sort_by(|a, b| a.unix_ms.total_cmp(&b.unix_ms));
+    crate::storage::save_session(app, &session)?;
+    Ok(marker)
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 在正在进行的监测中添加手动标记（本机时钟）
+#[tauri::command]
+pub fn add_session_marker(app: AppHandle, label: String) -> Result<SessionMarker, String> {
+    add(
+        &app,
+        &MarkerRequest {
+            label,
+            timestamp_ms: now_ms(),
+            clock: MarkerClock::Unix,
+            source: None,
+            session_id: None,
+        },
+    )
+}
+
+/// 已对时的外部时钟
+#[tauri::command]
+pub fn list_clock_sources() -> Result<Vec<ClockEstimate>, String> {
+    let all = sync_samples().lock().unwrap();
+    let mut estimates: Vec<ClockEstimate> = all
+        .iter()
+        .filter_map(|(source, samples)| estimate(source, samples))
+        .collect();
+    estimates.sort_by(|a, b| a.source.cmp(&b.source));
+    Ok(estimates)
+}
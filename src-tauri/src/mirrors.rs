@@ -0,0 +1,328 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// 官方源的保留 id
+const OFFICIAL_ID: &str = "official";
+/// 测速超时，超时视为不可用
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+/// 测速结果缓存时间
+const PROBE_TTL: Duration = Duration::from_secs(10 * 60);
+/// GitHub 测速地址（代理需能转发 github.com 页面）
+const GITHUB_PROBE_URL: &str = "https://github.com/GameTechDev/PresentMon/releases";
+/// 需要走 GitHub 代理的域名
+const GITHUB_HOSTS: &[&str] = &[
+    "github.com",
+    "objects.githubusercontent.com",
+    "raw.githubusercontent.com",
+];
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Origin {
+    /// GameBench 服务器（规则、过滤名单、应用更新）
+    Server,
+    /// GitHub 发布下载（PresentMon 等）
+    GitHub,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Mirror {
+    pub id: String,
+    pub name: String,
+    /// 同步了服务器接口/静态文件的镜像根地址，路径与官方服务器一致
+    #[serde(default)]
+    pub server_url: Option<String>,
+    /// GitHub 下载代理前缀，完整的 GitHub 地址直接拼在后面
+    #[serde(default)]
+    pub github_proxy: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MirrorSettings {
+    /// 自定义镜像，官方源始终可用
+    pub list: Vec<Mirror>,
+    /// 按测速结果自动选择最快的源
+    pub auto_select: bool,
+    /// 手动指定的首选源 id（关闭自动选择时生效）
+    pub preferred: Option<String>,
+}
+
+impl Default for MirrorSettings {
+    fn default() -> Self {
+        MirrorSettings {
+            list: Vec::new(),
+            auto_select: true,
+            preferred: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MirrorStatus {
+    pub id: String,
+    pub name: String,
+    /// 服务器镜像延迟 (ms)，未提供或不可用时为 None
+    pub server_latency_ms: Option<u64>,
+    /// GitHub 代理延迟 (ms)
+    pub github_latency_ms: Option<u64>,
+    /// 当前服务器请求使用的源
+    pub server_selected: bool,
+    /// 当前 GitHub 下载使用的源
+    pub github_selected: bool,
+}
+
+/// 每类来源按延迟排序的镜像 id 及测速时间
+type Ranking = HashMap<Origin, (Vec<(String, Option<u64>)>, Instant)>;
+
+fn ranking() -> &'static Mutex<Ranking> {
+    static RANKING: OnceLock<Mutex<Ranking>> = OnceLock::new();
+    RANKING.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+// ==================== 镜像列表 ====================
+
+/// 官方源 + 用户配置的镜像
+fn all_mirrors() -> Vec<Mirror> {
+    let settings = crate::settings::get();
+    let mut mirrors = vec![Mirror {
+        id: OFFICIAL_ID.to_string(),
+        name: "官方".to_string(),
+        server_url: Some(settings.server_url.clone()),
+        github_proxy: Some(String::new()),
+    }];
+    mirrors.extend(
+        settings
+            .mirrors
+            .list
+            .into_iter()
+            .filter(|m| m.id != OFFICIAL_ID && !m.id.trim().is_empty()),
+    );
+    mirrors
+}
+
+/// 把官方地址改写为镜像地址；镜像不提供该来源时返回 None
+fn rewrite(mirror: &Mirror, origin: Origin, target: &str) -> Option<String> {
+    match origin {
+        Origin::Server => {
+            let base = mirror
+                .server_url
+                .as_deref()
+                .filter(|s| !s.trim().is_empty())?;
+            Some(format!("{}{}", base.trim_end_matches('/'), target))
+        }
+        Origin::GitHub => {
+            let proxy = mirror.github_proxy.as_deref()?;
+            if proxy.is_empty() {
+                return Some(target.to_string());
+            }
+            Some(format!("{}/{}", proxy.trim_end_matches('/'), target))
+        }
+    }
+}
+
+fn is_github(url: &str) -> bool {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(str::to_lowercase))
+        .is_some_and(|host| GITHUB_HOSTS.contains(&host.as_str()))
+}
+
+// ==================== 测速 ====================
+
+/// 任意 HTTP 响应（包括 404）都说明线路可达，只有连接失败和超时算不可用
+async fn probe(client: reqwest::Client, url: String) -> Option<u64> {
+    let start = Instant::now();
+    client
+        .head(&url)
+        .timeout(PROBE_TIMEOUT)
+        .send()
+        .await
+        .ok()
+        .map(|_| start.elapsed().as_millis() as u64)
+}
+
+/// 并发测速并按延迟排序，不可用的源排在最后
+async fn rank(origin: Origin) -> Vec<(String, Option<u64>)> {
+    if crate::connectivity::is_offline() {
+        return Vec::new();
+    }
+    let client = match crate::proxy::client() {
+        Ok(client) => client,
+        Err(e) => {
+            log::warn!("{}", e);
+            return Vec::new();
+        }
+    };
+    let mut tasks = tokio::task::JoinSet::new();
+    for mirror in all_mirrors() {
+        let target = match origin {
+            Origin::Server => "",
+            Origin::GitHub => GITHUB_PROBE_URL,
+        };
+        if let Some(url) = rewrite(&mirror, origin, target) {
+            let client = client.clone();
+            tasks.spawn(async move { (mirror.id, probe(client, url).await) });
+        }
+    }
+
+    let mut results = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok(result) = joined {
+            results.push(result);
+        }
+    }
+    results.sort_by_key(|(_, latency)| latency.unwrap_or(u64::MAX));
+    if let Some((id, Some(ms))) = results.first() {
+        log::info!("{:?} 最快的源: {} ({} ms)", origin, id, ms);
+    }
+    ranking()
+        .lock()
+        .unwrap()
+        .insert(origin, (results.clone(), Instant::now()));
+    results
+}
+
+/// 候选源的尝试顺序：手动首选 → 测速排名（自动选择时）→ 配置顺序
+async fn ordered_ids(origin: Origin) -> Vec<String> {
+    let settings = crate::settings::get().mirrors;
+    let mut ids = Vec::new();
+    if !settings.auto_select {
+        ids.extend(settings.preferred.clone());
+    } else {
+        let cached = ranking()
+            .lock()
+            .unwrap()
+            .get(&origin)
+            .filter(|(_, at)| at.elapsed() < PROBE_TTL)
+            .map(|(list, _)| list.clone());
+        let ranked = match cached {
+            Some(list) => list,
+            None => rank(origin).await,
+        };
+        ids.extend(ranked.into_iter().map(|(id, _)| id));
+    }
+    for mirror in all_mirrors() {
+        if !ids.contains(&mirror.id) {
+            ids.push(mirror.id);
+        }
+    }
+    ids
+}
+
+/// 当前会被优先使用的源
+async fn selected(mirrors: &[Mirror], origin: Origin) -> Option<String> {
+    ordered_ids(origin).await.into_iter().find(|id| {
+        mirrors
+            .iter()
+            .any(|m| &m.id == id && rewrite(m, origin, "").is_some())
+    })
+}
+
+// ==================== 请求 ====================
+
+/// 服务器路径的候选地址；显式指定的服务器地址不走镜像
+pub async fn server_urls(explicit_base: Option<String>, path: &str) -> Vec<String> {
+    if let Some(base) = explicit_base.filter(|b| !b.trim().is_empty()) {
+        return vec![format!("{}{}", base.trim_end_matches('/'), path)];
+    }
+    candidates(Origin::Server, path).await
+}
+
+/// 完整地址的候选地址；只有 GitHub 地址会走代理
+pub async fn url_candidates(url: &str) -> Vec<String> {
+    if is_github(url) {
+        candidates(Origin::GitHub, url).await
+    } else {
+        vec![url.to_string()]
+    }
+}
+
+async fn candidates(origin: Origin, target: &str) -> Vec<String> {
+    let mirrors = all_mirrors();
+    let mut urls = Vec::new();
+    for id in ordered_ids(origin).await {
+        let Some(mirror) = mirrors.iter().find(|m| m.id == id) else {
+            continue;
+        };
+        if let Some(url) = rewrite(mirror, origin, target) {
+            if !urls.contains(&url) {
+                urls.push(url);
+            }
+        }
+    }
+    urls
+}
+
+/// 依次尝试候选地址，返回第一个成功的响应及其实际地址
+pub async fn get_with(
+    urls: &[String],
+    configure: impl Fn(reqwest::RequestBuilder) -> reqwest::RequestBuilder,
+) -> Result<(reqwest::Url, reqwest::Response), String> {
+    let client = crate::proxy::client()?;
+    let mut last_error = "没有可用的下载源".to_string();
+    for url in urls {
+        match configure(client.get(url))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+        {
+            Ok(response) => return Ok((response.url().clone(), response)),
+            Err(e) => {
+                log::warn!("下载源不可用 {}: {}", url, e);
+                last_error = e.to_string();
+            }
+        }
+    }
+    Err(last_error)
+}
+
+/// 依次尝试候选地址并解析 JSON
+pub async fn get_json<T: serde::de::DeserializeOwned>(urls: &[String]) -> Result<T, String> {
+    let (url, response) = get_with(urls, |r| r.timeout(Duration::from_secs(15))).await?;
+    response
+        .json()
+        .await
+        .map_err(|e| format!("解析 {} 失败: {}", url, e))
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 列出所有源；`probe` 为 true 时重新测速
+#[tauri::command]
+pub async fn list_mirrors(probe: bool) -> Result<Vec<MirrorStatus>, String> {
+    let (server, github) = if probe {
+        (rank(Origin::Server).await, rank(Origin::GitHub).await)
+    } else {
+        let cache = ranking().lock().unwrap();
+        let get = |origin| {
+            cache
+                .get(&origin)
+                .map(|(l, _)| l.clone())
+                .unwrap_or_default()
+        };
+        (get(Origin::Server), get(Origin::GitHub))
+    };
+    let mirrors = all_mirrors();
+    let server_selected = selected(&mirrors, Origin::Server).await;
+    let github_selected = selected(&mirrors, Origin::GitHub).await;
+    let latency = |list: &[(String, Option<u64>)], id: &str| {
+        list.iter().find(|(i, _)| i == id).and_then(|(_, ms)| *ms)
+    };
+
+    Ok(mirrors
+        .into_iter()
+        .map(|m| MirrorStatus {
+            server_latency_ms: latency(&server, &m.id),
+            github_latency_ms: latency(&github, &m.id),
+            server_selected: server_selected.as_deref() == Some(m.id.as_str()),
+            github_selected: github_selected.as_deref() == Some(m.id.as_str()),
+            id: m.id,
+            name: m.name,
+        })
+        .collect())
+}
@@ -0,0 +1,425 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+/// 用户自定义 profile 存储文件
+const PROFILES_FILE: &str = "benchmark_profiles.json";
+/// 应用 profile 前的系统设置快照（每应用一次追加一份），用于恢复
+const BACKUP_FILE: &str = "os_settings_backup.json";
+
+/// Windows 内置电源计划
+pub const POWER_SCHEME_BALANCED: &str = "381b4222-f694-41f0-9685-ff5bb260df2e";
+pub const POWER_SCHEME_HIGH_PERFORMANCE: &str = "8c5e7fda-e8bf-4a96-9a85-a6e23a8c635c";
+pub const POWER_SCHEME_ULTIMATE: &str = "e9a42b02-d5df-448d-aa00-03f14749eb61";
+
+// ==================== 数据结构 ====================
+
+/// 一组可切换的系统设置，None 表示不修改 / 未能读取
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OsSettings {
+    /// 电源计划 GUID
+    pub power_scheme: Option<String>,
+    /// 游戏模式
+    pub game_mode: Option<bool>,
+    /// 硬件加速 GPU 计划 (HAGS)，修改后需重启生效
+    pub hags: Option<bool>,
+    /// 核心停放: 最少未停放核心百分比 (100 = 关闭核心停放)
+    pub core_parking_min_percent: Option<u32>,
+    /// 核心停放设置所属的电源计划 GUID，None 表示切换电源计划后的当前计划
+    /// 快照中记录被修改的计划，恢复时写回同一计划
+    #[serde(default)]
+    pub core_parking_scheme: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkProfile {
+    pub name: String,
+    pub description: String,
+    /// 是否为内置 profile（不可删除）
+    #[serde(default)]
+    pub builtin: bool,
+    pub settings: OsSettings,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplyResult {
+    /// 应用前的系统设置
+    pub previous: OsSettings,
+    /// 需要用户注意的事项（如需重启）
+    pub warnings: Vec<String>,
+}
+
+fn builtin_profiles() -> Vec<BenchmarkProfile> {
+    vec![
+        BenchmarkProfile {
+            name: "标准测试环境".to_string(),
+            description: "高性能电源计划 + 游戏模式 + 关闭核心停放，适合横向对比".to_string(),
+            builtin: true,
+            settings: OsSettings {
+                power_scheme: Some(POWER_SCHEME_HIGH_PERFORMANCE.to_string()),
+                game_mode: Some(true),
+                hags: None,
+                core_parking_min_percent: Some(100),
+                core_parking_scheme: None,
+            },
+        },
+        BenchmarkProfile {
+            name: "极致性能".to_string(),
+            description: "卓越性能电源计划（需系统已启用）+ 开启 HAGS + 关闭核心停放".to_string(),
+            builtin: true,
+            settings: OsSettings {
+                power_scheme: Some(POWER_SCHEME_ULTIMATE.to_string()),
+                game_mode: Some(true),
+                hags: Some(true),
+                core_parking_min_percent: Some(100),
+                core_parking_scheme: None,
+            },
+        },
+        BenchmarkProfile {
+            name: "日常使用".to_string(),
+            description: "平衡电源计划 + 系统默认核心停放".to_string(),
+            builtin: true,
+            settings: OsSettings {
+                power_scheme: Some(POWER_SCHEME_BALANCED.to_string()),
+                game_mode: Some(true),
+                hags: None,
+                core_parking_min_percent: None,
+                core_parking_scheme: None,
+            },
+        },
+    ]
+}
+
+// ==================== 系统设置读写 (Windows) ====================
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::OsSettings;
+    use windows::core::GUID;
+    use windows::Win32::Foundation::{LocalFree, ERROR_SUCCESS, HLOCAL};
+    use windows::Win32::System::Power::{
+        PowerGetActiveScheme, PowerReadACValueIndex, PowerSetActiveScheme, PowerWriteACValueIndex,
+        PowerWriteDCValueIndex,
+    };
+    use windows::Win32::System::Registry::HKEY;
+    use winreg::enums::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE, KEY_SET_VALUE};
+    use winreg::RegKey;
+
+    /// 处理器电源管理子组
+    const SUBGROUP_PROCESSOR: GUID = GUID::from_u128(0x54533251_82be_4824_96c1_47b60b740d00);
+    /// CPMINCORES: 最少未停放核心数 (%)
+    const SETTING_CPMINCORES: GUID = GUID::from_u128(0x0cc5b647_c1df_4637_891a_dec35c318583);
+
+    const GAME_BAR_KEY: &str = r"Software\Microsoft\GameBar";
+    const GRAPHICS_DRIVERS_KEY: &str = r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers";
+
+    fn guid_to_string(guid: &GUID) -> String {
+        format!("{:032x}", guid.to_u128())
+            .chars()
+            .enumerate()
+            .flat_map(|(i, c)| {
+                let dash = matches!(i, 8 | 12 | 16 | 20);
+                dash.then_some('-').into_iter().chain(std::iter::once(c))
+            })
+            .collect()
+    }
+
+    fn parse_guid(s: &str) -> Option<GUID> {
+        u128::from_str_radix(&s.replace('-', ""), 16)
+            .ok()
+            .map(GUID::from_u128)
+    }
+
+    fn active_scheme() -> Option<GUID> {
+        unsafe {
+            let mut ptr: *mut GUID = std::ptr::null_mut();
+            if PowerGetActiveScheme(HKEY::default(), &mut ptr) != ERROR_SUCCESS || ptr.is_null() {
+                return None;
+            }
+            let guid = *ptr;
+            let _ = LocalFree(HLOCAL(ptr as _));
+            Some(guid)
+        }
+    }
+
+    fn read_core_parking(scheme: &GUID) -> Option<u32> {
+        let mut value = 0u32;
+        let result = unsafe {
+            PowerReadACValueIndex(
+                HKEY::default(),
+                Some(scheme),
+                Some(&SUBGROUP_PROCESSOR),
+                Some(&SETTING_CPMINCORES),
+                &mut value,
+            )
+        };
+        (result == ERROR_SUCCESS).then_some(value)
+    }
+
+    pub fn read_settings() -> OsSettings {
+        let scheme = active_scheme();
+        let game_mode = RegKey::predef(HKEY_CURRENT_USER)
+            .open_subkey(GAME_BAR_KEY)
+            .and_then(|k| k.get_value::<u32, _>("AutoGameModeEnabled"))
+            // 未设置时 Windows 默认开启
+            .map(|v| v != 0)
+            .unwrap_or(true);
+        let hags = RegKey::predef(HKEY_LOCAL_MACHINE)
+            .open_subkey(GRAPHICS_DRIVERS_KEY)
+            .and_then(|k| k.get_value::<u32, _>("HwSchMode"))
+            .ok()
+            .map(|v| v == 2);
+
+        OsSettings {
+            power_scheme: scheme.as_ref().map(guid_to_string),
+            game_mode: Some(game_mode),
+            hags,
+            core_parking_min_percent: scheme.as_ref().and_then(read_core_parking),
+            core_parking_scheme: scheme.as_ref().map(guid_to_string),
+        }
+    }
+
+    /// 确定核心停放设置要写入的电源计划（通常是 profile 要切换到的计划）并写入 `target`
+    /// 快照与应用使用同一计划，切换电源计划失败时也不会改到用户原来的计划
+    pub fn pin_parking_scheme(target: &mut OsSettings) {
+        if target.core_parking_min_percent.is_some() && target.core_parking_scheme.is_none() {
+            target.core_parking_scheme = target
+                .power_scheme
+                .as_deref()
+                .and_then(parse_guid)
+                .or_else(active_scheme)
+                .as_ref()
+                .map(guid_to_string);
+        }
+    }
+
+    /// 应用 `target`（已确定核心停放计划）前的快照：核心停放取自将被修改的计划
+    pub fn snapshot_for(target: &OsSettings) -> OsSettings {
+        let mut current = read_settings();
+        if target.core_parking_min_percent.is_some() {
+            let modified = target.core_parking_scheme.as_deref().and_then(parse_guid);
+            current.core_parking_min_percent = modified.as_ref().and_then(read_core_parking);
+            current.core_parking_scheme = modified.as_ref().map(guid_to_string);
+        }
+        current
+    }
+
+    pub fn apply_settings(target: &OsSettings, warnings: &mut Vec<String>) -> Result<(), String> {
+        if let Some(scheme) = &target.power_scheme {
+            let guid =
+                parse_guid(scheme).ok_or_else(|| format!("无效的电源计划 GUID: {}", scheme))?;
+            let result = unsafe { PowerSetActiveScheme(HKEY::default(), Some(&guid)) };
+            if result != ERROR_SUCCESS {
+                warnings.push(format!("切换电源计划失败（该计划可能未安装）: {}", scheme));
+            }
+        }
+
+        if let Some(percent) = target.core_parking_min_percent {
+            let scheme = match &target.core_parking_scheme {
+                Some(s) => {
+                    Some(parse_guid(s).ok_or_else(|| format!("无效的电源计划 GUID: {}", s))?)
+                }
+                // 未确定计划时写入要切换到的计划，不回退到可能未切换成功的当前计划
+                None => match &target.power_scheme {
+                    Some(s) => parse_guid(s),
+                    None => active_scheme(),
+                },
+            };
+            match scheme {
+                Some(scheme) => unsafe {
+                    let ac = PowerWriteACValueIndex(
+                        HKEY::default(),
+                        &scheme,
+                        Some(&SUBGROUP_PROCESSOR),
+                        Some(&SETTING_CPMINCORES),
+                        percent,
+                    );
+                    let _ = PowerWriteDCValueIndex(
+                        HKEY::default(),
+                        &scheme,
+                        Some(&SUBGROUP_PROCESSOR),
+                        Some(&SETTING_CPMINCORES),
+                        percent,
+                    );
+                    // 修改的是当前计划时重新激活使修改生效
+                    if active_scheme() == Some(scheme) {
+                        let _ = PowerSetActiveScheme(HKEY::default(), Some(&scheme));
+                    }
+                    if ac != ERROR_SUCCESS {
+                        warnings.push("修改核心停放设置失败（可能需要管理员权限）".to_string());
+                    }
+                },
+                None => warnings.push("无法读取当前电源计划，跳过核心停放设置".to_string()),
+            }
+        }
+
+        if let Some(enabled) = target.game_mode {
+            let result = RegKey::predef(HKEY_CURRENT_USER)
+                .create_subkey(GAME_BAR_KEY)
+                .and_then(|(k, _)| k.set_value("AutoGameModeEnabled", &(enabled as u32)));
+            if let Err(e) = result {
+                warnings.push(format!("修改游戏模式失败: {}", e));
+            }
+        }
+
+        if let Some(enabled) = target.hags {
+            let current = read_settings().hags;
+            if current != Some(enabled) {
+                let result = RegKey::predef(HKEY_LOCAL_MACHINE)
+                    .open_subkey_with_flags(GRAPHICS_DRIVERS_KEY, KEY_SET_VALUE)
+                    .and_then(|k| k.set_value("HwSchMode", &(if enabled { 2u32 } else { 1u32 })));
+                match result {
+                    Ok(()) => {
+                        warnings.push("硬件加速 GPU 计划 (HAGS) 已修改，需要重启后生效".to_string())
+                    }
+                    Err(e) => warnings.push(format!("修改 HAGS 失败（需要管理员权限）: {}", e)),
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    use super::OsSettings;
+
+    pub fn read_settings() -> OsSettings {
+        OsSettings::default()
+    }
+
+    pub fn pin_parking_scheme(_target: &mut OsSettings) {}
+
+    pub fn snapshot_for(_target: &OsSettings) -> OsSettings {
+        OsSettings::default()
+    }
+
+    pub fn apply_settings(_target: &OsSettings, _warnings: &mut Vec<String>) -> Result<(), String> {
+        Err("仅支持 Windows".to_string())
+    }
+}
+
+// ==================== 持久化 ====================
+
+fn data_file(app: &AppHandle, name: &str) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("无法获取数据目录: {}", e))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建数据目录: {}", e))?;
+    Ok(dir.join(name))
+}
+
+fn load_user_profiles(app: &AppHandle) -> Result<Vec<BenchmarkProfile>, String> {
+    let path = data_file(app, PROFILES_FILE)?;
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let text = std::fs::read_to_string(&path).map_err(|e| format!("读取 profile 失败: {}", e))?;
+    serde_json::from_str(&text).map_err(|e| format!("解析 profile 失败: {}", e))
+}
+
+/// 读取快照列表（按应用顺序）
+fn load_backups(path: &Path) -> Result<Vec<OsSettings>, String> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let text = std::fs::read_to_string(path).map_err(|e| format!("读取设置快照失败: {}", e))?;
+    serde_json::from_str(&text).map_err(|e| format!("解析设置快照失败: {}", e))
+}
+
+fn save_user_profiles(app: &AppHandle, profiles: &[BenchmarkProfile]) -> Result<(), String> {
+    let path = data_file(app, PROFILES_FILE)?;
+    let text = serde_json::to_string_pretty(profiles).map_err(|e| e.to_string())?;
+    std::fs::write(&path, text).map_err(|e| format!("保存 profile 失败: {}", e))
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 读取当前系统设置
+#[tauri::command]
+pub fn snapshot_os_settings() -> Result<OsSettings, String> {
+    Ok(platform::read_settings())
+}
+
+/// 列出内置与用户自定义的测试环境 profile
+#[tauri::command]
+pub fn list_benchmark_profiles(app: AppHandle) -> Result<Vec<BenchmarkProfile>, String> {
+    let mut profiles = builtin_profiles();
+    profiles.extend(load_user_profiles(&app)?);
+    Ok(profiles)
+}
+
+/// 保存（新建或覆盖）用户自定义 profile
+#[tauri::command]
+pub fn save_benchmark_profile(app: AppHandle, mut profile: BenchmarkProfile) -> Result<(), String> {
+    crate::kiosk::ensure_unlocked()?;
+    if builtin_profiles().iter().any(|p| p.name == profile.name) {
+        return Err(format!("不能覆盖内置 profile「{}」", profile.name));
+    }
+    profile.builtin = false;
+
+    let mut profiles = load_user_profiles(&app)?;
+    profiles.retain(|p| p.name != profile.name);
+    profiles.push(profile);
+    save_user_profiles(&app, &profiles)
+}
+
+/// 删除用户自定义 profile
+#[tauri::command]
+pub fn delete_benchmark_profile(app: AppHandle, name: String) -> Result<(), String> {
+    crate::kiosk::ensure_unlocked()?;
+    let mut profiles = load_user_profiles(&app)?;
+    let before = profiles.len();
+    profiles.retain(|p| p.name != name);
+    if profiles.len() == before {
+        return Err(format!("未找到 profile「{}」", name));
+    }
+    save_user_profiles(&app, &profiles)
+}
+
+/// 应用 profile，应用前自动快照当前设置以便恢复
+#[tauri::command]
+pub fn apply_benchmark_profile(app: AppHandle, name: String) -> Result<ApplyResult, String> {
+    crate::kiosk::ensure_unlocked()?;
+    let profile = list_benchmark_profiles(app.clone())?
+        .into_iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| format!("未找到 profile「{}」", name))?;
+
+    let mut settings = profile.settings.clone();
+    platform::pin_parking_scheme(&mut settings);
+    let previous = platform::snapshot_for(&settings);
+    let backup = data_file(&app, BACKUP_FILE)?;
+    // 连续应用多个 profile 时每次追加快照（各 profile 可能修改不同的电源计划），恢复时倒序写回
+    let mut backups = load_backups(&backup)?;
+    backups.push(previous.clone());
+    let text = serde_json::to_string_pretty(&backups).map_err(|e| e.to_string())?;
+    std::fs::write(&backup, text).map_err(|e| format!("保存设置快照失败: {}", e))?;
+
+    let mut warnings = Vec::new();
+    platform::apply_settings(&settings, &mut warnings)?;
+    log::info!("已应用测试环境 profile: {}", profile.name);
+
+    Ok(ApplyResult { previous, warnings })
+}
+
+/// 恢复到应用 profile 之前的系统设置
+#[tauri::command]
+pub fn restore_os_settings(app: AppHandle) -> Result<Vec<String>, String> {
+    let backup = data_file(&app, BACKUP_FILE)?;
+    let backups = load_backups(&backup)?;
+    if backups.is_empty() {
+        return Err("没有可恢复的设置快照".to_string());
+    }
+
+    // 从最后应用的 profile 开始撤销，最早的快照最后写回，得到原始状态
+    let mut warnings = Vec::new();
+    for previous in backups.iter().rev() {
+        platform::apply_settings(previous, &mut warnings)?;
+    }
+    let _ = std::fs::remove_file(&backup);
+    log::info!("已恢复系统设置");
+    Ok(warnings)
+}
@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+/// 采样间隔
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+
+// ==================== 数据结构 ====================
+
+/// GameBench 自身（含 PresentMon）在测试期间的资源占用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OverheadReport {
+    /// 平均 CPU 占用（占全部逻辑核心的百分比）
+    pub avg_cpu_percent: f64,
+    pub peak_cpu_percent: f64,
+    /// 平均内存占用 (MB)
+    pub avg_ram_mb: f64,
+    pub peak_ram_mb: f64,
+    /// 平均 GPU 占用 (%)，最低开销档位下不采样
+    pub avg_gpu_percent: Option<f64>,
+    /// 统计的进程 (e.g., ["gamebench-desktop.exe", "PresentMon.exe"])
+    pub processes: Vec<String>,
+    pub samples: u32,
+}
+
+#[derive(Default)]
+struct Sample {
+    cpu_percent: f64,
+    ram_mb: f64,
+    gpu_percent: Option<f64>,
+}
+
+// ==================== 采样 ====================
+
+#[cfg(target_os = "windows")]
+fn gpu_percent(pids: &[u32]) -> Option<f64> {
+    let total: u64 = pids
+        .iter()
+        .filter_map(|pid| crate::process_activity::gpu_utilization(*pid))
+        .sum();
+    Some(total as f64)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn gpu_percent(_pids: &[u32]) -> Option<f64> {
+    None
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// 在 FPS 监测期间采样本进程与 PresentMon 子进程的资源占用
+pub struct OverheadSampler {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<(Vec<Sample>, Vec<String>)>>,
+}
+
+impl OverheadSampler {
+    /// `include_gpu` 为 false 时不查询 GPU 计数器（WMI 查询本身也有开销）
+    pub fn start(presentmon_pid: Option<u32>, include_gpu: bool) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = stop.clone();
+
+        let handle = std::thread::spawn(move || {
+            let pids: Vec<u32> = std::iter::once(std::process::id())
+                .chain(presentmon_pid)
+                .collect();
+            let sys_pids: Vec<Pid> = pids.iter().map(|p| Pid::from_u32(*p)).collect();
+            let refresh = ProcessRefreshKind::nothing().with_cpu().with_memory();
+            let mut sys = System::new();
+            sys.refresh_processes_specifics(ProcessesToUpdate::Some(&sys_pids), true, refresh);
+            let cores = std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1) as f64;
+
+            let mut names = Vec::new();
+            let mut samples = Vec::new();
+            let mut last = Instant::now();
+            while !stop_flag.load(Ordering::Relaxed) {
+                if last.elapsed() >= SAMPLE_INTERVAL {
+                    sys.refresh_processes_specifics(
+                        ProcessesToUpdate::Some(&sys_pids),
+                        true,
+                        refresh,
+                    );
+                    let mut sample = Sample::default();
+                    for pid in &sys_pids {
+                        let Some(process) = sys.process(*pid) else {
+                            continue;
+                        };
+                        let name = process.name().to_string_lossy().to_string();
+                        if !names.contains(&name) {
+                            names.push(name);
+                        }
+                        // sysinfo 的 CPU 占用以单核为 100%
+                        sample.cpu_percent += process.cpu_usage() as f64 / cores;
+                        sample.ram_mb += process.memory() as f64 / (1024.0 * 1024.0);
+                    }
+                    if include_gpu {
+                        sample.gpu_percent = gpu_percent(&pids);
+                    }
+                    samples.push(sample);
+                    last = Instant::now();
+                }
+                std::thread::sleep(Duration::from_millis(200));
+            }
+            (samples, names)
+        });
+
+        OverheadSampler {
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    /// 停止采样并生成报告（测试时长不足一个采样间隔时返回 None）
+    pub fn finish(mut self) -> Option<OverheadReport> {
+        self.stop.store(true, Ordering::Relaxed);
+        let (samples, processes) = self.handle.take()?.join().ok()?;
+        if samples.is_empty() {
+            return None;
+        }
+
+        let count = samples.len() as f64;
+        let gpu: Vec<f64> = samples.iter().filter_map(|s| s.gpu_percent).collect();
+        Some(OverheadReport {
+            avg_cpu_percent: round1(samples.iter().map(|s| s.cpu_percent).sum::<f64>() / count),
+            peak_cpu_percent: round1(samples.iter().map(|s| s.cpu_percent).fold(0.0, f64::max)),
+            avg_ram_mb: round1(samples.iter().map(|s| s.ram_mb).sum::<f64>() / count),
+            peak_ram_mb: round1(samples.iter().map(|s| s.ram_mb).fold(0.0, f64::max)),
+            avg_gpu_percent: (!gpu.is_empty())
+                .then(|| round1(gpu.iter().sum::<f64>() / gpu.len() as f64)),
+            processes,
+            samples: samples.len() as u32,
+        })
+    }
+}
+
+impl Drop for OverheadSampler {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::fps_monitor;
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
+
+/// 悬浮窗的窗口标签，前端据此渲染悬浮窗而不是主界面
+pub const OVERLAY_LABEL: &str = "overlay";
+
+const OVERLAY_WIDTH: f64 = 220.0;
+const OVERLAY_HEIGHT: f64 = 84.0;
+/// 距屏幕左上角的距离（逻辑像素）
+const OVERLAY_MARGIN: f64 = 16.0;
+
+// ==================== 窗口 ====================
+
+pub fn is_open(app: &AppHandle) -> bool {
+    app.get_webview_window(OVERLAY_LABEL).is_some()
+}
+
+fn notify(app: &AppHandle, visible: bool) {
+    let _ = app.emit("overlay-changed", visible);
+}
+
+/// 打开置顶、透明、鼠标穿透的悬浮窗，内容由前端订阅 `fps-update` 渲染
+fn open(app: &AppHandle) -> Result<(), String> {
+    if is_open(app) {
+        return Ok(());
+    }
+    // 最低开销档位承诺不引入额外开销，悬浮窗的渲染与合成也算在内
+    if fps_monitor::minimal_active() {
+        return Err("最低开销档位监测中不显示悬浮窗".to_string());
+    }
+
+    let window = WebviewWindowBuilder::new(app, OVERLAY_LABEL, WebviewUrl::default())
+        .title("GameBench 悬浮窗")
+        .inner_size(OVERLAY_WIDTH, OVERLAY_HEIGHT)
+        .position(OVERLAY_MARGIN, OVERLAY_MARGIN)
+        .decorations(false)
+        .transparent(true)
+        .shadow(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .resizable(false)
+        .focused(false)
+        .build()
+        .map_err(|e| format!("创建悬浮窗失败: {}", e))?;
+    // 鼠标点击穿透到游戏
+    if let Err(e) = window.set_ignore_cursor_events(true) {
+        log::warn!("悬浮窗无法设置鼠标穿透: {}", e);
+    }
+
+    log::info!("已打开 FPS 悬浮窗");
+    notify(app, true);
+    Ok(())
+}
+
+fn close(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(OVERLAY_LABEL) {
+        let _ = window.close();
+        log::info!("已关闭 FPS 悬浮窗");
+        notify(app, false);
+    }
+}
+
+fn set_visible(app: &AppHandle, visible: bool) -> Result<bool, String> {
+    if visible {
+        open(app)?;
+    } else {
+        close(app);
+    }
+    Ok(visible)
+}
+
+/// 切换悬浮窗显示，返回切换后是否显示（供快捷键使用）
+pub fn toggle(app: &AppHandle) -> Result<bool, String> {
+    set_visible(app, !is_open(app))
+}
+
+/// 以最低开销档位开始监测时关闭悬浮窗
+pub fn on_capture_start(app: &AppHandle, profile: fps_monitor::CaptureProfile) {
+    if profile == fps_monitor::CaptureProfile::Minimal && is_open(app) {
+        log::info!("最低开销档位监测，关闭悬浮窗");
+        close(app);
+    }
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 显示或关闭 FPS 悬浮窗
+/// 创建窗口需在异步命令中进行，避免 Windows 上主线程死锁
+#[tauri::command]
+pub async fn set_overlay(app: AppHandle, visible: bool) -> Result<bool, String> {
+    set_visible(&app, visible)
+}
+
+#[tauri::command]
+pub async fn toggle_overlay(app: AppHandle) -> Result<bool, String> {
+    toggle(&app)
+}
+
+#[tauri::command]
+pub fn get_overlay_visible(app: AppHandle) -> Result<bool, String> {
+    Ok(is_open(&app))
+}
@@ -0,0 +1,11 @@
+pub use gamebench_core::pcie::{
+    read_gpu_links, LinkSampler, PcieIssue, PcieLinkReport, PcieLinkStatus,
+};
+
+// ==================== Tauri 命令 ====================
+
+/// 读取当前 GPU PCIe 链路状态
+#[tauri::command]
+pub fn get_pcie_link_status() -> Result<Vec<PcieLinkStatus>, String> {
+    Ok(read_gpu_links())
+}
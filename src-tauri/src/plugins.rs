@@ -0,0 +1,675 @@
+use crate::fps_monitor::FpsSession;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+const PLUGINS_DIR: &str = "plugins";
+const MANIFEST_FILE: &str = "plugin.json";
+/// 单行输出上限，超出视为插件异常并结束
+const MAX_LINE_BYTES: u64 = 64 * 1024;
+/// 每个插件最多声明的指标数
+const MAX_METRICS: usize = 32;
+/// 插件进程内存上限（Windows 作业对象）
+#[cfg(target_os = "windows")]
+const MEMORY_LIMIT_BYTES: usize = 256 * 1024 * 1024;
+/// 导出插件的最长运行时间
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(30);
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginKind {
+    /// 监测期间持续输出指标（如水冷泵速、冷排温度）
+    Telemetry,
+    /// 会话结束后接收会话 JSON 并导出到其他系统
+    Exporter,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricSpec {
+    pub key: String,
+    pub label: String,
+    #[serde(default)]
+    pub unit: Option<String>,
+}
+
+/// 插件目录下的 plugin.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginManifest {
+    /// 插件 id，只允许小写字母、数字、`-` 和 `_`
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    pub kind: PluginKind,
+    /// 可执行文件，相对插件目录
+    pub executable: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// 遥测插件输出的指标，未声明的指标会被忽略
+    #[serde(default)]
+    pub metrics: Vec<MetricSpec>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginInfo {
+    /// 插件目录名
+    pub dir: String,
+    /// 清单无效时为 None
+    pub manifest: Option<PluginManifest>,
+    pub enabled: bool,
+    /// 清单或可执行文件的问题
+    pub error: Option<String>,
+}
+
+/// 测试期间单个插件指标的统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginMetricSummary {
+    pub plugin_id: String,
+    pub key: String,
+    pub label: String,
+    pub unit: Option<String>,
+    pub min: f64,
+    pub avg: f64,
+    pub max: f64,
+    pub samples: u32,
+}
+
+/// 遥测插件每行输出: `{"metrics": {"pump_rpm": 2400}}`
+#[derive(Debug, Deserialize)]
+struct TelemetryLine {
+    metrics: HashMap<String, f64>,
+}
+
+/// 导出插件退出前可输出一行: `{"message": "已上传"}`
+#[derive(Debug, Deserialize)]
+struct ExporterResult {
+    message: String,
+}
+
+// ==================== 发现 ====================
+
+fn plugins_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("无法获取数据目录: {}", e))?
+        .join(PLUGINS_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建插件目录: {}", e))?;
+    Ok(dir)
+}
+
+fn valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+/// 可执行文件必须位于插件目录内
+fn executable_path(dir: &Path, manifest: &PluginManifest) -> Result<PathBuf, String> {
+    let dir = dir.canonicalize().map_err(|e| e.to_string())?;
+    let exe = dir
+        .join(&manifest.executable)
+        .canonicalize()
+        .map_err(|_| format!("找不到可执行文件 {}", manifest.executable))?;
+    if !exe.starts_with(&dir) {
+        return Err("可执行文件必须位于插件目录内".to_string());
+    }
+    Ok(exe)
+}
+
+fn load_manifest(dir: &Path) -> Result<PluginManifest, String> {
+    let text = std::fs::read_to_string(dir.join(MANIFEST_FILE))
+        .map_err(|_| format!("缺少 {}", MANIFEST_FILE))?;
+    let manifest: PluginManifest =
+        serde_json::from_str(&text).map_err(|e| format!("{} 格式错误: {}", MANIFEST_FILE, e))?;
+    if !valid_id(&manifest.id) {
+        return Err(format!("插件 id 无效: {}", manifest.id));
+    }
+    if manifest.metrics.len() > MAX_METRICS {
+        return Err(format!("最多声明 {} 个指标", MAX_METRICS));
+    }
+    executable_path(dir, &manifest)?;
+    Ok(manifest)
+}
+
+fn discover(app: &AppHandle) -> Vec<PluginInfo> {
+    let Ok(root) = plugins_dir(app) else {
+        return Vec::new();
+    };
+    let enabled = crate::settings::get().enabled_plugins;
+    let Ok(entries) = std::fs::read_dir(&root) else {
+        return Vec::new();
+    };
+
+    let mut plugins: Vec<PluginInfo> = entries
+        .flatten()
+        .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+        .map(|entry| {
+            let dir = entry.file_name().to_string_lossy().to_string();
+            match load_manifest(&entry.path()) {
+                Ok(manifest) => PluginInfo {
+                    enabled: enabled.contains(&manifest.id),
+                    dir,
+                    manifest: Some(manifest),
+                    error: None,
+                },
+                Err(e) => PluginInfo {
+                    dir,
+                    manifest: None,
+                    enabled: false,
+                    error: Some(e),
+                },
+            }
+        })
+        .collect();
+    plugins.sort_by(|a, b| a.dir.cmp(&b.dir));
+    plugins
+}
+
+/// 已启用的指定类型插件：(插件目录, 清单)
+fn enabled_plugins(app: &AppHandle, kind: PluginKind) -> Vec<(PathBuf, PluginManifest)> {
+    let Ok(root) = plugins_dir(app) else {
+        return Vec::new();
+    };
+    discover(app)
+        .into_iter()
+        .filter(|p| p.enabled)
+        .filter_map(|p| Some((root.join(&p.dir), p.manifest?)))
+        .filter(|(_, m)| m.kind == kind)
+        .collect()
+}
+
+// ==================== 沙箱 ====================
+
+/// Windows 作业对象：限制内存、禁止创建子进程，句柄关闭时结束插件
+#[cfg(target_os = "windows")]
+struct Job(windows::Win32::Foundation::HANDLE);
+
+#[cfg(target_os = "windows")]
+impl Job {
+    /// 插件以挂起状态启动：先加入作业对象并降为低完整性级别，再恢复运行，
+    /// 插件在受限之前执行不了任何代码
+    fn confine(child: &Child) -> Result<Job, String> {
+        let job = Job::assign(child)?;
+        lower_integrity(child)?;
+        resume(child.id())?;
+        Ok(job)
+    }
+
+    fn assign(child: &Child) -> Result<Job, String> {
+        use std::os::windows::io::AsRawHandle;
+        use windows::Win32::Foundation::HANDLE;
+        use windows::Win32::System::JobObjects::{
+            AssignProcessToJobObject, CreateJobObjectW, JobObjectExtendedLimitInformation,
+            SetInformationJobObject, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+            JOB_OBJECT_LIMIT_ACTIVE_PROCESS, JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
+            JOB_OBJECT_LIMIT_PROCESS_MEMORY,
+        };
+
+        unsafe {
+            let job = Job(CreateJobObjectW(None, None).map_err(|e| e.to_string())?);
+            let mut limits = JOBOBJECT_EXTENDED_LIMIT_INFORMATION::default();
+            limits.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE
+                | JOB_OBJECT_LIMIT_ACTIVE_PROCESS
+                | JOB_OBJECT_LIMIT_PROCESS_MEMORY;
+            limits.BasicLimitInformation.ActiveProcessLimit = 1;
+            limits.ProcessMemoryLimit = MEMORY_LIMIT_BYTES;
+            SetInformationJobObject(
+                job.0,
+                JobObjectExtendedLimitInformation,
+                &limits as *const _ as *const std::ffi::c_void,
+                std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+            )
+            .map_err(|e| e.to_string())?;
+            AssignProcessToJobObject(job.0, HANDLE(child.as_raw_handle()))
+                .map_err(|e| e.to_string())?;
+            Ok(job)
+        }
+    }
+}
+
+/// 把插件进程令牌降为低完整性级别，不能写入用户目录和注册表，也不能向普通进程发窗口消息
+#[cfg(target_os = "windows")]
+fn lower_integrity(child: &Child) -> Result<(), String> {
+    use std::os::windows::io::AsRawHandle;
+    use windows::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows::Win32::Security::{
+        CreateWellKnownSid, SetTokenInformation, TokenIntegrityLevel, WinLowLabelSid, PSID,
+        SID_AND_ATTRIBUTES, TOKEN_ADJUST_DEFAULT, TOKEN_MANDATORY_LABEL, TOKEN_QUERY,
+    };
+    use windows::Win32::System::Threading::OpenProcessToken;
+    /// SE_GROUP_INTEGRITY
+    const GROUP_INTEGRITY: u32 = 0x20;
+
+    unsafe {
+        let mut token = HANDLE::default();
+        OpenProcessToken(
+            HANDLE(child.as_raw_handle()),
+            TOKEN_ADJUST_DEFAULT | TOKEN_QUERY,
+            &mut token,
+        )
+        .map_err(|e| e.to_string())?;
+
+        // SECURITY_MAX_SID_SIZE
+        let mut sid = [0u8; 68];
+        let mut sid_len = sid.len() as u32;
+        let sid_ptr = PSID(sid.as_mut_ptr() as *mut std::ffi::c_void);
+        let result = CreateWellKnownSid(WinLowLabelSid, PSID::default(), sid_ptr, &mut sid_len)
+            .and_then(|_| {
+                let label = TOKEN_MANDATORY_LABEL {
+                    Label: SID_AND_ATTRIBUTES {
+                        Sid: sid_ptr,
+                        Attributes: GROUP_INTEGRITY,
+                    },
+                };
+                SetTokenInformation(
+                    token,
+                    TokenIntegrityLevel,
+                    &label as *const _ as *const std::ffi::c_void,
+                    std::mem::size_of::<TOKEN_MANDATORY_LABEL>() as u32 + sid_len,
+                )
+            });
+        let _ = CloseHandle(token);
+        result.map_err(|e| e.to_string())
+    }
+}
+
+/// 恢复以 CREATE_SUSPENDED 启动的进程的主线程
+#[cfg(target_os = "windows")]
+pub(crate) fn resume(pid: u32) -> Result<(), String> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Thread32First, Thread32Next, TH32CS_SNAPTHREAD, THREADENTRY32,
+    };
+    use windows::Win32::System::Threading::{OpenThread, ResumeThread, THREAD_SUSPEND_RESUME};
+
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0).map_err(|e| e.to_string())?;
+        let mut entry = THREADENTRY32 {
+            dwSize: std::mem::size_of::<THREADENTRY32>() as u32,
+            ..Default::default()
+        };
+        let mut resumed = false;
+        let mut more = Thread32First(snapshot, &mut entry).is_ok();
+        while more {
+            if entry.th32OwnerProcessID == pid {
+                if let Ok(thread) = OpenThread(THREAD_SUSPEND_RESUME, false, entry.th32ThreadID) {
+                    resumed |= ResumeThread(thread) != u32::MAX;
+                    let _ = CloseHandle(thread);
+                }
+            }
+            more = Thread32Next(snapshot, &mut entry).is_ok();
+        }
+        let _ = CloseHandle(snapshot);
+        if resumed {
+            Ok(())
+        } else {
+            Err("无法恢复挂起的进程".to_string())
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl Drop for Job {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = windows::Win32::Foundation::CloseHandle(self.0);
+        }
+    }
+}
+
+/// 在沙箱中运行的插件进程
+struct Sandboxed {
+    child: Child,
+    #[cfg(target_os = "windows")]
+    _job: Job,
+}
+
+impl Sandboxed {
+    /// 以插件目录为工作目录、精简环境变量启动插件
+    fn spawn(dir: &Path, manifest: &PluginManifest, stdin: Stdio) -> Result<Sandboxed, String> {
+        let exe = executable_path(dir, manifest)?;
+        let mut cmd = Command::new(exe);
+        cmd.args(&manifest.args)
+            .current_dir(dir)
+            .env_clear()
+            .env("GAMEBENCH_PLUGIN_API", "1")
+            .stdin(stdin)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null());
+        for key in ["SystemRoot", "TEMP", "TMP"] {
+            if let Ok(value) = std::env::var(key) {
+                cmd.env(key, value);
+            }
+        }
+        #[cfg(target_os = "windows")]
+        {
+            use std::os::windows::process::CommandExt;
+            // CREATE_NO_WINDOW | CREATE_SUSPENDED，受限后才恢复运行
+            cmd.creation_flags(0x08000000 | 0x00000004);
+        }
+
+        let child = cmd
+            .spawn()
+            .map_err(|e| format!("无法启动插件 {}: {}", manifest.id, e))?;
+        #[cfg(target_os = "windows")]
+        let job = match Job::confine(&child) {
+            Ok(job) => job,
+            Err(e) => {
+                let mut child = child;
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("无法限制插件 {}: {}", manifest.id, e));
+            }
+        };
+        Ok(Sandboxed {
+            child,
+            #[cfg(target_os = "windows")]
+            _job: job,
+        })
+    }
+
+    fn kill(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// 读取一行，超过长度上限时返回错误
+fn read_line_limited(reader: &mut impl BufRead, buf: &mut String) -> Result<usize, String> {
+    buf.clear();
+    let n = reader
+        .take(MAX_LINE_BYTES + 1)
+        .read_line(buf)
+        .map_err(|e| e.to_string())?;
+    if n as u64 > MAX_LINE_BYTES {
+        return Err("输出行过长".to_string());
+    }
+    Ok(n)
+}
+
+// ==================== 遥测插件 ====================
+
+#[derive(Default)]
+struct Accum {
+    min: f64,
+    max: f64,
+    sum: f64,
+    samples: u32,
+}
+
+impl Accum {
+    fn push(&mut self, value: f64) {
+        if self.samples == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+        self.sum += value;
+        self.samples += 1;
+    }
+}
+
+#[derive(Default)]
+struct HostState {
+    /// 最近读数，键为 `插件id.指标`
+    latest: BTreeMap<String, f64>,
+    accum: HashMap<(String, String), Accum>,
+}
+
+struct RunningPlugin {
+    manifest: PluginManifest,
+    process: Sandboxed,
+    reader: Option<JoinHandle<()>>,
+}
+
+/// 监测期间运行已启用的遥测插件
+pub struct TelemetryHost {
+    plugins: Vec<RunningPlugin>,
+    state: Arc<Mutex<HostState>>,
+}
+
+impl TelemetryHost {
+    pub fn start(app: &AppHandle) -> Self {
+        let state = Arc::new(Mutex::new(HostState::default()));
+        let mut plugins = Vec::new();
+
+        for (dir, manifest) in enabled_plugins(app, PluginKind::Telemetry) {
+            let mut process = match Sandboxed::spawn(&dir, &manifest, Stdio::null()) {
+                Ok(p) => p,
+                Err(e) => {
+                    log::warn!("{}", e);
+                    continue;
+                }
+            };
+            let Some(stdout) = process.child.stdout.take() else {
+                process.kill();
+                continue;
+            };
+            let reader = {
+                let state = state.clone();
+                let manifest = manifest.clone();
+                std::thread::spawn(move || read_telemetry(stdout, &manifest, &state))
+            };
+            log::info!("已启动遥测插件 {} {}", manifest.id, manifest.version);
+            plugins.push(RunningPlugin {
+                manifest,
+                process,
+                reader: Some(reader),
+            });
+        }
+
+        TelemetryHost { plugins, state }
+    }
+
+    /// 最近读数，附在实时快照中
+    pub fn latest(&self) -> BTreeMap<String, f64> {
+        self.state.lock().unwrap().latest.clone()
+    }
+
+    /// 结束插件并汇总测试期间的读数
+    pub fn finish(mut self) -> Vec<PluginMetricSummary> {
+        for plugin in &mut self.plugins {
+            plugin.process.kill();
+            if let Some(reader) = plugin.reader.take() {
+                let _ = reader.join();
+            }
+        }
+
+        let state = self.state.lock().unwrap();
+        let mut summaries = Vec::new();
+        for plugin in &self.plugins {
+            for metric in &plugin.manifest.metrics {
+                let key = (plugin.manifest.id.clone(), metric.key.clone());
+                let Some(acc) = state.accum.get(&key).filter(|a| a.samples > 0) else {
+                    continue;
+                };
+                summaries.push(PluginMetricSummary {
+                    plugin_id: plugin.manifest.id.clone(),
+                    key: metric.key.clone(),
+                    label: metric.label.clone(),
+                    unit: metric.unit.clone(),
+                    min: (acc.min * 10.0).round() / 10.0,
+                    avg: (acc.sum / acc.samples as f64 * 10.0).round() / 10.0,
+                    max: (acc.max * 10.0).round() / 10.0,
+                    samples: acc.samples,
+                });
+            }
+        }
+        summaries
+    }
+}
+
+impl Drop for TelemetryHost {
+    fn drop(&mut self) {
+        for plugin in &mut self.plugins {
+            plugin.process.kill();
+        }
+    }
+}
+
+fn read_telemetry(
+    stdout: std::process::ChildStdout,
+    manifest: &PluginManifest,
+    state: &Mutex<HostState>,
+) {
+    let mut reader = BufReader::new(stdout);
+    let mut line = String::new();
+    loop {
+        match read_line_limited(&mut reader, &mut line) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(e) => {
+                log::warn!("遥测插件 {} 已停止读取: {}", manifest.id, e);
+                break;
+            }
+        }
+        let Ok(parsed) = serde_json::from_str::<TelemetryLine>(line.trim()) else {
+            continue;
+        };
+
+        let mut state = state.lock().unwrap();
+        for metric in &manifest.metrics {
+            let Some(value) = parsed.metrics.get(&metric.key).filter(|v| v.is_finite()) else {
+                continue;
+            };
+            state
+                .latest
+                .insert(format!("{}.{}", manifest.id, metric.key), *value);
+            state
+                .accum
+                .entry((manifest.id.clone(), metric.key.clone()))
+                .or_default()
+                .push(*value);
+        }
+    }
+}
+
+// ==================== 导出插件 ====================
+
+/// 把脱敏后的会话 JSON 写入插件 stdin，等待退出并读取结果消息
+/// 读写都在单独线程中进行，插件不读 stdin 或输出过多时也不会阻塞超时判断
+fn run_exporter(
+    dir: &Path,
+    manifest: &PluginManifest,
+    session: &FpsSession,
+) -> Result<String, String> {
+    let redacted = crate::privacy::redact(session, crate::settings::get().privacy)?;
+    let json = serde_json::to_vec(&redacted).map_err(|e| e.to_string())?;
+
+    let mut process = Sandboxed::spawn(dir, manifest, Stdio::piped())?;
+    if let Some(mut stdin) = process.child.stdin.take() {
+        std::thread::spawn(move || {
+            let _ = stdin.write_all(&json);
+        });
+    }
+    let reader = process.child.stdout.take().map(|stdout| {
+        std::thread::spawn(move || {
+            let mut reader = BufReader::new(stdout);
+            let mut line = String::new();
+            let mut message = String::new();
+            while read_line_limited(&mut reader, &mut line).unwrap_or(0) > 0 {
+                if let Ok(result) = serde_json::from_str::<ExporterResult>(line.trim()) {
+                    message = result.message;
+                }
+            }
+            message
+        })
+    });
+
+    let start = Instant::now();
+    let status = loop {
+        match process.child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if start.elapsed() >= EXPORT_TIMEOUT => {
+                process.kill();
+                return Err(format!("导出插件 {} 超时", manifest.id));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(100)),
+            Err(e) => return Err(e.to_string()),
+        }
+    };
+    let message = reader.and_then(|r| r.join().ok()).unwrap_or_default();
+    if !status.success() {
+        return Err(format!("导出插件 {} 失败: {}", manifest.id, status));
+    }
+    Ok(message)
+}
+
+/// 会话保存后在后台依次运行已启用的导出插件
+pub fn run_exporters(app: &AppHandle, session: &FpsSession) {
+    let exporters = enabled_plugins(app, PluginKind::Exporter);
+    if exporters.is_empty() {
+        return;
+    }
+    let session = session.clone();
+    std::thread::spawn(move || {
+        for (dir, manifest) in exporters {
+            match run_exporter(&dir, &manifest, &session) {
+                Ok(message) => log::info!("导出插件 {}: {}", manifest.id, message),
+                Err(e) => log::warn!("{}", e),
+            }
+        }
+    });
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 列出插件目录中的插件
+#[tauri::command]
+pub fn list_plugins(app: AppHandle) -> Result<Vec<PluginInfo>, String> {
+    Ok(discover(&app))
+}
+
+/// 启用/停用插件（按用户 profile 保存），下次监测生效
+#[tauri::command]
+pub fn set_plugin_enabled(app: AppHandle, plugin_id: String, enabled: bool) -> Result<(), String> {
+    crate::kiosk::ensure_unlocked()?;
+    if !discover(&app)
+        .iter()
+        .any(|p| p.manifest.as_ref().is_some_and(|m| m.id == plugin_id))
+    {
+        return Err(format!("未找到插件 {}", plugin_id));
+    }
+    let mut settings = crate::settings::get();
+    settings.enabled_plugins.retain(|id| *id != plugin_id);
+    if enabled {
+        settings.enabled_plugins.push(plugin_id);
+    }
+    crate::settings::save(&app, settings)
+}
+
+/// 手动用导出插件导出已保存的会话，返回插件输出的消息
+#[tauri::command]
+pub async fn export_with_plugin(
+    app: AppHandle,
+    plugin_id: String,
+    session_id: String,
+) -> Result<String, String> {
+    let (dir, manifest) = enabled_plugins(&app, PluginKind::Exporter)
+        .into_iter()
+        .find(|(_, m)| m.id == plugin_id)
+        .ok_or_else(|| format!("导出插件 {} 未启用", plugin_id))?;
+    let session = crate::storage::load_session(&app, &session_id)?;
+    tauri::async_runtime::spawn_blocking(move || run_exporter(&dir, &manifest, &session))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// 插件目录路径，供用户放置插件
+#[tauri::command]
+pub fn get_plugins_dir(app: AppHandle) -> Result<String, String> {
+    Ok(plugins_dir(&app)?.to_string_lossy().to_string())
+}
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use tauri::{AppHandle, Emitter};
+
+/// 检测期间系统睡眠后的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SleepBehavior {
+    /// 唤醒后重新启动 PresentMon，继续记录同一个会话（睡眠时长不计入）
+    Resume,
+    /// 睡眠时立即结束会话，并标记为被睡眠中断
+    #[default]
+    Finalize,
+}
+
+fn app_handle() -> &'static OnceLock<AppHandle> {
+    static APP: OnceLock<AppHandle> = OnceLock::new();
+    &APP
+}
+
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn on_suspend() {
+    crate::fps_monitor::on_system_suspend();
+    if let Some(app) = app_handle().get() {
+        let _ = app.emit("system-suspend", ());
+    }
+}
+
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn on_resume() {
+    crate::fps_monitor::on_system_resume();
+    if let Some(app) = app_handle().get() {
+        let _ = app.emit("system-resume", ());
+    }
+}
+
+// ==================== 睡眠/唤醒通知 (Windows) ====================
+
+#[cfg(target_os = "windows")]
+unsafe extern "system" fn power_callback(
+    _context: *const core::ffi::c_void,
+    event: u32,
+    _setting: *const core::ffi::c_void,
+) -> u32 {
+    use windows::Win32::UI::WindowsAndMessaging::{PBT_APMRESUMEAUTOMATIC, PBT_APMSUSPEND};
+
+    // 回调在系统线程中执行，需尽快返回（睡眠前只有约 2 秒）
+    match event {
+        PBT_APMSUSPEND => on_suspend(),
+        PBT_APMRESUMEAUTOMATIC => on_resume(),
+        _ => {}
+    }
+    0
+}
+
+/// 注册睡眠/唤醒回调（进程生命周期内有效，不注销）
+#[cfg(target_os = "windows")]
+pub fn register(app: &AppHandle) {
+    use windows::Win32::Foundation::{ERROR_SUCCESS, HANDLE};
+    use windows::Win32::System::Power::{
+        PowerRegisterSuspendResumeNotification, DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::DEVICE_NOTIFY_CALLBACK;
+
+    if app_handle().set(app.clone()).is_err() {
+        return;
+    }
+    // 系统在注册期间持有该结构体的指针
+    let params: &'static mut DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS =
+        Box::leak(Box::new(DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS {
+            Callback: Some(power_callback),
+            Context: std::ptr::null_mut(),
+        }));
+    let mut handle = std::ptr::null_mut();
+    let result = unsafe {
+        PowerRegisterSuspendResumeNotification(
+            DEVICE_NOTIFY_CALLBACK,
+            HANDLE(params as *mut _ as *mut core::ffi::c_void),
+            &mut handle,
+        )
+    };
+    if result == ERROR_SUCCESS {
+        log::info!("已注册系统睡眠/唤醒通知");
+    } else {
+        log::warn!("注册系统睡眠/唤醒通知失败: {:?}", result);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn register(app: &AppHandle) {
+    let _ = app_handle().set(app.clone());
+}
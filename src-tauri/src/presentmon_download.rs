@@ -0,0 +1,104 @@
+use crate::tasks::{self, TaskHandle, TaskKind};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+/// 未捆绑 PresentMon 时下载的版本
+const PRESENTMON_VERSION: &str = "2.3.0";
+/// 官方发布的 PresentMon-2.3.0-x64.exe 的 SHA-256（小写十六进制）
+/// 经第三方镜像下载、且会以管理员权限运行，必须与此值一致才会保存；升级版本时一并更新
+const PRESENTMON_SHA256: &str = "";
+
+fn release_url() -> String {
+    format!(
+        "https://github.com/GameTechDev/PresentMon/releases/download/v{0}/PresentMon-{0}-x64.exe",
+        PRESENTMON_VERSION
+    )
+}
+
+/// 下载后的 PresentMon 存放位置（应用数据目录，不随更新覆盖）
+pub fn downloaded_path(app: &AppHandle) -> Option<PathBuf> {
+    let dir = app.path().app_data_dir().ok()?;
+    Some(dir.join("bin").join("PresentMon.exe"))
+}
+
+async fn download(task: TaskHandle, target: PathBuf) -> Result<String, String> {
+    if let Some(dir) = target.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("无法创建目录: {}", e))?;
+    }
+
+    // 未内置校验值时不下载，避免运行无法验证来源的可执行文件
+    if PRESENTMON_SHA256.len() != 64 {
+        return Err(format!(
+            "此版本未内置 PresentMon {} 的校验值，无法安全下载，请手动安装 PresentMon",
+            PRESENTMON_VERSION
+        ));
+    }
+
+    let urls = crate::mirrors::url_candidates(&release_url()).await;
+    let (url, mut response) = crate::mirrors::get_with(&urls, |r| r)
+        .await
+        .map_err(|e| format!("下载 PresentMon 失败: {}", e))?;
+    let total = response.content_length().unwrap_or(0);
+
+    let part = target.with_extension("part");
+    let mut file = std::fs::File::create(&part).map_err(|e| format!("无法写入文件: {}", e))?;
+    let mut downloaded = 0u64;
+    let mut hasher = Sha256::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("下载 PresentMon 中断: {}", e))?
+    {
+        if task.is_cancelled() {
+            drop(file);
+            let _ = std::fs::remove_file(&part);
+            return Err(tasks::CANCELLED.to_string());
+        }
+        file.write_all(&chunk).map_err(|e| e.to_string())?;
+        hasher.update(&chunk);
+        downloaded += chunk.len() as u64;
+        task.progress(downloaded, total, format!("正在从 {} 下载", url));
+    }
+    drop(file);
+
+    // 代理出错时可能返回 HTML 页面，至少确认是 Windows 可执行文件
+    let header = std::fs::read(&part)
+        .map(|b| b.get(..2) == Some(b"MZ"))
+        .unwrap_or(false);
+    if !header || (total > 0 && downloaded != total) {
+        let _ = std::fs::remove_file(&part);
+        return Err(format!("从 {} 下载的文件无效，请更换镜像后重试", url));
+    }
+    let digest = crate::signing::hex(&hasher.finalize());
+    if !digest.eq_ignore_ascii_case(PRESENTMON_SHA256) {
+        let _ = std::fs::remove_file(&part);
+        log::warn!("PresentMon 校验失败: {} (来自 {})", digest, url);
+        return Err(format!(
+            "从 {} 下载的 PresentMon 校验值不匹配，可能已被篡改，已删除",
+            url
+        ));
+    }
+    std::fs::rename(&part, &target).map_err(|e| e.to_string())?;
+
+    log::info!(
+        "已下载 PresentMon {}: {}",
+        PRESENTMON_VERSION,
+        target.display()
+    );
+    Ok(target.to_string_lossy().to_string())
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 通过镜像下载 PresentMon，返回任务 ID；任务结果为保存路径
+#[tauri::command]
+pub async fn download_presentmon(app: AppHandle) -> Result<String, String> {
+    crate::kiosk::ensure_unlocked()?;
+    crate::connectivity::ensure_online()?;
+    let target = downloaded_path(&app).ok_or_else(|| "无法获取应用数据目录".to_string())?;
+    tasks::spawn_async(&app, TaskKind::Download, "下载 PresentMon", move |task| {
+        download(task, target)
+    })
+}
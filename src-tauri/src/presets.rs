@@ -0,0 +1,128 @@
+use crate::fps_monitor::{BenchmarkPlan, MonitorOptions, TelemetrySet};
+use serde::{Deserialize, Serialize};
+
+// ==================== 数据结构 ====================
+
+/// 按游戏类型调校的基准测试预设
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkPreset {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// 采集时长 (秒)
+    pub duration_secs: f64,
+    /// 预热时长 (秒)
+    pub warmup_secs: f64,
+    /// 实时快照推送间隔 (秒)
+    pub snapshot_interval_secs: f64,
+    pub telemetry: TelemetrySet,
+    /// 额外计算的帧时间百分位
+    pub frametime_percentiles: Vec<f64>,
+    /// 自定义 Low FPS 百分比
+    pub low_percentiles: Vec<f64>,
+}
+
+impl BenchmarkPreset {
+    /// 按预设填充监测选项，传入 `plan` 时覆盖预设的时长（未指定预热时仍用预设值）
+    /// 已指定的百分位保留，与预设的百分位合并
+    pub fn apply(&self, options: &mut MonitorOptions, plan: Option<BenchmarkPlan>) {
+        options.benchmark = Some(match plan {
+            Some(plan) => BenchmarkPlan {
+                warmup_secs: plan.warmup_secs.or(Some(self.warmup_secs)),
+                duration_secs: plan.duration_secs,
+            },
+            None => BenchmarkPlan {
+                warmup_secs: Some(self.warmup_secs),
+                duration_secs: self.duration_secs,
+            },
+        });
+        options.snapshot_interval_secs = Some(self.snapshot_interval_secs);
+        options.telemetry = Some(self.telemetry.clone());
+        options.preset = Some(self.id.clone());
+        merge(
+            &mut options.frametime_percentiles,
+            &self.frametime_percentiles,
+        );
+        merge(&mut options.low_percentiles, &self.low_percentiles);
+    }
+}
+
+fn merge(target: &mut Vec<f64>, extra: &[f64]) {
+    for &p in extra {
+        if !target.contains(&p) {
+            target.push(p);
+        }
+    }
+}
+
+// ==================== 内置预设 ====================
+
+fn builtin() -> Vec<BenchmarkPreset> {
+    vec![
+        BenchmarkPreset {
+            id: "esports".to_string(),
+            name: "电竞 / 竞技".to_string(),
+            description: "长时间对局，关注帧时间尖峰与延迟：高频快照、DPC 延迟采样，看 P99 / P99.9"
+                .to_string(),
+            duration_secs: 600.0,
+            warmup_secs: 15.0,
+            snapshot_interval_secs: 0.5,
+            telemetry: TelemetrySet {
+                sensors: true,
+                hardware_state: true,
+                memory_pressure: false,
+                background_activity: true,
+                dpc_latency: true,
+                plugins: false,
+            },
+            frametime_percentiles: vec![99.0, 99.9],
+            low_percentiles: vec![],
+        },
+        BenchmarkPreset {
+            id: "aaa".to_string(),
+            name: "3A 单机".to_string(),
+            description: "固定场景短时测试，关注百分位帧时间与 5% Low，适合画质/驱动对比"
+                .to_string(),
+            duration_secs: 90.0,
+            warmup_secs: 10.0,
+            snapshot_interval_secs: 1.0,
+            telemetry: TelemetrySet::default(),
+            frametime_percentiles: vec![50.0, 95.0, 99.0],
+            low_percentiles: vec![5.0],
+        },
+        BenchmarkPreset {
+            id: "soak".to_string(),
+            name: "长时间稳定性".to_string(),
+            description: "1 小时烤机，观察温度、降频与内存压力随时间的变化，快照降为每 5 秒一次"
+                .to_string(),
+            duration_secs: 3600.0,
+            warmup_secs: 60.0,
+            snapshot_interval_secs: 5.0,
+            telemetry: TelemetrySet {
+                sensors: true,
+                hardware_state: true,
+                memory_pressure: true,
+                background_activity: true,
+                dpc_latency: false,
+                plugins: true,
+            },
+            frametime_percentiles: vec![99.0],
+            low_percentiles: vec![5.0],
+        },
+    ]
+}
+
+pub fn find(id: &str) -> Result<BenchmarkPreset, String> {
+    builtin()
+        .into_iter()
+        .find(|p| p.id == id)
+        .ok_or_else(|| format!("未知的基准测试预设: {}", id))
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 列出内置的基准测试预设
+#[tauri::command]
+pub fn list_benchmark_presets() -> Result<Vec<BenchmarkPreset>, String> {
+    Ok(builtin())
+}
@@ -0,0 +1,410 @@
+use crate::fps_monitor::FpsSession;
+use crate::tasks::{self, TaskKind};
+use crate::units::DecimalSeparator;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use sysinfo::System;
+use tauri::{AppHandle, Manager};
+
+/// 设备实例 ID 字段：保留硬件 ID，去掉机器唯一的实例部分
+const DEVICE_ID_KEYS: &[&str] = &["device_id", "render_gpu_id"];
+/// 只在本机有意义、分享时无用的字段
+const LOCAL_ONLY_KEYS: &[&str] = &["window_handle", "pid", "luid", "gpu_luid"];
+
+// ==================== 数据结构 ====================
+
+/// 导出与分享报告时的隐私选项
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrivacyOptions {
+    /// 去掉计算机名
+    pub strip_machine_name: bool,
+    /// 将路径中的用户名替换为 <user>
+    pub strip_user_paths: bool,
+    /// 去掉设备序列号 / 实例 ID 等可识别单台机器的信息
+    pub strip_serials: bool,
+}
+
+impl Default for PrivacyOptions {
+    fn default() -> Self {
+        PrivacyOptions {
+            strip_machine_name: true,
+            strip_user_paths: true,
+            strip_serials: true,
+        }
+    }
+}
+
+/// 单个会话导出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    /// 签名报告，附逐帧帧时间
+    #[default]
+    Json,
+    /// 摘要 + 逐帧帧时间，便于在 Excel / Python 中分析
+    Csv,
+}
+
+impl ExportFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+        }
+    }
+}
+
+// ==================== 脱敏 ====================
+
+struct Redactor {
+    options: PrivacyOptions,
+    /// 计算机名（小写）
+    machine: Option<String>,
+    /// 当前用户名（小写）
+    user: Option<String>,
+}
+
+impl Redactor {
+    fn new(options: PrivacyOptions) -> Self {
+        let non_trivial = |s: String| (s.len() >= 3).then(|| s.to_lowercase());
+        Redactor {
+            options,
+            machine: System::host_name().and_then(non_trivial),
+            user: std::env::var("USERNAME")
+                .or_else(|_| std::env::var("USER"))
+                .ok()
+                .and_then(non_trivial),
+        }
+    }
+
+    fn redact_str(&self, text: &str) -> String {
+        let mut out = text.to_string();
+        if self.options.strip_user_paths {
+            out = replace_user_dirs(&out);
+            if let Some(user) = &self.user {
+                out = replace_ignore_case(&out, user, "<user>");
+            }
+        }
+        if self.options.strip_machine_name {
+            if let Some(machine) = &self.machine {
+                out = replace_ignore_case(&out, machine, "<machine>");
+            }
+        }
+        out
+    }
+
+    fn redact(&self, value: &mut Value) {
+        match value {
+            Value::String(s) => *s = self.redact_str(s),
+            Value::Array(items) => items.iter_mut().for_each(|v| self.redact(v)),
+            Value::Object(map) => {
+                for (key, v) in map.iter_mut() {
+                    if self.options.strip_serials {
+                        if LOCAL_ONLY_KEYS.contains(&key.as_str()) {
+                            // 非 Option 的字符串字段置空，其余置为 null
+                            *v = match v {
+                                Value::String(_) => Value::String(String::new()),
+                                _ => Value::Null,
+                            };
+                            continue;
+                        }
+                        if let (true, Value::String(id)) =
+                            (DEVICE_ID_KEYS.contains(&key.as_str()), &*v)
+                        {
+                            *v = Value::String(strip_instance_id(id));
+                        }
+                    }
+                    self.redact(v);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// "PCI\VEN_10DE&DEV_2504&SUBSYS_...\4&2F3A1B&0&0008" → "PCI\VEN_10DE&DEV_2504&SUBSYS_..."
+fn strip_instance_id(id: &str) -> String {
+    match id.match_indices('\\').nth(1) {
+        Some((idx, _)) => id[..idx].to_string(),
+        None => id.to_string(),
+    }
+}
+
+/// 替换 "\Users\<name>\" 与 "/home/<name>/" 中的用户名（不限于当前用户）
+fn replace_user_dirs(text: &str) -> String {
+    let lower = text.to_lowercase();
+    let mut out = String::with_capacity(text.len());
+    let mut pos = 0;
+    while pos < text.len() {
+        let next = ["\\users\\", "/users/", "/home/"]
+            .iter()
+            .filter_map(|marker| lower[pos..].find(marker).map(|i| (pos + i, marker.len())))
+            .min();
+        let Some((start, len)) = next else {
+            break;
+        };
+        let name_start = start + len;
+        let name_end = text[name_start..]
+            .find(['\\', '/', '"', ' '])
+            .map(|i| name_start + i)
+            .unwrap_or(text.len());
+        out.push_str(&text[pos..name_start]);
+        if name_end > name_start {
+            out.push_str("<user>");
+        }
+        pos = name_end;
+    }
+    out.push_str(&text[pos..]);
+    out
+}
+
+/// 大小写不敏感替换（needle 已为小写）
+fn replace_ignore_case(text: &str, needle: &str, with: &str) -> String {
+    let lower = text.to_lowercase();
+    // 小写转换改变了字节长度（少见的 Unicode 字符）时不做替换，避免切错边界
+    if lower.len() != text.len() {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut pos = 0;
+    while let Some(i) = lower[pos..].find(needle) {
+        out.push_str(&text[pos..pos + i]);
+        out.push_str(with);
+        pos += i + needle.len();
+    }
+    out.push_str(&text[pos..]);
+    out
+}
+
+/// 生成脱敏后的会话副本
+pub fn redact(session: &FpsSession, options: PrivacyOptions) -> Result<FpsSession, String> {
+    let mut value = serde_json::to_value(session).map_err(|e| e.to_string())?;
+    Redactor::new(options).redact(&mut value);
+    serde_json::from_value(value).map_err(|e| e.to_string())
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 分享前预览脱敏结果；未传入选项时使用设置中的隐私选项
+#[tauri::command]
+pub fn redact_session(
+    session: FpsSession,
+    options: Option<PrivacyOptions>,
+) -> Result<FpsSession, String> {
+    redact(
+        &session,
+        options.unwrap_or_else(|| crate::settings::get().privacy),
+    )
+}
+
+/// 将会话脱敏、签名后写入 `dir`，返回文件路径
+pub fn write_export(
+    session: &FpsSession,
+    options: PrivacyOptions,
+    dir: &Path,
+) -> Result<PathBuf, String> {
+    let path = dir.join(format!("gamebench-{}.json", session.session_id));
+    write_export_to(session, None, options, &path, ExportFormat::Json)?;
+    Ok(path)
+}
+
+/// 帧时间附在签名报告之后，校验签名时忽略
+#[derive(Serialize)]
+struct JsonExport<T: Serialize> {
+    #[serde(flatten)]
+    report: T,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frame_times_ms: Option<Vec<f64>>,
+}
+
+/// 将会话脱敏后按格式写入 `path`；有原始帧时间时一并导出
+pub fn write_export_to(
+    session: &FpsSession,
+    frames: Option<&[f64]>,
+    options: PrivacyOptions,
+    path: &Path,
+    format: ExportFormat,
+) -> Result<(), String> {
+    let redacted = redact(session, options)?;
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| format!("无法创建导出目录: {}", e))?;
+    }
+    let bytes = match format {
+        // 脱敏在签名之前，校验时不需要原始数据；旧版本录制的会话没有采集流程记录，按未签名导出
+        ExportFormat::Json => {
+            let frame_times_ms = frames.map(<[f64]>::to_vec);
+            match crate::signing::sign(&redacted) {
+                Ok(report) => serde_json::to_vec_pretty(&JsonExport {
+                    report,
+                    frame_times_ms,
+                }),
+                Err(e) => {
+                    log::warn!("{}", e);
+                    serde_json::to_vec_pretty(&JsonExport {
+                        report: &redacted,
+                        frame_times_ms,
+                    })
+                }
+            }
+            .map_err(|e| e.to_string())?
+        }
+        ExportFormat::Csv => write_csv(&redacted, frames.unwrap_or_default())?,
+    };
+    std::fs::write(path, bytes).map_err(|e| format!("导出失败: {}", e))?;
+    log::info!("已导出会话: {}", path.display());
+    Ok(())
+}
+
+/// CSV：开头为摘要（项目, 值），空一行后为逐帧数据
+/// 按单位设置格式化数字；小数点为逗号时用分号分隔，Excel 可直接打开
+fn write_csv(session: &FpsSession, frames: &[f64]) -> Result<Vec<u8>, String> {
+    let units = crate::units::current();
+    let delimiter = match units.decimal {
+        DecimalSeparator::Dot => b',',
+        DecimalSeparator::Comma => b';',
+    };
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_writer(Vec::new());
+
+    let started_at = chrono::DateTime::from_timestamp_millis(session.started_at_ms as i64)
+        .map(|t| t.with_timezone(&chrono::Local).to_rfc3339())
+        .unwrap_or_default();
+    let summary = [
+        ("session_id", session.session_id.clone()),
+        ("process_name", session.process_name.clone()),
+        ("started_at", started_at),
+        ("duration_secs", units.number(session.duration_secs, 1)),
+        ("total_frames", session.total_frames.to_string()),
+        ("avg_fps", units.number(session.avg_fps, 1)),
+        ("fps_1_low", units.number(session.fps_1_low, 1)),
+        ("fps_01_low", units.number(session.fps_01_low, 1)),
+        ("min_fps", units.number(session.min_fps, 1)),
+        ("max_fps", units.number(session.max_fps, 1)),
+        (
+            "frametime_stdev_ms",
+            units.number(session.pacing.stdev_ms, 2),
+        ),
+        ("frametime_p99_ms", units.number(session.pacing.p99_ms, 2)),
+        ("frametime_p999_ms", units.number(session.pacing.p999_ms, 2)),
+    ];
+    let err = |e: csv::Error| format!("导出失败: {}", e);
+    for (key, value) in &summary {
+        writer.write_record([*key, value.as_str()]).map_err(err)?;
+    }
+    if let Some(qoe) = &session.qoe {
+        writer
+            .write_record(["qoe_score", units.number(qoe.score, 1).as_str()])
+            .map_err(err)?;
+        writer
+            .write_record(["qoe_grade", qoe.grade.label()])
+            .map_err(err)?;
+    }
+    if let Some(present) = &session.present {
+        let flag = |v: Option<bool>| v.map(|v| v.to_string()).unwrap_or_default();
+        writer
+            .write_record(["present_mode", present.present_mode.as_str()])
+            .map_err(err)?;
+        writer
+            .write_record(["vsync", flag(present.vsync).as_str()])
+            .map_err(err)?;
+        writer
+            .write_record(["allows_tearing", flag(present.allows_tearing).as_str()])
+            .map_err(err)?;
+    }
+    if let Some(latency) = &session.latency {
+        for (prefix, summary) in [
+            ("display_latency", &latency.display),
+            ("click_to_photon", &latency.click_to_photon),
+        ] {
+            let Some(summary) = summary else {
+                continue;
+            };
+            for (stat, value) in [
+                ("avg_ms", summary.avg_ms),
+                ("median_ms", summary.median_ms),
+                ("p95_ms", summary.p95_ms),
+            ] {
+                let key = format!("{}_{}", prefix, stat);
+                writer
+                    .write_record([key.as_str(), units.number(value, 2).as_str()])
+                    .map_err(err)?;
+            }
+        }
+    }
+
+    if !frames.is_empty() {
+        writer.write_record([""]).map_err(err)?;
+        writer
+            .write_record(["frame", "elapsed_ms", "frametime_ms"])
+            .map_err(err)?;
+        let mut elapsed = 0.0;
+        for (i, ft) in frames.iter().enumerate() {
+            elapsed += ft;
+            writer
+                .write_record([
+                    (i + 1).to_string(),
+                    units.number(elapsed, 3),
+                    units.number(*ft, 4),
+                ])
+                .map_err(err)?;
+        }
+    }
+    writer.into_inner().map_err(|e| format!("导出失败: {}", e))
+}
+
+/// 导出已保存的会话（脱敏、JSON 带签名），有原始帧时间时附带逐帧数据，返回任务 ID
+/// `path` 为目标文件，未传入时写入下载目录；`format` 默认 JSON
+/// 任务结果为导出的文件路径
+#[tauri::command]
+pub fn export_session(
+    app: AppHandle,
+    session_id: String,
+    options: Option<PrivacyOptions>,
+    path: Option<String>,
+    format: Option<ExportFormat>,
+) -> Result<String, String> {
+    crate::kiosk::ensure_unlocked()?;
+    let format = format.unwrap_or_default();
+    let session = crate::storage::get_session(app.clone(), session_id)?;
+    let path = match path {
+        Some(path) => PathBuf::from(path),
+        None => app
+            .path()
+            .download_dir()
+            .map_err(|e| format!("无法获取下载目录: {}", e))?
+            .join(format!(
+                "gamebench-{}.{}",
+                session.session_id,
+                format.extension()
+            )),
+    };
+    let options = options.unwrap_or_else(|| crate::settings::get().privacy);
+
+    let worker = app.clone();
+    tasks::spawn(&app, TaskKind::Report, "导出报告", move |task| {
+        // 旧版本录制的会话没有保存原始帧时间，只导出摘要
+        let frames = match crate::storage::load_frames(&worker, &session.session_id) {
+            Ok(log) => {
+                let total = log.frame_count() as u64;
+                let mut frames = Vec::with_capacity(log.frame_count());
+                log.for_each_chunk(0, usize::MAX, |chunk| {
+                    frames.extend_from_slice(chunk);
+                    task.progress(frames.len() as u64, total, "读取帧数据");
+                    task.check()
+                })?;
+                Some(frames)
+            }
+            Err(e) => {
+                log::info!("{}", e);
+                None
+            }
+        };
+        task.check()?;
+        write_export_to(&session, frames.as_deref(), options, &path, format)?;
+        Ok(path.to_string_lossy().to_string())
+    })
+}
@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// 活跃状态的缓存时长：Win32_Thread 查询较慢，后台扫描每 5 秒一次，不必每次都对每个游戏查询
+const CACHE_TTL: Duration = Duration::from_secs(15);
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityState {
+    /// 正常运行
+    #[default]
+    Active,
+    /// 进程被挂起（所有线程处于挂起等待）
+    Suspended,
+    /// 窗口最小化/隐藏到托盘，且没有 GPU 占用
+    Idle,
+}
+
+// ==================== 状态检测 (Windows) ====================
+
+/// Win32_Thread.ThreadState: 5 = Waiting
+#[cfg(target_os = "windows")]
+const THREAD_STATE_WAITING: u64 = 5;
+/// Win32_Thread.ThreadWaitReason: 5 = Suspended
+#[cfg(target_os = "windows")]
+const WAIT_REASON_SUSPENDED: u64 = 5;
+
+#[cfg(target_os = "windows")]
+fn is_suspended(pid: u32) -> bool {
+    use crate::drives::variant_u64;
+    use wmi::{COMLibrary, WMIConnection};
+
+    let Ok(com) = COMLibrary::new() else {
+        return false;
+    };
+    let Ok(wmi) = WMIConnection::new(com) else {
+        return false;
+    };
+    let query = format!(
+        "SELECT ThreadState, ThreadWaitReason FROM Win32_Thread WHERE ProcessHandle = '{}'",
+        pid
+    );
+    let threads: Vec<HashMap<String, wmi::Variant>> = wmi.raw_query(&query).unwrap_or_default();
+
+    !threads.is_empty()
+        && threads.iter().all(|t| {
+            variant_u64(t.get("ThreadState")) == Some(THREAD_STATE_WAITING)
+                && variant_u64(t.get("ThreadWaitReason")) == Some(WAIT_REASON_SUSPENDED)
+        })
+}
+
+/// 进程所有 GPU 引擎的当前占用率之和
+#[cfg(target_os = "windows")]
+pub(crate) fn gpu_utilization(pid: u32) -> Option<u64> {
+    use crate::drives::variant_u64;
+    use wmi::{COMLibrary, WMIConnection};
+
+    let com = COMLibrary::new().ok()?;
+    let wmi = WMIConnection::new(com).ok()?;
+    let query = format!(
+        "SELECT UtilizationPercentage FROM Win32_PerfFormattedData_GPUPerformanceCounters_GPUEngine \
+         WHERE Name LIKE 'pid_{}_%'",
+        pid
+    );
+    let results: Vec<HashMap<String, wmi::Variant>> = wmi.raw_query(&query).ok()?;
+    Some(
+        results
+            .iter()
+            .filter_map(|item| variant_u64(item.get("UtilizationPercentage")))
+            .sum(),
+    )
+}
+
+#[cfg(target_os = "windows")]
+fn is_minimized(pid: u32) -> bool {
+    use windows::Win32::UI::WindowsAndMessaging::IsIconic;
+
+    // 没有可见窗口（托盘中的启动器）也视为最小化
+    match crate::game_window::find_main_window(pid) {
+        Some(hwnd) => unsafe { IsIconic(hwnd).as_bool() },
+        None => true,
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn detect_state(pid: u32) -> ActivityState {
+    if is_suspended(pid) {
+        ActivityState::Suspended
+    } else if is_minimized(pid) && gpu_utilization(pid) == Some(0) {
+        ActivityState::Idle
+    } else {
+        ActivityState::Active
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn detect_state(pid: u32) -> ActivityState {
+    let mut sys = sysinfo::System::new();
+    let pid = sysinfo::Pid::from_u32(pid);
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::Some(&[pid]), true);
+    match sys.process(pid).map(|p| p.status()) {
+        Some(sysinfo::ProcessStatus::Stop) => ActivityState::Suspended,
+        _ => ActivityState::Active,
+    }
+}
+
+fn cache() -> &'static Mutex<HashMap<u32, (Instant, ActivityState)>> {
+    static CACHE: OnceLock<Mutex<HashMap<u32, (Instant, ActivityState)>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 判断进程是否处于活跃状态（结果缓存 CACHE_TTL）
+pub fn activity_state(pid: u32) -> ActivityState {
+    let now = Instant::now();
+    {
+        let mut cache = cache().lock().unwrap();
+        cache.retain(|_, (checked, _)| now.duration_since(*checked) < CACHE_TTL);
+        if let Some((_, state)) = cache.get(&pid) {
+            return *state;
+        }
+    }
+    let state = detect_state(pid);
+    cache().lock().unwrap().insert(pid, (now, state));
+    state
+}
@@ -0,0 +1,179 @@
+use sysinfo::{Pid, System};
+
+// ==================== 安全名单 ====================
+
+/// 禁止结束/挂起的系统关键进程（小写）
+/// 误杀这些进程会导致蓝屏、注销或桌面崩溃
+const PROTECTED_PROCESSES: &[&str] = &[
+    "system",
+    "idle",
+    "registry",
+    "smss.exe",
+    "csrss.exe",
+    "wininit.exe",
+    "winlogon.exe",
+    "services.exe",
+    "lsass.exe",
+    "lsaiso.exe",
+    "svchost.exe",
+    "dwm.exe",
+    "explorer.exe",
+    "fontdrvhost.exe",
+    "audiodg.exe",
+    "sihost.exe",
+    "ctfmon.exe",
+    "conhost.exe",
+    "memory compression",
+    "securityhealthservice.exe",
+    "msmpeng.exe",
+    "presentmon.exe",
+];
+
+/// 检查进程是否允许被操作，返回进程名
+fn check_target(sys: &System, pid: u32) -> Result<String, String> {
+    // PID 0/4 为 Windows 内核保留进程
+    if pid == 0 || pid == 4 {
+        return Err(format!("PID {} 是系统保留进程，不允许操作", pid));
+    }
+    if pid == std::process::id() {
+        return Err("不能操作 GameBench 自身进程".to_string());
+    }
+
+    let process = sys
+        .process(Pid::from_u32(pid))
+        .ok_or_else(|| format!("未找到进程 PID {}", pid))?;
+    let name = process.name().to_string_lossy().to_string();
+
+    if PROTECTED_PROCESSES.contains(&name.to_lowercase().as_str()) {
+        return Err(format!("{} 是系统关键进程，已被安全名单保护", name));
+    }
+
+    Ok(name)
+}
+
+fn load_processes() -> System {
+    let mut sys = System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    sys
+}
+
+// ==================== 挂起/恢复 (Windows) ====================
+
+/// 逐个挂起/恢复进程内的所有线程
+#[cfg(target_os = "windows")]
+fn set_process_suspended(pid: u32, suspend: bool) -> Result<(), String> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Thread32First, Thread32Next, TH32CS_SNAPTHREAD, THREADENTRY32,
+    };
+    use windows::Win32::System::Threading::{
+        OpenThread, ResumeThread, SuspendThread, THREAD_SUSPEND_RESUME,
+    };
+
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0)
+            .map_err(|e| format!("无法枚举线程: {}", e))?;
+
+        let mut entry = THREADENTRY32 {
+            dwSize: std::mem::size_of::<THREADENTRY32>() as u32,
+            ..Default::default()
+        };
+
+        let mut touched = 0;
+        if Thread32First(snapshot, &mut entry).is_ok() {
+            loop {
+                if entry.th32OwnerProcessID == pid {
+                    if let Ok(thread) = OpenThread(THREAD_SUSPEND_RESUME, false, entry.th32ThreadID)
+                    {
+                        if suspend {
+                            SuspendThread(thread);
+                        } else {
+                            ResumeThread(thread);
+                        }
+                        let _ = CloseHandle(thread);
+                        touched += 1;
+                    }
+                }
+                if Thread32Next(snapshot, &mut entry).is_err() {
+                    break;
+                }
+            }
+        }
+        let _ = CloseHandle(snapshot);
+
+        if touched == 0 {
+            return Err(format!("无法访问 PID {} 的线程，请以管理员身份运行", pid));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn set_process_suspended(pid: u32, suspend: bool) -> Result<(), String> {
+    let sys = load_processes();
+    let signal = if suspend {
+        sysinfo::Signal::Stop
+    } else {
+        sysinfo::Signal::Continue
+    };
+    match sys
+        .process(Pid::from_u32(pid))
+        .and_then(|p| p.kill_with(signal))
+    {
+        Some(true) => Ok(()),
+        _ => Err(format!("无法向 PID {} 发送信号", pid)),
+    }
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 结束后台占用进程
+/// `confirmed` 必须由前端在用户确认后传入 true
+#[tauri::command]
+pub fn terminate_process(pid: u32, confirmed: bool) -> Result<(), String> {
+    crate::kiosk::ensure_unlocked()?;
+    let sys = load_processes();
+    let name = check_target(&sys, pid)?;
+
+    if !confirmed {
+        return Err(format!("结束 {} (PID {}) 需要用户确认", name, pid));
+    }
+
+    let process = sys
+        .process(Pid::from_u32(pid))
+        .ok_or_else(|| format!("未找到进程 PID {}", pid))?;
+    if !process.kill() {
+        return Err(format!("结束 {} 失败，请以管理员身份运行", name));
+    }
+
+    log::info!("已结束进程: {} (PID {})", name, pid);
+    Ok(())
+}
+
+/// 挂起后台占用进程（可通过 resume_process 恢复）
+#[tauri::command]
+pub fn suspend_process(pid: u32, confirmed: bool) -> Result<(), String> {
+    crate::kiosk::ensure_unlocked()?;
+    let sys = load_processes();
+    let name = check_target(&sys, pid)?;
+
+    if !confirmed {
+        return Err(format!("挂起 {} (PID {}) 需要用户确认", name, pid));
+    }
+
+    set_process_suspended(pid, true)?;
+    log::info!("已挂起进程: {} (PID {})", name, pid);
+    Ok(())
+}
+
+/// 恢复被挂起的进程
+#[tauri::command]
+pub fn resume_process(pid: u32) -> Result<(), String> {
+    let sys = load_processes();
+    let name = check_target(&sys, pid)?;
+
+    set_process_suspended(pid, false)?;
+    log::info!("已恢复进程: {} (PID {})", name, pid);
+    Ok(())
+}
@@ -0,0 +1,251 @@
+use crate::{mirrors, settings};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, Manager};
+
+/// 过滤名单下载接口
+const FILTER_PATH: &str = "/api/process-filter";
+/// 误判上报接口
+const REPORT_PATH: &str = "/api/process-filter/reports";
+
+/// 下载的过滤名单缓存
+const REMOTE_FILE: &str = "process_filter.json";
+/// 用户手动忽略的进程
+const LOCAL_FILE: &str = "process_filter.local.json";
+
+/// 内置系统进程前缀（小写）
+const BUILTIN_PREFIXES: &[&str] = &["system", "svchost", "csrss", "conhost", "runtime"];
+
+/// 内置非游戏进程（小写）: 系统组件、国内常见安全软件/常驻软件、游戏平台客户端
+const BUILTIN_EXACT: &[&str] = &[
+    "explorer.exe",
+    "dwm.exe",
+    "searchhost.exe",
+    "shellexperiencehost.exe",
+    "startmenuexperiencehost.exe",
+    "textinputhost.exe",
+    "applicationframehost.exe",
+    // 安全软件
+    "360tray.exe",
+    "360safe.exe",
+    "360sd.exe",
+    "zhudongfangyu.exe",
+    "qqpcrtp.exe",
+    "qqpctray.exe",
+    "qqpcmgr.exe",
+    "hipstray.exe",
+    "usysdiag.exe",
+    "wsctrlsvc.exe",
+    "kxetray.exe",
+    "kwsprotect64.exe",
+    // 常驻软件
+    "qq.exe",
+    "wechat.exe",
+    "weixin.exe",
+    "dingtalk.exe",
+    "feishu.exe",
+    "yy.exe",
+    "baidunetdisk.exe",
+    "thunder.exe",
+    "sogoucloud.exe",
+    "cloudmusic.exe",
+    "kugou.exe",
+    // 游戏平台客户端
+    "steam.exe",
+    "steamwebhelper.exe",
+    "wegame.exe",
+    "hyp.exe",
+    "epicgameslauncher.exe",
+    "battle.net.exe",
+    "eadesktop.exe",
+    "upc.exe",
+    "galaxyclient.exe",
+];
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProcessFilter {
+    #[serde(default)]
+    pub version: u32,
+    /// 完整进程名（小写）
+    #[serde(default)]
+    pub exact: Vec<String>,
+    /// 进程名前缀（小写）
+    #[serde(default)]
+    pub prefixes: Vec<String>,
+}
+
+/// 误判上报
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FalsePositiveReport {
+    pub process_name: String,
+    /// 被误识别成的游戏名
+    pub game_name: Option<String>,
+    pub note: Option<String>,
+}
+
+impl ProcessFilter {
+    fn builtin() -> Self {
+        ProcessFilter {
+            version: 0,
+            exact: BUILTIN_EXACT.iter().map(|s| s.to_string()).collect(),
+            prefixes: BUILTIN_PREFIXES.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn extend(&mut self, other: ProcessFilter) {
+        self.version = self.version.max(other.version);
+        self.exact
+            .extend(other.exact.into_iter().map(|s| s.to_lowercase()));
+        self.prefixes
+            .extend(other.prefixes.into_iter().map(|s| s.to_lowercase()));
+        self.exact.sort();
+        self.exact.dedup();
+        self.prefixes.sort();
+        self.prefixes.dedup();
+    }
+
+    /// `exe_name` 须为小写
+    pub fn is_skipped(&self, exe_name: &str) -> bool {
+        exe_name.is_empty()
+            || self.exact.iter().any(|e| e == exe_name)
+            || self
+                .prefixes
+                .iter()
+                .any(|p| exe_name.starts_with(p.as_str()))
+    }
+}
+
+// ==================== 读写 ====================
+
+fn current() -> &'static Mutex<ProcessFilter> {
+    static FILTER: OnceLock<Mutex<ProcessFilter>> = OnceLock::new();
+    FILTER.get_or_init(|| Mutex::new(ProcessFilter::builtin()))
+}
+
+fn data_file(app: &AppHandle, name: &str) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("无法获取数据目录: {}", e))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建数据目录: {}", e))?;
+    Ok(dir.join(name))
+}
+
+fn read_filter(app: &AppHandle, name: &str) -> ProcessFilter {
+    data_file(app, name)
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn write_filter(app: &AppHandle, name: &str, filter: &ProcessFilter) -> Result<(), String> {
+    let path = data_file(app, name)?;
+    let text = serde_json::to_string_pretty(filter).map_err(|e| e.to_string())?;
+    std::fs::write(&path, text).map_err(|e| format!("保存过滤名单失败: {}", e))
+}
+
+/// 合并内置、下载与本地名单并生效
+pub fn reload(app: &AppHandle) -> ProcessFilter {
+    let mut filter = ProcessFilter::builtin();
+    filter.extend(read_filter(app, REMOTE_FILE));
+    filter.extend(read_filter(app, LOCAL_FILE));
+    *current().lock().unwrap() = filter.clone();
+    filter
+}
+
+/// 是否为应跳过的非游戏进程（`exe_name` 须为小写）
+pub fn is_skipped(exe_name: &str) -> bool {
+    current().lock().unwrap().is_skipped(exe_name)
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 从服务端下载最新过滤名单，返回生效的进程名数量
+#[tauri::command]
+pub async fn update_process_filter(
+    app: AppHandle,
+    server_url: Option<String>,
+) -> Result<usize, String> {
+    crate::connectivity::ensure_online()?;
+    let urls = mirrors::server_urls(server_url, FILTER_PATH).await;
+    let filter: ProcessFilter = mirrors::get_json(&urls)
+        .await
+        .map_err(|e| format!("下载过滤名单失败: {}", e))?;
+
+    write_filter(&app, REMOTE_FILE, &filter)?;
+    let merged = reload(&app);
+    log::info!(
+        "已更新进程过滤名单 v{}: {} 个进程, {} 个前缀",
+        merged.version,
+        merged.exact.len(),
+        merged.prefixes.len()
+    );
+    Ok(merged.exact.len() + merged.prefixes.len())
+}
+
+/// 获取当前生效的过滤名单
+#[tauri::command]
+pub fn get_process_filter(app: AppHandle) -> Result<ProcessFilter, String> {
+    Ok(reload(&app))
+}
+
+/// 上报误识别为游戏的进程，并在本地忽略该进程
+/// 返回是否成功上报到服务端（离线时仅本地忽略）
+#[tauri::command]
+pub async fn report_false_positive(
+    app: AppHandle,
+    report: FalsePositiveReport,
+    server_url: Option<String>,
+) -> Result<bool, String> {
+    let name = report.process_name.trim().to_lowercase();
+    if name.is_empty() {
+        return Err("进程名不能为空".to_string());
+    }
+
+    let mut local = read_filter(&app, LOCAL_FILE);
+    if !local.exact.contains(&name) {
+        local.exact.push(name.clone());
+        write_filter(&app, LOCAL_FILE, &local)?;
+    }
+    reload(&app);
+    log::info!("已忽略进程: {}", name);
+    if crate::connectivity::is_offline() {
+        return Ok(false);
+    }
+
+    let base = server_url.unwrap_or_else(|| settings::get().server_url);
+    let url = format!("{}{}", base.trim_end_matches('/'), REPORT_PATH);
+    let sent = match crate::proxy::client() {
+        Ok(client) => client
+            .post(&url)
+            .json(&report)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| e.to_string()),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = &sent {
+        log::warn!("上报误判失败: {}", e);
+    }
+    Ok(sent.is_ok())
+}
+
+/// 取消本地忽略
+#[tauri::command]
+pub fn unignore_process(app: AppHandle, process_name: String) -> Result<(), String> {
+    let name = process_name.trim().to_lowercase();
+    let mut local = read_filter(&app, LOCAL_FILE);
+    let before = local.exact.len();
+    local.exact.retain(|e| *e != name);
+    if local.exact.len() == before {
+        return Err(format!("{} 不在本地忽略列表中", process_name));
+    }
+    write_filter(&app, LOCAL_FILE, &local)?;
+    reload(&app);
+    Ok(())
+}
@@ -0,0 +1,246 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// 连通性测试超时
+const TEST_TIMEOUT: Duration = Duration::from_secs(8);
+/// 连通性测试中的 GitHub 地址
+const GITHUB_URL: &str = "https://github.com";
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProxyMode {
+    /// 跟随系统代理（Windows Internet 设置与 HTTP(S)_PROXY 环境变量）
+    #[default]
+    System,
+    /// 使用下方手动填写的代理
+    Manual,
+    /// 直连，忽略系统代理
+    Off,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProxySettings {
+    pub mode: ProxyMode,
+    /// 手动代理地址：http://host:port 或 socks5://host:port，可带 user:pass@
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemProxy {
+    /// 来源（Windows Internet 设置 / 环境变量名）
+    pub source: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndpointStatus {
+    pub name: String,
+    pub url: String,
+    pub reachable: bool,
+    /// HTTP 状态码（连接成功时）
+    pub status: Option<u16>,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectivityReport {
+    pub mode: ProxyMode,
+    /// 实际生效的代理（直连时为 None）
+    pub proxy: Option<String>,
+    pub endpoints: Vec<EndpointStatus>,
+}
+
+// ==================== 系统代理 ====================
+
+/// 读取 Windows Internet 设置中的代理（ProxyServer 可能是 host:port 或 http=...;https=...）
+#[cfg(target_os = "windows")]
+fn windows_proxy() -> Option<String> {
+    use winreg::enums::HKEY_CURRENT_USER;
+    use winreg::RegKey;
+
+    let key = RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey("Software\\Microsoft\\Windows\\CurrentVersion\\Internet Settings")
+        .ok()?;
+    if key.get_value::<u32, _>("ProxyEnable").ok()? == 0 {
+        return None;
+    }
+    let server: String = key.get_value("ProxyServer").ok()?;
+    let server = server
+        .split(';')
+        .find_map(|part| part.strip_prefix("https="))
+        .or_else(|| {
+            server
+                .split(';')
+                .find_map(|part| part.strip_prefix("http="))
+        })
+        .unwrap_or(&server)
+        .trim()
+        .to_string();
+    if server.is_empty() {
+        return None;
+    }
+    Some(if server.contains("://") {
+        server
+    } else {
+        format!("http://{}", server)
+    })
+}
+
+#[cfg(not(target_os = "windows"))]
+fn windows_proxy() -> Option<String> {
+    None
+}
+
+/// 检测系统代理，与 reqwest 的查找顺序一致：环境变量优先
+pub fn detect_system_proxy() -> Option<SystemProxy> {
+    for var in ["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"] {
+        if let Ok(url) = std::env::var(var) {
+            if !url.trim().is_empty() {
+                return Some(SystemProxy {
+                    source: var.to_string(),
+                    url,
+                });
+            }
+        }
+    }
+    windows_proxy().map(|url| SystemProxy {
+        source: "Windows Internet 设置".to_string(),
+        url,
+    })
+}
+
+// ==================== HTTP 客户端 ====================
+
+/// 按代理设置创建 ClientBuilder；所有访问外网的请求都应从这里创建，离线模式下返回错误
+pub fn builder() -> Result<reqwest::ClientBuilder, String> {
+    crate::connectivity::ensure_online()?;
+    let settings = crate::settings::get().proxy;
+    let builder = reqwest::Client::builder();
+    Ok(match settings.mode {
+        // reqwest 默认即读取系统代理
+        ProxyMode::System => builder,
+        ProxyMode::Off => builder.no_proxy(),
+        ProxyMode::Manual => {
+            let url = settings.url.trim();
+            if url.is_empty() {
+                return Err("已选择手动代理，但未填写代理地址".to_string());
+            }
+            let proxy =
+                reqwest::Proxy::all(url).map_err(|e| format!("代理地址无效 {}: {}", url, e))?;
+            builder.no_proxy().proxy(proxy)
+        }
+    })
+}
+
+pub fn client() -> Result<reqwest::Client, String> {
+    builder()?
+        .build()
+        .map_err(|e| format!("无法创建 HTTP 客户端: {}", e))
+}
+
+/// 当前生效的代理地址
+fn effective_proxy() -> Option<String> {
+    let settings = crate::settings::get().proxy;
+    match settings.mode {
+        ProxyMode::System => detect_system_proxy().map(|p| p.url),
+        ProxyMode::Manual => Some(settings.url),
+        ProxyMode::Off => None,
+    }
+}
+
+// ==================== 连通性测试 ====================
+
+async fn test_endpoint(client: reqwest::Client, name: String, url: String) -> EndpointStatus {
+    let start = Instant::now();
+    let result = client.head(&url).timeout(TEST_TIMEOUT).send().await;
+    let latency = start.elapsed().as_millis() as u64;
+    match result {
+        Ok(response) => EndpointStatus {
+            name,
+            url,
+            reachable: true,
+            status: Some(response.status().as_u16()),
+            latency_ms: Some(latency),
+            error: None,
+        },
+        Err(e) => EndpointStatus {
+            name,
+            url,
+            reachable: false,
+            status: None,
+            latency_ms: None,
+            error: Some(if e.is_timeout() {
+                "连接超时".to_string()
+            } else {
+                e.to_string()
+            }),
+        },
+    }
+}
+
+/// 需要测试的外部地址：服务器、GitHub、更新镜像、下载镜像、管理端
+fn endpoints() -> Vec<(String, String)> {
+    let settings = crate::settings::get();
+    let mut list = vec![
+        ("GameBench 服务器".to_string(), settings.server_url.clone()),
+        ("GitHub".to_string(), GITHUB_URL.to_string()),
+    ];
+    if let Some(url) = settings.update.mirror_url.filter(|u| !u.trim().is_empty()) {
+        list.push(("更新镜像".to_string(), url));
+    }
+    for mirror in &settings.mirrors.list {
+        if let Some(url) = mirror.server_url.as_ref().filter(|u| !u.trim().is_empty()) {
+            list.push((format!("{}（服务器镜像）", mirror.name), url.clone()));
+        }
+        if let Some(proxy) = mirror
+            .github_proxy
+            .as_ref()
+            .filter(|u| !u.trim().is_empty())
+        {
+            list.push((format!("{}（GitHub 代理）", mirror.name), proxy.clone()));
+        }
+    }
+    if let Some(url) = crate::kiosk::management_url() {
+        list.push(("管理端".to_string(), url));
+    }
+    list
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 检测系统代理
+#[tauri::command]
+pub fn get_system_proxy() -> Result<Option<SystemProxy>, String> {
+    Ok(detect_system_proxy())
+}
+
+/// 按当前代理设置逐个测试外部地址的可达性
+#[tauri::command]
+pub async fn test_connectivity() -> Result<ConnectivityReport, String> {
+    // 离线模式下 client() 直接返回错误，不会发出任何请求
+    let client = client()?;
+    let mut tasks = tokio::task::JoinSet::new();
+    let list = endpoints();
+    for (name, url) in list.iter().cloned() {
+        tasks.spawn(test_endpoint(client.clone(), name, url));
+    }
+
+    let mut results = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok(status) = joined {
+            results.push(status);
+        }
+    }
+    // 保持 endpoints() 的顺序
+    results.sort_by_key(|s| list.iter().position(|(n, _)| *n == s.name));
+
+    Ok(ConnectivityReport {
+        mode: crate::settings::get().proxy.mode,
+        proxy: effective_proxy(),
+        endpoints: results,
+    })
+}
@@ -0,0 +1,406 @@
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Position, Scope};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::rc::Rc;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+// 钩子是由内嵌 Rhai 引擎执行的 .rhai 脚本。引擎不能 import 模块，除下面注册的函数
+// （print、write_file、append_file、exec）外不能访问文件或进程，超时后在下一条语句处中止。
+
+/// 脚本输出保留的最大长度
+const MAX_OUTPUT_BYTES: usize = 4096;
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEvent {
+    /// 开始监测
+    SessionStart,
+    /// 会话结束并保存
+    SessionComplete,
+    /// 监测中出现明显卡顿（帧时间尖峰）
+    StutterDetected,
+}
+
+impl HookEvent {
+    fn as_str(self) -> &'static str {
+        match self {
+            HookEvent::SessionStart => "session-start",
+            HookEvent::SessionComplete => "session-complete",
+            HookEvent::StutterDetected => "stutter-detected",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScriptHook {
+    pub id: String,
+    pub event: HookEvent,
+    /// .rhai 脚本路径
+    pub script: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 超时后中止脚本及其启动的程序 (秒)
+    #[serde(default = "default_timeout")]
+    pub timeout_secs: u32,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_timeout() -> u32 {
+    10
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HookRun {
+    /// 脚本运行错误（语法错误、抛出的异常等）
+    pub error: Option<String>,
+    pub timed_out: bool,
+    /// print 输出与 exec 启动程序的输出（截断）
+    pub output: String,
+    pub duration_ms: u64,
+}
+
+/// 卡顿事件数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StutterEvent {
+    pub process_name: String,
+    pub session_id: String,
+    /// 有效监测时间 (秒)
+    pub at_secs: f64,
+    pub frametime_ms: f64,
+    /// 近期帧时间中位数 (ms)
+    pub baseline_ms: f64,
+}
+
+// ==================== 启动程序 ====================
+
+/// 超时后等待输出读取线程的时间；脚本启动的孙进程可能仍握着管道，不再无限等待
+const READER_GRACE: Duration = Duration::from_millis(500);
+
+/// Windows 作业对象：句柄关闭时结束程序及其启动的所有子进程
+#[cfg(target_os = "windows")]
+struct KillOnClose(windows::Win32::Foundation::HANDLE);
+
+#[cfg(target_os = "windows")]
+impl KillOnClose {
+    /// 程序以挂起状态启动，加入作业对象后再恢复运行，启动的子进程都在作业内
+    fn assign(child: &std::process::Child) -> Result<KillOnClose, String> {
+        use std::os::windows::io::AsRawHandle;
+        use windows::Win32::Foundation::HANDLE;
+        use windows::Win32::System::JobObjects::{
+            AssignProcessToJobObject, CreateJobObjectW, JobObjectExtendedLimitInformation,
+            SetInformationJobObject, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+            JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
+        };
+
+        unsafe {
+            let job = KillOnClose(CreateJobObjectW(None, None).map_err(|e| e.to_string())?);
+            let mut limits = JOBOBJECT_EXTENDED_LIMIT_INFORMATION::default();
+            limits.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+            SetInformationJobObject(
+                job.0,
+                JobObjectExtendedLimitInformation,
+                &limits as *const _ as *const std::ffi::c_void,
+                std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+            )
+            .map_err(|e| e.to_string())?;
+            AssignProcessToJobObject(job.0, HANDLE(child.as_raw_handle()))
+                .map_err(|e| e.to_string())?;
+            crate::plugins::resume(child.id())?;
+            Ok(job)
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl Drop for KillOnClose {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = windows::Win32::Foundation::CloseHandle(self.0);
+        }
+    }
+}
+
+/// 在后台线程读取管道，结果通过 channel 返回，调用方可以不等读取结束
+fn collect(pipe: Option<impl Read + Send + 'static>) -> mpsc::Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(pipe) = pipe {
+            let mut limited = pipe.take(MAX_OUTPUT_BYTES as u64);
+            let _ = limited.read_to_end(&mut buf);
+            // 超出部分继续读掉，否则管道写满后程序会阻塞到超时
+            let _ = io::copy(&mut limited.into_inner(), &mut io::sink());
+        }
+        let _ = tx.send(String::from_utf8_lossy(&buf).to_string());
+    });
+    rx
+}
+
+/// 运行程序直到退出或到达截止时间，返回 (退出码, 是否超时, 输出)
+fn exec_program(
+    program: &str,
+    args: &[String],
+    dir: &Path,
+    env: &[(&str, String)],
+    deadline: Instant,
+) -> Result<(Option<i32>, bool, String), String> {
+    let mut cmd = Command::new(program);
+    cmd.args(args)
+        .current_dir(dir)
+        .envs(env.iter().map(|(k, v)| (k, v)))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        // CREATE_NO_WINDOW | CREATE_SUSPENDED，加入作业对象后才恢复运行
+        cmd.creation_flags(0x08000000 | 0x00000004);
+    }
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("无法运行 {}: {}", program, e))?;
+    #[cfg(target_os = "windows")]
+    let job = match KillOnClose::assign(&child) {
+        Ok(job) => job,
+        Err(e) => {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(format!("无法运行 {}: {}", program, e));
+        }
+    };
+    let stdout = collect(child.stdout.take());
+    let stderr = collect(child.stderr.take());
+
+    let (exit_code, timed_out) = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break (status.code(), false),
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                break (None, true);
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(e) => return Err(e.to_string()),
+        }
+    };
+    // 关闭作业对象，结束程序留下的子进程，管道随之关闭
+    #[cfg(target_os = "windows")]
+    drop(job);
+
+    let read = |rx: mpsc::Receiver<String>| {
+        if timed_out {
+            rx.recv_timeout(READER_GRACE).unwrap_or_default()
+        } else {
+            rx.recv().unwrap_or_default()
+        }
+    };
+    let mut output = read(stdout);
+    let err = read(stderr);
+    if !err.trim().is_empty() {
+        output.push_str(&err);
+    }
+    Ok((exit_code, timed_out, output))
+}
+
+// ==================== 执行 ====================
+
+/// 追加脚本输出，总长度不超过 MAX_OUTPUT_BYTES
+fn append_output(output: &RefCell<String>, text: &str) {
+    let mut output = output.borrow_mut();
+    if output.len() >= MAX_OUTPUT_BYTES || text.trim().is_empty() {
+        return;
+    }
+    if !output.is_empty() {
+        output.push('\n');
+    }
+    output.push_str(text.trim_end());
+    if output.len() > MAX_OUTPUT_BYTES {
+        let mut end = MAX_OUTPUT_BYTES;
+        while !output.is_char_boundary(end) {
+            end -= 1;
+        }
+        output.truncate(end);
+    }
+}
+
+fn terminated() -> Box<EvalAltResult> {
+    EvalAltResult::ErrorTerminated(Dynamic::UNIT, Position::NONE).into()
+}
+
+/// 相对路径按脚本所在目录解析
+fn resolve(dir: &Path, path: &str) -> PathBuf {
+    dir.join(path)
+}
+
+/// 构建受限的脚本引擎：禁止 import 和 eval，限制递归与数据大小，到截止时间后中止
+fn build_engine(
+    dir: PathBuf,
+    env: Vec<(&'static str, String)>,
+    deadline: Instant,
+    output: Rc<RefCell<String>>,
+) -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_module_resolver(DummyModuleResolver::new())
+        .set_max_call_levels(32)
+        .set_max_expr_depths(64, 32)
+        .set_max_string_size(1 << 20)
+        .set_max_array_size(10_000)
+        .set_max_map_size(10_000);
+    engine.disable_symbol("eval");
+    engine.on_progress(move |_| (Instant::now() >= deadline).then_some(Dynamic::UNIT));
+
+    let out = output.clone();
+    engine.on_print(move |text| append_output(&out, text));
+    let out = output.clone();
+    engine.on_debug(move |text, _, _| append_output(&out, text));
+
+    let base = dir.clone();
+    engine.register_fn(
+        "write_file",
+        move |path: &str, text: &str| -> Result<(), Box<EvalAltResult>> {
+            std::fs::write(resolve(&base, path), text)
+                .map_err(|e| format!("无法写入 {}: {}", path, e).into())
+        },
+    );
+    let base = dir.clone();
+    engine.register_fn(
+        "append_file",
+        move |path: &str, text: &str| -> Result<(), Box<EvalAltResult>> {
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(resolve(&base, path))
+                .and_then(|mut file| file.write_all(text.as_bytes()))
+                .map_err(|e| format!("无法写入 {}: {}", path, e).into())
+        },
+    );
+    // exec(program, [args...]) 运行本地程序并返回退出码（没有退出码时为 -1），输出并入脚本输出
+    engine.register_fn(
+        "exec",
+        move |program: &str, args: Array| -> Result<i64, Box<EvalAltResult>> {
+            if Instant::now() >= deadline {
+                return Err(terminated());
+            }
+            let args: Vec<String> = args.into_iter().map(|a| a.to_string()).collect();
+            let (code, timed_out, text) = exec_program(program, &args, &dir, &env, deadline)?;
+            append_output(&output, &text);
+            if timed_out {
+                return Err(terminated());
+            }
+            Ok(code.map_or(-1, i64::from))
+        },
+    );
+    engine
+}
+
+/// 运行脚本：事件数据作为 `event` 常量传入，常用字段同时以环境变量传给 exec 启动的程序
+fn run(hook: &ScriptHook, payload: &serde_json::Value) -> Result<HookRun, String> {
+    let script = Path::new(&hook.script);
+    let source = std::fs::read_to_string(script)
+        .map_err(|e| format!("无法读取脚本 {}: {}", hook.script, e))?;
+    let dir = script.parent().map(Path::to_path_buf).unwrap_or_default();
+    let field = |key: &str| {
+        payload
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+    let env = vec![
+        ("GAMEBENCH_EVENT", hook.event.as_str().to_string()),
+        ("GAMEBENCH_PROCESS", field("process_name")),
+        ("GAMEBENCH_SESSION_ID", field("session_id")),
+    ];
+
+    let start = Instant::now();
+    let deadline = start + Duration::from_secs(hook.timeout_secs.max(1) as u64);
+    let output = Rc::new(RefCell::new(String::new()));
+    let engine = build_engine(dir, env, deadline, output.clone());
+    let mut scope = Scope::new();
+    let event = rhai::serde::to_dynamic(payload).map_err(|e| e.to_string())?;
+    scope.push_constant("event", event);
+
+    let (timed_out, error) = match engine.run_with_scope(&mut scope, &source) {
+        Ok(()) => (false, None),
+        Err(e) if matches!(e.unwrap_inner(), EvalAltResult::ErrorTerminated(..)) => (true, None),
+        Err(e) => (false, Some(e.to_string())),
+    };
+    let output = output.borrow().clone();
+    Ok(HookRun {
+        error,
+        timed_out,
+        output,
+        duration_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
+/// 当前 profile 中订阅了该事件的脚本
+fn hooks_for(event: HookEvent) -> Vec<ScriptHook> {
+    crate::settings::get()
+        .script_hooks
+        .into_iter()
+        .filter(|h| h.enabled && h.event == event)
+        .collect()
+}
+
+/// 是否有脚本订阅该事件（没有时不做卡顿检测）
+pub fn has_hooks(event: HookEvent) -> bool {
+    !hooks_for(event).is_empty()
+}
+
+/// 在后台触发事件对应的脚本；网吧模式下不运行用户脚本
+pub fn fire(event: HookEvent, payload: impl Serialize) {
+    if crate::kiosk::ensure_unlocked().is_err() {
+        return;
+    }
+    let hooks = hooks_for(event);
+    if hooks.is_empty() {
+        return;
+    }
+    let Ok(payload) = serde_json::to_value(payload) else {
+        return;
+    };
+    std::thread::spawn(move || {
+        for hook in hooks {
+            match run(&hook, &payload) {
+                Ok(result) if result.timed_out => {
+                    log::warn!("脚本 {} 超时已结束", hook.script)
+                }
+                Ok(HookRun { error: Some(e), .. }) => {
+                    log::warn!("脚本 {} 出错: {}", hook.script, e)
+                }
+                Ok(_) => log::info!("已运行 {} 脚本 {}", event.as_str(), hook.script),
+                Err(e) => log::warn!("{}", e),
+            }
+        }
+    });
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 用示例数据试运行脚本，返回输出
+#[tauri::command]
+pub async fn test_script_hook(hook: ScriptHook) -> Result<HookRun, String> {
+    crate::kiosk::ensure_unlocked()?;
+    let payload = serde_json::json!({
+        "event": hook.event.as_str(),
+        "process_name": "example.exe",
+        "session_id": "20240101-120000-example",
+        "test": true,
+    });
+    tauri::async_runtime::spawn_blocking(move || run(&hook, &payload))
+        .await
+        .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,228 @@
+use crate::game_detect::{self, GameNameLanguage};
+use crate::launchers;
+use crate::pinyin;
+use crate::storage;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+/// 默认返回条数
+const DEFAULT_LIMIT: usize = 20;
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchKind {
+    /// 已知游戏列表（含国内平台游戏）
+    KnownGame,
+    /// 国内平台已安装的游戏
+    InstalledGame,
+    /// 已保存的监测会话
+    Session,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub kind: SearchKind,
+    /// 显示名称（按界面语言选择中文或英文名）
+    pub title: String,
+    /// 附加信息（另一语言的名称、会话数据等）
+    pub subtitle: Option<String>,
+    pub process_name: Option<String>,
+    pub session_id: Option<String>,
+    /// 匹配得分，越高越相关
+    pub score: i64,
+}
+
+/// 参与匹配的候选项
+struct Candidate {
+    hit: SearchHit,
+    /// 匹配用的文本（名称、进程名及其拼音）
+    keys: Vec<String>,
+}
+
+// ==================== 候选项 ====================
+
+/// 名称的匹配文本: 原文 + 全拼 + 拼音首字母
+fn name_keys(name: &str) -> Vec<String> {
+    let lower = name.to_lowercase();
+    let full = pinyin::full(name);
+    if full == lower {
+        return vec![lower];
+    }
+    vec![lower, full, pinyin::initials(name)]
+}
+
+fn game_keys(name_en: &str, name_zh: Option<&str>, process_name: &str) -> Vec<String> {
+    let mut keys = name_keys(name_en);
+    if let Some(zh) = name_zh {
+        keys.extend(name_keys(zh));
+    }
+    keys.push(process_name.to_lowercase());
+    keys
+}
+
+/// 另一语言的名称（与显示名称相同时不重复显示）
+fn other_name(title: &str, name_en: &str, name_zh: Option<&str>) -> Option<String> {
+    [name_zh, Some(name_en)]
+        .into_iter()
+        .flatten()
+        .find(|n| *n != title)
+        .map(str::to_string)
+}
+
+/// 进程名(小写) → (英文名, 中文名)
+pub(crate) fn known_names() -> HashMap<String, (String, Option<String>)> {
+    let mut names: HashMap<String, (String, Option<String>)> = game_detect::known_game_names()
+        .into_iter()
+        .map(|g| (g.process_name, (g.name_en, g.name_zh)))
+        .collect();
+    for (process, game) in launchers::known_games() {
+        names.insert(
+            process.to_string(),
+            (game.name_en.to_string(), Some(game.name_zh.to_string())),
+        );
+    }
+    names
+}
+
+fn known_game_candidates(language: GameNameLanguage) -> Vec<Candidate> {
+    let mut candidates: Vec<Candidate> = game_detect::known_game_names()
+        .into_iter()
+        .map(|g| Candidate {
+            keys: game_keys(&g.name_en, g.name_zh.as_deref(), &g.process_name),
+            hit: SearchHit {
+                kind: SearchKind::KnownGame,
+                subtitle: other_name(&g.name, &g.name_en, g.name_zh.as_deref()),
+                title: g.name,
+                process_name: Some(g.process_name),
+                session_id: None,
+                score: 0,
+            },
+        })
+        .collect();
+
+    candidates.extend(launchers::known_games().map(|(process, game)| {
+        let title = language.pick(game.name_en, Some(game.name_zh));
+        Candidate {
+            keys: game_keys(game.name_en, Some(game.name_zh), process),
+            hit: SearchHit {
+                kind: SearchKind::KnownGame,
+                subtitle: other_name(&title, game.name_en, Some(game.name_zh)),
+                title,
+                process_name: Some(process.to_string()),
+                session_id: None,
+                score: 0,
+            },
+        }
+    }));
+    candidates
+}
+
+fn installed_game_candidates() -> Vec<Candidate> {
+    launchers::installed_games()
+        .into_iter()
+        .map(|g| Candidate {
+            keys: name_keys(&g.name),
+            hit: SearchHit {
+                kind: SearchKind::InstalledGame,
+                title: g.name,
+                subtitle: Some(g.install_dir),
+                process_name: None,
+                session_id: None,
+                score: 0,
+            },
+        })
+        .collect()
+}
+
+fn session_candidates(app: &AppHandle, language: GameNameLanguage) -> Vec<Candidate> {
+    let names = known_names();
+    let sessions = storage::load_sessions(app).unwrap_or_default();
+
+    sessions
+        .into_iter()
+        .map(|s| {
+            let process = s.process_name.to_lowercase();
+            let (title, mut keys) = match names.get(&process) {
+                Some((en, zh)) => (
+                    language.pick(en, zh.as_deref()),
+                    game_keys(en, zh.as_deref(), &process),
+                ),
+                None => (s.process_name.clone(), vec![process]),
+            };
+            if let Some(window_title) = &s.metadata.window_title {
+                keys.extend(name_keys(window_title));
+            }
+            Candidate {
+                keys,
+                hit: SearchHit {
+                    kind: SearchKind::Session,
+                    title,
+                    subtitle: Some(format!("{} · 平均 {:.0} FPS", s.session_id, s.avg_fps)),
+                    process_name: Some(s.process_name),
+                    session_id: Some(s.session_id),
+                    score: 0,
+                },
+            }
+        })
+        .collect()
+}
+
+// ==================== 匹配 ====================
+
+fn best_score(matcher: &SkimMatcherV2, keys: &[String], query: &str) -> Option<i64> {
+    keys.iter()
+        .filter_map(|key| matcher.fuzzy_match(key, query))
+        .max()
+}
+
+fn rank(candidates: Vec<Candidate>, query: &str, limit: usize) -> Vec<SearchHit> {
+    let matcher = SkimMatcherV2::default().ignore_case();
+    // 去掉空格，便于 "hei shen hua" 匹配全拼
+    let query: String = query.split_whitespace().collect();
+
+    let mut hits: Vec<SearchHit> = candidates
+        .into_iter()
+        .filter_map(|c| {
+            let score = best_score(&matcher, &c.keys, &query)?;
+            Some(SearchHit { score, ..c.hit })
+        })
+        .collect();
+
+    // 同分时较新的会话在前（会话 ID 以时间开头）
+    hits.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| b.session_id.cmp(&a.session_id))
+    });
+    // 同一游戏的多个进程名只保留得分最高的一条
+    let mut seen = std::collections::HashSet::new();
+    hits.retain(|h| h.kind == SearchKind::Session || seen.insert((h.kind, h.title.clone())));
+    hits.truncate(limit);
+    hits
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 按名称、拼音或首字母模糊搜索已知游戏、已安装游戏与监测会话
+#[tauri::command]
+pub fn search(
+    app: AppHandle,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<SearchHit>, String> {
+    if query.trim().is_empty() {
+        return Ok(vec![]);
+    }
+
+    let language = crate::settings::get().game_name_language;
+    let mut candidates = known_game_candidates(language);
+    candidates.extend(installed_game_candidates());
+    candidates.extend(session_candidates(&app, language));
+
+    Ok(rank(candidates, &query, limit.unwrap_or(DEFAULT_LIMIT)))
+}
@@ -0,0 +1,38 @@
+use gamebench_core::sensors;
+pub use gamebench_core::sensors::{adapter_usage, latest, subscribe, SensorKind, SensorReadings};
+use std::time::Duration;
+use tauri::AppHandle;
+
+// ==================== Tauri 命令 ====================
+
+/// 前端订阅传感器，读数通过 `sensors-updated` 事件推送，返回订阅 id
+#[tauri::command]
+pub fn subscribe_sensors(
+    app: AppHandle,
+    kinds: Vec<SensorKind>,
+    interval_ms: u64,
+) -> Result<u64, String> {
+    if kinds.is_empty() {
+        return Err("至少需要订阅一个传感器".to_string());
+    }
+    sensors::set_event_sink(crate::events::sink(&app));
+    Ok(sensors::add_subscription(
+        &kinds,
+        Duration::from_millis(interval_ms),
+    ))
+}
+
+#[tauri::command]
+pub fn unsubscribe_sensors(id: u64) -> Result<(), String> {
+    if sensors::unsubscribe(id) {
+        Ok(())
+    } else {
+        Err(format!("未找到传感器订阅 {}", id))
+    }
+}
+
+/// 最近一次读数（不触发轮询）
+#[tauri::command]
+pub fn get_sensor_readings() -> Result<SensorReadings, String> {
+    Ok(latest())
+}
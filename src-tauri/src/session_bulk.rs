@@ -0,0 +1,430 @@
+use crate::capture_time::{CapturePause, PauseReason};
+use crate::driver_reset::DriverReset;
+use crate::fps_monitor::{FpsSession, SessionStatus};
+use crate::frame_cap;
+use crate::input_latency::{InputLatency, LatencySummary};
+use crate::markers::SessionMarker;
+use crate::metrics::FramePacing;
+use crate::plugins::PluginMetricSummary;
+use crate::present_mode::PresentInfo;
+use crate::privacy::{self, PrivacyOptions};
+use crate::qoe::QoeScore;
+use crate::session_query;
+use crate::storage;
+use crate::tasks::{self, TaskKind};
+use crate::vram_health;
+use crate::vrr;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+/// 两段会话间隔超过该值时不视为同一次测试被意外拆分 (秒)
+const MAX_MERGE_GAP_SECS: i64 = 30 * 60;
+
+// ==================== 数据结构 ====================
+
+/// 批量操作结果：部分失败不影响其余会话
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BulkResult {
+    /// 成功处理的会话 ID（导出时为文件路径）
+    pub succeeded: Vec<String>,
+    /// (会话 ID, 失败原因)
+    pub failed: Vec<(String, String)>,
+}
+
+impl BulkResult {
+    fn record(&mut self, id: &str, result: Result<String, String>) {
+        match result {
+            Ok(value) => self.succeeded.push(value),
+            Err(e) => self.failed.push((id.to_string(), e)),
+        }
+    }
+}
+
+// ==================== 合并 ====================
+
+/// 按帧数加权的调和平均，用于由各段的平均/Low FPS 推出整体值
+/// 平均 FPS 结果精确；1% / 0.1% Low 没有原始帧时间，结果为近似值
+fn weighted_fps(parts: &[FpsSession], fps: impl Fn(&FpsSession) -> f64) -> f64 {
+    let frames: f64 = parts.iter().map(|s| s.total_frames as f64).sum();
+    let frame_ms: f64 = parts
+        .iter()
+        .filter(|s| fps(s) > 0.0)
+        .map(|s| s.total_frames as f64 * 1000.0 / fps(s))
+        .sum();
+    if frame_ms > 0.0 {
+        (frames * 1000.0 / frame_ms * 10.0).round() / 10.0
+    } else {
+        0.0
+    }
+}
+
+/// 按样本数加权合并各段延迟：平均值准确，中位数与 P95 无法由各段结果推出，只能近似
+fn merge_latency(parts: &[FpsSession]) -> Option<InputLatency> {
+    let merge = |pick: fn(&InputLatency) -> Option<&LatencySummary>| {
+        let summaries: Vec<&LatencySummary> = parts
+            .iter()
+            .filter_map(|s| s.latency.as_ref().and_then(pick))
+            .collect();
+        let samples: u64 = summaries.iter().map(|l| l.samples).sum();
+        if samples == 0 {
+            return None;
+        }
+        let weighted = |f: fn(&LatencySummary) -> f64| {
+            let total: f64 = summaries.iter().map(|l| f(l) * l.samples as f64).sum();
+            (total / samples as f64 * 100.0).round() / 100.0
+        };
+        Some(LatencySummary {
+            avg_ms: weighted(|l| l.avg_ms),
+            median_ms: weighted(|l| l.median_ms),
+            p95_ms: weighted(|l| l.p95_ms),
+            samples,
+        })
+    };
+    let display = merge(|l| l.display.as_ref());
+    let click_to_photon = merge(|l| l.click_to_photon.as_ref());
+    (display.is_some() || click_to_photon.is_some()).then_some(InputLatency {
+        display,
+        click_to_photon,
+    })
+}
+
+/// 各段占多数的呈现模式一致时合并（占比按帧数加权），否则无法得知整体的占多数模式
+fn merge_present(parts: &[FpsSession]) -> Option<PresentInfo> {
+    let first = parts.first()?.present.as_ref()?;
+    let infos: Vec<(&PresentInfo, f64)> = parts
+        .iter()
+        .map(|s| Some((s.present.as_ref()?, s.total_frames as f64)))
+        .collect::<Option<_>>()?;
+    if infos
+        .iter()
+        .any(|(p, _)| p.present_mode != first.present_mode)
+    {
+        return None;
+    }
+    let frames: f64 = infos.iter().map(|(_, n)| n).sum();
+    let pct: f64 = infos
+        .iter()
+        .map(|(p, n)| p.present_mode_pct * n)
+        .sum::<f64>();
+    let same = |f: fn(&PresentInfo) -> Option<bool>| {
+        f(first).filter(|v| infos.iter().all(|(p, _)| f(p) == Some(*v)))
+    };
+    Some(PresentInfo {
+        present_mode_pct: if frames > 0.0 {
+            (pct / frames * 10.0).round() / 10.0
+        } else {
+            first.present_mode_pct
+        },
+        sync_interval: first
+            .sync_interval
+            .filter(|v| infos.iter().all(|(p, _)| p.sync_interval == Some(*v))),
+        vsync: same(|p| p.vsync),
+        allows_tearing: same(|p| p.allows_tearing),
+        ..first.clone()
+    })
+}
+
+/// 将同一游戏被意外拆分的多段会话合并为一段（按开始时间排序）
+pub fn merge(mut parts: Vec<FpsSession>) -> Result<FpsSession, String> {
+    if parts.len() < 2 {
+        return Err("至少需要选择两段会话才能合并".to_string());
+    }
+    parts.sort_by(|a, b| a.session_id.cmp(&b.session_id));
+
+    let first = &parts[0];
+    if let Some(other) = parts
+        .iter()
+        .find(|s| !s.process_name.eq_ignore_ascii_case(&first.process_name))
+    {
+        return Err(format!(
+            "只能合并同一游戏的会话: {} 与 {}",
+            first.process_name, other.process_name
+        ));
+    }
+    if parts.iter().any(|s| s.import.is_some()) {
+        return Err("导入的会话不能与其他会话合并".to_string());
+    }
+    if let Some(other) = parts
+        .iter()
+        .find(|s| s.capture_profile != first.capture_profile)
+    {
+        return Err(format!(
+            "{} 与 {} 的采集档位不同，不能合并",
+            first.session_id, other.session_id
+        ));
+    }
+
+    let starts = parts
+        .iter()
+        .map(|s| {
+            session_query::started_at(&s.session_id)
+                .ok_or_else(|| format!("无法从会话 ID 解析开始时间: {}", s.session_id))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut pauses: Vec<CapturePause> = Vec::new();
+    let mut active = 0.0;
+    for (i, part) in parts.iter().enumerate() {
+        if i > 0 {
+            let prev = &parts[i - 1];
+            let prev_end = starts[i - 1]
+                + chrono::Duration::milliseconds((prev.wall_duration_secs * 1000.0) as i64);
+            let gap = (starts[i] - prev_end).num_milliseconds() as f64 / 1000.0;
+            if gap > MAX_MERGE_GAP_SECS as f64 {
+                return Err(format!(
+                    "{} 与 {} 相隔超过 {} 分钟，不像是同一次测试",
+                    prev.session_id,
+                    part.session_id,
+                    MAX_MERGE_GAP_SECS / 60
+                ));
+            }
+            pauses.push(CapturePause {
+                at_secs: active,
+                duration_secs: (gap.max(0.0) * 10.0).round() / 10.0,
+                reason: PauseReason::Split,
+            });
+        }
+        pauses.extend(part.pauses.iter().map(|p| CapturePause {
+            at_secs: p.at_secs + active,
+            ..p.clone()
+        }));
+        active += part.duration_secs;
+    }
+
+    let last = parts.last().unwrap_or(first);
+    let wall = (*starts.last().unwrap_or(&starts[0]) - starts[0]).num_milliseconds() as f64
+        / 1000.0
+        + last.wall_duration_secs;
+
+    let mut tags: Vec<String> = Vec::new();
+    for tag in parts.iter().flat_map(|s| s.tags.iter()) {
+        if !tags.contains(tag) {
+            tags.push(tag.clone());
+        }
+    }
+
+    // 插件指标按采样数加权合并
+    let mut plugin_metrics: Vec<PluginMetricSummary> = Vec::new();
+    for metric in parts.iter().flat_map(|s| s.plugin_metrics.iter()) {
+        match plugin_metrics
+            .iter_mut()
+            .find(|m| m.plugin_id == metric.plugin_id && m.key == metric.key)
+        {
+            Some(m) => {
+                let samples = m.samples + metric.samples;
+                m.avg = ((m.avg * m.samples as f64 + metric.avg * metric.samples as f64)
+                    / samples.max(1) as f64
+                    * 10.0)
+                    .round()
+                    / 10.0;
+                m.min = m.min.min(metric.min);
+                m.max = m.max.max(metric.max);
+                m.samples = samples;
+            }
+            None => plugin_metrics.push(metric.clone()),
+        }
+    }
+
+    // 各段共用同一采集流程时保留，否则合并结果无法签名
+    let pipeline = first
+        .pipeline
+        .clone()
+        .filter(|p| parts.iter().all(|s| s.pipeline.as_ref() == Some(p)));
+
+    let mut merged = first.clone();
+    merged.session_id = format!("{}-merged", first.session_id);
+    merged.avg_fps = weighted_fps(&parts, |s| s.avg_fps);
+    merged.fps_1_low = weighted_fps(&parts, |s| s.fps_1_low);
+    merged.fps_01_low = weighted_fps(&parts, |s| s.fps_01_low);
+    merged.max_fps = parts.iter().map(|s| s.max_fps).fold(0.0, f64::max);
+    merged.min_fps = parts
+        .iter()
+        .map(|s| s.min_fps)
+        .fold(f64::INFINITY, f64::min);
+    merged.total_frames = parts.iter().map(|s| s.total_frames).sum();
+    merged.stutter_count = parts.iter().map(|s| s.stutter_count).sum();
+    // 百分位无法由各段结果推出，有原始帧时间时在 merge_sessions 中重算
+    merged.percentiles = Default::default();
+    // 长帧数可直接相加，标准差与百分位同样在 merge_sessions 中重算
+    merged.pacing = FramePacing {
+        over_16ms: parts.iter().map(|s| s.pacing.over_16ms).sum(),
+        over_33ms: parts.iter().map(|s| s.pacing.over_33ms).sum(),
+        ..Default::default()
+    };
+    // 合并后不再是单次定时测试
+    merged.benchmark = None;
+    // 体验评分、VRR 覆盖率与限帧建议同样需要原始帧时间，在 merge_sessions 中重算
+    merged.qoe = None;
+    merged.vrr = None;
+    merged.frame_cap = None;
+    merged.present = merge_present(&parts);
+    merged.latency = merge_latency(&parts);
+    // 各段都有显存计数时增量相加，缺任一段就无法确认整段测试没有纠错
+    let vram: Option<Vec<_>> = parts.iter().map(|s| s.vram.as_ref()).collect();
+    merged.vram = vram.and_then(|reports| vram_health::merge(&reports));
+    merged.duration_secs = (active * 10.0).round() / 10.0;
+    merged.wall_duration_secs = (wall * 10.0).round() / 10.0;
+    merged.pauses = pauses;
+    // 任一片段非正常结束时，合并结果沿用该状态
+    merged.status = parts
+        .iter()
+        .map(|s| s.status)
+        .find(|status| *status != SessionStatus::Completed)
+        .unwrap_or_default();
+    merged.pipeline = pipeline;
+    merged.tags = tags;
+    merged.plugin_metrics = plugin_metrics;
+    merged.markers = merged_markers(&parts);
+    merged.driver_resets = merged_driver_resets(&parts);
+    merged.merged_from = parts.iter().map(|s| s.session_id.clone()).collect();
+    merged.suggestions = crate::suggestions::build_session_suggestions(&merged);
+    Ok(merged)
+}
+
+/// 各段标记按合并后的开始时间重新定位；旧会话缺少毫秒级开始时间时保留原位置
+fn merged_markers(parts: &[FpsSession]) -> Vec<SessionMarker> {
+    let first = &parts[0];
+    let mut markers: Vec<SessionMarker> = Vec::new();
+    for part in parts {
+        for marker in &part.markers {
+            let mut marker = marker.clone();
+            if first.started_at_ms > 0 && marker.unix_ms > 0.0 {
+                marker.at_secs = ((marker.unix_ms - first.started_at_ms as f64) / 1000.0 * 1000.0)
+                    .round()
+                    / 1000.0;
+            }
+            markers.push(marker);
+        }
+    }
+    markers.sort_by(|a, b| a.unix_ms.total_cmp(&b.unix_ms));
+    markers
+}
+
+/// 驱动重置事件同样按合并后的开始时间重新定位
+fn merged_driver_resets(parts: &[FpsSession]) -> Vec<DriverReset> {
+    let start_ms = parts[0].started_at_ms;
+    let mut resets: Vec<DriverReset> = parts
+        .iter()
+        .flat_map(|s| s.driver_resets.iter().cloned())
+        .collect();
+    if start_ms > 0 {
+        for reset in &mut resets {
+            reset.at_secs = (reset.unix_ms.saturating_sub(start_ms) as f64 / 100.0).round() / 10.0;
+        }
+    }
+    resets.sort_by_key(|r| r.unix_ms);
+    resets
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 批量删除会话
+#[tauri::command]
+pub fn delete_sessions(app: AppHandle, session_ids: Vec<String>) -> Result<BulkResult, String> {
+    crate::kiosk::ensure_unlocked()?;
+    let mut result = BulkResult::default();
+    for id in &session_ids {
+        result.record(id, storage::remove_session(&app, id).map(|_| id.clone()));
+    }
+    log::info!(
+        "批量删除会话: 成功 {}，失败 {}",
+        result.succeeded.len(),
+        result.failed.len()
+    );
+    Ok(result)
+}
+
+/// 批量脱敏、签名导出到 `dir`（默认下载目录），返回任务 ID
+/// 任务结果中成功项为导出的文件路径；取消后已导出的文件保留
+#[tauri::command]
+pub fn export_sessions(
+    app: AppHandle,
+    session_ids: Vec<String>,
+    dir: Option<String>,
+    options: Option<PrivacyOptions>,
+) -> Result<String, String> {
+    crate::kiosk::ensure_unlocked()?;
+    let dir = match dir {
+        Some(dir) => PathBuf::from(dir),
+        None => app
+            .path()
+            .download_dir()
+            .map_err(|e| format!("无法获取下载目录: {}", e))?,
+    };
+    std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建导出目录: {}", e))?;
+    let options = options.unwrap_or_else(|| crate::settings::get().privacy);
+
+    let worker = app.clone();
+    tasks::spawn(&app, TaskKind::Report, "批量导出", move |task| {
+        let mut result = BulkResult::default();
+        for (i, id) in session_ids.iter().enumerate() {
+            task.check()?;
+            let exported = storage::load_session(&worker, id)
+                .and_then(|session| privacy::write_export(&session, options, &dir))
+                .map(|path| path.to_string_lossy().to_string());
+            result.record(id, exported);
+            task.progress(i as u64 + 1, session_ids.len() as u64, id.clone());
+        }
+        Ok(result)
+    })
+}
+
+/// 合并被意外拆分的多段会话，保存合并结果并删除原会话（`keep_originals` 为 true 时保留）
+#[tauri::command]
+pub fn merge_sessions(
+    app: AppHandle,
+    session_ids: Vec<String>,
+    keep_originals: Option<bool>,
+) -> Result<FpsSession, String> {
+    crate::kiosk::ensure_unlocked()?;
+    let parts = session_ids
+        .iter()
+        .map(|id| storage::load_session(&app, id))
+        .collect::<Result<Vec<_>, _>>()?;
+    let percentiles = parts
+        .first()
+        .map(|s| s.percentiles.clone())
+        .unwrap_or_default();
+    let mut merged = merge(parts)?;
+    // 各段都保存了原始帧时间时，按顺序拼接
+    let frames = merged
+        .merged_from
+        .iter()
+        .map(|id| storage::load_frames(&app, id).and_then(|log| log.read_all()))
+        .collect::<Result<Vec<_>, _>>()
+        .map(|frames| frames.concat());
+    if let Ok(frames) = &frames {
+        merged.percentiles = percentiles.recompute(frames);
+        merged.pacing = FramePacing::from_frames(frames);
+        let display = merged.gpu_routing.as_ref().and_then(|r| r.display.as_ref());
+        let refresh_hz = display.and_then(|d| d.refresh_hz);
+        let refresh_range = display.and_then(|d| d.refresh_range);
+        let present = merged.present.as_ref();
+        merged.vrr =
+            refresh_range.and_then(|range| vrr::analyze(frames, range, refresh_hz, present));
+        merged.frame_cap = refresh_hz.and_then(|hz| frame_cap::advise(frames, hz, refresh_range));
+        merged.qoe = QoeScore::from_frames(frames, merged.stutter_count, refresh_hz);
+        merged.suggestions = crate::suggestions::build_session_suggestions(&merged);
+    }
+    storage::save_session(&app, &merged)?;
+    if let Ok(frames) = frames {
+        if let Err(e) = storage::save_frames(&app, &merged.session_id, &frames) {
+            log::warn!("保存合并后的帧时间失败: {}", e);
+        }
+    }
+
+    if !keep_originals.unwrap_or(false) {
+        for id in &merged.merged_from {
+            if let Err(e) = storage::remove_session(&app, id) {
+                log::warn!("合并后删除原会话 {} 失败: {}", id, e);
+            }
+        }
+    }
+    log::info!(
+        "已合并 {} 段会话为 {}",
+        merged.merged_from.len(),
+        merged.session_id
+    );
+    Ok(merged)
+}
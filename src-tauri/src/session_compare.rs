@@ -0,0 +1,368 @@
+use crate::fps_monitor::FpsSession;
+use crate::metrics::{percentile_low_fps, FrametimeHistogram};
+use crate::frame_stats::{FrameStats, FrameStatsReport};
+use crate::storage;
+use crate::tasks::{self, TaskHandle, TaskKind};
+use crate::thermal::ThermalReport;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tauri::AppHandle;
+
+/// 分段统计的默认时长 (秒)
+const DEFAULT_SEGMENT_SECS: f64 = 60.0;
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionComparison {
+    /// 平均 FPS 变化 (%)
+    pub avg_fps_delta_percent: f64,
+    /// 1% Low 变化 (%)
+    pub fps_1_low_delta_percent: f64,
+    /// 两次测试环境不一致之处，结果差异可能由此导致
+    pub mismatches: Vec<String>,
+}
+
+/// 多会话对比中单个会话的逐帧分析结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionFrameAnalysis {
+    pub session_id: String,
+    pub stats: FrameStatsReport,
+    pub histogram: FrametimeHistogram,
+    /// 按固定时长切分的分段统计
+    pub segments: Vec<SegmentStats>,
+    /// 相对基准会话（第一个）的平均 FPS 变化 (%)
+    pub avg_fps_delta_percent: f64,
+    /// 相对基准会话的 1% Low 变化 (%)
+    pub fps_1_low_delta_percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SegmentStats {
+    /// 分段起点（帧时间累计秒数）
+    pub start_secs: f64,
+    pub avg_fps: f64,
+    pub fps_1_low: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiSessionComparison {
+    pub baseline_id: String,
+    pub segment_secs: f64,
+    pub sessions: Vec<SessionFrameAnalysis>,
+}
+
+fn delta_percent(baseline: f64, candidate: f64) -> f64 {
+    if baseline <= 0.0 {
+        return 0.0;
+    }
+    ((candidate - baseline) / baseline * 100.0 * 10.0).round() / 10.0
+}
+
+// ==================== 环境差异 ====================
+
+fn display_mismatches(a: &FpsSession, b: &FpsSession, out: &mut Vec<String>) {
+    let (Some(ra), Some(rb)) = (&a.gpu_routing, &b.gpu_routing) else {
+        return;
+    };
+
+    if ra.path != rb.path {
+        out.push(format!(
+            "GPU 输出路径不同：{:?} → {:?}（独显直连与核显复制输出的性能差异可达 15%）",
+            ra.path, rb.path
+        ));
+    }
+    if ra.render_gpu != rb.render_gpu {
+        out.push(format!(
+            "渲染 GPU 不同：{} → {}",
+            ra.render_gpu.as_deref().unwrap_or("未知"),
+            rb.render_gpu.as_deref().unwrap_or("未知")
+        ));
+    } else if let (Some(ia), Some(ib)) = (&ra.render_gpu_id, &rb.render_gpu_id) {
+        // 同型号的两块显卡名称相同，按设备实例区分
+        if ia != ib {
+            out.push(format!(
+                "渲染 GPU 型号相同但不是同一块显卡：{} → {}",
+                ia, ib
+            ));
+        }
+    }
+
+    if let (Some(ma), Some(mb)) = (&ra.mux, &rb.mux) {
+        if ma.mode != mb.mode {
+            out.push(format!(
+                "笔记本 MUX 模式不同：{:?} → {:?}（独显直连与混合输出的帧率差异可达 10%~20%）",
+                ma.mode, mb.mode
+            ));
+        }
+    }
+
+    if let (Some(da), Some(db)) = (&ra.display, &rb.display) {
+        if da.internal != db.internal {
+            let label = |internal: bool| {
+                if internal {
+                    "内屏"
+                } else {
+                    "外接显示器"
+                }
+            };
+            out.push(format!(
+                "显示器不同：{} → {}",
+                label(da.internal),
+                label(db.internal)
+            ));
+        } else if da.monitor_name != db.monitor_name {
+            out.push(format!(
+                "显示器不同：{} → {}",
+                da.monitor_name, db.monitor_name
+            ));
+        }
+        if (da.width, da.height) != (db.width, db.height) {
+            out.push(format!(
+                "分辨率不同：{}x{} → {}x{}",
+                da.width, da.height, db.width, db.height
+            ));
+        }
+        if da.refresh_hz != db.refresh_hz {
+            out.push(format!(
+                "刷新率不同：{} Hz → {} Hz",
+                da.refresh_hz.unwrap_or(0.0),
+                db.refresh_hz.unwrap_or(0.0)
+            ));
+        }
+    }
+}
+
+fn thermal_mismatches(a: &FpsSession, b: &FpsSession, out: &mut Vec<String>) {
+    let (Some(ta), Some(tb)) = (&a.thermal, &b.thermal) else {
+        return;
+    };
+
+    let mode = |t: &ThermalReport| t.perf_mode.as_ref().map(|m| m.mode);
+    if let (Some(ma), Some(mb)) = (mode(ta), mode(tb)) {
+        if ma != mb {
+            out.push(format!("笔记本性能模式不同：{:?} → {:?}", ma, mb));
+        }
+    }
+    if ta.chassis.cooling != tb.chassis.cooling {
+        out.push(format!(
+            "散热配置不同：{:?} → {:?}",
+            ta.chassis.cooling, tb.chassis.cooling
+        ));
+    }
+}
+
+fn game_mismatches(a: &FpsSession, b: &FpsSession, out: &mut Vec<String>) {
+    let version = |s: &FpsSession| {
+        s.metadata
+            .game_version
+            .as_ref()
+            .and_then(|v| v.file_version.clone())
+    };
+    if let (Some(va), Some(vb)) = (version(a), version(b)) {
+        if va != vb {
+            out.push(format!("游戏版本不同：{} → {}", va, vb));
+        }
+    }
+
+    if a.metadata.launch_args != b.metadata.launch_args {
+        out.push(format!(
+            "启动参数不同：「{}」→「{}」",
+            a.metadata.launch_args.join(" "),
+            b.metadata.launch_args.join(" ")
+        ));
+    }
+
+    // 独立翻转与 DWM 合成之间的切换（如全屏改为窗口化）本身就会影响帧率
+    if let (Some(pa), Some(pb)) = (&a.present, &b.present) {
+        if pa.present_mode != pb.present_mode {
+            out.push(format!(
+                "呈现模式不同：{} → {}",
+                pa.present_mode, pb.present_mode
+            ));
+        }
+        if let (Some(va), Some(vb)) = (pa.vsync, pb.vsync) {
+            if va != vb {
+                let label = |on: bool| if on { "开" } else { "关" };
+                out.push(format!("垂直同步不同：{} → {}", label(va), label(vb)));
+            }
+        }
+    }
+}
+
+// ==================== 逐帧分析 ====================
+
+/// 分段累计帧时间，满一段即计算该段的平均 FPS 与 1% Low
+struct SegmentBuilder {
+    segment_ms: f64,
+    elapsed_ms: f64,
+    start_ms: f64,
+    frames: Vec<f64>,
+    segments: Vec<SegmentStats>,
+}
+
+impl SegmentBuilder {
+    fn new(segment_secs: f64) -> Self {
+        SegmentBuilder {
+            segment_ms: segment_secs * 1000.0,
+            elapsed_ms: 0.0,
+            start_ms: 0.0,
+            frames: Vec::new(),
+            segments: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, frametime_ms: f64) {
+        self.frames.push(frametime_ms);
+        self.elapsed_ms += frametime_ms;
+        if self.elapsed_ms - self.start_ms >= self.segment_ms {
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.frames.is_empty() {
+            return;
+        }
+        let total: f64 = self.frames.iter().sum();
+        let avg_fps = if total > 0.0 {
+            self.frames.len() as f64 * 1000.0 / total
+        } else {
+            0.0
+        };
+        self.segments.push(SegmentStats {
+            start_secs: (self.start_ms / 100.0).round() / 10.0,
+            avg_fps: (avg_fps * 10.0).round() / 10.0,
+            fps_1_low: (percentile_low_fps(&self.frames, 1.0) * 10.0).round() / 10.0,
+        });
+        self.start_ms = self.elapsed_ms;
+        self.frames.clear();
+    }
+
+    fn finish(mut self) -> Vec<SegmentStats> {
+        self.flush();
+        self.segments
+    }
+}
+
+/// 流式读取一个会话的原始帧时间，计算百分位、分布与分段统计
+fn analyze_frames(
+    app: &AppHandle,
+    session_id: &str,
+    segment_secs: f64,
+    task: &TaskHandle,
+) -> Result<SessionFrameAnalysis, String> {
+    let log = storage::load_frames(app, session_id)?;
+    let mut stats = FrameStats::default();
+    let mut histogram = FrametimeHistogram::default();
+    let mut segments = SegmentBuilder::new(segment_secs);
+    log.for_each_chunk(0, usize::MAX, |frames| {
+        stats.extend(frames);
+        histogram.merge(&FrametimeHistogram::from_frames(frames));
+        for &ft in frames {
+            segments.push(ft);
+        }
+        task.check()
+    })?;
+    if stats.count() == 0 {
+        return Err(format!("会话 {} 没有保存原始帧时间", session_id));
+    }
+
+    Ok(SessionFrameAnalysis {
+        session_id: session_id.to_string(),
+        stats: stats.report(),
+        histogram,
+        segments: segments.finish(),
+        avg_fps_delta_percent: 0.0,
+        fps_1_low_delta_percent: 0.0,
+    })
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 对比两次测试结果，并列出可能影响可比性的环境差异
+#[tauri::command]
+pub fn compare_sessions(
+    baseline: FpsSession,
+    candidate: FpsSession,
+) -> Result<SessionComparison, String> {
+    if !baseline
+        .process_name
+        .eq_ignore_ascii_case(&candidate.process_name)
+    {
+        return Err(format!(
+            "不能对比不同游戏的结果：{} / {}",
+            baseline.process_name, candidate.process_name
+        ));
+    }
+
+    let mut mismatches = Vec::new();
+    display_mismatches(&baseline, &candidate, &mut mismatches);
+    game_mismatches(&baseline, &candidate, &mut mismatches);
+    thermal_mismatches(&baseline, &candidate, &mut mismatches);
+
+    Ok(SessionComparison {
+        avg_fps_delta_percent: delta_percent(baseline.avg_fps, candidate.avg_fps),
+        fps_1_low_delta_percent: delta_percent(baseline.fps_1_low, candidate.fps_1_low),
+        mismatches,
+    })
+}
+
+/// 基于原始帧时间对比多个会话（第一个为基准），返回任务 ID
+/// 各会话在 rayon 线程池中并行分析，每完成一个会话推送一次进度，结果经 task-finished 返回
+#[tauri::command]
+pub fn compare_session_frames(
+    app: AppHandle,
+    session_ids: Vec<String>,
+    segment_secs: Option<f64>,
+) -> Result<String, String> {
+    let baseline_id = session_ids
+        .first()
+        .cloned()
+        .ok_or_else(|| "至少需要选择一个会话".to_string())?;
+    let segment_secs = segment_secs
+        .filter(|s| *s > 0.0)
+        .unwrap_or(DEFAULT_SEGMENT_SECS);
+
+    let sessions = session_ids
+        .iter()
+        .map(|id| storage::load_session(&app, id))
+        .collect::<Result<Vec<_>, _>>()?;
+    if let Some(other) = sessions.iter().find(|s| {
+        !s.process_name
+            .eq_ignore_ascii_case(&sessions[0].process_name)
+    }) {
+        return Err(format!(
+            "不能对比不同游戏的结果：{} / {}",
+            sessions[0].process_name, other.process_name
+        ));
+    }
+
+    let worker = app.clone();
+    tasks::spawn(&app, TaskKind::Analysis, "会话对比", move |task| {
+        let total = session_ids.len() as u64;
+        let done = AtomicU64::new(0);
+        let mut analyses = session_ids
+            .par_iter()
+            .map(|id| {
+                let analysis = analyze_frames(&worker, id, segment_secs, task)?;
+                let done = done.fetch_add(1, Ordering::Relaxed) + 1;
+                task.progress(done, total, format!("已分析 {}", id));
+                Ok(analysis)
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let (base_avg, base_low) = (analyses[0].stats.avg_fps, analyses[0].stats.fps_1_low);
+        for analysis in &mut analyses {
+            analysis.avg_fps_delta_percent = delta_percent(base_avg, analysis.stats.avg_fps);
+            analysis.fps_1_low_delta_percent = delta_percent(base_low, analysis.stats.fps_1_low);
+        }
+
+        Ok(MultiSessionComparison {
+            baseline_id,
+            segment_secs,
+            sessions: analyses,
+        })
+    })
+}
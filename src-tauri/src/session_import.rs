@@ -0,0 +1,659 @@
+use crate::fps_monitor::{self, FpsSession};
+use crate::frame_log::FrameLogWriter;
+use crate::frame_stats::FrameStats;
+use crate::metrics::{FpsSummary, FramePacing};
+use crate::qoe::{QoeAccumulator, QoeScore};
+use crate::stutter::StutterDetector;
+use crate::tasks::{self, TaskHandle, TaskKind};
+use crate::timeline::TimelineRecorder;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use tauri::AppHandle;
+
+/// 少于该帧数的数据无法得出有意义的统计
+const MIN_FRAMES: usize = 100;
+/// 帧时间合理范围 (ms)：低于下限相当于 20000 FPS，高于上限视为卡死
+const MIN_PLAUSIBLE_FRAMETIME: f64 = 0.05;
+const MAX_PLAUSIBLE_FRAMETIME: f64 = 5000.0;
+/// 问题帧占比超过该值时拒绝导入
+const REJECT_RATIO: f64 = 0.01;
+/// 帧时间累计与时间戳跨度的允许偏差
+const TIMELINE_TOLERANCE: f64 = 0.05;
+/// 帧时间变异系数低于该值时疑似人工生成（真实采集即使锁帧也有抖动）
+const MIN_NATURAL_CV: f64 = 0.001;
+
+/// 时间戳列（PresentMon 1.x / FrameView / OCAT 为秒，PresentMon 2.x 为毫秒）
+const TIME_COLUMNS: &[&str] = &["timeinseconds", "cpustarttime", "time", "timestamp"];
+/// 帧时间列 (ms)
+const FRAMETIME_COLUMNS: &[&str] = &["msbetweenpresents", "frametime", "frametime(ms)"];
+const PROCESS_COLUMNS: &[&str] = &["application", "processname", "process"];
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    /// 通过全部检查
+    Valid,
+    /// 可以导入，但结果需要人工确认（不参与排行榜）
+    Flagged,
+    /// 数据损坏或疑似伪造，不导入
+    Rejected,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueSeverity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    /// 问题代码 (e.g., "non_monotonic_time")
+    pub code: String,
+    pub severity: IssueSeverity,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub verdict: Verdict,
+    /// 有效帧数
+    pub frames: u64,
+    pub issues: Vec<ValidationIssue>,
+    /// 帧时间序列的 SHA-256，用于查重
+    pub content_hash: String,
+}
+
+/// 导入来源，随会话保存
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportInfo {
+    /// 原始文件名
+    pub source_file: String,
+    /// 识别出的格式 (e.g., "PresentMon 2.x")
+    pub source_format: String,
+    pub validation: ValidationReport,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportResult {
+    /// 是否已写入会话记录
+    pub imported: bool,
+    pub report: ValidationReport,
+    /// 被拒绝时仍返回解析结果，便于查看
+    pub session: Option<FpsSession>,
+}
+
+// ==================== CSV 解析 ====================
+
+/// CSV 格式信息
+struct CsvInfo {
+    format: String,
+    process_name: Option<String>,
+    /// 无法解析的数据行
+    bad_rows: usize,
+}
+
+fn find_column(header: &csv::StringRecord, names: &[&str]) -> Option<usize> {
+    names.iter().find_map(|name| {
+        header
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name))
+    })
+}
+
+/// 逐行读取 CSV，每行的 (时间戳, 帧时间 ms) 交给 `row`，缺少时间列时时间戳为 None；
+/// 不在内存中保留帧数据，超大文件也只占用固定内存
+fn parse_csv(
+    task: &TaskHandle,
+    path: &Path,
+    mut row: impl FnMut(Option<f64>, f64) -> Result<(), String>,
+) -> Result<CsvInfo, String> {
+    let file_len = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_path(path)
+        .map_err(|e| format!("无法读取 CSV: {}", e))?;
+    let header = reader
+        .headers()
+        .map_err(|e| format!("无法读取 CSV 表头: {}", e))?
+        .clone();
+
+    let frametime_idx = find_column(&header, FRAMETIME_COLUMNS)
+        .ok_or_else(|| "未找到帧时间列（MsBetweenPresents / FrameTime）".to_string())?;
+    let time_idx = find_column(&header, TIME_COLUMNS);
+    let process_idx = find_column(&header, PROCESS_COLUMNS);
+
+    let has = |name: &str| find_column(&header, &[name]).is_some();
+    let format = if has("CPUStartTime") {
+        "PresentMon 2.x"
+    } else if has("MsBetweenPresents") && has("GPU0Clk(MHz)") {
+        "FrameView"
+    } else if has("MsBetweenPresents") {
+        "PresentMon 1.x / OCAT"
+    } else {
+        "通用帧时间 CSV"
+    }
+    .to_string();
+
+    let mut bad_rows = 0;
+    let mut process_name: Option<String> = None;
+    let mut record = csv::StringRecord::new();
+    for line in 0usize.. {
+        if line % PROGRESS_ROWS == 0 {
+            task.check()?;
+            task.progress(reader.position().byte(), file_len, "解析并校验帧数据");
+        }
+        match reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(_) => {
+                bad_rows += 1;
+                continue;
+            }
+        }
+        let Some(frametime) = record
+            .get(frametime_idx)
+            .and_then(|v| v.trim().parse::<f64>().ok())
+        else {
+            bad_rows += 1;
+            continue;
+        };
+        let time = time_idx.and_then(|i| record.get(i)?.trim().parse::<f64>().ok());
+        if process_name.is_none() {
+            process_name = process_idx
+                .and_then(|i| record.get(i))
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty());
+        }
+        row(time, frametime)?;
+    }
+
+    Ok(CsvInfo {
+        format,
+        process_name,
+        bad_rows,
+    })
+}
+
+// ==================== 校验 ====================
+
+fn issue(issues: &mut Vec<ValidationIssue>, code: &str, severity: IssueSeverity, message: String) {
+    issues.push(ValidationIssue {
+        code: code.to_string(),
+        severity,
+        message,
+    });
+}
+
+fn percent(count: usize, total: usize) -> f64 {
+    (count as f64 / total.max(1) as f64 * 100.0 * 10.0).round() / 10.0
+}
+
+fn plausible(frametime: f64) -> bool {
+    frametime.is_finite()
+        && (MIN_PLAUSIBLE_FRAMETIME..=MAX_PLAUSIBLE_FRAMETIME).contains(&frametime)
+}
+
+/// 逐行累计的校验数据
+#[derive(Default)]
+struct RowChecks {
+    rows: usize,
+    /// 帧时间不在合理范围内的行
+    implausible: usize,
+    /// 与上一行完全相同的行（有时间戳时）
+    duplicates: usize,
+    prev_row: Option<(Option<f64>, f64)>,
+    timestamps: usize,
+    first_time: Option<f64>,
+    last_time: Option<f64>,
+    /// 时间戳倒退次数
+    backwards: usize,
+    /// 第一个有效帧时间，核对时间线时不计入累计
+    first_frame: Option<f64>,
+    /// 有效帧时间序列的 SHA-256，用于查重
+    hasher: Sha256,
+}
+
+impl RowChecks {
+    /// 记录一行，返回该帧是否计入统计
+    fn push(&mut self, time: Option<f64>, frametime: f64) -> bool {
+        self.rows += 1;
+        // 重复行（连续两行时间戳与帧时间完全相同，常见于拼接或复制粘贴）
+        let row = (time, frametime);
+        if time.is_some() && self.prev_row == Some(row) {
+            self.duplicates += 1;
+        }
+        self.prev_row = Some(row);
+
+        if let Some(t) = time {
+            if self.last_time.is_some_and(|last| t < last) {
+                self.backwards += 1;
+            }
+            self.timestamps += 1;
+            self.first_time.get_or_insert(t);
+            self.last_time = Some(t);
+        }
+
+        if !plausible(frametime) {
+            self.implausible += 1;
+            return false;
+        }
+        self.first_frame.get_or_insert(frametime);
+        self.hasher.update(frametime.to_le_bytes());
+        true
+    }
+}
+
+/// 由逐行校验数据与有效帧的统计得出校验报告
+fn validate(
+    checks: RowChecks,
+    stats: &FrameStats,
+    bad_rows: usize,
+    existing_hashes: &[String],
+) -> ValidationReport {
+    let mut issues = Vec::new();
+    let total = checks.rows;
+    let frames = stats.count() as usize;
+
+    if bad_rows > 0 {
+        let severity = if bad_rows as f64 > total.max(1) as f64 * REJECT_RATIO {
+            IssueSeverity::Error
+        } else {
+            IssueSeverity::Warning
+        };
+        issue(
+            &mut issues,
+            "unparsable_rows",
+            severity,
+            format!("{} 行数据无法解析", bad_rows),
+        );
+    }
+
+    // 帧时间合理范围
+    let implausible = checks.implausible;
+    if implausible > 0 {
+        let severity = if implausible as f64 > total as f64 * REJECT_RATIO {
+            IssueSeverity::Error
+        } else {
+            IssueSeverity::Warning
+        };
+        issue(
+            &mut issues,
+            "implausible_frametime",
+            severity,
+            format!(
+                "{} 帧（{}%）帧时间不在 {}~{} ms 的合理范围内",
+                implausible,
+                percent(implausible, total),
+                MIN_PLAUSIBLE_FRAMETIME,
+                MAX_PLAUSIBLE_FRAMETIME
+            ),
+        );
+    }
+
+    let duplicates = checks.duplicates;
+    if duplicates > 0 {
+        let severity = if duplicates as f64 > total as f64 * REJECT_RATIO {
+            IssueSeverity::Error
+        } else {
+            IssueSeverity::Warning
+        };
+        issue(
+            &mut issues,
+            "duplicate_rows",
+            severity,
+            format!("{} 行与上一行完全相同", duplicates),
+        );
+    }
+
+    // 时间戳单调递增
+    if checks.timestamps == 0 {
+        issue(
+            &mut issues,
+            "no_timestamps",
+            IssueSeverity::Warning,
+            "没有时间戳列，无法校验时间线".to_string(),
+        );
+    } else if checks.backwards > 0 {
+        let backwards = checks.backwards;
+        let severity = if backwards as f64 > total as f64 * REJECT_RATIO {
+            IssueSeverity::Error
+        } else {
+            IssueSeverity::Warning
+        };
+        issue(
+            &mut issues,
+            "non_monotonic_time",
+            severity,
+            format!("时间戳有 {} 处倒退，数据可能被拼接或重新排序", backwards),
+        );
+    }
+
+    if frames < MIN_FRAMES {
+        issue(
+            &mut issues,
+            "too_few_frames",
+            IssueSeverity::Error,
+            format!("有效帧只有 {} 帧（至少需要 {} 帧）", frames, MIN_FRAMES),
+        );
+    } else {
+        // 帧时间累计应与时间戳跨度一致；时间戳单位按更接近的一种判断（秒或毫秒）
+        if let (Some(first), Some(last)) = (checks.first_time, checks.last_time) {
+            let span = last - first;
+            let sum = stats.total_ms() - checks.first_frame.unwrap_or(0.0);
+            let span_ms = if (span * 1000.0 - sum).abs() < (span - sum).abs() {
+                span * 1000.0
+            } else {
+                span
+            };
+            if checks.timestamps > 1
+                && span_ms > 0.0
+                && ((sum - span_ms) / span_ms).abs() > TIMELINE_TOLERANCE
+            {
+                issue(
+                    &mut issues,
+                    "timeline_mismatch",
+                    IssueSeverity::Warning,
+                    format!(
+                        "帧时间累计 {:.1}s 与时间戳跨度 {:.1}s 不一致，数据可能经过编辑",
+                        sum / 1000.0,
+                        span_ms / 1000.0
+                    ),
+                );
+            }
+        }
+
+        let mean = stats.mean_ms();
+        if mean > 0.0 && stats.stdev_ms() / mean < MIN_NATURAL_CV {
+            issue(
+                &mut issues,
+                "unnatural_uniformity",
+                IssueSeverity::Warning,
+                "帧时间几乎完全一致，真实采集不会如此平稳，疑似人工生成".to_string(),
+            );
+        }
+    }
+
+    let hash: String = checks
+        .hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    if existing_hashes.contains(&hash) {
+        issue(
+            &mut issues,
+            "duplicate_import",
+            IssueSeverity::Error,
+            "会话记录中已有完全相同的帧数据".to_string(),
+        );
+    }
+
+    let verdict = if issues.iter().any(|i| i.severity == IssueSeverity::Error) {
+        Verdict::Rejected
+    } else if issues.is_empty() {
+        Verdict::Valid
+    } else {
+        Verdict::Flagged
+    };
+
+    ValidationReport {
+        verdict,
+        frames: frames as u64,
+        issues,
+        content_hash: hash,
+    }
+}
+
+// ==================== 统计 ====================
+
+/// 由帧时间得出的会话统计
+pub(crate) struct FrameTotals {
+    summary: FpsSummary,
+    pacing: FramePacing,
+    stutter_count: u32,
+    qoe: Option<QoeScore>,
+    total_ms: f64,
+}
+
+impl FrameTotals {
+    /// 由完整的帧时间序列精确计算
+    pub(crate) fn from_frames(frame_times: &[f64]) -> Self {
+        let stutter_count = crate::stutter::count_stutters(frame_times);
+        FrameTotals {
+            summary: FpsSummary::from_frames(frame_times),
+            pacing: FramePacing::from_frames(frame_times),
+            stutter_count,
+            // 导入与恢复的数据不含显示器信息，按 60 Hz 估算
+            qoe: QoeScore::from_frames(frame_times, stutter_count, None),
+            total_ms: frame_times.iter().sum(),
+        }
+    }
+}
+
+/// 导入时逐帧累计：有效帧同时写入帧时间文件、直方图统计、卡顿检测与每秒聚合，
+/// 不保留帧时间序列（Low FPS 与百分位按直方图近似）
+struct ImportSink {
+    checks: RowChecks,
+    stats: FrameStats,
+    stutters: StutterDetector,
+    stutter_count: u32,
+    qoe: QoeAccumulator,
+    timeline: TimelineRecorder,
+    elapsed_ms: f64,
+    frames: FrameLogWriter,
+}
+
+impl ImportSink {
+    fn new(frames: FrameLogWriter) -> Self {
+        ImportSink {
+            checks: RowChecks::default(),
+            stats: FrameStats::default(),
+            stutters: StutterDetector::default(),
+            stutter_count: 0,
+            qoe: QoeAccumulator::new(None),
+            // 会话 ID 要等读到进程名后才确定，保存前再填入
+            timeline: TimelineRecorder::new("", false),
+            elapsed_ms: 0.0,
+            frames,
+        }
+    }
+
+    fn push(&mut self, time: Option<f64>, frametime: f64) -> Result<(), String> {
+        if !self.checks.push(time, frametime) {
+            return Ok(());
+        }
+        self.stats.push(frametime);
+        if self.stutters.push(frametime).is_some() {
+            self.stutter_count += 1;
+        }
+        self.qoe.push(frametime);
+        self.timeline.push(self.elapsed_ms / 1000.0, frametime);
+        self.elapsed_ms += frametime;
+        self.frames.push(frametime)
+    }
+
+    fn totals(&self) -> FrameTotals {
+        FrameTotals {
+            summary: FpsSummary::from_stats(&self.stats),
+            pacing: FramePacing::from_stats(&self.stats),
+            stutter_count: self.stutter_count,
+            qoe: self.qoe.finish(self.stutter_count),
+            total_ms: self.stats.total_ms(),
+        }
+    }
+}
+
+/// 由帧统计构建会话（导入与崩溃恢复共用）
+pub(crate) fn build_session(
+    session_id: String,
+    process_name: String,
+    totals: FrameTotals,
+    import: Option<ImportInfo>,
+) -> FpsSession {
+    let round = |v: f64| (v * 10.0).round() / 10.0;
+    let summary = totals.summary;
+    let stutter_count = totals.stutter_count;
+
+    FpsSession {
+        session_id,
+        process_name,
+        avg_fps: summary.avg_fps,
+        fps_1_low: summary.fps_1_low,
+        fps_01_low: summary.fps_01_low,
+        max_fps: summary.max_fps,
+        min_fps: summary.min_fps,
+        total_frames: summary.total_frames,
+        duration_secs: round(totals.total_ms / 1000.0),
+        wall_duration_secs: round(totals.total_ms / 1000.0),
+        pauses: Vec::new(),
+        status: Default::default(),
+        pcie_link: None,
+        vram: None,
+        gpu_routing: None,
+        capture_rule: None,
+        capture_profile: Default::default(),
+        stream: None,
+        metadata: Default::default(),
+        thermal: None,
+        overhead: None,
+        pipeline: None,
+        import,
+        suggestions: Vec::new(),
+        tags: Vec::new(),
+        merged_from: Vec::new(),
+        plugin_metrics: Vec::new(),
+        started_at_ms: 0,
+        markers: Vec::new(),
+        driver_resets: Vec::new(),
+        dpc_latency: None,
+        memory_pressure: None,
+        background_activity: Vec::new(),
+        launch: None,
+        stutter_count,
+        percentiles: Default::default(),
+        pacing: totals.pacing,
+        benchmark: None,
+        qoe: totals.qoe,
+        vrr: None,
+        frame_cap: None,
+        present: None,
+        latency: None,
+    }
+}
+
+// ==================== 导入 ====================
+
+/// 解析时每读取这么多行更新一次进度并检查是否取消
+const PROGRESS_ROWS: usize = 50_000;
+
+fn import(
+    app: &AppHandle,
+    task: &TaskHandle,
+    path: &str,
+    process_name: Option<String>,
+) -> Result<ImportResult, String> {
+    let file = Path::new(path);
+    // 会话库锁定时无法读取已有会话，跳过查重
+    let existing: Vec<String> = crate::storage::load_sessions(app)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|s| s.import.map(|i| i.validation.content_hash))
+        .collect();
+
+    // 有效帧边解析边写入帧时间文件；被拒绝或出错时丢弃
+    let staged = crate::storage::staged_frames_path(app)?;
+    let mut sink = ImportSink::new(FrameLogWriter::create(&staged)?);
+    let csv = parse_csv(task, file, |time, frametime| sink.push(time, frametime))?;
+    task.check()?;
+    let totals = sink.totals();
+    let ImportSink {
+        checks,
+        stats,
+        timeline,
+        frames,
+        ..
+    } = sink;
+    let report = validate(checks, &stats, csv.bad_rows, &existing);
+
+    let process_name = process_name
+        .or(csv.process_name)
+        .unwrap_or_else(|| "imported".to_string());
+    let source_file = file
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let session_id = format!("import-{}", fps_monitor::new_session_id(&process_name));
+    let mut session = build_session(
+        session_id,
+        process_name,
+        totals,
+        Some(ImportInfo {
+            source_file: source_file.clone(),
+            source_format: csv.format.clone(),
+            validation: report.clone(),
+        }),
+    );
+
+    log::info!(
+        "导入 {} ({}): {} 帧 → {:?}",
+        source_file,
+        csv.format,
+        report.frames,
+        report.verdict
+    );
+    for i in &report.issues {
+        log::warn!("导入校验 [{}]: {}", i.code, i.message);
+    }
+
+    if report.verdict == Verdict::Rejected {
+        return Ok(ImportResult {
+            imported: false,
+            report,
+            session: Some(session),
+        });
+    }
+
+    // 写入会话记录后不再响应取消
+    task.check()?;
+    task.progress(1, 1, "保存会话");
+    session.suggestions = crate::suggestions::build_session_suggestions(&session);
+    crate::storage::save_session(app, &session)?;
+    if let Some(mut timeline) = timeline.finish() {
+        timeline.session_id = session.session_id.clone();
+        if let Err(e) = crate::storage::save_timeline(app, &timeline) {
+            log::warn!("保存每秒聚合数据失败: {}", e);
+        }
+    }
+    if let Err(e) = frames
+        .finish()
+        .and_then(|_| crate::storage::commit_frames(app, &session.session_id, &staged))
+    {
+        log::warn!("保存原始帧时间失败: {}", e);
+    }
+    Ok(ImportResult {
+        imported: true,
+        report,
+        session: Some(session),
+    })
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 导入第三方帧时间 CSV（PresentMon / FrameView / OCAT / CapFrameX 等），返回任务 ID
+/// 校验不通过的数据不会写入会话记录
+#[tauri::command]
+pub fn import_frame_csv(
+    app: AppHandle,
+    path: String,
+    process_name: Option<String>,
+) -> Result<String, String> {
+    crate::kiosk::ensure_unlocked()?;
+    let worker = app.clone();
+    tasks::spawn(&app, TaskKind::Import, "导入帧时间", move |task| {
+        import(&worker, task, &path, process_name)
+    })
+}
@@ -0,0 +1,289 @@
+use crate::fps_monitor::{CaptureProfile, SessionStatus};
+use crate::search;
+use crate::storage::{self, SessionIndexEntry};
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use tauri::AppHandle;
+
+/// 默认每页条数
+const DEFAULT_PAGE_SIZE: usize = 50;
+/// 每页最大条数
+const MAX_PAGE_SIZE: usize = 500;
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionFilter {
+    /// 游戏进程名或中英文名（不区分大小写）
+    pub game: Option<String>,
+    /// 开始日期 (YYYY-MM-DD，含当天)
+    pub date_from: Option<String>,
+    /// 结束日期 (YYYY-MM-DD，含当天)
+    pub date_to: Option<String>,
+    /// 需同时包含的标签
+    pub tags: Vec<String>,
+    /// 最短有效时长 (秒)
+    pub min_duration_secs: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortField {
+    #[default]
+    StartedAt,
+    GameName,
+    AvgFps,
+    Fps1Low,
+    Duration,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SessionSort {
+    pub field: SortField,
+    pub descending: bool,
+}
+
+impl Default for SessionSort {
+    /// 默认最新的在前
+    fn default() -> Self {
+        SessionSort {
+            field: SortField::StartedAt,
+            descending: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageRequest {
+    /// 页码，从 0 开始
+    pub page: usize,
+    pub page_size: usize,
+}
+
+/// 历史列表用的会话摘要（不含建议、元数据等详情）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub session_id: String,
+    pub process_name: String,
+    /// 按界面语言选择的游戏名（未知游戏为进程名）
+    pub game_name: String,
+    /// 开始时间 (本地时间 "YYYY-MM-DD HH:MM:SS")
+    pub started_at: Option<String>,
+    pub avg_fps: f64,
+    pub fps_1_low: f64,
+    pub fps_01_low: f64,
+    pub duration_secs: f64,
+    pub total_frames: u64,
+    pub status: SessionStatus,
+    pub capture_profile: CaptureProfile,
+    /// 是否从第三方 CSV 导入
+    pub imported: bool,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionPage {
+    pub items: Vec<SessionSummary>,
+    /// 符合筛选条件的总数
+    pub total: usize,
+    pub page: usize,
+    pub page_size: usize,
+}
+
+// ==================== 筛选与排序 ====================
+
+/// 会话 ID 以本地开始时间开头 (e.g., "20240601-203015-cs2")
+pub fn started_at(session_id: &str) -> Option<NaiveDateTime> {
+    let prefix = session_id.get(..15)?;
+    NaiveDateTime::parse_from_str(prefix, "%Y%m%d-%H%M%S").ok()
+}
+
+fn parse_date(value: &Option<String>) -> Result<Option<NaiveDate>, String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            NaiveDate::parse_from_str(v, "%Y-%m-%d")
+                .map_err(|_| format!("日期格式应为 YYYY-MM-DD: {}", v))
+        })
+        .transpose()
+}
+
+/// 整理标签：去掉首尾空白、空标签与重复标签
+pub fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().to_string();
+        if !tag.is_empty() && !result.contains(&tag) {
+            result.push(tag);
+        }
+    }
+    result
+}
+
+struct Matcher {
+    game: Option<String>,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    tags: Vec<String>,
+    min_duration: f64,
+}
+
+impl Matcher {
+    fn new(filter: &SessionFilter) -> Result<Self, String> {
+        Ok(Matcher {
+            game: filter
+                .game
+                .as_deref()
+                .map(|g| g.trim().to_lowercase())
+                .filter(|g| !g.is_empty()),
+            from: parse_date(&filter.date_from)?,
+            to: parse_date(&filter.date_to)?,
+            tags: normalize_tags(filter.tags.clone()),
+            min_duration: filter.min_duration_secs.unwrap_or(0.0),
+        })
+    }
+
+    fn matches(&self, session: &SessionIndexEntry, names: &[String]) -> bool {
+        if let Some(game) = &self.game {
+            if !names.iter().any(|n| n.to_lowercase() == *game) {
+                return false;
+            }
+        }
+        if self.from.is_some() || self.to.is_some() {
+            let Some(date) = started_at(&session.session_id).map(|t| t.date()) else {
+                return false;
+            };
+            if self.from.is_some_and(|from| date < from) || self.to.is_some_and(|to| date > to) {
+                return false;
+            }
+        }
+        session.duration_secs >= self.min_duration
+            && self.tags.iter().all(|t| session.tags.contains(t))
+    }
+}
+
+fn compare(a: &SessionSummary, b: &SessionSummary, field: SortField) -> Ordering {
+    let by_f64 = |x: f64, y: f64| x.partial_cmp(&y).unwrap_or(Ordering::Equal);
+    match field {
+        // 会话 ID 以开始时间开头，可直接按字符串排序
+        SortField::StartedAt => a.session_id.cmp(&b.session_id),
+        SortField::GameName => a.game_name.cmp(&b.game_name),
+        SortField::AvgFps => by_f64(a.avg_fps, b.avg_fps),
+        SortField::Fps1Low => by_f64(a.fps_1_low, b.fps_1_low),
+        SortField::Duration => by_f64(a.duration_secs, b.duration_secs),
+    }
+}
+
+fn summarize(session: SessionIndexEntry, game_name: String) -> SessionSummary {
+    SessionSummary {
+        started_at: started_at(&session.session_id)
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string()),
+        session_id: session.session_id,
+        process_name: session.process_name,
+        game_name,
+        avg_fps: session.avg_fps,
+        fps_1_low: session.fps_1_low,
+        fps_01_low: session.fps_01_low,
+        duration_secs: session.duration_secs,
+        total_frames: session.total_frames,
+        status: session.status,
+        capture_profile: session.capture_profile,
+        imported: session.imported,
+        tags: session.tags,
+    }
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 分页查询会话摘要，筛选与排序都在后端完成（基于摘要索引，不读取完整会话）
+#[tauri::command]
+pub fn query_sessions(
+    app: AppHandle,
+    filter: Option<SessionFilter>,
+    sort: Option<SessionSort>,
+    page: Option<PageRequest>,
+) -> Result<SessionPage, String> {
+    let matcher = Matcher::new(&filter.unwrap_or_default())?;
+    let sort = sort.unwrap_or_default();
+    let (page, page_size) = page
+        .map(|p| (p.page, p.page_size.clamp(1, MAX_PAGE_SIZE)))
+        .unwrap_or((0, DEFAULT_PAGE_SIZE));
+
+    let language = crate::settings::get().game_name_language;
+    let known = search::known_names();
+
+    let mut summaries: Vec<SessionSummary> = storage::load_index(&app)?
+        .into_iter()
+        .filter_map(|session| {
+            let process = session.process_name.to_lowercase();
+            let (name_en, name_zh) = known.get(&process).cloned().unzip();
+            let names: Vec<String> = [Some(process), name_en.clone(), name_zh.clone().flatten()]
+                .into_iter()
+                .flatten()
+                .collect();
+            if !matcher.matches(&session, &names) {
+                return None;
+            }
+            let game_name = match name_en {
+                Some(en) => language.pick(&en, name_zh.flatten().as_deref()),
+                None => session.process_name.clone(),
+            };
+            Some(summarize(session, game_name))
+        })
+        .collect();
+
+    summaries.sort_by(|a, b| {
+        let ord = compare(a, b, sort.field);
+        if sort.descending {
+            ord.reverse()
+        } else {
+            ord
+        }
+    });
+
+    let total = summaries.len();
+    let items = summaries
+        .into_iter()
+        .skip(page.saturating_mul(page_size))
+        .take(page_size)
+        .collect();
+
+    Ok(SessionPage {
+        items,
+        total,
+        page,
+        page_size,
+    })
+}
+
+/// 设置会话标签（覆盖原有标签）
+#[tauri::command]
+pub fn set_session_tags(
+    app: AppHandle,
+    session_id: String,
+    tags: Vec<String>,
+) -> Result<Vec<String>, String> {
+    crate::kiosk::ensure_unlocked()?;
+    let mut session = storage::load_session(&app, &session_id)?;
+    session.tags = normalize_tags(tags);
+    storage::save_session(&app, &session)?;
+    Ok(session.tags)
+}
+
+/// 全部会话用过的标签（按使用次数排序，用于筛选下拉框）
+#[tauri::command]
+pub fn list_session_tags(app: AppHandle) -> Result<Vec<String>, String> {
+    let mut counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    for entry in storage::load_index(&app)? {
+        for tag in entry.tags {
+            *counts.entry(tag).or_default() += 1;
+        }
+    }
+    let mut tags: Vec<(String, usize)> = counts.into_iter().collect();
+    tags.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    Ok(tags.into_iter().map(|(tag, _)| tag).collect())
+}
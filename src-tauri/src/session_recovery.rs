@@ -0,0 +1,279 @@
+use crate::fps_monitor::{CaptureProfile, FpsSession, SessionStatus};
+use crate::session_meta::SessionMetadata;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, UNIX_EPOCH};
+use sysinfo::{Pid, System};
+use tauri::{AppHandle, Emitter};
+
+/// 检查点目录（位于当前用户档案的数据目录下）
+const CHECKPOINT_DIR: &str = "checkpoints";
+/// 帧时间写入检查点的间隔，应用崩溃时最多丢失这段时间的帧
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+// ==================== 数据结构 ====================
+
+/// 进行中会话的检查点头信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointHeader {
+    pub session_id: String,
+    pub process_name: String,
+    pub capture_profile: CaptureProfile,
+    /// 开始时间 (unix 毫秒)
+    pub started_at_ms: u64,
+    /// 写入检查点的 GameBench 进程 PID
+    pub app_pid: u32,
+    /// 本次会话启动的 PresentMon PID
+    pub presentmon_pid: u32,
+    pub metadata: SessionMetadata,
+}
+
+/// 启动时恢复的会话（供前端提示）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveredSession {
+    pub session_id: String,
+    pub process_name: String,
+    pub total_frames: u64,
+    pub duration_secs: f64,
+    /// 是否结束了上次遗留的 PresentMon 进程
+    pub stopped_presentmon: bool,
+}
+
+fn recovered() -> &'static Mutex<Vec<RecoveredSession>> {
+    static RECOVERED: OnceLock<Mutex<Vec<RecoveredSession>>> = OnceLock::new();
+    RECOVERED.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+// ==================== 检查点写入 ====================
+
+fn checkpoint_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = crate::user_profile::data_dir(app)?.join(CHECKPOINT_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建检查点目录: {}", e))?;
+    Ok(dir)
+}
+
+fn header_path(dir: &Path, session_id: &str) -> PathBuf {
+    dir.join(format!("{}.json", session_id))
+}
+
+fn frames_path(dir: &Path, session_id: &str) -> PathBuf {
+    dir.join(format!("{}.frames", session_id))
+}
+
+/// 监测期间定期把帧时间追加到检查点文件
+pub struct Checkpoint {
+    dir: PathBuf,
+    session_id: String,
+    writer: BufWriter<File>,
+    last_flush: Instant,
+}
+
+impl Checkpoint {
+    /// 写入头信息并创建帧文件；失败时仅记录日志，不影响监测
+    pub fn begin(app: &AppHandle, header: &CheckpointHeader) -> Option<Self> {
+        let result = (|| -> Result<Self, String> {
+            let dir = checkpoint_dir(app)?;
+            let json = serde_json::to_string(header).map_err(|e| e.to_string())?;
+            std::fs::write(header_path(&dir, &header.session_id), json)
+                .map_err(|e| format!("写入检查点失败: {}", e))?;
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(frames_path(&dir, &header.session_id))
+                .map_err(|e| format!("创建检查点帧文件失败: {}", e))?;
+            Ok(Checkpoint {
+                dir,
+                session_id: header.session_id.clone(),
+                writer: BufWriter::new(file),
+                last_flush: Instant::now(),
+            })
+        })();
+        result.map_err(|e| log::warn!("{}", e)).ok()
+    }
+
+    /// 追加一帧；每 FLUSH_INTERVAL 落盘一次
+    pub fn push(&mut self, frametime: f64) {
+        let _ = writeln!(self.writer, "{:.4}", frametime);
+        if self.last_flush.elapsed() >= FLUSH_INTERVAL {
+            let _ = self.writer.flush();
+            self.last_flush = Instant::now();
+        }
+    }
+
+    /// 会话已正常保存，删除检查点
+    pub fn finish(self) {
+        let Checkpoint {
+            dir,
+            session_id,
+            writer,
+            ..
+        } = self;
+        drop(writer);
+        remove_checkpoint(&dir, &session_id);
+    }
+}
+
+fn remove_checkpoint(dir: &Path, session_id: &str) {
+    let _ = std::fs::remove_file(header_path(dir, session_id));
+    let _ = std::fs::remove_file(frames_path(dir, session_id));
+}
+
+// ==================== 崩溃恢复 ====================
+
+/// 上次实例遗留的 PresentMon 仍在运行时结束它（按 PID + 进程名确认，避免误杀复用的 PID）
+fn stop_orphan_presentmon(pid: u32) -> bool {
+    let mut sys = System::new();
+    let target = Pid::from_u32(pid);
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::Some(&[target]), true);
+    let Some(process) = sys.process(target) else {
+        return false;
+    };
+    let name = process.name().to_string_lossy().to_lowercase();
+    if !name.starts_with("presentmon") {
+        return false;
+    }
+    let killed = process.kill();
+    if killed {
+        log::info!("已结束上次遗留的 PresentMon (PID {})", pid);
+    }
+    killed
+}
+
+fn read_frames(path: &Path) -> Vec<f64> {
+    let Ok(file) = File::open(path) else {
+        return Vec::new();
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| line.trim().parse::<f64>().ok())
+        .filter(|ft| *ft > 0.0 && ft.is_finite())
+        .collect()
+}
+
+/// 帧文件最后写入时间 (unix 毫秒)
+fn last_write_ms(path: &Path) -> Option<u64> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_millis() as u64)
+}
+
+fn recover_one(
+    app: &AppHandle,
+    dir: &Path,
+    header: CheckpointHeader,
+) -> Result<Option<RecoveredSession>, String> {
+    let stopped_presentmon =
+        header.app_pid != std::process::id() && stop_orphan_presentmon(header.presentmon_pid);
+    // 内置 ETW 采集的实时会话不会随应用退出而结束
+    if header.app_pid != std::process::id() && crate::present_etw::stop_orphan(&header.session_id) {
+        log::info!("已停止上次遗留的 ETW 采集会话 {}", header.session_id);
+    }
+
+    let frames_file = frames_path(dir, &header.session_id);
+    let frames = read_frames(&frames_file);
+    if frames.is_empty() {
+        log::info!("检查点 {} 没有帧数据，已丢弃", header.session_id);
+        remove_checkpoint(dir, &header.session_id);
+        return Ok(None);
+    }
+
+    let mut session = crate::session_import::build_session(
+        header.session_id.clone(),
+        header.process_name.clone(),
+        crate::session_import::FrameTotals::from_frames(&frames),
+        None,
+    );
+    session.status = SessionStatus::Recovered;
+    session.capture_profile = header.capture_profile;
+    session.started_at_ms = header.started_at_ms;
+    session.metadata = header.metadata;
+    if let Some(end_ms) = last_write_ms(&frames_file) {
+        let wall = end_ms.saturating_sub(header.started_at_ms) as f64 / 1000.0;
+        session.wall_duration_secs = ((wall * 10.0).round() / 10.0).max(session.duration_secs);
+    }
+    session.suggestions = crate::suggestions::build_session_suggestions(&session);
+
+    crate::storage::save_session(app, &session)?;
+    if let Some(timeline) = crate::timeline::from_frame_times(&session.session_id, &frames) {
+        if let Err(e) = crate::storage::save_timeline(app, &timeline) {
+            log::warn!("保存每秒聚合数据失败: {}", e);
+        }
+    }
+    if let Err(e) = crate::storage::save_frames(app, &session.session_id, &frames) {
+        log::warn!("保存原始帧时间失败: {}", e);
+    }
+    remove_checkpoint(dir, &header.session_id);
+    log::info!(
+        "已恢复中断的会话 {}: {} 帧",
+        session.session_id,
+        session.total_frames
+    );
+    Ok(Some(summary(&session, stopped_presentmon)))
+}
+
+fn summary(session: &FpsSession, stopped_presentmon: bool) -> RecoveredSession {
+    RecoveredSession {
+        session_id: session.session_id.clone(),
+        process_name: session.process_name.clone(),
+        total_frames: session.total_frames,
+        duration_secs: session.duration_secs,
+        stopped_presentmon,
+    }
+}
+
+/// 恢复上次异常退出时未完成的会话，并清理遗留的 PresentMon
+/// 启动时与开始新监测前调用；正在进行的会话不会被处理
+pub fn recover(app: &AppHandle) {
+    let Ok(dir) = checkpoint_dir(app) else {
+        return;
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return;
+    };
+    let active: Vec<String> = crate::fps_monitor::active_sessions()
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+
+    let mut restored = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let header: CheckpointHeader = match std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| serde_json::from_str(&text).map_err(|e| e.to_string()))
+        {
+            Ok(h) => h,
+            Err(e) => {
+                log::warn!("跳过无法解析的检查点 {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        if active.contains(&header.session_id) {
+            continue;
+        }
+        match recover_one(app, &dir, header) {
+            Ok(Some(item)) => restored.push(item),
+            Ok(None) => {}
+            Err(e) => log::warn!("恢复会话失败: {}", e),
+        }
+    }
+
+    if !restored.is_empty() {
+        let _ = app.emit("fps-session-recovered", &restored);
+        recovered().lock().unwrap().extend(restored);
+    }
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 取出本次启动后恢复的会话（取出后清空，前端只提示一次）
+#[tauri::command]
+pub fn take_recovered_sessions() -> Result<Vec<RecoveredSession>, String> {
+    Ok(std::mem::take(&mut *recovered().lock().unwrap()))
+}
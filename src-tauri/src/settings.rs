@@ -0,0 +1,202 @@
+use crate::fps_monitor::{BusyPolicy, CaptureBackend, CaptureProfile};
+use crate::game_detect::GameNameLanguage;
+use crate::hotkeys::HotkeySettings;
+use crate::lan_bench::LanSettings;
+use crate::mirrors::MirrorSettings;
+use crate::power::SleepBehavior;
+use crate::privacy::PrivacyOptions;
+use crate::proxy::ProxySettings;
+use crate::script_hooks::ScriptHook;
+use crate::smoothing::FpsSmoothing;
+use crate::thermal::ChassisProfile;
+use crate::units::UnitPrefs;
+use crate::updater::UpdateSettings;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use tauri::AppHandle;
+
+/// GameBench CN 服务端
+pub const DEFAULT_SERVER_URL: &str = "https://gamebench-cn.vercel.app";
+
+const SETTINGS_FILE: &str = "settings.json";
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    /// 启动游戏时自动弹出 FPS 监测提示
+    pub auto_detect: bool,
+    /// 检测到游戏启动时自动开始监测，游戏退出时自动结束
+    pub auto_monitor: bool,
+    /// 自动上传 FPS 数据
+    pub auto_upload: bool,
+    /// 服务器地址
+    pub server_url: String,
+    /// 离线模式：不发起任何互联网请求（局域网测试不受影响）
+    pub offline: bool,
+    /// 其他方式均失败时，允许通过 PowerShell 检测硬件（较慢，可能被安全软件拦截）
+    pub allow_powershell_fallback: bool,
+    /// 报告与导出使用的单位和数字格式（默认按系统语言区域推断）
+    pub units: UnitPrefs,
+    /// 机身与散热配置（随测试结果保存）
+    pub chassis: ChassisProfile,
+    /// 游戏名称显示语言（默认按系统语言区域推断）
+    pub game_name_language: GameNameLanguage,
+    /// 默认采集档位
+    pub capture_profile: CaptureProfile,
+    /// 帧数据采集后端：PresentMon 或内置 ETW（无需 PresentMon.exe）
+    pub capture_backend: CaptureBackend,
+    /// 监测期间通过 ETW 采样 DPC/ISR 延迟（需管理员权限，最低开销档位下不采样）
+    pub dpc_sampling: bool,
+    /// 已有监测进行中时开始新监测的默认处理方式
+    pub busy_policy: BusyPolicy,
+    /// 实时 FPS 显示的平滑方式
+    pub fps_smoothing: FpsSmoothing,
+    /// 监测期间系统睡眠后继续还是结束
+    pub sleep_behavior: SleepBehavior,
+    /// 导出与分享报告时的隐私选项
+    pub privacy: PrivacyOptions,
+    /// 云端账户（每个用户 profile 各自登录）
+    pub cloud_account: Option<CloudAccount>,
+    /// 局域网测试台
+    pub lan: LanSettings,
+    /// 应用更新通道与镜像
+    pub update: UpdateSettings,
+    /// 下载镜像（国内访问 GitHub/服务器不稳定时使用）
+    pub mirrors: MirrorSettings,
+    /// 网络代理
+    pub proxy: ProxySettings,
+    /// 已启用的插件 id（第三方插件默认不启用）
+    pub enabled_plugins: Vec<String>,
+    /// 会话事件脚本
+    pub script_hooks: Vec<ScriptHook>,
+    /// 全局快捷键（游戏全屏时开始/停止监测、添加标记）
+    pub hotkeys: HotkeySettings,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloudAccount {
+    pub user_name: String,
+    /// 服务端签发的登录令牌
+    pub token: String,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        AppSettings {
+            auto_detect: true,
+            auto_monitor: false,
+            auto_upload: false,
+            server_url: DEFAULT_SERVER_URL.to_string(),
+            offline: false,
+            allow_powershell_fallback: false,
+            units: UnitPrefs::for_locale(&system_locale()),
+            chassis: ChassisProfile::default(),
+            game_name_language: GameNameLanguage::for_locale(&system_locale()),
+            capture_profile: CaptureProfile::default(),
+            capture_backend: CaptureBackend::default(),
+            dpc_sampling: false,
+            busy_policy: BusyPolicy::default(),
+            fps_smoothing: FpsSmoothing::default(),
+            sleep_behavior: SleepBehavior::default(),
+            privacy: PrivacyOptions::default(),
+            cloud_account: None,
+            lan: LanSettings::default(),
+            update: UpdateSettings::default(),
+            mirrors: MirrorSettings::default(),
+            proxy: ProxySettings::default(),
+            enabled_plugins: Vec::new(),
+            script_hooks: Vec::new(),
+            hotkeys: HotkeySettings::default(),
+        }
+    }
+}
+
+/// 系统语言区域 (e.g., "zh-CN")
+#[cfg(target_os = "windows")]
+fn system_locale() -> String {
+    use winreg::enums::HKEY_CURRENT_USER;
+    use winreg::RegKey;
+
+    RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey("Control Panel\\International")
+        .and_then(|key| key.get_value::<String, _>("LocaleName"))
+        .unwrap_or_else(|_| "zh-CN".to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn system_locale() -> String {
+    std::env::var("LANG")
+        .ok()
+        .and_then(|lang| lang.split('.').next().map(str::to_string))
+        .unwrap_or_else(|| "zh-CN".to_string())
+}
+
+// ==================== 读写 ====================
+
+fn current() -> &'static Mutex<AppSettings> {
+    static SETTINGS: OnceLock<Mutex<AppSettings>> = OnceLock::new();
+    SETTINGS.get_or_init(|| Mutex::new(AppSettings::default()))
+}
+
+/// 设置按用户 profile 分别保存
+fn settings_file(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(crate::user_profile::data_dir(app)?.join(SETTINGS_FILE))
+}
+
+/// 启动或切换 profile 时从数据目录加载设置
+pub fn load(app: &AppHandle) {
+    let settings: AppSettings = settings_file(app)
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default();
+    *current().lock().unwrap() = settings;
+    crate::hotkeys::reload();
+}
+
+/// 当前生效的设置
+pub fn get() -> AppSettings {
+    current().lock().unwrap().clone()
+}
+
+pub(crate) fn save(app: &AppHandle, settings: AppSettings) -> Result<(), String> {
+    let path = settings_file(app)?;
+    let text = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
+    std::fs::write(&path, text).map_err(|e| format!("保存设置失败: {}", e))?;
+    *current().lock().unwrap() = settings;
+    crate::hotkeys::reload();
+    Ok(())
+}
+
+/// 合并管理端下发的部分设置（只覆盖下发的字段）
+pub fn apply_managed(app: &AppHandle, patch: &serde_json::Value) -> Result<(), String> {
+    let serde_json::Value::Object(fields) = patch else {
+        return Err("下发的设置格式错误".to_string());
+    };
+    let mut merged = serde_json::to_value(get()).map_err(|e| e.to_string())?;
+    if let serde_json::Value::Object(map) = &mut merged {
+        for (key, value) in fields {
+            map.insert(key.clone(), value.clone());
+        }
+    }
+    let settings: AppSettings =
+        serde_json::from_value(merged).map_err(|e| format!("下发的设置无效: {}", e))?;
+    save(app, settings)
+}
+
+// ==================== Tauri 命令 ====================
+
+#[tauri::command]
+pub fn get_settings() -> Result<AppSettings, String> {
+    Ok(get())
+}
+
+#[tauri::command]
+pub fn update_settings(app: AppHandle, settings: AppSettings) -> Result<(), String> {
+    crate::kiosk::ensure_unlocked()?;
+    crate::hotkeys::validate(&settings.hotkeys)?;
+    save(&app, settings)
+}
@@ -0,0 +1,300 @@
+use crate::capture_rules::SwapchainSelect;
+use crate::fps_monitor::{CaptureBackend, CaptureProfile, FpsSession};
+use gamebench_core::ed25519;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::sync::OnceLock;
+use tauri::{AppHandle, Manager};
+
+/// 本机签名私钥（32 字节 Ed25519 种子），首次启动时随机生成，不随备份迁移
+const KEY_FILE: &str = "signing_key.bin";
+const ALGORITHM: &str = "Ed25519";
+/// 签名报告格式版本
+const REPORT_VERSION: u32 = 2;
+
+// ==================== 数据结构 ====================
+
+/// 采集流程描述，随会话一起签名：同一份数据换了采集后端、
+/// 裁剪设置或硬件，签名都会失效
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapturePipeline {
+    /// GameBench 版本
+    pub app_version: String,
+    /// 采集后端 (e.g., "PresentMon")
+    pub backend: String,
+    /// 采集后端版本（无法读取时为 None）
+    pub backend_version: Option<String>,
+    pub capture_profile: CaptureProfile,
+    /// 使用的采集规则 id
+    pub capture_rule: Option<String>,
+    /// 开头裁掉的预热时长 (秒)
+    pub warmup_secs: f64,
+    /// 多交换链时的选择方式
+    pub swapchain: SwapchainSelect,
+    /// 硬件指纹：CPU、GPU 型号与内存容量的 SHA-256（不含序列号）
+    pub hardware_fingerprint: String,
+}
+
+/// 带签名的导出报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedReport {
+    pub format_version: u32,
+    pub algorithm: String,
+    /// 签名时间 (RFC3339)
+    pub signed_at: String,
+    /// 按原始 JSON 保存，校验时不会因为本版本不认识的字段而丢掉内容
+    pub session: Value,
+    /// 签名安装的公钥（十六进制），排行榜按已登记的公钥判断报告来源
+    #[serde(default)]
+    pub public_key: String,
+    /// 十六进制签名
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureCheck {
+    /// 签名与报告附带的公钥匹配；该公钥不与任何用户信任的公钥比对
+    pub valid: bool,
+    /// 不通过时的原因
+    pub reason: Option<String>,
+    /// 签名公钥指纹（SHA-256 前 8 字节），需自行核对是否属于提交者
+    pub key_fingerprint: Option<String>,
+    /// 由本机签名密钥签出
+    pub local_key: bool,
+}
+
+// ==================== 采集流程 ====================
+
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// 硬件在运行期间不会变化，只计算一次
+fn hardware_fingerprint() -> String {
+    static FINGERPRINT: OnceLock<String> = OnceLock::new();
+    FINGERPRINT
+        .get_or_init(|| {
+            let cpu = crate::hardware::get_cpu_info()
+                .map(|c| c.name)
+                .unwrap_or_default();
+            let mut gpus: Vec<String> = crate::hardware::get_gpu_info()
+                .map(|gpus| gpus.into_iter().map(|g| g.name).collect())
+                .unwrap_or_default();
+            gpus.sort();
+            let ram_gb = crate::hardware::detect_ram_info().total_gb.round() as u64;
+            let descriptor = format!("{}|{}|{}", cpu.trim(), gpus.join(","), ram_gb);
+            hex(&Sha256::digest(descriptor.as_bytes()))
+        })
+        .clone()
+}
+
+/// 监测结束时记录本次采集流程
+pub fn capture_pipeline(
+    app: &AppHandle,
+    backend: CaptureBackend,
+    profile: CaptureProfile,
+    capture_rule: Option<String>,
+    warmup_secs: f64,
+    swapchain: SwapchainSelect,
+) -> CapturePipeline {
+    CapturePipeline {
+        app_version: app.package_info().version.to_string(),
+        backend: match backend {
+            CaptureBackend::PresentMon => "PresentMon".to_string(),
+            CaptureBackend::Native => "ETW".to_string(),
+        },
+        backend_version: match backend {
+            CaptureBackend::PresentMon => crate::fps_monitor::presentmon_version(app),
+            CaptureBackend::Native => None,
+        },
+        capture_profile: profile,
+        capture_rule,
+        warmup_secs,
+        swapchain,
+        hardware_fingerprint: hardware_fingerprint(),
+    }
+}
+
+// ==================== 密钥 ====================
+
+fn signing_key() -> &'static OnceLock<[u8; 32]> {
+    static KEY: OnceLock<[u8; 32]> = OnceLock::new();
+    &KEY
+}
+
+fn read_or_create_key(app: &AppHandle) -> Result<[u8; 32], String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("无法获取数据目录: {}", e))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建数据目录: {}", e))?;
+    let path = dir.join(KEY_FILE);
+    if let Ok(bytes) = std::fs::read(&path) {
+        return bytes
+            .try_into()
+            .map_err(|_| format!("签名密钥文件已损坏: {}", path.display()));
+    }
+    let mut seed = [0u8; 32];
+    getrandom::getrandom(&mut seed).map_err(|e| format!("无法生成签名密钥: {}", e))?;
+    std::fs::write(&path, seed).map_err(|e| format!("无法保存签名密钥: {}", e))?;
+    log::info!("已生成本机报告签名密钥");
+    Ok(seed)
+}
+
+/// 启动时读取本机签名密钥，没有时生成；失败时导出的报告不签名
+pub fn load(app: &AppHandle) {
+    match read_or_create_key(app) {
+        Ok(seed) => {
+            let _ = signing_key().set(seed);
+        }
+        Err(e) => log::warn!("{}", e),
+    }
+}
+
+fn unhex(text: &str) -> Option<Vec<u8>> {
+    (0..text.len())
+        .step_by(2)
+        .map(|i| {
+            text.get(i..i + 2)
+                .and_then(|b| u8::from_str_radix(b, 16).ok())
+        })
+        .collect()
+}
+
+// ==================== 签名 ====================
+
+/// 键名递归排序的紧凑 JSON，与字段顺序和 serde_json 的 Map 实现无关
+fn canonical_json(value: &Value) -> Vec<u8> {
+    fn write(value: &Value, out: &mut Vec<u8>) {
+        match value {
+            Value::Object(map) => {
+                let mut entries: Vec<_> = map.iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+                out.push(b'{');
+                for (i, (key, value)) in entries.into_iter().enumerate() {
+                    if i > 0 {
+                        out.push(b',');
+                    }
+                    write(&Value::String(key.clone()), out);
+                    out.push(b':');
+                    write(value, out);
+                }
+                out.push(b'}');
+            }
+            Value::Array(items) => {
+                out.push(b'[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(b',');
+                    }
+                    write(item, out);
+                }
+                out.push(b']');
+            }
+            // 标量序列化到 Vec 不会失败
+            scalar => out.extend(serde_json::to_vec(scalar).unwrap_or_default()),
+        }
+    }
+    let mut out = Vec::new();
+    write(value, &mut out);
+    out
+}
+
+/// 签名内容包含采集流程，换了采集后端、裁剪设置或硬件后签名失效
+fn signed_message(session: &Value) -> Result<Vec<u8>, String> {
+    if session.get("pipeline").is_none_or(Value::is_null) {
+        return Err("该会话没有采集流程记录（旧版本录制），无法签名".to_string());
+    }
+    Ok(canonical_json(session))
+}
+
+/// 公钥指纹，按 4 位一组显示
+fn fingerprint(public_key: &[u8; 32]) -> String {
+    let digest = hex(&Sha256::digest(public_key)[..8]);
+    digest
+        .as_bytes()
+        .chunks(4)
+        .map(|c| String::from_utf8_lossy(c).to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 用本机私钥对（已脱敏的）会话签名
+pub fn sign(session: &FpsSession) -> Result<SignedReport, String> {
+    let seed = signing_key()
+        .get()
+        .ok_or_else(|| "本机签名密钥不可用，报告未签名".to_string())?;
+    let session = serde_json::to_value(session).map_err(|e| e.to_string())?;
+    let signature = ed25519::sign(seed, &signed_message(&session)?);
+    Ok(SignedReport {
+        format_version: REPORT_VERSION,
+        algorithm: ALGORITHM.to_string(),
+        signed_at: chrono::Local::now().to_rfc3339(),
+        public_key: hex(&ed25519::public_key(seed)),
+        signature: hex(&signature),
+        session,
+    })
+}
+
+/// 用报告附带的公钥校验。只证明报告由该公钥对应的安装签出且未被修改；
+/// 公钥本身没有与任何用户信任的公钥比对，任何人都能用自己的密钥签出“有效”报告，
+/// 来源需按返回的指纹自行核对
+pub fn verify(report: &SignedReport) -> SignatureCheck {
+    let failed = |reason: String, key_fingerprint: Option<String>| SignatureCheck {
+        valid: false,
+        reason: Some(reason),
+        key_fingerprint,
+        local_key: false,
+    };
+    if report.algorithm != ALGORITHM {
+        return failed(format!("不支持的签名算法: {}", report.algorithm), None);
+    }
+    let Some(public_key) = unhex(&report.public_key).and_then(|k| <[u8; 32]>::try_from(k).ok())
+    else {
+        return failed("报告缺少有效的签名公钥".to_string(), None);
+    };
+    let key_fingerprint = Some(fingerprint(&public_key));
+    let Some(signature) = unhex(&report.signature).and_then(|s| <[u8; 64]>::try_from(s).ok())
+    else {
+        return failed("签名格式错误".to_string(), key_fingerprint);
+    };
+    match signed_message(&report.session) {
+        Ok(message) if ed25519::verify(&public_key, &message, &signature) => SignatureCheck {
+            valid: true,
+            reason: None,
+            key_fingerprint,
+            local_key: signing_key()
+                .get()
+                .is_some_and(|seed| ed25519::public_key(seed) == public_key),
+        },
+        Ok(_) => failed(
+            "签名不匹配，报告内容或采集流程信息已被修改".to_string(),
+            key_fingerprint,
+        ),
+        Err(e) => failed(e, key_fingerprint),
+    }
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 校验签名报告（导入排行榜/社区提交前）
+#[tauri::command]
+pub fn verify_signed_report(report: SignedReport) -> Result<SignatureCheck, String> {
+    Ok(verify(&report))
+}
+
+/// 读取导出的报告文件并校验签名
+#[tauri::command]
+pub fn verify_report_file(path: String) -> Result<SignatureCheck, String> {
+    let text = std::fs::read_to_string(&path).map_err(|e| format!("读取报告失败: {}", e))?;
+    let report: SignedReport =
+        serde_json::from_str(&text).map_err(|e| format!("不是有效的签名报告: {}", e))?;
+    let check = verify(&report);
+    log::info!(
+        "校验报告签名: {} → {}",
+        path,
+        if check.valid { "通过" } else { "不通过" }
+    );
+    Ok(check)
+}
@@ -0,0 +1,554 @@
+use serde::{Deserialize, Serialize};
+use sysinfo::System;
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupSource {
+    /// 注册表 Run 键
+    Registry,
+    /// 启动文件夹
+    StartupFolder,
+    /// 任务计划程序
+    ScheduledTask,
+    /// 自动启动的第三方服务
+    Service,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GamingImpact {
+    None,
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartupEntry {
+    /// 唯一标识，用于 disable_startup_items
+    pub id: String,
+    /// 显示名称
+    pub name: String,
+    /// 启动命令 / 文件路径
+    pub command: String,
+    pub source: StartupSource,
+    /// 位置描述 (e.g., "HKCU\\...\\Run")
+    pub location: String,
+    /// 是否启用
+    pub enabled: bool,
+    /// 当前是否在运行
+    pub running: bool,
+    /// 当前 CPU 占用 (%)
+    pub cpu_percent: f64,
+    /// 当前内存占用 (MB)
+    pub memory_mb: f64,
+    /// 对游戏的影响评级
+    pub impact: GamingImpact,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisableResult {
+    pub id: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// 原始启动项（尚未测量占用）
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+struct RawEntry {
+    id: String,
+    name: String,
+    command: String,
+    source: StartupSource,
+    location: String,
+    enabled: bool,
+    pid: Option<u32>,
+}
+
+// ==================== 资源占用 ====================
+
+/// 启动项对应的运行中进程占用
+struct Footprint {
+    running: bool,
+    cpu_percent: f64,
+    memory_mb: f64,
+}
+
+/// 采样两次以获得有效的 CPU 占用率
+fn sample_processes() -> System {
+    let mut sys = System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    std::thread::sleep(
+        sysinfo::MINIMUM_CPU_UPDATE_INTERVAL.max(std::time::Duration::from_millis(500)),
+    );
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    sys
+}
+
+/// 从启动命令中提取可执行文件名（小写）
+/// e.g. "\"C:\\Program Files\\Steam\\steam.exe\" -silent" → "steam.exe"
+fn exe_name_from_command(command: &str) -> Option<String> {
+    let command = command.trim();
+    let path = if let Some(rest) = command.strip_prefix('"') {
+        rest.split('"').next()?
+    } else {
+        let lower = command.to_lowercase();
+        match lower.find(".exe") {
+            Some(idx) => &command[..idx + 4],
+            None => command.split_whitespace().next()?,
+        }
+    };
+
+    let file = path.rsplit(['\\', '/']).next()?.to_lowercase();
+    if file.is_empty() {
+        None
+    } else {
+        Some(file)
+    }
+}
+
+/// 按进程名或 PID 汇总占用
+fn measure(sys: &System, exe_name: Option<&str>, pid: Option<u32>) -> Footprint {
+    let mut footprint = Footprint {
+        running: false,
+        cpu_percent: 0.0,
+        memory_mb: 0.0,
+    };
+
+    for (process_pid, process) in sys.processes() {
+        let matched = match (pid, exe_name) {
+            (Some(p), _) if p > 0 => process_pid.as_u32() == p,
+            (_, Some(name)) => process.name().to_string_lossy().to_lowercase() == name,
+            _ => false,
+        };
+        if matched {
+            footprint.running = true;
+            footprint.cpu_percent += process.cpu_usage() as f64;
+            footprint.memory_mb += process.memory() as f64 / (1024.0 * 1024.0);
+        }
+    }
+
+    footprint.cpu_percent = (footprint.cpu_percent * 10.0).round() / 10.0;
+    footprint.memory_mb = (footprint.memory_mb * 10.0).round() / 10.0;
+    footprint
+}
+
+/// 根据常驻占用评估对游戏的影响
+fn rate_impact(footprint: &Footprint) -> GamingImpact {
+    if !footprint.running {
+        GamingImpact::None
+    } else if footprint.cpu_percent >= 5.0 || footprint.memory_mb >= 300.0 {
+        GamingImpact::High
+    } else if footprint.cpu_percent >= 1.0 || footprint.memory_mb >= 100.0 {
+        GamingImpact::Medium
+    } else {
+        GamingImpact::Low
+    }
+}
+
+// ==================== 启动项枚举 (Windows) ====================
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::*;
+    use std::collections::HashMap;
+    use winreg::enums::{
+        HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE, KEY_READ, KEY_SET_VALUE, REG_BINARY,
+    };
+    use winreg::{RegKey, RegValue, HKEY};
+
+    const RUN_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Run";
+    const RUN32_KEY: &str = r"Software\WOW6432Node\Microsoft\Windows\CurrentVersion\Run";
+    const APPROVED_KEY: &str =
+        r"Software\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved";
+
+    /// 注册表位置: (id 前缀, 根键, Run 路径, StartupApproved 子键)
+    fn registry_locations() -> Vec<(&'static str, HKEY, &'static str, &'static str)> {
+        vec![
+            ("hkcu", HKEY_CURRENT_USER, RUN_KEY, "Run"),
+            ("hklm", HKEY_LOCAL_MACHINE, RUN_KEY, "Run"),
+            ("hklm32", HKEY_LOCAL_MACHINE, RUN32_KEY, "Run32"),
+        ]
+    }
+
+    fn root_key(hkey: HKEY) -> RegKey {
+        RegKey::predef(hkey)
+    }
+
+    /// StartupApproved 中首字节为奇数（0x03 等）表示已被禁用
+    fn is_approved(root: &RegKey, approved_sub: &str, value_name: &str) -> bool {
+        root.open_subkey(format!(r"{}\{}", APPROVED_KEY, approved_sub))
+            .and_then(|key| key.get_raw_value(value_name))
+            .map(|v| v.bytes.first().map(|b| b & 1 == 0).unwrap_or(true))
+            .unwrap_or(true)
+    }
+
+    fn registry_entries() -> Vec<RawEntry> {
+        let mut entries = Vec::new();
+        for (prefix, hkey, run_path, approved_sub) in registry_locations() {
+            let root = root_key(hkey);
+            let Ok(run) = root.open_subkey_with_flags(run_path, KEY_READ) else {
+                continue;
+            };
+            for (name, _) in run.enum_values().flatten() {
+                let command: String = run.get_value(&name).unwrap_or_default();
+                entries.push(RawEntry {
+                    id: format!("registry:{}:{}", prefix, name),
+                    enabled: is_approved(&root, approved_sub, &name),
+                    name,
+                    command,
+                    source: StartupSource::Registry,
+                    location: format!(r"{}\{}", prefix.to_uppercase(), run_path),
+                    pid: None,
+                });
+            }
+        }
+        entries
+    }
+
+    fn startup_folders() -> Vec<(&'static str, std::path::PathBuf, HKEY)> {
+        let mut folders = Vec::new();
+        if let Ok(appdata) = std::env::var("APPDATA") {
+            folders.push((
+                "user",
+                std::path::PathBuf::from(appdata)
+                    .join(r"Microsoft\Windows\Start Menu\Programs\Startup"),
+                HKEY_CURRENT_USER,
+            ));
+        }
+        if let Ok(programdata) = std::env::var("ProgramData") {
+            folders.push((
+                "common",
+                std::path::PathBuf::from(programdata)
+                    .join(r"Microsoft\Windows\Start Menu\Programs\StartUp"),
+                HKEY_LOCAL_MACHINE,
+            ));
+        }
+        folders
+    }
+
+    /// 通过 IShellLinkW 解析快捷方式的目标与参数，返回 "\"目标\" 参数" 形式的命令
+    fn shortcut_command(path: &std::path::Path) -> Option<String> {
+        use windows::core::{Interface, HSTRING};
+        use windows::Win32::System::Com::{
+            CoCreateInstance, CoInitializeEx, CoUninitialize, IPersistFile, CLSCTX_INPROC_SERVER,
+            COINIT_MULTITHREADED, STGM_READ,
+        };
+        use windows::Win32::UI::Shell::{IShellLinkW, ShellLink};
+
+        unsafe {
+            // 调用线程已按其他模式初始化 COM 时仍可直接使用，只是不需要配对释放
+            let initialized = CoInitializeEx(None, COINIT_MULTITHREADED).is_ok();
+            let resolve = || -> windows::core::Result<(String, String)> {
+                let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+                link.cast::<IPersistFile>()?
+                    .Load(&HSTRING::from(path.as_os_str()), STGM_READ)?;
+                let mut target = [0u16; 1024];
+                link.GetPath(&mut target, std::ptr::null_mut(), 0)?;
+                let mut args = [0u16; 1024];
+                link.GetArguments(&mut args)?;
+                let text = |buf: &[u16]| {
+                    let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+                    String::from_utf16_lossy(&buf[..len])
+                };
+                Ok((text(&target), text(&args)))
+            };
+            let resolved = resolve();
+            if initialized {
+                CoUninitialize();
+            }
+            let (target, args) = resolved.ok()?;
+            if target.is_empty() {
+                return None;
+            }
+            Some(if args.trim().is_empty() {
+                format!("\"{}\"", target)
+            } else {
+                format!("\"{}\" {}", target, args.trim())
+            })
+        }
+    }
+
+    fn folder_entries() -> Vec<RawEntry> {
+        let mut entries = Vec::new();
+        for (prefix, folder, hkey) in startup_folders() {
+            let Ok(dir) = std::fs::read_dir(&folder) else {
+                continue;
+            };
+            let root = root_key(hkey);
+            for file in dir.flatten() {
+                let file_name = file.file_name().to_string_lossy().to_string();
+                if file_name.eq_ignore_ascii_case("desktop.ini") {
+                    continue;
+                }
+                // 快捷方式按目标程序匹配进程，解析失败时退回文件本身
+                let path = file.path();
+                let is_shortcut = path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("lnk"));
+                let command = is_shortcut
+                    .then(|| shortcut_command(&path))
+                    .flatten()
+                    .unwrap_or_else(|| path.to_string_lossy().to_string());
+                entries.push(RawEntry {
+                    id: format!("folder:{}:{}", prefix, file_name),
+                    enabled: is_approved(&root, "StartupFolder", &file_name),
+                    name: file_name.clone(),
+                    command,
+                    source: StartupSource::StartupFolder,
+                    location: folder.to_string_lossy().to_string(),
+                    pid: None,
+                });
+            }
+        }
+        entries
+    }
+
+    /// 根目录下的第三方计划任务（更新器、助手等通常注册在这里）
+    fn task_entries() -> Vec<RawEntry> {
+        use wmi::{COMLibrary, WMIConnection};
+
+        let Ok(com) = COMLibrary::new() else {
+            return vec![];
+        };
+        let Ok(wmi) =
+            WMIConnection::with_namespace_path(r"ROOT\Microsoft\Windows\TaskScheduler", com)
+        else {
+            return vec![];
+        };
+        let results: Vec<HashMap<String, wmi::Variant>> = wmi
+            .raw_query(
+                r"SELECT TaskName, TaskPath, State FROM MSFT_ScheduledTask WHERE TaskPath = '\\'",
+            )
+            .unwrap_or_default();
+
+        results
+            .iter()
+            .filter_map(|item| {
+                let name = match item.get("TaskName") {
+                    Some(wmi::Variant::String(s)) => s.clone(),
+                    _ => return None,
+                };
+                // State: 1 = Disabled
+                let enabled = !matches!(item.get("State"), Some(wmi::Variant::UI4(1)));
+                Some(RawEntry {
+                    id: format!("task:{}", name),
+                    command: format!(r"\{}", name),
+                    name,
+                    source: StartupSource::ScheduledTask,
+                    location: "Task Scheduler".to_string(),
+                    enabled,
+                    pid: None,
+                })
+            })
+            .collect()
+    }
+
+    /// 自动启动的第三方服务（排除 Windows 目录下的系统服务）
+    fn service_entries() -> Vec<RawEntry> {
+        use wmi::{COMLibrary, WMIConnection};
+
+        let Ok(com) = COMLibrary::new() else {
+            return vec![];
+        };
+        let Ok(wmi) = WMIConnection::new(com) else {
+            return vec![];
+        };
+        let results: Vec<HashMap<String, wmi::Variant>> = wmi
+            .raw_query(
+                "SELECT Name, DisplayName, PathName, ProcessId FROM Win32_Service \
+                 WHERE StartMode = 'Auto'",
+            )
+            .unwrap_or_default();
+
+        results
+            .iter()
+            .filter_map(|item| {
+                let name = match item.get("Name") {
+                    Some(wmi::Variant::String(s)) => s.clone(),
+                    _ => return None,
+                };
+                let path = match item.get("PathName") {
+                    Some(wmi::Variant::String(s)) => s.clone(),
+                    _ => String::new(),
+                };
+                if path.to_lowercase().contains(r"\windows\") {
+                    return None;
+                }
+                let display = match item.get("DisplayName") {
+                    Some(wmi::Variant::String(s)) => s.clone(),
+                    _ => name.clone(),
+                };
+                let pid = match item.get("ProcessId") {
+                    Some(wmi::Variant::UI4(n)) => Some(*n),
+                    _ => None,
+                };
+                Some(RawEntry {
+                    id: format!("service:{}", name),
+                    name: display,
+                    command: path,
+                    source: StartupSource::Service,
+                    location: "Services".to_string(),
+                    enabled: true,
+                    pid,
+                })
+            })
+            .collect()
+    }
+
+    pub(super) fn list_entries() -> Vec<RawEntry> {
+        let mut entries = registry_entries();
+        entries.extend(folder_entries());
+        entries.extend(task_entries());
+        entries.extend(service_entries());
+        entries
+    }
+
+    /// 与任务管理器一致：在 StartupApproved 中写入 0x03 标记禁用
+    fn write_disabled_flag(hkey: HKEY, approved_sub: &str, value_name: &str) -> Result<(), String> {
+        let (key, _) = root_key(hkey)
+            .create_subkey(format!(r"{}\{}", APPROVED_KEY, approved_sub))
+            .map_err(|e| format!("无法打开 StartupApproved: {}", e))?;
+        let mut bytes = vec![0u8; 12];
+        bytes[0] = 0x03;
+        key.set_raw_value(
+            value_name,
+            &RegValue {
+                bytes,
+                vtype: REG_BINARY,
+            },
+        )
+        .map_err(|e| format!("写入注册表失败（可能需要管理员权限）: {}", e))
+    }
+
+    pub(super) fn disable(id: &str) -> Result<(), String> {
+        let (kind, rest) = id.split_once(':').ok_or("无效的启动项 ID")?;
+        match kind {
+            "registry" => {
+                let (prefix, name) = rest.split_once(':').ok_or("无效的启动项 ID")?;
+                let (_, hkey, _, approved_sub) = registry_locations()
+                    .into_iter()
+                    .find(|(p, ..)| *p == prefix)
+                    .ok_or("未知的注册表位置")?;
+                write_disabled_flag(hkey, approved_sub, name)
+            }
+            "folder" => {
+                let (prefix, name) = rest.split_once(':').ok_or("无效的启动项 ID")?;
+                let hkey = if prefix == "user" {
+                    HKEY_CURRENT_USER
+                } else {
+                    HKEY_LOCAL_MACHINE
+                };
+                write_disabled_flag(hkey, "StartupFolder", name)
+            }
+            "task" => {
+                use std::os::windows::process::CommandExt;
+                let status = std::process::Command::new("schtasks")
+                    .args(["/Change", "/TN", &format!(r"\{}", rest), "/Disable"])
+                    .creation_flags(0x08000000) // CREATE_NO_WINDOW
+                    .status()
+                    .map_err(|e| format!("无法调用 schtasks: {}", e))?;
+                if status.success() {
+                    Ok(())
+                } else {
+                    Err("禁用计划任务失败（可能需要管理员权限）".to_string())
+                }
+            }
+            "service" => {
+                // 改为手动启动（3），比直接禁用更安全，依赖的程序仍可按需拉起
+                let key = RegKey::predef(HKEY_LOCAL_MACHINE)
+                    .open_subkey_with_flags(
+                        format!(r"SYSTEM\CurrentControlSet\Services\{}", rest),
+                        KEY_SET_VALUE,
+                    )
+                    .map_err(|e| format!("无法打开服务配置（需要管理员权限）: {}", e))?;
+                key.set_value("Start", &3u32)
+                    .map_err(|e| format!("修改服务启动类型失败: {}", e))
+            }
+            _ => Err(format!("未知的启动项类型: {}", kind)),
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    use super::*;
+
+    pub(super) fn list_entries() -> Vec<RawEntry> {
+        // 非 Windows 平台的 stub
+        vec![]
+    }
+
+    pub(super) fn disable(_id: &str) -> Result<(), String> {
+        Err("仅支持 Windows".to_string())
+    }
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 列出启动项及其资源占用，按游戏影响从高到低排序
+#[tauri::command]
+pub fn list_startup_items() -> Result<Vec<StartupEntry>, String> {
+    let raw = platform::list_entries();
+    let sys = sample_processes();
+
+    let mut entries: Vec<StartupEntry> = raw
+        .into_iter()
+        .map(|entry| {
+            let exe = exe_name_from_command(&entry.command);
+            let footprint = measure(&sys, exe.as_deref(), entry.pid);
+            StartupEntry {
+                impact: rate_impact(&footprint),
+                id: entry.id,
+                name: entry.name,
+                command: entry.command,
+                source: entry.source,
+                location: entry.location,
+                enabled: entry.enabled,
+                running: footprint.running,
+                cpu_percent: footprint.cpu_percent,
+                memory_mb: footprint.memory_mb,
+            }
+        })
+        .collect();
+
+    entries.sort_by(|a, b| {
+        b.impact.cmp(&a.impact).then(
+            b.memory_mb
+                .partial_cmp(&a.memory_mb)
+                .unwrap_or(std::cmp::Ordering::Equal),
+        )
+    });
+
+    log::info!("枚举到 {} 个启动项", entries.len());
+    Ok(entries)
+}
+
+/// 禁用选中的启动项，逐项返回结果
+#[tauri::command]
+pub fn disable_startup_items(ids: Vec<String>) -> Result<Vec<DisableResult>, String> {
+    crate::kiosk::ensure_unlocked()?;
+    Ok(ids
+        .into_iter()
+        .map(|id| match platform::disable(&id) {
+            Ok(()) => {
+                log::info!("已禁用启动项: {}", id);
+                DisableResult {
+                    id,
+                    success: true,
+                    error: None,
+                }
+            }
+            Err(e) => {
+                log::warn!("禁用启动项 {} 失败: {}", id, e);
+                DisableResult {
+                    id,
+                    success: false,
+                    error: Some(e),
+                }
+            }
+        })
+        .collect())
+}
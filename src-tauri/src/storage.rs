@@ -0,0 +1,1131 @@
+use crate::fps_monitor::{CaptureProfile, FpsSession, SessionStatus};
+use crate::frame_log::{self, FrameLog};
+use crate::tasks::{self, TaskHandle, TaskKind};
+use crate::timeline::SessionTimeline;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::AppHandle;
+
+/// 会话存储目录（会话数据库与附属数据）
+const SESSIONS_DIR: &str = "sessions";
+/// 会话数据库（会话目录下），每个会话一行；每秒聚合、帧时间等大块数据仍按文件保存
+const DB_FILE: &str = "sessions.db";
+/// 其他连接正在写入时的等待时间
+const DB_BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+/// 每秒聚合数据目录（会话目录下，与会话摘要分开保存，列出会话时不读取）
+const TIMELINES_DIR: &str = "timelines";
+/// 原始帧时间目录（会话目录下，分块压缩，格式见 frame_log）
+const FRAMES_DIR: &str = "frames";
+/// 未加密的帧时间文件扩展名（加密后同会话为 .bin）
+const FRAMES_EXT: &str = "gbf";
+/// 导入时逐帧写入、尚未保存的帧时间文件扩展名
+const STAGED_FRAMES_EXT: &str = "staged";
+/// 原始采集日志目录（会话目录下，逐帧 gzip CSV，格式见 capture_log）
+const CAPTURE_LOGS_DIR: &str = "capture_logs";
+/// 未加密的原始采集日志扩展名（加密后同会话为 .bin）
+const CAPTURE_LOG_EXT: &str = "csv.gz";
+/// 存在此文件即表示会话库已加密，内容用于校验密码
+const KEY_CHECK_FILE: &str = "key_check.bin";
+const KEY_CHECK_TEXT: &[u8] = b"gamebench-session-store";
+/// 旧版逐文件存储的摘要索引 (index.json / index.bin)，迁移到数据库后删除
+const LEGACY_INDEX_NAME: &str = "index";
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoreStatus {
+    /// 会话库是否已加密
+    pub encrypted: bool,
+    /// 已加密时是否已输入密码解锁
+    pub unlocked: bool,
+    /// 已保存的会话数
+    pub session_count: usize,
+    /// 锁定期间完成、等待解锁后写入的会话数
+    pub pending: usize,
+}
+
+/// 会话摘要：历史列表筛选、排序与展示所需的字段，与完整记录分列保存，列表查询不必读取完整会话
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionIndexEntry {
+    pub session_id: String,
+    pub process_name: String,
+    pub avg_fps: f64,
+    pub fps_1_low: f64,
+    pub fps_01_low: f64,
+    pub duration_secs: f64,
+    pub total_frames: u64,
+    pub status: SessionStatus,
+    pub capture_profile: CaptureProfile,
+    /// 是否从第三方 CSV 导入
+    pub imported: bool,
+    pub tags: Vec<String>,
+}
+
+impl SessionIndexEntry {
+    fn new(session: &FpsSession) -> Self {
+        SessionIndexEntry {
+            session_id: session.session_id.clone(),
+            process_name: session.process_name.clone(),
+            avg_fps: session.avg_fps,
+            fps_1_low: session.fps_1_low,
+            fps_01_low: session.fps_01_low,
+            duration_secs: session.duration_secs,
+            total_frames: session.total_frames,
+            status: session.status,
+            capture_profile: session.capture_profile,
+            imported: session.import.is_some(),
+            tags: session.tags.clone(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct StoreState {
+    /// 解锁后的密码（仅保存在内存中）
+    passphrase: Option<String>,
+    /// 会话库锁定时完成的会话
+    pending: Vec<FpsSession>,
+    /// 会话库锁定时完成的每秒聚合数据
+    pending_timelines: Vec<SessionTimeline>,
+    /// 会话库锁定时完成的原始帧时间（会话 ID, 已压缩数据）
+    pending_frames: Vec<(String, Vec<u8>)>,
+}
+
+fn state() -> &'static Mutex<StoreState> {
+    static STATE: OnceLock<Mutex<StoreState>> = OnceLock::new();
+    STATE.get_or_init(|| Mutex::new(StoreState::default()))
+}
+
+// ==================== 加密 (Windows DPAPI) ====================
+
+/// DPAPI 加密，密码作为附加熵：同一 Windows 账户下没有密码也无法解密
+#[cfg(target_os = "windows")]
+fn protect(data: &[u8], passphrase: &str) -> Result<Vec<u8>, String> {
+    use windows::core::PCWSTR;
+    use windows::Win32::Foundation::{LocalFree, HLOCAL};
+    use windows::Win32::Security::Cryptography::{
+        CryptProtectData, CRYPTPROTECT_UI_FORBIDDEN, CRYPT_INTEGER_BLOB,
+    };
+
+    let input = CRYPT_INTEGER_BLOB {
+        cbData: data.len() as u32,
+        pbData: data.as_ptr() as *mut u8,
+    };
+    let entropy = CRYPT_INTEGER_BLOB {
+        cbData: passphrase.len() as u32,
+        pbData: passphrase.as_ptr() as *mut u8,
+    };
+    let mut output = CRYPT_INTEGER_BLOB::default();
+    unsafe {
+        CryptProtectData(
+            &input,
+            PCWSTR::null(),
+            Some(&entropy),
+            None,
+            None,
+            CRYPTPROTECT_UI_FORBIDDEN,
+            &mut output,
+        )
+        .map_err(|e| format!("加密失败: {}", e))?;
+        let bytes = std::slice::from_raw_parts(output.pbData, output.cbData as usize).to_vec();
+        let _ = LocalFree(HLOCAL(output.pbData as *mut core::ffi::c_void));
+        Ok(bytes)
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn unprotect(data: &[u8], passphrase: &str) -> Result<Vec<u8>, String> {
+    use windows::Win32::Foundation::{LocalFree, HLOCAL};
+    use windows::Win32::Security::Cryptography::{
+        CryptUnprotectData, CRYPTPROTECT_UI_FORBIDDEN, CRYPT_INTEGER_BLOB,
+    };
+
+    let input = CRYPT_INTEGER_BLOB {
+        cbData: data.len() as u32,
+        pbData: data.as_ptr() as *mut u8,
+    };
+    let entropy = CRYPT_INTEGER_BLOB {
+        cbData: passphrase.len() as u32,
+        pbData: passphrase.as_ptr() as *mut u8,
+    };
+    let mut output = CRYPT_INTEGER_BLOB::default();
+    unsafe {
+        CryptUnprotectData(
+            &input,
+            None,
+            Some(&entropy),
+            None,
+            None,
+            CRYPTPROTECT_UI_FORBIDDEN,
+            &mut output,
+        )
+        .map_err(|_| "密码错误或数据已损坏".to_string())?;
+        let bytes = std::slice::from_raw_parts(output.pbData, output.cbData as usize).to_vec();
+        let _ = LocalFree(HLOCAL(output.pbData as *mut core::ffi::c_void));
+        Ok(bytes)
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn protect(_data: &[u8], _passphrase: &str) -> Result<Vec<u8>, String> {
+    Err("会话加密仅支持 Windows".to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn unprotect(_data: &[u8], _passphrase: &str) -> Result<Vec<u8>, String> {
+    Err("会话加密仅支持 Windows".to_string())
+}
+
+// ==================== 文件读写 ====================
+
+/// 当前用户 profile 的会话目录
+fn sessions_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    sessions_dir_in(&crate::user_profile::data_dir(app)?)
+}
+
+fn sessions_dir_in(profile_dir: &Path) -> Result<PathBuf, String> {
+    let dir = profile_dir.join(SESSIONS_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建会话目录: {}", e))?;
+    Ok(dir)
+}
+
+fn timelines_dir(dir: &Path) -> Result<PathBuf, String> {
+    let dir = dir.join(TIMELINES_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建聚合数据目录: {}", e))?;
+    Ok(dir)
+}
+
+fn frames_dir(dir: &Path) -> Result<PathBuf, String> {
+    let dir = dir.join(FRAMES_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建帧数据目录: {}", e))?;
+    Ok(dir)
+}
+
+fn capture_logs_dir(dir: &Path) -> Result<PathBuf, String> {
+    let dir = dir.join(CAPTURE_LOGS_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建原始采集日志目录: {}", e))?;
+    Ok(dir)
+}
+
+fn is_encrypted(dir: &Path) -> bool {
+    dir.join(KEY_CHECK_FILE).exists()
+}
+
+/// 会话 ID 只允许字母数字和连字符，防止拼出目录外的路径
+fn check_session_id(session_id: &str) -> Result<(), String> {
+    if session_id.is_empty()
+        || !session_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!("无效的会话 ID: {}", session_id));
+    }
+    Ok(())
+}
+
+fn session_path(dir: &Path, session_id: &str, encrypted: bool) -> Result<PathBuf, String> {
+    check_session_id(session_id)?;
+    let ext = if encrypted { "bin" } else { "json" };
+    Ok(dir.join(format!("{}.{}", session_id, ext)))
+}
+
+/// 按会话 ID 命名的记录文件列表 (路径, 是否加密)：每秒聚合数据，以及旧版逐文件存储的会话
+fn session_files(dir: &Path) -> Vec<(PathBuf, bool)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.file_name().and_then(|n| n.to_str()) != Some(KEY_CHECK_FILE))
+        .filter(|p| p.file_stem().and_then(|n| n.to_str()) != Some(LEGACY_INDEX_NAME))
+        .filter_map(|p| match p.extension().and_then(|e| e.to_str()) {
+            Some("json") => Some((p, false)),
+            Some("bin") => Some((p, true)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn frames_path(dir: &Path, session_id: &str, encrypted: bool) -> Result<PathBuf, String> {
+    let path = session_path(&frames_dir(dir)?, session_id, encrypted)?;
+    Ok(if encrypted {
+        path
+    } else {
+        path.with_extension(FRAMES_EXT)
+    })
+}
+
+/// 帧时间文件列表 (路径, 是否加密)
+fn frame_files(dir: &Path) -> Vec<(PathBuf, bool)> {
+    let Ok(entries) = frames_dir(dir).and_then(|d| std::fs::read_dir(d).map_err(|e| e.to_string()))
+    else {
+        return vec![];
+    };
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .filter_map(|p| match p.extension().and_then(|e| e.to_str()) {
+            Some(FRAMES_EXT) => Some((p, false)),
+            Some("bin") => Some((p, true)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn log_path(dir: &Path, session_id: &str, encrypted: bool) -> Result<PathBuf, String> {
+    let path = session_path(&capture_logs_dir(dir)?, session_id, encrypted)?;
+    Ok(if encrypted {
+        path
+    } else {
+        path.with_extension(CAPTURE_LOG_EXT)
+    })
+}
+
+/// 原始采集日志列表 (会话 ID, 路径, 是否加密)
+fn capture_log_files(dir: &Path) -> Vec<(String, PathBuf, bool)> {
+    let Ok(entries) =
+        capture_logs_dir(dir).and_then(|d| std::fs::read_dir(d).map_err(|e| e.to_string()))
+    else {
+        return vec![];
+    };
+    let plain_suffix = format!(".{}", CAPTURE_LOG_EXT);
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .filter_map(|p| {
+            let name = p.file_name()?.to_str()?.to_string();
+            if let Some(id) = name.strip_suffix(&plain_suffix) {
+                Some((id.to_string(), p, false))
+            } else {
+                name.strip_suffix(".bin").map(|id| (id.to_string(), p, true))
+            }
+        })
+        .collect()
+}
+
+fn read_bytes(path: &Path, encrypted: bool, passphrase: Option<&str>) -> Result<Vec<u8>, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("读取会话失败: {}", e))?;
+    match (encrypted, passphrase) {
+        (false, _) => Ok(bytes),
+        (true, Some(p)) => unprotect(&bytes, p),
+        (true, None) => Err("会话库已加密，请先输入密码解锁".to_string()),
+    }
+}
+
+fn write_record<T: Serialize>(
+    dir: &Path,
+    session_id: &str,
+    value: &T,
+    passphrase: Option<&str>,
+) -> Result<(), String> {
+    let json = serde_json::to_vec_pretty(value).map_err(|e| e.to_string())?;
+    let (path, bytes) = match passphrase {
+        Some(p) => (session_path(dir, session_id, true)?, protect(&json, p)?),
+        None => (session_path(dir, session_id, false)?, json),
+    };
+    std::fs::write(&path, bytes).map_err(|e| format!("保存会话失败: {}", e))
+}
+
+fn read_record<T: DeserializeOwned>(
+    path: &Path,
+    encrypted: bool,
+    passphrase: Option<&str>,
+) -> Result<T, String> {
+    let json = read_bytes(path, encrypted, passphrase)?;
+    serde_json::from_slice(&json).map_err(|e| format!("会话文件格式错误: {}", e))
+}
+
+fn write_timeline(
+    dir: &Path,
+    timeline: &SessionTimeline,
+    passphrase: Option<&str>,
+) -> Result<(), String> {
+    write_record(
+        &timelines_dir(dir)?,
+        &timeline.session_id,
+        timeline,
+        passphrase,
+    )
+}
+
+/// 以新密码（None 为明文）重写全部聚合数据文件
+fn rewrite_timelines(dir: &Path, from: Option<&str>, to: Option<&str>) -> Result<(), String> {
+    let timelines = timelines_dir(dir)?;
+    for (path, encrypted) in session_files(&timelines) {
+        let timeline: SessionTimeline = read_record(&path, encrypted, from)?;
+        write_record(&timelines, &timeline.session_id, &timeline, to)?;
+        std::fs::remove_file(&path).map_err(|e| format!("删除旧聚合数据失败: {}", e))?;
+    }
+    Ok(())
+}
+
+fn write_frames(
+    dir: &Path,
+    session_id: &str,
+    data: &[u8],
+    passphrase: Option<&str>,
+) -> Result<(), String> {
+    let path = frames_path(dir, session_id, passphrase.is_some())?;
+    let bytes = match passphrase {
+        Some(p) => protect(data, p)?,
+        None => data.to_vec(),
+    };
+    std::fs::write(&path, bytes).map_err(|e| format!("保存帧数据失败: {}", e))
+}
+
+/// 以新密码（None 为明文）重写全部帧时间文件
+fn rewrite_frames(dir: &Path, from: Option<&str>, to: Option<&str>) -> Result<(), String> {
+    for (path, encrypted) in frame_files(dir) {
+        let Some(id) = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .map(str::to_string)
+        else {
+            continue;
+        };
+        let data = read_bytes(&path, encrypted, from)?;
+        write_frames(dir, &id, &data, to)?;
+        std::fs::remove_file(&path).map_err(|e| format!("删除旧帧数据失败: {}", e))?;
+    }
+    Ok(())
+}
+
+/// 以新密码（None 为明文）重写全部原始采集日志
+fn rewrite_capture_logs(dir: &Path, from: Option<&str>, to: Option<&str>) -> Result<(), String> {
+    for (id, path, encrypted) in capture_log_files(dir) {
+        let data = read_bytes(&path, encrypted, from)?;
+        let bytes = match to {
+            Some(p) => protect(&data, p)?,
+            None => data,
+        };
+        std::fs::write(log_path(dir, &id, to.is_some())?, bytes)
+            .map_err(|e| format!("保存原始采集日志失败: {}", e))?;
+        std::fs::remove_file(&path).map_err(|e| format!("删除旧原始采集日志失败: {}", e))?;
+    }
+    Ok(())
+}
+
+/// 删除会话的附属数据（每秒聚合、原始帧时间、原始采集日志），不存在时跳过
+fn remove_attachments(dir: &Path, session_id: &str, encrypted: bool) -> Result<(), String> {
+    for path in [
+        session_path(&timelines_dir(dir)?, session_id, encrypted)?,
+        frames_path(dir, session_id, encrypted)?,
+        log_path(dir, session_id, encrypted)?,
+    ] {
+        if path.exists() {
+            std::fs::remove_file(&path).map_err(|e| format!("删除会话数据失败: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
+// ==================== 会话数据库 (SQLite) ====================
+
+fn db_error(e: rusqlite::Error) -> String {
+    format!("会话数据库错误: {}", e)
+}
+
+/// 打开会话目录下的数据库并建表；目录中还有旧版逐文件存储的会话时先迁移
+fn open_db(dir: &Path) -> Result<Connection, String> {
+    let conn = Connection::open(dir.join(DB_FILE))
+        .map_err(|e| format!("无法打开会话数据库: {}", e))?;
+    conn.busy_timeout(DB_BUSY_TIMEOUT).map_err(db_error)?;
+    // summary 为空表示尚未生成摘要（迁移自旧版文件存储），列表查询时补齐
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS sessions (
+             session_id TEXT PRIMARY KEY,
+             summary BLOB,
+             data BLOB NOT NULL
+         )",
+    )
+    .map_err(db_error)?;
+    migrate_files(dir, &conn)?;
+    Ok(conn)
+}
+
+/// 迁移旧版逐文件存储（每个会话一个 .json，加密后为 .bin）的会话
+/// 文件内容原样写入（加密会话不需要密码），写入成功后删除旧文件与摘要索引
+fn migrate_files(dir: &Path, conn: &Connection) -> Result<(), String> {
+    let files = session_files(dir);
+    let legacy_index: Vec<PathBuf> = [false, true]
+        .into_iter()
+        .filter_map(|encrypted| session_path(dir, LEGACY_INDEX_NAME, encrypted).ok())
+        .filter(|p| p.exists())
+        .collect();
+    if files.is_empty() && legacy_index.is_empty() {
+        return Ok(());
+    }
+    let encrypted = is_encrypted(dir);
+    let tx = conn.unchecked_transaction().map_err(db_error)?;
+    let mut migrated = Vec::new();
+    for (path, file_encrypted) in files {
+        let Some(id) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        // 加密中途失败留下的文件与会话库状态不一致，保留原文件
+        if file_encrypted != encrypted || check_session_id(id).is_err() {
+            log::warn!("跳过无法迁移的会话文件 {}", path.display());
+            continue;
+        }
+        let data = std::fs::read(&path).map_err(|e| format!("读取会话失败: {}", e))?;
+        // 旧文件只会是恢复旧版备份或尚未迁移的会话，以文件内容为准
+        tx.execute(
+            "INSERT OR REPLACE INTO sessions (session_id, summary, data) VALUES (?1, NULL, ?2)",
+            params![id, data],
+        )
+        .map_err(db_error)?;
+        migrated.push(path);
+    }
+    tx.commit().map_err(db_error)?;
+
+    for path in migrated.iter().chain(&legacy_index) {
+        if let Err(e) = std::fs::remove_file(path) {
+            log::warn!("删除已迁移的会话文件 {} 失败: {}", path.display(), e);
+        }
+    }
+    if !migrated.is_empty() {
+        log::info!("已将 {} 个会话文件迁移到会话数据库", migrated.len());
+    }
+    Ok(())
+}
+
+/// 按会话库加密状态编码一条记录（passphrase 为 None 时为明文 JSON）
+fn encode<T: Serialize>(value: &T, passphrase: Option<&str>) -> Result<Vec<u8>, String> {
+    let json = serde_json::to_vec(value).map_err(|e| e.to_string())?;
+    match passphrase {
+        Some(p) => protect(&json, p),
+        None => Ok(json),
+    }
+}
+
+fn decode<T: DeserializeOwned>(bytes: Vec<u8>, passphrase: Option<&str>) -> Result<T, String> {
+    let json = match passphrase {
+        Some(p) => unprotect(&bytes, p)?,
+        None => bytes,
+    };
+    serde_json::from_slice(&json).map_err(|e| format!("会话记录格式错误: {}", e))
+}
+
+fn write_session(
+    conn: &Connection,
+    session: &FpsSession,
+    passphrase: Option<&str>,
+) -> Result<(), String> {
+    check_session_id(&session.session_id)?;
+    conn.execute(
+        "INSERT OR REPLACE INTO sessions (session_id, summary, data) VALUES (?1, ?2, ?3)",
+        params![
+            session.session_id,
+            encode(&SessionIndexEntry::new(session), passphrase)?,
+            encode(session, passphrase)?
+        ],
+    )
+    .map_err(db_error)?;
+    Ok(())
+}
+
+fn read_session(
+    conn: &Connection,
+    session_id: &str,
+    passphrase: Option<&str>,
+) -> Result<FpsSession, String> {
+    let data: Vec<u8> = conn
+        .query_row(
+            "SELECT data FROM sessions WHERE session_id = ?1",
+            [session_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(db_error)?
+        .ok_or_else(|| format!("会话 {} 不存在", session_id))?;
+    decode(data, passphrase)
+}
+
+/// 全部会话 ID（按开始时间倒序）
+fn session_ids(conn: &Connection) -> Result<Vec<String>, String> {
+    let mut stmt = conn
+        .prepare("SELECT session_id FROM sessions ORDER BY session_id DESC")
+        .map_err(db_error)?;
+    let ids = stmt
+        .query_map([], |row| row.get(0))
+        .map_err(db_error)?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(db_error)?;
+    Ok(ids)
+}
+
+/// 以新密码（None 为明文）在一个事务中重写全部会话，中途失败时会话保持原样
+fn rewrite_sessions(
+    task: &TaskHandle,
+    conn: &mut Connection,
+    total: u64,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> Result<usize, String> {
+    let ids = session_ids(conn)?;
+    let tx = conn.transaction().map_err(db_error)?;
+    for (i, id) in ids.iter().enumerate() {
+        task.progress(i as u64, total, if to.is_some() { "加密会话" } else { "解密会话" });
+        let session = read_session(&tx, id, from)?;
+        write_session(&tx, &session, to)?;
+    }
+    tx.commit().map_err(db_error)?;
+    Ok(ids.len())
+}
+
+fn temp_db_path() -> PathBuf {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    std::env::temp_dir().join(format!(
+        "gamebench-{}-{}.db",
+        std::process::id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+/// 把备份中的数据库内容写入临时文件后执行 `f`，结束后删除临时文件
+fn with_temp_db<T>(bytes: &[u8], f: impl FnOnce(&Path) -> Result<T, String>) -> Result<T, String> {
+    let path = temp_db_path();
+    std::fs::write(&path, bytes).map_err(|e| format!("写入临时数据库失败: {}", e))?;
+    let result = f(&path);
+    let _ = std::fs::remove_file(&path);
+    result
+}
+
+/// 是否为会话数据库文件（备份时需要整体快照、恢复时合并）
+pub fn is_session_db(path: &str) -> bool {
+    path.rsplit('/').next() == Some(DB_FILE)
+}
+
+/// 会话数据库的一致快照（VACUUM INTO），用于备份
+pub fn snapshot_db(path: &Path) -> Result<Vec<u8>, String> {
+    let conn = Connection::open(path).map_err(|e| format!("无法打开会话数据库: {}", e))?;
+    conn.busy_timeout(DB_BUSY_TIMEOUT).map_err(db_error)?;
+    let target = temp_db_path();
+    conn.execute("VACUUM INTO ?1", [target.to_string_lossy()])
+        .map_err(db_error)?;
+    let bytes = std::fs::read(&target).map_err(|e| format!("读取数据库快照失败: {}", e));
+    let _ = std::fs::remove_file(&target);
+    bytes
+}
+
+/// 备份中的会话数据库包含的会话数，无法读取时为 0
+pub fn count_db_sessions(bytes: &[u8]) -> usize {
+    with_temp_db(bytes, |path| {
+        Connection::open(path)
+            .and_then(|conn| conn.query_row("SELECT COUNT(*) FROM sessions", [], |row| row.get(0)))
+            .map_err(db_error)
+    })
+    .unwrap_or(0)
+}
+
+/// 把备份中的会话数据库合并到 `target`：同 ID 的会话以备份为准，本机其他会话保留
+pub fn merge_db(target: &Path, bytes: &[u8]) -> Result<(), String> {
+    let dir = target
+        .parent()
+        .ok_or_else(|| format!("无效的会话数据库路径: {}", target.display()))?;
+    std::fs::create_dir_all(dir).map_err(|e| format!("无法创建会话目录: {}", e))?;
+    let conn = open_db(dir)?;
+    with_temp_db(bytes, |path| {
+        conn.execute("ATTACH DATABASE ?1 AS backup", [path.to_string_lossy()])
+            .map_err(db_error)?;
+        let merged = conn
+            .execute(
+                "INSERT OR REPLACE INTO main.sessions (session_id, summary, data)
+                 SELECT session_id, summary, data FROM backup.sessions",
+                [],
+            )
+            .map_err(db_error);
+        let _ = conn.execute("DETACH DATABASE backup", []);
+        merged.map(|_| ())
+    })
+}
+
+fn verify_passphrase(dir: &Path, passphrase: &str) -> Result<(), String> {
+    let check = std::fs::read(dir.join(KEY_CHECK_FILE)).map_err(|e| e.to_string())?;
+    if unprotect(&check, passphrase)? == KEY_CHECK_TEXT {
+        Ok(())
+    } else {
+        Err("密码错误".to_string())
+    }
+}
+
+/// 会话库已加密时返回当前密码；锁定时返回 Err
+fn current_passphrase(dir: &Path) -> Result<Option<String>, String> {
+    if !is_encrypted(dir) {
+        return Ok(None);
+    }
+    state()
+        .lock()
+        .unwrap()
+        .passphrase
+        .clone()
+        .map(Some)
+        .ok_or_else(|| "会话库已加密，请先输入密码解锁".to_string())
+}
+
+// ==================== 对外接口 ====================
+
+/// 保存完成的会话；会话库加密且未解锁时暂存在内存中，解锁后写入
+pub fn save_session(app: &AppHandle, session: &FpsSession) -> Result<(), String> {
+    let dir = sessions_dir(app)?;
+    let passphrase = if is_encrypted(&dir) {
+        let mut state = state().lock().unwrap();
+        match state.passphrase.clone() {
+            Some(p) => Some(p),
+            None => {
+                log::info!("会话库已锁定，{} 将在解锁后保存", session.session_id);
+                state.pending.push(session.clone());
+                return Ok(());
+            }
+        }
+    } else {
+        None
+    };
+    write_session(&open_db(&dir)?, session, passphrase.as_deref())
+}
+
+/// 保存会话的每秒聚合数据，加密与锁定规则同会话
+pub fn save_timeline(app: &AppHandle, timeline: &SessionTimeline) -> Result<(), String> {
+    let dir = sessions_dir(app)?;
+    if is_encrypted(&dir) {
+        let mut state = state().lock().unwrap();
+        match state.passphrase.clone() {
+            Some(p) => write_timeline(&dir, timeline, Some(&p)),
+            None => {
+                state.pending_timelines.push(timeline.clone());
+                Ok(())
+            }
+        }
+    } else {
+        write_timeline(&dir, timeline, None)
+    }
+}
+
+pub fn load_timeline(app: &AppHandle, session_id: &str) -> Result<SessionTimeline, String> {
+    let dir = sessions_dir(app)?;
+    let passphrase = current_passphrase(&dir)?;
+    let encrypted = passphrase.is_some();
+    let path = session_path(&timelines_dir(&dir)?, session_id, encrypted)?;
+    if !path.exists() {
+        return Err(format!("会话 {} 没有每秒聚合数据", session_id));
+    }
+    read_record(&path, encrypted, passphrase.as_deref())
+}
+
+/// 分块压缩保存会话的原始帧时间，加密与锁定规则同会话
+pub fn save_frames(app: &AppHandle, session_id: &str, frame_times: &[f64]) -> Result<(), String> {
+    let dir = sessions_dir(app)?;
+    store_frames(&dir, session_id, frame_log::encode(frame_times)?)
+}
+
+/// 导入时逐帧写入的帧时间文件位置（帧数据目录下），写完后用 `commit_frames` 保存
+pub fn staged_frames_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let mut suffix = [0u8; 8];
+    getrandom::getrandom(&mut suffix).map_err(|e| e.to_string())?;
+    Ok(frames_dir(&sessions_dir(app)?)?.join(format!(
+        "import-{}.{}",
+        crate::signing::hex(&suffix),
+        STAGED_FRAMES_EXT
+    )))
+}
+
+/// 尚未保存的帧时间文件及其写入中的块数据，备份时跳过
+pub fn is_staged_frames(name: &str) -> bool {
+    let name = name.strip_suffix(".part").unwrap_or(name);
+    Path::new(name).extension().and_then(|e| e.to_str()) == Some(STAGED_FRAMES_EXT)
+}
+
+/// 保存逐帧写入的帧时间文件：未加密时直接移动到位，加密时整体读入后加密
+pub fn commit_frames(app: &AppHandle, session_id: &str, staged: &Path) -> Result<(), String> {
+    let dir = sessions_dir(app)?;
+    if is_encrypted(&dir) {
+        let data = std::fs::read(staged).map_err(|e| format!("保存帧数据失败: {}", e));
+        let _ = std::fs::remove_file(staged);
+        return store_frames(&dir, session_id, data?);
+    }
+    let path = frames_path(&dir, session_id, false)?;
+    std::fs::rename(staged, path).map_err(|e| {
+        let _ = std::fs::remove_file(staged);
+        format!("保存帧数据失败: {}", e)
+    })
+}
+
+fn store_frames(dir: &Path, session_id: &str, data: Vec<u8>) -> Result<(), String> {
+    if is_encrypted(dir) {
+        let mut state = state().lock().unwrap();
+        match state.passphrase.clone() {
+            Some(p) => write_frames(dir, session_id, &data, Some(&p)),
+            None => {
+                state.pending_frames.push((session_id.to_string(), data));
+                Ok(())
+            }
+        }
+    } else {
+        write_frames(dir, session_id, &data, None)
+    }
+}
+
+/// 读取会话的原始帧时间，返回后按需解压
+pub fn load_frames(app: &AppHandle, session_id: &str) -> Result<FrameLog, String> {
+    let dir = sessions_dir(app)?;
+    let passphrase = current_passphrase(&dir)?;
+    let encrypted = passphrase.is_some();
+    let path = frames_path(&dir, session_id, encrypted)?;
+    if !path.exists() {
+        return Err(format!("会话 {} 没有保存原始帧时间", session_id));
+    }
+    // 未加密时只读取索引，按块读取；加密文件需整体解密
+    if encrypted {
+        FrameLog::parse(read_bytes(&path, encrypted, passphrase.as_deref())?)
+    } else {
+        FrameLog::open(&path)
+    }
+}
+
+/// 原始采集日志在监测期间逐帧明文写入，会话库已加密时不可用
+pub fn check_capture_log(app: &AppHandle) -> Result<(), String> {
+    if is_encrypted(&sessions_dir(app)?) {
+        return Err("会话库已加密，无法保存原始采集日志".to_string());
+    }
+    Ok(())
+}
+
+/// 会话原始采集日志的路径
+pub fn capture_log_path(app: &AppHandle, session_id: &str) -> Result<PathBuf, String> {
+    check_capture_log(app)?;
+    log_path(&sessions_dir(app)?, session_id, false)
+}
+
+/// 读取全部已保存的会话（按开始时间倒序）
+pub fn load_sessions(app: &AppHandle) -> Result<Vec<FpsSession>, String> {
+    let dir = sessions_dir(app)?;
+    let passphrase = current_passphrase(&dir)?;
+    let conn = open_db(&dir)?;
+    let mut stmt = conn
+        .prepare("SELECT session_id, data FROM sessions ORDER BY session_id DESC")
+        .map_err(db_error)?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get(1)?)))
+        .map_err(db_error)?;
+    let mut sessions = Vec::new();
+    for row in rows {
+        let (id, data) = row.map_err(db_error)?;
+        match decode(data, passphrase.as_deref()) {
+            Ok(session) => sessions.push(session),
+            Err(e) => log::warn!("跳过会话 {}: {}", id, e),
+        }
+    }
+    Ok(sessions)
+}
+
+/// 读取全部会话的摘要（按开始时间倒序），只读取摘要列
+/// 迁移自旧版文件存储、还没有摘要的会话读取一次完整记录并补齐
+pub fn load_index(app: &AppHandle) -> Result<Vec<SessionIndexEntry>, String> {
+    let dir = sessions_dir(app)?;
+    let passphrase = current_passphrase(&dir)?;
+    let passphrase = passphrase.as_deref();
+    let conn = open_db(&dir)?;
+    let rows: Vec<(String, Option<Vec<u8>>)> = {
+        let mut stmt = conn
+            .prepare("SELECT session_id, summary FROM sessions ORDER BY session_id DESC")
+            .map_err(db_error)?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(db_error)?
+            .collect::<Result<_, _>>()
+            .map_err(db_error)?;
+        rows
+    };
+
+    let mut entries = Vec::with_capacity(rows.len());
+    for (id, summary) in rows {
+        let entry = match summary {
+            Some(bytes) => decode(bytes, passphrase),
+            None => read_session(&conn, &id, passphrase).and_then(|session| {
+                let entry = SessionIndexEntry::new(&session);
+                conn.execute(
+                    "UPDATE sessions SET summary = ?2 WHERE session_id = ?1",
+                    params![id, encode(&entry, passphrase)?],
+                )
+                .map_err(db_error)?;
+                Ok(entry)
+            }),
+        };
+        match entry {
+            Ok(entry) => entries.push(entry),
+            Err(e) => log::warn!("跳过会话 {}: {}", id, e),
+        }
+    }
+    Ok(entries)
+}
+
+pub fn load_session(app: &AppHandle, session_id: &str) -> Result<FpsSession, String> {
+    let dir = sessions_dir(app)?;
+    let passphrase = current_passphrase(&dir)?;
+    read_session(&open_db(&dir)?, session_id, passphrase.as_deref())
+}
+
+pub fn remove_session(app: &AppHandle, session_id: &str) -> Result<(), String> {
+    let dir = sessions_dir(app)?;
+    check_session_id(session_id)?;
+    remove_attachments(&dir, session_id, is_encrypted(&dir))?;
+    // 锁定期间完成、尚未写入的会话也一并丢弃
+    let pending = {
+        let mut state = state().lock().unwrap();
+        state
+            .pending_timelines
+            .retain(|t| t.session_id != session_id);
+        state.pending_frames.retain(|(id, _)| id != session_id);
+        let before = state.pending.len();
+        state.pending.retain(|s| s.session_id != session_id);
+        state.pending.len() < before
+    };
+    let removed = open_db(&dir)?
+        .execute("DELETE FROM sessions WHERE session_id = ?1", [session_id])
+        .map_err(db_error)?;
+    if removed == 0 && !pending {
+        return Err(format!("会话 {} 不存在", session_id));
+    }
+    Ok(())
+}
+
+/// 删除当前 profile 的全部会话，返回删除数量
+pub fn purge_sessions(app: &AppHandle) -> Result<usize, String> {
+    let dir = sessions_dir(app)?;
+    let removed = open_db(&dir)?
+        .execute("DELETE FROM sessions", [])
+        .map_err(db_error)?;
+    for (path, _) in session_files(&timelines_dir(&dir)?)
+        .into_iter()
+        .chain(frame_files(&dir))
+        .chain(capture_log_files(&dir).into_iter().map(|(_, p, e)| (p, e)))
+    {
+        std::fs::remove_file(&path).map_err(|e| format!("删除会话数据失败: {}", e))?;
+    }
+    let mut state = state().lock().unwrap();
+    state.pending.clear();
+    state.pending_timelines.clear();
+    state.pending_frames.clear();
+    Ok(removed)
+}
+
+/// 跨盘时 rename 会失败，退回复制后删除
+fn move_file(src: &Path, dst: &Path) -> std::io::Result<()> {
+    if std::fs::rename(src, dst).is_err() {
+        std::fs::copy(src, dst)?;
+        std::fs::remove_file(src)?;
+    }
+    Ok(())
+}
+
+/// 在两个 profile 之间移动会话，返回移动的数量
+/// 加密文件只能用原密码解密，两边会话库都未加密时才能迁移
+pub fn move_sessions(from: &Path, to: &Path, session_ids: &[String]) -> Result<usize, String> {
+    let from = sessions_dir_in(from)?;
+    let to = sessions_dir_in(to)?;
+    if is_encrypted(&from) || is_encrypted(&to) {
+        return Err("源或目标 profile 的会话库已加密，请先解除加密后再迁移".to_string());
+    }
+
+    let source = open_db(&from)?;
+    let target = open_db(&to)?;
+    let mut moved = 0;
+    for id in session_ids {
+        check_session_id(id)?;
+        let row: Option<(Option<Vec<u8>>, Vec<u8>)> = source
+            .query_row(
+                "SELECT summary, data FROM sessions WHERE session_id = ?1",
+                [id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .map_err(db_error)?;
+        let Some((summary, data)) = row else {
+            continue;
+        };
+        target
+            .execute(
+                "INSERT INTO sessions (session_id, summary, data) VALUES (?1, ?2, ?3)",
+                params![id, summary, data],
+            )
+            .map_err(|e| match e.sqlite_error_code() {
+                Some(rusqlite::ErrorCode::ConstraintViolation) => {
+                    format!("目标 profile 已存在会话 {}", id)
+                }
+                _ => format!("迁移会话 {} 失败: {}", id, e),
+            })?;
+        source
+            .execute("DELETE FROM sessions WHERE session_id = ?1", [id])
+            .map_err(db_error)?;
+        // 每秒聚合、原始帧时间与原始采集日志随会话一起迁移，缺失时跳过
+        for (src, dst) in [
+            (
+                session_path(&timelines_dir(&from)?, id, false)?,
+                session_path(&timelines_dir(&to)?, id, false)?,
+            ),
+            (frames_path(&from, id, false)?, frames_path(&to, id, false)?),
+            (log_path(&from, id, false)?, log_path(&to, id, false)?),
+        ] {
+            if src.exists() {
+                move_file(&src, &dst).map_err(|e| format!("迁移会话 {} 的数据失败: {}", id, e))?;
+            }
+        }
+        moved += 1;
+    }
+    Ok(moved)
+}
+
+// ==================== Tauri 命令 ====================
+
+#[tauri::command]
+pub fn list_sessions(app: AppHandle) -> Result<Vec<FpsSession>, String> {
+    load_sessions(&app)
+}
+
+#[tauri::command]
+pub fn get_session(app: AppHandle, session_id: String) -> Result<FpsSession, String> {
+    load_session(&app, &session_id)
+}
+
+#[tauri::command]
+pub fn delete_session(app: AppHandle, session_id: String) -> Result<(), String> {
+    crate::kiosk::ensure_unlocked()?;
+    remove_session(&app, &session_id)?;
+    log::info!("已删除会话 {}", session_id);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_session_store_status(app: AppHandle) -> Result<StoreStatus, String> {
+    let dir = sessions_dir(&app)?;
+    let state = state().lock().unwrap();
+    Ok(StoreStatus {
+        encrypted: is_encrypted(&dir),
+        unlocked: state.passphrase.is_some(),
+        session_count: open_db(&dir)?
+            .query_row("SELECT COUNT(*) FROM sessions", [], |row| row.get(0))
+            .map_err(db_error)?,
+        pending: state.pending.len(),
+    })
+}
+
+/// 输入密码解锁会话库，并写入锁定期间完成的会话
+#[tauri::command]
+pub fn unlock_session_store(app: AppHandle, passphrase: String) -> Result<(), String> {
+    let dir = sessions_dir(&app)?;
+    if !is_encrypted(&dir) {
+        return Err("会话库未加密".to_string());
+    }
+    verify_passphrase(&dir, &passphrase)?;
+
+    let conn = open_db(&dir)?;
+    let mut state = state().lock().unwrap();
+    for session in std::mem::take(&mut state.pending) {
+        write_session(&conn, &session, Some(&passphrase))?;
+    }
+    for timeline in std::mem::take(&mut state.pending_timelines) {
+        write_timeline(&dir, &timeline, Some(&passphrase))?;
+    }
+    for (id, data) in std::mem::take(&mut state.pending_frames) {
+        write_frames(&dir, &id, &data, Some(&passphrase))?;
+    }
+    state.passphrase = Some(passphrase);
+    log::info!("会话库已解锁");
+    Ok(())
+}
+
+/// 清除内存中的密码
+#[tauri::command]
+pub fn lock_session_store() -> Result<(), String> {
+    state().lock().unwrap().passphrase = None;
+    Ok(())
+}
+
+/// 用密码加密会话库（已有的会话会被重新加密保存），返回任务 ID
+/// 登记为维护任务，不可取消：逐个重写的会话文件中途停止会留下半加密的会话库
+#[tauri::command]
+pub fn enable_session_encryption(app: AppHandle, passphrase: String) -> Result<String, String> {
+    crate::kiosk::ensure_unlocked()?;
+    if passphrase.chars().count() < 6 {
+        return Err("密码至少需要 6 个字符".to_string());
+    }
+    let dir = sessions_dir(&app)?;
+    if is_encrypted(&dir) {
+        return Err("会话库已加密".to_string());
+    }
+
+    tasks::spawn(&app, TaskKind::Maintenance, "加密会话库", move |task| {
+        encrypt_store(task, &dir, passphrase)
+    })
+}
+
+fn encrypt_store(task: &TaskHandle, dir: &Path, passphrase: String) -> Result<(), String> {
+    let check = protect(KEY_CHECK_TEXT, &passphrase)?;
+    let mut conn = open_db(dir)?;
+    // 会话之后还需重写聚合数据、帧时间与原始采集日志
+    let total = session_ids(&conn)?.len() as u64 + 3;
+    let count = rewrite_sessions(task, &mut conn, total, None, Some(&passphrase))?;
+    // 摘要与记录都已重写，再清理数据库中残留的明文页
+    conn.execute_batch("VACUUM").map_err(db_error)?;
+    task.progress(total - 3, total, "加密每秒聚合数据");
+    rewrite_timelines(dir, None, Some(&passphrase))?;
+    task.progress(total - 2, total, "加密原始帧时间");
+    rewrite_frames(dir, None, Some(&passphrase))?;
+    task.progress(total - 1, total, "加密原始采集日志");
+    rewrite_capture_logs(dir, None, Some(&passphrase))?;
+    std::fs::write(dir.join(KEY_CHECK_FILE), check).map_err(|e| e.to_string())?;
+    state().lock().unwrap().passphrase = Some(passphrase);
+    log::info!("会话库已加密 ({} 个会话)", count);
+    Ok(())
+}
+
+/// 解除会话库加密（需要当前密码），返回任务 ID；与加密相同，不可取消
+#[tauri::command]
+pub fn disable_session_encryption(app: AppHandle, passphrase: String) -> Result<String, String> {
+    crate::kiosk::ensure_unlocked()?;
+    let dir = sessions_dir(&app)?;
+    if !is_encrypted(&dir) {
+        return Err("会话库未加密".to_string());
+    }
+    verify_passphrase(&dir, &passphrase)?;
+
+    tasks::spawn(
+        &app,
+        TaskKind::Maintenance,
+        "解除会话库加密",
+        move |task| decrypt_store(task, &dir, &passphrase),
+    )
+}
+
+fn decrypt_store(task: &TaskHandle, dir: &Path, passphrase: &str) -> Result<(), String> {
+    let mut conn = open_db(dir)?;
+    let total = session_ids(&conn)?.len() as u64 + 3;
+    rewrite_sessions(task, &mut conn, total, Some(passphrase), None)?;
+    task.progress(total - 3, total, "解密每秒聚合数据");
+    rewrite_timelines(dir, Some(passphrase), None)?;
+    task.progress(total - 2, total, "解密原始帧时间");
+    rewrite_frames(dir, Some(passphrase), None)?;
+    task.progress(total - 1, total, "解密原始采集日志");
+    rewrite_capture_logs(dir, Some(passphrase), None)?;
+    std::fs::remove_file(dir.join(KEY_CHECK_FILE)).map_err(|e| e.to_string())?;
+
+    let mut state = state().lock().unwrap();
+    for session in std::mem::take(&mut state.pending) {
+        write_session(&conn, &session, None)?;
+    }
+    for timeline in std::mem::take(&mut state.pending_timelines) {
+        write_timeline(dir, &timeline, None)?;
+    }
+    for (id, data) in std::mem::take(&mut state.pending_frames) {
+        write_frames(dir, &id, &data, None)?;
+    }
+    state.passphrase = None;
+    log::info!("会话库已解除加密");
+    Ok(())
+}
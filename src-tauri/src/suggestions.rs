@@ -0,0 +1,516 @@
+use crate::background_activity;
+use crate::drives::{self, HealthStatus};
+use crate::fps_monitor::{CaptureProfile, FpsSession};
+use crate::frame_cap::CapKind;
+use crate::gpu_routing::CompositionPath;
+use crate::hardware::{self, MemoryProfile};
+use crate::pcie::PcieIssue;
+use crate::thermal::PerfMode;
+use crate::units;
+use crate::vbs::{self, VbsState};
+use serde::{Deserialize, Serialize};
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestionLevel {
+    Info,
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Suggestion {
+    /// 建议类型标识 (e.g., "ram_jedec_default")
+    pub code: String,
+    pub level: SuggestionLevel,
+    pub title: String,
+    pub detail: String,
+}
+
+impl Suggestion {
+    fn new(code: &str, level: SuggestionLevel, title: &str, detail: String) -> Self {
+        Suggestion {
+            code: code.to_string(),
+            level,
+            title: title.to_string(),
+            detail,
+        }
+    }
+}
+
+// ==================== 规则 ====================
+
+/// 内存运行在 JEDEC 默认频率（未开启 XMP/EXPO）
+fn check_memory_speed(out: &mut Vec<Suggestion>) {
+    let ram = hardware::detect_ram_info();
+    if ram.profile != MemoryProfile::JedecDefault {
+        return;
+    }
+
+    let configured = ram.configured_speed_mhz.unwrap_or(0);
+    let rated_hint = match ram.rated_speed_mhz {
+        Some(rated) if rated > configured => format!("（内存条标称 {} MHz）", rated),
+        _ => String::new(),
+    };
+    out.push(Suggestion::new(
+        "ram_jedec_default",
+        SuggestionLevel::Warning,
+        "内存运行在默认频率",
+        format!(
+            "{} 当前运行在 {} MHz{}，通常意味着 BIOS 中未开启 XMP/EXPO。\
+             开启后 CPU 瓶颈场景的 1% Low 普遍可提升 10% 以上。",
+            ram.memory_type.as_deref().unwrap_or("内存"),
+            configured,
+            rated_hint
+        ),
+    ));
+}
+
+/// PCIe 链路通道数不足或降速
+fn check_pcie_link(session: &FpsSession, out: &mut Vec<Suggestion>) {
+    let Some(link) = &session.pcie_link else {
+        return;
+    };
+    for (issue, warning) in &link.warnings {
+        out.push(Suggestion::new(
+            match issue {
+                PcieIssue::WidthLimited => "pcie_width_limited",
+                PcieIssue::GenLimited => "pcie_gen_limited",
+                PcieIssue::Downclocked => "pcie_downclocked",
+            },
+            SuggestionLevel::Warning,
+            "显卡 PCIe 链路异常",
+            warning.clone(),
+        ));
+    }
+}
+
+/// 测试期间显存出现纠错：超频看似稳定，实际在静默纠错
+fn check_vram_stability(session: &FpsSession, out: &mut Vec<Suggestion>) {
+    let Some(vram) = session.vram.as_ref().filter(|v| v.stable == Some(false)) else {
+        return;
+    };
+    let (code, level) = if vram.uncorrected_delta > 0 {
+        ("vram_uncorrected_errors", SuggestionLevel::Critical)
+    } else {
+        ("vram_corrected_errors", SuggestionLevel::Warning)
+    };
+    out.push(Suggestion::new(
+        code,
+        level,
+        "显存纠错计数增长",
+        format!("{}：{}", vram.gpu_name, vram.note),
+    ));
+}
+
+/// 卡顿与 DPC/ISR 延迟风暴同时出现：驱动占用 CPU 导致游戏线程无法及时调度
+fn check_dpc_latency(session: &FpsSession, out: &mut Vec<Suggestion>) {
+    let Some(dpc) = &session.dpc_latency else {
+        return;
+    };
+    if dpc.stutters_with_storm == 0 {
+        return;
+    }
+    if let Some(warning) = dpc.warnings.first() {
+        out.push(Suggestion::new(
+            "dpc_latency_storm",
+            SuggestionLevel::Warning,
+            "卡顿与驱动延迟相关",
+            warning.clone(),
+        ));
+    }
+}
+
+/// 卡顿与硬页错误高峰同时出现：物理内存不足，游戏数据被换出到磁盘
+fn check_memory_pressure(session: &FpsSession, out: &mut Vec<Suggestion>) {
+    let Some(memory) = &session.memory_pressure else {
+        return;
+    };
+    if memory.stutters_with_pressure == 0 {
+        return;
+    }
+    if let Some(warning) = memory.warnings.first() {
+        out.push(Suggestion::new(
+            "memory_pressure_stutter",
+            SuggestionLevel::Warning,
+            "卡顿与内存不足相关",
+            warning.clone(),
+        ));
+    }
+}
+
+/// 测试期间有后台服务活动：结果可重复性差，建议处理后重测
+fn check_background_activity(session: &FpsSession, out: &mut Vec<Suggestion>) {
+    if session.background_activity.is_empty() {
+        return;
+    }
+    let items: Vec<String> = session
+        .background_activity
+        .iter()
+        .map(background_activity::describe)
+        .collect();
+    let mut advice: Vec<&str> = Vec::new();
+    for item in &session.background_activity {
+        let text = background_activity::advice(item.kind);
+        if !advice.contains(&text) {
+            advice.push(text);
+        }
+    }
+    out.push(Suggestion::new(
+        "background_interference",
+        SuggestionLevel::Warning,
+        "测试期间有后台服务活动",
+        format!(
+            "{}。这些服务会周期性占用 CPU 与磁盘，导致多次测试结果差异较大。建议：{}。",
+            items.join("、"),
+            advice.join("；")
+        ),
+    ));
+}
+
+/// 混合显卡笔记本: 游戏画面经核显复制输出，或游戏跑在核显上
+fn check_gpu_routing(session: &FpsSession, out: &mut Vec<Suggestion>) {
+    let Some(routing) = &session.gpu_routing else {
+        return;
+    };
+    let render = routing.render_gpu.as_deref().unwrap_or("独显");
+    let display = routing.display_gpu.as_deref().unwrap_or("核显");
+
+    match routing.path {
+        CompositionPath::HybridCopy => out.push(Suggestion::new(
+            "gpu_hybrid_copy",
+            SuggestionLevel::Warning,
+            "画面经核显复制输出",
+            format!(
+                "游戏由 {} 渲染，但屏幕连接在 {} 上，每帧画面都需要经 PCIe 复制到核显再输出，\
+                 通常会损失 5%~15% 的帧率并增加延迟。可在显卡控制面板中切换为「独显直连」\
+                 (MUX / Advanced Optimus)，或将外接显示器接到独显直出的接口。",
+                render, display
+            ),
+        )),
+        CompositionPath::IntegratedOnly => out.push(Suggestion::new(
+            "gpu_integrated_render",
+            SuggestionLevel::Critical,
+            "游戏运行在核显上",
+            format!(
+                "检测到游戏使用 {} 渲染，但系统中存在独立显卡。请在 Windows「设置 → 显示 → 图形」\
+                 或显卡控制面板中将该游戏设为「高性能」。",
+                render
+            ),
+        )),
+        _ => {}
+    }
+}
+
+/// 多数帧经 DWM 合成后显示（非独立翻转）
+fn check_present_mode(session: &FpsSession, out: &mut Vec<Suggestion>) {
+    let Some(present) = &session.present else {
+        return;
+    };
+    if !present.composed || present.present_mode_pct < 50.0 {
+        return;
+    }
+    // 复制模式（传统 BitBlt 交换链）每帧还要多一次拷贝
+    let copy = if present.present_mode.contains("Copy") {
+        "当前为复制模式，每帧还需额外拷贝一次，开销更大；"
+    } else {
+        ""
+    };
+    out.push(Suggestion::new(
+        "present_composed",
+        SuggestionLevel::Warning,
+        "画面经 DWM 合成输出",
+        format!(
+            "本次 {} 的帧以「{}」方式呈现，画面需经桌面窗口管理器合成后才显示，\
+             通常会增加一帧左右的延迟，帧率也可能被限制在桌面刷新率。{}\
+             建议使用独占全屏或支持翻转模型的无边框全屏，并关闭录屏、聊天软件等叠加层。",
+            units::current().percent(present.present_mode_pct, 0),
+            present.present_mode,
+            copy
+        ),
+    ));
+}
+
+/// 游戏所在磁盘存在健康问题
+fn check_drive_health(session: &FpsSession, out: &mut Vec<Suggestion>) {
+    let Some(exe_path) = &session.metadata.exe_path else {
+        return;
+    };
+    let Some(health) = drives::health_for_path(std::path::Path::new(exe_path)) else {
+        return;
+    };
+    if health.problems.is_empty() {
+        return;
+    }
+
+    let level = if health.status == HealthStatus::Unhealthy {
+        SuggestionLevel::Critical
+    } else {
+        SuggestionLevel::Warning
+    };
+    out.push(Suggestion::new(
+        "drive_health",
+        level,
+        "游戏所在磁盘存在健康问题",
+        format!(
+            "{}：{}。磁盘异常可能导致加载卡顿和帧时间尖峰，测试结果可能不具有代表性。",
+            health.model,
+            health.problems.join("；")
+        ),
+    ));
+}
+
+/// VBS / 内存完整性开启时的预计性能影响
+/// 同配置跑分差异的常见原因之一
+fn check_vbs(out: &mut Vec<Suggestion>) {
+    let status = vbs::detect_vbs_status();
+    if status.vbs != VbsState::Running {
+        return;
+    }
+    let Some(impact) = &status.expected_impact else {
+        return;
+    };
+
+    let level = if impact.max_percent >= 10 {
+        SuggestionLevel::Warning
+    } else {
+        SuggestionLevel::Info
+    };
+    let title = if status.hvci_running {
+        "已开启内存完整性 (HVCI)"
+    } else {
+        "已开启基于虚拟化的安全 (VBS)"
+    };
+    out.push(Suggestion::new(
+        "vbs_enabled",
+        level,
+        title,
+        format!(
+            "{}。根据社区同配置对比数据，预计平均帧率降低约 {}%~{}%，\
+             这也是相同硬件跑分存在差异的常见原因。如需关闭，可在\
+             「Windows 安全中心 → 设备安全性 → 内核隔离」中关闭内存完整性（会降低系统安全性）。",
+            impact.basis, impact.min_percent, impact.max_percent
+        ),
+    ));
+}
+
+// ==================== 入口 ====================
+
+/// 串流会话：本机硬件不影响画面帧率，只提示网络/解码情况
+fn check_stream(session: &FpsSession, out: &mut Vec<Suggestion>) {
+    let Some(stream) = &session.stream else {
+        return;
+    };
+    let units = units::current();
+    let level = if stream.late_frame_percent >= 5.0 {
+        SuggestionLevel::Warning
+    } else {
+        SuggestionLevel::Info
+    };
+    out.push(Suggestion::new(
+        "stream_session",
+        level,
+        "本次为串流会话",
+        format!(
+            "{} 串流目标 {} FPS，实际显示 {} FPS，迟到帧 {}。\
+             串流画面由远端渲染，帧率主要受网络与解码影响，与本机硬件性能无关。",
+            stream.client,
+            units.number(stream.target_fps, 0),
+            units.number(stream.delivered_fps, 1),
+            units.percent(stream.late_frame_percent, 1)
+        ),
+    ));
+}
+
+/// 笔记本厂商性能模式：测试中途切换或处于静音模式
+fn check_perf_mode(session: &FpsSession, out: &mut Vec<Suggestion>) {
+    let Some(thermal) = &session.thermal else {
+        return;
+    };
+    if let Some(first) = thermal.changes.first() {
+        out.push(Suggestion::new(
+            "perf_mode_changed",
+            SuggestionLevel::Warning,
+            "测试中性能模式发生切换",
+            format!(
+                "第 {:.0} 秒时性能模式从 {:?} 切换为 {:?}（共切换 {} 次），前后帧率不可直接比较。\
+                 请检查厂商控制中心是否开启了「智能模式」或插拔电源时自动切换。",
+                first.at_secs,
+                first.from,
+                first.to,
+                thermal.changes.len()
+            ),
+        ));
+    } else if let Some(mode) = thermal
+        .perf_mode
+        .as_ref()
+        .filter(|m| m.mode == PerfMode::Quiet)
+    {
+        out.push(Suggestion::new(
+            "perf_mode_quiet",
+            SuggestionLevel::Info,
+            "笔记本处于静音模式",
+            format!(
+                "{} 性能模式为静音，CPU/GPU 功耗墙较低。如需测试最高性能，请在厂商控制中心切换到性能/野兽模式。",
+                mode.vendor
+            ),
+        ));
+    }
+}
+
+/// GameBench 自身 CPU 占用超过此值时提示使用最低开销档位 (%)
+const OVERHEAD_CPU_WARN_PERCENT: f64 = 3.0;
+
+/// 自身开销偏高，可能影响测试结果
+fn check_overhead(session: &FpsSession, out: &mut Vec<Suggestion>) {
+    let Some(overhead) = &session.overhead else {
+        return;
+    };
+    if overhead.avg_cpu_percent < OVERHEAD_CPU_WARN_PERCENT
+        || session.capture_profile == CaptureProfile::Minimal
+    {
+        return;
+    }
+    let units = units::current();
+    out.push(Suggestion::new(
+        "observer_overhead",
+        SuggestionLevel::Info,
+        "GameBench 自身开销偏高",
+        format!(
+            "测试期间 GameBench 与 PresentMon 平均占用 {} CPU（峰值 {}）、{} MB 内存。\
+             CPU 瓶颈的游戏可能因此略微降低帧率，可在设置中将采集档位切换为「最低开销」。",
+            units.percent(overhead.avg_cpu_percent, 1),
+            units.percent(overhead.peak_cpu_percent, 1),
+            units.number(overhead.avg_ram_mb, 0)
+        ),
+    ));
+}
+
+/// 系统连续运行超过此天数时建议重启
+const UPTIME_WARN_DAYS: u64 = 7;
+
+/// 长时间未重启：驱动/后台进程的内存泄漏与碎片会拖慢帧率稳定性
+fn check_uptime(session: &FpsSession, out: &mut Vec<Suggestion>) {
+    let Some(uptime) = session.metadata.uptime_secs else {
+        return;
+    };
+    let days = uptime / 86400;
+    if days < UPTIME_WARN_DAYS {
+        return;
+    }
+    out.push(Suggestion::new(
+        "long_uptime",
+        SuggestionLevel::Info,
+        "系统长时间未重启",
+        format!(
+            "系统已连续运行 {} 天（上次开机：{}）。长时间运行后驱动与后台程序的资源占用会逐渐增加，\
+             可能导致帧率波动。建议重启后再测试；注意开启「快速启动」时关机不会重置运行时长，需选择「重启」。",
+            days,
+            session.metadata.boot_time.as_deref().unwrap_or("未知")
+        ),
+    ));
+}
+
+/// 测试期间显卡驱动超时恢复 (TDR)：对应时段的帧时间空洞不是游戏本身的卡顿
+fn check_driver_resets(session: &FpsSession, out: &mut Vec<Suggestion>) {
+    let Some(first) = session.driver_resets.first() else {
+        return;
+    };
+    let hole = first
+        .hole_ms
+        .map(|ms| {
+            format!(
+                "，对应约 {} ms 的帧时间空洞",
+                units::current().number(ms, 0)
+            )
+        })
+        .unwrap_or_default();
+    out.push(Suggestion::new(
+        "driver_reset",
+        SuggestionLevel::Critical,
+        "测试期间显卡驱动发生重置",
+        format!(
+            "第 {:.0} 秒记录到 {} 事件 {}（驱动超时检测与恢复，共 {} 次）{}。\
+             该次测试的 1% Low 与最低帧率不可信，建议检查显卡超频/降压设置、驱动版本与供电后重新测试。",
+            first.at_secs,
+            first.provider,
+            first.event_id,
+            session.driver_resets.len(),
+            hole
+        ),
+    ));
+}
+
+/// 限帧建议：说明限帧值的来由与延迟/流畅度取舍
+fn check_frame_cap(session: &FpsSession, out: &mut Vec<Suggestion>) {
+    let Some(advice) = &session.frame_cap else {
+        return;
+    };
+    let units = units::current();
+    let reason = match advice.kind {
+        CapKind::VrrCeiling => format!(
+            "本次平均 {} FPS，接近或超过显示器 {} Hz 的 VRR 上限。帧率超出上限后 VRR 失效，\
+             会出现撕裂，开启垂直同步时则会排队增加延迟。限制在 {} FPS 可让每一帧都落在 VRR 范围内，\
+             同时显卡不再满载，输入延迟通常会降低；代价是最高帧率略低。",
+            units.number(advice.avg_fps, 0),
+            units.number(advice.refresh_hz, 0),
+            advice.cap_fps
+        ),
+        CapKind::Sustained => format!(
+            "本次平均 {} FPS，但 95% 的帧只能稳定在 {} FPS，帧率起伏明显。限制在 {} FPS 后，\
+             约 {} 的帧会被拉平，帧时间更均匀、体感更顺；显卡留有余量时延迟也更稳定，\
+             代价是平均帧率下降、单帧延迟略有增加。",
+            units.number(advice.avg_fps, 0),
+            units.number(advice.sustained_fps, 0),
+            advice.cap_fps,
+            units.percent(advice.frames_above_cap_pct, 0)
+        ),
+        CapKind::Refresh => format!(
+            "本次 95% 的帧都高于 {} Hz 刷新率，超出的帧显示器无法呈现。限制在 {} FPS（或开启垂直同步）\
+             可消除撕裂并降低功耗与温度；竞技游戏更在意延迟时，也可不限帧并接受撕裂。",
+            units.number(advice.refresh_hz, 0),
+            advice.cap_fps
+        ),
+        CapKind::HalfRefresh => format!(
+            "本次平均 {} FPS，达不到 {} Hz 刷新率，固定刷新率显示器上帧间隔会在 1 帧和 2 帧之间跳动，\
+             体感比帧率数字更卡。限制在 {} FPS（半刷新率垂直同步）可让每帧都显示两个刷新周期，\
+             画面更均匀，代价是帧率和响应速度下降。",
+            units.number(advice.avg_fps, 0),
+            units.number(advice.refresh_hz, 0),
+            advice.cap_fps
+        ),
+    };
+    out.push(Suggestion::new(
+        "frame_cap",
+        SuggestionLevel::Info,
+        &format!("建议将帧率上限设为 {} FPS", advice.cap_fps),
+        reason,
+    ));
+}
+
+/// 为完成的 session 生成优化建议
+pub fn build_session_suggestions(session: &FpsSession) -> Vec<Suggestion> {
+    let mut out = Vec::new();
+    check_driver_resets(session, &mut out);
+    if session.stream.is_some() {
+        check_stream(session, &mut out);
+        return out;
+    }
+    check_memory_speed(&mut out);
+    check_pcie_link(session, &mut out);
+    check_vram_stability(session, &mut out);
+    check_dpc_latency(session, &mut out);
+    check_memory_pressure(session, &mut out);
+    check_background_activity(session, &mut out);
+    check_gpu_routing(session, &mut out);
+    check_present_mode(session, &mut out);
+    check_drive_health(session, &mut out);
+    check_vbs(&mut out);
+    check_uptime(session, &mut out);
+    check_perf_mode(session, &mut out);
+    check_overhead(session, &mut out);
+    check_frame_cap(session, &mut out);
+    out
+}
@@ -0,0 +1,263 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use tauri::{AppHandle, Emitter};
+
+/// 任务被取消时返回的错误
+pub const CANCELLED: &str = "任务已取消";
+
+// ==================== 数据结构 ====================
+
+/// 任务类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskKind {
+    /// 下载 PresentMon、应用更新
+    Download,
+    /// 导入第三方帧时间
+    Import,
+    /// 导出报告与会话
+    Report,
+    /// 帧时间分析、多会话对比
+    Analysis,
+    /// 会话库加密、备份与恢复
+    Maintenance,
+}
+
+impl TaskKind {
+    /// 维护任务中途停止会留下半加密或半恢复的数据，不允许取消
+    fn cancellable(self) -> bool {
+        self != TaskKind::Maintenance
+    }
+}
+
+/// 后台任务当前状态（list_tasks / task-started）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskInfo {
+    pub task_id: String,
+    pub kind: TaskKind,
+    pub name: String,
+    pub done: u64,
+    pub total: u64,
+    pub message: String,
+    /// 开始时间 (unix 毫秒)
+    pub started_at_ms: u64,
+    pub cancellable: bool,
+    /// 已请求取消，等待任务到达检查点
+    pub cancel_requested: bool,
+}
+
+/// 任务进度（task-progress 事件）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskProgress {
+    pub task_id: String,
+    pub done: u64,
+    pub total: u64,
+    pub message: String,
+}
+
+/// 任务结束（task-finished 事件），成功时 `result` 为命令原本的返回值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskFinished {
+    pub task_id: String,
+    pub cancelled: bool,
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+}
+
+struct TaskEntry {
+    cancelled: AtomicBool,
+    info: Mutex<TaskInfo>,
+}
+
+/// 传给任务函数，用于上报进度和检查是否已取消
+#[derive(Clone)]
+pub struct TaskHandle {
+    app: AppHandle,
+    entry: Arc<TaskEntry>,
+}
+
+impl TaskHandle {
+    fn id(&self) -> String {
+        self.entry.info.lock().unwrap().task_id.clone()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.entry.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// 已取消时返回 Err，便于在循环中用 `?` 提前结束
+    pub fn check(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err(CANCELLED.to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn progress(&self, done: u64, total: u64, message: impl Into<String>) {
+        let progress = {
+            let mut info = self.entry.info.lock().unwrap();
+            info.done = done;
+            info.total = total;
+            info.message = message.into();
+            TaskProgress {
+                task_id: info.task_id.clone(),
+                done,
+                total,
+                message: info.message.clone(),
+            }
+        };
+        let _ = self.app.emit("task-progress", progress);
+    }
+
+    /// 从任务表移除并推送 task-finished
+    fn finish<T: Serialize>(self, outcome: Result<T, String>) {
+        let (id, name) = {
+            let info = self.entry.info.lock().unwrap();
+            (info.task_id.clone(), info.name.clone())
+        };
+        running().lock().unwrap().remove(&id);
+
+        let cancelled = self.is_cancelled();
+        let (result, error) = match outcome {
+            Ok(value) => (serde_json::to_value(value).ok(), None),
+            Err(e) => (None, Some(e)),
+        };
+        if cancelled {
+            log::info!("任务 {} ({}) 已取消", id, name);
+        } else if let Some(e) = &error {
+            log::warn!("任务 {} ({}) 失败: {}", id, name, e);
+        }
+        let _ = self.app.emit(
+            "task-finished",
+            TaskFinished {
+                task_id: id,
+                cancelled,
+                result,
+                error,
+            },
+        );
+    }
+}
+
+// ==================== 任务表 ====================
+
+fn running() -> &'static Mutex<HashMap<String, Arc<TaskEntry>>> {
+    static RUNNING: OnceLock<Mutex<HashMap<String, Arc<TaskEntry>>>> = OnceLock::new();
+    RUNNING.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 登记新任务；同名下载或任意维护任务进行中时拒绝重复启动
+fn register(app: &AppHandle, kind: TaskKind, name: &str) -> Result<TaskHandle, String> {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+    let mut running = running().lock().unwrap();
+    let conflict = running.values().find_map(|entry| {
+        let info = entry.info.lock().unwrap();
+        let clash = match kind {
+            TaskKind::Download => info.kind == kind && info.name == name,
+            TaskKind::Maintenance => info.kind == kind,
+            _ => false,
+        };
+        clash.then(|| info.name.clone())
+    });
+    if let Some(other) = conflict {
+        return Err(format!("「{}」正在进行，请等待完成后再试", other));
+    }
+
+    let info = TaskInfo {
+        task_id: format!("task-{}", NEXT_ID.fetch_add(1, Ordering::Relaxed)),
+        kind,
+        name: name.to_string(),
+        done: 0,
+        total: 0,
+        message: String::new(),
+        started_at_ms: crate::markers::now_ms() as u64,
+        cancellable: kind.cancellable(),
+        cancel_requested: false,
+    };
+    let _ = app.emit("task-started", &info);
+    let entry = Arc::new(TaskEntry {
+        cancelled: AtomicBool::new(false),
+        info: Mutex::new(info.clone()),
+    });
+    running.insert(info.task_id, entry.clone());
+    Ok(TaskHandle {
+        app: app.clone(),
+        entry,
+    })
+}
+
+/// 在后台线程运行任务，立即返回任务 ID
+/// 结果通过 task-finished 事件推送
+pub fn spawn<T, F>(app: &AppHandle, kind: TaskKind, name: &str, f: F) -> Result<String, String>
+where
+    T: Serialize,
+    F: FnOnce(&TaskHandle) -> Result<T, String> + Send + 'static,
+{
+    let handle = register(app, kind, name)?;
+    let id = handle.id();
+    std::thread::spawn(move || {
+        let outcome = f(&handle);
+        handle.finish(outcome);
+    });
+    Ok(id)
+}
+
+/// 在异步运行时中运行任务（网络下载等），立即返回任务 ID
+pub fn spawn_async<T, F, Fut>(
+    app: &AppHandle,
+    kind: TaskKind,
+    name: &str,
+    f: F,
+) -> Result<String, String>
+where
+    T: Serialize,
+    F: FnOnce(TaskHandle) -> Fut,
+    Fut: Future<Output = Result<T, String>> + Send + 'static,
+{
+    let handle = register(app, kind, name)?;
+    let id = handle.id();
+    let future = f(handle.clone());
+    tauri::async_runtime::spawn(async move {
+        let outcome = future.await;
+        handle.finish(outcome);
+    });
+    Ok(id)
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 进行中的后台任务，按开始时间排序
+#[tauri::command]
+pub fn list_tasks() -> Vec<TaskInfo> {
+    let mut tasks: Vec<TaskInfo> = running()
+        .lock()
+        .unwrap()
+        .values()
+        .map(|entry| {
+            let mut info = entry.info.lock().unwrap().clone();
+            info.cancel_requested = entry.cancelled.load(Ordering::Relaxed);
+            info
+        })
+        .collect();
+    tasks.sort_by_key(|t| t.started_at_ms);
+    tasks
+}
+
+/// 取消后台任务，任务在下一个检查点结束
+#[tauri::command]
+pub fn cancel_task(task_id: String) -> Result<(), String> {
+    let running = running().lock().unwrap();
+    let entry = running
+        .get(&task_id)
+        .ok_or_else(|| format!("任务 {} 不存在或已结束", task_id))?;
+    let info = entry.info.lock().unwrap();
+    if !info.cancellable {
+        return Err(format!("「{}」无法中途取消", info.name));
+    }
+    entry.cancelled.store(true, Ordering::Relaxed);
+    Ok(())
+}
@@ -0,0 +1,350 @@
+use crate::gpu_mux;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// 性能模式采样间隔
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+/// ASUS ATK ACPI 设备号：散热策略（Throttle Thermal Policy）
+const ASUS_DEVID_THERMAL_POLICY: u32 = 0x0012_0075;
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FormFactor {
+    Desktop,
+    Laptop,
+    /// ITX / 迷你主机
+    MiniPc,
+    #[default]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoolingSetup {
+    /// 原装散热器
+    StockAir,
+    /// 塔式风冷
+    TowerAir,
+    /// 一体式水冷
+    Aio,
+    /// 分体式水冷
+    CustomLoop,
+    /// 笔记本原装散热
+    LaptopStock,
+    /// 笔记本 + 散热底座
+    LaptopCoolingPad,
+    #[default]
+    Unknown,
+}
+
+/// 用户填写的机身与散热配置
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChassisProfile {
+    pub form_factor: FormFactor,
+    pub cooling: CoolingSetup,
+    /// 备注 (e.g., "侧板打开", "室温 30°C")
+    pub notes: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PerfMode {
+    Quiet,
+    Balanced,
+    Performance,
+    /// 厂商自定义模式（Lenovo 自定义 / ASUS Manual 等）
+    Custom,
+    #[default]
+    Unknown,
+}
+
+/// 厂商性能模式（通过 OEM WMI 接口读取）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OemPerfMode {
+    /// 厂商 (Lenovo / ASUS / Dell)
+    pub vendor: String,
+    pub mode: PerfMode,
+    /// 厂商接口返回的原始值
+    pub raw: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerfModeChange {
+    /// 相对测试开始的时间 (秒)
+    pub at_secs: f64,
+    pub from: PerfMode,
+    pub to: PerfMode,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThermalReport {
+    /// 用户填写的机身与散热配置
+    pub chassis: ChassisProfile,
+    /// 根据 SMBIOS 机箱类型识别的形态
+    pub detected_form_factor: FormFactor,
+    /// 测试开始时的厂商性能模式
+    pub perf_mode: Option<OemPerfMode>,
+    /// 测试期间的性能模式切换
+    pub changes: Vec<PerfModeChange>,
+}
+
+// ==================== 机箱类型 (Windows) ====================
+
+/// SMBIOS 机箱类型代码 → 形态
+fn form_factor_from_chassis(code: u64) -> FormFactor {
+    match code {
+        3 | 4 | 6 | 7 | 15 | 17 | 23 | 24 => FormFactor::Desktop,
+        8..=10 | 14 | 30..=32 => FormFactor::Laptop,
+        5 | 16 | 34..=36 => FormFactor::MiniPc,
+        _ => FormFactor::Unknown,
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub fn detect_form_factor() -> FormFactor {
+    use crate::drives::variant_u64;
+    use std::collections::HashMap;
+    use wmi::{COMLibrary, WMIConnection};
+
+    let Ok(com) = COMLibrary::new() else {
+        return FormFactor::Unknown;
+    };
+    let Ok(wmi) = WMIConnection::new(com) else {
+        return FormFactor::Unknown;
+    };
+    let results: Vec<HashMap<String, wmi::Variant>> = wmi
+        .raw_query("SELECT ChassisTypes FROM Win32_SystemEnclosure")
+        .unwrap_or_default();
+
+    results
+        .iter()
+        .filter_map(|item| match item.get("ChassisTypes") {
+            Some(wmi::Variant::Array(types)) => Some(types),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|t| variant_u64(Some(t)))
+        .map(form_factor_from_chassis)
+        .find(|f| *f != FormFactor::Unknown)
+        .unwrap_or_default()
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn detect_form_factor() -> FormFactor {
+    // 有电池的设备视为笔记本
+    let laptop = std::fs::read_dir("/sys/class/power_supply")
+        .map(|entries| {
+            entries
+                .flatten()
+                .any(|e| e.file_name().to_string_lossy().starts_with("BAT"))
+        })
+        .unwrap_or(false);
+    if laptop {
+        FormFactor::Laptop
+    } else {
+        form_factor_from_chassis(
+            std::fs::read_to_string("/sys/class/dmi/id/chassis_type")
+                .ok()
+                .and_then(|s| s.trim().parse().ok())
+                .unwrap_or(0),
+        )
+    }
+}
+
+// ==================== 厂商性能模式 ====================
+
+/// Lenovo Legion / LOQ: LENOVO_GAMEZONE_DATA.GetSmartFanMode
+/// 1 = 安静, 2 = 均衡, 3 = 野兽, 255 = 自定义
+#[cfg(target_os = "windows")]
+fn lenovo_perf_mode() -> Option<OemPerfMode> {
+    use wmi::{COMLibrary, WMIConnection};
+
+    #[derive(Deserialize)]
+    #[serde(rename = "LENOVO_GAMEZONE_DATA")]
+    struct GameZoneData {
+        #[serde(rename = "__Path")]
+        path: String,
+    }
+
+    #[derive(Deserialize)]
+    struct SmartFanOutput {
+        #[serde(rename = "Data")]
+        data: u32,
+    }
+
+    let com = COMLibrary::new().ok()?;
+    let wmi = WMIConnection::with_namespace_path("ROOT\\WMI", com).ok()?;
+    let instance = wmi.query::<GameZoneData>().ok()?.into_iter().next()?;
+    let output: SmartFanOutput = wmi
+        .exec_instance_method::<GameZoneData, _, _>("GetSmartFanMode", &instance.path, ())
+        .ok()?;
+
+    let mode = match output.data {
+        1 => PerfMode::Quiet,
+        2 => PerfMode::Balanced,
+        3 => PerfMode::Performance,
+        255 => PerfMode::Custom,
+        _ => PerfMode::Unknown,
+    };
+    Some(OemPerfMode {
+        vendor: "Lenovo".to_string(),
+        mode,
+        raw: output.data.to_string(),
+    })
+}
+
+/// Dell: BIOS WMI 的 ThermalManagement 属性
+#[cfg(target_os = "windows")]
+fn dell_perf_mode() -> Option<OemPerfMode> {
+    use std::collections::HashMap;
+    use wmi::{COMLibrary, WMIConnection};
+
+    let com = COMLibrary::new().ok()?;
+    let wmi = WMIConnection::with_namespace_path("ROOT\\dcim\\sysman\\biosattributes", com).ok()?;
+    let results: Vec<HashMap<String, wmi::Variant>> = wmi
+        .raw_query(
+            "SELECT CurrentValue FROM EnumerationAttribute WHERE AttributeName = 'ThermalManagement'",
+        )
+        .ok()?;
+
+    let raw = match results.first()?.get("CurrentValue")? {
+        wmi::Variant::String(s) => s.clone(),
+        wmi::Variant::Array(values) => match values.first()? {
+            wmi::Variant::String(s) => s.clone(),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let mode = match raw.to_lowercase().as_str() {
+        "quiet" | "cool" => PerfMode::Quiet,
+        "optimized" => PerfMode::Balanced,
+        "ultraperformance" => PerfMode::Performance,
+        _ => PerfMode::Unknown,
+    };
+    Some(OemPerfMode {
+        vendor: "Dell".to_string(),
+        mode,
+        raw,
+    })
+}
+
+#[cfg(not(target_os = "windows"))]
+fn lenovo_perf_mode() -> Option<OemPerfMode> {
+    None
+}
+
+#[cfg(not(target_os = "windows"))]
+fn dell_perf_mode() -> Option<OemPerfMode> {
+    None
+}
+
+/// ASUS ROG / TUF: 散热策略 0 = 性能(均衡), 1 = 增强(Turbo), 2 = 静音
+fn asus_perf_mode() -> Option<OemPerfMode> {
+    let value = gpu_mux::asus_dsts(ASUS_DEVID_THERMAL_POLICY)?;
+    let mode = match value {
+        0 => PerfMode::Balanced,
+        1 => PerfMode::Performance,
+        2 => PerfMode::Quiet,
+        _ => PerfMode::Unknown,
+    };
+    Some(OemPerfMode {
+        vendor: "ASUS".to_string(),
+        mode,
+        raw: value.to_string(),
+    })
+}
+
+/// 依次尝试各厂商接口，均不支持时返回 None
+pub fn read_perf_mode() -> Option<OemPerfMode> {
+    lenovo_perf_mode()
+        .or_else(asus_perf_mode)
+        .or_else(dell_perf_mode)
+}
+
+// ==================== 测试期间采样 ====================
+
+/// 在 FPS 监测期间定期读取厂商性能模式，记录中途切换
+pub struct PerfModeSampler {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<(Option<OemPerfMode>, Vec<PerfModeChange>)>>,
+}
+
+impl PerfModeSampler {
+    pub fn start() -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = stop.clone();
+
+        let handle = std::thread::spawn(move || {
+            let started = Instant::now();
+            let initial = read_perf_mode();
+            let mut changes = Vec::new();
+            // 不支持的机型无需继续轮询
+            let Some(mut current) = initial.as_ref().map(|m| m.mode) else {
+                return (None, changes);
+            };
+            let mut last = Instant::now();
+            while !stop_flag.load(Ordering::Relaxed) {
+                if last.elapsed() >= SAMPLE_INTERVAL {
+                    if let Some(mode) = read_perf_mode().map(|m| m.mode) {
+                        if mode != current {
+                            let at_secs = (started.elapsed().as_secs_f64() * 10.0).round() / 10.0;
+                            log::warn!("性能模式在测试中切换: {:?} → {:?}", current, mode);
+                            changes.push(PerfModeChange {
+                                at_secs,
+                                from: current,
+                                to: mode,
+                            });
+                            current = mode;
+                        }
+                    }
+                    last = Instant::now();
+                }
+                std::thread::sleep(Duration::from_millis(200));
+            }
+            (initial, changes)
+        });
+
+        PerfModeSampler {
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    /// 停止采样，结合用户填写的机身配置生成报告
+    pub fn finish(mut self) -> Option<ThermalReport> {
+        self.stop.store(true, Ordering::Relaxed);
+        let (perf_mode, changes) = self.handle.take()?.join().ok()?;
+        Some(ThermalReport {
+            chassis: crate::settings::get().chassis,
+            detected_form_factor: detect_form_factor(),
+            perf_mode,
+            changes,
+        })
+    }
+}
+
+impl Drop for PerfModeSampler {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 识别机身形态（用于预填机身配置）
+#[tauri::command]
+pub fn get_form_factor() -> Result<FormFactor, String> {
+    Ok(detect_form_factor())
+}
+
+/// 读取当前厂商性能模式（不支持的机型返回 None）
+#[tauri::command]
+pub fn get_oem_perf_mode() -> Result<Option<OemPerfMode>, String> {
+    Ok(read_perf_mode())
+}
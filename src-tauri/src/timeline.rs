@@ -0,0 +1,126 @@
+use crate::metrics::percentile_low_fps;
+use crate::sensors;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+/// 聚合间隔 (秒)
+const INTERVAL_SECS: f64 = 1.0;
+
+// ==================== 数据结构 ====================
+
+/// 会话的每秒聚合数据，独立于会话摘要单独保存
+/// 按列存储，各列长度相同；未采样遥测或读数不可用时为 null
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionTimeline {
+    pub session_id: String,
+    /// 每行覆盖的时长 (秒)
+    pub interval_secs: f64,
+    /// 每行开始时的有效采集时长 (秒)
+    pub at_secs: Vec<f32>,
+    pub fps: Vec<f32>,
+    pub fps_1_low: Vec<f32>,
+    /// 该秒内最长的帧时间 (ms)
+    pub max_frametime_ms: Vec<f32>,
+    pub cpu_percent: Vec<Option<f32>>,
+    pub cpu_temp_c: Vec<Option<f32>>,
+    pub gpu_percent: Vec<Option<f32>>,
+    pub ram_used_gb: Vec<Option<f32>>,
+}
+
+// ==================== 聚合 ====================
+
+fn round1(v: f64) -> f32 {
+    ((v * 10.0).round() / 10.0) as f32
+}
+
+/// 监测期间按有效采集时长每秒汇总一行
+pub struct TimelineRecorder {
+    timeline: SessionTimeline,
+    bucket: Vec<f64>,
+    bucket_start: Option<f64>,
+    /// 是否附带传感器读数（最低开销档位不采样遥测）
+    telemetry: bool,
+}
+
+impl TimelineRecorder {
+    pub fn new(session_id: &str, telemetry: bool) -> Self {
+        TimelineRecorder {
+            timeline: SessionTimeline {
+                session_id: session_id.to_string(),
+                interval_secs: INTERVAL_SECS,
+                ..Default::default()
+            },
+            bucket: Vec::new(),
+            bucket_start: None,
+            telemetry,
+        }
+    }
+
+    /// 每个计入统计的帧调用，`at_secs` 为当前有效采集时长
+    pub fn push(&mut self, at_secs: f64, frametime_ms: f64) {
+        match self.bucket_start {
+            Some(start) if at_secs - start >= INTERVAL_SECS => {
+                self.flush(start);
+                self.bucket_start = Some(at_secs);
+            }
+            Some(_) => {}
+            None => self.bucket_start = Some(at_secs),
+        }
+        self.bucket.push(frametime_ms);
+    }
+
+    fn flush(&mut self, start: f64) {
+        if self.bucket.is_empty() {
+            return;
+        }
+        let frames = std::mem::take(&mut self.bucket);
+        let avg = frames.iter().sum::<f64>() / frames.len() as f64;
+        let max = frames.iter().cloned().fold(0.0_f64, f64::max);
+        let t = &mut self.timeline;
+        t.at_secs.push(round1(start));
+        t.fps.push(round1(1000.0 / avg));
+        t.fps_1_low.push(round1(percentile_low_fps(&frames, 1.0)));
+        t.max_frametime_ms
+            .push(((max * 100.0).round() / 100.0) as f32);
+
+        let readings = if self.telemetry {
+            sensors::latest()
+        } else {
+            sensors::SensorReadings::default()
+        };
+        t.cpu_percent
+            .push(readings.cpu.map(|c| round1(c.usage_percent)));
+        t.cpu_temp_c.push(readings.cpu_temp_c.map(round1));
+        t.gpu_percent
+            .push(readings.gpu.map(|g| round1(g.utilization_percent)));
+        t.ram_used_gb.push(readings.ram.map(|r| round1(r.used_gb)));
+    }
+
+    /// 汇总最后不足一秒的帧；没有帧时返回 None
+    pub fn finish(mut self) -> Option<SessionTimeline> {
+        if let Some(start) = self.bucket_start {
+            self.flush(start);
+        }
+        (!self.timeline.at_secs.is_empty()).then_some(self.timeline)
+    }
+}
+
+/// 由帧时间序列生成聚合数据（导入、崩溃恢复的会话没有时间戳与遥测，按帧时间累计）
+pub fn from_frame_times(session_id: &str, frame_times: &[f64]) -> Option<SessionTimeline> {
+    let mut recorder = TimelineRecorder::new(session_id, false);
+    let mut elapsed_ms = 0.0;
+    for &ft in frame_times {
+        recorder.push(elapsed_ms / 1000.0, ft);
+        elapsed_ms += ft;
+    }
+    recorder.finish()
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 读取会话的每秒聚合数据（用于历史趋势图）
+#[tauri::command]
+pub fn get_session_timeline(app: AppHandle, session_id: String) -> Result<SessionTimeline, String> {
+    storage::load_timeline(&app, &session_id)
+}
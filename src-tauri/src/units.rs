@@ -0,0 +1,7 @@
+use crate::settings;
+pub use gamebench_core::units::{DecimalSeparator, UnitPrefs};
+
+/// 当前设置中的单位偏好
+pub fn current() -> UnitPrefs {
+    settings::get().units
+}
@@ -0,0 +1,439 @@
+use crate::tasks::{self, TaskHandle, TaskKind};
+use base64::Engine;
+use gamebench_core::ed25519;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// 启动后延迟检查更新，避免与硬件检测争抢网络
+const STARTUP_CHECK_DELAY: Duration = Duration::from_secs(30);
+/// 安装前等待当前监测结束并保存的最长时间
+const FINALIZE_TIMEOUT: Duration = Duration::from_secs(15);
+/// 发布签名公钥（十六进制 Ed25519），构建时注入；未注入的构建拒绝下载更新
+const UPDATE_PUBLIC_KEY: Option<&str> = option_env!("GAMEBENCH_UPDATE_PUBKEY");
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    /// 测试版，更新更快但可能不稳定
+    Beta,
+}
+
+impl UpdateChannel {
+    fn as_str(self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => "stable",
+            UpdateChannel::Beta => "beta",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpdateSettings {
+    pub channel: UpdateChannel,
+    /// 启动时自动检查更新
+    pub auto_check: bool,
+    /// 更新专用镜像地址（为空时按镜像设置访问服务器的 /api/updates）
+    pub mirror_url: Option<String>,
+}
+
+impl Default for UpdateSettings {
+    fn default() -> Self {
+        UpdateSettings {
+            channel: UpdateChannel::default(),
+            auto_check: true,
+            mirror_url: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UpdatePackage {
+    /// 可为相对清单地址的相对路径，镜像只需同步整个目录
+    url: String,
+    sha256: String,
+    size: u64,
+    /// 对安装包内容的 Ed25519 签名 (base64)
+    signature: String,
+}
+
+/// 从指定版本升级的差分安装包（只含变化的文件）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DeltaPackage {
+    from_version: String,
+    #[serde(flatten)]
+    package: UpdatePackage,
+}
+
+/// `{base}/{channel}/latest.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UpdateManifest {
+    version: String,
+    #[serde(default)]
+    notes: String,
+    #[serde(default)]
+    pub_date: Option<String>,
+    full: UpdatePackage,
+    #[serde(default)]
+    deltas: Vec<DeltaPackage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateInfo {
+    pub current_version: String,
+    pub version: String,
+    pub channel: UpdateChannel,
+    pub notes: String,
+    pub pub_date: Option<String>,
+    /// 是否有比当前更新的版本
+    pub available: bool,
+    /// 是否使用差分安装包
+    pub delta: bool,
+    /// 需要下载的大小 (bytes)
+    pub download_size: u64,
+}
+
+/// 已下载并校验通过、等待安装的更新
+struct PreparedUpdate {
+    version: String,
+    path: PathBuf,
+    signature: String,
+}
+
+fn prepared() -> &'static Mutex<Option<PreparedUpdate>> {
+    static PREPARED: OnceLock<Mutex<Option<PreparedUpdate>>> = OnceLock::new();
+    PREPARED.get_or_init(|| Mutex::new(None))
+}
+
+// ==================== 签名 ====================
+
+fn public_key() -> Result<[u8; 32], String> {
+    let hex = UPDATE_PUBLIC_KEY
+        .ok_or_else(|| "此版本未内置更新签名公钥，无法安全更新，请从官网下载安装包".to_string())?;
+    let bytes: Vec<u8> = (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|b| u8::from_str_radix(b, 16).ok())
+        })
+        .collect::<Option<_>>()
+        .ok_or_else(|| "内置更新签名公钥格式错误".to_string())?;
+    bytes
+        .try_into()
+        .map_err(|_| "内置更新签名公钥格式错误".to_string())
+}
+
+/// 用内置公钥校验发布签名（清单与安装包均由发布私钥签名）
+fn verify_signature(data: &[u8], signature: &str, what: &str) -> Result<(), String> {
+    let key = public_key()?;
+    let signature: [u8; 64] = base64::engine::general_purpose::STANDARD
+        .decode(signature.trim())
+        .ok()
+        .and_then(|s| s.try_into().ok())
+        .ok_or_else(|| format!("{}签名格式错误", what))?;
+    if !ed25519::verify(&key, data, &signature) {
+        return Err(format!("{}签名校验失败，可能已被篡改", what));
+    }
+    Ok(())
+}
+
+// ==================== 清单 ====================
+
+async fn manifest_urls() -> Vec<String> {
+    let update = crate::settings::get().update;
+    let path = format!("/{}/latest.json", update.channel.as_str());
+    match update.mirror_url.filter(|m| !m.trim().is_empty()) {
+        Some(base) => vec![format!("{}{}", base.trim_end_matches('/'), path)],
+        None => crate::mirrors::server_urls(None, &format!("/api/updates{}", path)).await,
+    }
+}
+
+fn parse_version(v: &str) -> Result<semver::Version, String> {
+    semver::Version::parse(v.trim_start_matches('v'))
+        .map_err(|e| format!("版本号无效 {}: {}", v, e))
+}
+
+/// 有对应当前版本的差分包时优先使用，否则下载完整安装包
+fn choose_package<'a>(manifest: &'a UpdateManifest, current: &str) -> (&'a UpdatePackage, bool) {
+    match manifest.deltas.iter().find(|d| d.from_version == current) {
+        Some(delta) => (&delta.package, true),
+        None => (&manifest.full, false),
+    }
+}
+
+async fn fetch_manifest() -> Result<(reqwest::Url, UpdateManifest), String> {
+    let (url, response) = crate::mirrors::get_with(&manifest_urls().await, |r| {
+        r.timeout(Duration::from_secs(15))
+    })
+    .await
+    .map_err(|e| format!("检查更新失败: {}", e))?;
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| format!("检查更新失败: {}", e))?;
+
+    // 清单旁的 latest.json.sig 是对清单原始字节的签名，镜像无法伪造版本或安装包
+    let sig_url = url
+        .join("latest.json.sig")
+        .map_err(|e| format!("签名地址无效: {}", e))?;
+    let (_, sig_response) = crate::mirrors::get_with(&[sig_url.to_string()], |r| {
+        r.timeout(Duration::from_secs(15))
+    })
+    .await
+    .map_err(|e| format!("获取更新清单签名失败: {}", e))?;
+    let signature = sig_response
+        .text()
+        .await
+        .map_err(|e| format!("获取更新清单签名失败: {}", e))?;
+    verify_signature(&body, &signature, "更新清单")?;
+
+    let manifest = serde_json::from_slice::<UpdateManifest>(&body)
+        .map_err(|e| format!("更新清单格式错误: {}", e))?;
+    Ok((url, manifest))
+}
+
+fn build_info(app: &AppHandle, manifest: &UpdateManifest) -> Result<UpdateInfo, String> {
+    let current = app.package_info().version.to_string();
+    let available = parse_version(&manifest.version)? > parse_version(&current)?;
+    let (package, delta) = choose_package(manifest, &current);
+    Ok(UpdateInfo {
+        version: manifest.version.clone(),
+        channel: crate::settings::get().update.channel,
+        notes: manifest.notes.clone(),
+        pub_date: manifest.pub_date.clone(),
+        available,
+        delta,
+        download_size: if available { package.size } else { 0 },
+        current_version: current,
+    })
+}
+
+// ==================== 下载 ====================
+
+fn updates_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| format!("无法获取缓存目录: {}", e))?
+        .join("updates");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建更新目录: {}", e))?;
+    Ok(dir)
+}
+
+fn file_sha256(path: &Path) -> Result<String, String> {
+    let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+    Ok(crate::signing::hex(&Sha256::digest(&bytes)))
+}
+
+fn verify_package_file(path: &Path, signature: &str) -> Result<(), String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("无法读取安装包: {}", e))?;
+    verify_signature(&bytes, signature, "安装包")
+}
+
+/// 断点续传下载到 `.part` 文件，完成并校验后改名；取消时保留 `.part` 供下次续传
+async fn download_package(
+    task: &TaskHandle,
+    url: &reqwest::Url,
+    package: &UpdatePackage,
+    target: &Path,
+) -> Result<(), String> {
+    if target.exists()
+        && file_sha256(target)?.eq_ignore_ascii_case(&package.sha256)
+        && verify_package_file(target, &package.signature).is_ok()
+    {
+        return Ok(());
+    }
+    let part = target.with_extension("part");
+    let existing = std::fs::metadata(&part).map(|m| m.len()).unwrap_or(0);
+
+    // 相对地址已落在所选镜像上；GitHub 等外部地址再按镜像设置选择代理
+    let urls = crate::mirrors::url_candidates(url.as_str()).await;
+    let (_, mut response) = crate::mirrors::get_with(&urls, |request| {
+        if existing > 0 && existing < package.size {
+            request.header(reqwest::header::RANGE, format!("bytes={}-", existing))
+        } else {
+            request
+        }
+    })
+    .await
+    .map_err(|e| format!("下载更新失败: {}", e))?;
+
+    // 服务端不支持 Range 时从头下载
+    let resume = response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resume)
+        .truncate(!resume)
+        .open(&part)
+        .map_err(|e| format!("无法写入更新文件: {}", e))?;
+    let mut downloaded = if resume { existing } else { 0 };
+
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("下载中断（再次下载会从断点继续）: {}", e))?
+    {
+        task.check()?;
+        file.write_all(&chunk).map_err(|e| e.to_string())?;
+        downloaded += chunk.len() as u64;
+        task.progress(downloaded, package.size, "");
+    }
+    drop(file);
+
+    if !file_sha256(&part)?.eq_ignore_ascii_case(&package.sha256) {
+        let _ = std::fs::remove_file(&part);
+        return Err("更新文件校验失败，已删除，请重新下载".to_string());
+    }
+    if let Err(e) = verify_package_file(&part, &package.signature) {
+        let _ = std::fs::remove_file(&part);
+        return Err(format!("{}，已删除", e));
+    }
+    std::fs::rename(&part, target).map_err(|e| e.to_string())
+}
+
+// ==================== 安装前处理 ====================
+
+/// 结束正在进行的监测并等待会话保存；会话库锁定时未保存的会话会随重启丢失，拒绝安装
+async fn preserve_captures(app: &AppHandle) -> Result<(), String> {
+    if crate::fps_monitor::get_fps_status()?.running {
+        crate::fps_monitor::stop_capture(app, "安装更新");
+        // 可能同时在监测多个进程，等待全部保存
+        let finished = tokio::time::timeout(FINALIZE_TIMEOUT, async {
+            while crate::fps_monitor::get_fps_status().is_ok_and(|s| s.running) {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+            }
+        })
+        .await;
+        if finished.is_err() {
+            return Err("等待当前监测保存超时，请手动停止监测后再安装更新".to_string());
+        }
+        log::info!("安装更新前已结束并保存当前监测");
+    }
+
+    let store = crate::storage::get_session_store_status(app.clone())?;
+    if store.pending > 0 {
+        return Err(format!(
+            "会话库已锁定，有 {} 条新会话尚未保存。请先解锁会话库再安装更新",
+            store.pending
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn launch_installer(path: &Path) -> Result<(), String> {
+    // NSIS: /P 被动模式（只显示进度），/R 安装完成后重新启动应用
+    std::process::Command::new(path)
+        .args(["/P", "/R"])
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("无法启动安装程序: {}", e))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn launch_installer(_path: &Path) -> Result<(), String> {
+    Err("仅支持 Windows".to_string())
+}
+
+// ==================== 自动检查 ====================
+
+/// 启动后在后台检查一次更新，有新版本时发送 `update-available`
+pub fn start(app: &AppHandle) {
+    if !crate::settings::get().update.auto_check || crate::connectivity::is_offline() {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(STARTUP_CHECK_DELAY).await;
+        match fetch_manifest()
+            .await
+            .and_then(|(_, manifest)| build_info(&app, &manifest))
+        {
+            Ok(info) if info.available => {
+                log::info!("发现新版本 {} ({})", info.version, info.channel.as_str());
+                let _ = app.emit("update-available", &info);
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("{}", e),
+        }
+    });
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 按设置中的更新通道检查新版本
+#[tauri::command]
+pub async fn check_for_update(app: AppHandle) -> Result<UpdateInfo, String> {
+    crate::connectivity::ensure_online()?;
+    let (_, manifest) = fetch_manifest().await?;
+    build_info(&app, &manifest)
+}
+
+/// 下载并校验更新（支持断点续传）
+async fn download(app: AppHandle, task: TaskHandle) -> Result<UpdateInfo, String> {
+    let (manifest_url, manifest) = fetch_manifest().await?;
+    let info = build_info(&app, &manifest)?;
+    if !info.available {
+        return Err(format!("当前已是最新版本 {}", info.current_version));
+    }
+
+    let (package, delta) = choose_package(&manifest, &info.current_version);
+    let url = manifest_url
+        .join(&package.url)
+        .map_err(|e| format!("安装包地址无效: {}", e))?;
+    let target = updates_dir(&app)?.join(format!(
+        "gamebench-{}{}-setup.exe",
+        manifest.version,
+        if delta { "-delta" } else { "" }
+    ));
+    download_package(&task, &url, package, &target).await?;
+
+    log::info!("更新 {} 已下载: {}", manifest.version, target.display());
+    *prepared().lock().unwrap() = Some(PreparedUpdate {
+        version: manifest.version.clone(),
+        path: target,
+        signature: package.signature.clone(),
+    });
+    Ok(info)
+}
+
+/// 后台下载更新，返回任务 ID；任务结果为 UpdateInfo
+#[tauri::command]
+pub async fn download_update(app: AppHandle) -> Result<String, String> {
+    crate::kiosk::ensure_unlocked()?;
+    crate::connectivity::ensure_online()?;
+    let worker = app.clone();
+    tasks::spawn_async(&app, TaskKind::Download, "下载更新", move |task| {
+        download(worker, task)
+    })
+}
+
+/// 安装已下载的更新：先保存正在进行的监测，然后启动安装程序并退出
+#[tauri::command]
+pub async fn install_update(app: AppHandle) -> Result<(), String> {
+    crate::kiosk::ensure_unlocked()?;
+    let (version, path, signature) = prepared()
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|p| (p.version.clone(), p.path.clone(), p.signature.clone()))
+        .ok_or_else(|| "请先下载更新".to_string())?;
+
+    // 下载后安装包可能被替换，启动前再校验一次
+    verify_package_file(&path, &signature)?;
+    preserve_captures(&app).await?;
+    launch_installer(&path)?;
+    log::info!("正在安装更新 {}，应用即将退出", version);
+    app.exit(0);
+    Ok(())
+}
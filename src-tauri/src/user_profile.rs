@@ -0,0 +1,236 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// 本机用户 profile 列表
+const REGISTRY_FILE: &str = "profiles.json";
+/// 非默认 profile 的数据目录
+const PROFILES_DIR: &str = "profiles";
+/// 默认 profile 直接使用数据目录根目录（兼容单用户时期的数据）
+pub const DEFAULT_PROFILE_ID: &str = "default";
+
+// ==================== 数据结构 ====================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserProfile {
+    pub id: String,
+    /// 显示名称 (e.g., "小明", "3 号机")
+    pub name: String,
+    /// 创建时间 (unix 秒)
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileRegistry {
+    pub profiles: Vec<UserProfile>,
+    /// 上次使用的 profile
+    pub last_active: String,
+    /// 启动时让用户选择 profile（网吧 / 家庭共用电脑）
+    pub ask_on_startup: bool,
+}
+
+impl Default for ProfileRegistry {
+    fn default() -> Self {
+        ProfileRegistry {
+            profiles: vec![UserProfile {
+                id: DEFAULT_PROFILE_ID.to_string(),
+                name: "默认".to_string(),
+                created_at: 0,
+            }],
+            last_active: DEFAULT_PROFILE_ID.to_string(),
+            ask_on_startup: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileList {
+    pub registry: ProfileRegistry,
+    /// 当前生效的 profile
+    pub active: String,
+}
+
+// ==================== 读写 ====================
+
+fn active() -> &'static Mutex<String> {
+    static ACTIVE: OnceLock<Mutex<String>> = OnceLock::new();
+    ACTIVE.get_or_init(|| Mutex::new(DEFAULT_PROFILE_ID.to_string()))
+}
+
+fn root_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("无法获取数据目录: {}", e))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建数据目录: {}", e))?;
+    Ok(dir)
+}
+
+/// 指定 profile 的数据目录（设置、会话记录）
+pub fn profile_dir(app: &AppHandle, id: &str) -> Result<PathBuf, String> {
+    let root = root_dir(app)?;
+    if id == DEFAULT_PROFILE_ID {
+        return Ok(root);
+    }
+    if !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(format!("无效的 profile ID: {}", id));
+    }
+    let dir = root.join(PROFILES_DIR).join(id);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建 profile 目录: {}", e))?;
+    Ok(dir)
+}
+
+/// 当前 profile 的数据目录
+pub fn data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let id = active().lock().unwrap().clone();
+    profile_dir(app, &id)
+}
+
+fn load_registry(app: &AppHandle) -> ProfileRegistry {
+    root_dir(app)
+        .ok()
+        .and_then(|dir| std::fs::read_to_string(dir.join(REGISTRY_FILE)).ok())
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn save_registry(app: &AppHandle, registry: &ProfileRegistry) -> Result<(), String> {
+    let path = root_dir(app)?.join(REGISTRY_FILE);
+    let text = serde_json::to_string_pretty(registry).map_err(|e| e.to_string())?;
+    std::fs::write(&path, text).map_err(|e| format!("保存 profile 列表失败: {}", e))
+}
+
+/// 启动时恢复上次使用的 profile（需在加载设置之前调用）
+pub fn load(app: &AppHandle) {
+    let registry = load_registry(app);
+    let id = if registry
+        .profiles
+        .iter()
+        .any(|p| p.id == registry.last_active)
+    {
+        registry.last_active
+    } else {
+        DEFAULT_PROFILE_ID.to_string()
+    };
+    *active().lock().unwrap() = id;
+}
+
+// ==================== Tauri 命令 ====================
+
+#[tauri::command]
+pub fn list_user_profiles(app: AppHandle) -> Result<ProfileList, String> {
+    Ok(ProfileList {
+        registry: load_registry(&app),
+        active: active().lock().unwrap().clone(),
+    })
+}
+
+#[tauri::command]
+pub fn create_user_profile(app: AppHandle, name: String) -> Result<UserProfile, String> {
+    crate::kiosk::ensure_unlocked()?;
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("名称不能为空".to_string());
+    }
+    let mut registry = load_registry(&app);
+    if registry.profiles.iter().any(|p| p.name == name) {
+        return Err(format!("已存在名为「{}」的 profile", name));
+    }
+
+    let now = chrono::Utc::now();
+    let profile = UserProfile {
+        id: format!("p{}", now.timestamp_millis()),
+        name,
+        created_at: now.timestamp(),
+    };
+    profile_dir(&app, &profile.id)?;
+    registry.profiles.push(profile.clone());
+    save_registry(&app, &registry)?;
+    log::info!("已创建 profile: {} ({})", profile.name, profile.id);
+    Ok(profile)
+}
+
+/// 切换 profile：重新加载该 profile 的设置，并锁定会话库
+#[tauri::command]
+pub fn switch_user_profile(app: AppHandle, id: String) -> Result<(), String> {
+    crate::kiosk::ensure_unlocked()?;
+    let mut registry = load_registry(&app);
+    let profile = registry
+        .profiles
+        .iter()
+        .find(|p| p.id == id)
+        .cloned()
+        .ok_or_else(|| format!("未找到 profile: {}", id))?;
+
+    *active().lock().unwrap() = id.clone();
+    crate::settings::load(&app);
+    crate::storage::lock_session_store()?;
+
+    registry.last_active = id;
+    save_registry(&app, &registry)?;
+    log::info!("已切换到 profile: {}", profile.name);
+    let _ = app.emit("profile-switched", &profile);
+    Ok(())
+}
+
+/// 删除 profile 及其全部设置和会话记录（不能删除默认 profile 或当前 profile）
+#[tauri::command]
+pub fn delete_user_profile(app: AppHandle, id: String) -> Result<(), String> {
+    crate::kiosk::ensure_unlocked()?;
+    if id == DEFAULT_PROFILE_ID {
+        return Err("不能删除默认 profile".to_string());
+    }
+    if *active().lock().unwrap() == id {
+        return Err("不能删除正在使用的 profile，请先切换到其他 profile".to_string());
+    }
+    let mut registry = load_registry(&app);
+    let before = registry.profiles.len();
+    registry.profiles.retain(|p| p.id != id);
+    if registry.profiles.len() == before {
+        return Err(format!("未找到 profile: {}", id));
+    }
+
+    let dir = profile_dir(&app, &id)?;
+    std::fs::remove_dir_all(&dir).map_err(|e| format!("删除 profile 数据失败: {}", e))?;
+    save_registry(&app, &registry)?;
+    log::info!("已删除 profile {}", id);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_profile_prompt_on_startup(app: AppHandle, enabled: bool) -> Result<(), String> {
+    crate::kiosk::ensure_unlocked()?;
+    let mut registry = load_registry(&app);
+    registry.ask_on_startup = enabled;
+    save_registry(&app, &registry)
+}
+
+/// 将会话记录迁移到另一个 profile，返回迁移的会话数
+#[tauri::command]
+pub fn migrate_sessions(
+    app: AppHandle,
+    session_ids: Vec<String>,
+    from: String,
+    to: String,
+) -> Result<usize, String> {
+    crate::kiosk::ensure_unlocked()?;
+    if from == to {
+        return Err("源 profile 与目标 profile 相同".to_string());
+    }
+    let registry = load_registry(&app);
+    for id in [&from, &to] {
+        if !registry.profiles.iter().any(|p| &p.id == id) {
+            return Err(format!("未找到 profile: {}", id));
+        }
+    }
+
+    let moved = crate::storage::move_sessions(
+        &profile_dir(&app, &from)?,
+        &profile_dir(&app, &to)?,
+        &session_ids,
+    )?;
+    log::info!("已迁移 {} 个会话: {} → {}", moved, from, to);
+    Ok(moved)
+}
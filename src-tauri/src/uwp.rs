@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+
+// ==================== 数据结构 ====================
+
+/// UWP / Xbox Game Pass 应用包
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageInfo {
+    /// 包全名 (e.g., "Microsoft.624F8B84B80_3.2.1.0_x64__8wekyb3d8bbwe")
+    pub full_name: String,
+    /// 显示名称 (e.g., "Forza Horizon 5")
+    pub display_name: String,
+    /// 安装目录
+    pub install_dir: Option<String>,
+    /// 是否为游戏（包含 MicrosoftGame.config）
+    pub is_game: bool,
+}
+
+/// 包元数据所在的注册表位置（HKCU）
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+const PACKAGES_KEY: &str = r"Software\Classes\Local Settings\Software\Microsoft\Windows\CurrentVersion\AppModel\Repository\Packages";
+
+/// Game Pass 游戏包根目录中的游戏配置文件
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+const GAME_CONFIG_FILE: &str = "MicrosoftGame.config";
+
+/// 进程路径位于包安装目录时才需要查询包信息
+pub fn is_packaged_path(exe_path: &str) -> bool {
+    let lower = exe_path.to_lowercase();
+    lower.contains("\\windowsapps\\") || lower.contains("\\xboxgames\\")
+}
+
+// ==================== 包查询 (Windows) ====================
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::{PackageInfo, GAME_CONFIG_FILE, PACKAGES_KEY};
+    use windows::core::{HSTRING, PWSTR};
+    use windows::Win32::Foundation::{CloseHandle, ERROR_SUCCESS};
+    use windows::Win32::Storage::Packaging::Appx::GetPackageFullName;
+    use windows::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+    use windows::Win32::UI::Shell::SHLoadIndirectString;
+    use winreg::enums::HKEY_CURRENT_USER;
+    use winreg::RegKey;
+
+    /// 显示名称常为 "@{Package?ms-resource://...}" 形式的资源引用，需要解析
+    fn resolve_display_name(raw: &str) -> Option<String> {
+        if !raw.starts_with('@') {
+            return Some(raw.to_string());
+        }
+        let mut buffer = [0u16; 512];
+        unsafe { SHLoadIndirectString(&HSTRING::from(raw), &mut buffer, None).ok()? };
+        let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        let name = String::from_utf16_lossy(&buffer[..len]);
+        (!name.is_empty()).then_some(name)
+    }
+
+    fn read_package(packages: &RegKey, full_name: &str) -> Option<PackageInfo> {
+        let key = packages.open_subkey(full_name).ok()?;
+        let raw: String = key.get_value("DisplayName").unwrap_or_default();
+        let install_dir: Option<String> = key.get_value("PackageRootFolder").ok();
+        let is_game = install_dir
+            .as_ref()
+            .map(|dir| std::path::Path::new(dir).join(GAME_CONFIG_FILE).exists())
+            .unwrap_or(false);
+
+        Some(PackageInfo {
+            full_name: full_name.to_string(),
+            // 资源解析失败时退回包名
+            display_name: resolve_display_name(&raw)
+                .unwrap_or_else(|| full_name.split('_').next().unwrap_or(full_name).to_string()),
+            install_dir,
+            is_game,
+        })
+    }
+
+    /// 获取进程所属的应用包全名
+    fn package_full_name(pid: u32) -> Option<String> {
+        unsafe {
+            let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+            let mut buffer = [0u16; 256];
+            let mut len = buffer.len() as u32;
+            let result = GetPackageFullName(handle, &mut len, PWSTR(buffer.as_mut_ptr()));
+            let _ = CloseHandle(handle);
+            if result != ERROR_SUCCESS || len == 0 {
+                return None;
+            }
+            // 返回长度包含结尾的 \0
+            Some(String::from_utf16_lossy(&buffer[..(len as usize - 1)]))
+        }
+    }
+
+    pub fn package_for_pid(pid: u32) -> Option<PackageInfo> {
+        let full_name = package_full_name(pid)?;
+        let packages = RegKey::predef(HKEY_CURRENT_USER)
+            .open_subkey(PACKAGES_KEY)
+            .ok()?;
+        read_package(&packages, &full_name)
+    }
+
+    pub fn list_packages() -> Vec<PackageInfo> {
+        let Ok(packages) = RegKey::predef(HKEY_CURRENT_USER).open_subkey(PACKAGES_KEY) else {
+            return vec![];
+        };
+        packages
+            .enum_keys()
+            .flatten()
+            .filter_map(|name| read_package(&packages, &name))
+            .collect()
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    use super::PackageInfo;
+
+    pub fn package_for_pid(_pid: u32) -> Option<PackageInfo> {
+        None
+    }
+
+    pub fn list_packages() -> Vec<PackageInfo> {
+        vec![]
+    }
+}
+
+pub use platform::package_for_pid;
+
+// ==================== Tauri 命令 ====================
+
+/// 列出已安装的 UWP / Game Pass 游戏
+#[tauri::command]
+pub fn list_packaged_games() -> Result<Vec<PackageInfo>, String> {
+    let mut games: Vec<PackageInfo> = platform::list_packages()
+        .into_iter()
+        .filter(|p| p.is_game)
+        .collect();
+    games.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+    Ok(games)
+}
@@ -0,0 +1,11 @@
+pub use gamebench_core::vram_health::{
+    merge, read_vram_counters, VramErrorCounters, VramSampler, VramStabilityReport,
+};
+
+// ==================== Tauri 命令 ====================
+
+/// 读取 NVIDIA 显卡当前的显存纠错计数（驱动加载以来）
+#[tauri::command]
+pub fn get_vram_error_counters() -> Result<Vec<VramErrorCounters>, String> {
+    Ok(read_vram_counters())
+}
@@ -3,19 +3,50 @@ import Dashboard from "./pages/Dashboard";
 import FpsMonitor from "./pages/FpsMonitor";
 import Settings from "./pages/Settings";
 import Logs from "./pages/Logs";
-import { Monitor, Gauge, Settings as SettingsIcon, Gamepad2, FileText } from "lucide-react";
-import { detectHardware, scanRunningGames, onGameDetected, onGameExited } from "./lib/tauri-api";
-import type { HardwareInfo, DetectedGame } from "./lib/types";
+import LanBench from "./pages/LanBench";
+import History from "./pages/History";
+import {
+  Monitor, Gauge, Settings as SettingsIcon, Gamepad2, FileText, Lock, Network, Search,
+  History as HistoryIcon,
+} from "lucide-react";
+import {
+  detectHardware,
+  scanRunningGames,
+  onGameDetected,
+  onGameExited,
+  onHardwareUpdated,
+  listUserProfiles,
+  switchUserProfile,
+  onProfileSwitched,
+  getKioskStatus,
+  exitKiosk,
+  onKioskUpdated,
+  onUpdateAvailable,
+  search,
+  listTasks,
+  cancelTask,
+  onTaskStarted,
+  onTaskProgress,
+  onTaskFinished,
+} from "./lib/tauri-api";
+import type {
+  HardwareInfo, DetectedGame, ProfileList, KioskStatus, SearchHit, TaskInfo,
+} from "./lib/types";
 
-type Page = "dashboard" | "fps" | "settings" | "logs";
+type Page = "dashboard" | "fps" | "history" | "lan" | "settings" | "logs";
 
 const NAV_ITEMS: { id: Page; label: string; icon: React.ReactNode }[] = [
   { id: "dashboard", label: "硬件概览", icon: <Monitor size={20} /> },
   { id: "fps", label: "FPS 监测", icon: <Gauge size={20} /> },
+  { id: "history", label: "历史记录", icon: <HistoryIcon size={20} /> },
+  { id: "lan", label: "多机测试", icon: <Network size={20} /> },
   { id: "logs", label: "运行日志", icon: <FileText size={20} /> },
   { id: "settings", label: "设置", icon: <SettingsIcon size={20} /> },
 ];
 
+/** 网吧模式下只保留测试与结果页 */
+const KIOSK_PAGES: Page[] = ["fps"];
+
 export default function App() {
   const [page, setPage] = useState<Page>("dashboard");
 
@@ -24,6 +55,16 @@ export default function App() {
   const [hardwareLoading, setHardwareLoading] = useState(true);
   const [hardwareError, setHardwareError] = useState<string | null>(null);
   const [runningGames, setRunningGames] = useState<DetectedGame[]>([]);
+  const [profiles, setProfiles] = useState<ProfileList | null>(null);
+  // 共用电脑：启动时先选择 profile
+  const [choosingProfile, setChoosingProfile] = useState(false);
+  const [kiosk, setKiosk] = useState<KioskStatus | null>(null);
+  const [exitingKiosk, setExitingKiosk] = useState(false);
+  const [kioskPin, setKioskPin] = useState("");
+  const [kioskError, setKioskError] = useState<string | null>(null);
+  const [updateVersion, setUpdateVersion] = useState<string | null>(null);
+  const kioskMode = kiosk?.enabled ?? false;
+  const navItems = kioskMode ? NAV_ITEMS.filter((item) => KIOSK_PAGES.includes(item.id)) : NAV_ITEMS;
 
   // 加载硬件信息
   const loadHardware = useCallback(async () => {
@@ -53,25 +94,62 @@ export default function App() {
   useEffect(() => {
     loadHardware();
     loadGames();
+    Promise.all([listUserProfiles(), getKioskStatus()])
+      .then(([list, status]) => {
+        setProfiles(list);
+        setKiosk(status);
+        // 网吧模式下不允许切换用户
+        setChoosingProfile(
+          !status.enabled && list.registry.ask_on_startup && list.registry.profiles.length > 1
+        );
+      })
+      .catch(() => {});
 
     // 监听游戏启动/退出事件
     const unsub1 = onGameDetected((game) => {
       setRunningGames((prev) => [...prev, game]);
     });
-    const unsub2 = onGameExited((name) => {
-      setRunningGames((prev) => prev.filter((g) => g.process_name !== name));
+    const unsub2 = onGameExited((exited) => {
+      setRunningGames((prev) => prev.filter((g) => g.pid !== exited.pid));
+    });
+    const unsub3 = onHardwareUpdated(setHardware);
+    const unsub4 = onProfileSwitched(() => {
+      listUserProfiles().then(setProfiles).catch(() => {});
     });
 
+    const unsub5 = onKioskUpdated(setKiosk);
+    const unsub6 = onUpdateAvailable((info) => setUpdateVersion(info.version));
+
     // 定期刷新游戏列表
     const interval = setInterval(loadGames, 10000);
 
     return () => {
       unsub1.then((fn) => fn());
       unsub2.then((fn) => fn());
+      unsub3.then((fn) => fn());
+      unsub4.then((fn) => fn());
+      unsub5.then((fn) => fn());
+      unsub6.then((fn) => fn());
       clearInterval(interval);
     };
   }, [loadHardware, loadGames]);
 
+  // 进入网吧模式时跳转到测试页
+  useEffect(() => {
+    if (kioskMode && !KIOSK_PAGES.includes(page)) setPage("fps");
+  }, [kioskMode, page]);
+
+  const handleExitKiosk = async () => {
+    setKioskError(null);
+    try {
+      await exitKiosk(kioskPin);
+      setExitingKiosk(false);
+      setKioskPin("");
+    } catch (e) {
+      setKioskError(String(e));
+    }
+  };
+
   return (
     <div className="flex h-screen bg-surface">
       {/* 侧边栏 */}
@@ -88,9 +166,11 @@ export default function App() {
           </div>
         </div>
 
+        {!kioskMode && <SearchBox onOpen={(hit) => setPage(hit.kind === "session" ? "history" : "fps")} />}
+
         {/* 导航 */}
         <nav className="flex-1 py-3 px-3 space-y-1">
-          {NAV_ITEMS.map((item) => (
+          {navItems.map((item) => (
             <button
               key={item.id}
               onClick={() => setPage(item.id)}
@@ -106,8 +186,34 @@ export default function App() {
           ))}
         </nav>
 
+        <BackgroundTasks />
+
         {/* 版本信息 */}
         <div className="px-5 py-3 border-t border-border text-[10px] text-slate-600">
+          {kioskMode && (
+            <button
+              onClick={() => setExitingKiosk(true)}
+              className="flex items-center gap-1 mb-1 text-slate-400 hover:text-white"
+            >
+              <Lock size={10} /> 网吧模式 · 退出
+            </button>
+          )}
+          {!kioskMode && profiles && profiles.registry.profiles.length > 1 && (
+            <button
+              onClick={() => setChoosingProfile(true)}
+              className="block mb-1 text-slate-400 hover:text-white"
+            >
+              用户: {profiles.registry.profiles.find((p) => p.id === profiles.active)?.name}
+            </button>
+          )}
+          {!kioskMode && updateVersion && (
+            <button
+              onClick={() => setPage("settings")}
+              className="block mb-1 text-brand-400 hover:underline"
+            >
+              新版本 {updateVersion} 可用
+            </button>
+          )}
           v0.1.1 · PresentMon 2.x
         </div>
       </aside>
@@ -124,9 +230,185 @@ export default function App() {
           />
         )}
         {page === "fps" && <FpsMonitor />}
+        {page === "history" && <History />}
+        {page === "lan" && <LanBench />}
         {page === "logs" && <Logs />}
         {page === "settings" && <Settings />}
       </main>
+
+      {exitingKiosk && (
+        <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/60">
+          <div className="w-80 p-5 rounded-xl bg-surface-card border border-border space-y-3">
+            <div className="text-sm font-semibold text-white">退出网吧模式</div>
+            <input
+              type="password"
+              value={kioskPin}
+              onChange={(e) => setKioskPin(e.target.value)}
+              onKeyDown={(e) => e.key === "Enter" && handleExitKiosk()}
+              placeholder="管理员 PIN"
+              className="w-full px-3 py-2 text-sm rounded-lg bg-surface border border-border text-white"
+              autoFocus
+            />
+            {kioskError && <div className="text-xs text-red-400">{kioskError}</div>}
+            <div className="flex justify-end gap-2">
+              <button
+                onClick={() => {
+                  setExitingKiosk(false);
+                  setKioskPin("");
+                  setKioskError(null);
+                }}
+                className="px-3 py-1.5 text-xs text-slate-400 hover:text-white"
+              >
+                取消
+              </button>
+              <button
+                onClick={handleExitKiosk}
+                className="px-3 py-1.5 text-xs rounded-lg bg-brand-600 text-white hover:bg-brand-500"
+              >
+                退出
+              </button>
+            </div>
+          </div>
+        </div>
+      )}
+
+      {choosingProfile && profiles && (
+        <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/60">
+          <div className="w-80 p-5 rounded-xl bg-surface-card border border-border space-y-2">
+            <div className="text-sm font-semibold text-white mb-3">选择用户</div>
+            {profiles.registry.profiles.map((p) => (
+              <button
+                key={p.id}
+                onClick={async () => {
+                  if (p.id !== profiles.active) {
+                    await switchUserProfile(p.id).catch(() => {});
+                  }
+                  setChoosingProfile(false);
+                }}
+                className={`w-full px-3 py-2 text-sm text-left rounded-lg border border-border ${
+                  p.id === profiles.active ? "text-brand-400" : "text-slate-300 hover:text-white"
+                }`}
+              >
+                {p.name}
+              </button>
+            ))}
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
+
+/** 侧边栏底部的后台任务列表（下载、导入、导出、维护等） */
+function BackgroundTasks() {
+  const [tasks, setTasks] = useState<TaskInfo[]>([]);
+
+  useEffect(() => {
+    const refresh = () => listTasks().then(setTasks).catch(() => {});
+    refresh();
+    const unsub1 = onTaskStarted(refresh);
+    const unsub2 = onTaskFinished(refresh);
+    const unsub3 = onTaskProgress((p) => {
+      setTasks((prev) =>
+        prev.map((t) =>
+          t.task_id === p.task_id ? { ...t, done: p.done, total: p.total, message: p.message } : t
+        )
+      );
+    });
+    return () => {
+      unsub1.then((fn) => fn());
+      unsub2.then((fn) => fn());
+      unsub3.then((fn) => fn());
+    };
+  }, []);
+
+  if (tasks.length === 0) return null;
+
+  return (
+    <div className="px-5 py-2 border-t border-border space-y-1.5">
+      {tasks.map((t) => (
+        <div key={t.task_id} className="text-[10px]">
+          <div className="flex items-center justify-between gap-2 text-slate-400">
+            <span className="truncate">{t.name}</span>
+            {t.cancellable && !t.cancel_requested && (
+              <button
+                onClick={() =>
+                  cancelTask(t.task_id)
+                    .then(() => listTasks().then(setTasks))
+                    .catch(() => {})
+                }
+                className="text-slate-500 hover:text-white"
+              >
+                取消
+              </button>
+            )}
+            {t.cancel_requested && <span className="text-slate-600">正在取消</span>}
+          </div>
+          <div className="h-1 mt-0.5 rounded bg-surface overflow-hidden">
+            <div
+              className="h-full bg-brand-600"
+              style={{ width: t.total > 0 ? `${Math.min(100, (t.done / t.total) * 100)}%` : "0%" }}
+            />
+          </div>
+        </div>
+      ))}
+    </div>
+  );
+}
+
+const SEARCH_KIND_LABELS: Record<SearchHit["kind"], string> = {
+  known_game: "游戏",
+  installed_game: "已安装",
+  session: "记录",
+};
+
+/** 侧边栏搜索：匹配在后端完成，前端只拿前几条结果 */
+function SearchBox({ onOpen }: { onOpen: (hit: SearchHit) => void }) {
+  const [query, setQuery] = useState("");
+  const [hits, setHits] = useState<SearchHit[]>([]);
+
+  useEffect(() => {
+    if (!query.trim()) {
+      setHits([]);
+      return;
+    }
+    const timer = setTimeout(() => {
+      search(query, 8).then(setHits).catch(() => setHits([]));
+    }, 150);
+    return () => clearTimeout(timer);
+  }, [query]);
+
+  return (
+    <div className="relative px-3 pt-3">
+      <div className="flex items-center gap-2 px-2.5 py-1.5 rounded-lg bg-surface border border-border">
+        <Search size={14} className="text-slate-500" />
+        <input
+          value={query}
+          onChange={(e) => setQuery(e.target.value)}
+          placeholder="搜索游戏 / 记录（支持拼音）"
+          className="w-full bg-transparent text-xs text-white outline-none placeholder:text-slate-600"
+        />
+      </div>
+      {hits.length > 0 && (
+        <div className="absolute left-3 right-3 z-40 mt-1 py-1 rounded-lg bg-surface-card border border-border shadow-lg">
+          {hits.map((hit) => (
+            <button
+              key={`${hit.kind}-${hit.session_id ?? hit.process_name ?? hit.title}`}
+              onClick={() => {
+                setQuery("");
+                onOpen(hit);
+              }}
+              className="w-full px-3 py-1.5 text-left hover:bg-surface-hover"
+            >
+              <div className="text-xs text-white truncate">
+                <span className="text-[10px] text-slate-500 mr-1">{SEARCH_KIND_LABELS[hit.kind]}</span>
+                {hit.title}
+              </div>
+              {hit.subtitle && <div className="text-[10px] text-slate-500 truncate">{hit.subtitle}</div>}
+            </button>
+          ))}
+        </div>
+      )}
     </div>
   );
 }
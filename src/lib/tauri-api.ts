@@ -9,6 +9,83 @@ import type {
   FpsSession,
   FpsStatus,
   DetectedGame,
+  StartupEntry,
+  DisableResult,
+  DriveInfo,
+  InstallReport,
+  PcieLinkStatus,
+  DriveHealth,
+  OsSettings,
+  BenchmarkProfile,
+  ApplyResult,
+  GpuRouting,
+  SessionComparison,
+  PackageInfo,
+  InstalledGame,
+  CaptureRule,
+  GameExited,
+  ProcessFilter,
+  FalsePositiveReport,
+  AppSettings,
+  SensorKind,
+  SensorReadings,
+  FormFactor,
+  OemPerfMode,
+  CaptureProfile,
+  StoreStatus,
+  PrivacyOptions,
+  ProfileList,
+  UserProfile,
+  KioskStatus,
+  LanAgent,
+  AgentInfo,
+  LanRun,
+  LanRunReport,
+  SignedReport,
+  SignatureCheck,
+  ImportResult,
+  KnownGameName,
+  SearchHit,
+  SessionFilter,
+  SessionSort,
+  PageRequest,
+  SessionPage,
+  BulkResult,
+  BackupSummary,
+  UpdateInfo,
+  MirrorStatus,
+  SystemProxy,
+  ConnectivityReport,
+  PluginInfo,
+  ScriptHook,
+  HookRun,
+  SessionMarker,
+  ClockEstimate,
+  DriverReset,
+  RecoveredSession,
+  BusyPolicy,
+  QueuedCapture,
+  StartOutcome,
+  CapturePhase,
+  PhaseChange,
+  CaptureEvent,
+  SessionTimeline,
+  ExportFormat,
+  FrameStatsReport,
+  MultiSessionComparison,
+  FpsStutter,
+  TaskProgress,
+  TaskFinished,
+  MonitorOptions,
+  TaskInfo,
+  BenchmarkCountdown,
+  BenchmarkPlan,
+  BenchmarkPreset,
+  HotkeyStatus,
+  HotkeyEvent,
+  LoadlessReport,
+  PairedComparison,
+  VramErrorCounters,
 } from "./types";
 
 // ==================== 硬件检测 ====================
@@ -17,6 +94,15 @@ export async function detectHardware(): Promise<HardwareInfo> {
   return invoke<HardwareInfo>("detect_hardware");
 }
 
+/** 较慢的探测项完成后推送完整硬件信息 */
+export function onHardwareUpdated(
+  callback: (info: HardwareInfo) => void
+): Promise<UnlistenFn> {
+  return listen<HardwareInfo>("hardware-updated", (event) => {
+    callback(event.payload);
+  });
+}
+
 export async function getCpuInfo(): Promise<CpuInfo> {
   return invoke<CpuInfo>("get_cpu_info");
 }
@@ -31,18 +117,72 @@ export async function getRamInfo(): Promise<RamInfo> {
 
 // ==================== FPS 监测 ====================
 
-export async function startFpsMonitor(processName: string): Promise<void> {
-  return invoke("start_fps_monitor", { processName });
+/**
+ * `profile` 未传入时使用设置中的默认采集档位
+ * `policy` 决定已有监测进行中时拒绝、排队还是替换，未传入时使用设置中的默认值
+ */
+export async function startFpsMonitor(
+  processName: string,
+  pid?: number,
+  profile?: CaptureProfile,
+  policy?: BusyPolicy,
+  options?: MonitorOptions
+): Promise<StartOutcome> {
+  return invoke<StartOutcome>("start_fps_monitor", { processName, pid, profile, policy, options });
+}
+
+/**
+ * 定时基准测试：预热后采集指定时长并自动停止
+ * `preset` 与 `plan` 至少传入一个，同时传入时以 `plan` 的时长为准
+ */
+export async function runBenchmark(
+  processName: string,
+  preset?: string,
+  plan?: BenchmarkPlan,
+  pid?: number,
+  profile?: CaptureProfile,
+  options?: MonitorOptions
+): Promise<StartOutcome> {
+  return invoke<StartOutcome>("run_benchmark", {
+    processName,
+    pid,
+    profile,
+    preset,
+    plan,
+    options,
+  });
+}
+
+export async function listBenchmarkPresets(): Promise<BenchmarkPreset[]> {
+  return invoke<BenchmarkPreset[]>("list_benchmark_presets");
+}
+
+/** `sessionId` 未传入时停止所有进行中的监测 */
+export async function stopFpsMonitor(sessionId?: string): Promise<void> {
+  return invoke("stop_fps_monitor", { sessionId });
+}
+
+/** 暂停期间 PresentMon 继续运行，帧不计入会话统计 */
+export async function pauseFpsMonitor(sessionId?: string): Promise<void> {
+  return invoke("pause_fps_monitor", { sessionId });
 }
 
-export async function stopFpsMonitor(): Promise<void> {
-  return invoke("stop_fps_monitor");
+export async function resumeFpsMonitor(sessionId?: string): Promise<void> {
+  return invoke("resume_fps_monitor", { sessionId });
 }
 
 export async function getFpsStatus(): Promise<FpsStatus> {
   return invoke<FpsStatus>("get_fps_status");
 }
 
+export async function getFpsQueue(): Promise<QueuedCapture[]> {
+  return invoke<QueuedCapture[]>("get_fps_queue");
+}
+
+export async function clearFpsQueue(): Promise<void> {
+  return invoke("clear_fps_queue");
+}
+
 // FPS 事件监听
 export function onFpsUpdate(
   callback: (snapshot: FpsSnapshot) => void
@@ -53,17 +193,33 @@ export function onFpsUpdate(
 }
 
 export function onFpsStarted(
-  callback: (processName: string) => void
+  callback: (capture: CaptureEvent) => void
 ): Promise<UnlistenFn> {
-  return listen<string>("fps-started", (event) => {
+  return listen<CaptureEvent>("fps-started", (event) => {
+    callback(event.payload);
+  });
+}
+
+/** 检测到卡顿（帧时间尖峰） */
+export function onFpsStutter(callback: (stutter: FpsStutter) => void): Promise<UnlistenFn> {
+  return listen<FpsStutter>("fps-stutter", (event) => {
+    callback(event.payload);
+  });
+}
+
+/** 监测生命周期状态切换 */
+export function onFpsPhase(
+  callback: (change: PhaseChange) => void
+): Promise<UnlistenFn> {
+  return listen<PhaseChange>("fps-phase", (event) => {
     callback(event.payload);
   });
 }
 
 export function onFpsStopped(
-  callback: (processName: string) => void
+  callback: (capture: CaptureEvent) => void
 ): Promise<UnlistenFn> {
-  return listen<string>("fps-stopped", (event) => {
+  return listen<CaptureEvent>("fps-stopped", (event) => {
     callback(event.payload);
   });
 }
@@ -76,6 +232,23 @@ export function onFpsSessionComplete(
   });
 }
 
+export function onBenchmarkCountdown(
+  callback: (countdown: BenchmarkCountdown) => void
+): Promise<UnlistenFn> {
+  return listen<BenchmarkCountdown>("benchmark-countdown", (event) => {
+    callback(event.payload);
+  });
+}
+
+/** 定时基准测试结束，附带生成的会话 */
+export function onBenchmarkComplete(
+  callback: (session: FpsSession) => void
+): Promise<UnlistenFn> {
+  return listen<FpsSession>("benchmark-complete", (event) => {
+    callback(event.payload);
+  });
+}
+
 export function onFpsError(
   callback: (error: string) => void
 ): Promise<UnlistenFn> {
@@ -90,8 +263,8 @@ export async function scanRunningGames(): Promise<DetectedGame[]> {
   return invoke<DetectedGame[]>("scan_running_games");
 }
 
-export async function getKnownGames(): Promise<[string, string][]> {
-  return invoke<[string, string][]>("get_known_games");
+export async function getKnownGames(): Promise<KnownGameName[]> {
+  return invoke<KnownGameName[]>("get_known_games");
 }
 
 // 游戏事件监听
@@ -104,9 +277,772 @@ export function onGameDetected(
 }
 
 export function onGameExited(
-  callback: (processName: string) => void
+  callback: (game: GameExited) => void
 ): Promise<UnlistenFn> {
-  return listen<string>("game-exited", (event) => {
+  return listen<GameExited>("game-exited", (event) => {
     callback(event.payload);
   });
 }
+
+// ==================== 进程管理 ====================
+
+export async function terminateProcess(
+  pid: number,
+  confirmed: boolean
+): Promise<void> {
+  return invoke("terminate_process", { pid, confirmed });
+}
+
+export async function suspendProcess(
+  pid: number,
+  confirmed: boolean
+): Promise<void> {
+  return invoke("suspend_process", { pid, confirmed });
+}
+
+export async function resumeProcess(pid: number): Promise<void> {
+  return invoke("resume_process", { pid });
+}
+
+// ==================== 启动项分析 ====================
+
+export async function listStartupItems(): Promise<StartupEntry[]> {
+  return invoke<StartupEntry[]>("list_startup_items");
+}
+
+export async function disableStartupItems(
+  ids: string[]
+): Promise<DisableResult[]> {
+  return invoke<DisableResult[]>("disable_startup_items", { ids });
+}
+
+// ==================== 存储设备 ====================
+
+export async function listDrives(): Promise<DriveInfo[]> {
+  return invoke<DriveInfo[]>("list_drives");
+}
+
+export async function getDriveHealth(): Promise<DriveHealth[]> {
+  return invoke<DriveHealth[]>("get_drive_health");
+}
+
+/** 安装位置与完整性报告；后台任务（统计占用空间较慢），可取消 */
+export async function getInstallReport(
+  pid: number,
+  session?: FpsSession,
+  onProgress?: (progress: TaskProgress) => void
+): Promise<InstallReport> {
+  return runTask<InstallReport>(
+    () => invoke<string>("get_install_report", { pid, session }),
+    onProgress
+  );
+}
+
+// ==================== PCIe 链路 ====================
+
+export async function getPcieLinkStatus(): Promise<PcieLinkStatus[]> {
+  return invoke<PcieLinkStatus[]>("get_pcie_link_status");
+}
+
+// ==================== 显存纠错 ====================
+
+export async function getVramErrorCounters(): Promise<VramErrorCounters[]> {
+  return invoke<VramErrorCounters[]>("get_vram_error_counters");
+}
+
+// ==================== 混合显卡 ====================
+
+export async function getGpuRouting(pid: number): Promise<GpuRouting> {
+  return invoke<GpuRouting>("get_gpu_routing", { pid });
+}
+
+// ==================== 测试环境 profile ====================
+
+export async function snapshotOsSettings(): Promise<OsSettings> {
+  return invoke<OsSettings>("snapshot_os_settings");
+}
+
+export async function listBenchmarkProfiles(): Promise<BenchmarkProfile[]> {
+  return invoke<BenchmarkProfile[]>("list_benchmark_profiles");
+}
+
+export async function saveBenchmarkProfile(
+  profile: BenchmarkProfile
+): Promise<void> {
+  return invoke("save_benchmark_profile", { profile });
+}
+
+export async function deleteBenchmarkProfile(name: string): Promise<void> {
+  return invoke("delete_benchmark_profile", { name });
+}
+
+export async function applyBenchmarkProfile(
+  name: string
+): Promise<ApplyResult> {
+  return invoke<ApplyResult>("apply_benchmark_profile", { name });
+}
+
+export async function restoreOsSettings(): Promise<string[]> {
+  return invoke<string[]>("restore_os_settings");
+}
+
+// ==================== 结果对比 ====================
+
+export async function compareSessions(
+  baseline: FpsSession,
+  candidate: FpsSession
+): Promise<SessionComparison> {
+  return invoke<SessionComparison>("compare_sessions", { baseline, candidate });
+}
+
+/** 基于原始帧时间对比多个会话，第一个为基准；后台任务，可取消 */
+export async function compareSessionFrames(
+  sessionIds: string[],
+  segmentSecs?: number,
+  onProgress?: (progress: TaskProgress) => void
+): Promise<MultiSessionComparison> {
+  return runTask<MultiSessionComparison>(
+    () => invoke<string>("compare_session_frames", { sessionIds, segmentSecs }),
+    onProgress
+  );
+}
+
+// ==================== 国内游戏平台 ====================
+
+export async function listLauncherGames(): Promise<InstalledGame[]> {
+  return invoke<InstalledGame[]>("list_launcher_games");
+}
+
+// ==================== UWP / Game Pass ====================
+
+export async function listPackagedGames(): Promise<PackageInfo[]> {
+  return invoke<PackageInfo[]>("list_packaged_games");
+}
+
+// ==================== 采集规则 ====================
+
+export async function updateCaptureRules(serverUrl?: string): Promise<number> {
+  return invoke<number>("update_capture_rules", { serverUrl });
+}
+
+export async function listCaptureRules(): Promise<CaptureRule[]> {
+  return invoke<CaptureRule[]>("list_capture_rules");
+}
+
+export async function saveLocalCaptureRule(rule: CaptureRule): Promise<void> {
+  return invoke("save_local_capture_rule", { rule });
+}
+
+export async function deleteLocalCaptureRule(id: string): Promise<void> {
+  return invoke("delete_local_capture_rule", { id });
+}
+
+// ==================== 进程过滤 ====================
+
+export async function updateProcessFilter(serverUrl?: string): Promise<number> {
+  return invoke<number>("update_process_filter", { serverUrl });
+}
+
+export async function getProcessFilter(): Promise<ProcessFilter> {
+  return invoke<ProcessFilter>("get_process_filter");
+}
+
+/** 上报误判并在本地忽略该进程，返回是否成功上报 */
+export async function reportFalsePositive(
+  report: FalsePositiveReport,
+  serverUrl?: string
+): Promise<boolean> {
+  return invoke<boolean>("report_false_positive", { report, serverUrl });
+}
+
+export async function unignoreProcess(processName: string): Promise<void> {
+  return invoke("unignore_process", { processName });
+}
+
+// ==================== 设置 ====================
+
+export async function getSettings(): Promise<AppSettings> {
+  return invoke<AppSettings>("get_settings");
+}
+
+export async function updateSettings(settings: AppSettings): Promise<void> {
+  return invoke("update_settings", { settings });
+}
+
+// ==================== 散热与机身 ====================
+
+export async function getFormFactor(): Promise<FormFactor> {
+  return invoke<FormFactor>("get_form_factor");
+}
+
+/** 读取笔记本厂商性能模式，不支持的机型返回 null */
+export async function getOemPerfMode(): Promise<OemPerfMode | null> {
+  return invoke<OemPerfMode | null>("get_oem_perf_mode");
+}
+
+// ==================== 传感器 ====================
+
+/** 订阅传感器，返回订阅 id；读数通过 onSensorsUpdated 推送 */
+export async function subscribeSensors(
+  kinds: SensorKind[],
+  intervalMs: number
+): Promise<number> {
+  return invoke<number>("subscribe_sensors", { kinds, intervalMs });
+}
+
+export async function unsubscribeSensors(id: number): Promise<void> {
+  return invoke("unsubscribe_sensors", { id });
+}
+
+export async function getSensorReadings(): Promise<SensorReadings> {
+  return invoke<SensorReadings>("get_sensor_readings");
+}
+
+export function onSensorsUpdated(
+  callback: (readings: SensorReadings) => void
+): Promise<UnlistenFn> {
+  return listen<SensorReadings>("sensors-updated", (event) => {
+    callback(event.payload);
+  });
+}
+
+// ==================== 会话存储 ====================
+
+export async function listSessions(): Promise<FpsSession[]> {
+  return invoke<FpsSession[]>("list_sessions");
+}
+
+export async function getSession(sessionId: string): Promise<FpsSession> {
+  return invoke<FpsSession>("get_session", { sessionId });
+}
+
+export async function deleteSession(sessionId: string): Promise<void> {
+  return invoke("delete_session", { sessionId });
+}
+
+export async function getSessionStoreStatus(): Promise<StoreStatus> {
+  return invoke<StoreStatus>("get_session_store_status");
+}
+
+export async function unlockSessionStore(passphrase: string): Promise<void> {
+  return invoke("unlock_session_store", { passphrase });
+}
+
+export async function lockSessionStore(): Promise<void> {
+  return invoke("lock_session_store");
+}
+
+/** 重写全部会话文件；后台任务，不可取消 */
+export async function enableSessionEncryption(
+  passphrase: string,
+  onProgress?: (progress: TaskProgress) => void
+): Promise<void> {
+  return runTask<void>(
+    () => invoke<string>("enable_session_encryption", { passphrase }),
+    onProgress
+  );
+}
+
+/** 解密全部会话文件；后台任务，不可取消 */
+export async function disableSessionEncryption(
+  passphrase: string,
+  onProgress?: (progress: TaskProgress) => void
+): Promise<void> {
+  return runTask<void>(
+    () => invoke<string>("disable_session_encryption", { passphrase }),
+    onProgress
+  );
+}
+
+// ==================== 隐私 ====================
+
+/** 未传入 options 时使用设置中的隐私选项 */
+export async function redactSession(
+  session: FpsSession,
+  options?: PrivacyOptions
+): Promise<FpsSession> {
+  return invoke<FpsSession>("redact_session", { session, options });
+}
+
+/** 脱敏后导出，附带逐帧帧时间；未传入 path 时写入下载目录，返回文件路径；后台任务，可取消 */
+export async function exportSession(
+  sessionId: string,
+  options?: PrivacyOptions,
+  path?: string,
+  format?: ExportFormat,
+  onProgress?: (progress: TaskProgress) => void
+): Promise<string> {
+  return runTask<string>(
+    () => invoke<string>("export_session", { sessionId, options, path, format }),
+    onProgress
+  );
+}
+
+// ==================== 用户 profile ====================
+
+export async function listUserProfiles(): Promise<ProfileList> {
+  return invoke<ProfileList>("list_user_profiles");
+}
+
+export async function createUserProfile(name: string): Promise<UserProfile> {
+  return invoke<UserProfile>("create_user_profile", { name });
+}
+
+export async function switchUserProfile(id: string): Promise<void> {
+  return invoke("switch_user_profile", { id });
+}
+
+export async function deleteUserProfile(id: string): Promise<void> {
+  return invoke("delete_user_profile", { id });
+}
+
+export async function setProfilePromptOnStartup(enabled: boolean): Promise<void> {
+  return invoke("set_profile_prompt_on_startup", { enabled });
+}
+
+/** 将会话迁移到另一个 profile，返回迁移数量 */
+export async function migrateSessions(
+  sessionIds: string[],
+  from: string,
+  to: string
+): Promise<number> {
+  return invoke<number>("migrate_sessions", { sessionIds, from, to });
+}
+
+export function onProfileSwitched(
+  callback: (profile: UserProfile) => void
+): Promise<UnlistenFn> {
+  return listen<UserProfile>("profile-switched", (event) => callback(event.payload));
+}
+
+// ==================== 网吧模式 ====================
+
+export async function getKioskStatus(): Promise<KioskStatus> {
+  return invoke<KioskStatus>("get_kiosk_status");
+}
+
+export async function enableKiosk(
+  pin: string,
+  purgeAfterView: boolean,
+  managementUrl?: string
+): Promise<void> {
+  return invoke("enable_kiosk", { pin, purgeAfterView, managementUrl });
+}
+
+export async function exitKiosk(pin: string): Promise<void> {
+  return invoke("exit_kiosk", { pin });
+}
+
+/** 结果已展示；网吧模式下会删除该会话 */
+export async function markSessionViewed(sessionId: string): Promise<void> {
+  return invoke("mark_session_viewed", { sessionId });
+}
+
+/** 立即拉取管理端配置，返回是否有更新 */
+export async function syncKioskConfig(): Promise<boolean> {
+  return invoke<boolean>("sync_kiosk_config");
+}
+
+export function onKioskUpdated(callback: (status: KioskStatus) => void): Promise<UnlistenFn> {
+  return listen<KioskStatus>("kiosk-updated", (event) => callback(event.payload));
+}
+
+// ==================== 局域网测试台 ====================
+
+export async function discoverLanAgents(timeoutMs?: number): Promise<LanAgent[]> {
+  return invoke<LanAgent[]>("discover_lan_agents", { timeoutMs });
+}
+
+export async function getLanAgentInfo(agent: LanAgent): Promise<AgentInfo> {
+  return invoke<AgentInfo>("get_lan_agent_info", { agent });
+}
+
+/** 向多台测试机下发测试，`delaySecs` 后同步开始（默认 5 秒） */
+export async function startLanRun(
+  agents: LanAgent[],
+  processName: string,
+  durationSecs: number,
+  delaySecs?: number
+): Promise<LanRun> {
+  return invoke<LanRun>("start_lan_run", { agents, processName, durationSecs, delaySecs });
+}
+
+export async function collectLanResults(runId: string): Promise<LanRunReport> {
+  return invoke<LanRunReport>("collect_lan_results", { runId });
+}
+
+/** 设置对照机（传入 null 取消），本机开始监测时对照机同步开始 */
+export async function setReferenceAgent(agent: LanAgent | null): Promise<void> {
+  return invoke<void>("set_reference_agent", { agent });
+}
+
+/** 最近的对照报告（新的在前） */
+export async function listPairedComparisons(): Promise<PairedComparison[]> {
+  return invoke<PairedComparison[]>("list_paired_comparisons");
+}
+
+export function onPairedComparison(
+  callback: (report: PairedComparison) => void
+): Promise<UnlistenFn> {
+  return listen<PairedComparison>("paired-comparison", (event) => callback(event.payload));
+}
+
+// ==================== 报告签名 ====================
+
+export async function verifySignedReport(report: SignedReport): Promise<SignatureCheck> {
+  return invoke<SignatureCheck>("verify_signed_report", { report });
+}
+
+/** 校验导出的报告文件是否被修改 */
+export async function verifyReportFile(path: string): Promise<SignatureCheck> {
+  return invoke<SignatureCheck>("verify_report_file", { path });
+}
+
+// ==================== 导入 ====================
+
+/** 导入第三方帧时间 CSV，校验不通过时不写入会话记录；后台任务，可取消 */
+export async function importFrameCsv(
+  path: string,
+  processName?: string,
+  onProgress?: (progress: TaskProgress) => void
+): Promise<ImportResult> {
+  return runTask<ImportResult>(
+    () => invoke<string>("import_frame_csv", { path, processName }),
+    onProgress
+  );
+}
+
+// ==================== 搜索 ====================
+
+/** 支持中文名、英文名、拼音与首字母 (e.g., "hsh" → 黑神话：悟空) */
+export async function search(query: string, limit?: number): Promise<SearchHit[]> {
+  return invoke<SearchHit[]>("search", { query, limit: limit ?? null });
+}
+
+// ==================== 会话查询 ====================
+
+export async function querySessions(
+  filter: SessionFilter,
+  sort: SessionSort,
+  page: PageRequest
+): Promise<SessionPage> {
+  return invoke<SessionPage>("query_sessions", { filter, sort, page });
+}
+
+export async function setSessionTags(sessionId: string, tags: string[]): Promise<string[]> {
+  return invoke<string[]>("set_session_tags", { sessionId, tags });
+}
+
+export async function listSessionTags(): Promise<string[]> {
+  return invoke<string[]>("list_session_tags");
+}
+
+// ==================== 批量操作 ====================
+
+export async function deleteSessions(sessionIds: string[]): Promise<BulkResult> {
+  return invoke<BulkResult>("delete_sessions", { sessionIds });
+}
+
+/** dir 为空时导出到下载目录；后台任务，可取消 */
+export async function exportSessions(
+  sessionIds: string[],
+  dir?: string,
+  options?: PrivacyOptions,
+  onProgress?: (progress: TaskProgress) => void
+): Promise<BulkResult> {
+  return runTask<BulkResult>(
+    () =>
+      invoke<string>("export_sessions", {
+        sessionIds,
+        dir: dir ?? null,
+        options: options ?? null,
+      }),
+    onProgress
+  );
+}
+
+export async function mergeSessions(sessionIds: string[], keepOriginals = false): Promise<FpsSession> {
+  return invoke<FpsSession>("merge_sessions", { sessionIds, keepOriginals });
+}
+
+// ==================== 备份与恢复 ====================
+
+/** path 为空时保存到下载目录；后台任务 */
+export async function backupData(
+  path?: string,
+  onProgress?: (progress: TaskProgress) => void
+): Promise<BackupSummary> {
+  return runTask<BackupSummary>(
+    () => invoke<string>("backup_data", { path: path ?? null }),
+    onProgress
+  );
+}
+
+export async function inspectBackup(path: string): Promise<BackupSummary> {
+  return invoke<BackupSummary>("inspect_backup", { path });
+}
+
+export async function restoreData(
+  path: string,
+  onProgress?: (progress: TaskProgress) => void
+): Promise<BackupSummary> {
+  return runTask<BackupSummary>(() => invoke<string>("restore_data", { path }), onProgress);
+}
+
+// ==================== 应用更新 ====================
+
+export async function checkForUpdate(): Promise<UpdateInfo> {
+  return invoke<UpdateInfo>("check_for_update");
+}
+
+/** 后台任务，取消后再次下载会从断点继续；进度的 done / total 为字节数 */
+export async function downloadUpdate(
+  onProgress?: (progress: TaskProgress) => void
+): Promise<UpdateInfo> {
+  return runTask<UpdateInfo>(() => invoke<string>("download_update"), onProgress);
+}
+
+/** 会先结束并保存进行中的监测，然后退出应用运行安装程序 */
+export async function installUpdate(): Promise<void> {
+  return invoke<void>("install_update");
+}
+
+export function onUpdateAvailable(callback: (info: UpdateInfo) => void): Promise<UnlistenFn> {
+  return listen<UpdateInfo>("update-available", (event) => callback(event.payload));
+}
+
+// ==================== 下载镜像 ====================
+
+/** `probe` 为 true 时重新测速 */
+export async function listMirrors(probe: boolean): Promise<MirrorStatus[]> {
+  return invoke<MirrorStatus[]>("list_mirrors", { probe });
+}
+
+/** 通过镜像下载 PresentMon，返回保存路径；后台任务，进度的 done / total 为字节数 */
+export async function downloadPresentmon(
+  onProgress?: (progress: TaskProgress) => void
+): Promise<string> {
+  return runTask<string>(() => invoke<string>("download_presentmon"), onProgress);
+}
+
+// ==================== 网络代理 ====================
+
+export async function getSystemProxy(): Promise<SystemProxy | null> {
+  return invoke<SystemProxy | null>("get_system_proxy");
+}
+
+/** 按当前代理设置测试服务器、GitHub 与各镜像的可达性 */
+export async function testConnectivity(): Promise<ConnectivityReport> {
+  return invoke<ConnectivityReport>("test_connectivity");
+}
+
+// ==================== 插件 ====================
+
+export async function listPlugins(): Promise<PluginInfo[]> {
+  return invoke<PluginInfo[]>("list_plugins");
+}
+
+/** 按用户 profile 保存，下次监测生效 */
+export async function setPluginEnabled(pluginId: string, enabled: boolean): Promise<void> {
+  return invoke<void>("set_plugin_enabled", { pluginId, enabled });
+}
+
+export async function exportWithPlugin(pluginId: string, sessionId: string): Promise<string> {
+  return invoke<string>("export_with_plugin", { pluginId, sessionId });
+}
+
+export async function getPluginsDir(): Promise<string> {
+  return invoke<string>("get_plugins_dir");
+}
+
+// ==================== 脚本钩子 ====================
+
+/** 用示例事件数据试运行脚本 */
+export async function testScriptHook(hook: ScriptHook): Promise<HookRun> {
+  return invoke<HookRun>("test_script_hook", { hook });
+}
+
+// ==================== 全局快捷键 ====================
+
+/** 各快捷键的注册结果 */
+export async function getHotkeyStatus(): Promise<HotkeyStatus[]> {
+  return invoke<HotkeyStatus[]>("get_hotkey_status");
+}
+
+export function onHotkeyTriggered(
+  callback: (event: HotkeyEvent) => void
+): Promise<UnlistenFn> {
+  return listen<HotkeyEvent>("hotkey-triggered", (event) => {
+    callback(event.payload);
+  });
+}
+
+// ==================== 悬浮窗 ====================
+
+/** 显示或关闭 FPS 悬浮窗（最低开销档位监测中无法显示） */
+export async function setOverlay(visible: boolean): Promise<boolean> {
+  return invoke<boolean>("set_overlay", { visible });
+}
+
+export async function toggleOverlay(): Promise<boolean> {
+  return invoke<boolean>("toggle_overlay");
+}
+
+export async function getOverlayVisible(): Promise<boolean> {
+  return invoke<boolean>("get_overlay_visible");
+}
+
+export function onOverlayChanged(callback: (visible: boolean) => void): Promise<UnlistenFn> {
+  return listen<boolean>("overlay-changed", (event) => {
+    callback(event.payload);
+  });
+}
+
+// ==================== 时间标记 ====================
+
+/** 在当前监测会话中添加标记 */
+export async function addSessionMarker(label: string): Promise<SessionMarker> {
+  return invoke<SessionMarker>("add_session_marker", { label });
+}
+
+/** 已通过本地 API 同步时钟的外部工具 */
+export async function listClockSources(): Promise<ClockEstimate[]> {
+  return invoke<ClockEstimate[]>("list_clock_sources");
+}
+
+export function onSessionMarker(
+  callback: (marker: SessionMarker) => void
+): Promise<UnlistenFn> {
+  return listen<SessionMarker>("session-marker", (event) => {
+    callback(event.payload);
+  });
+}
+
+// ==================== 驱动重置 ====================
+
+/** 测试期间检测到显卡驱动超时恢复 (TDR) */
+export function onDriverReset(
+  callback: (reset: DriverReset) => void
+): Promise<UnlistenFn> {
+  return listen<DriverReset>("driver-reset", (event) => {
+    callback(event.payload);
+  });
+}
+
+// ==================== 崩溃恢复 ====================
+
+/** 取出本次启动后恢复的会话（只返回一次） */
+export async function takeRecoveredSessions(): Promise<RecoveredSession[]> {
+  return invoke<RecoveredSession[]>("take_recovered_sessions");
+}
+
+export function onFpsSessionRecovered(
+  callback: (sessions: RecoveredSession[]) => void
+): Promise<UnlistenFn> {
+  return listen<RecoveredSession[]>("fps-session-recovered", (event) => {
+    callback(event.payload);
+  });
+}
+
+// ==================== 每秒聚合 ====================
+
+/** 会话的每秒聚合数据，用于历史趋势图 */
+export async function getSessionTimeline(sessionId: string): Promise<SessionTimeline> {
+  return invoke<SessionTimeline>("get_session_timeline", { sessionId });
+}
+
+// ==================== 原始帧时间 ====================
+
+/** 会话保存的原始帧时间 (ms)，可只读取一段 */
+export async function getSessionFrames(
+  sessionId: string,
+  start?: number,
+  count?: number
+): Promise<number[]> {
+  return invoke<number[]>("get_session_frames", { sessionId, start, count });
+}
+
+/** 按块流式统计原始帧时间，适用于超长会话 */
+export async function analyzeSessionFrames(
+  sessionId: string,
+  start?: number,
+  count?: number
+): Promise<FrameStatsReport> {
+  return invoke<FrameStatsReport>("analyze_session_frames", { sessionId, start, count });
+}
+
+/** 无加载分析：去除检测到的加载画面后的时长与帧率 */
+export async function analyzeLoadless(sessionId: string): Promise<LoadlessReport> {
+  return invoke<LoadlessReport>("analyze_loadless", { sessionId });
+}
+
+/** 导出加载段时间点 (CSV)，返回文件路径 */
+export async function exportLoadSegments(sessionId: string, path?: string): Promise<string> {
+  return invoke<string>("export_load_segments", { sessionId, path });
+}
+
+// ==================== 后台任务 ====================
+
+/** 进行中的后台任务，按开始时间排序 */
+export async function listTasks(): Promise<TaskInfo[]> {
+  return invoke<TaskInfo[]>("list_tasks");
+}
+
+export function onTaskStarted(callback: (task: TaskInfo) => void): Promise<UnlistenFn> {
+  return listen<TaskInfo>("task-started", (event) => {
+    callback(event.payload);
+  });
+}
+
+export function onTaskProgress(callback: (progress: TaskProgress) => void): Promise<UnlistenFn> {
+  return listen<TaskProgress>("task-progress", (event) => {
+    callback(event.payload);
+  });
+}
+
+export function onTaskFinished(callback: (finished: TaskFinished) => void): Promise<UnlistenFn> {
+  return listen<TaskFinished>("task-finished", (event) => {
+    callback(event.payload);
+  });
+}
+
+/** 取消后台任务，等待中的 runTask 以「任务已取消」失败 */
+export async function cancelTask(taskId: string): Promise<void> {
+  return invoke<void>("cancel_task", { taskId });
+}
+
+/**
+ * 启动后台任务并等待 task-finished 结果
+ * 拿到任务 ID 后立即以 0 进度回调一次 onProgress，界面可据此显示取消按钮
+ */
+export async function runTask<T>(
+  start: () => Promise<string>,
+  onProgress?: (progress: TaskProgress) => void
+): Promise<T> {
+  let taskId: string | null = null;
+  // 拿到任务 ID 之前就已结束的任务
+  const early: TaskFinished[] = [];
+  let resolveFinished: (finished: TaskFinished) => void = () => {};
+  const finished = new Promise<TaskFinished>((resolve) => {
+    resolveFinished = resolve;
+  });
+  const unlistenFinished = await onTaskFinished((f) => {
+    if (taskId === null) early.push(f);
+    else if (f.task_id === taskId) resolveFinished(f);
+  });
+  const unlistenProgress = await onTaskProgress((p) => {
+    if (p.task_id === taskId) onProgress?.(p);
+  });
+  try {
+    taskId = await start();
+    onProgress?.({ task_id: taskId, done: 0, total: 0, message: "" });
+    const done = early.find((f) => f.task_id === taskId);
+    if (done) resolveFinished(done);
+    const result = await finished;
+    if (result.error !== null) throw result.error;
+    return result.result as T;
+  } finally {
+    unlistenFinished();
+    unlistenProgress();
+  }
+}
@@ -14,12 +14,21 @@ export interface GpuInfo {
   vram_gb: number;
   driver_version: string;
   resolution: string;
+  device_id: string;
+  luid: string | null;
 }
 
+export type MemoryProfile = "jedec_default" | "jedec" | "xmp_expo" | "unknown";
+
 export interface RamInfo {
   total_gb: number;
   used_gb: number;
   available_gb: number;
+  memory_type: string | null;
+  module_count: number;
+  rated_speed_mhz: number | null;
+  configured_speed_mhz: number | null;
+  profile: MemoryProfile;
 }
 
 export interface HardwareInfo {
@@ -27,22 +36,260 @@ export interface HardwareInfo {
   gpus: GpuInfo[];
   ram: RamInfo;
   os: string;
+  direct_storage: DirectStorageSupport;
+  drive_health: DriveHealth[];
+  vbs: VbsStatus;
+  gpu_mux: MuxStatus;
+  pending: HardwareProbe[];
+  degraded: DegradedProbe[];
+}
+
+export interface DegradedProbe {
+  probe: HardwareProbe;
+  reason: string;
+  suggestion: string;
+}
+
+export type HardwareProbe =
+  | "cpu"
+  | "gpu"
+  | "ram"
+  | "os"
+  | "direct_storage"
+  | "drive_health"
+  | "vbs"
+  | "gpu_mux";
+
+export type VbsState = "off" | "enabled" | "running" | "unknown";
+
+export interface ExpectedImpact {
+  min_percent: number;
+  max_percent: number;
+  basis: string;
+}
+
+export interface VbsStatus {
+  vbs: VbsState;
+  hvci_running: boolean;
+  credential_guard_running: boolean;
+  cpu_has_mbec: boolean | null;
+  expected_impact: ExpectedImpact | null;
+}
+
+export interface DirectStorageSupport {
+  os_build: number;
+  os_supported: boolean;
+  os_bypass_io: boolean;
+  bypass_io_drives: string[];
 }
 
 // ==================== FPS 监测 ====================
 
 export interface FpsSnapshot {
   fps: number;
+  raw_fps: number;
+  smoothing: FpsSmoothing;
   fps_1_low: number;
   fps_01_low: number;
   frametime_ms: number;
   cpu_busy_ms: number;
   gpu_busy_ms: number;
   process_name: string;
+  /** 所属会话 ID（同时监测多个进程时区分来源） */
+  session_id: string;
   elapsed_secs: number;
+  sensors: SensorReadings;
+  /** 按适配器拆分的 GPU 占用（多显卡系统） */
+  adapters: AdapterUsage[];
+  /** 遥测插件最近读数，键为 `插件id.指标` */
+  plugin_metrics: Record<string, number>;
+  /** 快照窗口内的帧时间分布 */
+  histogram: FrametimeHistogram;
+  percentiles: PercentileMetrics;
+  /** 快照窗口内的呈现模式、垂直同步与撕裂（内置 ETW 采集不提供） */
+  present: PresentInfo | null;
+  /** 快照窗口内输入到画面的延迟（需要 PresentMon v2） */
+  latency: InputLatency | null;
+}
+
+/** 监测选项：除 1% / 0.1% Low 外额外计算的百分位 */
+export interface MonitorOptions {
+  /** 采集后端，未指定时使用设置中的默认值 */
+  backend?: CaptureBackend;
+  /** 帧时间百分位，如 95 → P95 帧时间 */
+  frametime_percentiles: number[];
+  /** 自定义 Low FPS，如 5 → 5% Low */
+  low_percentiles: number[];
+  /** 逐帧写入原始采集日志（gzip 压缩的 CSV），会话库加密时不可用 */
+  capture_log?: boolean;
+  /** 定时基准测试，通常由 runBenchmark 设置 */
+  benchmark?: BenchmarkPlan;
+  /** 实时快照推送间隔 (秒)，未指定时按采集档位 */
+  snapshot_interval_secs?: number;
+  /** 启动的后台采样，未指定时全部启动 */
+  telemetry?: TelemetrySet;
+  /** 基准测试预设 ID */
+  preset?: string;
+}
+
+/** 监测期间的后台采样项（最低开销档位下全部关闭） */
+export interface TelemetrySet {
+  sensors: boolean;
+  /** PCIe 链路、混合显卡输出路径与笔记本性能模式 */
+  hardware_state: boolean;
+  memory_pressure: boolean;
+  background_activity: boolean;
+  /** 为 false 时按设置中的 DPC 采样开关 */
+  dpc_latency: boolean;
+  plugins: boolean;
+}
+
+/** 按游戏类型调校的基准测试预设 */
+export interface BenchmarkPreset {
+  id: string;
+  name: string;
+  description: string;
+  duration_secs: number;
+  warmup_secs: number;
+  snapshot_interval_secs: number;
+  telemetry: TelemetrySet;
+  frametime_percentiles: number[];
+  low_percentiles: number[];
+}
+
+export interface BenchmarkPlan {
+  /** 预热时长 (秒)，未指定时沿用采集规则 */
+  warmup_secs?: number;
+  duration_secs: number;
+}
+
+export interface BenchmarkRun {
+  warmup_secs: number;
+  duration_secs: number;
+  /** 是否采满计划时长 */
+  completed: boolean;
+  /** 所用的基准测试预设 ID */
+  preset: string | null;
+}
+
+export type BenchmarkStage = "warmup" | "capture";
+
+/** 基准测试倒计时（benchmark-countdown） */
+export interface BenchmarkCountdown {
+  session_id: string;
+  stage: BenchmarkStage;
+  remaining_secs: number;
+}
+
+export interface PercentileValue {
+  percentile: number;
+  value: number;
+}
+
+export interface PercentileMetrics {
+  frametime_ms: PercentileValue[];
+  low_fps: PercentileValue[];
+}
+
+/** 卡顿事件，附带卡顿前后的帧时间 */
+export interface FpsStutter {
+  session_id: string;
+  process_name: string;
+  unix_ms: number;
+  at_secs: number;
+  frametime_ms: number;
+  median_ms: number;
+  ratio: number;
+  before_ms: number[];
+  after_ms: number[];
+  count: number;
+}
+
+/** 帧时间分布（帧数），边界为 120 / 60 / 30 FPS 对应的帧时间 */
+export interface FrametimeHistogram {
+  lt_8ms: number;
+  ms_8_16: number;
+  ms_16_33: number;
+  gt_33ms: number;
+}
+
+/** 原始帧时间的流式统计结果 */
+export interface FrameStatsReport {
+  total_frames: number;
+  duration_secs: number;
+  avg_fps: number;
+  fps_1_low: number;
+  fps_01_low: number;
+  min_fps: number;
+  max_fps: number;
+}
+
+/** 检测到的加载段，时间相对首帧（不含暂停时段） */
+export interface LoadSegment {
+  first_frame: number;
+  frames: number;
+  start_secs: number;
+  end_secs: number;
+  duration_secs: number;
+}
+
+/** 去除加载画面后的时长与帧率 */
+export interface LoadlessReport {
+  total_secs: number;
+  load_secs: number;
+  loadless_secs: number;
+  segments: LoadSegment[];
+  loadless: FrameStatsReport;
+}
+
+export type SessionStatus = "completed" | "interrupted_by_sleep" | "recovered";
+
+export type CapturePhase =
+  | "idle"
+  | "starting"
+  | "waiting_for_process"
+  | "capturing"
+  | "paused"
+  | "stopping"
+  | "finalizing"
+  | "done"
+  | "failed";
+
+/** `fps-phase` 事件 */
+export interface PhaseChange {
+  phase: CapturePhase;
+  previous: CapturePhase;
+  reason: string;
+  process_name: string;
+  /** starting 阶段尚未生成时为空 */
+  session_id: string;
+}
+
+/** reject: 拒绝；queue: 排队等当前监测结束；replace: 停止当前监测后开始 */
+export type BusyPolicy = "reject" | "queue" | "replace";
+
+export type StartOutcome =
+  | { outcome: "started"; session_id: string }
+  | { outcome: "queued"; position: number; busy_with: string }
+  | { outcome: "replaced"; stopped: string };
+
+export interface QueuedCapture {
+  process_name: string;
+  pid: number | null;
+  profile: CaptureProfile;
+}
+
+/** split: 合并会话时原来分段之间的间隔；user: 手动暂停 */
+export type PauseReason = "suspend" | "no_frames" | "split" | "user";
+
+export interface CapturePause {
+  at_secs: number;
+  duration_secs: number;
+  reason: PauseReason;
 }
 
 export interface FpsSession {
+  session_id: string;
   process_name: string;
   avg_fps: number;
   fps_1_low: number;
@@ -51,19 +298,1292 @@ export interface FpsSession {
   min_fps: number;
   total_frames: number;
   duration_secs: number;
+  wall_duration_secs: number;
+  pauses: CapturePause[];
+  status: SessionStatus;
+  pcie_link: PcieLinkReport | null;
+  /** 显存纠错计数与稳定性说明 */
+  vram: VramStabilityReport | null;
+  gpu_routing: GpuRouting | null;
+  capture_rule: string | null;
+  capture_profile: CaptureProfile;
+  stream: StreamStats | null;
+  metadata: SessionMetadata;
+  thermal: ThermalReport | null;
+  overhead: OverheadReport | null;
+  pipeline: CapturePipeline | null;
+  import: ImportInfo | null;
+  suggestions: Suggestion[];
+  tags: string[];
+  merged_from: string[];
+  plugin_metrics: PluginMetricSummary[];
+  /** 会话开始时刻 (Unix 毫秒) */
+  started_at_ms: number;
+  markers: SessionMarker[];
+  /** 测试期间的显卡驱动重置 (TDR) */
+  driver_resets: DriverReset[];
+  dpc_latency: DpcLatencyReport | null;
+  memory_pressure: MemoryPressureReport | null;
+  /** 测试期间活动的后台服务 */
+  background_activity: BackgroundInterference[];
+  /** 监测开始前后不久启动的游戏才有 */
+  launch: LaunchTiming | null;
+  /** 卡顿次数（帧时间超过近期中位数 2.5 倍） */
+  stutter_count: number;
+  percentiles: PercentileMetrics;
+  /** 帧间隔稳定性 */
+  pacing: FramePacing;
+  /** 定时基准测试的计划与是否完成 */
+  benchmark: BenchmarkRun | null;
+  /** 体验评分，帧数不足时为 null */
+  qoe: QoeScore | null;
+  /** 显示器支持 VRR 时才有 */
+  vrr: VrrReport | null;
+  /** 限帧建议（说明文字在 suggestions 中） */
+  frame_cap: FrameCapAdvice | null;
+  /** 占多数的呈现模式、垂直同步与撕裂 */
+  present: PresentInfo | null;
+  /** 输入到画面的延迟（需要 PresentMon v2） */
+  latency: InputLatency | null;
+}
+
+export type CapKind = "vrr_ceiling" | "sustained" | "refresh" | "half_refresh";
+
+export interface FrameCapAdvice {
+  kind: CapKind;
+  cap_fps: number;
+  refresh_hz: number;
+  avg_fps: number;
+  /** 95% 的帧都能达到的帧率 */
+  sustained_fps: number;
+  /** 限帧后会被拉平的帧占比 (%) */
+  frames_above_cap_pct: number;
+}
+
+/** 呈现方式摘要 */
+export interface PresentInfo {
+  /** 占比最高的呈现模式，如 "Hardware: Independent Flip" */
+  present_mode: string;
+  present_mode_pct: number;
+  /** 画面经由 DWM 合成 */
+  composed: boolean;
+  sync_interval: number | null;
+  vsync: boolean | null;
+  allows_tearing: boolean | null;
+}
+
+export interface LatencySummary {
+  avg_ms: number;
+  median_ms: number;
+  p95_ms: number;
+  samples: number;
+}
+
+/** 输入到画面的延迟 */
+export interface InputLatency {
+  /** 从开始模拟这一帧到显示在屏幕上 */
+  display: LatencySummary | null;
+  /** 从鼠标点击到画面变化，只有带点击的帧才有 */
+  click_to_photon: LatencySummary | null;
+}
+
+/** 帧间隔稳定性：平均帧率掩盖不了的波动 */
+export interface FramePacing {
+  stdev_ms: number;
+  /** 变异系数（标准差 / 平均帧时间） */
+  cv: number;
+  p99_ms: number;
+  p999_ms: number;
+  /** 超过 16.7 ms 的帧数 */
+  over_16ms: number;
+  /** 超过 33.3 ms 的帧数 */
+  over_33ms: number;
+}
+
+export type QoeGrade = "excellent" | "good" | "fair" | "poor";
+
+/** 按人眼感知计算的体验评分 (0–100)，各分项为 0–1 */
+export interface QoeScore {
+  score: number;
+  grade: QoeGrade;
+  /** 平均帧率相对刷新率（对数尺度） */
+  fluency: number;
+  /** 相邻帧的帧时间变化 */
+  consistency: number;
+  /** 每分钟卡顿次数 */
+  stutter: number;
+  /** 落在 VRR 区间内的帧比例 */
+  refresh_fit: number;
+  /** 未知时按 60 Hz 估算 */
+  refresh_hz: number | null;
+  stutters_per_min: number;
+  /** 低于 VRR 下限 (48 Hz) 的帧占比 (%) */
+  below_vrr_pct: number;
+}
+
+/** 游戏启动与加载耗时（时间均为 Unix 毫秒） */
+export interface LaunchTiming {
+  process_started_ms: number;
+  first_frame_ms: number;
+  stable_ms: number | null;
+  /** 进程启动 → 第一帧 (秒) */
+  time_to_first_frame_secs: number;
+  /** 进程启动 → 帧时间稳定 (秒)，测试结束前未稳定时为 null */
+  time_to_stable_secs: number | null;
+}
+
+/** GameBench 自身（含 PresentMon）在测试期间的资源占用 */
+export interface OverheadReport {
+  avg_cpu_percent: number;
+  peak_cpu_percent: number;
+  avg_ram_mb: number;
+  peak_ram_mb: number;
+  avg_gpu_percent: number | null;
+  processes: string[];
+  samples: number;
+}
+
+export interface StreamStats {
+  client: string;
+  target_fps: number;
+  delivered_fps: number;
+  late_frames: number;
+  late_frame_percent: number;
+}
+
+export interface SessionMetadata {
+  pid: number | null;
+  exe_path: string | null;
+  command_line: string | null;
+  launch_args: string[];
+  game_version: GameVersion | null;
+  window_title: string | null;
+  window_handle: number | null;
+  uptime_secs: number | null;
+  boot_time: string | null;
+}
+
+export interface GameVersion {
+  file_version: string | null;
+  product_version: string | null;
+  signed_at: number | null;
+  modified_at: number | null;
 }
 
 export interface FpsStatus {
+  /** 有任一监测占用中（starting 到 finalizing） */
   running: boolean;
+  /** phase / process_name / current_fps 取最近开始的监测 */
+  phase: CapturePhase;
   process_name: string | null;
   current_fps: number | null;
+  /** 所有进行中的监测 */
+  captures: CaptureStatus[];
+}
+
+export interface CaptureStatus {
+  session_id: string;
+  process_name: string;
+  phase: CapturePhase;
+  current_fps: number | null;
+}
+
+/** `fps-started` / `fps-stopped` 事件 */
+export interface CaptureEvent {
+  session_id: string;
+  process_name: string;
 }
 
 // ==================== 游戏检测 ====================
 
+export interface KnownGameName {
+  name: string;
+  name_en: string;
+  name_zh: string | null;
+  process_name: string;
+}
+
 export interface DetectedGame {
   process_name: string;
   pid: number;
+  /** 按设置语言选择的显示名称 */
   game_name: string | null;
+  game_name_en: string | null;
+  game_name_zh: string | null;
   app_id: number | null;
+  package_name: string | null;
+  emulator: string | null;
+  streaming_client: string | null;
+  launcher: Launcher | null;
+  related_processes: string[];
+  window_title: string | null;
+  window_handle: number | null;
+  activity: ActivityState;
+  match_reason: MatchReason;
+  confidence: Confidence;
+  start_time: number;
+  instance: number;
+}
+
+export interface GameExited {
+  process_name: string;
+  game_name: string | null;
+  pid: number;
+  run_secs: number;
+  capture_active: boolean;
+  session_id: string | null;
+}
+
+export type MatchReason = "known_list" | "package" | "steam_path" | "heuristic";
+
+export type Confidence = "low" | "medium" | "high";
+
+export type ActivityState = "active" | "suspended" | "idle";
+
+export type Launcher = "wegame" | "mihoyo" | "netease";
+
+export interface InstalledGame {
+  name: string;
+  launcher: Launcher;
+  install_dir: string;
+}
+
+export interface PackageInfo {
+  full_name: string;
+  display_name: string;
+  install_dir: string | null;
+  is_game: boolean;
+}
+
+// ==================== 启动项分析 ====================
+
+export type StartupSource = "registry" | "startup_folder" | "scheduled_task" | "service";
+
+export type GamingImpact = "none" | "low" | "medium" | "high";
+
+export interface StartupEntry {
+  id: string;
+  name: string;
+  command: string;
+  source: StartupSource;
+  location: string;
+  enabled: boolean;
+  running: boolean;
+  cpu_percent: number;
+  memory_mb: number;
+  impact: GamingImpact;
+}
+
+export interface DisableResult {
+  id: string;
+  success: boolean;
+  error: string | null;
+}
+
+// ==================== 存储设备 ====================
+
+export type DriveKind = "nvme" | "sata_ssd" | "hdd" | "unknown";
+
+export interface DriveInfo {
+  mount_point: string;
+  name: string;
+  kind: DriveKind;
+  file_system: string;
+  total_gb: number;
+  available_gb: number;
+  removable: boolean;
+  disk_number: number | null;
+}
+
+export type HealthStatus = "healthy" | "warning" | "unhealthy" | "unknown";
+
+export interface DriveHealth {
+  disk_number: number;
+  model: string;
+  status: HealthStatus;
+  temperature_c: number | null;
+  wear_percent: number | null;
+  read_errors_uncorrected: number | null;
+  /** SMART 197 待映射扇区，仅 ATA/SATA 盘 */
+  pending_sectors: number | null;
+  power_on_hours: number | null;
+  problems: string[];
+}
+
+export interface InstallReport {
+  process_name: string;
+  install_dir: string;
+  is_steam: boolean;
+  drive: DriveInfo | null;
+  size_gb: number;
+  on_slowest_drive: boolean;
+  fastest_drive: DriveInfo | null;
+  direct_storage: GameDirectStorage;
+  recommendation: string | null;
+  integrity: InstallIntegrity | null;
+}
+
+export interface InstallIntegrity {
+  app_id: string;
+  expected_size_gb: number | null;
+  issues: string[];
+  verify_url: string;
+}
+
+export interface GameDirectStorage {
+  bypass_io_capable: boolean;
+  ships_runtime: boolean;
+  runtime_loaded: boolean | null;
+}
+
+// ==================== PCIe 链路 ====================
+
+export interface PcieLinkStatus {
+  gpu_name: string;
+  current_gen: number;
+  current_width: number;
+  max_gen: number;
+  max_width: number;
+}
+
+export type PcieIssue = "width_limited" | "gen_limited" | "downclocked";
+
+export interface PcieLinkReport {
+  gpu_name: string;
+  max_gen: number;
+  max_width: number;
+  min_observed_gen: number;
+  min_observed_width: number;
+  downclocked: boolean;
+  width_limited: boolean;
+  samples: number;
+  /** [异常类型, 提示信息] */
+  warnings: [PcieIssue, string][];
+}
+
+// ==================== 显存纠错 ====================
+
+/** 驱动加载以来的计数，显卡不提供的项为 null */
+export interface VramErrorCounters {
+  gpu_index: number;
+  gpu_name: string;
+  ecc_enabled: boolean | null;
+  corrected: number | null;
+  uncorrected: number | null;
+  retired_sbe: number | null;
+  retired_dbe: number | null;
+  retire_pending: boolean | null;
+}
+
+export interface VramStabilityReport {
+  gpu_name: string;
+  /** 显卡是否公开了纠错计数（消费级显卡通常不提供） */
+  exposed: boolean;
+  corrected_delta: number;
+  uncorrected_delta: number;
+  retired_pages_delta: number;
+  retire_pending: boolean;
+  /** 计数可读时：测试期间没有新增任何错误 */
+  stable: boolean | null;
+  samples: number;
+  note: string;
+}
+
+// ==================== 混合显卡 ====================
+
+export interface GpuAdapter {
+  name: string;
+  luid: string;
+  device_id: string | null;
+  integrated: boolean;
+  has_outputs: boolean;
+}
+
+export type CompositionPath =
+  | "direct"
+  | "hybrid_copy"
+  | "integrated_only"
+  | "unknown";
+
+export interface GpuRouting {
+  hybrid: boolean;
+  render_gpu: string | null;
+  render_gpu_id: string | null;
+  display_gpu: string | null;
+  path: CompositionPath;
+  display: DisplayTarget | null;
+  mux: MuxStatus | null;
+  adapters: GpuAdapter[];
+}
+
+export type MuxMode =
+  | "hybrid"
+  | "discrete_only"
+  | "dynamic_direct"
+  | "not_applicable"
+  | "unknown";
+
+export interface MuxStatus {
+  mode: MuxMode;
+  source: "vendor" | "topology";
+  panel_gpu: string | null;
+  detail: string;
+}
+
+export interface DisplayTarget {
+  monitor_name: string;
+  internal: boolean;
+  connection: string;
+  width: number;
+  height: number;
+  refresh_hz: number | null;
+  gpu_luid: string | null;
+  /** EDID 中的刷新率范围 */
+  refresh_range: VrrRange | null;
+}
+
+export interface VrrRange {
+  min_hz: number;
+  max_hz: number;
+}
+
+/** active: 独立翻转；inactive: 经 DWM 合成，VRR 未启用；unknown: 无呈现模式数据 */
+export type VrrStatus = "active" | "inactive" | "unknown";
+
+/** VRR 有效性（百分比为帧占比） */
+export interface VrrReport {
+  range: VrrRange;
+  status: VrrStatus;
+  /** 当前刷新率低于显示器上限时以当前刷新率为准 */
+  effective_max_hz: number;
+  /** 支持低帧率补偿 (LFC) */
+  lfc: boolean;
+  coverage_pct: number;
+  below_range_pct: number;
+  above_range_pct: number;
+}
+
+// ==================== 结果对比 ====================
+
+export interface SessionComparison {
+  avg_fps_delta_percent: number;
+  fps_1_low_delta_percent: number;
+  mismatches: string[];
+}
+
+/** 多会话对比中单个会话的逐帧分析结果 */
+export interface SessionFrameAnalysis {
+  session_id: string;
+  stats: FrameStatsReport;
+  histogram: FrametimeHistogram;
+  segments: SegmentStats[];
+  avg_fps_delta_percent: number;
+  fps_1_low_delta_percent: number;
+}
+
+export interface SegmentStats {
+  start_secs: number;
+  avg_fps: number;
+  fps_1_low: number;
+}
+
+export interface MultiSessionComparison {
+  baseline_id: string;
+  segment_secs: number;
+  sessions: SessionFrameAnalysis[];
+}
+
+// ==================== 优化建议 ====================
+
+export type SuggestionLevel = "info" | "warning" | "critical";
+
+export interface Suggestion {
+  code: string;
+  level: SuggestionLevel;
+  title: string;
+  detail: string;
+}
+
+// ==================== 测试环境 profile ====================
+
+export interface OsSettings {
+  power_scheme: string | null;
+  game_mode: boolean | null;
+  hags: boolean | null;
+  core_parking_min_percent: number | null;
+  /** 核心停放设置所属的电源计划，null 表示当前计划 */
+  core_parking_scheme?: string | null;
+}
+
+export interface BenchmarkProfile {
+  name: string;
+  description: string;
+  builtin: boolean;
+  settings: OsSettings;
+}
+
+export interface ApplyResult {
+  previous: OsSettings;
+  warnings: string[];
+}
+
+// ==================== 采集规则 ====================
+
+export type SwapchainSelect = "all" | "dominant";
+
+export interface SceneMarker {
+  name: string;
+  description: string;
+  duration_secs: number | null;
+}
+
+export interface CaptureRule {
+  id: string;
+  game_name: string;
+  processes: string[];
+  capture_process: string | null;
+  swapchain: SwapchainSelect;
+  warmup_secs: number;
+  scene_markers: SceneMarker[];
+  local: boolean;
+}
+
+// ==================== 进程过滤 ====================
+
+export interface ProcessFilter {
+  version: number;
+  exact: string[];
+  prefixes: string[];
+}
+
+export interface FalsePositiveReport {
+  process_name: string;
+  game_name: string | null;
+  note: string | null;
+}
+
+// ==================== 设置 ====================
+
+export interface AppSettings {
+  auto_detect: boolean;
+  /** 检测到游戏启动时自动开始监测，游戏退出时自动结束 */
+  auto_monitor: boolean;
+  auto_upload: boolean;
+  server_url: string;
+  /** 离线模式：不发起任何互联网请求 */
+  offline: boolean;
+  allow_powershell_fallback: boolean;
+  units: UnitPrefs;
+  chassis: ChassisProfile;
+  game_name_language: GameNameLanguage;
+  capture_profile: CaptureProfile;
+  capture_backend: CaptureBackend;
+  /** ETW DPC/ISR 延迟采样（需管理员权限） */
+  dpc_sampling: boolean;
+  /** 已有监测进行中时开始新监测的默认处理方式 */
+  busy_policy: BusyPolicy;
+  fps_smoothing: FpsSmoothing;
+  sleep_behavior: SleepBehavior;
+  privacy: PrivacyOptions;
+  cloud_account: CloudAccount | null;
+  lan: LanSettings;
+  update: UpdateSettings;
+  mirrors: MirrorSettings;
+  proxy: ProxySettings;
+  enabled_plugins: string[];
+  script_hooks: ScriptHook[];
+  /** 全局快捷键 */
+  hotkeys: HotkeySettings;
+}
+
+export interface CloudAccount {
+  user_name: string;
+  token: string;
+}
+
+/** standard: 帧时间 + 后台采样；minimal: 仅帧时间，最低开销 */
+export type CaptureProfile = "standard" | "minimal";
+
+/** present_mon: 启动 PresentMon.exe；native: 进程内直接消费 DXGI / D3D9 Present 事件 */
+export type CaptureBackend = "present_mon" | "native";
+
+export type GameNameLanguage = "zh" | "en";
+
+export type FpsSmoothing = "average" | "ema" | "median";
+
+export type SleepBehavior = "resume" | "finalize";
+
+export interface UnitPrefs {
+  size: "gb" | "gib";
+  temperature: "celsius" | "fahrenheit";
+  decimal: "dot" | "comma";
+}
+
+// ==================== 传感器 ====================
+
+export type SensorKind = "cpu" | "cpu_temp" | "gpu" | "ram" | "battery";
+
+export interface CpuReading {
+  usage_percent: number;
+  frequency_mhz: number;
+}
+
+export interface GpuReading {
+  utilization_percent: number;
+  dedicated_used_mb: number;
+  /** 各适配器分别的读数（多显卡系统） */
+  adapters: AdapterReading[];
+}
+
+export interface AdapterReading {
+  luid: string;
+  name: string | null;
+  utilization_percent: number;
+  dedicated_used_mb: number;
+}
+
+/** 快照中单个适配器的占用情况 */
+export interface AdapterUsage {
+  luid: string;
+  name: string | null;
+  /** 整机在该适配器上的 3D 引擎占用率 */
+  utilization_percent: number;
+  /** 被监测进程在该适配器上的 3D 引擎占用率 */
+  process_percent: number;
+  /** 是否为游戏渲染所在的适配器 */
+  render: boolean;
+  /** GPU 占用时间，只归属到渲染适配器 */
+  gpu_busy_ms: number | null;
+}
+
+export interface RamReading {
+  used_gb: number;
+  total_gb: number;
+}
+
+export interface BatteryReading {
+  percent: number;
+  on_ac: boolean;
+  charging: boolean;
+}
+
+export interface SensorReadings {
+  cpu: CpuReading | null;
+  cpu_temp_c: number | null;
+  gpu: GpuReading | null;
+  ram: RamReading | null;
+  battery: BatteryReading | null;
+}
+
+// ==================== 散热与机身 ====================
+
+export type FormFactor = "desktop" | "laptop" | "mini_pc" | "unknown";
+
+export type CoolingSetup =
+  | "stock_air"
+  | "tower_air"
+  | "aio"
+  | "custom_loop"
+  | "laptop_stock"
+  | "laptop_cooling_pad"
+  | "unknown";
+
+export interface ChassisProfile {
+  form_factor: FormFactor;
+  cooling: CoolingSetup;
+  notes: string;
+}
+
+export type PerfMode = "quiet" | "balanced" | "performance" | "custom" | "unknown";
+
+export interface OemPerfMode {
+  vendor: string;
+  mode: PerfMode;
+  raw: string;
+}
+
+export interface PerfModeChange {
+  at_secs: number;
+  from: PerfMode;
+  to: PerfMode;
+}
+
+export interface ThermalReport {
+  chassis: ChassisProfile;
+  detected_form_factor: FormFactor;
+  perf_mode: OemPerfMode | null;
+  changes: PerfModeChange[];
+}
+
+// ==================== 会话存储与隐私 ====================
+
+export interface StoreStatus {
+  encrypted: boolean;
+  unlocked: boolean;
+  session_count: number;
+  pending: number;
+}
+
+export interface PrivacyOptions {
+  strip_machine_name: boolean;
+  strip_user_paths: boolean;
+  strip_serials: boolean;
+}
+
+/** 单个会话导出格式，均附带逐帧帧时间（如有） */
+export type ExportFormat = "json" | "csv";
+
+// ==================== 用户 profile ====================
+
+export interface UserProfile {
+  id: string;
+  name: string;
+  created_at: number;
+}
+
+export interface ProfileRegistry {
+  profiles: UserProfile[];
+  last_active: string;
+  ask_on_startup: boolean;
+}
+
+export interface ProfileList {
+  registry: ProfileRegistry;
+  active: string;
+}
+
+// ==================== 网吧模式 ====================
+
+export interface KioskStatus {
+  enabled: boolean;
+  purge_after_view: boolean;
+  management_url: string | null;
+  applied_version: number;
+}
+
+// ==================== 局域网测试台 ====================
+
+export interface LanSettings {
+  agent_enabled: boolean;
+  port: number;
+  token: string;
+  /** 对照机：本机开始监测时同时在对照机上开始，结束后自动生成对比报告 */
+  reference: LanAgent | null;
+}
+
+export interface LanAgent {
+  name: string;
+  address: string;
+  port: number;
+  version: string;
+}
+
+export interface AgentInfo {
+  name: string;
+  version: string;
+  cpu: string;
+  gpus: string[];
+  busy: boolean;
+}
+
+export type RunStatus = "scheduled" | "running" | "completed" | "failed";
+
+export interface LanRun {
+  run_id: string;
+  process_name: string;
+  duration_secs: number;
+  agents: LanAgent[];
+  failed: [string, string][];
+}
+
+export interface LanAgentResult {
+  agent: LanAgent;
+  status: RunStatus;
+  session: FpsSession | null;
+  error: string | null;
+  comparison: SessionComparison | null;
+}
+
+export interface LanRunReport {
+  run_id: string;
+  process_name: string;
+  baseline: string | null;
+  results: LanAgentResult[];
+  finished: boolean;
+}
+
+/** 本机会话与对照机同步采集结果的对比 */
+export interface PairedComparison {
+  run_id: string;
+  session_id: string;
+  reference: LanAgent;
+  reference_session: FpsSession | null;
+  /** 以对照机为基准，本机结果的变化 */
+  comparison: SessionComparison | null;
+  error: string | null;
+}
+
+// ==================== 报告签名 ====================
+
+export interface CapturePipeline {
+  app_version: string;
+  backend: string;
+  backend_version: string | null;
+  capture_profile: CaptureProfile;
+  capture_rule: string | null;
+  warmup_secs: number;
+  swapchain: SwapchainSelect;
+  hardware_fingerprint: string;
+}
+
+export interface SignedReport {
+  format_version: number;
+  algorithm: string;
+  signed_at: string;
+  session: FpsSession;
+  public_key: string;
+  signature: string;
+}
+
+export interface SignatureCheck {
+  /** 签名与报告附带的公钥匹配；公钥不与任何信任列表比对 */
+  valid: boolean;
+  reason: string | null;
+  /** 签名公钥指纹，需自行核对是否属于提交者 */
+  key_fingerprint: string | null;
+  /** 由本机签名密钥签出 */
+  local_key: boolean;
+}
+
+// ==================== 导入 ====================
+
+export type Verdict = "valid" | "flagged" | "rejected";
+
+export interface ValidationIssue {
+  code: string;
+  severity: "warning" | "error";
+  message: string;
+}
+
+export interface ValidationReport {
+  verdict: Verdict;
+  frames: number;
+  issues: ValidationIssue[];
+  content_hash: string;
+}
+
+export interface ImportInfo {
+  source_file: string;
+  source_format: string;
+  validation: ValidationReport;
+}
+
+export interface ImportResult {
+  imported: boolean;
+  report: ValidationReport;
+  session: FpsSession | null;
+}
+
+// ==================== 搜索 ====================
+
+export type SearchKind = "known_game" | "installed_game" | "session";
+
+export interface SearchHit {
+  kind: SearchKind;
+  title: string;
+  subtitle: string | null;
+  process_name: string | null;
+  session_id: string | null;
+  score: number;
+}
+
+// ==================== 会话查询 ====================
+
+export interface SessionFilter {
+  /** 进程名或中英文游戏名 */
+  game?: string | null;
+  /** YYYY-MM-DD，含当天 */
+  date_from?: string | null;
+  date_to?: string | null;
+  tags?: string[];
+  min_duration_secs?: number | null;
+}
+
+export type SortField = "started_at" | "game_name" | "avg_fps" | "fps_1_low" | "duration";
+
+export interface SessionSort {
+  field: SortField;
+  descending: boolean;
+}
+
+export interface PageRequest {
+  /** 从 0 开始 */
+  page: number;
+  page_size: number;
+}
+
+export interface SessionSummary {
+  session_id: string;
+  process_name: string;
+  game_name: string;
+  started_at: string | null;
+  avg_fps: number;
+  fps_1_low: number;
+  fps_01_low: number;
+  duration_secs: number;
+  total_frames: number;
+  status: SessionStatus;
+  capture_profile: CaptureProfile;
+  imported: boolean;
+  tags: string[];
+}
+
+export interface SessionPage {
+  items: SessionSummary[];
+  total: number;
+  page: number;
+  page_size: number;
+}
+
+// ==================== 批量操作 ====================
+
+export interface BulkResult {
+  /** 成功的会话 ID（导出时为文件路径） */
+  succeeded: string[];
+  failed: [string, string][];
+}
+
+// ==================== 备份与恢复 ====================
+
+export interface BackupSummary {
+  path: string;
+  app_version: string;
+  created_at: string;
+  files: number;
+  sessions: number;
+  size_bytes: number;
+}
+
+// ==================== 应用更新 ====================
+
+export type UpdateChannel = "stable" | "beta";
+
+export interface UpdateSettings {
+  channel: UpdateChannel;
+  auto_check: boolean;
+  /** 更新专用镜像地址，为空时按下载镜像设置访问服务器 */
+  mirror_url: string | null;
+}
+
+export interface UpdateInfo {
+  current_version: string;
+  version: string;
+  channel: UpdateChannel;
+  notes: string;
+  pub_date: string | null;
+  available: boolean;
+  /** 是否使用差分安装包 */
+  delta: boolean;
+  download_size: number;
+}
+
+// ==================== 下载镜像 ====================
+
+export interface Mirror {
+  id: string;
+  name: string;
+  /** 与官方服务器路径一致的镜像根地址 */
+  server_url: string | null;
+  /** GitHub 下载代理前缀 */
+  github_proxy: string | null;
+}
+
+export interface MirrorSettings {
+  list: Mirror[];
+  auto_select: boolean;
+  preferred: string | null;
+}
+
+export interface MirrorStatus {
+  id: string;
+  name: string;
+  server_latency_ms: number | null;
+  github_latency_ms: number | null;
+  server_selected: boolean;
+  github_selected: boolean;
+}
+
+// ==================== 网络代理 ====================
+
+export type ProxyMode = "system" | "manual" | "off";
+
+export interface ProxySettings {
+  mode: ProxyMode;
+  /** http://host:port 或 socks5://host:port */
+  url: string;
+}
+
+export interface SystemProxy {
+  source: string;
+  url: string;
+}
+
+export interface EndpointStatus {
+  name: string;
+  url: string;
+  reachable: boolean;
+  status: number | null;
+  latency_ms: number | null;
+  error: string | null;
+}
+
+export interface ConnectivityReport {
+  mode: ProxyMode;
+  proxy: string | null;
+  endpoints: EndpointStatus[];
+}
+
+// ==================== 插件 ====================
+
+export type PluginKind = "telemetry" | "exporter";
+
+export interface MetricSpec {
+  key: string;
+  label: string;
+  unit: string | null;
+}
+
+export interface PluginManifest {
+  id: string;
+  name: string;
+  version: string;
+  description: string;
+  kind: PluginKind;
+  executable: string;
+  args: string[];
+  metrics: MetricSpec[];
+}
+
+export interface PluginInfo {
+  dir: string;
+  manifest: PluginManifest | null;
+  enabled: boolean;
+  error: string | null;
+}
+
+export interface PluginMetricSummary {
+  plugin_id: string;
+  key: string;
+  label: string;
+  unit: string | null;
+  min: number;
+  avg: number;
+  max: number;
+  samples: number;
+}
+
+// ==================== 脚本钩子 ====================
+
+export type HookEvent = "session_start" | "session_complete" | "stutter_detected";
+
+export interface ScriptHook {
+  id: string;
+  event: HookEvent;
+  /** .rhai 脚本路径 */
+  script: string;
+  enabled: boolean;
+  timeout_secs: number;
+}
+
+export interface HookRun {
+  /** 脚本运行错误 */
+  error: string | null;
+  timed_out: boolean;
+  output: string;
+  duration_ms: number;
+}
+
+// ==================== 全局快捷键 ====================
+
+export type HotkeyAction = "start" | "stop" | "mark" | "overlay";
+
+export interface HotkeyBinding {
+  action: HotkeyAction;
+  /** 组合键，如 "Ctrl+Shift+F9" */
+  keys: string;
+}
+
+export interface HotkeySettings {
+  enabled: boolean;
+  bindings: HotkeyBinding[];
+}
+
+export interface HotkeyStatus {
+  action: HotkeyAction;
+  keys: string;
+  registered: boolean;
+  /** 格式错误或已被其他程序占用 */
+  error: string | null;
+}
+
+export interface HotkeyEvent {
+  action: HotkeyAction;
+  error: string | null;
+}
+
+// ==================== 时间标记 ====================
+
+export type MarkerClock = "unix" | "source";
+
+export interface SessionMarker {
+  label: string;
+  /** 外部工具名，界面手动添加时为 null */
+  source: string | null;
+  unix_ms: number;
+  /** 相对会话开始的秒数 */
+  at_secs: number;
+  /** 对时误差估计（毫秒），本机时钟或 Unix 时间戳为 null */
+  uncertainty_ms: number | null;
+}
+
+export interface ClockEstimate {
+  source: string;
+  offset_ms: number;
+  drift_ppm: number;
+  uncertainty_ms: number;
+  samples: number;
+}
+
+// ==================== 驱动重置 ====================
+
+export interface DriverReset {
+  /** Display / nvlddmkm / amdkmdag */
+  provider: string;
+  event_id: number;
+  unix_ms: number;
+  /** 距会话开始的秒数 */
+  at_secs: number;
+  message: string;
+  /** 重置前后最长的一帧 (ms) */
+  hole_ms: number | null;
+}
+
+// ==================== DPC/ISR 延迟 ====================
+
+export interface DpcOffender {
+  driver: string;
+  dpc_count: number;
+  isr_count: number;
+  /** 单次最长执行时间 (µs) */
+  max_us: number;
+  total_ms: number;
+}
+
+export interface DpcLatencyReport {
+  /** 无法采样时的原因 */
+  error: string | null;
+  max_dpc_us: number;
+  max_isr_us: number;
+  storm_secs: number;
+  stutters: number;
+  stutters_with_storm: number;
+  offenders: DpcOffender[];
+  warnings: string[];
+}
+
+// ==================== 内存压力 ====================
+
+export interface MemoryPressureReport {
+  total_ram_gb: number;
+  /** 硬页错误读取 (次/秒) */
+  peak_hard_faults_per_sec: number;
+  avg_hard_faults_per_sec: number;
+  min_available_mb: number;
+  /** 待机列表最小值 (MB) */
+  min_standby_mb: number;
+  peak_commit_percent: number;
+  pressure_secs: number;
+  stutters: number;
+  stutters_with_pressure: number;
+  samples: number;
+  warnings: string[];
+}
+
+// ==================== 后台干扰 ====================
+
+export type InterferenceKind =
+  | "defender_scan"
+  | "search_indexer"
+  | "dot_net_optimization"
+  | "windows_update"
+  | "maintenance";
+
+export interface BackgroundInterference {
+  kind: InterferenceKind;
+  process_name: string;
+  first_at_secs: number;
+  last_at_secs: number;
+  active_secs: number;
+  peak_cpu_percent: number;
+  disk_read_mb: number;
+}
+
+// ==================== 崩溃恢复 ====================
+
+/** 应用异常退出后从检查点恢复的会话 */
+export interface RecoveredSession {
+  session_id: string;
+  process_name: string;
+  total_frames: number;
+  duration_secs: number;
+  /** 是否结束了上次遗留的 PresentMon 进程 */
+  stopped_presentmon: boolean;
+}
+
+// ==================== 每秒聚合 ====================
+
+/** 会话的每秒聚合数据（按列存储，各数组长度相同） */
+export interface SessionTimeline {
+  session_id: string;
+  interval_secs: number;
+  at_secs: number[];
+  fps: number[];
+  fps_1_low: number[];
+  max_frametime_ms: number[];
+  cpu_percent: (number | null)[];
+  cpu_temp_c: (number | null)[];
+  gpu_percent: (number | null)[];
+  ram_used_gb: (number | null)[];
+}
+
+// ==================== 后台任务 ====================
+
+export type TaskKind = "download" | "import" | "report" | "analysis" | "maintenance";
+
+export interface TaskInfo {
+  task_id: string;
+  kind: TaskKind;
+  name: string;
+  done: number;
+  total: number;
+  message: string;
+  started_at_ms: number;
+  /** 加密、备份与恢复等维护任务不可中途取消 */
+  cancellable: boolean;
+  cancel_requested: boolean;
+}
+
+export interface TaskProgress {
+  task_id: string;
+  done: number;
+  total: number;
+  message: string;
+}
+
+/** 后台任务结束，成功时 result 为命令原本的返回值 */
+export interface TaskFinished {
+  task_id: string;
+  cancelled: boolean;
+  result: unknown;
+  error: string | null;
 }
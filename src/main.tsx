@@ -1,10 +1,13 @@
 import React from "react";
 import ReactDOM from "react-dom/client";
+import { getCurrentWindow } from "@tauri-apps/api/window";
 import App from "./App";
+import Overlay from "./pages/Overlay";
 import "./index.css";
 
 ReactDOM.createRoot(document.getElementById("root")!).render(
   <React.StrictMode>
-    <App />
+    {/* 悬浮窗与主窗口共用同一页面，按窗口标签区分 */}
+    {getCurrentWindow().label === "overlay" ? <Overlay /> : <App />}
   </React.StrictMode>
 );
@@ -1,5 +1,21 @@
 import { Cpu, MonitorSmartphone, MemoryStick, RefreshCw, Loader2, Gamepad2 } from "lucide-react";
-import type { HardwareInfo, DetectedGame } from "../lib/types";
+import { useState } from "react";
+import type { HardwareInfo, DetectedGame, MuxMode } from "../lib/types";
+import { reportFalsePositive } from "../lib/tauri-api";
+
+const MUX_LABELS: Record<MuxMode, string> = {
+  hybrid: "混合输出（Optimus）",
+  discrete_only: "独显直连",
+  dynamic_direct: "独显直连（Advanced Optimus）",
+  not_applicable: "不适用",
+  unknown: "未知",
+};
+
+/** 另一种语言的游戏名（中文名显示时附带英文名，反之亦然） */
+function alternateName(game: DetectedGame): string | null {
+  const other = [game.game_name_zh, game.game_name_en].find((n) => n && n !== game.game_name);
+  return other ?? null;
+}
 
 interface DashboardProps {
   hardware: HardwareInfo | null;
@@ -16,6 +32,22 @@ export default function Dashboard({
   runningGames,
   onRefreshHardware,
 }: DashboardProps) {
+  const [ignored, setIgnored] = useState<string[]>([]);
+  const visibleGames = runningGames.filter((g) => !ignored.includes(g.process_name));
+
+  const handleNotAGame = async (game: DetectedGame) => {
+    setIgnored((prev) => [...prev, game.process_name]);
+    try {
+      await reportFalsePositive({
+        process_name: game.process_name,
+        game_name: game.game_name,
+        note: null,
+      });
+    } catch (_e) {
+      // 静默失败
+    }
+  };
+
   return (
     <div className="p-6 space-y-6">
       {/* 标题栏 */}
@@ -35,6 +67,17 @@ export default function Dashboard({
         </button>
       </div>
 
+      {hardware && hardware.degraded.length > 0 && (
+        <div className="p-4 rounded-lg bg-yellow-500/10 border border-yellow-500/30 text-yellow-400 text-sm space-y-1">
+          {hardware.degraded.map((d) => (
+            <div key={d.probe}>
+              {d.reason}
+              <span className="text-xs text-yellow-500/80"> — {d.suggestion}</span>
+            </div>
+          ))}
+        </div>
+      )}
+
       {hardwareError && (
         <div className="p-4 rounded-lg bg-red-500/10 border border-red-500/30 text-red-400 text-sm">
           检测失败: {hardwareError}
@@ -114,6 +157,14 @@ export default function Dashboard({
                   <div className="text-xs text-slate-500">驱动版本</div>
                   <div className="text-xs text-slate-400">{gpu.driver_version}</div>
                 </div>
+                {hardware.gpu_mux?.panel_gpu === gpu.name && hardware.gpu_mux.mode !== "unknown" && (
+                  <div>
+                    <div className="text-xs text-slate-500">笔记本 MUX</div>
+                    <div className="text-xs text-slate-400" title={hardware.gpu_mux.detail}>
+                      {MUX_LABELS[hardware.gpu_mux.mode]}
+                    </div>
+                  </div>
+                )}
               </div>
             </div>
           ))}
@@ -164,7 +215,7 @@ export default function Dashboard({
           <Gamepad2 size={16} className="text-brand-400" />
           运行中的游戏
         </h2>
-        {runningGames.length === 0 ? (
+        {visibleGames.length === 0 ? (
           <div className="p-8 text-center rounded-xl bg-surface-card border border-border">
             <Gamepad2 size={32} className="mx-auto mb-3 text-slate-600" />
             <div className="text-sm text-slate-500">未检测到正在运行的游戏</div>
@@ -172,7 +223,7 @@ export default function Dashboard({
           </div>
         ) : (
           <div className="space-y-2">
-            {runningGames.map((game) => (
+            {visibleGames.map((game) => (
               <div
                 key={game.pid}
                 className="flex items-center justify-between p-4 rounded-xl bg-surface-card border border-border"
@@ -180,13 +231,26 @@ export default function Dashboard({
                 <div>
                   <div className="text-sm font-medium text-white">
                     {game.game_name || game.process_name}
+                    {game.window_title && game.window_title !== game.game_name && (
+                      <span className="text-slate-400 font-normal"> — {game.window_title}</span>
+                    )}
                   </div>
                   <div className="text-xs text-slate-500">
+                    {alternateName(game) && `${alternateName(game)} · `}
                     {game.process_name} · PID: {game.pid}
+                    {game.instance > 1 && ` · 实例 #${game.instance}`}
                     {game.app_id && ` · Steam #${game.app_id}`}
+                    {game.confidence === "low" && " · 可能不是游戏"}
                   </div>
                 </div>
                 <div className="flex items-center gap-2">
+                  <button
+                    onClick={() => handleNotAGame(game)}
+                    className="text-xs text-slate-500 hover:text-slate-300"
+                    title="忽略该进程并上报误判"
+                  >
+                    不是游戏
+                  </button>
                   <div className="w-2 h-2 rounded-full bg-green-500 animate-pulse" />
                   <span className="text-xs text-green-400">运行中</span>
                 </div>
@@ -1,26 +1,103 @@
 import { useEffect, useState, useRef } from "react";
-import { Play, Square, Activity, Clock, Zap, AlertTriangle } from "lucide-react";
 import {
-  LineChart, Line, XAxis, YAxis, CartesianGrid, Tooltip,
-  ResponsiveContainer, ReferenceLine,
+  Activity, AlertTriangle, Clock, Flag, Pause, PictureInPicture2, Play, Square, Zap,
+} from "lucide-react";
+import {
+  CartesianGrid, Line, LineChart, ReferenceLine, ResponsiveContainer, Tooltip, XAxis, YAxis,
 } from "recharts";
 import {
-  startFpsMonitor, stopFpsMonitor, scanRunningGames,
-  onFpsUpdate, onFpsStopped, onFpsSessionComplete, onFpsError,
+  addSessionMarker, cancelTask, exportSession, getOverlayVisible, importFrameCsv,
+  listBenchmarkPresets, markSessionViewed, onBenchmarkCountdown, onDriverReset, onFpsError,
+  onFpsPhase, onFpsSessionComplete, onFpsSessionRecovered, onFpsStarted, onFpsStopped, onFpsStutter,
+  onFpsUpdate, onHotkeyTriggered, onOverlayChanged, onSessionMarker, pauseFpsMonitor,
+  resumeFpsMonitor, runBenchmark, scanRunningGames, setOverlay, startFpsMonitor, stopFpsMonitor,
+  takeRecoveredSessions,
 } from "../lib/tauri-api";
-import type { FpsSnapshot, FpsSession, DetectedGame } from "../lib/types";
+import type {
+  AdapterUsage, BenchmarkCountdown, BenchmarkPreset, CapturePhase, DetectedGame, DriverReset,
+  ExportFormat, FpsSession, FpsSnapshot, FpsStutter, FrametimeHistogram, ImportResult, InputLatency,
+  LatencySummary, MonitorOptions, PercentileMetrics, PresentInfo, QoeGrade, QoeScore,
+  RecoveredSession, SessionMarker, TaskProgress, Verdict, VrrReport,
+} from "../lib/types";
 
 const MAX_CHART_POINTS = 120; // 2分钟 (每秒1个点)
 
+const EMPTY_HISTOGRAM: FrametimeHistogram = { lt_8ms: 0, ms_8_16: 0, ms_16_33: 0, gt_33ms: 0 };
+
+const HISTOGRAM_BUCKETS: [keyof FrametimeHistogram, string, string][] = [
+  ["lt_8ms", "< 8.3 ms", "bg-green-500"],
+  ["ms_8_16", "8.3–16.7 ms", "bg-blue-500"],
+  ["ms_16_33", "16.7–33.3 ms", "bg-yellow-500"],
+  ["gt_33ms", "> 33.3 ms", "bg-red-500"],
+];
+
+/** 解析附加指标输入：P95 → 帧时间百分位，5% → 5% Low */
+function parseMetrics(text: string): MonitorOptions {
+  const options: MonitorOptions = { frametime_percentiles: [], low_percentiles: [] };
+  for (const token of text.split(/[\s,，]+/).filter(Boolean)) {
+    const percentile = /^p(\d+(?:\.\d+)?)$/i.exec(token);
+    const low = /^(\d+(?:\.\d+)?)%(?:\s*low)?$/i.exec(token);
+    if (percentile) options.frametime_percentiles.push(Number(percentile[1]));
+    else if (low) options.low_percentiles.push(Number(low[1]));
+  }
+  return options;
+}
+
+function formatPercentiles(p: PercentileMetrics | undefined): string {
+  if (!p) return "";
+  return [
+    ...p.frametime_ms.map((v) => `P${v.percentile} ${v.value.toFixed(2)} ms`),
+    ...p.low_fps.map((v) => `${v.percentile}% Low ${v.value.toFixed(1)}`),
+  ].join(" · ");
+}
+
+const PHASE_LABELS: Partial<Record<CapturePhase, string>> = {
+  starting: "正在启动 PresentMon...",
+  capturing: "● 记录中",
+  paused: "已暂停（不计入统计）",
+  stopping: "正在停止...",
+  finalizing: "正在汇总并保存...",
+};
+
 export default function FpsMonitor() {
   const [running, setRunning] = useState(false);
   const [processName, setProcessName] = useState("");
+  const [selectedPid, setSelectedPid] = useState<number | null>(null);
+  // 附加百分位指标，如 "P95 P99 5%"
+  const [metrics, setMetrics] = useState("");
+  const [captureLog, setCaptureLog] = useState(false);
+  // 定时基准测试时长（秒），留空为手动停止
+  const [benchmarkSecs, setBenchmarkSecs] = useState("");
+  const [countdown, setCountdown] = useState<BenchmarkCountdown | null>(null);
+  const [overlayOn, setOverlayOn] = useState(false);
+  // 基准测试预设，选中时按预设的时长与采样项测试
+  const [presets, setPresets] = useState<BenchmarkPreset[]>([]);
+  const [presetId, setPresetId] = useState("");
   const [games, setGames] = useState<DetectedGame[]>([]);
   const [snapshots, setSnapshots] = useState<FpsSnapshot[]>([]);
   const [latest, setLatest] = useState<FpsSnapshot | null>(null);
   const [session, setSession] = useState<FpsSession | null>(null);
   const [error, setError] = useState<string | null>(null);
+  const [markers, setMarkers] = useState<SessionMarker[]>([]);
+  const [resets, setResets] = useState<DriverReset[]>([]);
+  const [recovered, setRecovered] = useState<RecoveredSession[]>([]);
+  const [queued, setQueued] = useState<string | null>(null);
+  const [phase, setPhase] = useState<CapturePhase>("idle");
+  const [phaseReason, setPhaseReason] = useState("");
+  // 同时进行的其他监测: 会话 ID → 进程名与当前 FPS
+  const [exportedPath, setExportedPath] = useState<string | null>(null);
+  // 进行中的导出任务
+  const [exportTask, setExportTask] = useState<TaskProgress | null>(null);
+  const [others, setOthers] = useState<Record<string, { process_name: string; fps: number | null }>>(
+    {}
+  );
   const chartRef = useRef<FpsSnapshot[]>([]);
+  // 本次监测累计的帧时间分布
+  const [histogram, setHistogram] = useState<FrametimeHistogram>(EMPTY_HISTOGRAM);
+  // 本次监测最近一次卡顿
+  const [lastStutter, setLastStutter] = useState<FpsStutter | null>(null);
+  // 本页面图表显示的监测
+  const activeRef = useRef<string | null>(null);
 
   // 扫描运行中的游戏
   const refreshGames = async () => {
@@ -30,27 +107,75 @@ export default function FpsMonitor() {
       // 自动选择第一个
       if (g.length > 0 && !processName) {
         setProcessName(g[0].process_name);
+        setSelectedPid(g[0].pid);
       }
     } catch (_) {}
   };
 
   useEffect(() => {
     refreshGames();
+    listBenchmarkPresets().then(setPresets).catch(() => {});
     const interval = setInterval(refreshGames, 5000);
 
     // 监听事件
     const unsub1 = onFpsUpdate((snap) => {
+      if (!activeRef.current) activeRef.current = snap.session_id;
+      if (snap.session_id !== activeRef.current) {
+        setOthers((prev) => ({
+          ...prev,
+          [snap.session_id]: { process_name: snap.process_name, fps: snap.fps },
+        }));
+        return;
+      }
       setLatest(snap);
+      if (snap.histogram) {
+        setHistogram((prev) => ({
+          lt_8ms: prev.lt_8ms + snap.histogram.lt_8ms,
+          ms_8_16: prev.ms_8_16 + snap.histogram.ms_8_16,
+          ms_16_33: prev.ms_16_33 + snap.histogram.ms_16_33,
+          gt_33ms: prev.gt_33ms + snap.histogram.gt_33ms,
+        }));
+      }
       chartRef.current = [...chartRef.current.slice(-MAX_CHART_POINTS + 1), snap];
       setSnapshots([...chartRef.current]);
     });
 
-    const unsub2 = onFpsStopped(() => {
-      setRunning(false);
+    const unsub10 = onFpsStutter((s) => {
+      if (s.session_id === activeRef.current) setLastStutter(s);
+    });
+
+    const unsub11 = onBenchmarkCountdown((c) => {
+      if (c.session_id === activeRef.current) setCountdown(c);
+    });
+
+    const unsub2 = onFpsStopped((e) => {
+      setOthers(({ [e.session_id]: _, ...rest }) => rest);
+      if (e.session_id === activeRef.current) {
+        activeRef.current = null;
+        setRunning(false);
+        setCountdown(null);
+      }
+    });
+
+    const unsub9 = onFpsPhase((change) => {
+      if (change.session_id !== activeRef.current) return;
+      setPhase(change.phase);
+      setPhaseReason(change.reason);
+    });
+
+    // 排队中的监测在上一个结束后由后端自动开始
+    const unsub8 = onFpsStarted((e) => {
+      if (activeRef.current && e.session_id !== activeRef.current) return;
+      activeRef.current = e.session_id;
+      setRunning(true);
+      setQueued(null);
     });
 
     const unsub3 = onFpsSessionComplete((s) => {
+      if (activeRef.current && s.session_id !== activeRef.current) return;
       setSession(s);
+      // 网吧模式：结果展示后即从会话库删除
+      markSessionViewed(s.session_id).catch(() => {});
     });
 
     const unsub4 = onFpsError((err) => {
@@ -58,12 +183,45 @@ export default function FpsMonitor() {
       setRunning(false);
     });
 
+    // 界面按钮与本地 API 注入的标记都通过事件推送
+    const unsub5 = onSessionMarker((m) => {
+      setMarkers((prev) => [...prev, m]);
+    });
+
+    const unsub6 = onDriverReset((r) => {
+      setResets((prev) => [...prev, r]);
+    });
+
+    // 上次异常退出时未完成的会话（启动时已恢复，开始新监测前也会检查）
+    takeRecoveredSessions().then(setRecovered).catch(() => {});
+    const unsub7 = onFpsSessionRecovered((list) => {
+      setRecovered((prev) => [...prev, ...list]);
+    });
+
+    // 悬浮窗也可通过快捷键切换，或在最低开销档位开始监测时被关闭
+    getOverlayVisible().then(setOverlayOn).catch(() => {});
+    const unsub13 = onOverlayChanged(setOverlayOn);
+
+    // 全局快捷键在游戏内触发，失败原因显示在这里
+    const unsub12 = onHotkeyTriggered((e) => {
+      if (e.error) setError(`快捷键：${e.error}`);
+    });
+
     return () => {
       clearInterval(interval);
       unsub1.then((fn) => fn());
       unsub2.then((fn) => fn());
       unsub3.then((fn) => fn());
       unsub4.then((fn) => fn());
+      unsub5.then((fn) => fn());
+      unsub6.then((fn) => fn());
+      unsub7.then((fn) => fn());
+      unsub8.then((fn) => fn());
+      unsub9.then((fn) => fn());
+      unsub10.then((fn) => fn());
+      unsub11.then((fn) => fn());
+      unsub12.then((fn) => fn());
+      unsub13.then((fn) => fn());
     };
   }, []);
 
@@ -74,19 +232,120 @@ export default function FpsMonitor() {
     }
     setError(null);
     setSession(null);
+    setCountdown(null);
+    setExportedPath(null);
     setSnapshots([]);
+    setMarkers([]);
+    setResets([]);
     chartRef.current = [];
+    setHistogram(EMPTY_HISTOGRAM);
+    setLastStutter(null);
     try {
-      await startFpsMonitor(processName);
-      setRunning(true);
+      // UWP / Game Pass 游戏、同一游戏多开时按 PID 监测
+      const packaged = games.find(
+        (g) => g.process_name === processName && g.package_name
+      );
+      const instances = games.filter((g) => g.process_name === processName);
+      const pid =
+        packaged?.pid ?? (instances.length > 1 ? selectedPid ?? undefined : undefined);
+      const options = { ...parseMetrics(metrics), capture_log: captureLog };
+      const duration = Number(benchmarkSecs);
+      const plan = duration > 0 ? { duration_secs: duration } : undefined;
+      const outcome =
+        presetId || plan
+          ? await runBenchmark(processName, presetId || undefined, plan, pid, undefined, options)
+          : await startFpsMonitor(processName, pid, undefined, undefined, options);
+      if (outcome.outcome === "queued") {
+        setQueued(`已排队（第 ${outcome.position} 个），${outcome.busy_with} 的监测结束后自动开始`);
+      } else {
+        activeRef.current = outcome.outcome === "started" ? outcome.session_id : null;
+        setRunning(true);
+      }
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  // 监测中再开始另一个进程（如游戏 + 模拟器），结果显示在下方的同时监测列表
+  const handleStartAlso = async () => {
+    if (!processName.trim()) return;
+    setError(null);
+    try {
+      const outcome = await startFpsMonitor(
+        processName,
+        selectedPid ?? undefined,
+        undefined,
+        "reject",
+        { ...parseMetrics(metrics), capture_log: captureLog }
+      );
+      if (outcome.outcome === "started") {
+        setOthers((prev) => ({
+          ...prev,
+          [outcome.session_id]: { process_name: processName, fps: null },
+        }));
+      }
     } catch (e) {
       setError(String(e));
     }
   };
 
+  const histogramTotal =
+    histogram.lt_8ms + histogram.ms_8_16 + histogram.ms_16_33 + histogram.gt_33ms;
+
+  // 等待游戏启动时显示后端给出的原因（如"等待 cs2.exe 启动"）
+  const phaseLabel =
+    phase === "waiting_for_process"
+      ? `${phaseReason}...`
+      : countdown && phase === "capturing"
+        ? `${countdown.stage === "warmup" ? "预热中" : "● 基准测试"}，剩余 ${countdown.remaining_secs} 秒`
+        : PHASE_LABELS[phase];
+
   const handleStop = async () => {
     try {
-      await stopFpsMonitor();
+      await stopFpsMonitor(activeRef.current ?? undefined);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const handleExport = async (format: ExportFormat) => {
+    if (!session) return;
+    setExportedPath(null);
+    try {
+      setExportedPath(
+        await exportSession(session.session_id, undefined, undefined, format, setExportTask)
+      );
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setExportTask(null);
+    }
+  };
+
+  const handleTogglePause = async () => {
+    const sessionId = activeRef.current ?? undefined;
+    try {
+      if (phase === "paused") {
+        await resumeFpsMonitor(sessionId);
+      } else {
+        await pauseFpsMonitor(sessionId);
+      }
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const handleToggleOverlay = async () => {
+    try {
+      await setOverlay(!overlayOn);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const handleMarker = async () => {
+    try {
+      await addSessionMarker(`标记 ${markers.length + 1}`);
     } catch (e) {
       setError(String(e));
     }
@@ -111,29 +370,126 @@ export default function FpsMonitor() {
             <input
               type="text"
               value={processName}
-              onChange={(e) => setProcessName(e.target.value)}
-              placeholder="输入进程名，如 cs2.exe"
+              onChange={(e) => {
+                setProcessName(e.target.value);
+                setSelectedPid(null);
+              }}
+              placeholder="输入进程名，如 cs2.exe（游戏未运行时将等待其启动）"
               className="flex-1 px-3 py-2 text-sm rounded-lg bg-surface-card border border-border text-white placeholder-slate-600 focus:outline-none focus:border-brand-600"
-              disabled={running}
             />
             {games.length > 0 && (
               <select
-                value={processName}
-                onChange={(e) => setProcessName(e.target.value)}
-                disabled={running}
+                value={selectedPid ?? ""}
+                onChange={(e) => {
+                  const game = games.find((g) => g.pid === Number(e.target.value));
+                  setProcessName(game?.process_name ?? "");
+                  setSelectedPid(game?.pid ?? null);
+                }}
                 className="px-3 py-2 text-sm rounded-lg bg-surface-card border border-border text-white focus:outline-none"
               >
                 <option value="">选择运行中的游戏</option>
                 {games.map((g) => (
-                  <option key={g.pid} value={g.process_name}>
+                  <option key={g.pid} value={g.pid}>
                     {g.game_name || g.process_name}
+                    {g.instance > 1 && ` #${g.instance}`}
                   </option>
                 ))}
               </select>
             )}
+            <input
+              type="text"
+              value={metrics}
+              onChange={(e) => setMetrics(e.target.value)}
+              disabled={running}
+              title="除 1% / 0.1% Low 外额外计算的指标：P95 为帧时间百分位，5% 为 5% Low"
+              placeholder="附加指标，如 P95 P99 5%"
+              className="w-44 px-3 py-2 text-sm rounded-lg bg-surface-card border border-border text-white placeholder-slate-600 focus:outline-none focus:border-brand-600"
+            />
+            <select
+              value={presetId}
+              onChange={(e) => setPresetId(e.target.value)}
+              disabled={running}
+              title={presets.find((p) => p.id === presetId)?.description ?? "基准测试预设"}
+              className="px-3 py-2 text-sm rounded-lg bg-surface-card border border-border text-white focus:outline-none"
+            >
+              <option value="">无预设</option>
+              {presets.map((p) => (
+                <option key={p.id} value={p.id}>
+                  {p.name}（{formatDuration(p.duration_secs)}）
+                </option>
+              ))}
+            </select>
+            <input
+              type="number"
+              min={1}
+              value={benchmarkSecs}
+              onChange={(e) => setBenchmarkSecs(e.target.value)}
+              disabled={running}
+              title="定时基准测试：预热（按采集规则）后采集指定秒数并自动停止，留空为手动停止"
+              placeholder={presetId ? "预设时长" : "定时 (秒)"}
+              className="w-24 px-3 py-2 text-sm rounded-lg bg-surface-card border border-border text-white placeholder-slate-600 focus:outline-none focus:border-brand-600"
+            />
+            <label
+              title="逐帧保存时间戳、帧时间、CPU/GPU Busy 与呈现模式，长时间会话也可完整复现"
+              className="flex items-center gap-2 text-sm text-slate-400"
+            >
+              <input
+                type="checkbox"
+                checked={captureLog}
+                onChange={(e) => setCaptureLog(e.target.checked)}
+                disabled={running}
+              />
+              原始帧日志
+            </label>
           </div>
         </div>
 
+        <button
+          onClick={handleToggleOverlay}
+          title="置顶显示当前 FPS / 1% Low / 帧时间，鼠标可穿透（最低开销档位下不可用）"
+          className={`flex items-center gap-2 px-4 py-2 rounded-lg border text-sm transition-colors ${
+            overlayOn
+              ? "bg-brand-600/20 border-brand-600 text-white"
+              : "bg-surface-card border-border text-slate-300 hover:bg-surface-hover"
+          }`}
+        >
+          <PictureInPicture2 size={16} />
+          悬浮窗
+        </button>
+
+        {running && (
+          <button
+            onClick={handleMarker}
+            title="在当前时刻添加标记，外部工具也可通过局域网 API 注入"
+            className="flex items-center gap-2 px-4 py-2 rounded-lg bg-surface-card border border-border text-slate-300 text-sm hover:bg-surface-hover transition-colors"
+          >
+            <Flag size={16} />
+            添加标记
+          </button>
+        )}
+
+        {running && (
+          <button
+            onClick={handleStartAlso}
+            title="保持当前监测，同时监测所选的另一个进程（如游戏 + 模拟器）"
+            className="flex items-center gap-2 px-4 py-2 rounded-lg bg-surface-card border border-border text-slate-300 text-sm hover:bg-surface-hover transition-colors"
+          >
+            <Play size={16} />
+            同时监测
+          </button>
+        )}
+
+        {(phase === "capturing" || phase === "paused") && (
+          <button
+            onClick={handleTogglePause}
+            title="暂停期间的帧（加载画面、切出游戏等）不计入最终统计"
+            className="flex items-center gap-2 px-4 py-2 rounded-lg bg-surface-card border border-border text-slate-300 text-sm hover:bg-surface-hover transition-colors"
+          >
+            {phase === "paused" ? <Play size={16} /> : <Pause size={16} />}
+            {phase === "paused" ? "继续" : "暂停"}
+          </button>
+        )}
+
         {running ? (
           <button
             onClick={handleStop}
@@ -160,11 +516,80 @@ export default function FpsMonitor() {
         </div>
       )}
 
+      {Object.keys(others).length > 0 && (
+        <div className="flex flex-wrap gap-2">
+          {Object.entries(others).map(([id, o]) => (
+            <div
+              key={id}
+              className="flex items-center gap-2 px-3 py-1.5 rounded-lg bg-surface-card border border-border text-xs text-slate-300"
+            >
+              <Activity size={12} className="text-green-400" />
+              {o.process_name}
+              <span className="text-white">{o.fps != null ? `${o.fps.toFixed(1)} FPS` : "等待数据..."}</span>
+              <button
+                onClick={() => stopFpsMonitor(id).catch((e) => setError(String(e)))}
+                title="停止并保存该监测"
+                className="text-slate-500 hover:text-red-400"
+              >
+                <Square size={12} />
+              </button>
+            </div>
+          ))}
+        </div>
+      )}
+
+      {queued && (
+        <div className="flex items-center gap-2 p-3 rounded-lg bg-brand-500/10 border border-brand-500/30 text-brand-400 text-sm">
+          <Clock size={16} />
+          {queued}
+        </div>
+      )}
+
+      {/* 崩溃恢复 */}
+      {recovered.length > 0 && (
+        <div className="p-3 rounded-lg bg-yellow-500/10 border border-yellow-500/30 text-yellow-400 text-sm space-y-1">
+          <div className="flex items-center justify-between gap-2 font-medium">
+            <span className="flex items-center gap-2">
+              <AlertTriangle size={16} />
+              上次监测因应用异常退出而中断，已从检查点恢复到历史记录
+            </span>
+            <button
+              onClick={() => setRecovered([])}
+              className="text-xs text-yellow-300/80 hover:text-yellow-200"
+            >
+              知道了
+            </button>
+          </div>
+          {recovered.map((r) => (
+            <div key={r.session_id} className="text-xs text-yellow-300/80">
+              {r.process_name} · {r.total_frames} 帧 · {formatDuration(r.duration_secs)}
+              {r.stopped_presentmon && " · 已结束遗留的 PresentMon"}
+            </div>
+          ))}
+        </div>
+      )}
+
+      {/* 显卡驱动重置 (TDR) */}
+      {(session ? session.driver_resets : resets).length > 0 && (
+        <div className="p-3 rounded-lg bg-red-500/10 border border-red-500/30 text-red-400 text-sm space-y-1">
+          <div className="flex items-center gap-2 font-medium">
+            <AlertTriangle size={16} />
+            测试期间显卡驱动发生重置，对应时段的帧时间空洞不是游戏卡顿
+          </div>
+          {(session ? session.driver_resets : resets).map((r, i) => (
+            <div key={i} className="text-xs text-red-300/80" title={r.message}>
+              {formatDuration(r.at_secs)} · {r.provider} 事件 {r.event_id}
+              {r.hole_ms != null && ` · 帧时间空洞约 ${r.hole_ms.toFixed(0)} ms`}
+            </div>
+          ))}
+        </div>
+      )}
+
       {/* 实时数据概览 */}
       {latest && (
         <div className="grid grid-cols-4 gap-3">
           <StatCard
-            label="当前 FPS"
+            label={latest.smoothing === "average" ? "当前 FPS" : `当前 FPS（原始 ${latest.raw_fps.toFixed(1)}）`}
             value={latest.fps.toFixed(1)}
             color={fpsColor(latest.fps)}
             icon={<Activity size={16} />}
@@ -190,10 +615,57 @@ export default function FpsMonitor() {
         </div>
       )}
 
+      {latest?.present && <PresentLine present={latest.present} />}
+      {latest?.latency && <LatencyLine latency={latest.latency} />}
+      {latest && latest.adapters.length > 1 && <AdapterLine adapters={latest.adapters} />}
+
+      {latest && formatPercentiles(latest.percentiles) && (
+        <div className="text-xs text-slate-400">
+          附加指标（快照窗口）: {formatPercentiles(latest.percentiles)}
+        </div>
+      )}
+
+      {/* 帧时间分布 */}
+      {latest && histogramTotal > 0 && (
+        <div className="rounded-xl bg-surface-card border border-border p-4">
+          <div className="text-xs text-slate-500 mb-2">帧时间分布（本次监测）</div>
+          <div className="flex h-3 rounded overflow-hidden">
+            {HISTOGRAM_BUCKETS.map(([key, , color]) => (
+              <div
+                key={key}
+                className={color}
+                style={{ width: `${(histogram[key] / histogramTotal) * 100}%` }}
+              />
+            ))}
+          </div>
+          <div className="flex flex-wrap gap-4 mt-2 text-xs text-slate-400">
+            {HISTOGRAM_BUCKETS.map(([key, label, color]) => (
+              <span key={key} className="flex items-center gap-1">
+                <span className={`inline-block w-2 h-2 rounded-sm ${color}`} />
+                {label} {((histogram[key] / histogramTotal) * 100).toFixed(1)}%
+              </span>
+            ))}
+          </div>
+        </div>
+      )}
+
       {/* FPS 图表 */}
       <div className="rounded-xl bg-surface-card border border-border p-4">
         <div className="text-xs text-slate-500 mb-3">
-          FPS 实时曲线 {running && <span className="text-green-400">● 记录中</span>}
+          FPS 实时曲线{" "}
+          {running && phaseLabel && (
+            <span className={phase === "capturing" ? "text-green-400" : "text-yellow-400"}>
+              {phaseLabel}
+            </span>
+          )}
+          {markers.length > 0 && <span className="text-amber-400"> · {markers.length} 个标记</span>}
+          {lastStutter && lastStutter.count > 0 && (
+            <span className="text-red-400">
+              {" "}
+              · {lastStutter.count} 次卡顿（最近 {lastStutter.frametime_ms.toFixed(0)} ms，
+              {lastStutter.ratio.toFixed(1)}× 中位数）
+            </span>
+          )}
         </div>
         <div className="h-64">
           {snapshots.length > 1 ? (
@@ -243,7 +715,7 @@ export default function FpsMonitor() {
             </ResponsiveContainer>
           ) : (
             <div className="flex items-center justify-center h-full text-slate-600 text-sm">
-              {running ? "等待数据..." : "开始监测后显示 FPS 曲线"}
+              {running ? phaseLabel ?? "等待数据..." : "开始监测后显示 FPS 曲线"}
             </div>
           )}
         </div>
@@ -252,20 +724,255 @@ export default function FpsMonitor() {
       {/* Session 总结 */}
       {session && (
         <div className="rounded-xl bg-surface-card border border-green-500/30 p-5">
-          <div className="text-sm font-semibold text-green-400 mb-3">
-            ✅ 监测完成 — {session.process_name}
+          <div className="flex items-center justify-between mb-3">
+            <div className="text-sm font-semibold text-green-400">
+              ✅ 监测完成 — {session.process_name}
+            </div>
+            <div className="flex items-center gap-2 text-xs">
+              {exportTask && (
+                <>
+                  <span className="text-slate-400">
+                    导出中
+                    {exportTask.total > 0 &&
+                      ` ${Math.round((exportTask.done / exportTask.total) * 100)}%`}
+                  </span>
+                  <button
+                    onClick={() => cancelTask(exportTask.task_id).catch(() => {})}
+                    className="px-2 py-1 rounded bg-surface border border-border text-slate-300 hover:text-white"
+                  >
+                    取消
+                  </button>
+                </>
+              )}
+              {(["csv", "json"] as ExportFormat[]).map((format) => (
+                <button
+                  key={format}
+                  disabled={exportTask !== null}
+                  onClick={() => handleExport(format)}
+                  title="导出摘要与逐帧帧时间到下载目录"
+                  className="px-2 py-1 rounded bg-surface border border-border text-slate-300 hover:text-white"
+                >
+                  导出 {format.toUpperCase()}
+                </button>
+              ))}
+            </div>
           </div>
-          <div className="grid grid-cols-3 sm:grid-cols-6 gap-4">
+          {exportedPath && <div className="text-xs text-slate-400 mb-3">已导出到 {exportedPath}</div>}
+          {session.qoe && <QoeSummary qoe={session.qoe} />}
+          {session.vrr && <VrrSummary vrr={session.vrr} />}
+          {session.present && (
+            <div className="mb-4">
+              <PresentLine present={session.present} />
+            </div>
+          )}
+          {session.latency && (
+            <div className="mb-4">
+              <LatencyLine latency={session.latency} />
+            </div>
+          )}
+          {session.vram && (
+            <div
+              className={`text-xs mb-4 ${
+                session.vram.stable === false ? "text-yellow-400" : "text-slate-400"
+              }`}
+            >
+              显存稳定性: {session.vram.note}
+            </div>
+          )}
+          <div className="grid grid-cols-3 sm:grid-cols-7 gap-4">
             <MiniStat label="平均 FPS" value={session.avg_fps.toFixed(1)} />
             <MiniStat label="1% Low" value={session.fps_1_low.toFixed(1)} />
             <MiniStat label="0.1% Low" value={session.fps_01_low.toFixed(1)} />
             <MiniStat label="最高" value={session.max_fps.toFixed(0)} />
             <MiniStat label="最低" value={session.min_fps.toFixed(0)} />
             <MiniStat label="总帧数" value={session.total_frames.toLocaleString()} />
+            <MiniStat label="卡顿" value={session.stutter_count.toString()} />
           </div>
+          {session.pacing.p99_ms > 0 && (
+            <div className="grid grid-cols-3 sm:grid-cols-6 gap-4 mt-3">
+              <MiniStat label="帧时间标准差" value={`${session.pacing.stdev_ms.toFixed(2)} ms`} />
+              <MiniStat label="变异系数" value={`${(session.pacing.cv * 100).toFixed(1)}%`} />
+              <MiniStat label="P99 帧时间" value={`${session.pacing.p99_ms.toFixed(1)} ms`} />
+              <MiniStat label="P99.9 帧时间" value={`${session.pacing.p999_ms.toFixed(1)} ms`} />
+              <MiniStat label="> 16.7 ms" value={session.pacing.over_16ms.toLocaleString()} />
+              <MiniStat label="> 33.3 ms" value={session.pacing.over_33ms.toLocaleString()} />
+            </div>
+          )}
           <div className="mt-3 text-xs text-slate-500">
             监测时长: {formatDuration(session.duration_secs)}
+            {session.pauses.length > 0 &&
+              `（已扣除 ${session.pauses.length} 次中断共 ${formatDuration(
+                session.pauses.reduce((sum, p) => sum + p.duration_secs, 0)
+              )}${session.pauses.some((p) => p.reason === "suspend") ? "，含系统睡眠" : ""}）`}
+            {session.capture_profile === "minimal" && " · 最低开销档位"}
+            {formatPercentiles(session.percentiles) && ` · ${formatPercentiles(session.percentiles)}`}
+            {session.status === "interrupted_by_sleep" && (
+              <span className="text-yellow-400"> · 系统睡眠导致监测提前结束</span>
+            )}
+            {session.status === "recovered" && (
+              <span className="text-yellow-400"> · 应用异常退出后恢复</span>
+            )}
+            {session.benchmark &&
+              (session.benchmark.completed ? (
+                ` · 定时测试 ${session.benchmark.duration_secs} 秒${
+                  session.benchmark.preset
+                    ? `（${presets.find((p) => p.id === session.benchmark?.preset)?.name ?? session.benchmark.preset}）`
+                    : ""
+                }`
+              ) : (
+                <span className="text-yellow-400">
+                  {" "}· 定时测试未采满 {session.benchmark.duration_secs} 秒
+                </span>
+              ))}
+            {session.overhead && (
+              <span title={session.overhead.processes.join(", ")}>
+                {" "}· 自身开销: CPU {session.overhead.avg_cpu_percent}% / 内存{" "}
+                {session.overhead.avg_ram_mb.toFixed(0)} MB
+                {session.overhead.avg_gpu_percent != null &&
+                  ` / GPU ${session.overhead.avg_gpu_percent}%`}
+              </span>
+            )}
           </div>
+          {session.plugin_metrics.length > 0 && (
+            <div className="mt-2 flex flex-wrap gap-x-4 gap-y-1 text-xs text-slate-400">
+              {session.plugin_metrics.map((m) => (
+                <span key={`${m.plugin_id}.${m.key}`} title={m.plugin_id}>
+                  {m.label}: 平均 {m.avg} / 最高 {m.max}
+                  {m.unit ? ` ${m.unit}` : ""}
+                </span>
+              ))}
+            </div>
+          )}
+          {session.dpc_latency && (
+            <div className="mt-2 text-xs text-slate-400">
+              {session.dpc_latency.error ? (
+                <span>DPC/ISR 延迟采样不可用：{session.dpc_latency.error}</span>
+              ) : (
+                <>
+                  <div>
+                    DPC 最长 {session.dpc_latency.max_dpc_us.toFixed(0)} µs · ISR 最长{" "}
+                    {session.dpc_latency.max_isr_us.toFixed(0)} µs · 延迟风暴{" "}
+                    {session.dpc_latency.storm_secs} 秒 · 卡顿 {session.dpc_latency.stutters} 次（
+                    {session.dpc_latency.stutters_with_storm} 次与风暴重合）
+                  </div>
+                  {session.dpc_latency.offenders.map((o) => (
+                    <div key={o.driver} className="text-slate-500">
+                      {o.driver}: 最长 {o.max_us.toFixed(0)} µs · 累计 {o.total_ms.toFixed(1)} ms ·
+                      DPC {o.dpc_count} / ISR {o.isr_count}
+                    </div>
+                  ))}
+                </>
+              )}
+            </div>
+          )}
+          {session.launch && (
+            <div className="mt-2 text-xs text-slate-400">
+              启动耗时：进程启动 → 第一帧 {session.launch.time_to_first_frame_secs} 秒
+              {session.launch.time_to_stable_secs != null
+                ? ` · → 帧时间稳定 ${session.launch.time_to_stable_secs} 秒`
+                : " · 测试结束前帧时间未稳定"}
+            </div>
+          )}
+          {session.memory_pressure && (
+            <div className="mt-2 text-xs text-slate-400">
+              内存 {session.memory_pressure.total_ram_gb} GB · 硬页错误峰值{" "}
+              {session.memory_pressure.peak_hard_faults_per_sec} 次/秒 · 最低可用{" "}
+              {session.memory_pressure.min_available_mb} MB · 待机列表最低{" "}
+              {session.memory_pressure.min_standby_mb} MB
+              {session.memory_pressure.stutters_with_pressure > 0 && (
+                <span className="text-yellow-400">
+                  {" "}
+                  · {session.memory_pressure.stutters_with_pressure} 次卡顿与内存压力重合
+                </span>
+              )}
+            </div>
+          )}
+          {session.background_activity.length > 0 && (
+            <div className="mt-2 text-xs text-yellow-400/90">
+              测试期间有后台服务活动：
+              {session.background_activity
+                .map((b) => `${b.process_name}（${formatDuration(b.first_at_secs)} 起，共 ${b.active_secs} 秒）`)
+                .join("、")}
+            </div>
+          )}
+          {session.markers.length > 0 && (
+            <div className="mt-2 space-y-0.5 text-xs text-slate-400">
+              {session.markers.map((m, i) => (
+                <div key={i}>
+                  <Flag size={10} className="inline mr-1 text-amber-400" />
+                  {formatDuration(m.at_secs)} {m.label}
+                  {m.source && <span className="text-slate-500"> · {m.source}</span>}
+                  {m.uncertainty_ms != null && (
+                    <span className="text-slate-500"> ±{m.uncertainty_ms.toFixed(1)} ms</span>
+                  )}
+                </div>
+              ))}
+            </div>
+          )}
+        </div>
+      )}
+
+      <CsvImport />
+    </div>
+  );
+}
+
+const VERDICT_LABELS: Record<Verdict, [string, string]> = {
+  valid: ["校验通过", "text-green-400"],
+  flagged: ["已导入，需人工确认", "text-yellow-400"],
+  rejected: ["已拒绝导入", "text-red-400"],
+};
+
+/** 导入第三方工具录制的帧时间 CSV，导入前校验数据完整性 */
+function CsvImport() {
+  const [path, setPath] = useState("");
+  const [result, setResult] = useState<ImportResult | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const handleImport = async () => {
+    setError(null);
+    setResult(null);
+    try {
+      setResult(await importFrameCsv(path.trim()));
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  return (
+    <div className="rounded-xl bg-surface-card border border-border p-5 space-y-3">
+      <div className="text-sm font-semibold text-white">导入 CSV</div>
+      <div className="text-xs text-slate-500">
+        支持 PresentMon、FrameView、OCAT、CapFrameX 导出的帧时间 CSV
+      </div>
+      <div className="flex gap-2">
+        <input
+          type="text"
+          value={path}
+          placeholder="CSV 文件路径"
+          onChange={(e) => setPath(e.target.value)}
+          className="flex-1 px-3 py-2 text-sm rounded-lg bg-surface border border-border text-white placeholder-slate-600 focus:outline-none focus:border-brand-600"
+        />
+        <button
+          onClick={handleImport}
+          disabled={!path.trim()}
+          className="px-4 py-2 text-sm rounded-lg bg-surface border border-border text-slate-300 hover:text-white disabled:opacity-50"
+        >
+          导入
+        </button>
+      </div>
+      {error && <div className="text-xs text-red-400">{error}</div>}
+      {result && (
+        <div className="space-y-1 text-xs">
+          <div className={VERDICT_LABELS[result.report.verdict][1]}>
+            {VERDICT_LABELS[result.report.verdict][0]} · {result.report.frames.toLocaleString()} 帧
+            {result.session && ` · 平均 ${result.session.avg_fps.toFixed(1)} FPS`}
+          </div>
+          {result.report.issues.map((i) => (
+            <div key={i.code} className={i.severity === "error" ? "text-red-400" : "text-yellow-400"}>
+              {i.message}
+            </div>
+          ))}
         </div>
       )}
     </div>
@@ -288,6 +995,133 @@ function StatCard({
   );
 }
 
+const QOE_GRADES: Record<QoeGrade, { label: string; color: string }> = {
+  excellent: { label: "非常流畅", color: "text-green-400" },
+  good: { label: "流畅", color: "text-lime-400" },
+  fair: { label: "一般", color: "text-yellow-400" },
+  poor: { label: "卡顿明显", color: "text-red-400" },
+};
+
+/** 体验评分：比平均 FPS 更接近实际感受，放在会话总结最前面 */
+function QoeSummary({ qoe }: { qoe: QoeScore }) {
+  const grade = QOE_GRADES[qoe.grade];
+  const parts: [string, number, string][] = [
+    ["流畅度", qoe.fluency, `相对 ${qoe.refresh_hz ?? 60} Hz${qoe.refresh_hz ? "" : "（估算）"}`],
+    ["帧时间一致性", qoe.consistency, "相邻帧的帧时间变化"],
+    ["卡顿", qoe.stutter, `每分钟 ${qoe.stutters_per_min} 次`],
+    ["刷新率匹配", qoe.refresh_fit, `${qoe.below_vrr_pct}% 的帧低于 VRR 下限`],
+  ];
+  return (
+    <div className="flex items-center gap-6 mb-4 p-4 rounded-lg bg-surface border border-border">
+      <div className="text-center">
+        <div className={`text-4xl font-bold ${grade.color}`}>{qoe.score.toFixed(0)}</div>
+        <div className={`text-xs ${grade.color}`}>体验评分 · {grade.label}</div>
+      </div>
+      <div className="grid grid-cols-2 gap-x-6 gap-y-2 flex-1">
+        {parts.map(([label, value, hint]) => (
+          <div key={label} title={hint}>
+            <div className="flex justify-between text-xs text-slate-500">
+              <span>{label}</span>
+              <span>{Math.round(value * 100)}</span>
+            </div>
+            <div className="h-1.5 rounded bg-surface-card overflow-hidden">
+              <div className="h-full bg-brand-600" style={{ width: `${value * 100}%` }} />
+            </div>
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
+
+/** VRR 覆盖率：帧率超出上限时建议限帧，跌破下限时建议降低画质 */
+function VrrSummary({ vrr }: { vrr: VrrReport }) {
+  if (vrr.status === "inactive") {
+    return (
+      <div className="mb-4 text-xs text-slate-400">
+        <span className="text-yellow-400">VRR 未启用</span>
+        （画面经 DWM 合成，可切换为独占全屏或开启窗口化游戏优化；显示器范围 {vrr.range.min_hz}–
+        {vrr.range.max_hz} Hz）
+      </div>
+    );
+  }
+  const hint =
+    vrr.above_range_pct >= 5
+      ? `有 ${vrr.above_range_pct}% 的帧高于 ${vrr.effective_max_hz} Hz，可将帧率上限设为略低于刷新率`
+      : vrr.below_range_pct >= 5
+        ? `有 ${vrr.below_range_pct}% 的帧低于 ${vrr.range.min_hz} Hz${
+            vrr.lfc ? "，由 LFC 倍帧显示" : "，显示器不支持 LFC，会出现抖动"
+          }`
+        : null;
+  return (
+    <div className="mb-4 text-xs text-slate-400">
+      VRR 覆盖率{" "}
+      <span className={vrr.coverage_pct >= 90 ? "text-green-400" : "text-yellow-400"}>
+        {vrr.coverage_pct}%
+      </span>
+      （显示器范围 {vrr.range.min_hz}–{vrr.range.max_hz} Hz{vrr.lfc ? "，支持 LFC" : ""}）
+      {hint && ` · ${hint}`}
+    </div>
+  );
+}
+
+/** 呈现模式、垂直同步与撕裂，DWM 合成时标黄 */
+function PresentLine({ present }: { present: PresentInfo }) {
+  const flag = (v: boolean | null) => (v == null ? "未知" : v ? "开" : "关");
+  return (
+    <div className="text-xs text-slate-400">
+      呈现模式{" "}
+      <span className={present.composed ? "text-yellow-400" : "text-white"}>
+        {present.present_mode}
+      </span>
+      （{present.present_mode_pct}% 的帧）· 垂直同步 {flag(present.vsync)}
+      {present.sync_interval != null &&
+        present.sync_interval > 1 &&
+        ` (间隔 ${present.sync_interval})`}
+      {" · "}允许撕裂 {flag(present.allows_tearing)}
+      {present.composed && " · 经 DWM 合成，可能增加延迟并限制帧率"}
+    </div>
+  );
+}
+
+/** 输入到画面的延迟：平均 / 中位数 / P95 */
+function LatencyLine({ latency }: { latency: InputLatency }) {
+  const rows: [string, LatencySummary | null][] = [
+    ["显示延迟", latency.display],
+    ["点击到画面", latency.click_to_photon],
+  ];
+  return (
+    <div className="text-xs text-slate-400 space-y-0.5">
+      {rows.map(
+        ([label, s]) =>
+          s && (
+            <div key={label}>
+              {label}: 平均 <span className="text-white">{s.avg_ms.toFixed(1)} ms</span>
+              {" · "}中位数 {s.median_ms.toFixed(1)} ms · P95 {s.p95_ms.toFixed(1)} ms
+              （{s.samples} 帧）
+            </div>
+          ),
+      )}
+    </div>
+  );
+}
+
+/** 多显卡系统中各适配器的占用，渲染适配器附带 GPU 占用时间 */
+function AdapterLine({ adapters }: { adapters: AdapterUsage[] }) {
+  return (
+    <div className="text-xs text-slate-400 space-y-0.5">
+      {adapters.map((a) => (
+        <div key={a.luid}>
+          <span className={a.render ? "text-white" : ""}>{a.name ?? a.luid}</span>
+          {a.render && "（渲染）"}: 整机 {a.utilization_percent.toFixed(0)}% · 游戏{" "}
+          {a.process_percent.toFixed(0)}%
+          {a.gpu_busy_ms != null && ` · GPU 占用 ${a.gpu_busy_ms.toFixed(2)} ms`}
+        </div>
+      ))}
+    </div>
+  );
+}
+
 function MiniStat({ label, value }: { label: string; value: string }) {
   return (
     <div>
@@ -0,0 +1,455 @@
+import { useEffect, useState } from "react";
+import { History as HistoryIcon, ChevronLeft, ChevronRight, Download, Merge, Trash2 } from "lucide-react";
+import {
+  CartesianGrid, Line, LineChart, ResponsiveContainer, Tooltip, XAxis, YAxis,
+} from "recharts";
+import {
+  analyzeLoadless, cancelTask, deleteSessions, exportLoadSegments, exportSessions,
+  getSessionTimeline, listSessionTags, mergeSessions, querySessions, setSessionTags,
+} from "../lib/tauri-api";
+import type {
+  BulkResult, LoadlessReport, SessionFilter, SessionPage, SessionSort, SessionTimeline, SortField,
+  TaskProgress,
+} from "../lib/types";
+
+const PAGE_SIZE = 50;
+
+function describe(action: string, r: BulkResult): string {
+  const failed = r.failed.map(([id, reason]) => `${id}: ${reason}`).join("；");
+  return `${action} ${r.succeeded.length} 条${failed ? `，失败: ${failed}` : ""}`;
+}
+
+/** 速通计时格式 H:MM:SS.mmm */
+function clock(secs: number): string {
+  const ms = Math.round(secs * 1000);
+  const h = Math.floor(ms / 3_600_000);
+  const m = Math.floor(ms / 60_000) % 60;
+  const s = Math.floor(ms / 1000) % 60;
+  const pad = (v: number, n: number) => v.toString().padStart(n, "0");
+  return `${h}:${pad(m, 2)}:${pad(s, 2)}.${pad(ms % 1000, 3)}`;
+}
+
+const SORT_LABELS: [SortField, string][] = [
+  ["started_at", "时间"],
+  ["game_name", "游戏"],
+  ["avg_fps", "平均 FPS"],
+  ["fps_1_low", "1% Low"],
+  ["duration", "时长"],
+];
+
+/** 历史记录：筛选、排序与分页都在后端完成，前端只拿当前页的摘要 */
+export default function History() {
+  const [filter, setFilter] = useState<SessionFilter>({});
+  const [sort, setSort] = useState<SessionSort>({ field: "started_at", descending: true });
+  const [page, setPage] = useState(0);
+  const [result, setResult] = useState<SessionPage | null>(null);
+  const [allTags, setAllTags] = useState<string[]>([]);
+  const [error, setError] = useState<string | null>(null);
+  const [selected, setSelected] = useState<Set<string>>(new Set());
+  const [notice, setNotice] = useState<string | null>(null);
+  // 进行中的批量导出
+  const [exportTask, setExportTask] = useState<TaskProgress | null>(null);
+  const [trend, setTrend] = useState<SessionTimeline | null>(null);
+  const [loadless, setLoadless] = useState<LoadlessReport | null>(null);
+
+  const load = () => {
+    querySessions(filter, sort, { page, page_size: PAGE_SIZE })
+      .then((r) => {
+        setResult(r);
+        setError(null);
+      })
+      .catch((e) => setError(String(e)));
+  };
+
+  useEffect(load, [filter, sort, page]);
+
+  useEffect(() => {
+    listSessionTags().then(setAllTags).catch(() => {});
+  }, []);
+
+  const updateFilter = (patch: Partial<SessionFilter>) => {
+    setFilter({ ...filter, ...patch });
+    setPage(0);
+  };
+
+  const toggleSort = (field: SortField) => {
+    setSort(sort.field === field ? { field, descending: !sort.descending } : { field, descending: true });
+    setPage(0);
+  };
+
+  const editTags = async (sessionId: string, current: string[]) => {
+    const input = window.prompt("标签（用逗号分隔）", current.join(", "));
+    if (input === null) return;
+    try {
+      await setSessionTags(sessionId, input.split(/[,，]/));
+      load();
+      listSessionTags().then(setAllTags).catch(() => {});
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  // 趋势图来自每秒聚合数据，不依赖原始帧记录
+  const toggleTrend = async (id: string) => {
+    setLoadless(null);
+    if (trend?.session_id === id) {
+      setTrend(null);
+      return;
+    }
+    try {
+      setTrend(await getSessionTimeline(id));
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  // 无加载分析需要原始帧时间，旧版本录制的会话不可用
+  const runLoadless = async (id: string) => {
+    try {
+      setLoadless(await analyzeLoadless(id));
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const handleExportLoads = async (id: string) => {
+    try {
+      setNotice(`加载段已导出到 ${await exportLoadSegments(id)}`);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const trendPoints = trend
+    ? trend.at_secs.map((at, i) => ({ at, fps: trend.fps[i], low: trend.fps_1_low[i] }))
+    : [];
+
+  const toggleSelected = (id: string) => {
+    const next = new Set(selected);
+    if (next.has(id)) next.delete(id);
+    else next.add(id);
+    setSelected(next);
+  };
+
+  const runBulk = async (action: () => Promise<string>) => {
+    setError(null);
+    try {
+      setNotice(await action());
+      setSelected(new Set());
+      load();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const handleDelete = () => {
+    if (!window.confirm(`确定删除选中的 ${selected.size} 条记录？`)) return;
+    runBulk(async () => describe("已删除", await deleteSessions([...selected])));
+  };
+
+  const handleExport = () =>
+    runBulk(async () => {
+      try {
+        const r = await exportSessions([...selected], undefined, undefined, setExportTask);
+        return describe("已导出到下载目录", r);
+      } finally {
+        setExportTask(null);
+      }
+    });
+
+  const handleMerge = () =>
+    runBulk(async () => {
+      const merged = await mergeSessions([...selected]);
+      return `已合并为 ${merged.session_id}（1% / 0.1% Low 为按各段估算的近似值）`;
+    });
+
+  const pages = result ? Math.max(1, Math.ceil(result.total / PAGE_SIZE)) : 1;
+  const inputClass =
+    "px-2.5 py-1.5 text-xs rounded-lg bg-surface border border-border text-white focus:outline-none focus:border-brand-600";
+
+  return (
+    <div className="p-6 space-y-4">
+      <h1 className="text-xl font-bold text-white flex items-center gap-2">
+        <HistoryIcon size={22} className="text-brand-400" />
+        历史记录
+        {result && <span className="text-xs font-normal text-slate-500">共 {result.total} 条</span>}
+      </h1>
+
+      <div className="flex flex-wrap gap-2">
+        <input
+          placeholder="游戏（进程名 / 中英文名）"
+          value={filter.game ?? ""}
+          onChange={(e) => updateFilter({ game: e.target.value || null })}
+          className={inputClass}
+        />
+        <input
+          type="date"
+          value={filter.date_from ?? ""}
+          onChange={(e) => updateFilter({ date_from: e.target.value || null })}
+          className={inputClass}
+        />
+        <input
+          type="date"
+          value={filter.date_to ?? ""}
+          onChange={(e) => updateFilter({ date_to: e.target.value || null })}
+          className={inputClass}
+        />
+        <input
+          type="number"
+          min={0}
+          placeholder="最短时长 (秒)"
+          value={filter.min_duration_secs ?? ""}
+          onChange={(e) =>
+            updateFilter({ min_duration_secs: e.target.value ? Number(e.target.value) : null })
+          }
+          className={`${inputClass} w-32`}
+        />
+        <select
+          value={filter.tags?.[0] ?? ""}
+          onChange={(e) => updateFilter({ tags: e.target.value ? [e.target.value] : [] })}
+          className={inputClass}
+        >
+          <option value="">全部标签</option>
+          {allTags.map((tag) => (
+            <option key={tag} value={tag}>
+              {tag}
+            </option>
+          ))}
+        </select>
+      </div>
+      {selected.size > 0 && (
+        <div className="flex items-center gap-2 text-xs">
+          <span className="text-slate-400">已选 {selected.size} 条</span>
+          {exportTask ? (
+            <>
+              <span className="text-slate-400">
+                导出中 {exportTask.done}/{exportTask.total || selected.size}
+              </span>
+              <button
+                onClick={() => cancelTask(exportTask.task_id).catch(() => {})}
+                className="px-2.5 py-1 rounded-lg bg-surface-card border border-border text-slate-300 hover:text-white"
+              >
+                取消
+              </button>
+            </>
+          ) : (
+            <button
+              onClick={handleExport}
+              className="flex items-center gap-1 px-2.5 py-1 rounded-lg bg-surface-card border border-border text-slate-300 hover:text-white"
+            >
+              <Download size={12} /> 导出
+            </button>
+          )}
+          <button
+            onClick={handleMerge}
+            disabled={selected.size < 2}
+            title="同一游戏被意外拆分成多段的记录"
+            className="flex items-center gap-1 px-2.5 py-1 rounded-lg bg-surface-card border border-border text-slate-300 hover:text-white disabled:opacity-40"
+          >
+            <Merge size={12} /> 合并
+          </button>
+          <button
+            onClick={handleDelete}
+            className="flex items-center gap-1 px-2.5 py-1 rounded-lg bg-surface-card border border-border text-red-400 hover:text-red-300"
+          >
+            <Trash2 size={12} /> 删除
+          </button>
+        </div>
+      )}
+      {error && <div className="text-xs text-red-400">{error}</div>}
+      {notice && <div className="text-xs text-slate-400">{notice}</div>}
+
+      <div className="rounded-xl bg-surface-card border border-border p-4">
+        <table className="w-full text-sm">
+          <thead className="text-xs text-slate-500">
+            <tr>
+              <th className="w-6">
+                <input
+                  type="checkbox"
+                  checked={
+                    !!result?.items.length && result.items.every((s) => selected.has(s.session_id))
+                  }
+                  onChange={(e) =>
+                    setSelected(
+                      e.target.checked ? new Set(result?.items.map((s) => s.session_id)) : new Set()
+                    )
+                  }
+                />
+              </th>
+              {SORT_LABELS.map(([field, label]) => (
+                <th
+                  key={field}
+                  onClick={() => toggleSort(field)}
+                  className={`py-1 cursor-pointer hover:text-white ${
+                    field === "started_at" || field === "game_name" ? "text-left" : "text-right"
+                  }`}
+                >
+                  {label}
+                  {sort.field === field && (sort.descending ? " ↓" : " ↑")}
+                </th>
+              ))}
+              <th className="text-left pl-4">标签</th>
+            </tr>
+          </thead>
+          <tbody>
+            {result?.items.map((s) => (
+              <tr key={s.session_id} className="border-t border-border">
+                <td>
+                  <input
+                    type="checkbox"
+                    checked={selected.has(s.session_id)}
+                    onChange={() => toggleSelected(s.session_id)}
+                  />
+                </td>
+                <td className="py-1.5 text-slate-400 text-xs">{s.started_at ?? s.session_id}</td>
+                <td className="text-white">
+                  <button
+                    onClick={() => toggleTrend(s.session_id)}
+                    title="查看每秒帧率趋势"
+                    className="hover:text-brand-400"
+                  >
+                    {s.game_name}
+                  </button>
+                  {s.imported && <span className="ml-1 text-[10px] text-slate-500">导入</span>}
+                  {s.status === "interrupted_by_sleep" && (
+                    <span className="ml-1 text-[10px] text-yellow-400">睡眠中断</span>
+                  )}
+                  {s.status === "recovered" && (
+                    <span className="ml-1 text-[10px] text-yellow-400">崩溃恢复</span>
+                  )}
+                </td>
+                <td className="text-right text-white">{s.avg_fps}</td>
+                <td className="text-right text-white">{s.fps_1_low}</td>
+                <td className="text-right text-slate-400">{Math.round(s.duration_secs)}s</td>
+                <td className="pl-4">
+                  <button
+                    onClick={() => editTags(s.session_id, s.tags)}
+                    className="text-xs text-slate-400 hover:text-white"
+                  >
+                    {s.tags.length > 0 ? s.tags.join(" · ") : "+ 标签"}
+                  </button>
+                </td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+        {result && result.items.length === 0 && (
+          <div className="py-6 text-center text-xs text-slate-500">没有符合条件的记录</div>
+        )}
+      </div>
+
+      {trend && (
+        <div className="rounded-xl bg-surface-card border border-border p-4">
+          <div className="flex items-center justify-between mb-2 text-xs text-slate-400">
+            <span>{trend.session_id} · 每秒 FPS / 1% Low</span>
+            <div className="flex gap-3">
+              <button
+                onClick={() => runLoadless(trend.session_id)}
+                title="去除加载画面后统计时长与帧率（速通计时）"
+                className="hover:text-white"
+              >
+                去除加载
+              </button>
+              <button
+                onClick={() => {
+                  setTrend(null);
+                  setLoadless(null);
+                }}
+                className="hover:text-white"
+              >
+                关闭
+              </button>
+            </div>
+          </div>
+          {loadless && (
+            <div className="mb-3 rounded-lg bg-surface border border-border p-3 text-xs">
+              <div className="flex items-center justify-between text-slate-400">
+                <span>
+                  无加载时长 <span className="text-white">{clock(loadless.loadless_secs)}</span>
+                  {" · "}全程 {clock(loadless.total_secs)} · 加载 {loadless.segments.length} 段，共{" "}
+                  {loadless.load_secs.toFixed(1)}s
+                </span>
+                {loadless.segments.length > 0 && (
+                  <button
+                    onClick={() => handleExportLoads(trend.session_id)}
+                    className="hover:text-white"
+                  >
+                    导出加载时间点
+                  </button>
+                )}
+              </div>
+              <div className="mt-1 text-slate-400">
+                无加载 FPS: 平均 <span className="text-white">{loadless.loadless.avg_fps}</span>
+                {" · 1% Low "}
+                <span className="text-white">{loadless.loadless.fps_1_low}</span>
+                {" · 0.1% Low "}
+                <span className="text-white">{loadless.loadless.fps_01_low}</span>
+              </div>
+              {loadless.segments.length > 0 && (
+                <div className="mt-1 text-slate-500">
+                  {loadless.segments
+                    .map((s) => `${clock(s.start_secs)}–${clock(s.end_secs)}`)
+                    .join("，")}
+                </div>
+              )}
+            </div>
+          )}
+          <div className="h-48">
+            <ResponsiveContainer width="100%" height="100%">
+              <LineChart data={trendPoints}>
+                <CartesianGrid strokeDasharray="3 3" stroke="#1e293b" />
+                <XAxis
+                  dataKey="at"
+                  tick={{ fill: "#64748b", fontSize: 10 }}
+                  tickFormatter={(v) => `${Math.round(v)}s`}
+                />
+                <YAxis tick={{ fill: "#64748b", fontSize: 10 }} domain={[0, "auto"]} />
+                <Tooltip
+                  contentStyle={{
+                    backgroundColor: "#1a2233",
+                    border: "1px solid #1e293b",
+                    borderRadius: "8px",
+                    fontSize: "12px",
+                  }}
+                  labelFormatter={(v) => `${Number(v).toFixed(0)}s`}
+                />
+                <Line
+                  type="monotone"
+                  dataKey="fps"
+                  stroke="#3b82f6"
+                  strokeWidth={2}
+                  dot={false}
+                  isAnimationActive={false}
+                  name="FPS"
+                />
+                <Line
+                  type="monotone"
+                  dataKey="low"
+                  stroke="#eab308"
+                  strokeWidth={1}
+                  dot={false}
+                  isAnimationActive={false}
+                  name="1% Low"
+                />
+              </LineChart>
+            </ResponsiveContainer>
+          </div>
+        </div>
+      )}
+
+      <div className="flex items-center justify-end gap-2 text-xs text-slate-400">
+        <button disabled={page === 0} onClick={() => setPage(page - 1)} className="disabled:opacity-30">
+          <ChevronLeft size={16} />
+        </button>
+        {page + 1} / {pages}
+        <button
+          disabled={page + 1 >= pages}
+          onClick={() => setPage(page + 1)}
+          className="disabled:opacity-30"
+        >
+          <ChevronRight size={16} />
+        </button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,282 @@
+import { useEffect, useState } from "react";
+import { Network, Play, RefreshCw } from "lucide-react";
+import {
+  collectLanResults, discoverLanAgents, getLanAgentInfo, getSettings, listPairedComparisons,
+  onPairedComparison, setReferenceAgent, startLanRun,
+} from "../lib/tauri-api";
+import type {
+  AgentInfo, LanAgent, LanRun, LanRunReport, PairedComparison, RunStatus,
+} from "../lib/types";
+
+const STATUS_LABELS: Record<RunStatus, string> = {
+  scheduled: "等待开始",
+  running: "测试中",
+  completed: "已完成",
+  failed: "失败",
+};
+
+const agentKey = (a: LanAgent) => `${a.address}:${a.port}`;
+
+/** 局域网测试台：一台主控机同时驱动多台测试机跑分并汇总对比 */
+export default function LanBench() {
+  const [agents, setAgents] = useState<LanAgent[]>([]);
+  const [infos, setInfos] = useState<Record<string, AgentInfo>>({});
+  const [selected, setSelected] = useState<Set<string>>(new Set());
+  const [discovering, setDiscovering] = useState(false);
+  const [processName, setProcessName] = useState("");
+  const [duration, setDuration] = useState(60);
+  const [run, setRun] = useState<LanRun | null>(null);
+  const [report, setReport] = useState<LanRunReport | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [reference, setReference] = useState<LanAgent | null>(null);
+  const [paired, setPaired] = useState<PairedComparison[]>([]);
+
+  const discover = async () => {
+    setDiscovering(true);
+    setError(null);
+    try {
+      const found = await discoverLanAgents();
+      setAgents(found);
+      setSelected(new Set(found.map(agentKey)));
+      found.forEach((agent) =>
+        getLanAgentInfo(agent)
+          .then((info) => setInfos((prev) => ({ ...prev, [agentKey(agent)]: info })))
+          .catch(() => {})
+      );
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setDiscovering(false);
+    }
+  };
+
+  useEffect(() => {
+    discover();
+    getSettings()
+      .then((s) => setReference(s.lan.reference))
+      .catch(() => {});
+    listPairedComparisons().then(setPaired).catch(() => {});
+    const unsub = onPairedComparison((report) => setPaired((prev) => [report, ...prev]));
+    return () => {
+      unsub.then((fn) => fn());
+    };
+  }, []);
+
+  const changeReference = async (agent: LanAgent | null) => {
+    setError(null);
+    try {
+      await setReferenceAgent(agent);
+      setReference(agent);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  // 测试进行中定期拉取结果，全部结束后停止
+  useEffect(() => {
+    if (!run || report?.finished) return;
+    const interval = setInterval(() => {
+      collectLanResults(run.run_id).then(setReport).catch((e) => setError(String(e)));
+    }, 3000);
+    return () => clearInterval(interval);
+  }, [run, report?.finished]);
+
+  const handleStart = async () => {
+    if (!processName.trim()) {
+      setError("请输入游戏进程名");
+      return;
+    }
+    setError(null);
+    setReport(null);
+    try {
+      const targets = agents.filter((a) => selected.has(agentKey(a)));
+      setRun(await startLanRun(targets, processName.trim(), duration));
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const toggle = (agent: LanAgent) => {
+    const next = new Set(selected);
+    if (next.has(agentKey(agent))) next.delete(agentKey(agent));
+    else next.add(agentKey(agent));
+    setSelected(next);
+  };
+
+  return (
+    <div className="p-6 space-y-6 max-w-4xl">
+      <div className="flex items-center justify-between">
+        <h1 className="text-xl font-bold text-white flex items-center gap-2">
+          <Network size={22} className="text-brand-400" />
+          局域网测试台
+        </h1>
+        <button
+          onClick={discover}
+          disabled={discovering}
+          className="flex items-center gap-1.5 px-3 py-1.5 text-xs rounded-lg bg-surface-card border border-border text-slate-400 hover:text-white disabled:opacity-50"
+        >
+          <RefreshCw size={14} className={discovering ? "animate-spin" : ""} />
+          查找测试机
+        </button>
+      </div>
+
+      <div className="rounded-xl bg-surface-card border border-border p-5 space-y-2">
+        <div className="text-sm font-semibold text-white mb-2">测试机</div>
+        {agents.length === 0 && (
+          <div className="text-xs text-slate-500">
+            未发现测试机。请在各测试机的设置中开启“局域网测试机”，并填写与本机相同的共享令牌。
+          </div>
+        )}
+        {agents.map((agent) => {
+          const info = infos[agentKey(agent)];
+          return (
+            <label key={agentKey(agent)} className="flex items-center gap-3 text-sm">
+              <input
+                type="checkbox"
+                checked={selected.has(agentKey(agent))}
+                onChange={() => toggle(agent)}
+              />
+              <span className="text-white">{agent.name}</span>
+              <span className="text-xs text-slate-500">
+                {agent.address} · v{agent.version}
+                {info && ` · ${info.cpu} · ${info.gpus.join(" / ")}`}
+                {info?.busy && " · 正在监测"}
+              </span>
+              <button
+                onClick={(e) => {
+                  e.preventDefault();
+                  changeReference(
+                    reference && agentKey(reference) === agentKey(agent) ? null : agent
+                  );
+                }}
+                title="本机开始监测时，对照机同步开始并在结束后自动对比"
+                className="ml-auto text-xs text-slate-400 hover:text-white"
+              >
+                {reference && agentKey(reference) === agentKey(agent) ? "取消对照机" : "设为对照机"}
+              </button>
+            </label>
+          );
+        })}
+      </div>
+
+      {reference && (
+        <div className="rounded-xl bg-surface-card border border-border p-5">
+          <div className="text-sm font-semibold text-white mb-1">
+            对照机 · {reference.name}
+            <span className="ml-2 text-xs text-slate-500">{reference.address}</span>
+          </div>
+          <div className="text-xs text-slate-500 mb-3">
+            本机开始监测时对照机同步开始，结束后自动生成对比（以对照机为基准）
+          </div>
+          {paired.length === 0 && <div className="text-xs text-slate-500">暂无对照报告</div>}
+          {paired.length > 0 && (
+            <table className="w-full text-sm">
+              <thead className="text-xs text-slate-500">
+                <tr>
+                  <th className="text-left py-1">本机会话</th>
+                  <th className="text-right">对照机 FPS</th>
+                  <th className="text-right">平均 FPS 差异</th>
+                  <th className="text-right">1% Low 差异</th>
+                </tr>
+              </thead>
+              <tbody>
+                {paired.map((p) => (
+                  <tr key={p.run_id} className="border-t border-border align-top">
+                    <td className="py-1.5 text-white">
+                      {p.session_id}
+                      {p.error && <div className="text-xs text-red-400">{p.error}</div>}
+                      {p.comparison?.mismatches.map((m) => (
+                        <div key={m} className="text-xs text-yellow-400">{m}</div>
+                      ))}
+                    </td>
+                    <td className="text-right text-white">
+                      {p.reference_session?.avg_fps ?? "—"}
+                    </td>
+                    <td className="text-right text-slate-400">
+                      {p.comparison ? `${p.comparison.avg_fps_delta_percent}%` : "—"}
+                    </td>
+                    <td className="text-right text-slate-400">
+                      {p.comparison ? `${p.comparison.fps_1_low_delta_percent}%` : "—"}
+                    </td>
+                  </tr>
+                ))}
+              </tbody>
+            </table>
+          )}
+        </div>
+      )}
+
+      <div className="flex gap-2">
+        <input
+          type="text"
+          value={processName}
+          placeholder="游戏进程名 (e.g., cs2.exe)"
+          onChange={(e) => setProcessName(e.target.value)}
+          className="flex-1 px-3 py-2 text-sm rounded-lg bg-surface border border-border text-white focus:outline-none focus:border-brand-600"
+        />
+        <input
+          type="number"
+          min={10}
+          value={duration}
+          onChange={(e) => setDuration(Number(e.target.value))}
+          className="w-24 px-3 py-2 text-sm rounded-lg bg-surface border border-border text-white focus:outline-none"
+        />
+        <button
+          onClick={handleStart}
+          disabled={selected.size === 0}
+          className="flex items-center gap-1.5 px-4 py-2 text-sm rounded-lg bg-brand-600 text-white hover:bg-brand-500 disabled:opacity-50"
+        >
+          <Play size={14} />
+          同步开始
+        </button>
+      </div>
+      {error && <div className="text-xs text-red-400">{error}</div>}
+      {run && run.failed.length > 0 && (
+        <div className="text-xs text-yellow-400">
+          {run.failed.map(([name, reason]) => `${name}: ${reason}`).join("；")}
+        </div>
+      )}
+
+      {report && (
+        <div className="rounded-xl bg-surface-card border border-border p-5">
+          <div className="text-sm font-semibold text-white mb-3">
+            对比报告 · {report.process_name}
+            {report.baseline && (
+              <span className="ml-2 text-xs text-slate-500">基准: {report.baseline}</span>
+            )}
+          </div>
+          <table className="w-full text-sm">
+            <thead className="text-xs text-slate-500">
+              <tr>
+                <th className="text-left py-1">测试机</th>
+                <th className="text-left">状态</th>
+                <th className="text-right">平均 FPS</th>
+                <th className="text-right">1% Low</th>
+                <th className="text-right">差异</th>
+              </tr>
+            </thead>
+            <tbody>
+              {report.results.map((r) => (
+                <tr key={agentKey(r.agent)} className="border-t border-border align-top">
+                  <td className="py-1.5 text-white">{r.agent.name}</td>
+                  <td className="text-slate-400">
+                    {STATUS_LABELS[r.status]}
+                    {r.error && <div className="text-xs text-red-400">{r.error}</div>}
+                    {r.comparison?.mismatches.map((m) => (
+                      <div key={m} className="text-xs text-yellow-400">{m}</div>
+                    ))}
+                  </td>
+                  <td className="text-right text-white">{r.session?.avg_fps ?? "—"}</td>
+                  <td className="text-right text-white">{r.session?.fps_1_low ?? "—"}</td>
+                  <td className="text-right text-slate-400">
+                    {r.comparison ? `${r.comparison.avg_fps_delta_percent}%` : "—"}
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      )}
+    </div>
+  );
+}
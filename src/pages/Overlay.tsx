@@ -0,0 +1,41 @@
+import { useEffect, useState } from "react";
+import { onFpsStopped, onFpsUpdate } from "../lib/tauri-api";
+import type { FpsSnapshot } from "../lib/types";
+
+/** 置顶悬浮窗：只渲染最近一次 fps-update，窗口本身透明且鼠标穿透 */
+export default function Overlay() {
+  const [snapshot, setSnapshot] = useState<FpsSnapshot | null>(null);
+
+  useEffect(() => {
+    // 悬浮窗背景透明，覆盖全局样式中的深色背景
+    document.documentElement.style.background = "transparent";
+    document.body.style.background = "transparent";
+
+    const unsub1 = onFpsUpdate(setSnapshot);
+    const unsub2 = onFpsStopped((c) =>
+      setSnapshot((prev) => (prev?.session_id === c.session_id ? null : prev))
+    );
+    return () => {
+      unsub1.then((fn) => fn());
+      unsub2.then((fn) => fn());
+    };
+  }, []);
+
+  return (
+    <div className="m-1 px-3 py-2 rounded-lg bg-black/60 text-white font-mono leading-tight select-none">
+      {snapshot ? (
+        <>
+          <div className="text-2xl font-bold">
+            {snapshot.fps.toFixed(0)}
+            <span className="ml-1 text-xs font-normal text-slate-300">FPS</span>
+          </div>
+          <div className="text-xs text-slate-300">
+            1% Low {snapshot.fps_1_low.toFixed(0)} · {snapshot.frametime_ms.toFixed(1)} ms
+          </div>
+        </>
+      ) : (
+        <div className="text-xs text-slate-300">等待监测…</div>
+      )}
+    </div>
+  );
+}
@@ -1,10 +1,120 @@
-import { useState } from "react";
+import { useEffect, useState } from "react";
 import { ExternalLink, FolderOpen } from "lucide-react";
+import {
+  backupData, cancelTask, checkForUpdate, createUserProfile, deleteUserProfile,
+  disableSessionEncryption, downloadPresentmon, downloadUpdate, enableKiosk,
+  enableSessionEncryption, getFormFactor, getHotkeyStatus, getOemPerfMode, getPluginsDir,
+  getSessionStoreStatus, getSettings, getSystemProxy, inspectBackup, installUpdate, listMirrors,
+  listPlugins, listUserProfiles, lockSessionStore, onProfileSwitched, restoreData, setPluginEnabled,
+  setProfilePromptOnStartup, switchUserProfile, testConnectivity, testScriptHook,
+  unlockSessionStore, updateSettings, verifyReportFile,
+} from "../lib/tauri-api";
+import type {
+  AppSettings, BackupSummary, ChassisProfile, ConnectivityReport, HookEvent, HotkeyAction,
+  HotkeySettings, HotkeyStatus, LanSettings, Mirror, MirrorSettings, MirrorStatus, OemPerfMode,
+  PluginInfo, PluginKind, PrivacyOptions, ProfileList, ProxySettings, ScriptHook, SignatureCheck,
+  StoreStatus, SystemProxy, TaskProgress, UnitPrefs, UpdateInfo, UpdateSettings,
+} from "../lib/types";
+
+const DEFAULT_SETTINGS: AppSettings = {
+  auto_detect: true,
+  auto_monitor: false,
+  auto_upload: false,
+  server_url: "https://gamebench-cn.vercel.app",
+  offline: false,
+  allow_powershell_fallback: false,
+  units: { size: "gib", temperature: "celsius", decimal: "dot" },
+  chassis: { form_factor: "unknown", cooling: "unknown", notes: "" },
+  game_name_language: "zh",
+  capture_profile: "standard",
+  capture_backend: "present_mon",
+  dpc_sampling: false,
+  busy_policy: "reject",
+  fps_smoothing: "average",
+  sleep_behavior: "finalize",
+  privacy: { strip_machine_name: true, strip_user_paths: true, strip_serials: true },
+  cloud_account: null,
+  lan: { agent_enabled: false, port: 47860, token: "", reference: null },
+  update: { channel: "stable", auto_check: true, mirror_url: null },
+  mirrors: { list: [], auto_select: true, preferred: null },
+  proxy: { mode: "system", url: "" },
+  enabled_plugins: [],
+  script_hooks: [],
+  hotkeys: {
+    enabled: true,
+    bindings: [
+      { action: "start", keys: "Ctrl+Shift+F9" },
+      { action: "stop", keys: "Ctrl+Shift+F10" },
+      { action: "mark", keys: "Ctrl+Shift+F11" },
+      { action: "overlay", keys: "Ctrl+Shift+F12" },
+    ],
+  },
+};
+
+const PERF_MODE_LABELS: Record<OemPerfMode["mode"], string> = {
+  quiet: "静音",
+  balanced: "均衡",
+  performance: "性能",
+  custom: "自定义",
+  unknown: "未知",
+};
 
 export default function Settings() {
-  const [autoDetect, setAutoDetect] = useState(true);
-  const [autoUpload, setAutoUpload] = useState(false);
-  const [serverUrl, setServerUrl] = useState("https://gamebench-cn.vercel.app");
+  const [settings, setSettings] = useState<AppSettings>(DEFAULT_SETTINGS);
+  const [perfMode, setPerfMode] = useState<OemPerfMode | null>(null);
+
+  const loadSettings = () =>
+    getSettings()
+      .then(async (loaded) => {
+        // 未填写机身形态时按 SMBIOS 机箱类型预填
+        if (loaded.chassis.form_factor === "unknown") {
+          const detected = await getFormFactor().catch(() => "unknown" as const);
+          loaded = { ...loaded, chassis: { ...loaded.chassis, form_factor: detected } };
+        }
+        setSettings(loaded);
+      })
+      .catch(() => {});
+
+  useEffect(() => {
+    loadSettings();
+    getOemPerfMode().then(setPerfMode).catch(() => {});
+    // 切换 profile 后设置随之变化
+    const unsub = onProfileSwitched(() => loadSettings());
+    return () => {
+      unsub.then((fn) => fn());
+    };
+  }, []);
+
+  const updatePrivacy = (patch: Partial<PrivacyOptions>) =>
+    update({ privacy: { ...settings.privacy, ...patch } });
+
+  const updateLan = (patch: Partial<LanSettings>) =>
+    update({ lan: { ...settings.lan, ...patch } });
+
+  const updateUpdater = (patch: Partial<UpdateSettings>) =>
+    update({ update: { ...settings.update, ...patch } });
+
+  const updateMirrors = (patch: Partial<MirrorSettings>) =>
+    update({ mirrors: { ...settings.mirrors, ...patch } });
+
+  const updateProxy = (patch: Partial<ProxySettings>) =>
+    update({ proxy: { ...settings.proxy, ...patch } });
+
+  const updateChassis = (patch: Partial<ChassisProfile>) =>
+    update({ chassis: { ...settings.chassis, ...patch } });
+
+  // 快捷键需要后端校验组合键，失败时保留原设置并提示
+  const saveHotkeys = async (hotkeys: HotkeySettings) => {
+    const next = { ...settings, hotkeys };
+    await updateSettings(next);
+    setSettings(next);
+  };
+
+  const update = (patch: Partial<AppSettings>) => {
+    const next = { ...settings, ...patch };
+    setSettings(next);
+    updateSettings(next).catch(() => {});
+  };
 
   return (
     <div className="p-6 space-y-6 max-w-2xl">
@@ -40,11 +150,94 @@ export default function Settings() {
             </div>
           </div>
 
+          <UnitSelect
+            label="游戏名称语言"
+            value={settings.game_name_language}
+            options={[
+              ["zh", "中文官方名（无中文名时显示英文）"],
+              ["en", "英文名"],
+            ]}
+            onChange={(v) => update({ game_name_language: v as AppSettings["game_name_language"] })}
+          />
+
+          <UnitSelect
+            label="默认采集档位"
+            value={settings.capture_profile}
+            options={[
+              ["standard", "标准（帧时间 + 传感器与硬件状态采样）"],
+              ["minimal", "最低开销（仅帧时间，适合评测认证）"],
+            ]}
+            onChange={(v) => update({ capture_profile: v as AppSettings["capture_profile"] })}
+          />
+
+          <UnitSelect
+            label="帧数据采集后端"
+            value={settings.capture_backend}
+            options={[
+              ["present_mon", "PresentMon（支持所有图形 API）"],
+              ["native", "内置 ETW（无需 PresentMon.exe，仅 DXGI / D3D9，需管理员权限）"],
+            ]}
+            onChange={(v) => update({ capture_backend: v as AppSettings["capture_backend"] })}
+          />
+
+          <Toggle
+            label="DPC/ISR 延迟采样"
+            description="监测期间记录驱动的 DPC/ISR 执行时间，找出与卡顿同时发生的延迟风暴（需以管理员身份运行）"
+            checked={settings.dpc_sampling}
+            onChange={(v) => update({ dpc_sampling: v })}
+          />
+
+          <UnitSelect
+            label="已有监测进行中时开始新监测"
+            value={settings.busy_policy}
+            options={[
+              ["reject", "拒绝（提示正在监测）"],
+              ["queue", "排队，当前监测结束后自动开始"],
+              ["replace", "停止并保存当前监测，改为新的游戏"],
+            ]}
+            onChange={(v) => update({ busy_policy: v as AppSettings["busy_policy"] })}
+          />
+
+          <UnitSelect
+            label="实时 FPS 平滑（仅影响显示，统计始终使用原始帧时间）"
+            value={settings.fps_smoothing}
+            options={[
+              ["average", "算术平均（每个快照窗口）"],
+              ["ema", "指数移动平均（CPU 尖峰时更平稳）"],
+              ["median", "窗口中位数（忽略个别长帧）"],
+            ]}
+            onChange={(v) => update({ fps_smoothing: v as AppSettings["fps_smoothing"] })}
+          />
+
+          <UnitSelect
+            label="监测期间系统睡眠"
+            value={settings.sleep_behavior}
+            options={[
+              ["finalize", "结束本次监测并标记为被睡眠中断"],
+              ["resume", "唤醒后继续监测（睡眠时长不计入）"],
+            ]}
+            onChange={(v) => update({ sleep_behavior: v as AppSettings["sleep_behavior"] })}
+          />
+
           <Toggle
             label="自动检测游戏"
             description="启动游戏时自动弹出 FPS 监测提示"
-            checked={autoDetect}
-            onChange={setAutoDetect}
+            checked={settings.auto_detect}
+            onChange={(v) => update({ auto_detect: v })}
+          />
+
+          <Toggle
+            label="自动开始监测"
+            description="检测到游戏启动时自动开始 FPS 监测，游戏退出时自动结束并保存"
+            checked={settings.auto_monitor}
+            onChange={(v) => update({ auto_monitor: v })}
+          />
+
+          <Toggle
+            label="允许 PowerShell 备用检测"
+            description="WMI 与 DXGI 均无法识别显卡时使用（较慢，可能被安全软件拦截）"
+            checked={settings.allow_powershell_fallback}
+            onChange={(v) => update({ allow_powershell_fallback: v })}
           />
         </div>
       </Section>
@@ -52,22 +245,288 @@ export default function Settings() {
       {/* 数据上传 */}
       <Section title="数据上传">
         <div className="space-y-3">
+          <Toggle
+            label="离线模式"
+            description="不访问任何互联网服务：不上传数据、不检查更新、不同步规则与管理端配置（局域网测试不受影响）"
+            checked={settings.offline}
+            onChange={(v) => update({ offline: v })}
+          />
           <Toggle
             label="自动上传 FPS 数据"
-            description="匿名上传性能数据，帮助其他用户参考（不包含个人信息）"
-            checked={autoUpload}
-            onChange={setAutoUpload}
+            description={
+              settings.offline
+                ? "离线模式下不会上传"
+                : "匿名上传性能数据，帮助其他用户参考（不包含个人信息）"
+            }
+            checked={settings.auto_upload}
+            onChange={(v) => update({ auto_upload: v })}
           />
 
           <div>
             <div className="text-xs text-slate-500 mb-1">服务器地址</div>
             <input
               type="text"
-              value={serverUrl}
-              onChange={(e) => setServerUrl(e.target.value)}
+              value={settings.server_url}
+              onChange={(e) => update({ server_url: e.target.value })}
+              className="w-full px-3 py-2 text-sm rounded-lg bg-surface border border-border text-white focus:outline-none focus:border-brand-600"
+            />
+          </div>
+        </div>
+      </Section>
+
+      {/* 用户 profile */}
+      <Section title="用户 profile">
+        <UserProfiles />
+      </Section>
+
+      {/* 局域网测试台 */}
+      <Section title="局域网测试台">
+        <div className="space-y-3">
+          <Toggle
+            label="作为局域网测试机"
+            description="允许局域网内的主控机发现本机并远程发起测试（需填写共享令牌）"
+            checked={settings.lan.agent_enabled}
+            onChange={(v) => updateLan({ agent_enabled: v })}
+          />
+          <div className="grid grid-cols-3 gap-3">
+            <div className="col-span-2">
+              <div className="text-xs text-slate-500 mb-1">共享令牌（主控机与测试机需一致）</div>
+              <input
+                type="password"
+                value={settings.lan.token}
+                onChange={(e) => updateLan({ token: e.target.value })}
+                className="w-full px-3 py-2 text-sm rounded-lg bg-surface border border-border text-white focus:outline-none focus:border-brand-600"
+              />
+            </div>
+            <div>
+              <div className="text-xs text-slate-500 mb-1">本地 API 端口</div>
+              <input
+                type="number"
+                value={settings.lan.port}
+                onChange={(e) => updateLan({ port: Number(e.target.value) })}
+                className="w-full px-3 py-2 text-sm rounded-lg bg-surface border border-border text-white focus:outline-none focus:border-brand-600"
+              />
+            </div>
+          </div>
+          <div className="text-xs text-slate-500">
+            外部工具（回放脚本、OBS 插件等）可通过 POST /api/clock/sync 对时，再用 POST /api/markers
+            向正在进行的测试注入时间标记
+          </div>
+        </div>
+      </Section>
+
+      {/* 网吧模式 */}
+      <Section title="网吧模式">
+        <KioskSetup />
+      </Section>
+
+      {/* 备份与迁移 */}
+      <Section title="备份与迁移">
+        <DataBackup />
+      </Section>
+
+      {/* 应用更新 */}
+      <Section title="应用更新">
+        <div className="space-y-3">
+          {settings.offline && <OfflineNotice />}
+          <UnitSelect
+            label="更新通道"
+            value={settings.update.channel}
+            options={[
+              ["stable", "稳定版"],
+              ["beta", "测试版（更早体验新功能，可能不稳定）"],
+            ]}
+            onChange={(v) => updateUpdater({ channel: v as UpdateSettings["channel"] })}
+          />
+          <div>
+            <div className="text-xs text-slate-500 mb-1">更新专用镜像（可选，填写后优先于下方的下载镜像）</div>
+            <input
+              type="text"
+              value={settings.update.mirror_url ?? ""}
+              placeholder="https://mirror.example.cn/gamebench/updates"
+              onChange={(e) => updateUpdater({ mirror_url: e.target.value || null })}
+              className="w-full px-3 py-2 text-sm rounded-lg bg-surface border border-border text-white focus:outline-none focus:border-brand-600"
+            />
+          </div>
+          <Toggle
+            label="启动时检查更新"
+            description="发现新版本时提示，不会自动安装"
+            checked={settings.update.auto_check}
+            onChange={(v) => updateUpdater({ auto_check: v })}
+          />
+          <AppUpdate />
+        </div>
+      </Section>
+
+      {/* 网络代理 */}
+      <Section title="网络代理">
+        <div className="space-y-3">
+          {settings.offline && <OfflineNotice />}
+          <UnitSelect
+            label="代理"
+            value={settings.proxy.mode}
+            options={[
+              ["system", "跟随系统代理"],
+              ["manual", "手动配置（HTTP / SOCKS5）"],
+              ["off", "不使用代理"],
+            ]}
+            onChange={(v) => updateProxy({ mode: v as ProxySettings["mode"] })}
+          />
+          {settings.proxy.mode === "manual" && (
+            <input
+              type="text"
+              value={settings.proxy.url}
+              placeholder="http://127.0.0.1:7890 或 socks5://127.0.0.1:1080"
+              onChange={(e) => updateProxy({ url: e.target.value })}
               className="w-full px-3 py-2 text-sm rounded-lg bg-surface border border-border text-white focus:outline-none focus:border-brand-600"
             />
+          )}
+          <div className="text-xs text-slate-500">局域网测试始终直连，不经过代理</div>
+          <Connectivity mode={settings.proxy.mode} />
+        </div>
+      </Section>
+
+      {/* 插件 */}
+      <Section title="插件">
+        <Plugins onChanged={loadSettings} />
+      </Section>
+
+      {/* 事件脚本 */}
+      <Section title="事件脚本">
+        <ScriptHooks
+          hooks={settings.script_hooks}
+          onChange={(script_hooks) => update({ script_hooks })}
+        />
+      </Section>
+
+      {/* 全局快捷键 */}
+      <Section title="全局快捷键">
+        <Hotkeys hotkeys={settings.hotkeys} onSave={saveHotkeys} />
+      </Section>
+
+      {/* 下载镜像 */}
+      <Section title="下载镜像">
+        <div className="space-y-3">
+          {settings.offline && <OfflineNotice />}
+          <Toggle
+            label="自动选择最快的源"
+            description="规则、过滤名单、应用更新与 PresentMon 下载按测速结果选择源，失败时自动换下一个"
+            checked={settings.mirrors.auto_select}
+            onChange={(v) => updateMirrors({ auto_select: v })}
+          />
+          <DownloadMirrors mirrors={settings.mirrors} onChange={updateMirrors} />
+          <PresentmonDownload />
+        </div>
+      </Section>
+
+      {/* 隐私与加密 */}
+      <Section title="隐私与加密">
+        <div className="space-y-3">
+          <Toggle
+            label="导出时去掉计算机名"
+            description="导出与分享报告中的计算机名替换为 <machine>"
+            checked={settings.privacy.strip_machine_name}
+            onChange={(v) => updatePrivacy({ strip_machine_name: v })}
+          />
+          <Toggle
+            label="导出时去掉用户路径"
+            description="C:\Users\用户名 等路径中的用户名替换为 <user>"
+            checked={settings.privacy.strip_user_paths}
+            onChange={(v) => updatePrivacy({ strip_user_paths: v })}
+          />
+          <Toggle
+            label="导出时去掉设备序列号"
+            description="去掉显卡设备实例 ID、窗口句柄等可识别单台机器的信息"
+            checked={settings.privacy.strip_serials}
+            onChange={(v) => updatePrivacy({ strip_serials: v })}
+          />
+          <SessionEncryption />
+          <ReportSignature />
+        </div>
+      </Section>
+
+      {/* 单位与格式 */}
+      <Section title="单位与格式">
+        <div className="grid grid-cols-3 gap-3">
+          <UnitSelect
+            label="容量"
+            value={settings.units.size}
+            options={[
+              ["gib", "GiB (1024³)"],
+              ["gb", "GB (1000³)"],
+            ]}
+            onChange={(v) => update({ units: { ...settings.units, size: v as UnitPrefs["size"] } })}
+          />
+          <UnitSelect
+            label="温度"
+            value={settings.units.temperature}
+            options={[
+              ["celsius", "°C"],
+              ["fahrenheit", "°F"],
+            ]}
+            onChange={(v) =>
+              update({ units: { ...settings.units, temperature: v as UnitPrefs["temperature"] } })
+            }
+          />
+          <UnitSelect
+            label="小数点"
+            value={settings.units.decimal}
+            options={[
+              ["dot", "1.5"],
+              ["comma", "1,5"],
+            ]}
+            onChange={(v) =>
+              update({ units: { ...settings.units, decimal: v as UnitPrefs["decimal"] } })
+            }
+          />
+        </div>
+      </Section>
+
+      {/* 机身与散热 */}
+      <Section title="机身与散热">
+        <div className="space-y-3">
+          <div className="grid grid-cols-2 gap-3">
+            <UnitSelect
+              label="机身形态"
+              value={settings.chassis.form_factor}
+              options={[
+                ["desktop", "台式机"],
+                ["laptop", "笔记本"],
+                ["mini_pc", "迷你主机 / ITX"],
+                ["unknown", "未填写"],
+              ]}
+              onChange={(v) => updateChassis({ form_factor: v as ChassisProfile["form_factor"] })}
+            />
+            <UnitSelect
+              label="散热方案"
+              value={settings.chassis.cooling}
+              options={[
+                ["stock_air", "原装风冷"],
+                ["tower_air", "塔式风冷"],
+                ["aio", "一体式水冷"],
+                ["custom_loop", "分体式水冷"],
+                ["laptop_stock", "笔记本原装散热"],
+                ["laptop_cooling_pad", "笔记本 + 散热底座"],
+                ["unknown", "未填写"],
+              ]}
+              onChange={(v) => updateChassis({ cooling: v as ChassisProfile["cooling"] })}
+            />
           </div>
+          <div>
+            <div className="text-xs text-slate-500 mb-1">备注</div>
+            <input
+              type="text"
+              value={settings.chassis.notes}
+              placeholder="如：侧板打开、室温 30°C"
+              onChange={(e) => updateChassis({ notes: e.target.value })}
+              className="w-full px-3 py-2 text-sm rounded-lg bg-surface border border-border text-white"
+            />
+          </div>
+          {perfMode && (
+            <div className="text-xs text-slate-400">
+              当前 {perfMode.vendor} 性能模式：{PERF_MODE_LABELS[perfMode.mode]}（测试期间切换模式会记录在结果中）
+            </div>
+          )}
         </div>
       </Section>
 
@@ -109,6 +568,840 @@ function Section({ title, children }: { title: string; children: React.ReactNode
   );
 }
 
+/** 多用户 profile：各自独立的设置、会话记录与云端账户 */
+function UserProfiles() {
+  const [list, setList] = useState<ProfileList | null>(null);
+  const [name, setName] = useState("");
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = () => listUserProfiles().then(setList).catch(() => {});
+  useEffect(() => {
+    refresh();
+  }, []);
+
+  const run = async (action: () => Promise<unknown>) => {
+    setError(null);
+    try {
+      await action();
+    } catch (e) {
+      setError(String(e));
+    }
+    refresh();
+  };
+
+  if (!list) return null;
+
+  return (
+    <div className="space-y-3">
+      {list.registry.profiles.map((p) => (
+        <div key={p.id} className="flex items-center justify-between text-sm">
+          <span className={p.id === list.active ? "text-brand-400" : "text-white"}>
+            {p.name}
+            {p.id === list.active && "（当前）"}
+          </span>
+          <div className="flex gap-2">
+            {p.id !== list.active && (
+              <ActionButton label="切换" onClick={() => run(() => switchUserProfile(p.id))} />
+            )}
+            {p.id !== list.active && p.id !== "default" && (
+              <ActionButton label="删除" onClick={() => run(() => deleteUserProfile(p.id))} />
+            )}
+          </div>
+        </div>
+      ))}
+      <div className="flex gap-2">
+        <input
+          type="text"
+          value={name}
+          placeholder="新 profile 名称"
+          onChange={(e) => setName(e.target.value)}
+          className="flex-1 px-3 py-2 text-sm rounded-lg bg-surface border border-border text-white focus:outline-none focus:border-brand-600"
+        />
+        <ActionButton
+          label="新建"
+          onClick={() => run(() => createUserProfile(name).then(() => setName("")))}
+        />
+      </div>
+      <Toggle
+        label="启动时选择 profile"
+        description="网吧或家庭共用电脑时，每次启动先选择使用者"
+        checked={list.registry.ask_on_startup}
+        onChange={(v) => run(() => setProfilePromptOnStartup(v))}
+      />
+      {error && <div className="text-xs text-red-400">{error}</div>}
+    </div>
+  );
+}
+
+/** 本地会话库密码加密（多人共用的测试机） */
+function SessionEncryption() {
+  const [status, setStatus] = useState<StoreStatus | null>(null);
+  const [passphrase, setPassphrase] = useState("");
+  const [progress, setProgress] = useState<TaskProgress | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = () => getSessionStoreStatus().then(setStatus).catch(() => {});
+  useEffect(() => {
+    refresh();
+  }, []);
+
+  const run = async (
+    action: (p: string, onProgress: (progress: TaskProgress) => void) => Promise<void>
+  ) => {
+    setError(null);
+    try {
+      await action(passphrase, setProgress);
+      setPassphrase("");
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setProgress(null);
+    }
+    refresh();
+  };
+
+  if (!status) return null;
+
+  return (
+    <div className="space-y-2">
+      <div className="text-sm text-white">会话库加密</div>
+      <div className="text-xs text-slate-500">
+        {status.encrypted
+          ? status.unlocked
+            ? `已加密并解锁（${status.session_count} 个会话）`
+            : `已加密，需输入密码才能查看 ${status.session_count} 个会话` +
+              (status.pending > 0 ? `，${status.pending} 个新会话等待解锁后保存` : "")
+          : `未加密（${status.session_count} 个会话）`}
+      </div>
+      <div className="flex gap-2">
+        <input
+          type="password"
+          value={passphrase}
+          placeholder="密码"
+          onChange={(e) => setPassphrase(e.target.value)}
+          className="flex-1 px-3 py-2 text-sm rounded-lg bg-surface border border-border text-white focus:outline-none focus:border-brand-600"
+        />
+        {!status.encrypted && (
+          <ActionButton label="加密" onClick={() => run(enableSessionEncryption)} />
+        )}
+        {status.encrypted && !status.unlocked && (
+          <ActionButton label="解锁" onClick={() => run(unlockSessionStore)} />
+        )}
+        {status.encrypted && status.unlocked && (
+          <ActionButton label="锁定" onClick={() => run(() => lockSessionStore())} />
+        )}
+        {status.encrypted && (
+          <ActionButton label="解除加密" onClick={() => run(disableSessionEncryption)} />
+        )}
+      </div>
+      {progress && <TaskStatus progress={progress} />}
+      {error && <div className="text-xs text-red-400">{error}</div>}
+    </div>
+  );
+}
+
+/** 开启网吧模式：只保留测试与结果页，退出需要 PIN */
+function KioskSetup() {
+  const [pin, setPin] = useState("");
+  const [purge, setPurge] = useState(true);
+  const [managementUrl, setManagementUrl] = useState("");
+  const [error, setError] = useState<string | null>(null);
+
+  const handleEnable = async () => {
+    setError(null);
+    try {
+      await enableKiosk(pin, purge, managementUrl.trim() || undefined);
+      setPin("");
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  return (
+    <div className="space-y-3">
+      <div className="text-xs text-slate-500">
+        适用于网吧、门店展示机：仅保留 FPS 测试与结果查看，设置、进程管理与导出等功能均被锁定。
+      </div>
+      <Toggle
+        label="查看后自动删除会话"
+        description="结果展示后立即从本机删除，下一次启动时也会清除遗留记录"
+        checked={purge}
+        onChange={setPurge}
+      />
+      <div>
+        <div className="text-xs text-slate-500 mb-1">管理端地址（可选，定期拉取集中配置）</div>
+        <input
+          type="text"
+          value={managementUrl}
+          placeholder="http://192.168.1.10:8080"
+          onChange={(e) => setManagementUrl(e.target.value)}
+          className="w-full px-3 py-2 text-sm rounded-lg bg-surface border border-border text-white focus:outline-none focus:border-brand-600"
+        />
+      </div>
+      <div className="flex gap-2">
+        <input
+          type="password"
+          value={pin}
+          placeholder="退出 PIN（至少 4 位）"
+          onChange={(e) => setPin(e.target.value)}
+          className="flex-1 px-3 py-2 text-sm rounded-lg bg-surface border border-border text-white focus:outline-none focus:border-brand-600"
+        />
+        <ActionButton label="开启网吧模式" onClick={handleEnable} />
+      </div>
+      {error && <div className="text-xs text-red-400">{error}</div>}
+    </div>
+  );
+}
+
+/** 换电脑或重装系统前，把设置、自定义规则与会话库打包成一个文件 */
+function DataBackup() {
+  const [path, setPath] = useState("");
+  const [message, setMessage] = useState<string | null>(null);
+  const [progress, setProgress] = useState<TaskProgress | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const describe = (b: BackupSummary) =>
+    `${b.files} 个文件，其中 ${b.sessions} 条会话 · ${(b.size_bytes / 1024 / 1024).toFixed(1)} MB`;
+
+  const handleBackup = async () => {
+    setError(null);
+    try {
+      const b = await backupData(path.trim() || undefined, setProgress);
+      setMessage(`已备份到 ${b.path}（${describe(b)}）`);
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setProgress(null);
+    }
+  };
+
+  const handleRestore = async () => {
+    setError(null);
+    try {
+      const info = await inspectBackup(path.trim());
+      const ok = window.confirm(
+        `从 ${info.created_at.slice(0, 19).replace("T", " ")} 的备份（v${info.app_version}，${describe(info)}）恢复？\n` +
+          "同名的设置与会话会被覆盖。"
+      );
+      if (!ok) return;
+      await restoreData(path.trim(), setProgress);
+      window.location.reload();
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setProgress(null);
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <div className="text-xs text-slate-500">
+        包含全部用户的设置、自定义采集规则与会话记录。会话库加密时无法备份（加密数据与本机账户绑定）。不填路径时备份到下载目录。
+      </div>
+      <div className="flex gap-2">
+        <input
+          type="text"
+          value={path}
+          placeholder="备份文件路径 (.gbbak)"
+          onChange={(e) => setPath(e.target.value)}
+          className="flex-1 px-3 py-2 text-sm rounded-lg bg-surface border border-border text-white focus:outline-none focus:border-brand-600"
+        />
+        <ActionButton label="备份" onClick={handleBackup} />
+        <ActionButton label="恢复" onClick={handleRestore} />
+      </div>
+      {progress && <TaskStatus progress={progress} />}
+      {message && <div className="text-xs text-slate-400">{message}</div>}
+      {error && <div className="text-xs text-red-400">{error}</div>}
+    </div>
+  );
+}
+
+function ReportSignature() {
+  const [path, setPath] = useState("");
+  const [check, setCheck] = useState<SignatureCheck | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const handleVerify = async () => {
+    setError(null);
+    try {
+      setCheck(await verifyReportFile(path.trim()));
+    } catch (e) {
+      setCheck(null);
+      setError(String(e));
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <div className="text-xs text-slate-500">
+        校验导出的 JSON 报告。签名只证明报告由附带的公钥签出后未被修改，公钥不会与任何你信任的公钥比对，
+        任何人都能用自己的密钥签出“有效”报告；请核对公钥指纹是否属于提交者。
+      </div>
+      <div className="flex gap-2">
+        <input
+          type="text"
+          value={path}
+          placeholder="报告文件路径 (.json)"
+          onChange={(e) => setPath(e.target.value)}
+          className="flex-1 px-3 py-2 text-sm rounded-lg bg-surface border border-border text-white focus:outline-none focus:border-brand-600"
+        />
+        <ActionButton label="校验签名" onClick={handleVerify} />
+      </div>
+      {check && (
+        <div className={`text-xs ${check.valid ? "text-slate-400" : "text-red-400"}`}>
+          {check.valid ? "签名有效，内容未被修改" : check.reason}
+          {check.key_fingerprint && ` · 公钥指纹 ${check.key_fingerprint}`}
+          {check.local_key && "（本机签名）"}
+        </div>
+      )}
+      {error && <div className="text-xs text-red-400">{error}</div>}
+    </div>
+  );
+}
+
+/** 不可取消的维护任务进度 */
+function TaskStatus({ progress }: { progress: TaskProgress }) {
+  return (
+    <div className="text-xs text-slate-500">
+      {progress.message || "处理中"}
+      {progress.total > 0 && ` · ${progress.done} / ${progress.total}`}
+    </div>
+  );
+}
+
+/** 检查 → 下载（可断点续传）→ 安装；安装前会保存进行中的监测 */
+function AppUpdate() {
+  const [info, setInfo] = useState<UpdateInfo | null>(null);
+  const [progress, setProgress] = useState<TaskProgress | null>(null);
+  const [downloaded, setDownloaded] = useState(false);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const run = async (action: () => Promise<void>) => {
+    setBusy(true);
+    setError(null);
+    try {
+      await action();
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const mb = (bytes: number) => `${(bytes / 1024 / 1024).toFixed(1)} MB`;
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center gap-2">
+        <ActionButton
+          label={busy ? "请稍候…" : "检查更新"}
+          onClick={() => run(async () => setInfo(await checkForUpdate()))}
+        />
+        {info?.available && !downloaded && (
+          <ActionButton
+            label={`下载 ${info.version}（${mb(info.download_size)}${info.delta ? "，差分包" : ""}）`}
+            onClick={() =>
+              run(async () => {
+                try {
+                  await downloadUpdate(setProgress);
+                  setDownloaded(true);
+                } finally {
+                  setProgress(null);
+                }
+              })
+            }
+          />
+        )}
+        {downloaded && <ActionButton label="重启并安装" onClick={() => run(installUpdate)} />}
+      </div>
+      {info && !info.available && (
+        <div className="text-xs text-slate-500">当前已是最新版本 {info.current_version}</div>
+      )}
+      {info?.available && info.notes && (
+        <div className="text-xs text-slate-400 whitespace-pre-line">{info.notes}</div>
+      )}
+      {progress && (
+        <div className="flex items-center gap-2 text-xs text-slate-500">
+          已下载 {mb(progress.done)} / {mb(progress.total)}
+          <button
+            onClick={() => cancelTask(progress.task_id).catch(() => {})}
+            className="text-slate-400 hover:text-white"
+          >
+            取消
+          </button>
+        </div>
+      )}
+      {error && <div className="text-xs text-red-400">{error}</div>}
+    </div>
+  );
+}
+
+function Connectivity({ mode }: { mode: ProxySettings["mode"] }) {
+  const [systemProxy, setSystemProxy] = useState<SystemProxy | null>(null);
+  const [report, setReport] = useState<ConnectivityReport | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    getSystemProxy().then(setSystemProxy).catch(() => {});
+  }, []);
+
+  const test = async () => {
+    setBusy(true);
+    setError(null);
+    try {
+      setReport(await testConnectivity());
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      {mode === "system" && (
+        <div className="text-xs text-slate-500">
+          {systemProxy
+            ? `系统代理：${systemProxy.url}（${systemProxy.source}）`
+            : "未检测到系统代理，将直连"}
+        </div>
+      )}
+      <ActionButton label={busy ? "测试中…" : "测试网络连接"} onClick={test} />
+      {report && (
+        <div className="space-y-1 text-xs">
+          {report.endpoints.map((e) => (
+            <div key={`${e.name}-${e.url}`} className="flex justify-between gap-4">
+              <span className="text-slate-300">{e.name}</span>
+              <span className={e.reachable ? "text-green-400" : "text-red-400"}>
+                {e.reachable ? `${e.latency_ms} ms (HTTP ${e.status})` : e.error}
+              </span>
+            </div>
+          ))}
+        </div>
+      )}
+      {error && <div className="text-xs text-red-400">{error}</div>}
+    </div>
+  );
+}
+
+const PLUGIN_KIND_LABELS: Record<PluginKind, string> = {
+  telemetry: "遥测",
+  exporter: "导出",
+};
+
+function Plugins({ onChanged }: { onChanged: () => void }) {
+  const [plugins, setPlugins] = useState<PluginInfo[]>([]);
+  const [dir, setDir] = useState("");
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = () => listPlugins().then(setPlugins).catch((e) => setError(String(e)));
+
+  useEffect(() => {
+    refresh();
+    getPluginsDir().then(setDir).catch(() => {});
+  }, []);
+
+  const toggle = async (id: string, enabled: boolean) => {
+    setError(null);
+    try {
+      await setPluginEnabled(id, enabled);
+      await refresh();
+      onChanged();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  return (
+    <div className="space-y-3">
+      <div className="text-xs text-slate-500">
+        将插件放到 {dir || "插件目录"} 下的子目录中（包含 plugin.json）。插件在受限进程中以低完整性级别
+        运行（不能写入用户目录），默认不启用，只启用信任的插件。
+      </div>
+      {plugins.length === 0 && <div className="text-xs text-slate-500">未发现插件</div>}
+      {plugins.map((p) =>
+        p.manifest ? (
+          <Toggle
+            key={p.dir}
+            label={`${p.manifest.name} ${p.manifest.version}（${PLUGIN_KIND_LABELS[p.manifest.kind]}）`}
+            description={p.manifest.description || p.manifest.id}
+            checked={p.enabled}
+            onChange={(v) => toggle(p.manifest!.id, v)}
+          />
+        ) : (
+          <div key={p.dir} className="text-xs text-red-400">
+            {p.dir}: {p.error}
+          </div>
+        )
+      )}
+      <ActionButton label="重新扫描" onClick={refresh} />
+      {error && <div className="text-xs text-red-400">{error}</div>}
+    </div>
+  );
+}
+
+const HOOK_EVENT_LABELS: Record<HookEvent, string> = {
+  session_start: "开始监测",
+  session_complete: "会话完成",
+  stutter_detected: "检测到卡顿",
+};
+
+function ScriptHooks({
+  hooks,
+  onChange,
+}: {
+  hooks: ScriptHook[];
+  onChange: (hooks: ScriptHook[]) => void;
+}) {
+  const [results, setResults] = useState<Record<string, string>>({});
+
+  const patch = (id: string, change: Partial<ScriptHook>) =>
+    onChange(hooks.map((h) => (h.id === id ? { ...h, ...change } : h)));
+
+  const add = () =>
+    onChange([
+      ...hooks,
+      {
+        id: `hook-${Date.now()}`,
+        event: "session_complete",
+        script: "",
+        enabled: true,
+        timeout_secs: 10,
+      },
+    ]);
+
+  const test = async (hook: ScriptHook) => {
+    try {
+      const run = await testScriptHook(hook);
+      const status = run.timed_out ? "超时" : (run.error ?? "完成");
+      setResults({ ...results, [hook.id]: `${status}（${run.duration_ms} ms）${run.output}` });
+    } catch (e) {
+      setResults({ ...results, [hook.id]: String(e) });
+    }
+  };
+
+  return (
+    <div className="space-y-3">
+      <div className="text-xs text-slate-500">
+        脚本为 Rhai（.rhai），事件数据通过 event 对象读取（如 event.session_id）。可用
+        print、write_file(路径, 文本)、append_file(路径, 文本) 和 exec(程序, [参数])；exec
+        启动的程序可读取 GAMEBENCH_EVENT / GAMEBENCH_PROCESS / GAMEBENCH_SESSION_ID
+        环境变量，超时后连同其子进程一起结束。脚本按当前 profile 保存，网吧模式下不运行。
+      </div>
+      {hooks.map((hook) => (
+        <div key={hook.id} className="space-y-1">
+          <div className="flex items-center gap-2">
+            <input
+              type="checkbox"
+              checked={hook.enabled}
+              onChange={(e) => patch(hook.id, { enabled: e.target.checked })}
+            />
+            <select
+              value={hook.event}
+              onChange={(e) => patch(hook.id, { event: e.target.value as HookEvent })}
+              className="px-2 py-2 text-sm rounded-lg bg-surface border border-border text-white"
+            >
+              {Object.entries(HOOK_EVENT_LABELS).map(([value, label]) => (
+                <option key={value} value={value}>
+                  {label}
+                </option>
+              ))}
+            </select>
+            <input
+              type="text"
+              value={hook.script}
+              placeholder={"C:\\scripts\\on-complete.rhai"}
+              onChange={(e) => patch(hook.id, { script: e.target.value })}
+              className="flex-1 px-3 py-2 text-sm rounded-lg bg-surface border border-border text-white focus:outline-none focus:border-brand-600"
+            />
+            <ActionButton label="试运行" onClick={() => test(hook)} />
+            <button
+              onClick={() => onChange(hooks.filter((h) => h.id !== hook.id))}
+              className="text-xs text-red-400 hover:text-red-300"
+            >
+              删除
+            </button>
+          </div>
+          {results[hook.id] && (
+            <div className="text-xs text-slate-500 whitespace-pre-line">{results[hook.id]}</div>
+          )}
+        </div>
+      ))}
+      <ActionButton label="添加脚本" onClick={add} />
+    </div>
+  );
+}
+
+const HOTKEY_ACTION_LABELS: Record<HotkeyAction, string> = {
+  start: "开始监测（前台游戏）",
+  stop: "停止监测",
+  mark: "添加标记",
+  overlay: "显示/关闭悬浮窗",
+};
+
+function Hotkeys({
+  hotkeys,
+  onSave,
+}: {
+  hotkeys: HotkeySettings;
+  onSave: (hotkeys: HotkeySettings) => Promise<void>;
+}) {
+  const [drafts, setDrafts] = useState<Record<string, string>>({});
+  const [status, setStatus] = useState<HotkeyStatus[]>([]);
+  const [error, setError] = useState<string | null>(null);
+
+  // 后端在快捷键线程上异步重新注册，稍后再读取结果
+  const refreshStatus = () =>
+    setTimeout(() => getHotkeyStatus().then(setStatus).catch(() => {}), 300);
+
+  useEffect(() => {
+    refreshStatus();
+  }, []);
+
+  const save = async (next: HotkeySettings) => {
+    try {
+      await onSave(next);
+      setError(null);
+      setDrafts({});
+      refreshStatus();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const commit = (action: HotkeyAction) => {
+    const keys = drafts[action]?.trim();
+    if (keys === undefined) return;
+    const rest = hotkeys.bindings.filter((b) => b.action !== action);
+    save({ ...hotkeys, bindings: keys ? [...rest, { action, keys }] : rest });
+  };
+
+  return (
+    <div className="space-y-3">
+      <Toggle
+        label="启用全局快捷键"
+        description="游戏全屏时无需切回本窗口即可开始/停止监测、添加标记"
+        checked={hotkeys.enabled}
+        onChange={(enabled) => save({ ...hotkeys, enabled })}
+      />
+      {(Object.keys(HOTKEY_ACTION_LABELS) as HotkeyAction[]).map((action) => {
+        const binding = hotkeys.bindings.find((b) => b.action === action);
+        const state = status.find((s) => s.action === action);
+        return (
+          <div key={action} className="flex items-center gap-2">
+            <div className="w-40 text-sm text-slate-300">{HOTKEY_ACTION_LABELS[action]}</div>
+            <input
+              type="text"
+              value={drafts[action] ?? binding?.keys ?? ""}
+              placeholder="未绑定"
+              disabled={!hotkeys.enabled}
+              onChange={(e) => setDrafts({ ...drafts, [action]: e.target.value })}
+              onBlur={() => commit(action)}
+              className="flex-1 px-3 py-2 text-sm rounded-lg bg-surface border border-border text-white focus:outline-none focus:border-brand-600"
+            />
+            {hotkeys.enabled && state && !state.registered && (
+              <span className="text-xs text-red-400">{state.error ?? "注册失败"}</span>
+            )}
+          </div>
+        );
+      })}
+      {error && <div className="text-xs text-red-400">{error}</div>}
+      <div className="text-xs text-slate-500">
+        格式如 Ctrl+Shift+F9；字母、数字键需搭配修饰键，清空则取消绑定
+      </div>
+    </div>
+  );
+}
+
+function DownloadMirrors({
+  mirrors,
+  onChange,
+}: {
+  mirrors: MirrorSettings;
+  onChange: (patch: Partial<MirrorSettings>) => void;
+}) {
+  const [statuses, setStatuses] = useState<MirrorStatus[]>([]);
+  const [probing, setProbing] = useState(false);
+  const [draft, setDraft] = useState({ name: "", server_url: "", github_proxy: "" });
+
+  useEffect(() => {
+    listMirrors(false).then(setStatuses).catch(() => {});
+  }, [mirrors]);
+
+  const probe = async () => {
+    setProbing(true);
+    try {
+      setStatuses(await listMirrors(true));
+    } finally {
+      setProbing(false);
+    }
+  };
+
+  const add = () => {
+    if (!draft.name.trim() || (!draft.server_url.trim() && !draft.github_proxy.trim())) return;
+    const mirror: Mirror = {
+      id: `custom-${Date.now()}`,
+      name: draft.name.trim(),
+      server_url: draft.server_url.trim() || null,
+      github_proxy: draft.github_proxy.trim() || null,
+    };
+    onChange({ list: [...mirrors.list, mirror] });
+    setDraft({ name: "", server_url: "", github_proxy: "" });
+  };
+
+  const remove = (id: string) =>
+    onChange({
+      list: mirrors.list.filter((m) => m.id !== id),
+      preferred: mirrors.preferred === id ? null : mirrors.preferred,
+    });
+
+  const latency = (ms: number | null, selected: boolean) =>
+    ms === null ? "—" : `${ms} ms${selected ? " ✓" : ""}`;
+
+  const inputClass =
+    "flex-1 px-3 py-2 text-sm rounded-lg bg-surface border border-border text-white focus:outline-none focus:border-brand-600";
+
+  return (
+    <div className="space-y-2">
+      <div className="grid grid-cols-[1fr_auto_auto_auto] gap-x-4 gap-y-1 text-xs items-center">
+        <div className="text-slate-500">源</div>
+        <div className="text-slate-500">服务器</div>
+        <div className="text-slate-500">GitHub</div>
+        <div />
+        {statuses.map((s) => (
+          <div key={s.id} className="contents">
+            <label className="flex items-center gap-2 text-slate-300">
+              {!mirrors.auto_select && (
+                <input
+                  type="radio"
+                  checked={(mirrors.preferred ?? "official") === s.id}
+                  onChange={() => onChange({ preferred: s.id })}
+                />
+              )}
+              {s.name}
+            </label>
+            <div className="text-slate-400">{latency(s.server_latency_ms, s.server_selected)}</div>
+            <div className="text-slate-400">{latency(s.github_latency_ms, s.github_selected)}</div>
+            {s.id === "official" ? (
+              <div />
+            ) : (
+              <button onClick={() => remove(s.id)} className="text-red-400 hover:text-red-300">
+                删除
+              </button>
+            )}
+          </div>
+        ))}
+      </div>
+      <div className="flex gap-2">
+        <input
+          type="text"
+          value={draft.name}
+          placeholder="名称"
+          onChange={(e) => setDraft({ ...draft, name: e.target.value })}
+          className={inputClass}
+        />
+        <input
+          type="text"
+          value={draft.server_url}
+          placeholder="服务器镜像 https://…"
+          onChange={(e) => setDraft({ ...draft, server_url: e.target.value })}
+          className={inputClass}
+        />
+        <input
+          type="text"
+          value={draft.github_proxy}
+          placeholder="GitHub 代理前缀 https://…"
+          onChange={(e) => setDraft({ ...draft, github_proxy: e.target.value })}
+          className={inputClass}
+        />
+      </div>
+      <div className="flex gap-2">
+        <ActionButton label="添加镜像" onClick={add} />
+        <ActionButton label={probing ? "测速中…" : "测速"} onClick={probe} />
+      </div>
+    </div>
+  );
+}
+
+function PresentmonDownload() {
+  const [progress, setProgress] = useState<TaskProgress | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [result, setResult] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const download = async () => {
+    setBusy(true);
+    setError(null);
+    try {
+      setResult(await downloadPresentmon(setProgress));
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+      setProgress(null);
+    }
+  };
+
+  return (
+    <div className="space-y-1">
+      <ActionButton label={busy ? "下载中…" : "下载 PresentMon"} onClick={download} />
+      {progress && (
+        <div className="flex items-center gap-2 text-xs text-slate-500">
+          {progress.total > 0 && `${Math.round((progress.done / progress.total) * 100)}%`}
+          <button
+            onClick={() => cancelTask(progress.task_id).catch(() => {})}
+            className="text-slate-400 hover:text-white"
+          >
+            取消
+          </button>
+        </div>
+      )}
+      {result && <div className="text-xs text-slate-500">已保存到 {result}</div>}
+      {error && <div className="text-xs text-red-400">{error}</div>}
+    </div>
+  );
+}
+
+function OfflineNotice() {
+  return <div className="text-xs text-amber-400">离线模式已开启，以下联网功能暂不可用</div>;
+}
+
+function ActionButton({ label, onClick }: { label: string; onClick: () => void }) {
+  return (
+    <button
+      onClick={onClick}
+      className="px-3 py-2 text-xs rounded-lg bg-surface-card border border-border text-slate-400 hover:text-white"
+    >
+      {label}
+    </button>
+  );
+}
+
+function UnitSelect({
+  label, value, options, onChange,
+}: {
+  label: string; value: string; options: [string, string][]; onChange: (v: string) => void;
+}) {
+  return (
+    <div>
+      <div className="text-xs text-slate-500 mb-1">{label}</div>
+      <select
+        value={value}
+        onChange={(e) => onChange(e.target.value)}
+        className="w-full px-3 py-2 text-sm rounded-lg bg-surface border border-border text-white focus:outline-none"
+      >
+        {options.map(([v, text]) => (
+          <option key={v} value={v}>
+            {text}
+          </option>
+        ))}
+      </select>
+    </div>
+  );
+}
+
 function Toggle({
   label, description, checked, onChange,
 }: {